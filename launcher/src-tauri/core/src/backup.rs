@@ -0,0 +1,177 @@
+//! Backups of files the launcher overwrites, with retention-based pruning
+//!
+//! Each backup is stored as `backups/<kind>/<id>.bak` next to an `<id>.json`
+//! record of where it came from.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::cmp::Reverse;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::LauncherError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum BackupKind {
+    /// launcher-config.json
+    LauncherConfig,
+    /// MCP client configs such as ~/.claude.json
+    ClientConfig,
+    /// Unity Packages/manifest.json
+    Manifest,
+}
+
+impl BackupKind {
+    pub const ALL: [BackupKind; 3] = [
+        BackupKind::LauncherConfig,
+        BackupKind::ClientConfig,
+        BackupKind::Manifest,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BackupKind::LauncherConfig => "launcher-config",
+            BackupKind::ClientConfig => "client-config",
+            BackupKind::Manifest => "manifest",
+        }
+    }
+
+    pub fn parse(kind: &str) -> Result<Self, LauncherError> {
+        Self::ALL
+            .into_iter()
+            .find(|k| k.as_str() == kind)
+            .ok_or_else(|| LauncherError::invalid(format!("Unknown backup kind: {}", kind)))
+    }
+}
+
+/// How many backups to keep, per kind
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct BackupRetention {
+    #[serde(default = "default_max_per_kind")]
+    pub max_per_kind: usize,
+    /// Backups older than this are pruned; 0 keeps them regardless of age
+    #[serde(default = "default_max_age_days")]
+    pub max_age_days: u32,
+}
+
+fn default_max_per_kind() -> usize {
+    20
+}
+
+fn default_max_age_days() -> u32 {
+    30
+}
+
+impl Default for BackupRetention {
+    fn default() -> Self {
+        BackupRetention {
+            max_per_kind: default_max_per_kind(),
+            max_age_days: default_max_age_days(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct BackupEntry {
+    pub id: String,
+    pub kind: BackupKind,
+    pub original_path: String,
+    /// Unix ms
+    pub created_at: i64,
+    pub size: u64,
+}
+
+fn backups_dir() -> PathBuf {
+    crate::config::launcher_dir().join("backups")
+}
+
+/// Where backups of a kind are stored
+pub fn kind_dir(kind: BackupKind) -> PathBuf {
+    backups_dir().join(kind.as_str())
+}
+
+/// Copy `path` into the backup store, then prune. Returns `None` if there was
+/// nothing to back up.
+pub fn create(
+    kind: BackupKind,
+    path: &Path,
+    retention: &BackupRetention,
+) -> Result<Option<BackupEntry>, LauncherError> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+
+    let dir = kind_dir(kind);
+    fs::create_dir_all(&dir)
+        .map_err(|e| LauncherError::io("Failed to create backup directory", &dir, e))?;
+
+    let created_at = crate::bridge::now_ms();
+    let id = format!(
+        "{}-{}",
+        created_at,
+        &uuid::Uuid::new_v4().simple().to_string()[..8]
+    );
+    let size = fs::copy(path, dir.join(format!("{}.bak", id)))
+        .map_err(|e| LauncherError::io("Failed to back up", path, e))?;
+
+    let entry = BackupEntry {
+        id: id.clone(),
+        kind,
+        original_path: path.to_string_lossy().to_string(),
+        created_at,
+        size,
+    };
+    let record = serde_json::to_string_pretty(&entry).map_err(|e| {
+        LauncherError::internal(format!("Failed to serialize backup record: {}", e))
+    })?;
+    let record_path = dir.join(format!("{}.json", id));
+    fs::write(&record_path, record)
+        .map_err(|e| LauncherError::io("Failed to write backup record", &record_path, e))?;
+
+    prune(kind, retention);
+    Ok(Some(entry))
+}
+
+/// Backups of a kind, newest first
+pub fn entries(kind: BackupKind) -> Vec<BackupEntry> {
+    let Ok(read_dir) = fs::read_dir(kind_dir(kind)) else {
+        return Vec::new();
+    };
+
+    let mut entries: Vec<BackupEntry> = read_dir
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|p| serde_json::from_str(&fs::read_to_string(p).ok()?).ok())
+        .collect();
+    entries.sort_by_key(|e| Reverse(e.created_at));
+    entries
+}
+
+fn remove(entry: &BackupEntry) {
+    let dir = kind_dir(entry.kind);
+    fs::remove_file(dir.join(format!("{}.bak", entry.id))).ok();
+    fs::remove_file(dir.join(format!("{}.json", entry.id))).ok();
+}
+
+/// Apply the retention policy to one kind
+pub fn prune(kind: BackupKind, retention: &BackupRetention) {
+    let cutoff = (retention.max_age_days > 0)
+        .then(|| crate::bridge::now_ms() - retention.max_age_days as i64 * 24 * 60 * 60 * 1000);
+
+    for (index, entry) in entries(kind).iter().enumerate() {
+        let too_many = index >= retention.max_per_kind;
+        let too_old = cutoff.is_some_and(|cutoff| entry.created_at < cutoff);
+        if too_many || too_old {
+            remove(entry);
+        }
+    }
+}
+
+/// Apply the retention policy to every kind
+pub fn prune_all(retention: &BackupRetention) {
+    for kind in BackupKind::ALL {
+        prune(kind, retention);
+    }
+}
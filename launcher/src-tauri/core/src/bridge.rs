@@ -0,0 +1,84 @@
+//! File-based bridge to the Unity extension.
+//!
+//! The extension polls `Assets/_MCP/commands` for `*.json` command files and
+//! exports its state to `Assets/_MCP/state`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::LauncherError;
+use crate::faults::{self, Fault};
+
+/// How old the editor heartbeat may be before the editor counts as disconnected
+const HEARTBEAT_TIMEOUT_MS: i64 = 15_000;
+
+pub fn mcp_dir(unity_project_path: &Path) -> PathBuf {
+    unity_project_path.join("Assets").join("_MCP")
+}
+
+pub fn state_dir(unity_project_path: &Path) -> PathBuf {
+    mcp_dir(unity_project_path).join("state")
+}
+
+pub fn commands_dir(unity_project_path: &Path) -> PathBuf {
+    mcp_dir(unity_project_path).join("commands")
+}
+
+/// Milliseconds since the Unix epoch, matching the bridge's timestamps
+pub fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+/// Read one of the state files exported by the bridge
+pub fn read_state(unity_project_path: &Path, name: &str) -> Option<serde_json::Value> {
+    // A hung bridge keeps its heartbeat but never writes results
+    if name != "editor-state.json" && faults::is_active(Fault::BridgeTimeout) {
+        return None;
+    }
+    let content = fs::read_to_string(state_dir(unity_project_path).join(name)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Timestamp of the last editor-state export
+pub fn editor_heartbeat_ms(unity_project_path: &Path) -> Option<i64> {
+    read_state(unity_project_path, "editor-state.json")?
+        .get("timestamp")?
+        .as_i64()
+}
+
+/// Whether a Unity editor with the bridge is currently running on the project
+pub fn is_editor_connected(unity_project_path: &Path) -> bool {
+    editor_heartbeat_ms(unity_project_path)
+        .map(|ts| now_ms() - ts < HEARTBEAT_TIMEOUT_MS)
+        .unwrap_or(false)
+}
+
+/// Queue a command for the bridge. The file is written under a temporary name
+/// first so the bridge never picks up a half-written command.
+pub fn send_command(
+    unity_project_path: &Path,
+    command: &serde_json::Value,
+) -> Result<PathBuf, LauncherError> {
+    let dir = commands_dir(unity_project_path);
+    crate::write_guard::check_write(&dir)?;
+    fs::create_dir_all(&dir)
+        .map_err(|e| LauncherError::io("Failed to create MCP commands directory", &dir, e))?;
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let tmp = dir.join(format!("{}.tmp", id));
+    let dest = dir.join(format!("{}.json", id));
+
+    let content = serde_json::to_string_pretty(command).map_err(|e| {
+        LauncherError::internal(format!("Failed to serialize bridge command: {}", e))
+    })?;
+    fs::write(&tmp, content)
+        .map_err(|e| LauncherError::io("Failed to write bridge command", &tmp, e))?;
+    fs::rename(&tmp, &dest)
+        .map_err(|e| LauncherError::io("Failed to queue bridge command", &dest, e))?;
+
+    Ok(dest)
+}
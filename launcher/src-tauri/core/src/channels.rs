@@ -0,0 +1,188 @@
+//! Scene channels: building one from a scene file and checking scene paths
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use crate::config::ProjectChannel;
+use crate::error::LauncherError;
+use crate::sanitize;
+
+/// MCP server variable listing the folders its file tools are confined to,
+/// separated like `PATH`
+pub const ALLOWED_ROOTS_VAR: &str = "BANTER_ALLOWED_ROOTS";
+
+/// MCP server variable naming the folder for its caches and generated files
+pub const DATA_DIR_VAR: &str = "BANTER_MCP_DATA_DIR";
+
+/// MCP server variable naming the client a workspace-mode server serves
+pub const INSTANCE_VAR: &str = "BANTER_MCP_INSTANCE";
+
+/// MCP server variable naming the lock file every server of a workspace-mode
+/// channel takes before a tool writes to the project
+pub const WRITE_LOCK_VAR: &str = "BANTER_MCP_WRITE_LOCK";
+
+/// Clients a channel can be shared with besides Claude Code, and the config
+/// file each one reads, relative to the project
+pub const WORKSPACE_CLIENTS: &[(&str, &str)] = &[("cursor", ".cursor/mcp.json")];
+
+/// Instance name of the Claude Code server in workspace mode
+pub const CLAUDE_INSTANCE: &str = "claude";
+
+/// Data folder when the channel doesn't set one. Unity doesn't import
+/// `Library`, and Unity .gitignores already leave it out.
+pub const DEFAULT_DATA_DIR: &str = "Library/BanterMCP";
+
+/// A channel folder setting: absolute, or relative to the project
+fn in_project(channel: &ProjectChannel, value: &str) -> PathBuf {
+    let path = Path::new(value);
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        Path::new(&channel.unity_project_path).join(path)
+    }
+}
+
+/// Folder the channel's MCP server starts in. It has to exist already; an
+/// unset `server_cwd` means the project.
+pub fn server_cwd(channel: &ProjectChannel) -> Result<PathBuf, LauncherError> {
+    let cwd = match &channel.server_cwd {
+        Some(cwd) => in_project(channel, cwd),
+        None => PathBuf::from(&channel.unity_project_path),
+    };
+    if !cwd.is_dir() {
+        return Err(LauncherError::not_found(format!(
+            "MCP server working directory not found: {}",
+            cwd.display()
+        ))
+        .with_path(&cwd)
+        .with_hint("Create the folder, or clear the channel's server working directory"));
+    }
+    Ok(cwd)
+}
+
+/// Folder the channel's MCP server keeps its caches and generated files in
+pub fn server_data_dir(channel: &ProjectChannel) -> PathBuf {
+    in_project(
+        channel,
+        channel
+            .server_data_dir
+            .as_deref()
+            .unwrap_or(DEFAULT_DATA_DIR),
+    )
+}
+
+/// Whether the channel's project is shared between several clients
+pub fn is_workspace(channel: &ProjectChannel) -> bool {
+    !channel.workspace_clients.is_empty()
+}
+
+/// Data folder of one client's server in workspace mode, so the servers'
+/// caches don't overwrite each other
+pub fn instance_data_dir(channel: &ProjectChannel, instance: &str) -> PathBuf {
+    server_data_dir(channel).join("instances").join(instance)
+}
+
+/// Lock file shared by all servers of a workspace-mode channel
+pub fn write_lock_path(channel: &ProjectChannel) -> PathBuf {
+    server_data_dir(channel).join("write.lock")
+}
+
+/// Config file a workspace client reads for the channel's project
+pub fn workspace_client_config(channel: &ProjectChannel, client: &str) -> Option<PathBuf> {
+    WORKSPACE_CLIENTS
+        .iter()
+        .find(|(name, _)| *name == client)
+        .map(|(_, file)| Path::new(&channel.unity_project_path).join(file))
+}
+
+/// Folders the channel's MCP server may touch: the project, then the
+/// channel's extra roots
+pub fn allowed_roots(channel: &ProjectChannel) -> Vec<PathBuf> {
+    let mut roots = vec![PathBuf::from(&channel.unity_project_path)];
+    for root in &channel.extra_allowed_roots {
+        let root = PathBuf::from(root);
+        if !roots.contains(&root) {
+            roots.push(root);
+        }
+    }
+    roots
+}
+
+/// `allowed_roots` as the value of `ALLOWED_ROOTS_VAR`
+pub fn allowed_roots_value(channel: &ProjectChannel) -> Result<OsString, LauncherError> {
+    std::env::join_paths(allowed_roots(channel)).map_err(|e| {
+        LauncherError::invalid(format!("Allowed roots can't be listed together: {}", e))
+            .with_hint("Remove the path separator from the folder name")
+    })
+}
+
+/// Unity project containing a scene: the parent of its nearest `Assets` folder
+pub fn project_root_for_scene(scene: &Path) -> Option<PathBuf> {
+    scene
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.file_name().and_then(|n| n.to_str()) == Some("Assets"))
+        .and_then(|assets| assets.parent())
+        .map(|p| p.to_path_buf())
+}
+
+/// Build a new scene channel; the caller adds it to the config
+pub fn add_channel(name: String, scene_path: String) -> Result<ProjectChannel, LauncherError> {
+    let name = sanitize::channel_name(&name)?;
+    let scene_file = sanitize::path("Scene path", &scene_path)?;
+
+    if !scene_file.exists() {
+        return Err(
+            LauncherError::not_found(format!("Scene file does not exist: {}", scene_path))
+                .with_path(&scene_file),
+        );
+    }
+
+    // Validate it's a .unity file
+    if scene_file.extension().map(|e| e.to_str().unwrap_or("")) != Some("unity") {
+        return Err(
+            LauncherError::invalid("Not a valid Unity scene file (must be .unity)")
+                .with_path(&scene_file),
+        );
+    }
+
+    let unity_project_path = project_root_for_scene(&scene_file)
+        .ok_or_else(|| {
+            LauncherError::invalid("Could not find Unity project root (no Assets folder in path)")
+                .with_path(&scene_file)
+                .with_hint("Pick a scene inside a Unity project's Assets folder")
+        })?
+        .to_string_lossy()
+        .to_string();
+
+    let channel = ProjectChannel {
+        id: uuid::Uuid::new_v4().to_string(),
+        name,
+        unity_project_path,
+        scene_path: Some(scene_path),
+        enabled: true,
+        ..Default::default()
+    };
+
+    Ok(channel)
+}
+
+/// Validate a Unity scene file path
+pub fn validate_unity_scene(path: String) -> Result<bool, LauncherError> {
+    let Ok(scene_path) = sanitize::path("Scene path", &path) else {
+        return Ok(false);
+    };
+
+    if !scene_path.exists() {
+        return Ok(false);
+    }
+
+    // Check if it's a .unity file
+    if scene_path.extension().map(|e| e.to_str().unwrap_or("")) != Some("unity") {
+        return Ok(false);
+    }
+
+    // Check if it's inside an Assets folder (valid Unity project structure)
+    let path_str = path.replace("\\", "/");
+    Ok(path_str.contains("/Assets/"))
+}
@@ -0,0 +1,195 @@
+//! The `banter` entry in Claude Code's config (`~/.claude.json`)
+//!
+//! Every other key in the file is left as it was. The entry's environment is
+//! built by the app (it includes the channel's secrets file), so updates take
+//! it as a parameter. The optional `banter-launcher` entry next to it runs the
+//! launcher's own MCP server, which serves launcher state.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::backup::{self, BackupRetention};
+use crate::error::LauncherError;
+use crate::faults::{self, Fault};
+use crate::plan::FilePlan;
+
+/// Get Claude Code config path
+pub fn get_claude_config_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".claude.json")
+}
+
+/// Read current Claude Code MCP configuration
+pub fn get_claude_mcp_config() -> Result<serde_json::Value, LauncherError> {
+    get_claude_mcp_config_from(&get_claude_config_path())
+}
+
+/// Read a Claude Code config file; a missing file reads as `{}`
+pub fn get_claude_mcp_config_from(config_path: &Path) -> Result<serde_json::Value, LauncherError> {
+    if config_path.exists() {
+        let content = fs::read_to_string(config_path)
+            .map_err(|e| LauncherError::io("Failed to read Claude config", config_path, e))?;
+        serde_json::from_str(&content).map_err(|e| {
+            LauncherError::parse(format!("Failed to parse Claude config: {}", e))
+                .with_path(config_path)
+        })
+    } else {
+        Ok(serde_json::json!({}))
+    }
+}
+
+/// Project the `banter` entry currently points Claude Code at, if any
+pub fn configured_project(claude_config: &serde_json::Value) -> Option<String> {
+    claude_config
+        .pointer("/mcpServers/banter/env/UNITY_PROJECT_PATH")
+        .and_then(|p| p.as_str())
+        .map(|p| p.to_string())
+}
+
+fn check_writable(plan: &FilePlan, config_path: &Path) -> Result<(), LauncherError> {
+    if plan.dry_run {
+        return Ok(());
+    }
+    faults::check(Fault::ClaudeConfigUnwritable)
+        .map_err(|e| LauncherError::io("Failed to write", config_path, e))
+}
+
+/// Point the `banter` entry at an MCP server started in `cwd` with `env`, as
+/// part of a plan. An unreadable config is replaced rather than refused, as
+/// before.
+pub fn plan_claude_mcp_update(
+    plan: &mut FilePlan,
+    config_path: &Path,
+    mcp_server_path: &str,
+    cwd: &str,
+    env: serde_json::Value,
+    retention: &BackupRetention,
+) -> Result<(), LauncherError> {
+    plan_claude_mcp_update_with(
+        plan,
+        config_path,
+        "node",
+        &[mcp_server_path.to_string()],
+        cwd,
+        env,
+        retention,
+    )
+}
+
+/// `plan_claude_mcp_update` with Claude Code starting `command` instead of
+/// `node` directly, e.g. the launcher proxying the server
+pub fn plan_claude_mcp_update_with(
+    plan: &mut FilePlan,
+    config_path: &Path,
+    command: &str,
+    args: &[String],
+    cwd: &str,
+    env: serde_json::Value,
+    retention: &BackupRetention,
+) -> Result<(), LauncherError> {
+    let mut config: serde_json::Value = if config_path.exists() {
+        let content = fs::read_to_string(config_path)
+            .map_err(|e| LauncherError::io("Failed to read Claude config", config_path, e))?;
+        serde_json::from_str(&content).unwrap_or(serde_json::json!({}))
+    } else {
+        serde_json::json!({})
+    };
+
+    if config.get("mcpServers").is_none() {
+        config["mcpServers"] = serde_json::json!({});
+    }
+
+    config["mcpServers"]["banter"] = serde_json::json!({
+        "command": command,
+        "args": args,
+        "cwd": cwd,
+        "env": env
+    });
+
+    let content = serde_json::to_string_pretty(&config).map_err(|e| {
+        LauncherError::internal(format!("Failed to serialize Claude config: {}", e))
+    })?;
+    check_writable(plan, config_path)?;
+
+    plan.backup(backup::BackupKind::ClientConfig, config_path, retention)?;
+    plan.write(config_path, content)
+}
+
+/// Remove the `banter` entry from Claude config as part of a plan
+pub fn plan_claude_mcp_removal(
+    plan: &mut FilePlan,
+    retention: &BackupRetention,
+) -> Result<(), LauncherError> {
+    plan_claude_mcp_removal_from(plan, &get_claude_config_path(), retention)
+}
+
+/// `plan_claude_mcp_removal` for a config file somewhere else
+pub fn plan_claude_mcp_removal_from(
+    plan: &mut FilePlan,
+    config_path: &Path,
+    retention: &BackupRetention,
+) -> Result<(), LauncherError> {
+    if !config_path.exists() {
+        return Ok(());
+    }
+
+    let mut config = get_claude_mcp_config_from(config_path)?;
+
+    if let Some(servers) = config.get_mut("mcpServers") {
+        if let Some(obj) = servers.as_object_mut() {
+            obj.remove("banter");
+        }
+    }
+
+    let content = serde_json::to_string_pretty(&config).map_err(|e| {
+        LauncherError::internal(format!("Failed to serialize Claude config: {}", e))
+    })?;
+    check_writable(plan, config_path)?;
+
+    plan.backup(backup::BackupKind::ClientConfig, config_path, retention)?;
+    plan.write(config_path, content)
+}
+
+/// Entry of the launcher's own MCP server (see the app's `status_server`)
+pub const LAUNCHER_ENTRY: &str = "banter-launcher";
+
+/// Point the `banter-launcher` entry at `command` with `args`, or remove it
+/// with `None`, as part of a plan. Removing an entry that isn't there writes
+/// nothing.
+pub fn plan_launcher_entry(
+    plan: &mut FilePlan,
+    config_path: &Path,
+    command: Option<(&str, &[String])>,
+    retention: &BackupRetention,
+) -> Result<(), LauncherError> {
+    let mut config = get_claude_mcp_config_from(config_path)?;
+    match command {
+        Some((command, args)) => {
+            if config.get("mcpServers").is_none() {
+                config["mcpServers"] = serde_json::json!({});
+            }
+            config["mcpServers"][LAUNCHER_ENTRY] = serde_json::json!({
+                "command": command,
+                "args": args,
+            });
+        }
+        None => {
+            let removed = config
+                .get_mut("mcpServers")
+                .and_then(|s| s.as_object_mut())
+                .and_then(|s| s.remove(LAUNCHER_ENTRY));
+            if removed.is_none() {
+                return Ok(());
+            }
+        }
+    }
+
+    let content = serde_json::to_string_pretty(&config).map_err(|e| {
+        LauncherError::internal(format!("Failed to serialize Claude config: {}", e))
+    })?;
+    check_writable(plan, config_path)?;
+
+    plan.backup(backup::BackupKind::ClientConfig, config_path, retention)?;
+    plan.write(config_path, content)
+}
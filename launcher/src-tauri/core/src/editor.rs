@@ -0,0 +1,130 @@
+//! Whether the Unity editor on a project is up, and if not, why
+//!
+//! A running editor keeps `Library/EditorInstance.json` with its process id
+//! and deletes it when it quits. An instance file whose process is gone
+//! therefore means the editor died; a live process whose bridge heartbeat
+//! has stopped for `HANG_AFTER_MS` is frozen (or stuck in a long import).
+//! Unity's crash handler also leaves recognizable lines in Editor.log,
+//! which give the cause. The app probes the process and reads the log; the
+//! verdict is `classify`.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs;
+use std::path::Path;
+
+/// Bridge silence with the editor still running before it counts as frozen
+pub const HANG_AFTER_MS: i64 = 90_000;
+
+/// Editor.log lines written when Unity crashes
+const CRASH_PATTERNS: &[&str] = &[
+    "Crash!!!",
+    "A crash has been intercepted by the crash handler",
+    "Received signal SIGSEGV",
+    "Received signal SIGABRT",
+    "Received signal SIGBUS",
+    "Native Crash Reporting",
+    "Unity has stopped working",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum EditorCondition {
+    /// The bridge heartbeat is fresh
+    Connected,
+    /// The editor runs but the bridge hasn't answered yet: opening, compiling
+    /// or without the extension
+    Starting,
+    /// The editor runs but the bridge went quiet `HANG_AFTER_MS` ago
+    NotResponding,
+    /// The editor exited without closing the project
+    Crashed,
+    /// No editor has the project open
+    Closed,
+}
+
+impl EditorCondition {
+    /// Whether the editor went down in a way the user should hear about
+    pub fn is_failure(self) -> bool {
+        matches!(
+            self,
+            EditorCondition::NotResponding | EditorCondition::Crashed
+        )
+    }
+}
+
+/// What is known about an editor when classifying it
+#[derive(Debug, Clone, Default)]
+pub struct EditorProbe {
+    /// The bridge heartbeat is fresh
+    pub connected: bool,
+    /// Age of the last heartbeat, if there ever was one
+    pub heartbeat_age_ms: Option<i64>,
+    /// Process id from the instance file, if it is there
+    pub pid: Option<u32>,
+    /// Whether that process still runs
+    pub alive: bool,
+    /// Crash line from the project's Editor.log, if it has one
+    pub crash_line: Option<String>,
+}
+
+/// Process id of the editor that has the project open, from
+/// `Library/EditorInstance.json`
+pub fn instance_pid(unity_project_path: &Path) -> Option<u32> {
+    let path = unity_project_path
+        .join("Library")
+        .join("EditorInstance.json");
+    let instance: serde_json::Value = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+    instance.get("process_id")?.as_u64()?.try_into().ok()
+}
+
+/// The last crash line in an Editor.log excerpt
+pub fn crash_line(log: &str) -> Option<String> {
+    log.lines()
+        .rev()
+        .find(|line| CRASH_PATTERNS.iter().any(|p| line.contains(p)))
+        .map(|line| line.trim().to_string())
+}
+
+/// Whether an Editor.log starting with `head` belongs to an editor on
+/// `unity_project_path`, going by the `-projectPath` Unity logs first
+pub fn log_is_for(head: &str, unity_project_path: &Path) -> bool {
+    let key = |s: &str| s.replace('\\', "/").trim_end_matches('/').to_lowercase();
+    let project = key(&unity_project_path.to_string_lossy());
+    !project.is_empty() && key(head).contains(&project)
+}
+
+/// The editor's condition, with the cause when it is a failure
+pub fn classify(probe: &EditorProbe) -> (EditorCondition, Option<String>) {
+    if probe.connected {
+        return (EditorCondition::Connected, None);
+    }
+    match probe.pid {
+        Some(pid) if probe.alive => match probe.heartbeat_age_ms {
+            Some(age) if age >= HANG_AFTER_MS => (
+                EditorCondition::NotResponding,
+                Some(format!(
+                    "Unity (process {}) is running but its bridge has been silent for {}s; \
+                     it is frozen or busy with a long import",
+                    pid,
+                    age / 1000
+                )),
+            ),
+            _ => (EditorCondition::Starting, None),
+        },
+        Some(pid) => (
+            EditorCondition::Crashed,
+            Some(match &probe.crash_line {
+                Some(line) => format!("Unity crashed: {}", line),
+                None => format!("Unity (process {}) exited without closing the project", pid),
+            }),
+        ),
+        None => match &probe.crash_line {
+            Some(line) => (
+                EditorCondition::Crashed,
+                Some(format!("Unity crashed: {}", line)),
+            ),
+            None => (EditorCondition::Closed, None),
+        },
+    }
+}
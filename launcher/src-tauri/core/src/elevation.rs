@@ -0,0 +1,185 @@
+//! Detecting and requesting admin rights for single steps
+//!
+//! The launcher never runs elevated as a whole. When a write target isn't
+//! writable by the current user, the caller either reports that clearly or,
+//! if the user agreed, runs just that step through the platform's elevation
+//! prompt (UAC, osascript, pkexec).
+
+use serde::Serialize;
+use specta::Type;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+use crate::error::{ErrorKind, LauncherError};
+use crate::i18n;
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ElevationStatus {
+    pub path: String,
+    /// Current user can write there without elevation
+    pub writable: bool,
+    /// Launcher is already running as admin/root
+    pub elevated: bool,
+    /// An elevation prompt is available on this platform
+    pub can_elevate: bool,
+}
+
+/// Closest ancestor of `path` that exists
+pub(crate) fn existing_ancestor(path: &Path) -> Option<&Path> {
+    path.ancestors().find(|p| p.exists())
+}
+
+/// Whether the current user can create files at `path` (or where it would be created)
+pub fn is_writable(path: &Path) -> bool {
+    let Some(dir) = existing_ancestor(path) else {
+        return false;
+    };
+    let dir = if dir.is_dir() {
+        dir.to_path_buf()
+    } else {
+        // Existing file: it's replacing that needs to work
+        return fs::OpenOptions::new().append(true).open(dir).is_ok();
+    };
+
+    let probe = dir.join(format!(".bantworks-write-test-{}", uuid::Uuid::new_v4()));
+    match fs::File::create(&probe) {
+        Ok(_) => {
+            fs::remove_file(&probe).ok();
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+pub fn is_elevated() -> bool {
+    if cfg!(target_os = "windows") {
+        // `net session` only succeeds from an elevated prompt
+        Command::new("net")
+            .arg("session")
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    } else {
+        Command::new("id")
+            .arg("-u")
+            .output()
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim() == "0")
+            .unwrap_or(false)
+    }
+}
+
+fn can_elevate() -> bool {
+    if cfg!(target_os = "windows") || cfg!(target_os = "macos") {
+        true
+    } else {
+        Command::new("pkexec").arg("--version").output().is_ok()
+    }
+}
+
+pub fn status(path: &Path) -> ElevationStatus {
+    ElevationStatus {
+        path: path.to_string_lossy().to_string(),
+        writable: is_writable(path),
+        elevated: is_elevated(),
+        can_elevate: can_elevate(),
+    }
+}
+
+/// Error for a step that needs admin rights the user hasn't granted
+pub fn required_error(path: &Path) -> LauncherError {
+    LauncherError::localized(
+        ErrorKind::PermissionDenied,
+        "error.elevation_required",
+        i18n::params(&[("path", path.display().to_string())]),
+    )
+    .with_path(path)
+    .with_localized_hint("hint.elevation_required")
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, b)| n | (*b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+fn powershell_quote(path: &Path) -> String {
+    format!("'{}'", path.to_string_lossy().replace('\'', "''"))
+}
+
+fn applescript_shell_quote(path: &Path) -> String {
+    // Shell single quotes, then escaped for an AppleScript string literal
+    let shell = format!("'{}'", path.to_string_lossy().replace('\'', "'\\''"));
+    shell.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Copy a file into a protected location, prompting for admin rights
+pub fn copy_elevated(source: &Path, dest: &Path) -> Result<(), LauncherError> {
+    let dest_dir = dest.parent().map(Path::to_path_buf).unwrap_or_default();
+
+    let output = if cfg!(target_os = "windows") {
+        let script = format!(
+            "New-Item -ItemType Directory -Force -Path {} | Out-Null; Copy-Item -Force -LiteralPath {} -Destination {}",
+            powershell_quote(&dest_dir),
+            powershell_quote(source),
+            powershell_quote(dest)
+        );
+        let utf16: Vec<u8> = script
+            .encode_utf16()
+            .flat_map(|u| u.to_le_bytes())
+            .collect();
+        let launcher = format!(
+            "$p = Start-Process powershell -Verb RunAs -Wait -PassThru -WindowStyle Hidden -ArgumentList '-NoProfile','-EncodedCommand','{}'; exit $p.ExitCode",
+            base64(&utf16)
+        );
+        Command::new("powershell")
+            .args(["-NoProfile", "-Command", &launcher])
+            .output()
+    } else if cfg!(target_os = "macos") {
+        let script = format!(
+            "do shell script \"mkdir -p {} && cp -f {} {}\" with administrator privileges",
+            applescript_shell_quote(&dest_dir),
+            applescript_shell_quote(source),
+            applescript_shell_quote(dest)
+        );
+        Command::new("osascript").args(["-e", &script]).output()
+    } else {
+        Command::new("pkexec")
+            .args(["sh", "-c", "mkdir -p \"$1\" && cp -f \"$2\" \"$3\"", "sh"])
+            .arg(&dest_dir)
+            .arg(source)
+            .arg(dest)
+            .output()
+    };
+
+    let output = output.map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => required_error(dest)
+            .with_hint("No elevation helper is available - move the project to a folder you own"),
+        _ => LauncherError::internal(format!("Failed to request elevation: {}", e)),
+    })?;
+    if !output.status.success() {
+        return Err(LauncherError::new(
+            ErrorKind::Cancelled,
+            format!(
+                "Elevated copy to {} was cancelled or failed",
+                dest.display()
+            ),
+        )
+        .with_path(dest));
+    }
+    Ok(())
+}
@@ -0,0 +1,181 @@
+//! Error type returned by every command
+//!
+//! Serialized to the frontend as `{ kind, message, hint, path }` so the UI can
+//! branch on `kind` and offer a fix instead of showing a raw string. Errors
+//! built from the message catalogue also carry `message_id`, `params` and
+//! `hint_id`; see `i18n`.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+use crate::i18n::{self, Params};
+use crate::sanitize::InputError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// Channel, file, backup, editor or client that doesn't exist
+    NotFound,
+    /// Rejected argument from the frontend
+    InvalidInput,
+    /// Malformed config or state file
+    Parse,
+    /// Filesystem failure other than missing files or permissions
+    Io,
+    /// OS permissions, the write allowlist or missing admin rights
+    PermissionDenied,
+    /// HTTP or socket failure
+    Network,
+    /// Not logged in or credentials rejected
+    Auth,
+    /// Unity editor or build failure
+    Unity,
+    /// Unity bridge missing or not responding
+    Bridge,
+    /// Operation clashes with something already running
+    Conflict,
+    /// Operation stopped by the user
+    Cancelled,
+    /// Launcher bug or background task failure
+    Internal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct LauncherError {
+    pub kind: ErrorKind,
+    pub message: String,
+    /// What the user can do about it
+    pub hint: Option<String>,
+    /// File or directory the error is about
+    pub path: Option<String>,
+    /// Catalogue ids of `message` and `hint`, serialized alongside them.
+    /// Boxed to keep `Result<_, LauncherError>` small.
+    #[serde(flatten)]
+    pub ids: Box<MessageIds>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct MessageIds {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Params::is_empty")]
+    pub params: Params,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint_id: Option<String>,
+}
+
+impl LauncherError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        LauncherError {
+            kind,
+            message: message.into(),
+            hint: None,
+            path: None,
+            ids: Box::default(),
+        }
+    }
+
+    /// Error whose message comes from the catalogue
+    pub fn localized(kind: ErrorKind, id: &str, params: Params) -> Self {
+        let mut error = Self::new(kind, i18n::text(id, &params));
+        error.ids.message_id = Some(id.to_string());
+        error.ids.params = params;
+        error
+    }
+
+    pub fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self.ids.hint_id = None;
+        self
+    }
+
+    /// Hint from the catalogue; hints take no parameters
+    pub fn with_localized_hint(mut self, id: &str) -> Self {
+        self.hint = Some(i18n::text(id, &Params::new()));
+        self.ids.hint_id = Some(id.to_string());
+        self
+    }
+
+    pub fn with_path(mut self, path: impl AsRef<Path>) -> Self {
+        self.path = Some(path.as_ref().to_string_lossy().to_string());
+        self
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::NotFound, message)
+    }
+
+    pub fn invalid(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::InvalidInput, message)
+    }
+
+    pub fn parse(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Parse, message)
+    }
+
+    pub fn network(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Network, message)
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Internal, message)
+    }
+
+    /// Filesystem error with context, e.g. `io("Failed to read config", path, e)`
+    pub fn io(context: &str, path: &Path, e: io::Error) -> Self {
+        let kind = match e.kind() {
+            io::ErrorKind::NotFound => ErrorKind::NotFound,
+            io::ErrorKind::PermissionDenied => ErrorKind::PermissionDenied,
+            _ => ErrorKind::Io,
+        };
+        let error = Self::new(kind, format!("{}: {}", context, e)).with_path(path);
+        match kind {
+            ErrorKind::PermissionDenied => error.with_localized_hint("hint.file_access"),
+            _ => error,
+        }
+    }
+
+    /// Background task that panicked or was dropped
+    pub fn task(name: &str, e: impl fmt::Display) -> Self {
+        Self::localized(
+            ErrorKind::Internal,
+            "error.task_failed",
+            i18n::params(&[("name", name.to_string()), ("error", e.to_string())]),
+        )
+    }
+
+    pub fn channel_not_found(channel_id: &str) -> Self {
+        Self::localized(
+            ErrorKind::NotFound,
+            "error.channel_not_found",
+            i18n::params(&[("channel_id", channel_id.to_string())]),
+        )
+        .with_localized_hint("hint.channel_removed")
+    }
+}
+
+impl fmt::Display for LauncherError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for LauncherError {}
+
+impl From<InputError> for LauncherError {
+    fn from(e: InputError) -> Self {
+        let path = match &e {
+            InputError::Traversal { path, .. } | InputError::OutsideRoot { path, .. } => {
+                Some(path.clone())
+            }
+            _ => None,
+        };
+        LauncherError {
+            path,
+            ..Self::localized(ErrorKind::InvalidInput, e.message_id(), e.params())
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! The Unity editor extension (`BanterMCPBridge.cs`) in channel projects
+
+use std::fs;
+
+use crate::bridge;
+use crate::elevation;
+use crate::error::LauncherError;
+use crate::journal;
+use crate::plan::FilePlan;
+use crate::preflight;
+use crate::sanitize;
+use crate::write_guard;
+
+/// Check if Unity extension is installed in a project
+pub fn check_unity_extension(unity_project_path: String) -> Result<bool, LauncherError> {
+    let extension_path = sanitize::path("Unity project path", &unity_project_path)?
+        .join("Assets")
+        .join("Editor")
+        .join("BanterMCPBridge.cs");
+
+    Ok(extension_path.exists())
+}
+
+/// Install Unity extension to a project, replacing an older copy.
+/// Projects in protected folders need `elevate` to prompt for admin rights.
+pub fn install_unity_extension(
+    unity_project_path: String,
+    mcp_root: String,
+    dry_run: Option<bool>,
+    elevate: Option<bool>,
+) -> Result<FilePlan, LauncherError> {
+    let source = sanitize::path("MCP root", &mcp_root)?
+        .join("unity-extension")
+        .join("Editor")
+        .join("BanterMCPBridge.cs");
+
+    let dest_dir = sanitize::path("Unity project path", &unity_project_path)?
+        .join("Assets")
+        .join("Editor");
+
+    let dest = dest_dir.join("BanterMCPBridge.cs");
+
+    let dry_run = dry_run.unwrap_or(false);
+    let install = || {
+        let mut plan = FilePlan::new(dry_run);
+
+        if !dry_run {
+            // Writability is left to the elevation check below
+            let size = fs::metadata(&source).map(|m| m.len()).unwrap_or(0);
+            preflight::ensure_space("Unity extension install", &dest_dir, size)?;
+        }
+
+        if !elevation::is_writable(&dest) && !elevation::is_elevated() {
+            if !elevate.unwrap_or(false) {
+                return Err(elevation::required_error(&dest_dir));
+            }
+            plan.copy_elevated(&source, &dest)?;
+            return Ok(plan);
+        }
+
+        plan.create_dir(&dest_dir)?;
+
+        plan.copy(&source, &dest)?;
+
+        Ok(plan)
+    };
+    if dry_run {
+        return install();
+    }
+    let operation = journal::Operation::ExtensionUpgrade {
+        unity_project_path,
+        mcp_root,
+    };
+    journal::run(operation, install)
+}
+
+/// Remove the Unity extension from a project as part of a plan, along with
+/// the bridge's `_MCP` folder and the .meta files Unity made for both. The
+/// `Editor` folder stays; it may hold other scripts.
+pub fn plan_extension_removal(
+    plan: &mut FilePlan,
+    unity_project_path: &str,
+) -> Result<(), LauncherError> {
+    let project = sanitize::path("Unity project path", unity_project_path)?;
+    let script = project
+        .join("Assets")
+        .join("Editor")
+        .join("BanterMCPBridge.cs");
+    plan.delete(&script)?;
+    plan.delete(&script.with_extension("cs.meta"))?;
+
+    let mcp_dir = bridge::mcp_dir(&project);
+    plan.delete_dir(&mcp_dir)?;
+    plan.delete(&mcp_dir.with_extension("meta"))
+}
+
+/// Set the custom scripts preference in Unity project's MCP state
+/// This writes to the _MCP/state folder which the Unity extension reads
+pub fn set_unity_custom_scripts(
+    unity_project_path: String,
+    enabled: bool,
+) -> Result<(), LauncherError> {
+    let state_dir = sanitize::path("Unity project path", &unity_project_path)?
+        .join("Assets")
+        .join("_MCP")
+        .join("state");
+
+    write_guard::check_write(&state_dir)?;
+    fs::create_dir_all(&state_dir)
+        .map_err(|e| LauncherError::io("Failed to create MCP state directory", &state_dir, e))?;
+
+    let settings_path = state_dir.join("launcher-settings.json");
+
+    let settings = serde_json::json!({
+        "enableCustomScripts": enabled
+    });
+
+    let content = serde_json::to_string_pretty(&settings)
+        .map_err(|e| LauncherError::internal(format!("Failed to serialize settings: {}", e)))?;
+
+    fs::write(&settings_path, content)
+        .map_err(|e| LauncherError::io("Failed to write settings", &settings_path, e))?;
+
+    Ok(())
+}
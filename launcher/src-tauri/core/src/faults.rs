@@ -0,0 +1,141 @@
+//! Injected failures for resilience testing (debug builds only)
+//!
+//! `BANTER_FAULTS=node-missing,config-locked` makes the named code paths fail
+//! the way the real problem would, so error handling and the doctor and
+//! repair flows can be checked without breaking a machine. Release builds
+//! ignore the variable.
+//!
+//! | Fault | Effect |
+//! |---|---|
+//! | `node-missing` | Starting or probing `node` fails as if it weren't on PATH |
+//! | `config-locked` | Reading and writing `launcher-config.json` fail as if another process held it open |
+//! | `claude-config-unwritable` | Writing `~/.claude.json` is refused |
+//! | `bridge-timeout` | The Unity bridge looks connected but never answers commands |
+//! | `low-disk-space` | Every volume reports a few MB free to the preflight checks |
+//! | `offline` | Probing Banter's servers fails as if there were no network |
+//!
+//! Dry runs are never failed: they don't touch the files either.
+
+use std::io;
+use std::sync::{OnceLock, RwLock};
+
+/// Comma-separated fault names to inject
+pub const ENV_VAR: &str = "BANTER_FAULTS";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    NodeMissing,
+    ConfigLocked,
+    ClaudeConfigUnwritable,
+    BridgeTimeout,
+    LowDiskSpace,
+    Offline,
+}
+
+impl Fault {
+    pub const ALL: [Fault; 6] = [
+        Fault::NodeMissing,
+        Fault::ConfigLocked,
+        Fault::ClaudeConfigUnwritable,
+        Fault::BridgeTimeout,
+        Fault::LowDiskSpace,
+        Fault::Offline,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Fault::NodeMissing => "node-missing",
+            Fault::ConfigLocked => "config-locked",
+            Fault::ClaudeConfigUnwritable => "claude-config-unwritable",
+            Fault::BridgeTimeout => "bridge-timeout",
+            Fault::LowDiskSpace => "low-disk-space",
+            Fault::Offline => "offline",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Fault> {
+        Self::ALL.into_iter().find(|f| f.as_str() == name)
+    }
+
+    /// The OS error the real problem would produce
+    fn error(self) -> io::Error {
+        match self {
+            Fault::NodeMissing => io::Error::new(
+                io::ErrorKind::NotFound,
+                "program not found (injected fault)",
+            ),
+            Fault::ConfigLocked => {
+                io::Error::other("the file is being used by another process (injected fault)")
+            }
+            Fault::ClaudeConfigUnwritable => io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "permission denied (injected fault)",
+            ),
+            Fault::BridgeTimeout => io::Error::new(
+                io::ErrorKind::TimedOut,
+                "no answer from the Unity bridge (injected fault)",
+            ),
+            Fault::LowDiskSpace => {
+                io::Error::other("there is not enough space on the disk (injected fault)")
+            }
+            Fault::Offline => io::Error::new(
+                io::ErrorKind::ConnectionRefused,
+                "network is unreachable (injected fault)",
+            ),
+        }
+    }
+}
+
+/// Set by `set`; takes precedence over the environment
+static OVERRIDE: RwLock<Option<Vec<Fault>>> = RwLock::new(None);
+
+static FROM_ENV: OnceLock<Vec<Fault>> = OnceLock::new();
+
+fn from_env() -> Vec<Fault> {
+    let Ok(names) = std::env::var(ENV_VAR) else {
+        return Vec::new();
+    };
+    names
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .filter_map(|name| {
+            let fault = Fault::parse(name);
+            if fault.is_none() {
+                eprintln!("warning: unknown fault in {}: {}", ENV_VAR, name);
+            }
+            fault
+        })
+        .collect()
+}
+
+/// Faults in effect; always none in release builds
+pub fn active() -> Vec<Fault> {
+    if !cfg!(debug_assertions) {
+        return Vec::new();
+    }
+    if let Some(faults) = OVERRIDE.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        return faults.clone();
+    }
+    FROM_ENV.get_or_init(from_env).clone()
+}
+
+pub fn is_active(fault: Fault) -> bool {
+    active().contains(&fault)
+}
+
+/// Inject `faults` instead of the ones named in the environment; tests use
+/// this
+pub fn set(faults: Vec<Fault>) {
+    *OVERRIDE.write().unwrap_or_else(|e| e.into_inner()) = Some(faults);
+}
+
+/// The error `fault` stands in for while it is active. Put in front of the
+/// real operation: `faults::check(Fault::NodeMissing).and_then(|_| ...)`.
+pub fn check(fault: Fault) -> io::Result<()> {
+    if is_active(fault) {
+        Err(fault.error())
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+//! Feature flags for experimental subsystems
+//!
+//! `LauncherConfig.features` maps flag names to on/off, so new code can ship
+//! switched off and be turned on per user. Flags missing from the map use
+//! their default; names this build doesn't know are kept in the file (a
+//! newer launcher may have written them) but ignored.
+
+use serde::Serialize;
+use specta::Type;
+
+use crate::config::LauncherConfig;
+use crate::error::LauncherError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feature {
+    /// Inspect the MCP traffic between clients and the server
+    ProxyInspector,
+    /// Run servers for more than one channel at a time
+    MultiServer,
+    /// Upload built bundles to Banter hosting
+    BanterUploads,
+}
+
+impl Feature {
+    pub const ALL: [Feature; 3] = [
+        Feature::ProxyInspector,
+        Feature::MultiServer,
+        Feature::BanterUploads,
+    ];
+
+    /// Key in `LauncherConfig.features`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Feature::ProxyInspector => "proxy-inspector",
+            Feature::MultiServer => "multi-server",
+            Feature::BanterUploads => "banter-uploads",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Feature> {
+        Self::ALL.into_iter().find(|f| f.as_str() == name)
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Feature::ProxyInspector => "Inspect MCP traffic between clients and the server",
+            Feature::MultiServer => "Run servers for several channels at once",
+            Feature::BanterUploads => "Upload built bundles to Banter hosting",
+        }
+    }
+
+    /// Uploads shipped before the flags did, so they stay on unless turned off
+    pub fn default_enabled(self) -> bool {
+        matches!(self, Feature::BanterUploads)
+    }
+}
+
+/// A flag as the settings screen shows it
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct FeatureFlag {
+    pub name: String,
+    pub description: String,
+    pub enabled: bool,
+    pub default_enabled: bool,
+}
+
+pub fn is_enabled(config: &LauncherConfig, feature: Feature) -> bool {
+    config
+        .features
+        .get(feature.as_str())
+        .copied()
+        .unwrap_or_else(|| feature.default_enabled())
+}
+
+/// Fail unless `feature` is on
+pub fn require(config: &LauncherConfig, feature: Feature) -> Result<(), LauncherError> {
+    if is_enabled(config, feature) {
+        Ok(())
+    } else {
+        Err(LauncherError::invalid(format!(
+            "{} is turned off (feature \"{}\")",
+            feature.description(),
+            feature.as_str()
+        ))
+        .with_hint("Turn the feature on in Settings"))
+    }
+}
+
+/// Every known flag and whether it is on
+pub fn flags(config: &LauncherConfig) -> Vec<FeatureFlag> {
+    Feature::ALL
+        .into_iter()
+        .map(|feature| FeatureFlag {
+            name: feature.as_str().to_string(),
+            description: feature.description().to_string(),
+            enabled: is_enabled(config, feature),
+            default_enabled: feature.default_enabled(),
+        })
+        .collect()
+}
+
+/// Turn a flag on or off in `config`
+pub fn set(config: &mut LauncherConfig, name: &str, enabled: bool) -> Result<(), LauncherError> {
+    let feature = Feature::parse(name)
+        .ok_or_else(|| LauncherError::invalid(format!("Unknown feature: {}", name)))?;
+    config
+        .features
+        .insert(feature.as_str().to_string(), enabled);
+    Ok(())
+}
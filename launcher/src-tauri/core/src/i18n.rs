@@ -0,0 +1,282 @@
+//! Message catalogue for user-facing backend text
+//!
+//! Errors and warnings that reach the user are looked up here by id and
+//! rendered in the locale chosen in `LauncherConfig.locale`, with `{name}`
+//! placeholders filled from their parameters. Commands return the id and
+//! parameters alongside the rendered text (`message_id` / `params` on
+//! `LauncherError`), so the frontend can render its own translation or fall
+//! back to the text as sent. Text without an id here is still English only.
+
+use serde::Serialize;
+use specta::Type;
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+/// Parameters substituted into a message
+pub type Params = BTreeMap<String, String>;
+
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Locales the catalogue has text for
+pub const LOCALES: &[&str] = &["en", "de"];
+
+/// Locale of rendered messages; refreshed from `AppState` whenever the config changes
+static LOCALE: RwLock<&'static str> = RwLock::new(DEFAULT_LOCALE);
+
+struct Entry {
+    id: &'static str,
+    en: &'static str,
+    de: &'static str,
+}
+
+const CATALOGUE: &[Entry] = &[
+    Entry {
+        id: "input.empty",
+        en: "{field} must not be empty",
+        de: "{field} darf nicht leer sein",
+    },
+    Entry {
+        id: "input.too_long",
+        en: "{field} is too long (max {max} characters)",
+        de: "{field} ist zu lang (höchstens {max} Zeichen)",
+    },
+    Entry {
+        id: "input.control_characters",
+        en: "{field} contains control characters",
+        de: "{field} enthält Steuerzeichen",
+    },
+    Entry {
+        id: "input.invalid_characters",
+        en: "{field} may only contain letters, digits, '-' and '_'",
+        de: "{field} darf nur Buchstaben, Ziffern, '-' und '_' enthalten",
+    },
+    Entry {
+        id: "input.traversal",
+        en: "{field} must not contain '..': {path}",
+        de: "{field} darf kein '..' enthalten: {path}",
+    },
+    Entry {
+        id: "input.outside_root",
+        en: "{field} {path} is outside {root}",
+        de: "{field} {path} liegt außerhalb von {root}",
+    },
+    Entry {
+        id: "input.duplicate",
+        en: "Duplicate {field}: {value}",
+        de: "{field} kommt doppelt vor: {value}",
+    },
+    Entry {
+        id: "input.unsupported",
+        en: "Unsupported {field}: {value}",
+        de: "Nicht unterstützter Wert für {field}: {value}",
+    },
+    Entry {
+        id: "input.invalid_schedule",
+        en: "{field} is not a valid schedule: {value} ({reason})",
+        de: "{field} ist kein gültiger Zeitplan: {value} ({reason})",
+    },
+    Entry {
+        id: "error.channel_not_found",
+        en: "Channel not found: {channel_id}",
+        de: "Kanal nicht gefunden: {channel_id}",
+    },
+    Entry {
+        id: "hint.channel_removed",
+        en: "The channel may have been removed - reload the channel list",
+        de: "Der Kanal wurde möglicherweise entfernt - Kanalliste neu laden",
+    },
+    Entry {
+        id: "hint.file_access",
+        en: "Check that the file isn't open in another program and that you have write access",
+        de: "Prüfen, ob die Datei in einem anderen Programm geöffnet ist und Schreibrechte bestehen",
+    },
+    Entry {
+        id: "error.task_failed",
+        en: "{name} task failed: {error}",
+        de: "Aufgabe {name} fehlgeschlagen: {error}",
+    },
+    Entry {
+        id: "error.config_parse",
+        en: "Failed to parse config: {error}",
+        de: "Konfiguration konnte nicht gelesen werden: {error}",
+    },
+    Entry {
+        id: "hint.config_parse",
+        en: "Fix the file by hand or restore a launcher-config backup",
+        de: "Datei von Hand korrigieren oder ein launcher-config-Backup wiederherstellen",
+    },
+    Entry {
+        id: "error.elevation_required",
+        en: "Writing to {path} requires administrator rights",
+        de: "Schreiben nach {path} erfordert Administratorrechte",
+    },
+    Entry {
+        id: "hint.elevation_required",
+        en: "Retry and approve the elevation prompt, or move the project to a folder you own",
+        de: "Erneut versuchen und die Rechteabfrage bestätigen oder das Projekt in einen eigenen Ordner verschieben",
+    },
+    Entry {
+        id: "error.write_refused",
+        en: "Refusing to write {path}: outside the launcher's allowed locations",
+        de: "Schreiben nach {path} verweigert: außerhalb der erlaubten Orte des Launchers",
+    },
+    Entry {
+        id: "hint.write_refused",
+        en: "Only the launcher's data folder, registered Unity projects and MCP client configs can be written",
+        de: "Nur der Datenordner des Launchers, registrierte Unity-Projekte und MCP-Client-Konfigurationen dürfen geschrieben werden",
+    },
+    Entry {
+        id: "validation.bundle_size",
+        en: "{platform} bundle is {size_mb} MB (limit {limit_mb} MB)",
+        de: "{platform}-Bundle ist {size_mb} MB groß (Grenze {limit_mb} MB)",
+    },
+    Entry {
+        id: "validation.texture_size",
+        en: "Texture imports at up to {size}px (recommended max {max}px for Quest)",
+        de: "Textur wird mit bis zu {size}px importiert (für Quest höchstens {max}px empfohlen)",
+    },
+    Entry {
+        id: "validation.unsupported_component",
+        en: "{component} on '{object}' conflicts with the Banter player rig",
+        de: "{component} auf '{object}' kollidiert mit dem Banter-Player-Rig",
+    },
+    Entry {
+        id: "validation.custom_script",
+        en: "Custom script on '{object}' is not supported in Banter (enable custom scripts to allow)",
+        de: "Eigenes Skript auf '{object}' wird in Banter nicht unterstützt (eigene Skripte aktivieren, um es zu erlauben)",
+    },
+    Entry {
+        id: "validation.missing_collider",
+        en: "'{object}' looks like a teleport surface but has no collider",
+        de: "'{object}' sieht nach einer Teleport-Fläche aus, hat aber keinen Collider",
+    },
+    Entry {
+        id: "validation.missing_script",
+        en: "'{object}' has a missing script (GUID {guid})",
+        de: "'{object}' hat ein fehlendes Skript (GUID {guid})",
+    },
+    Entry {
+        id: "validation.missing_script_unassigned",
+        en: "'{object}' has a script component with no script assigned",
+        de: "'{object}' hat eine Skript-Komponente ohne zugewiesenes Skript",
+    },
+    Entry {
+        id: "validation.broken_prefab",
+        en: "Prefab instance '{object}' points at a prefab that no longer exists (GUID {guid})",
+        de: "Prefab-Instanz '{object}' verweist auf ein Prefab, das es nicht mehr gibt (GUID {guid})",
+    },
+];
+
+fn template(entry: &Entry, locale: &str) -> &'static str {
+    match locale {
+        "de" => entry.de,
+        _ => entry.en,
+    }
+}
+
+/// Use `locale` for rendered messages; unknown locales fall back to English
+pub fn set_locale(locale: &str) {
+    let locale = LOCALES
+        .iter()
+        .find(|l| **l == locale)
+        .copied()
+        .unwrap_or(DEFAULT_LOCALE);
+    *LOCALE.write().unwrap_or_else(|e| e.into_inner()) = locale;
+}
+
+pub fn is_supported(locale: &str) -> bool {
+    LOCALES.contains(&locale)
+}
+
+/// Build parameters from name/value pairs
+pub fn params(pairs: &[(&str, String)]) -> Params {
+    pairs
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.clone()))
+        .collect()
+}
+
+fn fill(template: &str, params: &Params) -> String {
+    let mut text = template.to_string();
+    for (name, value) in params {
+        text = text.replace(&format!("{{{}}}", name), value);
+    }
+    text
+}
+
+/// Render a message in the current locale. An unknown id renders as itself.
+pub fn text(id: &str, params: &Params) -> String {
+    let locale = *LOCALE.read().unwrap_or_else(|e| e.into_inner());
+    match CATALOGUE.iter().find(|e| e.id == id) {
+        Some(entry) => fill(template(entry, locale), params),
+        None => id.to_string(),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct MessageCatalogue {
+    pub locale: String,
+    pub locales: Vec<String>,
+    /// Message id -> template with `{name}` placeholders
+    pub messages: BTreeMap<String, String>,
+}
+
+/// Every message template in `locale`, or the current locale if it isn't
+/// supported
+pub fn catalogue(locale: Option<&str>) -> MessageCatalogue {
+    let current = *LOCALE.read().unwrap_or_else(|e| e.into_inner());
+    let locale = locale
+        .filter(|l| is_supported(l))
+        .unwrap_or(current)
+        .to_string();
+
+    MessageCatalogue {
+        messages: CATALOGUE
+            .iter()
+            .map(|e| (e.id.to_string(), template(e, &locale).to_string()))
+            .collect(),
+        locales: LOCALES.iter().map(|l| l.to_string()).collect(),
+        locale,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_message_has_both_locales_and_matching_placeholders() {
+        let placeholders = |text: &str| {
+            let mut names: Vec<String> = text
+                .split('{')
+                .skip(1)
+                .filter_map(|rest| rest.split_once('}').map(|(name, _)| name.to_string()))
+                .collect();
+            names.sort();
+            names
+        };
+        for entry in CATALOGUE {
+            assert!(!entry.en.is_empty() && !entry.de.is_empty(), "{}", entry.id);
+            assert_eq!(
+                placeholders(entry.en),
+                placeholders(entry.de),
+                "{}",
+                entry.id
+            );
+        }
+    }
+
+    #[test]
+    fn fill_substitutes_named_parameters() {
+        let params = params(&[("field", "Name".to_string()), ("max", "64".to_string())]);
+        assert_eq!(
+            fill("{field} is longer than {max}", &params),
+            "Name is longer than 64"
+        );
+    }
+
+    #[test]
+    fn unknown_ids_render_as_themselves() {
+        assert_eq!(text("no.such.message", &Params::new()), "no.such.message");
+    }
+}
@@ -0,0 +1,232 @@
+//! Write-ahead journal for operations that take several steps
+//!
+//! Activating a channel writes the launcher config and then the client
+//! configs; a power loss in between leaves Claude Code pointed at the old
+//! project while the launcher says the new one is active. `run` writes an
+//! entry to the launcher's store (collection `journal`, see `storage`) before
+//! such an operation starts, and every `FilePlan` change made while it runs first saves what
+//! the file held (the plan calls `before_change`). A finished operation,
+//! failed or not, deletes its entry; one left behind was cut short by a
+//! crash. `incomplete` lists those, `rollback` puts their files back the way
+//! they were, and `discard` forgets one, e.g. after the app ran it again.
+//!
+//! Entries and the copies in `journal/<id>/` are written directly rather
+//! than through a plan, so they aren't journaled themselves, and synced to
+//! disk before the change they describe.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::cell::RefCell;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use crate::bridge;
+use crate::config;
+use crate::error::LauncherError;
+use crate::plan::FilePlan;
+use crate::storage;
+
+const COLLECTION: &str = "journal";
+
+/// What an interrupted operation was doing, enough to run it again
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Operation {
+    Activation {
+        channel_id: String,
+    },
+    ClientConfigSync {
+        channel_id: String,
+        mcp_server_path: String,
+    },
+    ExtensionUpgrade {
+        unity_project_path: String,
+        mcp_root: String,
+    },
+}
+
+/// A path as it was before the operation first changed it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct PriorFile {
+    pub path: String,
+    /// Copy of what the file held, `None` when it didn't exist
+    pub saved: Option<String>,
+    /// The operation created this directory
+    #[serde(default)]
+    pub created_dir: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct JournalEntry {
+    pub id: String,
+    pub operation: Operation,
+    pub started_ms: i64,
+    /// Launcher process that ran it
+    pub pid: u32,
+    /// In the order they were first changed
+    pub files: Vec<PriorFile>,
+}
+
+thread_local! {
+    /// The entry of the operation running on this thread
+    static CURRENT: RefCell<Option<JournalEntry>> = const { RefCell::new(None) };
+}
+
+pub fn journal_dir() -> PathBuf {
+    config::launcher_dir().join("journal")
+}
+
+fn saved_dir(id: &str) -> PathBuf {
+    journal_dir().join(id)
+}
+
+/// Store `entry`, which both backends commit before returning, so it is on
+/// disk before what it describes
+fn save(entry: &JournalEntry) -> Result<(), LauncherError> {
+    let content = serde_json::to_string_pretty(entry)
+        .map_err(|e| LauncherError::internal(format!("Failed to serialize journal: {}", e)))?;
+    storage::store().put(COLLECTION, &entry.id, &content)
+}
+
+fn remove(id: &str) -> Result<(), LauncherError> {
+    storage::store().delete(COLLECTION, id)?;
+    let dir = saved_dir(id);
+    if dir.is_dir() {
+        fs::remove_dir_all(&dir).map_err(|e| LauncherError::io("Failed to delete", &dir, e))?;
+    }
+    Ok(())
+}
+
+/// Clears this thread's entry even if the operation panics; the file stays,
+/// since a panic is as good as a crash
+struct Running;
+
+impl Drop for Running {
+    fn drop(&mut self) {
+        CURRENT.with(|current| current.borrow_mut().take());
+    }
+}
+
+/// Whether an operation is running on this thread
+pub fn is_running() -> bool {
+    CURRENT.with(|current| current.borrow().is_some())
+}
+
+/// Run `operation` under a journal entry. An operation started by another
+/// one (activation syncing the client configs) is part of the outer entry.
+pub fn run<T>(
+    operation: Operation,
+    f: impl FnOnce() -> Result<T, LauncherError>,
+) -> Result<T, LauncherError> {
+    if is_running() {
+        return f();
+    }
+    let entry = JournalEntry {
+        id: uuid::Uuid::new_v4().to_string(),
+        operation,
+        started_ms: bridge::now_ms(),
+        pid: std::process::id(),
+        files: Vec::new(),
+    };
+    save(&entry)?;
+    let id = entry.id.clone();
+    CURRENT.with(|current| *current.borrow_mut() = Some(entry));
+    let running = Running;
+    let result = f();
+    drop(running);
+    if let Err(e) = remove(&id) {
+        eprintln!("warning: failed to clear journal {}: {}", id, e);
+    }
+    result
+}
+
+fn note(entry: &mut JournalEntry, path: &Path, dir: bool) -> Result<(), LauncherError> {
+    let key = path.to_string_lossy().to_string();
+    if entry.files.iter().any(|f| f.path == key) {
+        return Ok(());
+    }
+    let saved = if !dir && path.is_file() {
+        let saved_dir = saved_dir(&entry.id);
+        let copy = saved_dir.join(entry.files.len().to_string());
+        fs::create_dir_all(&saved_dir)
+            .and_then(|_| fs::copy(path, &copy))
+            .and_then(|_| File::open(&copy)?.sync_all())
+            .map_err(|e| LauncherError::io("Failed to journal", path, e))?;
+        Some(copy.to_string_lossy().to_string())
+    } else {
+        None
+    };
+    entry.files.push(PriorFile {
+        path: key,
+        saved,
+        created_dir: dir,
+    });
+    save(entry)
+}
+
+/// Save what `path` holds before a running operation changes it; nothing
+/// outside an operation. `dir` is set for a directory about to be created.
+pub fn before_change(path: &Path, dir: bool) -> Result<(), LauncherError> {
+    CURRENT.with(|current| match current.borrow_mut().as_mut() {
+        Some(entry) => note(entry, path, dir),
+        None => Ok(()),
+    })
+}
+
+/// Entries left on disk, oldest first. Those of operations still running
+/// are included; the app tells them apart by `pid`.
+pub fn incomplete() -> Vec<JournalEntry> {
+    let mut entries: Vec<JournalEntry> = storage::store()
+        .list(COLLECTION)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(_, value)| serde_json::from_str(&value).ok())
+        .collect();
+    entries.sort_by_key(|entry: &JournalEntry| entry.started_ms);
+    entries
+}
+
+pub fn load(id: &str) -> Result<JournalEntry, LauncherError> {
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(LauncherError::invalid(format!("Not a journal id: {}", id)));
+    }
+    let content = storage::store()
+        .get(COLLECTION, id)?
+        .ok_or_else(|| LauncherError::not_found(format!("No interrupted operation {}", id)))?;
+    serde_json::from_str(&content)
+        .map_err(|e| LauncherError::parse(format!("Unreadable journal {}: {}", id, e)))
+}
+
+/// Put the entry's files back the way they were, newest change first, and
+/// forget it
+pub fn rollback(id: &str) -> Result<FilePlan, LauncherError> {
+    let entry = load(id)?;
+    let mut plan = FilePlan::new(false);
+    for file in entry.files.iter().rev() {
+        let path = Path::new(&file.path);
+        match &file.saved {
+            Some(saved) => {
+                // Its folder may have gone with a deleted directory
+                if let Some(parent) = path.parent() {
+                    plan.create_dir(parent)?;
+                }
+                plan.copy(Path::new(saved), path)?
+            }
+            // A directory goes only if the operation left nothing else in it
+            None if file.created_dir => {
+                if fs::read_dir(path).is_ok_and(|mut items| items.next().is_none()) {
+                    plan.delete_dir(path)?;
+                }
+            }
+            None => plan.delete(path)?,
+        }
+    }
+    remove(id)?;
+    Ok(plan)
+}
+
+/// Forget an entry without touching its files
+pub fn discard(id: &str) -> Result<(), LauncherError> {
+    load(id)?;
+    remove(id)
+}
@@ -0,0 +1,44 @@
+//! Core logic of the BANTWORKS MCP launcher
+//!
+//! Everything here is plain synchronous code with no Tauri dependency: the
+//! launcher config and its file, channels, the Claude Code client config,
+//! the Unity extension, and the pieces they are built on (errors, input
+//! sanitizing, the write allowlist, file plans and the journal that rolls
+//! back interrupted ones, backups and feature flags), per-user ports and
+//! instance files, disk space preflight checks, cron-like schedules, channel
+//! quotas, telling a crashed Unity editor from a closed one, opt-in usage
+//! counts, install paths written as environment references, command files
+//! dropped into a project, finding the channel a path belongs to, the store
+//! job history and caches are kept in, the commands that need tools a
+//! machine may lack, plus the fault injection used to test how all of it
+//! fails.
+//! The app crate wraps these in `#[tauri::command]`s and adds the state it
+//! keeps between calls; the tests in `tests/` run them against temporary
+//! directories.
+
+pub mod backup;
+pub mod bridge;
+pub mod channels;
+pub mod client_config;
+pub mod config;
+pub mod editor;
+pub mod elevation;
+pub mod error;
+pub mod extension;
+pub mod faults;
+pub mod features;
+pub mod i18n;
+pub mod journal;
+pub mod path_index;
+pub mod plan;
+pub mod portable;
+pub mod preflight;
+pub mod quotas;
+pub mod sanitize;
+pub mod schedule;
+pub mod stack;
+pub mod storage;
+pub mod telemetry;
+pub mod triggers;
+pub mod user;
+pub mod write_guard;
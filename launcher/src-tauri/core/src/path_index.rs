@@ -0,0 +1,101 @@
+//! Which channel a file belongs to
+//!
+//! A path handed to the launcher from outside (a file dropped on the window,
+//! a CLI argument) usually sits in a project that already has a channel.
+//! `find_channel_for_path` answers that from an index of every channel's
+//! project and scene, canonicalized the way `write_guard` does it, so
+//! symlinks, `..` and trailing separators don't hide a match and no
+//! duplicate channel gets created. The index is rebuilt whenever the config
+//! changes.
+//!
+//! Nested projects resolve to the innermost one. When several channels share
+//! a project, the one whose scene is the path wins, then enabled channels,
+//! then the first in the config.
+
+use serde::Serialize;
+use specta::Type;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use crate::config::{LauncherConfig, ProjectChannel};
+use crate::write_guard;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Type)]
+pub struct ChannelMatch {
+    pub channel_id: String,
+    pub channel_name: String,
+    /// The channel's project, which contains the path
+    pub unity_project_path: String,
+    /// The path is the channel's scene
+    pub scene_match: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    channel: ProjectChannel,
+    project: PathBuf,
+    scene: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PathIndex {
+    entries: Vec<Entry>,
+}
+
+impl PathIndex {
+    pub fn new(channels: &[ProjectChannel]) -> Self {
+        PathIndex {
+            entries: channels
+                .iter()
+                .map(|channel| Entry {
+                    channel: channel.clone(),
+                    project: write_guard::normalize(Path::new(&channel.unity_project_path)),
+                    scene: channel
+                        .scene_path
+                        .as_deref()
+                        .map(|scene| write_guard::normalize(Path::new(scene))),
+                })
+                .collect(),
+        }
+    }
+
+    /// The channel whose project contains `path`, if any
+    pub fn find(&self, path: &Path) -> Option<ChannelMatch> {
+        let path = write_guard::normalize(path);
+        let (_, entry) = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| path.starts_with(&entry.project))
+            .max_by_key(|(index, entry)| {
+                (
+                    entry.project.components().count(),
+                    entry.scene.as_ref() == Some(&path),
+                    entry.channel.enabled,
+                    std::cmp::Reverse(*index),
+                )
+            })?;
+        Some(ChannelMatch {
+            channel_id: entry.channel.id.clone(),
+            channel_name: entry.channel.name.clone(),
+            unity_project_path: entry.channel.unity_project_path.clone(),
+            scene_match: entry.scene.as_ref() == Some(&path),
+        })
+    }
+}
+
+static INDEX: RwLock<Option<PathIndex>> = RwLock::new(None);
+
+/// Index the channels of a config; called whenever it changes
+pub fn rebuild(config: &LauncherConfig) {
+    *INDEX.write().unwrap_or_else(|e| e.into_inner()) = Some(PathIndex::new(&config.channels));
+}
+
+/// The existing channel `path` belongs to, from the last `rebuild`
+pub fn find_channel_for_path(path: &Path) -> Option<ChannelMatch> {
+    INDEX
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()?
+        .find(path)
+}
@@ -0,0 +1,241 @@
+//! Recorded file effects of destructive operations
+//!
+//! Operations that touch user files go through a `FilePlan`. With `dry_run`
+//! set nothing is written and the plan lists exactly what would have been.
+//! Every target is checked against the write allowlist, dry run or not.
+//! Inside a `journal::run` operation each change first saves what the target
+//! held, so a crash can be rolled back.
+
+use serde::Serialize;
+use specta::Type;
+
+use crate::backup::{self, BackupKind, BackupRetention};
+use crate::error::LauncherError;
+use crate::{elevation, journal, write_guard};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum FileAction {
+    CreateDir,
+    Write,
+    Copy,
+    Delete,
+    Backup,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct FileOperation {
+    pub action: FileAction,
+    pub path: String,
+    /// Copy source
+    pub source: Option<String>,
+    /// Size of the written or copied content
+    pub bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct FilePlan {
+    pub dry_run: bool,
+    pub operations: Vec<FileOperation>,
+}
+
+impl FilePlan {
+    pub fn new(dry_run: bool) -> Self {
+        FilePlan {
+            dry_run,
+            operations: Vec::new(),
+        }
+    }
+
+    fn record(
+        &mut self,
+        action: FileAction,
+        path: &Path,
+        source: Option<&Path>,
+        bytes: Option<u64>,
+    ) {
+        self.operations.push(FileOperation {
+            action,
+            path: path.to_string_lossy().to_string(),
+            source: source.map(|s| s.to_string_lossy().to_string()),
+            bytes,
+        });
+    }
+
+    /// Create a directory and its parents; no-op if it already exists
+    pub fn create_dir(&mut self, path: &Path) -> Result<(), LauncherError> {
+        if path.is_dir() {
+            return Ok(());
+        }
+        write_guard::check_write(path)?;
+        self.record(FileAction::CreateDir, path, None, None);
+        if self.dry_run {
+            return Ok(());
+        }
+        journal::before_change(path, true)?;
+        fs::create_dir_all(path)
+            .map_err(|e| LauncherError::io("Failed to create directory", path, e))
+    }
+
+    pub fn write(&mut self, path: &Path, contents: impl AsRef<[u8]>) -> Result<(), LauncherError> {
+        let contents = contents.as_ref();
+        write_guard::check_write(path)?;
+        self.record(FileAction::Write, path, None, Some(contents.len() as u64));
+        if self.dry_run {
+            return Ok(());
+        }
+        journal::before_change(path, false)?;
+        // Write a sibling then rename, so a crash never leaves a half-written file
+        let tmp = path.with_file_name(format!(
+            ".{}.tmp",
+            path.file_name().unwrap_or_default().to_string_lossy()
+        ));
+        fs::write(&tmp, contents)
+            .and_then(|_| fs::rename(&tmp, path))
+            .map_err(|e| {
+                fs::remove_file(&tmp).ok();
+                LauncherError::io("Failed to write", path, e)
+            })
+    }
+
+    /// Write a file only the current user can read (mode 0600 on Unix; on
+    /// Windows the file inherits the per-user profile ACL)
+    pub fn write_private(
+        &mut self,
+        path: &Path,
+        contents: impl AsRef<[u8]>,
+    ) -> Result<(), LauncherError> {
+        let contents = contents.as_ref();
+        write_guard::check_write(path)?;
+        self.record(FileAction::Write, path, None, Some(contents.len() as u64));
+        if self.dry_run {
+            return Ok(());
+        }
+        journal::before_change(path, false)?;
+
+        let write = || {
+            let mut options = fs::OpenOptions::new();
+            options.write(true).create(true).truncate(true);
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::OpenOptionsExt;
+                options.mode(0o600);
+            }
+            let mut file = options.open(path)?;
+            #[cfg(unix)]
+            {
+                // `mode` only applies on creation
+                use std::os::unix::fs::PermissionsExt;
+                file.set_permissions(fs::Permissions::from_mode(0o600))?;
+            }
+            file.write_all(contents)
+        };
+        write().map_err(|e| LauncherError::io("Failed to write", path, e))
+    }
+
+    /// Copy a file. The source must exist even in a dry run.
+    pub fn copy(&mut self, source: &Path, dest: &Path) -> Result<(), LauncherError> {
+        write_guard::check_write(dest)?;
+        let bytes = source
+            .metadata()
+            .map_err(|e| LauncherError::io("Failed to read", source, e))?
+            .len();
+        self.record(FileAction::Copy, dest, Some(source), Some(bytes));
+        if self.dry_run {
+            return Ok(());
+        }
+        journal::before_change(dest, false)?;
+        fs::copy(source, dest)
+            .map(|_| ())
+            .map_err(|e| LauncherError::io("Failed to copy to", dest, e))
+    }
+
+    /// Copy a file into a location the user can't write to, via an elevation prompt
+    pub fn copy_elevated(&mut self, source: &Path, dest: &Path) -> Result<(), LauncherError> {
+        write_guard::check_write(dest)?;
+        let bytes = source
+            .metadata()
+            .map_err(|e| LauncherError::io("Failed to read", source, e))?
+            .len();
+        self.record(FileAction::Copy, dest, Some(source), Some(bytes));
+        if self.dry_run {
+            return Ok(());
+        }
+        journal::before_change(dest, false)?;
+        elevation::copy_elevated(source, dest)
+    }
+
+    /// Delete a file; no-op if it doesn't exist
+    pub fn delete(&mut self, path: &Path) -> Result<(), LauncherError> {
+        if !path.exists() {
+            return Ok(());
+        }
+        write_guard::check_write(path)?;
+        self.record(FileAction::Delete, path, None, None);
+        if self.dry_run {
+            return Ok(());
+        }
+        journal::before_change(path, false)?;
+        fs::remove_file(path).map_err(|e| LauncherError::io("Failed to delete", path, e))
+    }
+
+    /// Delete a directory and everything in it. Inside a journaled operation
+    /// every file below it is saved first; a rollback puts those back, but
+    /// not empty folders or links.
+    pub fn delete_dir(&mut self, path: &Path) -> Result<(), LauncherError> {
+        if !path.is_dir() {
+            return Ok(());
+        }
+        write_guard::check_write(path)?;
+        self.record(FileAction::Delete, path, None, None);
+        if self.dry_run {
+            return Ok(());
+        }
+        if journal::is_running() {
+            for file in files_below(path)? {
+                journal::before_change(&file, false)?;
+            }
+        }
+        fs::remove_dir_all(path).map_err(|e| LauncherError::io("Failed to delete", path, e))
+    }
+
+    /// Back up an existing file before it is overwritten
+    pub fn backup(
+        &mut self,
+        kind: BackupKind,
+        path: &Path,
+        retention: &BackupRetention,
+    ) -> Result<(), LauncherError> {
+        if !path.is_file() {
+            return Ok(());
+        }
+        let bytes = path.metadata().ok().map(|m| m.len());
+        self.record(FileAction::Backup, path, None, bytes);
+        if self.dry_run {
+            return Ok(());
+        }
+        backup::create(kind, path, retention).map(|_| ())
+    }
+}
+
+/// Regular files below `dir`, without following links
+fn files_below(dir: &Path) -> Result<Vec<PathBuf>, LauncherError> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let read = |e| LauncherError::io("Failed to read", &dir, e);
+        for entry in fs::read_dir(&dir).map_err(read)? {
+            let entry = entry.map_err(read)?;
+            let kind = entry.file_type().map_err(read)?;
+            if kind.is_dir() {
+                pending.push(entry.path());
+            } else if kind.is_file() {
+                files.push(entry.path());
+            }
+        }
+    }
+    Ok(files)
+}
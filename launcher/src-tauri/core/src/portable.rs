@@ -0,0 +1,134 @@
+//! Install paths in client configs as environment variable references
+//!
+//! With `portable_client_config` on, the `banter` entry names the MCP server
+//! checkout, the launcher folder and the launcher's own folder through
+//! `BANTER_MCP_HOME`, `BANTER_LAUNCHER_DIR` and `BANTER_LAUNCHER_HOME`
+//! instead of this machine's paths, so a synced `~/.claude.json` keeps
+//! working where they are installed somewhere else. Each client gets the
+//! syntax it expands: Claude Code takes `${NAME:-default}` and falls back to
+//! the path it was written with, Cursor takes `${env:NAME}` and knows the
+//! project as `${workspaceFolder}`. Paths nothing stands for stay literal.
+//! The app's `env_bootstrap` sets the variables on a machine.
+
+use std::path::{Path, PathBuf};
+
+pub const MCP_HOME_VAR: &str = "BANTER_MCP_HOME";
+pub const LAUNCHER_DIR_VAR: &str = "BANTER_LAUNCHER_DIR";
+pub const LAUNCHER_HOME_VAR: &str = "BANTER_LAUNCHER_HOME";
+
+/// How a client writes an environment reference
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Syntax {
+    /// `${NAME:-default}`
+    Claude,
+    /// `${env:NAME}`, with `${workspaceFolder}` for the project
+    Cursor,
+}
+
+impl Syntax {
+    /// The syntax of a client by its `WORKSPACE_CLIENTS` name, `None` when
+    /// it doesn't expand variables
+    pub fn for_client(client: &str) -> Option<Syntax> {
+        match client {
+            "claude" => Some(Syntax::Claude),
+            "cursor" => Some(Syntax::Cursor),
+            _ => None,
+        }
+    }
+}
+
+/// The folders a config's paths are rewritten against
+#[derive(Debug, Clone, Default)]
+pub struct Roots {
+    /// Variable and the folder it stands for on this machine
+    pub vars: Vec<(&'static str, PathBuf)>,
+    /// The project folder of a config that lives in it
+    pub workspace: Option<PathBuf>,
+}
+
+/// The MCP server checkout `entry` (e.g. `.../banter-mcp/dist/index.js`)
+/// belongs to
+pub fn mcp_home(entry: &Path) -> PathBuf {
+    let dir = entry.parent().unwrap_or(entry);
+    match dir.file_name() {
+        Some(name) if name == "dist" => dir.parent().unwrap_or(dir).to_path_buf(),
+        _ => dir.to_path_buf(),
+    }
+}
+
+fn key(path: &str) -> String {
+    let key = path.replace('\\', "/");
+    let key = key.trim_end_matches('/');
+    if cfg!(windows) {
+        key.to_ascii_lowercase()
+    } else {
+        key.to_string()
+    }
+}
+
+fn reference(syntax: Syntax, var: &str, root: &Path) -> Option<String> {
+    let root = root.to_string_lossy().replace('\\', "/");
+    match syntax {
+        // A default can't hold the brace that ends it
+        Syntax::Claude if root.contains('}') => None,
+        Syntax::Claude => Some(format!("${{{}:-{}}}", var, root.trim_end_matches('/'))),
+        Syntax::Cursor => Some(format!("${{env:{}}}", var)),
+    }
+}
+
+/// `path` with the longest root it is under replaced by its reference
+fn rewrite_path(path: &str, syntax: Syntax, roots: &Roots) -> String {
+    let workspace = roots
+        .workspace
+        .iter()
+        .filter(|_| syntax == Syntax::Cursor)
+        .map(|root| (root, Some("${workspaceFolder}".to_string())));
+    let vars = roots
+        .vars
+        .iter()
+        .map(|(var, root)| (root, reference(syntax, var, root)));
+    let target = key(path);
+    let found = workspace
+        .chain(vars)
+        .filter_map(|(root, reference)| Some((key(&root.to_string_lossy()), reference?)))
+        .filter(|(root, _)| {
+            !root.is_empty()
+                && target.starts_with(root.as_str())
+                && matches!(target.as_bytes().get(root.len()), None | Some(b'/'))
+        })
+        .max_by_key(|(root, _)| root.len());
+    match found {
+        // Normalizing keeps byte offsets, so the rest is cut from the original
+        Some((root, reference)) => format!("{}{}", reference, &path[root.len()..]),
+        None => path.to_string(),
+    }
+}
+
+/// `value` with every path in it rewritten; a list joined with the platform's
+/// path separator (like `BANTER_ALLOWED_ROOTS`) is rewritten entry by entry
+pub fn rewrite(value: &str, syntax: Syntax, roots: &Roots) -> String {
+    let separator = if cfg!(windows) { ';' } else { ':' };
+    value
+        .split(separator)
+        .map(|path| rewrite_path(path, syntax, roots))
+        .collect::<Vec<_>>()
+        .join(&separator.to_string())
+}
+
+/// Every string in a JSON value (an entry's `env`) rewritten
+pub fn rewrite_json(value: &mut serde_json::Value, syntax: Syntax, roots: &Roots) {
+    match value {
+        serde_json::Value::String(s) => *s = rewrite(s, syntax, roots),
+        serde_json::Value::Array(items) => {
+            for item in items {
+                rewrite_json(item, syntax, roots);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for (_, item) in map.iter_mut() {
+                rewrite_json(item, syntax, roots);
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,197 @@
+//! Disk space and write access checks before filling a disk
+//!
+//! A full drive or a read-only folder otherwise surfaces halfway through a
+//! copy, as whatever the OS said about the file that happened to fail. Installs,
+//! bundle builds and scene snapshots check their target volume first and
+//! refuse with an error that names the folder and what it needs; the doctor
+//! reports the same checks. Free space that can't be determined never blocks
+//! anything.
+
+use serde::Serialize;
+use specta::Type;
+use std::path::Path;
+
+use crate::elevation;
+use crate::error::{ErrorKind, LauncherError};
+use crate::faults::{self, Fault};
+
+const MB: u64 = 1024 * 1024;
+
+/// Headroom every operation needs on top of what it writes; Unity and the
+/// OS need somewhere to put temporary files too
+pub const MIN_FREE_BYTES: u64 = 200 * MB;
+
+/// A bundle build fills Unity's Library cache as well as the output folder
+pub const BUILD_FREE_BYTES: u64 = 2048 * MB;
+
+/// Free space reported while `Fault::LowDiskSpace` is injected
+const LOW_DISK_FAULT_BYTES: u64 = 5 * MB;
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct VolumeCheck {
+    /// What needs the space, e.g. "bundle build"
+    pub purpose: String,
+    pub path: String,
+    /// `None` when the OS couldn't tell
+    pub free_bytes: Option<u64>,
+    pub required_bytes: u64,
+    pub writable: bool,
+}
+
+impl VolumeCheck {
+    pub fn has_space(&self) -> bool {
+        self.free_bytes
+            .is_none_or(|free| free >= self.required_bytes)
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.writable && self.has_space()
+    }
+
+    /// e.g. "12.4 GB free in /projects/lobby (2.0 GB needed for bundle build)"
+    pub fn summary(&self) -> String {
+        if !self.writable {
+            return format!("{} isn't writable ({})", self.path, self.purpose);
+        }
+        let free = self
+            .free_bytes
+            .map(format_bytes)
+            .unwrap_or_else(|| "Unknown space".to_string());
+        format!(
+            "{} free in {} ({} needed for {})",
+            free,
+            self.path,
+            format_bytes(self.required_bytes),
+            self.purpose
+        )
+    }
+}
+
+/// Bytes in the largest fitting unit, one decimal
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = "bytes";
+    for next in UNITS {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next;
+    }
+    if unit == "bytes" {
+        format!("{} bytes", bytes)
+    } else {
+        format!("{:.1} {}", value, unit)
+    }
+}
+
+/// Space the current user may use on the volume holding `path` (or where it
+/// would be created)
+pub fn free_space(path: &Path) -> Option<u64> {
+    if faults::is_active(Fault::LowDiskSpace) {
+        return Some(LOW_DISK_FAULT_BYTES);
+    }
+    let dir = elevation::existing_ancestor(path)?;
+    volume_free_space(dir)
+}
+
+#[cfg(windows)]
+fn volume_free_space(dir: &Path) -> Option<u64> {
+    use std::os::windows::ffi::OsStrExt;
+
+    extern "system" {
+        fn GetDiskFreeSpaceExW(
+            directory: *const u16,
+            free_to_caller: *mut u64,
+            total: *mut u64,
+            total_free: *mut u64,
+        ) -> i32;
+    }
+    let wide: Vec<u16> = dir.as_os_str().encode_wide().chain([0]).collect();
+    let mut free = 0u64;
+    let ok = unsafe {
+        GetDiskFreeSpaceExW(
+            wide.as_ptr(),
+            &mut free,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        )
+    };
+    (ok != 0).then_some(free)
+}
+
+#[cfg(not(windows))]
+fn volume_free_space(dir: &Path) -> Option<u64> {
+    // POSIX output: one line per volume, "Available" in 1024-byte blocks
+    let output = std::process::Command::new("df")
+        .arg("-Pk")
+        .arg(dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let available: u64 = stdout
+        .lines()
+        .nth(1)?
+        .split_whitespace()
+        .nth(3)?
+        .parse()
+        .ok()?;
+    Some(available * 1024)
+}
+
+/// Check `path` for `required_bytes` of new data plus `MIN_FREE_BYTES`
+pub fn check(purpose: &str, path: &Path, required_bytes: u64) -> VolumeCheck {
+    VolumeCheck {
+        purpose: purpose.to_string(),
+        path: path.to_string_lossy().to_string(),
+        free_bytes: free_space(path),
+        required_bytes: required_bytes.saturating_add(MIN_FREE_BYTES),
+        writable: elevation::is_writable(path),
+    }
+}
+
+fn space_error(check: &VolumeCheck) -> LauncherError {
+    LauncherError::new(
+        ErrorKind::Io,
+        format!(
+            "Not enough disk space for {}: {} free, {} needed",
+            check.purpose,
+            format_bytes(check.free_bytes.unwrap_or(0)),
+            format_bytes(check.required_bytes)
+        ),
+    )
+    .with_path(&check.path)
+    .with_hint("Free up space on that drive, then retry")
+}
+
+/// Refuse when the volume is too full, whether or not it is writable.
+/// For steps that can fall back to elevation when it isn't.
+pub fn ensure_space(purpose: &str, path: &Path, required_bytes: u64) -> Result<(), LauncherError> {
+    let check = check(purpose, path, required_bytes);
+    if check.has_space() {
+        Ok(())
+    } else {
+        Err(space_error(&check))
+    }
+}
+
+/// Refuse when `path` can't be written or its volume is too full
+pub fn ensure(purpose: &str, path: &Path, required_bytes: u64) -> Result<(), LauncherError> {
+    let check = check(purpose, path, required_bytes);
+    if !check.writable {
+        return Err(LauncherError::new(
+            ErrorKind::PermissionDenied,
+            format!("Can't write to {} for {}", check.path, purpose),
+        )
+        .with_path(path)
+        .with_hint("Check the folder's permissions, or move the project somewhere you own"));
+    }
+    if !check.has_space() {
+        return Err(space_error(&check));
+    }
+    Ok(())
+}
@@ -0,0 +1,200 @@
+//! Channel quotas: limits on what a channel's MCP server does for an agent
+//!
+//! `ChannelQuotas` reach the server as environment variables, and the server
+//! enforces them itself. With the launcher's stdio proxy in between (session
+//! recording or server pooling) the proxy also checks every `tools/call`
+//! before the server sees it, so a server too old to know the variables is
+//! held to them as well. Only the tools that write files themselves
+//! (`write_vs_graph`, `write_webroot_js`) have a size and a target folder;
+//! scene edits go through the Unity bridge and count against the call rate
+//! only.
+
+use std::collections::VecDeque;
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::config::ProjectChannel;
+use crate::error::LauncherError;
+
+/// MCP server variable with the largest file a tool may write, in bytes
+pub const MAX_WRITE_BYTES_VAR: &str = "BANTER_MCP_MAX_WRITE_BYTES";
+
+/// MCP server variable with the tool calls allowed in any `WINDOW`
+pub const MAX_CALLS_PER_MINUTE_VAR: &str = "BANTER_MCP_MAX_CALLS_PER_MINUTE";
+
+/// MCP server variable listing the folders tools may not write to, absolute
+/// and separated like `PATH`
+pub const DENIED_PATHS_VAR: &str = "BANTER_MCP_DENIED_PATHS";
+
+/// Span `max_calls_per_minute` counts over
+pub const WINDOW: Duration = Duration::from_secs(60);
+
+/// Drop `.` and resolve `..` without touching the disk, the way the server's
+/// `path.join` does
+fn normalize(path: &Path) -> PathBuf {
+    let mut normal = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normal.pop();
+            }
+            other => normal.push(other),
+        }
+    }
+    normal
+}
+
+/// The channel's denied folders as absolute paths
+pub fn denied_dirs(channel: &ProjectChannel) -> Vec<PathBuf> {
+    let project = Path::new(&channel.unity_project_path);
+    channel
+        .quotas
+        .denied_paths
+        .iter()
+        .map(|denied| normalize(&project.join(denied)))
+        .collect()
+}
+
+/// Quota variables for the channel's server; quotas that aren't set are left
+/// out
+pub fn env(channel: &ProjectChannel) -> Result<Vec<(&'static str, String)>, LauncherError> {
+    let quotas = &channel.quotas;
+    let mut env = Vec::new();
+    if let Some(bytes) = quotas.max_write_bytes {
+        env.push((MAX_WRITE_BYTES_VAR, bytes.to_string()));
+    }
+    if let Some(calls) = quotas.max_calls_per_minute {
+        env.push((MAX_CALLS_PER_MINUTE_VAR, calls.to_string()));
+    }
+    if !quotas.denied_paths.is_empty() {
+        let joined = std::env::join_paths(denied_dirs(channel)).map_err(|e| {
+            LauncherError::invalid(format!("Denied paths can't be listed together: {}", e))
+                .with_hint("Remove the path separator from the folder name")
+        })?;
+        env.push((DENIED_PATHS_VAR, joined.to_string_lossy().to_string()));
+    }
+    Ok(env)
+}
+
+/// A file a tool call would write
+#[derive(Debug, Clone, PartialEq)]
+pub struct WriteTarget {
+    pub path: PathBuf,
+    /// Size of the content the call carries
+    pub bytes: u64,
+}
+
+/// Where `tool` would write in `project` given `args`, for the tools that
+/// write files themselves
+pub fn write_target(project: &Path, tool: &str, args: &serde_json::Value) -> Option<WriteTarget> {
+    let arg = |name: &str| args.get(name).and_then(|v| v.as_str());
+    let assets = project.join("Assets");
+    let (path, content) = match tool {
+        "write_vs_graph" => {
+            let folder = arg("folder")
+                .filter(|f| !f.is_empty())
+                .unwrap_or("Scripts/VisualScripting");
+            let file = format!("{}.asset", arg("graphName")?);
+            (assets.join(folder).join(file), arg("graphJson")?)
+        }
+        "write_webroot_js" => {
+            let mut file = arg("filename")?.to_string();
+            if !file.ends_with(".js") && !file.ends_with(".html") {
+                file.push_str(".js");
+            }
+            (assets.join("WebRoot").join(file), arg("code")?)
+        }
+        _ => return None,
+    };
+    Some(WriteTarget {
+        path: normalize(&path),
+        bytes: content.len() as u64,
+    })
+}
+
+/// Whether `path` is one of the `denied` folders or inside one
+pub fn is_denied(denied: &[PathBuf], path: &Path) -> bool {
+    let key = |p: &Path| {
+        let text = normalize(p).to_string_lossy().replace('\\', "/");
+        let text = text.trim_end_matches('/').to_string();
+        // Unity projects mostly live on Windows and macOS, where case doesn't
+        // tell folders apart
+        if cfg!(any(windows, target_os = "macos")) {
+            text.to_lowercase()
+        } else {
+            text
+        }
+    };
+    let path = key(path);
+    denied.iter().map(|d| key(d)).any(|denied| {
+        path == denied
+            || path
+                .strip_prefix(&denied)
+                .is_some_and(|rest| rest.starts_with('/'))
+    })
+}
+
+/// Tool calls within the last `WINDOW`
+#[derive(Debug, Default)]
+pub struct CallRate {
+    calls: VecDeque<Instant>,
+}
+
+impl CallRate {
+    /// Count a call at `now` if it stays within `limit`; a refused call
+    /// isn't counted
+    pub fn allow(&mut self, limit: u32, now: Instant) -> bool {
+        while self
+            .calls
+            .front()
+            .is_some_and(|at| now.duration_since(*at) >= WINDOW)
+        {
+            self.calls.pop_front();
+        }
+        if self.calls.len() >= limit as usize {
+            return false;
+        }
+        self.calls.push_back(now);
+        true
+    }
+}
+
+/// Why a call of `tool` with `args` breaks the channel's quotas, `None` when
+/// it doesn't. Allowed calls count against the rate.
+pub fn check(
+    channel: &ProjectChannel,
+    rate: &mut CallRate,
+    tool: &str,
+    args: &serde_json::Value,
+    now: Instant,
+) -> Option<String> {
+    let quotas = &channel.quotas;
+    let project = Path::new(&channel.unity_project_path);
+    if let Some(target) = write_target(project, tool, args) {
+        let shown = target.path.strip_prefix(project).unwrap_or(&target.path);
+        if is_denied(&denied_dirs(channel), &target.path) {
+            return Some(format!(
+                "{} may not write to {}: the folder is denied for this channel",
+                tool,
+                shown.display()
+            ));
+        }
+        if let Some(max) = quotas.max_write_bytes.filter(|max| target.bytes > *max) {
+            return Some(format!(
+                "{} would write {} bytes to {}; this channel allows {}",
+                tool,
+                target.bytes,
+                shown.display(),
+                max
+            ));
+        }
+    }
+    match quotas.max_calls_per_minute {
+        Some(limit) if !rate.allow(limit, now) => Some(format!(
+            "This channel allows {} tool calls a minute; try again shortly",
+            limit
+        )),
+        _ => None,
+    }
+}
@@ -0,0 +1,438 @@
+//! Validation of names and paths received from the frontend
+//!
+//! Everything here rejects rather than repairs: a value that fails is reported
+//! back to the user instead of being silently rewritten.
+
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+
+use crate::i18n::{self, Params};
+
+pub const MAX_NAME_LEN: usize = 128;
+pub const MAX_ID_LEN: usize = 64;
+pub const MAX_PATH_LEN: usize = 1024;
+pub const MAX_URL_LEN: usize = 2048;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputError {
+    Empty {
+        field: String,
+    },
+    TooLong {
+        field: String,
+        max: usize,
+    },
+    ControlCharacters {
+        field: String,
+    },
+    InvalidCharacters {
+        field: String,
+    },
+    Traversal {
+        field: String,
+        path: String,
+    },
+    OutsideRoot {
+        field: String,
+        path: String,
+        root: String,
+    },
+    Duplicate {
+        field: String,
+        value: String,
+    },
+    Unsupported {
+        field: String,
+        value: String,
+    },
+    InvalidSchedule {
+        field: String,
+        value: String,
+        reason: String,
+    },
+}
+
+impl InputError {
+    /// Catalogue id of the message
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            InputError::Empty { .. } => "input.empty",
+            InputError::TooLong { .. } => "input.too_long",
+            InputError::ControlCharacters { .. } => "input.control_characters",
+            InputError::InvalidCharacters { .. } => "input.invalid_characters",
+            InputError::Traversal { .. } => "input.traversal",
+            InputError::OutsideRoot { .. } => "input.outside_root",
+            InputError::Duplicate { .. } => "input.duplicate",
+            InputError::Unsupported { .. } => "input.unsupported",
+            InputError::InvalidSchedule { .. } => "input.invalid_schedule",
+        }
+    }
+
+    pub fn params(&self) -> Params {
+        match self {
+            InputError::Empty { field }
+            | InputError::ControlCharacters { field }
+            | InputError::InvalidCharacters { field } => i18n::params(&[("field", field.clone())]),
+            InputError::TooLong { field, max } => {
+                i18n::params(&[("field", field.clone()), ("max", max.to_string())])
+            }
+            InputError::Traversal { field, path } => {
+                i18n::params(&[("field", field.clone()), ("path", path.clone())])
+            }
+            InputError::OutsideRoot { field, path, root } => i18n::params(&[
+                ("field", field.clone()),
+                ("path", path.clone()),
+                ("root", root.clone()),
+            ]),
+            InputError::Duplicate { field, value } | InputError::Unsupported { field, value } => {
+                i18n::params(&[("field", field.clone()), ("value", value.clone())])
+            }
+            InputError::InvalidSchedule {
+                field,
+                value,
+                reason,
+            } => i18n::params(&[
+                ("field", field.clone()),
+                ("value", value.clone()),
+                ("reason", reason.clone()),
+            ]),
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", i18n::text(self.message_id(), &self.params()))
+    }
+}
+
+/// Free text: bounded length, no control characters
+pub fn text<'a>(field: &str, value: &'a str, max: usize) -> Result<&'a str, InputError> {
+    if value.chars().count() > max {
+        return Err(InputError::TooLong {
+            field: field.to_string(),
+            max,
+        });
+    }
+    if value.chars().any(char::is_control) {
+        return Err(InputError::ControlCharacters {
+            field: field.to_string(),
+        });
+    }
+    Ok(value)
+}
+
+/// Display name of a channel, trimmed
+pub fn channel_name(name: &str) -> Result<String, InputError> {
+    let name = text("Channel name", name.trim(), MAX_NAME_LEN)?;
+    if name.is_empty() {
+        return Err(InputError::Empty {
+            field: "Channel name".to_string(),
+        });
+    }
+    Ok(name.to_string())
+}
+
+/// Identifier used as a config key and in URLs and file names
+pub fn identifier(field: &str, value: &str) -> Result<(), InputError> {
+    if value.is_empty() {
+        return Err(InputError::Empty {
+            field: field.to_string(),
+        });
+    }
+    text(field, value, MAX_ID_LEN)?;
+    if !value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(InputError::InvalidCharacters {
+            field: field.to_string(),
+        });
+    }
+    Ok(())
+}
+
+pub fn channel_id(id: &str) -> Result<(), InputError> {
+    identifier("Channel id", id)
+}
+
+pub fn platform(platform: &str) -> Result<(), InputError> {
+    identifier("Platform", platform)
+}
+
+/// A filesystem path without `..` components
+pub fn path(field: &str, value: &str) -> Result<PathBuf, InputError> {
+    if value.is_empty() {
+        return Err(InputError::Empty {
+            field: field.to_string(),
+        });
+    }
+    text(field, value, MAX_PATH_LEN)?;
+
+    let path = PathBuf::from(value);
+    if path.components().any(|c| c == Component::ParentDir) {
+        return Err(InputError::Traversal {
+            field: field.to_string(),
+            path: value.to_string(),
+        });
+    }
+    Ok(path)
+}
+
+/// A path that must live under `root`. Windows paths compare without case,
+/// as the filesystem does.
+pub fn path_within(field: &str, value: &str, root: &Path) -> Result<PathBuf, InputError> {
+    let path = path(field, value)?;
+    let normalize = |p: &Path| p.to_string_lossy().replace('\\', "/");
+    let fold = |s: &str| {
+        if cfg!(windows) {
+            s.to_lowercase()
+        } else {
+            s.to_string()
+        }
+    };
+    let root_str = normalize(root);
+    let path_str = normalize(&path);
+
+    let inside = fold(&path_str)
+        .strip_prefix(fold(&root_str).trim_end_matches('/'))
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'));
+    if !inside {
+        return Err(InputError::OutsideRoot {
+            field: field.to_string(),
+            path: value.to_string(),
+            root: root_str,
+        });
+    }
+    Ok(path)
+}
+
+fn optional_path(field: &str, value: &Option<String>) -> Result<(), InputError> {
+    match value {
+        Some(value) => path(field, value).map(|_| ()),
+        None => Ok(()),
+    }
+}
+
+fn optional_url(field: &str, value: &Option<String>) -> Result<(), InputError> {
+    match value {
+        Some(value) => text(field, value, MAX_URL_LEN).map(|_| ()),
+        None => Ok(()),
+    }
+}
+
+pub fn channel(channel: &crate::config::ProjectChannel) -> Result<(), InputError> {
+    channel_id(&channel.id)?;
+    channel_name(&channel.name)?;
+    let project = path("Unity project path", &channel.unity_project_path)?;
+    if let Some(scene) = &channel.scene_path {
+        path_within("Scene path", scene, &project)?;
+    }
+    if let Some(method) = &channel.bundle_build_method {
+        text("Bundle build method", method, MAX_NAME_LEN)?;
+    }
+    for p in &channel.platforms {
+        platform(p)?;
+    }
+    optional_url("Space URL", &channel.space_url)?;
+    optional_url("Local test URL", &channel.local_test_url)?;
+    for root in &channel.extra_allowed_roots {
+        path("Extra allowed root", root)?;
+    }
+    if let Some(cwd) = &channel.server_cwd {
+        path("Server working directory", cwd)?;
+    }
+    if let Some(dir) = &channel.server_data_dir {
+        path("Server data directory", dir)?;
+    }
+    for client in &channel.workspace_clients {
+        workspace_client(client)?;
+    }
+    quotas(&channel.quotas)
+}
+
+pub fn quotas(quotas: &crate::config::ChannelQuotas) -> Result<(), InputError> {
+    let zero = |field: &str| InputError::Unsupported {
+        field: field.to_string(),
+        value: "0".to_string(),
+    };
+    if quotas.max_write_bytes == Some(0) {
+        return Err(zero("max_write_bytes"));
+    }
+    if quotas.max_calls_per_minute == Some(0) {
+        return Err(zero("max_calls_per_minute"));
+    }
+    for denied in &quotas.denied_paths {
+        path("Denied path", denied)?;
+    }
+    Ok(())
+}
+
+/// One of `channels::WORKSPACE_CLIENTS`
+pub fn workspace_client(client: &str) -> Result<(), InputError> {
+    if crate::channels::WORKSPACE_CLIENTS
+        .iter()
+        .any(|(name, _)| *name == client)
+    {
+        Ok(())
+    } else {
+        Err(InputError::Unsupported {
+            field: "Workspace client".to_string(),
+            value: client.to_string(),
+        })
+    }
+}
+
+/// Check a whole config before it is saved
+pub fn config(config: &crate::config::LauncherConfig) -> Result<(), InputError> {
+    let mut seen = std::collections::HashSet::new();
+    for c in &config.channels {
+        self::channel(c)?;
+        if !seen.insert(c.id.as_str()) {
+            return Err(InputError::Duplicate {
+                field: "channel id".to_string(),
+                value: c.id.clone(),
+            });
+        }
+    }
+    if let Some(id) = &config.active_channel_id {
+        channel_id(id)?;
+    }
+
+    path("MCP server path", &config.mcp_server_path)?;
+    optional_path("Unity editor path", &config.unity_editor_path)?;
+    optional_path("Banter client path", &config.banter_client_path)?;
+    optional_path("on-activate hook", &config.hooks.on_activate)?;
+    optional_path("on-deactivate hook", &config.hooks.on_deactivate)?;
+    optional_path("on-server-crash hook", &config.hooks.on_server_crash)?;
+    optional_url("Upload endpoint", &config.banter_upload_endpoint)?;
+    optional_url("Auth endpoint", &config.banter_auth_endpoint)?;
+    if !i18n::is_supported(&config.locale) {
+        return Err(InputError::Unsupported {
+            field: "locale".to_string(),
+            value: config.locale.clone(),
+        });
+    }
+    if let Some(token) = &config.banter_upload_token {
+        text("Upload token", token, MAX_URL_LEN)?;
+    }
+    let proxy = &config.network.proxy;
+    if proxy != crate::config::PROXY_SYSTEM && proxy != crate::config::PROXY_NONE {
+        text("Proxy", proxy, MAX_URL_LEN)?;
+        let scheme = proxy.split_once("://").map(|(s, _)| s);
+        if !matches!(scheme, Some("http" | "https" | "socks5")) {
+            return Err(InputError::Unsupported {
+                field: "proxy".to_string(),
+                value: proxy.clone(),
+            });
+        }
+    }
+    for host in &config.network.no_proxy {
+        text("No-proxy host", host, MAX_NAME_LEN)?;
+    }
+    if config.network.max_kib_per_sec == Some(0) {
+        return Err(InputError::Unsupported {
+            field: "bandwidth cap".to_string(),
+            value: "0".to_string(),
+        });
+    }
+    if let Some(endpoint) = &config.telemetry.endpoint {
+        text("Telemetry endpoint", endpoint, MAX_URL_LEN)?;
+        if !endpoint.starts_with("https://") {
+            return Err(InputError::Unsupported {
+                field: "telemetry endpoint".to_string(),
+                value: endpoint.clone(),
+            });
+        }
+    }
+    let schedules = [
+        ("Server restart", &config.schedules.server_restart),
+        ("Update check", &config.schedules.update_check),
+        ("Idle shutdown", &config.schedules.idle_shutdown),
+    ];
+    for (field, policy) in schedules {
+        if let Some(policy) = policy {
+            crate::schedule::validate(field, &policy.cron, &policy.time_zone)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identifiers_are_ascii_words() {
+        assert!(identifier("Id", "lobby-2_b").is_ok());
+        assert!(matches!(
+            identifier("Id", ""),
+            Err(InputError::Empty { .. })
+        ));
+        assert!(matches!(
+            identifier("Id", "lobby/2"),
+            Err(InputError::InvalidCharacters { .. })
+        ));
+        assert!(matches!(
+            identifier("Id", &"a".repeat(MAX_ID_LEN + 1)),
+            Err(InputError::TooLong { .. })
+        ));
+    }
+
+    #[test]
+    fn text_rejects_control_characters() {
+        assert_eq!(text("Name", "Lobby", MAX_NAME_LEN), Ok("Lobby"));
+        assert!(matches!(
+            text("Name", "Lob\nby", MAX_NAME_LEN),
+            Err(InputError::ControlCharacters { .. })
+        ));
+    }
+
+    #[test]
+    fn paths_may_not_traverse() {
+        assert!(path("Path", "/projects/lobby").is_ok());
+        assert!(matches!(
+            path("Path", "/projects/../etc"),
+            Err(InputError::Traversal { .. })
+        ));
+    }
+
+    #[test]
+    fn path_within_compares_whole_components() {
+        let root = Path::new("C:\\projects\\lobby");
+        assert!(path_within("Scene", "C:/projects/lobby/Assets/Main.unity", root).is_ok());
+        assert!(path_within("Scene", "C:/projects/lobby", root).is_ok());
+        assert!(matches!(
+            path_within("Scene", "C:/projects/lobby2/Assets/Main.unity", root),
+            Err(InputError::OutsideRoot { .. })
+        ));
+    }
+
+    #[test]
+    fn path_within_ignores_case_on_windows() {
+        let root = Path::new("C:\\Projects\\Lobby");
+        let mixed = path_within("Scene", "c:/projects/LOBBY/Assets/Main.unity", root);
+        assert_eq!(mixed.is_ok(), cfg!(windows));
+        assert!(matches!(
+            path_within("Scene", "c:/projects/LOBBY2/Assets/Main.unity", root),
+            Err(InputError::OutsideRoot { .. })
+        ));
+    }
+
+    #[test]
+    fn config_rejects_duplicate_channel_ids() {
+        let channel = crate::config::ProjectChannel {
+            id: "lobby".to_string(),
+            name: "Lobby".to_string(),
+            unity_project_path: "/projects/lobby".to_string(),
+            ..Default::default()
+        };
+        let mut config = crate::config::default_config();
+        config.channels = vec![channel.clone(), channel];
+
+        assert!(matches!(
+            self::config(&config),
+            Err(InputError::Duplicate { .. })
+        ));
+    }
+}
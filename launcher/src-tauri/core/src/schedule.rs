@@ -0,0 +1,386 @@
+//! Cron-like schedules evaluated against wall-clock time
+//!
+//! Scheduled server restarts, update checks and idle shutdowns (the app's
+//! `scheduler` module) are configured with the usual five cron fields,
+//! `minute hour day-of-month month day-of-week`, each `*`, a value, a range
+//! `a-b`, a step `*/n` or `a-b/n`, or a comma list of those; months and
+//! weekdays also take English names (`jan`, `mon`). `@hourly`, `@daily`,
+//! `@weekly` and `@monthly` are shorthands. As in cron, a day matches when
+//! either day field does if both are restricted.
+//!
+//! Schedules run in a time zone: `local`, the machine's zone with its
+//! daylight saving rules as the OS knows them, or a fixed offset (`UTC`,
+//! `+02:00`). When the clocks go forward a time in the skipped hour runs at the
+//! moment of the jump, so a nightly 02:30 restart still happens that night;
+//! when they go back a time that occurs twice runs the first time only.
+
+use crate::sanitize::InputError;
+
+const MINUTE: i64 = 60;
+const DAY: i64 = 86_400;
+
+/// Days searched for a match; enough for Feb 29 on a given weekday
+const SEARCH_DAYS: i64 = 366 * 28;
+
+const MONTHS: &[&str] = &[
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const WEEKDAYS: &[&str] = &["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// Where a schedule's wall clock is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeZone {
+    /// The machine's zone, daylight saving included
+    Local,
+    /// Seconds east of UTC
+    Fixed(i32),
+}
+
+impl TimeZone {
+    pub fn parse(raw: &str) -> Result<TimeZone, String> {
+        let raw = raw.trim();
+        if raw.is_empty() || raw.eq_ignore_ascii_case("local") {
+            return Ok(TimeZone::Local);
+        }
+        if raw.eq_ignore_ascii_case("utc") || raw.eq_ignore_ascii_case("z") {
+            return Ok(TimeZone::Fixed(0));
+        }
+        let offset = raw
+            .strip_prefix("UTC")
+            .or_else(|| raw.strip_prefix("utc"))
+            .unwrap_or(raw);
+        let (sign, rest) = match offset.chars().next() {
+            Some('+') => (1, &offset[1..]),
+            Some('-') => (-1, &offset[1..]),
+            _ => return Err(format!("unknown time zone {}", raw)),
+        };
+        let (hours, minutes) = rest.split_once(':').unwrap_or((rest, "0"));
+        let hours: i32 = hours.parse().map_err(|_| format!("bad offset {}", raw))?;
+        let minutes: i32 = minutes.parse().map_err(|_| format!("bad offset {}", raw))?;
+        if hours > 14 || minutes > 59 {
+            return Err(format!("offset out of range: {}", raw));
+        }
+        Ok(TimeZone::Fixed(sign * (hours * 3600 + minutes * 60)))
+    }
+
+    /// Seconds east of UTC at the instant `utc`
+    pub fn offset_at(self, utc: i64) -> i32 {
+        match self {
+            TimeZone::Local => local_offset(utc),
+            TimeZone::Fixed(offset) => offset,
+        }
+    }
+}
+
+/// A parsed five-field schedule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    minutes: u64,
+    hours: u32,
+    /// Bit 1-31
+    days: u32,
+    /// Bit 1-12
+    months: u16,
+    /// Bit 0 (Sunday) - 6
+    weekdays: u8,
+    days_restricted: bool,
+    weekdays_restricted: bool,
+}
+
+fn parse_value(raw: &str, names: &[&str], base: u32) -> Option<u32> {
+    if let Ok(value) = raw.parse() {
+        return Some(value);
+    }
+    let lower = raw.to_ascii_lowercase();
+    names
+        .iter()
+        .position(|name| *name == lower)
+        .map(|i| i as u32 + base)
+}
+
+/// Bits `min..=max` set by one field
+fn parse_field(field: &str, name: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, String> {
+    let mut bits = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or_else(|| format!("bad step in {} field: {}", name, part))?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((a, b)) = range.split_once('-') {
+            let a = parse_value(a, names, min);
+            let b = parse_value(b, names, min);
+            match (a, b) {
+                (Some(a), Some(b)) => (a, b),
+                _ => return Err(format!("bad range in {} field: {}", name, part)),
+            }
+        } else {
+            let value = parse_value(range, names, min)
+                .ok_or_else(|| format!("bad value in {} field: {}", name, part))?;
+            // `5/15` means from 5 to the end, as in most crons
+            (value, if step > 1 { max } else { value })
+        };
+        if start < min || end > max || start > end {
+            return Err(format!(
+                "{} field must be within {}-{}: {}",
+                name, min, max, part
+            ));
+        }
+        for value in (start..=end).step_by(step as usize) {
+            bits |= 1 << value;
+        }
+    }
+    Ok(bits)
+}
+
+impl Schedule {
+    pub fn parse(expression: &str) -> Result<Schedule, String> {
+        let expression = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            other => other,
+        };
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields.as_slice() else {
+            return Err(format!(
+                "expected 5 fields (minute hour day month weekday), got {}",
+                fields.len()
+            ));
+        };
+        // Weekday 7 is Sunday too
+        let mut weekdays = parse_field(weekday, "weekday", 0, 7, WEEKDAYS)?;
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays | 1) & 0x7f;
+        }
+        Ok(Schedule {
+            minutes: parse_field(minute, "minute", 0, 59, &[])?,
+            hours: parse_field(hour, "hour", 0, 23, &[])? as u32,
+            days: parse_field(day, "day", 1, 31, &[])? as u32,
+            months: parse_field(month, "month", 1, 12, MONTHS)? as u16,
+            weekdays: weekdays as u8,
+            days_restricted: !day.starts_with('*'),
+            weekdays_restricted: !weekday.starts_with('*'),
+        })
+    }
+
+    fn matches_day(&self, month: u32, day: u32, weekday: u32) -> bool {
+        if self.months & (1 << month) == 0 {
+            return false;
+        }
+        let by_day = self.days & (1 << day) != 0;
+        let by_weekday = self.weekdays & (1 << weekday) != 0;
+        match (self.days_restricted, self.weekdays_restricted) {
+            (true, true) => by_day || by_weekday,
+            (true, false) => by_day,
+            (false, true) => by_weekday,
+            (false, false) => true,
+        }
+    }
+
+    /// First time after `after` (Unix seconds, exclusive) the schedule runs
+    /// in `zone`; `None` for schedules that never match, like `0 0 31 2 *`
+    pub fn next_after(&self, after: i64, zone: TimeZone) -> Option<i64> {
+        self.next_with(after, |utc| zone.offset_at(utc))
+    }
+
+    /// `next_after` with the zone given as its UTC offset at an instant
+    pub fn next_with(&self, after: i64, offset_at: impl Fn(i64) -> i32) -> Option<i64> {
+        let local_after = after + offset_at(after) as i64;
+        // A day early, in case the clocks went back since midnight
+        let first_day = local_after.div_euclid(DAY) - 1;
+        for day in first_day..first_day + SEARCH_DAYS {
+            let (_, month, date) = civil_from_days(day);
+            let weekday = (day + 4).rem_euclid(7) as u32;
+            if !self.matches_day(month, date, weekday) {
+                continue;
+            }
+            for hour in (0..24).filter(|h| self.hours & (1 << h) != 0) {
+                for minute in (0..60).filter(|m| self.minutes & (1 << m) != 0) {
+                    let wall = day * DAY + hour as i64 * 3600 + minute as i64 * MINUTE;
+                    let utc = to_utc(wall, &offset_at);
+                    if utc > after {
+                        return Some(utc);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// The instant a wall-clock time happens: the first of two when the clocks
+/// went back, the moment of the jump when it was skipped
+fn to_utc(wall: i64, offset_at: &impl Fn(i64) -> i32) -> i64 {
+    let early = offset_at(wall - DAY) as i64;
+    let late = offset_at(wall + DAY) as i64;
+    let valid = |utc: i64| utc + offset_at(utc) as i64 == wall;
+    let candidates = [wall - early, wall - late];
+    if let Some(utc) = candidates.iter().copied().filter(|u| valid(*u)).min() {
+        return utc;
+    }
+    // Skipped: find the transition, the first instant on the later offset
+    let (mut lo, mut hi) = (wall - early, wall - late);
+    if lo > hi {
+        std::mem::swap(&mut lo, &mut hi);
+    }
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if offset_at(mid) as i64 == late {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    hi
+}
+
+/// Year, month and day of a day count since 1970-01-01 (Howard Hinnant's
+/// algorithm)
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    (yoe + era * 400 + i64::from(month <= 2), month, day)
+}
+
+/// Day count since 1970-01-01 of a date
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// `YYYY-MM-DDTHH:MM+hh:mm` of an instant in a zone
+pub fn format(utc: i64, zone: TimeZone) -> String {
+    let offset = zone.offset_at(utc) as i64;
+    let wall = utc + offset;
+    let (year, month, day) = civil_from_days(wall.div_euclid(DAY));
+    let secs = wall.rem_euclid(DAY);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}{}{:02}:{:02}",
+        year,
+        month,
+        day,
+        secs / 3600,
+        secs % 3600 / 60,
+        if offset < 0 { '-' } else { '+' },
+        offset.abs() / 3600,
+        offset.abs() % 3600 / 60
+    )
+}
+
+/// Check a configured schedule and zone
+pub fn validate(field: &str, expression: &str, time_zone: &str) -> Result<(), InputError> {
+    let invalid = |reason: String, value: &str| InputError::InvalidSchedule {
+        field: field.to_string(),
+        value: value.to_string(),
+        reason,
+    };
+    Schedule::parse(expression).map_err(|r| invalid(r, expression))?;
+    TimeZone::parse(time_zone).map_err(|r| invalid(r, time_zone))?;
+    Ok(())
+}
+
+/// Seconds east of UTC of the machine's zone at `utc`
+#[cfg(unix)]
+pub fn local_offset(utc: i64) -> i32 {
+    use std::os::raw::{c_char, c_int, c_long};
+
+    // Filled in by the C library; only the offset is read
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct Tm {
+        tm_sec: c_int,
+        tm_min: c_int,
+        tm_hour: c_int,
+        tm_mday: c_int,
+        tm_mon: c_int,
+        tm_year: c_int,
+        tm_wday: c_int,
+        tm_yday: c_int,
+        tm_isdst: c_int,
+        tm_gmtoff: c_long,
+        tm_zone: *const c_char,
+    }
+    extern "C" {
+        fn tzset();
+        fn localtime_r(time: *const i64, result: *mut Tm) -> *mut Tm;
+    }
+
+    let mut tm = std::mem::MaybeUninit::<Tm>::zeroed();
+    // SAFETY: `localtime_r` fills `tm` and returns null on failure only
+    unsafe {
+        tzset();
+        if localtime_r(&utc, tm.as_mut_ptr()).is_null() {
+            return 0;
+        }
+        tm.assume_init().tm_gmtoff as i32
+    }
+}
+
+/// Seconds east of UTC of the machine's zone at `utc`
+#[cfg(windows)]
+pub fn local_offset(utc: i64) -> i32 {
+    #[allow(dead_code)]
+    #[repr(C)]
+    #[derive(Default)]
+    struct SystemTime {
+        year: u16,
+        month: u16,
+        day_of_week: u16,
+        day: u16,
+        hour: u16,
+        minute: u16,
+        second: u16,
+        milliseconds: u16,
+    }
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn SystemTimeToTzSpecificLocalTime(
+            zone: *const std::ffi::c_void,
+            utc: *const SystemTime,
+            local: *mut SystemTime,
+        ) -> i32;
+    }
+
+    let (year, month, day) = civil_from_days(utc.div_euclid(DAY));
+    let secs = utc.rem_euclid(DAY);
+    let utc_time = SystemTime {
+        year: year as u16,
+        month: month as u16,
+        day: day as u16,
+        hour: (secs / 3600) as u16,
+        minute: (secs % 3600 / 60) as u16,
+        second: (secs % 60) as u16,
+        ..SystemTime::default()
+    };
+    let mut local = SystemTime::default();
+    // SAFETY: both structs are valid SYSTEMTIMEs; null means the current zone
+    if unsafe { SystemTimeToTzSpecificLocalTime(std::ptr::null(), &utc_time, &mut local) } == 0 {
+        return 0;
+    }
+    let local_secs = days_from_civil(local.year as i64, local.month as u32, local.day as u32) * DAY
+        + local.hour as i64 * 3600
+        + local.minute as i64 * MINUTE
+        + local.second as i64;
+    (local_secs - utc) as i32
+}
@@ -0,0 +1,162 @@
+//! Which parts of the toolchain this machine has, and the commands that
+//! need them
+//!
+//! The launcher stays useful on a machine missing half the stack, e.g. for
+//! editing channel configs on a laptop without Unity. `Capability` names the
+//! optional pieces; the app's `stack` module detects them. Only the
+//! commands in `REQUIREMENTS` are refused while what they need is missing,
+//! and the refusal says what was looked for and how to fix it. Everything
+//! else, from adding channels to writing client configs, keeps working.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::error::{ErrorKind, LauncherError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum Capability {
+    /// `node` on PATH, to run the MCP server
+    Node,
+    /// A Unity editor, from the config or Unity Hub
+    Unity,
+    /// Claude Code installed for this user
+    ClaudeCode,
+    /// Banter's servers can be reached
+    Network,
+}
+
+impl Capability {
+    pub const ALL: [Capability; 4] = [
+        Capability::Node,
+        Capability::Unity,
+        Capability::ClaudeCode,
+        Capability::Network,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Capability::Node => "Node.js",
+            Capability::Unity => "a Unity editor",
+            Capability::ClaudeCode => "Claude Code",
+            Capability::Network => "a network connection",
+        }
+    }
+
+    fn hint(self) -> &'static str {
+        match self {
+            Capability::Node => "Install Node.js 18 or later and make sure `node` is on PATH",
+            Capability::Unity => {
+                "Install the project's editor version in Unity Hub, or set the Unity editor path in settings"
+            }
+            Capability::ClaudeCode => "Install Claude Code and run it once",
+            Capability::Network => "Check the internet connection and the proxy in settings",
+        }
+    }
+
+    fn error_kind(self) -> ErrorKind {
+        match self {
+            Capability::Unity => ErrorKind::Unity,
+            Capability::Network => ErrorKind::Network,
+            Capability::Node | Capability::ClaudeCode => ErrorKind::NotFound,
+        }
+    }
+}
+
+/// Commands that can't do anything without a capability. Builds, extension
+/// checks and scene lists go through an open editor when there is one, so
+/// only their batchmode fallback needs an installed editor; the app refuses
+/// that where it launches Unity, not here.
+pub const REQUIREMENTS: &[(&str, Capability)] = &[
+    ("get_server_capabilities", Capability::Node),
+    ("relaunch_editor", Capability::Unity),
+    ("import_from_claude_config", Capability::ClaudeCode),
+    ("upload_banter_bundle", Capability::Network),
+    ("start_login", Capability::Network),
+    ("send_telemetry", Capability::Network),
+];
+
+/// What `command` needs, if anything
+pub fn requirement(command: &str) -> Option<Capability> {
+    REQUIREMENTS
+        .iter()
+        .find(|(name, _)| *name == command)
+        .map(|(_, capability)| *capability)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Type)]
+pub struct CapabilityFlag {
+    pub capability: Capability,
+    pub available: bool,
+    /// The version or path found, or what was looked for
+    pub detail: String,
+}
+
+/// Payload of `get_capabilities`
+#[derive(Debug, Clone, PartialEq, Serialize, Type)]
+pub struct Capabilities {
+    pub node: bool,
+    pub unity: bool,
+    pub claude_code: bool,
+    pub online: bool,
+    /// One per `Capability`, with what was found
+    pub flags: Vec<CapabilityFlag>,
+    /// Commands refused until what they need is found
+    pub disabled_commands: Vec<String>,
+    /// Unix ms
+    pub checked_at: i64,
+}
+
+impl Capabilities {
+    /// A capability without a flag counts as there
+    pub fn new(flags: Vec<CapabilityFlag>, checked_at: i64) -> Self {
+        let has = |capability| {
+            flags
+                .iter()
+                .find(|flag| flag.capability == capability)
+                .is_none_or(|flag| flag.available)
+        };
+        Capabilities {
+            node: has(Capability::Node),
+            unity: has(Capability::Unity),
+            claude_code: has(Capability::ClaudeCode),
+            online: has(Capability::Network),
+            disabled_commands: REQUIREMENTS
+                .iter()
+                .filter(|(_, capability)| !has(*capability))
+                .map(|(name, _)| name.to_string())
+                .collect(),
+            flags,
+            checked_at,
+        }
+    }
+
+    pub fn flag(&self, capability: Capability) -> Option<&CapabilityFlag> {
+        self.flags.iter().find(|flag| flag.capability == capability)
+    }
+
+    /// Refuse `command` when what it needs is missing
+    pub fn check(&self, command: &str) -> Result<(), LauncherError> {
+        let Some(capability) = requirement(command) else {
+            return Ok(());
+        };
+        match self.flag(capability) {
+            Some(flag) if !flag.available => Err(missing(flag, command)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The refusal of `action` for a missing capability
+pub fn missing(flag: &CapabilityFlag, action: &str) -> LauncherError {
+    LauncherError::new(
+        flag.capability.error_kind(),
+        format!(
+            "{} needs {}, which this machine lacks: {}",
+            action,
+            flag.capability.label(),
+            flag.detail
+        ),
+    )
+    .with_hint(flag.capability.hint())
+}
@@ -0,0 +1,317 @@
+//! Where the launcher keeps the state it builds up
+//!
+//! Job history, recorded sessions, the journal and the scan, GUID and asset
+//! caches are documents in a `Store`: a JSON string under a collection and a key. The `json` backend
+//! keeps each one as `<collection>/<key>.json` in the launcher folder, the
+//! layout these files always had. The `sqlite` backend keeps them all in one
+//! table of `launcher.db`, which any SQLite client can query:
+//!
+//! ```sql
+//! SELECT key, json_extract(value, '$.info.status') FROM documents
+//! WHERE collection = 'jobs' ORDER BY updated_ms DESC;
+//! ```
+//!
+//! `storage_backend` in the config picks one; switching moves every document
+//! of `COLLECTIONS` over. The launcher config itself stays in
+//! `launcher-config.json`, since it says which backend to open and is edited
+//! and committed by hand, and so do the session claims in each project (see
+//! `sessions`), which other launchers read. Logs, backups, snapshots,
+//! session event logs and the files the journal saved stay files too: they
+//! are appended to, copied or opened as they are.
+//!
+//! The JSON backend writes its files directly rather than through a plan:
+//! the journal is kept here, and a plan journals what it writes.
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+
+use crate::bridge;
+use crate::config;
+use crate::error::LauncherError;
+use crate::write_guard;
+
+/// Collections moved when the backend changes
+pub const COLLECTIONS: &[&str] = &["jobs", "cache", "sessions", "journal"];
+
+pub const DATABASE_FILE: &str = "launcher.db";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    #[default]
+    Json,
+    Sqlite,
+}
+
+impl StorageBackend {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StorageBackend::Json => "json",
+            StorageBackend::Sqlite => "sqlite",
+        }
+    }
+}
+
+/// Documents by collection and key. Collections and keys are file-name safe
+/// (see `check_name`), whatever the backend.
+pub trait Store: Send + Sync {
+    fn backend(&self) -> StorageBackend;
+    fn get(&self, collection: &str, key: &str) -> Result<Option<String>, LauncherError>;
+    fn put(&self, collection: &str, key: &str, value: &str) -> Result<(), LauncherError>;
+    /// Deleting a missing document is not an error
+    fn delete(&self, collection: &str, key: &str) -> Result<(), LauncherError>;
+    /// Every document of a collection as `(key, value)`, in no set order
+    fn list(&self, collection: &str) -> Result<Vec<(String, String)>, LauncherError>;
+}
+
+fn check_name(kind: &str, name: &str) -> Result<(), LauncherError> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(LauncherError::invalid(format!(
+            "Not a storage {}: {}",
+            kind, name
+        )));
+    }
+    Ok(())
+}
+
+/// One file per document
+pub struct JsonFiles {
+    root: PathBuf,
+}
+
+impl JsonFiles {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        JsonFiles { root: root.into() }
+    }
+
+    fn path(&self, collection: &str, key: &str) -> Result<PathBuf, LauncherError> {
+        check_name("collection", collection)?;
+        check_name("key", key)?;
+        Ok(self.root.join(collection).join(format!("{}.json", key)))
+    }
+}
+
+impl Store for JsonFiles {
+    fn backend(&self) -> StorageBackend {
+        StorageBackend::Json
+    }
+
+    fn get(&self, collection: &str, key: &str) -> Result<Option<String>, LauncherError> {
+        let path = self.path(collection, key)?;
+        match fs::read_to_string(&path) {
+            Ok(content) => Ok(Some(content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(LauncherError::io("Failed to read", &path, e)),
+        }
+    }
+
+    fn put(&self, collection: &str, key: &str, value: &str) -> Result<(), LauncherError> {
+        let path = self.path(collection, key)?;
+        write_guard::check_write(&path)?;
+        // Synced before the rename, so the journal is on disk before the
+        // change it describes
+        let tmp = path.with_extension("json.tmp");
+        fs::create_dir_all(self.root.join(collection))
+            .and_then(|_| {
+                let mut file = File::create(&tmp)?;
+                file.write_all(value.as_bytes())?;
+                file.sync_all()
+            })
+            .and_then(|_| fs::rename(&tmp, &path))
+            .map_err(|e| LauncherError::io("Failed to write", &path, e))
+    }
+
+    fn delete(&self, collection: &str, key: &str) -> Result<(), LauncherError> {
+        let path = self.path(collection, key)?;
+        write_guard::check_write(&path)?;
+        match fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(LauncherError::io("Failed to delete", &path, e))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn list(&self, collection: &str) -> Result<Vec<(String, String)>, LauncherError> {
+        check_name("collection", collection)?;
+        Ok(fs::read_dir(self.root.join(collection))
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|item| item.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|path| {
+                let key = path.file_stem()?.to_string_lossy().to_string();
+                check_name("key", &key).ok()?;
+                Some((key, fs::read_to_string(&path).ok()?))
+            })
+            .collect())
+    }
+}
+
+/// Every document in one SQLite table
+pub struct Sqlite {
+    path: PathBuf,
+    connection: Mutex<Connection>,
+}
+
+fn sql_error(path: &Path, e: rusqlite::Error) -> LauncherError {
+    LauncherError::internal(format!("SQLite: {}", e)).with_path(path)
+}
+
+impl Sqlite {
+    pub fn open(path: &Path) -> Result<Self, LauncherError> {
+        write_guard::check_write(path)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| LauncherError::io("Failed to create directory", dir, e))?;
+        }
+        let connection = Connection::open(path).map_err(|e| sql_error(path, e))?;
+        connection
+            .execute_batch(
+                "PRAGMA journal_mode = WAL;
+                 CREATE TABLE IF NOT EXISTS documents (
+                     collection TEXT NOT NULL,
+                     key TEXT NOT NULL,
+                     value TEXT NOT NULL,
+                     updated_ms INTEGER NOT NULL,
+                     PRIMARY KEY (collection, key)
+                 );",
+            )
+            .map_err(|e| sql_error(path, e))?;
+        Ok(Sqlite {
+            path: path.to_path_buf(),
+            connection: Mutex::new(connection),
+        })
+    }
+
+    fn connection(&self) -> std::sync::MutexGuard<'_, Connection> {
+        self.connection.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Store for Sqlite {
+    fn backend(&self) -> StorageBackend {
+        StorageBackend::Sqlite
+    }
+
+    fn get(&self, collection: &str, key: &str) -> Result<Option<String>, LauncherError> {
+        self.connection()
+            .query_row(
+                "SELECT value FROM documents WHERE collection = ?1 AND key = ?2",
+                params![collection, key],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| sql_error(&self.path, e))
+    }
+
+    fn put(&self, collection: &str, key: &str, value: &str) -> Result<(), LauncherError> {
+        check_name("collection", collection)?;
+        check_name("key", key)?;
+        self.connection()
+            .execute(
+                "INSERT INTO documents (collection, key, value, updated_ms)
+                 VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT (collection, key)
+                 DO UPDATE SET value = excluded.value, updated_ms = excluded.updated_ms",
+                params![collection, key, value, bridge::now_ms()],
+            )
+            .map(|_| ())
+            .map_err(|e| sql_error(&self.path, e))
+    }
+
+    fn delete(&self, collection: &str, key: &str) -> Result<(), LauncherError> {
+        self.connection()
+            .execute(
+                "DELETE FROM documents WHERE collection = ?1 AND key = ?2",
+                params![collection, key],
+            )
+            .map(|_| ())
+            .map_err(|e| sql_error(&self.path, e))
+    }
+
+    fn list(&self, collection: &str) -> Result<Vec<(String, String)>, LauncherError> {
+        let connection = self.connection();
+        let mut statement = connection
+            .prepare("SELECT key, value FROM documents WHERE collection = ?1")
+            .map_err(|e| sql_error(&self.path, e))?;
+        let rows = statement
+            .query_map(params![collection], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| sql_error(&self.path, e))?;
+        rows.collect::<Result<_, _>>()
+            .map_err(|e| sql_error(&self.path, e))
+    }
+}
+
+/// Open `backend` on the files in `dir`
+pub fn open(backend: StorageBackend, dir: &Path) -> Result<Arc<dyn Store>, LauncherError> {
+    Ok(match backend {
+        StorageBackend::Json => Arc::new(JsonFiles::new(dir)),
+        StorageBackend::Sqlite => Arc::new(Sqlite::open(&dir.join(DATABASE_FILE))?),
+    })
+}
+
+/// Move every document of `COLLECTIONS` from one store to another; returns
+/// how many moved. Each is deleted from `from` only once `to` has it.
+pub fn migrate(from: &dyn Store, to: &dyn Store) -> Result<usize, LauncherError> {
+    let mut moved = 0;
+    for collection in COLLECTIONS {
+        for (key, value) in from.list(collection)? {
+            to.put(collection, &key, &value)?;
+            from.delete(collection, &key)?;
+            moved += 1;
+        }
+    }
+    Ok(moved)
+}
+
+static STORE: RwLock<Option<Arc<dyn Store>>> = RwLock::new(None);
+
+/// The store `storage_backend` picked; JSON files until `set_backend` runs
+pub fn store() -> Arc<dyn Store> {
+    match STORE.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        Some(store) => store.clone(),
+        None => Arc::new(JsonFiles::new(config::launcher_dir())),
+    }
+}
+
+/// Follow `storage_backend`, moving the documents over when it changed. A
+/// backend that can't be opened leaves the current one in use.
+pub fn set_backend(backend: StorageBackend) -> Result<(), LauncherError> {
+    let mut current = STORE.write().unwrap_or_else(|e| e.into_inner());
+    let previous = match current.as_ref() {
+        Some(store) if store.backend() == backend => return Ok(()),
+        Some(store) => store.clone(),
+        None => Arc::new(JsonFiles::new(config::launcher_dir())) as Arc<dyn Store>,
+    };
+    let next = open(backend, &config::launcher_dir())?;
+    if previous.backend() != backend {
+        let moved = migrate(previous.as_ref(), next.as_ref())?;
+        if moved > 0 {
+            eprintln!("Moved {} documents to {} storage", moved, backend.name());
+        }
+    }
+    *current = Some(next);
+    Ok(())
+}
+
+/// `set_backend` for a config change, which goes ahead either way
+pub fn follow(backend: StorageBackend) {
+    if let Err(e) = set_backend(backend) {
+        eprintln!(
+            "warning: staying on {} storage: {}",
+            store().backend().name(),
+            e
+        );
+    }
+}
@@ -0,0 +1,175 @@
+//! Opt-in, anonymous usage counts
+//!
+//! Nothing is recorded until `telemetry.enabled` is turned on, and nothing
+//! leaves the machine until the user sends it. While on, each command run
+//! counts once under its fixed label ("Build bundle", "Export session"), and
+//! each failure once under its error kind. Counts only: no arguments,
+//! messages, paths, channel or project names, and no id that would tell two
+//! reports from the same machine apart. They stay in `telemetry.json` in the
+//! launcher folder, where the user can read, export or clear them; the app's
+//! `telemetry` module does that and sends them on request.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::bridge;
+use crate::config;
+use crate::error::{ErrorKind, LauncherError};
+use crate::plan::FilePlan;
+
+/// Shown in the app next to the switch, so turning it on is an informed choice
+pub const RECORDED: &[&str] = &[
+    "How often each launcher feature is used, by its fixed name",
+    "How often each kind of error happens (not found, network, Unity, ...)",
+    "The launcher version and operating system, when a report is sent",
+];
+
+pub const NOT_RECORDED: &[&str] = &[
+    "Error messages, file paths, channel, project or scene names",
+    "Tokens, account names or anything the MCP server or agent does",
+    "An id for this machine or user",
+];
+
+/// In-memory counts reach the file at most this often
+const SAVE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Counts kept since `since_ms`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Type)]
+pub struct Usage {
+    /// Unix ms of the first count, 0 when there is none
+    pub since_ms: i64,
+    /// Runs by feature
+    pub features: BTreeMap<String, u64>,
+    /// Failures by error kind
+    pub errors: BTreeMap<String, u64>,
+}
+
+impl Usage {
+    pub fn is_empty(&self) -> bool {
+        self.features.is_empty() && self.errors.is_empty()
+    }
+
+    /// Count one run of `feature`, failed with `error` if it did
+    pub fn record(&mut self, feature: &str, error: Option<ErrorKind>, now_ms: i64) {
+        if self.is_empty() {
+            self.since_ms = now_ms;
+        }
+        *self.features.entry(feature.to_string()).or_default() += 1;
+        if let Some(kind) = error {
+            *self.errors.entry(category(kind).to_string()).or_default() += 1;
+        }
+    }
+
+    /// Add `other`'s counts to these
+    pub fn merge(&mut self, other: Usage) {
+        if other.is_empty() {
+            return;
+        }
+        if self.is_empty() || other.since_ms < self.since_ms {
+            self.since_ms = other.since_ms;
+        }
+        for (feature, count) in other.features {
+            *self.features.entry(feature).or_default() += count;
+        }
+        for (kind, count) in other.errors {
+            *self.errors.entry(kind).or_default() += count;
+        }
+    }
+}
+
+/// The error category reported for `kind`
+pub fn category(kind: ErrorKind) -> &'static str {
+    match kind {
+        ErrorKind::NotFound => "not_found",
+        ErrorKind::InvalidInput => "invalid_input",
+        ErrorKind::Parse => "parse",
+        ErrorKind::Io => "io",
+        ErrorKind::PermissionDenied => "permission_denied",
+        ErrorKind::Network => "network",
+        ErrorKind::Auth => "auth",
+        ErrorKind::Unity => "unity",
+        ErrorKind::Bridge => "bridge",
+        ErrorKind::Conflict => "conflict",
+        ErrorKind::Cancelled => "cancelled",
+        ErrorKind::Internal => "internal",
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Counts not in the file yet, and when the file was last written
+static PENDING: Mutex<Option<(Usage, Instant)>> = Mutex::new(None);
+
+pub fn usage_path() -> PathBuf {
+    config::launcher_dir().join("telemetry.json")
+}
+
+/// Follow `telemetry.enabled`; counts already kept stay until cleared
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+fn read_file() -> Usage {
+    fs::read_to_string(usage_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_file(usage: &Usage) -> Result<(), LauncherError> {
+    let content = serde_json::to_string_pretty(usage)
+        .map_err(|e| LauncherError::internal(format!("Failed to serialize usage: {}", e)))?;
+    FilePlan::new(false).write(&usage_path(), content)
+}
+
+/// Count a run of `feature` when telemetry is on. Best effort: a command
+/// never fails because its count couldn't be saved.
+pub fn record(feature: &str, error: Option<ErrorKind>) {
+    if !is_enabled() {
+        return;
+    }
+    let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+    let (usage, saved) = pending.get_or_insert_with(|| (Usage::default(), Instant::now()));
+    usage.record(feature, error, bridge::now_ms());
+    if saved.elapsed() >= SAVE_INTERVAL {
+        let mut all = read_file();
+        all.merge(std::mem::take(usage));
+        if let Err(e) = write_file(&all) {
+            eprintln!("warning: failed to save usage counts: {}", e);
+        }
+        *saved = Instant::now();
+    }
+}
+
+/// Everything counted so far, saved to the file first
+pub fn flush() -> Result<Usage, LauncherError> {
+    let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+    let mut all = read_file();
+    if let Some((usage, saved)) = pending.as_mut().filter(|(u, _)| !u.is_empty()) {
+        all.merge(std::mem::take(usage));
+        write_file(&all)?;
+        *saved = Instant::now();
+    }
+    Ok(all)
+}
+
+/// Forget every count
+pub fn clear() -> Result<(), LauncherError> {
+    let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+    *pending = None;
+    let path = usage_path();
+    if path.exists() {
+        FilePlan::new(false).delete(&path)?;
+    }
+    Ok(())
+}
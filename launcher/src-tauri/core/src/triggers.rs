@@ -0,0 +1,100 @@
+//! Command files dropped into a project's trigger folder
+//!
+//! With `file_triggers` on, any tool that can write a file can drive the
+//! launcher: a build script, a Unity menu item, a render farm job. Each
+//! channel's project gets a `.banter-mcp/commands/` folder, and a JSON file
+//! put there runs one command on that channel:
+//!
+//! ```json
+//! { "command": "activate", "take_over": false }
+//! { "command": "restart-server" }
+//! { "command": "snapshot", "reason": "Before nightly import" }
+//! ```
+//!
+//! Only `*.json` files are read, oldest first, so a writer can write
+//! `name.json.tmp` and rename it once complete. Every file is deleted once
+//! handled, whether its command worked or not; the app's `triggers` module
+//! runs them and reports the outcome.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::ProjectChannel;
+use crate::error::LauncherError;
+
+/// Inside the channel's project
+pub const TRIGGER_DIR: &str = ".banter-mcp/commands";
+
+/// Larger files aren't commands and are dropped unread
+pub const MAX_TRIGGER_BYTES: u64 = 16 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+#[serde(tag = "command", rename_all = "kebab-case")]
+pub enum TriggerCommand {
+    /// Make the channel active, as `activate_channel` does
+    Activate {
+        #[serde(default)]
+        take_over: bool,
+    },
+    /// Stop the channel's pooled MCP server, if one is running, and start it
+    /// again
+    RestartServer,
+    /// Snapshot the channel's scenes
+    Snapshot {
+        #[serde(default)]
+        reason: Option<String>,
+    },
+}
+
+impl TriggerCommand {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TriggerCommand::Activate { .. } => "activate",
+            TriggerCommand::RestartServer => "restart-server",
+            TriggerCommand::Snapshot { .. } => "snapshot",
+        }
+    }
+}
+
+pub fn trigger_dir(channel: &ProjectChannel) -> PathBuf {
+    Path::new(&channel.unity_project_path).join(TRIGGER_DIR)
+}
+
+/// Command files waiting in `dir`, oldest first
+pub fn pending(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<(std::time::SystemTime, PathBuf)> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|item| item.file_type().is_ok_and(|t| t.is_file()))
+        .map(|item| item.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| Some((path.metadata().ok()?.modified().ok()?, path)))
+        .collect();
+    files.sort();
+    files.into_iter().map(|(_, path)| path).collect()
+}
+
+/// The command in a trigger file
+pub fn read(path: &Path) -> Result<TriggerCommand, LauncherError> {
+    let size = path
+        .metadata()
+        .map_err(|e| LauncherError::io("Failed to read trigger", path, e))?
+        .len();
+    if size > MAX_TRIGGER_BYTES {
+        return Err(LauncherError::invalid(format!(
+            "Trigger file is {} bytes; the limit is {}",
+            size, MAX_TRIGGER_BYTES
+        ))
+        .with_path(path));
+    }
+    let content = fs::read_to_string(path)
+        .map_err(|e| LauncherError::io("Failed to read trigger", path, e))?;
+    serde_json::from_str(&content).map_err(|e| {
+        LauncherError::parse(format!("Not a trigger command: {}", e))
+            .with_path(path)
+            .with_hint("Expected {\"command\": \"activate\" | \"restart-server\" | \"snapshot\"}")
+    })
+}
@@ -0,0 +1,167 @@
+//! Keeping launchers of different OS users on one machine apart
+//!
+//! Everything a launcher keeps lives in the user's own config folder, but
+//! ports are machine-wide. Default ports are therefore offset by a slot
+//! derived from the user name, so two artists sharing a workstation don't
+//! compete for the same preview server or control API port. The offset keeps
+//! parity, so one user's preview port never lands on another's control port.
+//!
+//! Running launchers also announce themselves in a machine-wide instance
+//! folder, one file per user, so a launcher can tell when another user's is
+//! running and which ports it holds.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use crate::bridge;
+use crate::error::LauncherError;
+use crate::plan::FilePlan;
+
+/// Distinct per-user port offsets; ports move by up to `2 * (SLOTS - 1)`
+pub const SLOTS: u16 = 500;
+
+/// An instance file not renewed for this long belongs to a launcher that is gone
+pub const STALE_AFTER_MS: i64 = 3 * 60 * 1000;
+
+/// The OS user running the launcher
+pub fn name() -> String {
+    std::env::var("USERNAME")
+        .or_else(|_| std::env::var("USER"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+pub fn host() -> String {
+    std::env::var("COMPUTERNAME")
+        .or_else(|_| std::env::var("HOSTNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// `user@host`
+pub fn owner() -> String {
+    format!("{}@{}", name(), host())
+}
+
+/// Stable slot for a user name (FNV-1a, case-insensitive since Windows user
+/// names are)
+pub fn slot_for(user: &str) -> u16 {
+    let hash = user.to_lowercase().bytes().fold(0x811c_9dc5u32, |hash, b| {
+        (hash ^ b as u32).wrapping_mul(0x0100_0193)
+    });
+    (hash % SLOTS as u32) as u16
+}
+
+/// `base` moved to the current user's slot
+pub fn port(base: u16) -> u16 {
+    port_for(base, &name())
+}
+
+pub fn port_for(base: u16, user: &str) -> u16 {
+    base.saturating_add(2 * slot_for(user))
+}
+
+/// A running launcher, as announced in the instance folder
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Instance {
+    pub user: String,
+    pub host: String,
+    pub pid: u32,
+    pub launcher_dir: String,
+    /// Ports the launcher is listening on
+    pub ports: Vec<u16>,
+    /// Unix ms
+    pub started_ms: i64,
+    /// Last renewal, Unix ms
+    pub updated_ms: i64,
+}
+
+impl Instance {
+    pub fn is_stale(&self) -> bool {
+        bridge::now_ms() - self.updated_ms > STALE_AFTER_MS
+    }
+}
+
+/// Set by `set_registry_dir`; `None` means the machine-wide default
+static REGISTRY_DIR: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Announce instances in `dir` instead of the machine-wide folder. Tests
+/// point this at a temporary directory.
+pub fn set_registry_dir(dir: impl Into<PathBuf>) {
+    *REGISTRY_DIR.write().unwrap_or_else(|e| e.into_inner()) = Some(dir.into());
+}
+
+/// Machine-wide folder every user can write to
+pub fn registry_dir() -> PathBuf {
+    if let Some(dir) = REGISTRY_DIR
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+    {
+        return dir;
+    }
+    let shared = if cfg!(windows) {
+        std::env::var_os("ProgramData")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("C:/ProgramData"))
+    } else if cfg!(target_os = "macos") {
+        PathBuf::from("/Users/Shared")
+    } else {
+        PathBuf::from("/var/tmp")
+    };
+    shared.join("banter-mcp").join("instances")
+}
+
+/// One file per user; user names are reduced to characters safe in a file name
+pub fn instance_path(user: &str) -> PathBuf {
+    let safe: String = user
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    registry_dir().join(format!("{}.json", safe))
+}
+
+/// Write or renew the current user's instance file
+pub fn announce(instance: &Instance) -> Result<(), LauncherError> {
+    let path = instance_path(&instance.user);
+    let content = serde_json::to_string_pretty(instance)
+        .map_err(|e| LauncherError::internal(format!("Failed to serialize instance: {}", e)))?;
+    let mut plan = FilePlan::new(false);
+    plan.create_dir(&registry_dir())?;
+    plan.write(&path, content)
+}
+
+/// Remove the current user's instance file, if it is this process's
+pub fn withdraw(user: &str) -> Result<(), LauncherError> {
+    let path = instance_path(user);
+    match read_instance(&path) {
+        Some(instance) if instance.pid == std::process::id() => FilePlan::new(false).delete(&path),
+        _ => Ok(()),
+    }
+}
+
+fn read_instance(path: &Path) -> Option<Instance> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+/// Live launchers of other users on this machine
+pub fn other_instances(user: &str) -> Vec<Instance> {
+    let Ok(entries) = fs::read_dir(registry_dir()) else {
+        return Vec::new();
+    };
+    let mut others: Vec<Instance> = entries
+        .filter_map(Result::ok)
+        .filter(|e| e.path().extension().is_some_and(|x| x == "json"))
+        .filter_map(|e| read_instance(&e.path()))
+        .filter(|i| !i.user.eq_ignore_ascii_case(user) && !i.is_stale())
+        .collect();
+    others.sort_by(|a, b| a.user.cmp(&b.user));
+    others
+}
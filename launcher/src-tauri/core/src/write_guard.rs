@@ -0,0 +1,137 @@
+//! Allowlist for backend file writes
+//!
+//! Writes are only allowed into the launcher's own directory, registered channel
+//! projects, the client config files the launcher manages and the machine-wide
+//! instance folder (`user::registry_dir`). Anything else is
+//! refused and recorded, so a path bug can't overwrite arbitrary user files.
+
+use serde::Serialize;
+use specta::Type;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+
+use crate::error::{ErrorKind, LauncherError};
+use crate::i18n;
+
+/// Violations kept for `get_write_violations`
+const MAX_VIOLATIONS: usize = 100;
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct WriteViolation {
+    pub path: String,
+    /// Unix ms
+    pub at: i64,
+}
+
+static VIOLATIONS: Mutex<Vec<WriteViolation>> = Mutex::new(Vec::new());
+
+/// Resolve symlinks and `.` through the longest existing ancestor, so paths
+/// that don't exist yet compare the same way as ones that do
+pub fn normalize(path: &Path) -> PathBuf {
+    let mut existing = path;
+    let mut rest = Vec::new();
+    while !existing.exists() {
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                rest.push(name.to_os_string());
+                existing = parent;
+            }
+            _ => break,
+        }
+    }
+
+    let mut normalized = existing
+        .canonicalize()
+        .unwrap_or_else(|_| existing.to_path_buf());
+    for name in rest.into_iter().rev() {
+        normalized.push(name);
+    }
+    normalized
+}
+
+/// Normalized allowed roots; refreshed from `AppState` whenever the config changes
+static ROOTS: RwLock<Vec<PathBuf>> = RwLock::new(Vec::new());
+
+/// Folders the launcher created itself this session (see `allow_new_dir`)
+static CREATED: RwLock<Vec<PathBuf>> = RwLock::new(Vec::new());
+
+/// Recompute the allowed roots for a config
+pub fn set_roots(config: &crate::config::LauncherConfig) {
+    let mut roots = vec![
+        crate::config::launcher_dir(),
+        crate::client_config::get_claude_config_path(),
+        crate::user::registry_dir(),
+    ];
+    roots.extend(
+        config
+            .channels
+            .iter()
+            .map(|c| PathBuf::from(&c.unity_project_path)),
+    );
+    let roots = roots.iter().map(|r| normalize(r)).collect();
+    *ROOTS.write().unwrap_or_else(|e| e.into_inner()) = roots;
+}
+
+/// Refuse writes outside the allowlist
+pub fn check_write(path: &Path) -> Result<(), LauncherError> {
+    let traverses = path.components().any(|c| c == Component::ParentDir);
+    let target = normalize(path);
+
+    let allowed = ROOTS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .chain(CREATED.read().unwrap_or_else(|e| e.into_inner()).iter())
+        .any(|root| target.starts_with(root));
+    if !traverses && allowed {
+        return Ok(());
+    }
+
+    eprintln!(
+        "Blocked write outside allowed locations: {}",
+        path.display()
+    );
+    let mut violations = VIOLATIONS.lock().unwrap_or_else(|e| e.into_inner());
+    if violations.len() >= MAX_VIOLATIONS {
+        violations.remove(0);
+    }
+    violations.push(WriteViolation {
+        path: path.to_string_lossy().to_string(),
+        at: crate::bridge::now_ms(),
+    });
+
+    Err(LauncherError::localized(
+        ErrorKind::PermissionDenied,
+        "error.write_refused",
+        i18n::params(&[("path", path.display().to_string())]),
+    )
+    .with_path(path)
+    .with_localized_hint("hint.write_refused"))
+}
+
+/// Allow writes below `path` for the rest of the session, provided it doesn't
+/// exist yet or is an empty folder: nothing of the user's can be overwritten
+/// there. Used when the launcher creates a folder outside the usual roots.
+pub fn allow_new_dir(path: &Path) -> Result<(), LauncherError> {
+    let empty = match path.read_dir() {
+        Ok(mut entries) => entries.next().is_none(),
+        Err(_) => !path.exists(),
+    };
+    if !empty {
+        return Err(LauncherError::new(
+            ErrorKind::Conflict,
+            format!("Folder is not empty: {}", path.display()),
+        )
+        .with_path(path)
+        .with_hint("Choose a new or empty folder"));
+    }
+
+    let mut created = CREATED.write().unwrap_or_else(|e| e.into_inner());
+    created.push(normalize(path));
+    Ok(())
+}
+
+/// Writes refused since the launcher started, oldest first
+pub fn violations() -> Vec<WriteViolation> {
+    VIOLATIONS.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
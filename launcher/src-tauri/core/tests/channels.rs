@@ -0,0 +1,224 @@
+mod common;
+
+use std::path::{Path, PathBuf};
+
+use launcher_core::channels;
+use launcher_core::config::ProjectChannel;
+use launcher_core::error::ErrorKind;
+use launcher_core::sanitize;
+
+#[test]
+fn channel_project_is_the_folder_above_assets() {
+    let dir = common::temp_dir();
+    let scene = common::scene(dir.path(), "Lobby", "Main");
+
+    let channel = channels::add_channel("  Lobby  ".to_string(), scene.clone()).unwrap();
+
+    assert_eq!(channel.name, "Lobby");
+    assert_eq!(
+        Path::new(&channel.unity_project_path),
+        dir.path().join("Lobby")
+    );
+    assert_eq!(channel.scene_path.as_deref(), Some(scene.as_str()));
+    assert!(channel.enabled);
+    assert!(!channel.id.is_empty());
+}
+
+#[test]
+fn each_channel_gets_its_own_id() {
+    let dir = common::temp_dir();
+    let scene = common::scene(dir.path(), "Lobby", "Main");
+
+    let first = channels::add_channel("A".to_string(), scene.clone()).unwrap();
+    let second = channels::add_channel("B".to_string(), scene).unwrap();
+
+    assert_ne!(first.id, second.id);
+}
+
+#[test]
+fn nearest_assets_folder_wins() {
+    let scene = Path::new("/work/Outer/Assets/Inner/Assets/Scenes/Main.unity");
+
+    assert_eq!(
+        channels::project_root_for_scene(scene).as_deref(),
+        Some(Path::new("/work/Outer/Assets/Inner"))
+    );
+    assert_eq!(
+        channels::project_root_for_scene(Path::new("/work/Main.unity")),
+        None
+    );
+}
+
+#[test]
+fn missing_scene_is_not_found() {
+    let dir = common::temp_dir();
+    let scene = dir.path().join("Lobby/Assets/Missing.unity");
+
+    let error = channels::add_channel("Lobby".to_string(), scene.to_string_lossy().to_string())
+        .unwrap_err();
+
+    assert_eq!(error.kind, ErrorKind::NotFound);
+}
+
+#[test]
+fn scene_must_be_a_unity_file_inside_assets() {
+    let dir = common::temp_dir();
+    let prefab = dir.path().join("Lobby/Assets/Floor.prefab");
+    common::write(&prefab, "");
+    let loose = dir.path().join("Loose/Main.unity");
+    common::write(&loose, "");
+
+    for path in [&prefab, &loose] {
+        let error = channels::add_channel("Lobby".to_string(), path.to_string_lossy().to_string())
+            .unwrap_err();
+        assert_eq!(error.kind, ErrorKind::InvalidInput, "{}", path.display());
+    }
+}
+
+#[test]
+fn channel_name_is_required() {
+    let dir = common::temp_dir();
+    let scene = common::scene(dir.path(), "Lobby", "Main");
+
+    let error = channels::add_channel("   ".to_string(), scene).unwrap_err();
+
+    assert_eq!(error.kind, ErrorKind::InvalidInput);
+    assert_eq!(error.ids.message_id.as_deref(), Some("input.empty"));
+}
+
+#[test]
+fn validate_unity_scene_checks_file_type_and_location() {
+    let dir = common::temp_dir();
+    let scene = common::scene(dir.path(), "Lobby", "Main");
+    let loose = dir.path().join("Main.unity");
+    common::write(&loose, "");
+
+    assert!(channels::validate_unity_scene(scene).unwrap());
+    assert!(!channels::validate_unity_scene(loose.to_string_lossy().to_string()).unwrap());
+    assert!(!channels::validate_unity_scene(
+        dir.path()
+            .join("Lobby/Assets/Gone.unity")
+            .to_string_lossy()
+            .to_string()
+    )
+    .unwrap());
+    assert!(!channels::validate_unity_scene("../Assets/Main.unity".to_string()).unwrap());
+}
+
+#[test]
+fn allowed_roots_start_with_the_project() {
+    let channel = ProjectChannel {
+        id: "lobby".to_string(),
+        name: "Lobby".to_string(),
+        unity_project_path: "/projects/lobby".to_string(),
+        extra_allowed_roots: vec![
+            "/shared/assets".to_string(),
+            "/projects/lobby".to_string(),
+            "/shared/assets".to_string(),
+        ],
+        ..Default::default()
+    };
+
+    assert_eq!(
+        channels::allowed_roots(&channel),
+        vec![
+            PathBuf::from("/projects/lobby"),
+            PathBuf::from("/shared/assets")
+        ]
+    );
+    let value = channels::allowed_roots_value(&channel).unwrap();
+    assert_eq!(
+        std::env::split_paths(&value).collect::<Vec<_>>(),
+        channels::allowed_roots(&channel)
+    );
+}
+
+#[test]
+fn extra_roots_may_not_traverse() {
+    let channel = ProjectChannel {
+        id: "lobby".to_string(),
+        name: "Lobby".to_string(),
+        unity_project_path: "/projects/lobby".to_string(),
+        extra_allowed_roots: vec!["/projects/lobby/../secrets".to_string()],
+        ..Default::default()
+    };
+
+    assert!(sanitize::channel(&channel).is_err());
+}
+
+#[test]
+fn server_dirs_default_to_the_project() {
+    let dir = common::temp_dir();
+    let project = dir.path().join("Lobby");
+    std::fs::create_dir_all(project.join("Server")).unwrap();
+    let mut channel = ProjectChannel {
+        id: "lobby".to_string(),
+        name: "Lobby".to_string(),
+        unity_project_path: project.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+
+    assert_eq!(channels::server_cwd(&channel).unwrap(), project);
+    assert_eq!(
+        channels::server_data_dir(&channel),
+        project.join(channels::DEFAULT_DATA_DIR)
+    );
+
+    let shared = dir.path().join("cache");
+    channel.server_cwd = Some("Server".to_string());
+    channel.server_data_dir = Some(shared.to_string_lossy().to_string());
+    assert_eq!(
+        channels::server_cwd(&channel).unwrap(),
+        project.join("Server")
+    );
+    assert_eq!(channels::server_data_dir(&channel), shared);
+}
+
+#[test]
+fn missing_server_cwd_is_not_found() {
+    let dir = common::temp_dir();
+    let channel = ProjectChannel {
+        id: "lobby".to_string(),
+        name: "Lobby".to_string(),
+        unity_project_path: dir.path().to_string_lossy().to_string(),
+        server_cwd: Some("Gone".to_string()),
+        ..Default::default()
+    };
+
+    let error = channels::server_cwd(&channel).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::NotFound);
+    assert!(sanitize::channel(&ProjectChannel {
+        server_data_dir: Some("../elsewhere".to_string()),
+        ..channel
+    })
+    .is_err());
+}
+
+#[test]
+fn workspace_servers_share_a_lock_but_not_their_data() {
+    let mut channel = ProjectChannel {
+        id: "lobby".to_string(),
+        name: "Lobby".to_string(),
+        unity_project_path: "/projects/lobby".to_string(),
+        ..Default::default()
+    };
+    assert!(!channels::is_workspace(&channel));
+
+    channel.workspace_clients = vec!["cursor".to_string()];
+    assert!(channels::is_workspace(&channel));
+    assert!(sanitize::channel(&channel).is_ok());
+
+    let data = channels::server_data_dir(&channel);
+    assert_eq!(channels::write_lock_path(&channel), data.join("write.lock"));
+    assert_ne!(
+        channels::instance_data_dir(&channel, channels::CLAUDE_INSTANCE),
+        channels::instance_data_dir(&channel, "cursor")
+    );
+    assert_eq!(
+        channels::workspace_client_config(&channel, "cursor"),
+        Some(Path::new("/projects/lobby").join(".cursor/mcp.json"))
+    );
+
+    channel.workspace_clients = vec!["notepad".to_string()];
+    assert!(sanitize::channel(&channel).is_err());
+}
@@ -0,0 +1,254 @@
+mod common;
+
+use std::fs;
+
+use launcher_core::backup::BackupRetention;
+use launcher_core::client_config;
+use launcher_core::error::ErrorKind;
+use launcher_core::plan::FilePlan;
+
+fn env(project: &str) -> serde_json::Value {
+    serde_json::json!({ "UNITY_PROJECT_PATH": project })
+}
+
+#[test]
+fn missing_file_reads_as_empty() {
+    let dir = common::temp_dir();
+    let config =
+        client_config::get_claude_mcp_config_from(&dir.path().join(".claude.json")).unwrap();
+
+    assert_eq!(config, serde_json::json!({}));
+    assert_eq!(client_config::configured_project(&config), None);
+}
+
+#[test]
+fn update_adds_the_banter_entry_and_keeps_the_rest() {
+    let dir = common::temp_dir();
+    let path = dir.path().join(".claude.json");
+    common::write(
+        &path,
+        r#"{ "theme": "dark", "mcpServers": { "other": { "command": "other-server" } } }"#,
+    );
+
+    let mut plan = FilePlan::new(false);
+    client_config::plan_claude_mcp_update(
+        &mut plan,
+        &path,
+        "/srv/banter/index.js",
+        "/projects/lobby",
+        env("/projects/lobby"),
+        &BackupRetention::default(),
+    )
+    .unwrap();
+    let config = common::read_json(&path);
+
+    assert_eq!(config["theme"], "dark");
+    assert_eq!(config["mcpServers"]["other"]["command"], "other-server");
+    assert_eq!(config["mcpServers"]["banter"]["command"], "node");
+    assert_eq!(
+        config["mcpServers"]["banter"]["args"],
+        serde_json::json!(["/srv/banter/index.js"])
+    );
+    assert_eq!(config["mcpServers"]["banter"]["cwd"], "/projects/lobby");
+    assert_eq!(
+        client_config::configured_project(&config).as_deref(),
+        Some("/projects/lobby")
+    );
+}
+
+#[test]
+fn update_creates_a_missing_file() {
+    let dir = common::temp_dir();
+    let path = dir.path().join(".claude.json");
+
+    client_config::plan_claude_mcp_update(
+        &mut FilePlan::new(false),
+        &path,
+        "/srv/banter/index.js",
+        "/projects/lobby",
+        env("/projects/lobby"),
+        &BackupRetention::default(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        client_config::configured_project(&common::read_json(&path)).as_deref(),
+        Some("/projects/lobby")
+    );
+}
+
+#[test]
+fn update_with_a_proxy_command_keeps_the_project() {
+    let dir = common::temp_dir();
+    let path = dir.path().join(".claude.json");
+
+    client_config::plan_claude_mcp_update_with(
+        &mut FilePlan::new(false),
+        &path,
+        "/opt/banter-launcher",
+        &[
+            "--mcp-proxy".to_string(),
+            "lobby".to_string(),
+            "/srv/banter/index.js".to_string(),
+        ],
+        "/projects/lobby",
+        env("/projects/lobby"),
+        &BackupRetention::default(),
+    )
+    .unwrap();
+    let config = common::read_json(&path);
+
+    assert_eq!(
+        config["mcpServers"]["banter"]["command"],
+        "/opt/banter-launcher"
+    );
+    assert_eq!(
+        config["mcpServers"]["banter"]["args"],
+        serde_json::json!(["--mcp-proxy", "lobby", "/srv/banter/index.js"])
+    );
+    assert_eq!(
+        client_config::configured_project(&config).as_deref(),
+        Some("/projects/lobby")
+    );
+}
+
+#[test]
+fn update_replaces_an_unreadable_file() {
+    let dir = common::temp_dir();
+    let path = dir.path().join(".claude.json");
+    common::write(&path, "{ not json");
+
+    client_config::plan_claude_mcp_update(
+        &mut FilePlan::new(false),
+        &path,
+        "/srv/banter/index.js",
+        "/projects/lobby",
+        env("/projects/lobby"),
+        &BackupRetention::default(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        common::read_json(&path)["mcpServers"]["banter"]["command"],
+        "node"
+    );
+}
+
+#[test]
+fn dry_run_update_leaves_the_file_alone() {
+    let dir = common::temp_dir();
+    let path = dir.path().join(".claude.json");
+    common::write(&path, r#"{ "mcpServers": {} }"#);
+
+    let mut plan = FilePlan::new(true);
+    client_config::plan_claude_mcp_update(
+        &mut plan,
+        &path,
+        "/srv/banter/index.js",
+        "/projects/lobby",
+        env("/projects/lobby"),
+        &BackupRetention::default(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        r#"{ "mcpServers": {} }"#
+    );
+    assert!(!plan.operations.is_empty());
+}
+
+#[test]
+fn removal_keeps_other_servers() {
+    let dir = common::temp_dir();
+    let path = dir.path().join(".claude.json");
+    common::write(
+        &path,
+        r#"{ "mcpServers": { "banter": { "command": "node" }, "other": { "command": "other-server" } } }"#,
+    );
+
+    client_config::plan_claude_mcp_removal_from(
+        &mut FilePlan::new(false),
+        &path,
+        &BackupRetention::default(),
+    )
+    .unwrap();
+    let config = common::read_json(&path);
+
+    assert!(config["mcpServers"].get("banter").is_none());
+    assert_eq!(config["mcpServers"]["other"]["command"], "other-server");
+}
+
+#[test]
+fn removal_without_a_file_does_nothing() {
+    let dir = common::temp_dir();
+    let path = dir.path().join(".claude.json");
+
+    let mut plan = FilePlan::new(false);
+    client_config::plan_claude_mcp_removal_from(&mut plan, &path, &BackupRetention::default())
+        .unwrap();
+
+    assert!(!path.exists());
+    assert!(plan.operations.is_empty());
+}
+
+#[test]
+fn removal_refuses_an_unreadable_file() {
+    let dir = common::temp_dir();
+    let path = dir.path().join(".claude.json");
+    common::write(&path, "{ not json");
+
+    let error = client_config::plan_claude_mcp_removal_from(
+        &mut FilePlan::new(false),
+        &path,
+        &BackupRetention::default(),
+    )
+    .unwrap_err();
+
+    assert_eq!(error.kind, ErrorKind::Parse);
+    assert_eq!(fs::read_to_string(&path).unwrap(), "{ not json");
+}
+
+#[test]
+fn launcher_entry_sits_next_to_banter_and_goes_away_alone() {
+    let dir = common::temp_dir();
+    let path = dir.path().join(".claude.json");
+    common::write(
+        &path,
+        r#"{ "mcpServers": { "banter": { "command": "node" } } }"#,
+    );
+    let args = ["--mcp-status".to_string()];
+
+    client_config::plan_launcher_entry(
+        &mut FilePlan::new(false),
+        &path,
+        Some(("/opt/banter-launcher", &args)),
+        &BackupRetention::default(),
+    )
+    .unwrap();
+    let config = common::read_json(&path);
+    assert_eq!(
+        config["mcpServers"][client_config::LAUNCHER_ENTRY]["args"],
+        serde_json::json!(["--mcp-status"])
+    );
+    assert_eq!(config["mcpServers"]["banter"]["command"], "node");
+
+    client_config::plan_launcher_entry(
+        &mut FilePlan::new(false),
+        &path,
+        None,
+        &BackupRetention::default(),
+    )
+    .unwrap();
+    let config = common::read_json(&path);
+    assert!(config["mcpServers"]
+        .get(client_config::LAUNCHER_ENTRY)
+        .is_none());
+    assert_eq!(config["mcpServers"]["banter"]["command"], "node");
+
+    // Nothing left to remove, nothing written
+    let mut plan = FilePlan::new(false);
+    client_config::plan_launcher_entry(&mut plan, &path, None, &BackupRetention::default())
+        .unwrap();
+    assert!(plan.operations.is_empty());
+}
@@ -0,0 +1,99 @@
+//! Snapshot tests for the `banter` entry updates
+//!
+//! Each folder in `tests/fixtures/client_config` holds an `input.json` (a
+//! Claude Code, Cursor or Windsurf config as users have them), the file
+//! expected after pointing `banter` at a server (`update.json`) and after
+//! removing it (`remove.json`). Everything but the `banter` entry has to come
+//! through unchanged. Run with `BANTER_UPDATE_SNAPSHOTS=1` to rewrite the
+//! expected files after an intended change, then review the diff.
+
+mod common;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use launcher_core::backup::BackupRetention;
+use launcher_core::client_config;
+use launcher_core::plan::FilePlan;
+
+fn fixtures() -> Vec<PathBuf> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/client_config");
+    let mut cases: Vec<PathBuf> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_dir())
+        .collect();
+    cases.sort();
+    assert!(!cases.is_empty(), "no fixtures in {}", dir.display());
+    cases
+}
+
+/// Run `mutate` on a copy of the case's input and compare the result with
+/// `<case>/<snapshot>`; returns a description of the mismatch, if any
+fn check(
+    case: &Path,
+    snapshot: &str,
+    mutate: impl Fn(&mut FilePlan, &Path, &BackupRetention),
+) -> Option<String> {
+    let dir = common::temp_dir();
+    let path = dir.path().join("config.json");
+    fs::copy(case.join("input.json"), &path).unwrap();
+
+    let mut plan = FilePlan::new(false);
+    mutate(&mut plan, &path, &BackupRetention::default());
+    let actual = fs::read_to_string(&path).unwrap();
+
+    let expected_path = case.join(snapshot);
+    if std::env::var_os("BANTER_UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&expected_path, format!("{}\n", actual.trim_end())).unwrap();
+        return None;
+    }
+    let expected = fs::read_to_string(&expected_path)
+        .unwrap_or_default()
+        .replace("\r\n", "\n");
+    if actual.trim_end() == expected.trim_end() {
+        None
+    } else {
+        Some(format!(
+            "{}:\n--- expected\n{}\n--- actual\n{}",
+            expected_path.display(),
+            expected.trim_end(),
+            actual.trim_end()
+        ))
+    }
+}
+
+fn assert_all(snapshot: &str, mutate: impl Fn(&mut FilePlan, &Path, &BackupRetention)) {
+    let mismatches: Vec<String> = fixtures()
+        .iter()
+        .filter_map(|case| check(case, snapshot, &mutate))
+        .collect();
+    assert!(
+        mismatches.is_empty(),
+        "{} snapshot(s) differ (BANTER_UPDATE_SNAPSHOTS=1 rewrites them)\n\n{}",
+        mismatches.len(),
+        mismatches.join("\n\n")
+    );
+}
+
+#[test]
+fn update_snapshots() {
+    assert_all("update.json", |plan, path, retention| {
+        client_config::plan_claude_mcp_update(
+            plan,
+            path,
+            "/srv/banter/index.js",
+            "/projects/lobby",
+            serde_json::json!({ "UNITY_PROJECT_PATH": "/projects/lobby" }),
+            retention,
+        )
+        .unwrap()
+    });
+}
+
+#[test]
+fn removal_snapshots() {
+    assert_all("remove.json", |plan, path, retention| {
+        client_config::plan_claude_mcp_removal_from(plan, path, retention).unwrap()
+    });
+}
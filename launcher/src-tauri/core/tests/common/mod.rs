@@ -0,0 +1,50 @@
+//! Shared setup for the integration tests
+//!
+//! Each test binary keeps its launcher directory (config, backups, the
+//! journal) in one temporary folder, and each test works in a fresh folder
+//! of its own; the write allowlist accepts both, as the app's does.
+
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use launcher_core::{config, write_guard};
+use tempfile::TempDir;
+
+static LAUNCHER_DIR: OnceLock<TempDir> = OnceLock::new();
+
+/// A new empty folder that writes are allowed into
+pub fn temp_dir() -> TempDir {
+    LAUNCHER_DIR.get_or_init(|| {
+        let dir = tempfile::tempdir().expect("create launcher dir");
+        config::set_launcher_dir(dir.path());
+        write_guard::allow_new_dir(dir.path()).expect("allow launcher dir");
+        dir
+    });
+    let dir = tempfile::tempdir().expect("create temp dir");
+    write_guard::allow_new_dir(dir.path()).expect("allow temp dir");
+    dir
+}
+
+/// Write `contents` to `path`, creating its parents
+pub fn write(path: &Path, contents: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
+}
+
+pub fn read_json(path: &Path) -> serde_json::Value {
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+/// `<root>/<project>/Assets/Scenes/<scene>.unity`, created
+pub fn scene(root: &Path, project: &str, scene: &str) -> String {
+    let path = root
+        .join(project)
+        .join("Assets")
+        .join("Scenes")
+        .join(format!("{}.unity", scene));
+    write(&path, "%YAML 1.1\n");
+    path.to_string_lossy().to_string()
+}
@@ -0,0 +1,171 @@
+mod common;
+
+use std::fs;
+
+use launcher_core::backup::{self, BackupKind};
+use launcher_core::config::{self, ProjectChannel};
+use launcher_core::error::ErrorKind;
+use launcher_core::plan::{FileAction, FilePlan};
+use launcher_core::sanitize;
+use launcher_core::storage::StorageBackend;
+use launcher_core::user;
+
+fn channel(id: &str) -> ProjectChannel {
+    ProjectChannel {
+        id: id.to_string(),
+        name: "Lobby".to_string(),
+        unity_project_path: "/projects/lobby".to_string(),
+        scene_path: Some("/projects/lobby/Assets/Lobby.unity".to_string()),
+        enabled: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn missing_file_reads_as_default() {
+    let dir = common::temp_dir();
+    let config = config::read_config_from(&dir.path().join("launcher-config.json")).unwrap();
+
+    assert!(config.channels.is_empty());
+    assert_eq!(config.active_channel_id, None);
+    assert_eq!(config.locale, "en");
+    assert_eq!(
+        config.preview_server_port,
+        user::port(config::DEFAULT_PREVIEW_SERVER_PORT)
+    );
+}
+
+#[test]
+fn write_then_read_round_trips() {
+    let dir = common::temp_dir();
+    let path = dir.path().join("launcher-config.json");
+    let mut written = config::default_config();
+    written.channels.push(channel("lobby"));
+    written.active_channel_id = Some("lobby".to_string());
+
+    let mut plan = FilePlan::new(false);
+    config::plan_config_write_to(&mut plan, &path, &written).unwrap();
+    let read = config::read_config_from(&path).unwrap();
+
+    assert_eq!(read.active_channel_id.as_deref(), Some("lobby"));
+    assert_eq!(read.channels.len(), 1);
+    assert_eq!(read.channels[0].name, "Lobby");
+    assert_eq!(plan.operations.len(), 1);
+    assert_eq!(plan.operations[0].action, FileAction::Write);
+}
+
+#[test]
+fn dry_run_writes_nothing() {
+    let dir = common::temp_dir();
+    let path = dir.path().join("launcher-config.json");
+
+    let mut plan = FilePlan::new(true);
+    config::plan_config_write_to(&mut plan, &path, &config::default_config()).unwrap();
+
+    assert!(!path.exists());
+    assert_eq!(plan.operations.len(), 1);
+    assert_eq!(plan.operations[0].path, path.to_string_lossy());
+}
+
+#[test]
+fn overwriting_backs_up_the_previous_file() {
+    let dir = common::temp_dir();
+    let path = dir.path().join("launcher-config.json");
+    let config = config::default_config();
+
+    config::plan_config_write_to(&mut FilePlan::new(false), &path, &config).unwrap();
+    let mut plan = FilePlan::new(false);
+    config::plan_config_write_to(&mut plan, &path, &config).unwrap();
+
+    assert_eq!(plan.operations[0].action, FileAction::Backup);
+    let original = path.to_string_lossy();
+    assert!(backup::entries(BackupKind::LauncherConfig)
+        .iter()
+        .any(|e| e.original_path == original));
+}
+
+#[test]
+fn older_config_gets_defaults_for_new_fields() {
+    let dir = common::temp_dir();
+    let path = dir.path().join("launcher-config.json");
+    common::write(
+        &path,
+        r#"{ "channels": [], "active_channel_id": null, "mcp_server_path": "/srv/index.js", "auto_start": true }"#,
+    );
+
+    let config = config::read_config_from(&path).unwrap();
+
+    assert_eq!(config.mcp_server_path, "/srv/index.js");
+    assert!(config.auto_start);
+    assert_eq!(
+        config.control_api_port,
+        user::port(config::DEFAULT_CONTROL_API_PORT)
+    );
+    assert_eq!(config.locale, "en");
+    assert!(!config.simulation_enabled);
+    assert!(!config.git_guards.refuse_dirty_activation);
+    assert!(!config.git_guards.checkpoint_on_activate);
+    assert!(config.health_gate.is_off());
+    assert!(!config.observer_mode);
+    assert!(!config.record_sessions);
+    assert!(!config.server_pool.enabled);
+    assert_eq!(config.server_pool.idle_timeout_secs, 300);
+    assert!(!config.serve_launcher_state);
+    assert!(!config.portable_client_config);
+    assert!(!config.file_triggers);
+    assert_eq!(config.storage_backend, StorageBackend::Json);
+    assert!(config.schedules.server_restart.is_none());
+    assert!(config.schedules.idle_shutdown.is_none());
+    assert_eq!(config.network.proxy, "system");
+    assert_eq!(config.network.max_kib_per_sec, None);
+    assert!(!config.relaunch_editor_on_crash);
+    assert!(!config.telemetry.enabled);
+    assert_eq!(config.telemetry.endpoint, None);
+}
+
+#[test]
+fn unreadable_config_is_a_parse_error() {
+    let dir = common::temp_dir();
+    let path = dir.path().join("launcher-config.json");
+    common::write(&path, "{ not json");
+
+    let error = config::read_config_from(&path).unwrap_err();
+
+    assert_eq!(error.kind, ErrorKind::Parse);
+    assert_eq!(error.path.as_deref(), Some(&*path.to_string_lossy()));
+    assert_eq!(error.ids.message_id.as_deref(), Some("error.config_parse"));
+    // The file is left alone for the user to fix
+    assert_eq!(fs::read_to_string(&path).unwrap(), "{ not json");
+}
+
+#[test]
+fn find_channel_by_id() {
+    let mut config = config::default_config();
+    config.channels.push(channel("lobby"));
+
+    assert_eq!(
+        config::find_channel(&config, "lobby").unwrap().name,
+        "Lobby"
+    );
+    assert_eq!(
+        config::find_channel(&config, "missing").unwrap_err().kind,
+        ErrorKind::NotFound
+    );
+}
+
+#[test]
+fn network_settings_are_validated() {
+    let mut config = config::default_config();
+    assert!(sanitize::config(&config).is_ok());
+
+    config.network.proxy = "http://proxy.studio.lan:3128".to_string();
+    config.network.max_kib_per_sec = Some(512);
+    assert!(sanitize::config(&config).is_ok());
+
+    config.network.proxy = "proxy.studio.lan:3128".to_string();
+    assert!(sanitize::config(&config).is_err());
+
+    config.network.proxy = "none".to_string();
+    config.network.max_kib_per_sec = Some(0);
+    assert!(sanitize::config(&config).is_err());
+}
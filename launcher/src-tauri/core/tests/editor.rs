@@ -0,0 +1,75 @@
+mod common;
+
+use std::path::Path;
+
+use launcher_core::editor::{self, EditorCondition, EditorProbe, HANG_AFTER_MS};
+
+const CRASHED_LOG: &str = "\
+COMMAND LINE ARGUMENTS:
+C:\\Program Files\\Unity\\Hub\\Editor\\2022.3.10f1\\Editor\\Unity.exe
+-projectpath
+C:\\Projects\\Lobby
+Refreshing native plugins compatible for Editor in 2.01 ms
+Crash!!!
+SymInit: Symbol-SearchPath: '.;C:\\Projects\\Lobby'
+";
+
+#[test]
+fn crash_lines_come_from_the_projects_log() {
+    assert_eq!(editor::crash_line(CRASHED_LOG).as_deref(), Some("Crash!!!"));
+    assert!(editor::crash_line("Reloading assemblies\nRefresh completed\n").is_none());
+
+    assert!(editor::log_is_for(
+        CRASHED_LOG,
+        Path::new("C:/Projects/Lobby/")
+    ));
+    assert!(!editor::log_is_for(
+        CRASHED_LOG,
+        Path::new("C:/Projects/Arena")
+    ));
+}
+
+#[test]
+fn editors_are_classified_by_process_and_heartbeat() {
+    let condition = |probe: EditorProbe| editor::classify(&probe).0;
+
+    assert_eq!(
+        condition(EditorProbe {
+            connected: true,
+            pid: Some(42),
+            alive: true,
+            ..Default::default()
+        }),
+        EditorCondition::Connected
+    );
+    assert_eq!(condition(EditorProbe::default()), EditorCondition::Closed);
+    assert_eq!(
+        condition(EditorProbe {
+            pid: Some(42),
+            alive: true,
+            heartbeat_age_ms: Some(5_000),
+            ..Default::default()
+        }),
+        EditorCondition::Starting
+    );
+    assert_eq!(
+        condition(EditorProbe {
+            pid: Some(42),
+            alive: true,
+            heartbeat_age_ms: Some(HANG_AFTER_MS),
+            ..Default::default()
+        }),
+        EditorCondition::NotResponding
+    );
+
+    // The instance file outlived its process
+    let (condition, cause) = editor::classify(&EditorProbe {
+        pid: Some(42),
+        alive: false,
+        crash_line: Some("Received signal SIGSEGV".to_string()),
+        ..Default::default()
+    });
+    assert_eq!(condition, EditorCondition::Crashed);
+    assert!(cause.unwrap().contains("SIGSEGV"));
+    assert!(condition.is_failure());
+}
@@ -0,0 +1,159 @@
+mod common;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use launcher_core::error::ErrorKind;
+use launcher_core::extension;
+use launcher_core::plan::{FileAction, FilePlan};
+use launcher_core::write_guard;
+
+/// An MCP root with the extension source in it
+fn mcp_root(dir: &Path, contents: &str) -> String {
+    let root = dir.join("banter-mcp");
+    common::write(
+        &root.join("unity-extension/Editor/BanterMCPBridge.cs"),
+        contents,
+    );
+    root.to_string_lossy().to_string()
+}
+
+fn project(dir: &Path) -> PathBuf {
+    let project = dir.join("Lobby");
+    fs::create_dir_all(project.join("Assets")).unwrap();
+    project
+}
+
+fn installed(project: &Path) -> PathBuf {
+    project.join("Assets/Editor/BanterMCPBridge.cs")
+}
+
+#[test]
+fn install_copies_the_bridge_into_assets_editor() {
+    let dir = common::temp_dir();
+    let root = mcp_root(dir.path(), "// bridge v2");
+    let project = project(dir.path());
+    let project_path = project.to_string_lossy().to_string();
+
+    assert!(!extension::check_unity_extension(project_path.clone()).unwrap());
+    let plan = extension::install_unity_extension(project_path.clone(), root, None, None).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(installed(&project)).unwrap(),
+        "// bridge v2"
+    );
+    assert!(extension::check_unity_extension(project_path).unwrap());
+    let actions: Vec<FileAction> = plan.operations.iter().map(|o| o.action).collect();
+    assert_eq!(actions, [FileAction::CreateDir, FileAction::Copy]);
+}
+
+#[test]
+fn install_replaces_an_older_copy() {
+    let dir = common::temp_dir();
+    let root = mcp_root(dir.path(), "// bridge v2");
+    let project = project(dir.path());
+    common::write(&installed(&project), "// bridge v1");
+
+    extension::install_unity_extension(project.to_string_lossy().to_string(), root, None, None)
+        .unwrap();
+
+    assert_eq!(
+        fs::read_to_string(installed(&project)).unwrap(),
+        "// bridge v2"
+    );
+}
+
+#[test]
+fn dry_run_install_writes_nothing() {
+    let dir = common::temp_dir();
+    let root = mcp_root(dir.path(), "// bridge v2");
+    let project = project(dir.path());
+
+    let plan = extension::install_unity_extension(
+        project.to_string_lossy().to_string(),
+        root,
+        Some(true),
+        None,
+    )
+    .unwrap();
+
+    assert!(plan.dry_run);
+    assert!(!installed(&project).exists());
+    assert!(!project.join("Assets/Editor").exists());
+}
+
+#[test]
+fn install_without_the_source_fails() {
+    let dir = common::temp_dir();
+    let project = project(dir.path());
+    let root = dir.path().join("empty-root").to_string_lossy().to_string();
+
+    let error =
+        extension::install_unity_extension(project.to_string_lossy().to_string(), root, None, None)
+            .unwrap_err();
+
+    assert_eq!(error.kind, ErrorKind::NotFound);
+    assert!(!installed(&project).exists());
+}
+
+#[test]
+fn install_outside_allowed_locations_is_refused() {
+    let dir = common::temp_dir();
+    let root = mcp_root(dir.path(), "// bridge v2");
+    // Not registered with the write allowlist
+    let elsewhere = tempfile::tempdir().unwrap();
+    let project = project(elsewhere.path());
+
+    let error =
+        extension::install_unity_extension(project.to_string_lossy().to_string(), root, None, None)
+            .unwrap_err();
+
+    assert_eq!(error.kind, ErrorKind::PermissionDenied);
+    assert!(!installed(&project).exists());
+    let refused = project.join("Assets").join("Editor");
+    let refused = refused.to_string_lossy();
+    assert!(write_guard::violations().iter().any(|v| v.path == refused));
+}
+
+#[test]
+fn custom_scripts_setting_is_written_for_the_bridge() {
+    let dir = common::temp_dir();
+    let project = project(dir.path());
+
+    extension::set_unity_custom_scripts(project.to_string_lossy().to_string(), true).unwrap();
+    let settings = common::read_json(&project.join("Assets/_MCP/state/launcher-settings.json"));
+
+    assert_eq!(settings["enableCustomScripts"], true);
+}
+
+#[test]
+fn removal_deletes_the_bridge_and_its_folders_but_not_editor() {
+    let dir = common::temp_dir();
+    let project = project(dir.path());
+    common::write(&installed(&project), "// bridge v2");
+    common::write(
+        &project.join("Assets/Editor/BanterMCPBridge.cs.meta"),
+        "guid: 1",
+    );
+    common::write(&project.join("Assets/Editor/Other.cs"), "// mine");
+    common::write(&project.join("Assets/_MCP/state/heartbeat.json"), "{}");
+    common::write(&project.join("Assets/_MCP.meta"), "guid: 2");
+    let project_path = project.to_string_lossy().to_string();
+
+    let mut dry = FilePlan::new(true);
+    extension::plan_extension_removal(&mut dry, &project_path).unwrap();
+    assert_eq!(dry.operations.len(), 4);
+    assert!(installed(&project).exists());
+
+    let mut plan = FilePlan::new(false);
+    extension::plan_extension_removal(&mut plan, &project_path).unwrap();
+    assert!(!extension::check_unity_extension(project_path.clone()).unwrap());
+    assert!(!project.join("Assets/_MCP").exists());
+    assert!(!project.join("Assets/_MCP.meta").exists());
+    assert!(project.join("Assets/Editor/Other.cs").exists());
+
+    // Nothing left to remove
+    let mut again = FilePlan::new(false);
+    extension::plan_extension_removal(&mut again, &project_path).unwrap();
+    assert!(again.operations.is_empty());
+}
@@ -0,0 +1,102 @@
+mod common;
+
+use std::sync::{Mutex, MutexGuard};
+
+use launcher_core::backup::BackupRetention;
+use launcher_core::bridge;
+use launcher_core::client_config;
+use launcher_core::config::{self, LauncherConfig};
+use launcher_core::error::ErrorKind;
+use launcher_core::faults::{self, Fault};
+use launcher_core::plan::FilePlan;
+
+/// Faults are global, so tests that inject them take turns
+static INJECTING: Mutex<()> = Mutex::new(());
+
+fn inject(injected: Vec<Fault>) -> MutexGuard<'static, ()> {
+    let guard = INJECTING.lock().unwrap_or_else(|e| e.into_inner());
+    faults::set(injected);
+    guard
+}
+
+#[test]
+fn fault_names_round_trip() {
+    for fault in Fault::ALL {
+        assert_eq!(Fault::parse(fault.as_str()), Some(fault));
+    }
+    assert_eq!(Fault::parse("disk-full"), None);
+}
+
+#[test]
+fn locked_config_fails_reads_and_writes() {
+    let dir = common::temp_dir();
+    let path = dir.path().join("launcher-config.json");
+    let mut plan = FilePlan::new(false);
+    config::plan_config_write_to(&mut plan, &path, &config::default_config()).unwrap();
+
+    let _guard = inject(vec![Fault::ConfigLocked]);
+    let err = config::read_config_from(&path).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Io);
+
+    let mut plan = FilePlan::new(false);
+    let err =
+        config::plan_config_write_to(&mut plan, &path, &config::default_config()).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Io);
+
+    faults::set(vec![]);
+    let config: LauncherConfig = config::read_config_from(&path).unwrap();
+    assert!(config.channels.is_empty());
+}
+
+#[test]
+fn unwritable_claude_config_is_refused_but_dry_runs_are_not() {
+    let dir = common::temp_dir();
+    let path = dir.path().join(".claude.json");
+    let env = serde_json::json!({ "UNITY_PROJECT_PATH": "/projects/lobby" });
+    let retention = BackupRetention::default();
+
+    let _guard = inject(vec![Fault::ClaudeConfigUnwritable]);
+    let mut plan = FilePlan::new(false);
+    let err = client_config::plan_claude_mcp_update(
+        &mut plan,
+        &path,
+        "/srv/banter/index.js",
+        "/projects/lobby",
+        env.clone(),
+        &retention,
+    )
+    .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::PermissionDenied);
+    assert!(err.hint.is_some());
+
+    let mut plan = FilePlan::new(true);
+    client_config::plan_claude_mcp_update(
+        &mut plan,
+        &path,
+        "/srv/banter/index.js",
+        "/projects/lobby",
+        env,
+        &retention,
+    )
+    .unwrap();
+    assert!(!path.exists());
+}
+
+#[test]
+fn timed_out_bridge_stays_connected_but_returns_nothing() {
+    let dir = common::temp_dir();
+    let project = dir.path().join("Lobby");
+    let state = bridge::state_dir(&project);
+    common::write(
+        &state.join("editor-state.json"),
+        &format!(r#"{{ "timestamp": {} }}"#, bridge::now_ms()),
+    );
+    common::write(&state.join("console-log.json"), r#"{ "entries": [] }"#);
+
+    let _guard = inject(vec![Fault::BridgeTimeout]);
+    assert!(bridge::is_editor_connected(&project));
+    assert_eq!(bridge::read_state(&project, "console-log.json"), None);
+
+    faults::set(vec![]);
+    assert!(bridge::read_state(&project, "console-log.json").is_some());
+}
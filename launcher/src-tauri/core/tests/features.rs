@@ -0,0 +1,50 @@
+mod common;
+
+use launcher_core::config;
+use launcher_core::error::ErrorKind;
+use launcher_core::features::{self, Feature};
+use launcher_core::plan::FilePlan;
+
+#[test]
+fn defaults_apply_until_set() {
+    let mut config = config::default_config();
+    assert!(features::is_enabled(&config, Feature::BanterUploads));
+    assert!(!features::is_enabled(&config, Feature::ProxyInspector));
+    assert!(features::require(&config, Feature::MultiServer).is_err());
+
+    features::set(&mut config, "multi-server", true).unwrap();
+    features::set(&mut config, "banter-uploads", false).unwrap();
+    assert!(features::require(&config, Feature::MultiServer).is_ok());
+    let err = features::require(&config, Feature::BanterUploads).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidInput);
+}
+
+#[test]
+fn unknown_names_are_refused() {
+    let mut config = config::default_config();
+    let err = features::set(&mut config, "time-travel", true).unwrap_err();
+
+    assert_eq!(err.kind, ErrorKind::InvalidInput);
+    assert!(config.features.is_empty());
+}
+
+#[test]
+fn flags_from_a_newer_launcher_survive_a_save() {
+    let dir = common::temp_dir();
+    let path = dir.path().join("launcher-config.json");
+    common::write(
+        &path,
+        r#"{ "channels": [], "active_channel_id": null, "mcp_server_path": "/srv/index.js",
+             "auto_start": false, "features": { "proxy-inspector": true, "time-travel": true } }"#,
+    );
+
+    let config = config::read_config_from(&path).unwrap();
+    assert!(features::is_enabled(&config, Feature::ProxyInspector));
+    let flags = features::flags(&config);
+    assert_eq!(flags.len(), Feature::ALL.len());
+
+    let mut plan = FilePlan::new(false);
+    config::plan_config_write_to(&mut plan, &path, &config).unwrap();
+    let saved = common::read_json(&path);
+    assert_eq!(saved["features"]["time-travel"], true);
+}
@@ -0,0 +1,104 @@
+mod common;
+
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+
+use launcher_core::error::LauncherError;
+use launcher_core::journal::{self, Operation};
+use launcher_core::plan::FilePlan;
+
+fn activation(channel_id: &str) -> Operation {
+    Operation::Activation {
+        channel_id: channel_id.to_string(),
+    }
+}
+
+fn left_for(channel_id: &str) -> Vec<journal::JournalEntry> {
+    journal::incomplete()
+        .into_iter()
+        .filter(|entry| entry.operation == activation(channel_id))
+        .collect()
+}
+
+#[test]
+fn finished_operations_leave_no_entry() {
+    let dir = common::temp_dir();
+    let config = dir.path().join("config.json");
+
+    journal::run(activation("finished"), || {
+        FilePlan::new(false).write(&config, "{}")
+    })
+    .unwrap();
+    let failed: Result<(), LauncherError> = journal::run(activation("failed"), || {
+        FilePlan::new(false).write(&config, "{\"a\":1}")?;
+        Err(LauncherError::invalid("second step failed"))
+    });
+
+    assert!(failed.is_err());
+    assert_eq!(fs::read_to_string(&config).unwrap(), "{\"a\":1}");
+    assert!(left_for("finished").is_empty());
+    assert!(left_for("failed").is_empty());
+}
+
+#[test]
+fn an_interrupted_operation_rolls_back() {
+    let dir = common::temp_dir();
+    let config = dir.path().join("config.json");
+    let client_dir = dir.path().join(".cursor");
+    let client = client_dir.join("mcp.json");
+    common::write(&config, "old");
+
+    let crashed = panic::catch_unwind(AssertUnwindSafe(|| {
+        journal::run(activation("crashed"), || -> Result<(), LauncherError> {
+            let mut plan = FilePlan::new(false);
+            plan.write(&config, "new")?;
+            plan.create_dir(&client_dir)?;
+            plan.write(&client, "{}")?;
+            panic!("power loss");
+        })
+    }));
+    assert!(crashed.is_err());
+    assert_eq!(fs::read_to_string(&config).unwrap(), "new");
+
+    let left = left_for("crashed");
+    assert_eq!(left.len(), 1);
+    assert_eq!(left[0].files.len(), 3);
+    journal::rollback(&left[0].id).unwrap();
+
+    assert_eq!(fs::read_to_string(&config).unwrap(), "old");
+    assert!(!client.exists());
+    assert!(!client_dir.exists());
+    assert!(left_for("crashed").is_empty());
+}
+
+#[test]
+fn a_deleted_directory_comes_back_on_rollback() {
+    let dir = common::temp_dir();
+    let extension = dir.path().join("_MCP");
+    let script = extension.join("Editor").join("Bridge.cs");
+    common::write(&script, "old bridge");
+    common::write(&extension.join("state.json"), "{}");
+
+    let crashed = panic::catch_unwind(AssertUnwindSafe(|| {
+        journal::run(activation("upgrade"), || -> Result<(), LauncherError> {
+            let mut plan = FilePlan::new(false);
+            plan.delete_dir(&extension)?;
+            plan.create_dir(&extension)?;
+            plan.write(&extension.join("new.cs"), "new bridge")?;
+            panic!("power loss");
+        })
+    }));
+    assert!(crashed.is_err());
+    assert!(!script.exists());
+
+    let left = left_for("upgrade");
+    assert_eq!(left.len(), 1);
+    journal::rollback(&left[0].id).unwrap();
+
+    assert_eq!(fs::read_to_string(&script).unwrap(), "old bridge");
+    assert_eq!(
+        fs::read_to_string(extension.join("state.json")).unwrap(),
+        "{}"
+    );
+    assert!(!extension.join("new.cs").exists());
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use std::path::Path;
+
+use launcher_core::config::ProjectChannel;
+use launcher_core::path_index::PathIndex;
+
+fn channel(id: &str, scene: &str) -> ProjectChannel {
+    ProjectChannel {
+        id: id.to_string(),
+        name: id.to_string(),
+        unity_project_path: launcher_core::channels::project_root_for_scene(Path::new(scene))
+            .unwrap()
+            .to_string_lossy()
+            .to_string(),
+        scene_path: Some(scene.to_string()),
+        enabled: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn paths_resolve_to_the_innermost_project() {
+    let dir = common::temp_dir();
+    let outer = common::scene(dir.path(), "outer", "Main");
+    let inner = common::scene(&dir.path().join("outer"), "inner", "Main");
+    let index = PathIndex::new(&[channel("outer", &outer), channel("inner", &inner)]);
+
+    let prefab = dir.path().join("outer/inner/Assets/Prefabs/Door.prefab");
+    let found = index.find(&prefab).unwrap();
+    assert_eq!(found.channel_id, "inner");
+    assert!(!found.scene_match);
+
+    // `..` and not-yet-existing files resolve like any other path
+    let roundabout = dir.path().join("outer/inner/../Assets/New.unity");
+    assert_eq!(index.find(&roundabout).unwrap().channel_id, "outer");
+    assert!(index.find(&dir.path().join("elsewhere/file.txt")).is_none());
+}
+
+#[test]
+fn the_channel_of_a_scene_wins_within_a_shared_project() {
+    let dir = common::temp_dir();
+    let lobby = common::scene(dir.path(), "world", "Lobby");
+    let arena = common::scene(dir.path(), "world", "Arena");
+    let index = PathIndex::new(&[channel("lobby", &lobby), channel("arena", &arena)]);
+
+    let found = index.find(Path::new(&arena)).unwrap();
+    assert_eq!(found.channel_id, "arena");
+    assert!(found.scene_match);
+
+    let other = dir.path().join("world/Assets/Scenes/Other.unity");
+    assert_eq!(index.find(&other).unwrap().channel_id, "lobby");
+}
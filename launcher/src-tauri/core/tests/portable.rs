@@ -0,0 +1,80 @@
+mod common;
+
+use std::path::{Path, PathBuf};
+
+use launcher_core::portable::{self, Roots, Syntax};
+
+fn roots(workspace: Option<&str>) -> Roots {
+    Roots {
+        vars: vec![
+            (portable::MCP_HOME_VAR, PathBuf::from("/opt/banter-mcp")),
+            (
+                portable::LAUNCHER_DIR_VAR,
+                PathBuf::from("/home/ada/.config/banter-mcp"),
+            ),
+            (portable::LAUNCHER_HOME_VAR, PathBuf::from("/home/ada")),
+        ],
+        workspace: workspace.map(PathBuf::from),
+    }
+}
+
+#[test]
+fn claude_paths_fall_back_to_this_machine() {
+    let roots = roots(None);
+    assert_eq!(
+        portable::mcp_home(Path::new("/opt/banter-mcp/dist/index.js")),
+        PathBuf::from("/opt/banter-mcp")
+    );
+    assert_eq!(
+        portable::rewrite("/opt/banter-mcp/dist/index.js", Syntax::Claude, &roots),
+        "${BANTER_MCP_HOME:-/opt/banter-mcp}/dist/index.js"
+    );
+    // The deepest folder wins
+    assert_eq!(
+        portable::rewrite(
+            "/home/ada/.config/banter-mcp/logs/lobby.log",
+            Syntax::Claude,
+            &roots
+        ),
+        "${BANTER_LAUNCHER_DIR:-/home/ada/.config/banter-mcp}/logs/lobby.log"
+    );
+    // Only whole folder names match, and other values stay as they are
+    assert_eq!(
+        portable::rewrite("/opt/banter-mcp-old/index.js", Syntax::Claude, &roots),
+        "/opt/banter-mcp-old/index.js"
+    );
+    assert_eq!(portable::rewrite("node", Syntax::Claude, &roots), "node");
+    assert_eq!(
+        portable::rewrite("/home/ada/Projects/Lobby", Syntax::Claude, &roots),
+        "${BANTER_LAUNCHER_HOME:-/home/ada}/Projects/Lobby"
+    );
+}
+
+#[test]
+fn cursor_paths_use_env_and_the_workspace() {
+    let roots = roots(Some("/home/ada/Projects/Lobby"));
+    assert_eq!(Syntax::for_client("cursor"), Some(Syntax::Cursor));
+    assert_eq!(Syntax::for_client("windsurf"), None);
+    assert_eq!(
+        portable::rewrite("/opt/banter-mcp/dist/index.js", Syntax::Cursor, &roots),
+        "${env:BANTER_MCP_HOME}/dist/index.js"
+    );
+    assert_eq!(
+        portable::rewrite(
+            "/home/ada/Projects/Lobby/Library/BanterMCP",
+            Syntax::Cursor,
+            &roots
+        ),
+        "${workspaceFolder}/Library/BanterMCP"
+    );
+    if cfg!(unix) {
+        assert_eq!(
+            portable::rewrite(
+                "/home/ada/Projects/Lobby:/srv/shared",
+                Syntax::Cursor,
+                &roots
+            ),
+            "${workspaceFolder}:/srv/shared"
+        );
+    }
+}
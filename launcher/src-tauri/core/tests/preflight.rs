@@ -0,0 +1,54 @@
+mod common;
+
+use std::sync::{Mutex, MutexGuard};
+
+use launcher_core::error::ErrorKind;
+use launcher_core::faults::{self, Fault};
+use launcher_core::preflight;
+
+/// Faults are global, so tests that inject them take turns
+static INJECTING: Mutex<()> = Mutex::new(());
+
+fn inject(injected: Vec<Fault>) -> MutexGuard<'static, ()> {
+    let guard = INJECTING.lock().unwrap_or_else(|e| e.into_inner());
+    faults::set(injected);
+    guard
+}
+
+#[test]
+fn bytes_are_formatted_in_the_largest_unit() {
+    assert_eq!(preflight::format_bytes(512), "512 bytes");
+    assert_eq!(preflight::format_bytes(1536), "1.5 KB");
+    assert_eq!(preflight::format_bytes(2048 * 1024 * 1024), "2.0 GB");
+}
+
+#[test]
+fn temp_folder_passes_with_headroom_added() {
+    let _guard = inject(vec![]);
+    let dir = common::temp_dir();
+    let target = dir.path().join("Builds").join("Banter");
+
+    let check = preflight::check("bundle build", &target, 1024);
+
+    assert!(check.writable);
+    assert_eq!(check.required_bytes, 1024 + preflight::MIN_FREE_BYTES);
+    assert!(check.is_ok(), "{}", check.summary());
+    preflight::ensure("bundle build", &target, 1024).unwrap();
+}
+
+#[test]
+fn low_disk_space_is_refused_with_the_folder() {
+    let dir = common::temp_dir();
+
+    let _guard = inject(vec![Fault::LowDiskSpace]);
+    let check = preflight::check("scene snapshot", dir.path(), 0);
+    assert!(!check.has_space());
+    let err = preflight::ensure("scene snapshot", dir.path(), 0).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Io);
+    assert!(err.message.contains("scene snapshot"));
+    assert_eq!(err.path.as_deref(), Some(&*dir.path().to_string_lossy()));
+    assert!(preflight::ensure_space("Unity extension install", dir.path(), 0).is_err());
+
+    faults::set(vec![]);
+    preflight::ensure("scene snapshot", dir.path(), 0).unwrap();
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use launcher_core::config::{ChannelQuotas, ProjectChannel};
+use launcher_core::quotas::{self, CallRate};
+use launcher_core::sanitize;
+
+fn channel(quotas: ChannelQuotas) -> ProjectChannel {
+    ProjectChannel {
+        id: "lobby".to_string(),
+        name: "Lobby".to_string(),
+        unity_project_path: "/projects/lobby".to_string(),
+        enabled: true,
+        quotas,
+        ..Default::default()
+    }
+}
+
+fn webroot_js(filename: &str, code: &str) -> serde_json::Value {
+    serde_json::json!({ "filename": filename, "code": code })
+}
+
+#[test]
+fn write_targets_follow_the_server() {
+    let project = Path::new("/projects/lobby");
+    let graph = serde_json::json!({ "graphName": "Door", "graphJson": "{}" });
+    assert_eq!(
+        quotas::write_target(project, "write_vs_graph", &graph).unwrap(),
+        quotas::WriteTarget {
+            path: PathBuf::from("/projects/lobby/Assets/Scripts/VisualScripting/Door.asset"),
+            bytes: 2,
+        }
+    );
+
+    let escaping = webroot_js("../Plugins/evil", "x");
+    assert_eq!(
+        quotas::write_target(project, "write_webroot_js", &escaping)
+            .unwrap()
+            .path,
+        PathBuf::from("/projects/lobby/Assets/Plugins/evil.js")
+    );
+    assert!(quotas::write_target(project, "create_gameobject", &graph).is_none());
+}
+
+#[test]
+fn denied_folders_and_sizes_refuse_writes() {
+    let channel = channel(ChannelQuotas {
+        max_write_bytes: Some(10),
+        denied_paths: vec!["Assets/Plugins".to_string()],
+        ..Default::default()
+    });
+    let mut rate = CallRate::default();
+    let now = Instant::now();
+
+    let check =
+        |rate: &mut CallRate, args| quotas::check(&channel, rate, "write_webroot_js", &args, now);
+    assert!(check(&mut rate, webroot_js("main", "ok")).is_none());
+    assert!(check(&mut rate, webroot_js("../Plugins/evil", "ok"))
+        .unwrap()
+        .contains("denied"));
+    assert!(check(&mut rate, webroot_js("main", "far too long"))
+        .unwrap()
+        .contains("allows 10"));
+    // A folder that merely starts with the same name isn't denied
+    assert!(check(&mut rate, webroot_js("../PluginsExtra/x", "ok")).is_none());
+}
+
+#[test]
+fn call_rate_counts_a_sliding_minute() {
+    let mut rate = CallRate::default();
+    let start = Instant::now();
+
+    assert!(rate.allow(2, start));
+    assert!(rate.allow(2, start + Duration::from_secs(30)));
+    assert!(!rate.allow(2, start + Duration::from_secs(59)));
+    // The first call has left the window; the refused one never counted
+    assert!(rate.allow(2, start + Duration::from_secs(60)));
+    assert!(!rate.allow(2, start + Duration::from_secs(61)));
+}
+
+#[test]
+fn quotas_reach_the_server_env() {
+    let channel = channel(ChannelQuotas {
+        max_write_bytes: Some(1024),
+        max_calls_per_minute: None,
+        denied_paths: vec!["Assets/Plugins".to_string()],
+    });
+    let env = quotas::env(&channel).unwrap();
+    assert_eq!(env[0], (quotas::MAX_WRITE_BYTES_VAR, "1024".to_string()));
+    assert_eq!(env[1].0, quotas::DENIED_PATHS_VAR);
+    assert_eq!(env.len(), 2);
+
+    assert!(sanitize::quotas(&channel.quotas).is_ok());
+    let zero = ChannelQuotas {
+        max_calls_per_minute: Some(0),
+        ..Default::default()
+    };
+    assert!(sanitize::quotas(&zero).is_err());
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use launcher_core::schedule::{self, days_from_civil, Schedule, TimeZone};
+
+const HOUR: i64 = 3600;
+
+fn at(year: i64, month: u32, day: u32, hour: i64, minute: i64) -> i64 {
+    days_from_civil(year, month, day) * 86_400 + hour * HOUR + minute * 60
+}
+
+/// Central European time in 2026: summer time from 29 March to 25 October,
+/// both switches at 01:00 UTC
+fn cet(utc: i64) -> i32 {
+    if (at(2026, 3, 29, 1, 0)..at(2026, 10, 25, 1, 0)).contains(&utc) {
+        7200
+    } else {
+        3600
+    }
+}
+
+#[test]
+fn weekday_ranges_and_names() {
+    let schedule = Schedule::parse("0 9 * * mon-fri").unwrap();
+    // Saturday 17 October 2026
+    let next = schedule.next_after(at(2026, 10, 17, 12, 0), TimeZone::Fixed(0));
+    assert_eq!(next, Some(at(2026, 10, 19, 9, 0)));
+
+    let weekly = Schedule::parse("@weekly").unwrap();
+    let next = weekly.next_after(at(2026, 10, 14, 0, 0), TimeZone::Fixed(0));
+    assert_eq!(next, Some(at(2026, 10, 18, 0, 0)));
+}
+
+#[test]
+fn restricted_day_fields_match_either() {
+    let schedule = Schedule::parse("0 0 13 * fri").unwrap();
+    let next = schedule.next_after(at(2026, 10, 14, 0, 0), TimeZone::Fixed(0));
+    assert_eq!(next, Some(at(2026, 10, 16, 0, 0)));
+}
+
+#[test]
+fn fixed_offsets_shift_the_wall_clock() {
+    let zone = TimeZone::parse("+02:00").unwrap();
+    let schedule = Schedule::parse("30 4 * * *").unwrap();
+    let next = schedule.next_after(at(2026, 10, 14, 0, 0), zone);
+    assert_eq!(next, Some(at(2026, 10, 14, 2, 30)));
+    assert_eq!(
+        schedule::format(next.unwrap(), zone),
+        "2026-10-14T04:30+02:00"
+    );
+}
+
+#[test]
+fn skipped_time_runs_when_the_clocks_jump() {
+    let schedule = Schedule::parse("30 2 * * *").unwrap();
+
+    let next = schedule.next_with(at(2026, 3, 28, 23, 0), cet);
+    assert_eq!(next, Some(at(2026, 3, 29, 1, 0)));
+
+    let after = schedule.next_with(next.unwrap(), cet);
+    assert_eq!(after, Some(at(2026, 3, 30, 0, 30)));
+}
+
+#[test]
+fn repeated_time_runs_once() {
+    let schedule = Schedule::parse("30 2 * * *").unwrap();
+
+    let first = schedule.next_with(at(2026, 10, 24, 22, 0), cet);
+    assert_eq!(first, Some(at(2026, 10, 25, 0, 30)));
+
+    let next = schedule.next_with(first.unwrap(), cet);
+    assert_eq!(next, Some(at(2026, 10, 26, 1, 30)));
+}
+
+#[test]
+fn impossible_dates_never_run() {
+    let schedule = Schedule::parse("0 0 31 2 *").unwrap();
+    assert_eq!(schedule.next_after(0, TimeZone::Fixed(0)), None);
+}
+
+#[test]
+fn malformed_schedules_are_refused() {
+    assert!(Schedule::parse("61 * * * *").is_err());
+    assert!(Schedule::parse("* * *").is_err());
+    assert!(Schedule::parse("*/0 * * * *").is_err());
+    assert!(TimeZone::parse("Mars/Olympus").is_err());
+
+    let err = schedule::validate("Server restart", "0 25 * * *", "local").unwrap_err();
+    assert_eq!(err.message_id(), "input.invalid_schedule");
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use launcher_core::error::ErrorKind;
+use launcher_core::stack::{Capabilities, Capability, CapabilityFlag, REQUIREMENTS};
+
+fn flag(capability: Capability, available: bool) -> CapabilityFlag {
+    CapabilityFlag {
+        capability,
+        available,
+        detail: format!("{} looked for", capability.label()),
+    }
+}
+
+#[test]
+fn only_the_commands_needing_a_missing_capability_are_disabled() {
+    let capabilities = Capabilities::new(
+        vec![
+            flag(Capability::Node, true),
+            flag(Capability::Unity, false),
+            flag(Capability::ClaudeCode, true),
+            flag(Capability::Network, true),
+        ],
+        0,
+    );
+    assert!(capabilities.node && !capabilities.unity && capabilities.online);
+
+    let unity_commands: Vec<&str> = REQUIREMENTS
+        .iter()
+        .filter(|(_, capability)| *capability == Capability::Unity)
+        .map(|(name, _)| *name)
+        .collect();
+    assert_eq!(capabilities.disabled_commands, unity_commands);
+    assert!(capabilities.check("relaunch_editor").is_err());
+    // These work through an editor that is already open
+    for command in [
+        "build_banter_bundle",
+        "verify_unity_extension",
+        "list_unity_scenes",
+        "focus_unity_window",
+    ] {
+        assert!(capabilities.check(command).is_ok(), "{}", command);
+    }
+    assert!(capabilities.check("get_server_capabilities").is_ok());
+    assert!(capabilities.check("save_config").is_ok());
+
+    // Nothing detected yet refuses nothing
+    let unknown = Capabilities::new(Vec::new(), 0);
+    assert!(unknown.node && unknown.unity && unknown.claude_code && unknown.online);
+    assert!(unknown.disabled_commands.is_empty());
+}
+
+#[test]
+fn refusals_say_what_is_missing_and_how_to_fix_it() {
+    let capabilities = Capabilities::new(
+        vec![
+            flag(Capability::Node, false),
+            flag(Capability::Network, false),
+        ],
+        0,
+    );
+
+    let err = capabilities.check("upload_banter_bundle").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Network);
+    assert!(err.message.contains("upload_banter_bundle"));
+    assert!(err.message.contains("a network connection looked for"));
+    assert!(err.hint.is_some());
+
+    let err = capabilities.check("get_server_capabilities").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::NotFound);
+    assert!(err.hint.unwrap().contains("Node.js"));
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use std::fs;
+
+use launcher_core::error::ErrorKind;
+use launcher_core::storage::{self, JsonFiles, Sqlite, StorageBackend, Store};
+
+#[test]
+fn json_documents_keep_the_file_layout() {
+    let dir = common::temp_dir();
+    let store = JsonFiles::new(dir.path());
+
+    store.put("jobs", "job-1", "{\"id\":1}").unwrap();
+    common::write(&dir.path().join("jobs/job-1.log"), "   0.1s Started");
+
+    assert_eq!(
+        fs::read_to_string(dir.path().join("jobs/job-1.json")).unwrap(),
+        "{\"id\":1}"
+    );
+    assert_eq!(
+        store.list("jobs").unwrap(),
+        vec![("job-1".to_string(), "{\"id\":1}".to_string())]
+    );
+    assert_eq!(store.get("jobs", "missing").unwrap(), None);
+    let error = store.put("jobs", "../escape", "{}").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::InvalidInput);
+
+    store.delete("jobs", "job-1").unwrap();
+    store.delete("jobs", "job-1").unwrap();
+    assert!(store.list("jobs").unwrap().is_empty());
+}
+
+#[test]
+fn switching_backends_moves_the_documents() {
+    let dir = common::temp_dir();
+    let files = JsonFiles::new(dir.path());
+    files.put("jobs", "job-1", "{\"id\":1}").unwrap();
+    files.put("cache", "scans", "{\"entries\":{}}").unwrap();
+
+    let database = Sqlite::open(&dir.path().join(storage::DATABASE_FILE)).unwrap();
+    assert_eq!(database.backend(), StorageBackend::Sqlite);
+    assert_eq!(storage::migrate(&files, &database).unwrap(), 2);
+
+    assert!(files.list("jobs").unwrap().is_empty());
+    assert!(!dir.path().join("cache/scans.json").exists());
+    assert_eq!(
+        database.get("jobs", "job-1").unwrap().as_deref(),
+        Some("{\"id\":1}")
+    );
+
+    assert_eq!(storage::migrate(&database, &files).unwrap(), 2);
+    assert_eq!(
+        files.get("cache", "scans").unwrap().as_deref(),
+        Some("{\"entries\":{}}")
+    );
+}
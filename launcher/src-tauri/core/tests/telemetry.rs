@@ -0,0 +1,38 @@
+mod common;
+
+use launcher_core::error::ErrorKind;
+use launcher_core::telemetry::{self, Usage};
+
+#[test]
+fn usage_counts_features_and_error_kinds() {
+    let mut usage = Usage::default();
+    assert!(usage.is_empty());
+
+    usage.record("Build bundle", None, 2_000);
+    usage.record("Build bundle", Some(ErrorKind::Unity), 3_000);
+    usage.record("Upload bundle", Some(ErrorKind::Network), 4_000);
+
+    assert_eq!(usage.since_ms, 2_000);
+    assert_eq!(usage.features["Build bundle"], 2);
+    assert_eq!(usage.features["Upload bundle"], 1);
+    assert_eq!(usage.errors["unity"], 1);
+    assert_eq!(usage.errors["network"], 1);
+
+    let mut earlier = Usage::default();
+    earlier.record("Build bundle", Some(ErrorKind::PermissionDenied), 1_000);
+    usage.merge(earlier);
+    assert_eq!(usage.since_ms, 1_000);
+    assert_eq!(usage.features["Build bundle"], 3);
+    assert_eq!(usage.errors["permission_denied"], 1);
+}
+
+#[test]
+fn nothing_is_counted_while_off() {
+    let _dir = common::temp_dir();
+    telemetry::set_enabled(false);
+
+    telemetry::record("Build bundle", Some(ErrorKind::Unity));
+
+    assert!(telemetry::flush().unwrap().is_empty());
+    assert!(!telemetry::usage_path().exists());
+}
@@ -0,0 +1,35 @@
+mod common;
+
+use std::fs;
+
+use launcher_core::error::ErrorKind;
+use launcher_core::triggers::{self, MAX_TRIGGER_BYTES};
+
+#[test]
+fn only_finished_json_files_are_pending() {
+    let dir = common::temp_dir();
+    let commands = dir.path().join(triggers::TRIGGER_DIR);
+    common::write(
+        &commands.join("activate.json"),
+        "{\"command\":\"activate\"}",
+    );
+    common::write(&commands.join("snapshot.json.tmp"), "{\"comm");
+    common::write(&commands.join("README.txt"), "Drop command files here");
+    fs::create_dir_all(commands.join("nested.json")).unwrap();
+
+    let pending = triggers::pending(&commands);
+
+    assert_eq!(pending, vec![commands.join("activate.json")]);
+    assert!(triggers::pending(&dir.path().join("missing")).is_empty());
+}
+
+#[test]
+fn oversized_trigger_files_are_refused() {
+    let dir = common::temp_dir();
+    let path = dir.path().join("huge.json");
+    common::write(&path, &" ".repeat(MAX_TRIGGER_BYTES as usize + 1));
+
+    let error = triggers::read(&path).unwrap_err();
+
+    assert_eq!(error.kind, ErrorKind::InvalidInput);
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use launcher_core::bridge;
+use launcher_core::config::{DEFAULT_CONTROL_API_PORT, DEFAULT_PREVIEW_SERVER_PORT};
+use launcher_core::user::{self, Instance};
+
+fn instance(name: &str, updated_ms: i64) -> Instance {
+    Instance {
+        user: name.to_string(),
+        host: "studio-pc".to_string(),
+        pid: std::process::id(),
+        launcher_dir: format!("/home/{}/.config/banter-mcp", name),
+        ports: vec![user::port_for(DEFAULT_PREVIEW_SERVER_PORT, name)],
+        started_ms: updated_ms,
+        updated_ms,
+    }
+}
+
+#[test]
+fn slots_are_stable_and_ignore_case() {
+    assert_eq!(user::slot_for("Alice"), user::slot_for("alice"));
+    assert_eq!(user::slot_for("alice"), user::slot_for("alice"));
+    for name in ["alice", "bob", "carol", "unknown", ""] {
+        assert!(user::slot_for(name) < user::SLOTS);
+    }
+}
+
+#[test]
+fn users_get_different_ports() {
+    assert_ne!(
+        user::port_for(DEFAULT_PREVIEW_SERVER_PORT, "alice"),
+        user::port_for(DEFAULT_PREVIEW_SERVER_PORT, "bob")
+    );
+}
+
+#[test]
+fn preview_and_control_ports_never_meet() {
+    for a in ["alice", "bob", "carol", "dave"] {
+        for b in ["alice", "bob", "carol", "dave"] {
+            assert_ne!(
+                user::port_for(DEFAULT_PREVIEW_SERVER_PORT, a),
+                user::port_for(DEFAULT_CONTROL_API_PORT, b)
+            );
+        }
+    }
+}
+
+#[test]
+fn other_users_live_instances_are_found() {
+    let dir = common::temp_dir();
+    user::set_registry_dir(dir.path().join("instances"));
+
+    user::announce(&instance("alice", bridge::now_ms())).unwrap();
+    user::announce(&instance(
+        "bob",
+        bridge::now_ms() - user::STALE_AFTER_MS - 1,
+    ))
+    .unwrap();
+    user::announce(&instance("carol", bridge::now_ms())).unwrap();
+
+    let others: Vec<String> = user::other_instances("Carol")
+        .into_iter()
+        .map(|i| i.user)
+        .collect();
+    assert_eq!(others, vec!["alice"]);
+
+    user::withdraw("alice").unwrap();
+    assert!(!user::instance_path("alice").exists());
+    assert!(user::other_instances("carol").is_empty());
+}
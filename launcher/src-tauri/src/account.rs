@@ -0,0 +1,372 @@
+//! Banter account login (OAuth device-code flow) with tokens kept in the OS keychain
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::error::{ErrorKind, LauncherError};
+use crate::net;
+use crate::state::SharedState;
+use launcher_core::config::NetworkSettings;
+
+pub const KEYCHAIN_SERVICE: &str = "bantworks-mcp";
+const KEYCHAIN_ACCOUNT: &str = "banter-account";
+
+const OAUTH_CLIENT_ID: &str = "bantworks-mcp-launcher";
+
+/// Used when `banter_auth_endpoint` isn't set in the launcher config
+const DEFAULT_AUTH_ENDPOINT: &str = "https://sidequestvr.com/oauth";
+
+const DEVICE_CODE_GRANT: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+/// Tokens as stored in the keychain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredCredentials {
+    access_token: String,
+    refresh_token: Option<String>,
+    /// Unix ms; `None` if the server didn't say
+    expires_at: Option<i64>,
+    username: Option<String>,
+}
+
+/// What the user needs to complete a device-code login
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct DeviceLogin {
+    pub user_code: String,
+    pub verification_uri: String,
+    pub verification_uri_complete: Option<String>,
+    pub expires_in: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct AccountStatus {
+    pub logged_in: bool,
+    pub username: Option<String>,
+    pub expires_at: Option<i64>,
+    /// "keychain", "config" (legacy token in launcher-config.json) or `None`
+    pub source: Option<String>,
+}
+
+/// Payload of the `account-login` event emitted when polling finishes
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct LoginEvent {
+    /// "success", "denied", "expired" or "failed"
+    status: String,
+    message: String,
+}
+
+pub fn auth_endpoint(config: &crate::LauncherConfig) -> String {
+    config
+        .banter_auth_endpoint
+        .clone()
+        .unwrap_or_else(|| DEFAULT_AUTH_ENDPOINT.to_string())
+        .trim_end_matches('/')
+        .to_string()
+}
+
+pub fn keychain_error(context: &str, e: keyring::Error) -> LauncherError {
+    LauncherError::internal(format!("{}: {}", context, e))
+        .with_hint("Check that the OS keychain / credential manager is available and unlocked")
+}
+
+fn keychain_entry() -> Result<keyring::Entry, LauncherError> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT)
+        .map_err(|e| keychain_error("Failed to open keychain", e))
+}
+
+fn load_credentials() -> Option<StoredCredentials> {
+    let secret = keychain_entry().ok()?.get_password().ok()?;
+    serde_json::from_str(&secret).ok()
+}
+
+fn store_credentials(credentials: &StoredCredentials) -> Result<(), LauncherError> {
+    let secret = serde_json::to_string(credentials)
+        .map_err(|e| LauncherError::internal(format!("Failed to serialize credentials: {}", e)))?;
+    keychain_entry()?
+        .set_password(&secret)
+        .map_err(|e| keychain_error("Failed to store credentials in keychain", e))
+}
+
+/// POST a form and return the JSON body, including for 4xx OAuth error responses
+fn post_form(
+    network: &NetworkSettings,
+    url: &str,
+    form: &[(&str, &str)],
+) -> Result<serde_json::Value, LauncherError> {
+    let response = match net::agent(network, url).post(url).send_form(form) {
+        Ok(response) => response,
+        Err(ureq::Error::Status(code, response)) if code < 500 => response,
+        Err(e) => {
+            return Err(
+                LauncherError::network(format!("Request to {} failed: {}", url, e)).with_hint(
+                    "Check your internet connection and the Banter auth endpoint in settings",
+                ),
+            )
+        }
+    };
+
+    response
+        .into_json()
+        .map_err(|e| LauncherError::network(format!("Invalid response from {}: {}", url, e)))
+}
+
+fn credentials_from_token_response(
+    token: &serde_json::Value,
+    username: Option<String>,
+) -> Option<StoredCredentials> {
+    let access_token = token.get("access_token")?.as_str()?.to_string();
+    let expires_at = token
+        .get("expires_in")
+        .and_then(|e| e.as_i64())
+        .map(|secs| crate::bridge::now_ms() + secs * 1000);
+
+    Some(StoredCredentials {
+        access_token,
+        refresh_token: token
+            .get("refresh_token")
+            .and_then(|r| r.as_str())
+            .map(|s| s.to_string()),
+        expires_at,
+        username,
+    })
+}
+
+/// Best-effort lookup of the account name for display
+fn fetch_username(network: &NetworkSettings, endpoint: &str, access_token: &str) -> Option<String> {
+    let url = format!("{}/userinfo", endpoint);
+    let info: serde_json::Value = net::agent(network, &url)
+        .get(&url)
+        .set("Authorization", &format!("Bearer {}", access_token))
+        .call()
+        .ok()?
+        .into_json()
+        .ok()?;
+
+    ["preferred_username", "username", "name"]
+        .iter()
+        .find_map(|k| info.get(*k).and_then(|v| v.as_str()))
+        .map(|s| s.to_string())
+}
+
+/// Poll the token endpoint until the user approves, denies or the code expires
+fn poll_for_token(
+    network: &NetworkSettings,
+    endpoint: &str,
+    device_code: &str,
+    mut interval: u64,
+    expires_in: u64,
+) -> Result<StoredCredentials, (String, String)> {
+    let token_url = format!("{}/token", endpoint);
+    let mut waited = 0;
+
+    while waited < expires_in {
+        thread::sleep(Duration::from_secs(interval));
+        waited += interval;
+
+        let response = post_form(
+            network,
+            &token_url,
+            &[
+                ("grant_type", DEVICE_CODE_GRANT),
+                ("device_code", device_code),
+                ("client_id", OAUTH_CLIENT_ID),
+            ],
+        )
+        .map_err(|e| ("failed".to_string(), e.message))?;
+
+        match response.get("error").and_then(|e| e.as_str()) {
+            None => {
+                let access_token = response
+                    .get("access_token")
+                    .and_then(|t| t.as_str())
+                    .unwrap_or_default();
+                let username = fetch_username(network, endpoint, access_token);
+                return credentials_from_token_response(&response, username).ok_or((
+                    "failed".to_string(),
+                    "Token response had no access_token".to_string(),
+                ));
+            }
+            Some("authorization_pending") => {}
+            Some("slow_down") => interval += 5,
+            Some("access_denied") => {
+                return Err(("denied".to_string(), "Login was denied".to_string()))
+            }
+            Some("expired_token") => break,
+            Some(other) => return Err(("failed".to_string(), format!("Login failed: {}", other))),
+        }
+    }
+
+    Err((
+        "expired".to_string(),
+        "Login code expired before it was approved".to_string(),
+    ))
+}
+
+/// Exchange a refresh token for a new access token
+fn refresh(
+    config: &crate::LauncherConfig,
+    credentials: &StoredCredentials,
+) -> Option<StoredCredentials> {
+    let refresh_token = credentials.refresh_token.as_deref()?;
+    let response = post_form(
+        &config.network,
+        &format!("{}/token", auth_endpoint(config)),
+        &[
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token),
+            ("client_id", OAUTH_CLIENT_ID),
+        ],
+    )
+    .ok()?;
+
+    let mut refreshed = credentials_from_token_response(&response, credentials.username.clone())?;
+    if refreshed.refresh_token.is_none() {
+        refreshed.refresh_token = credentials.refresh_token.clone();
+    }
+    store_credentials(&refreshed).ok()?;
+    Some(refreshed)
+}
+
+/// Access token for Banter APIs: the keychain login (refreshed if expired),
+/// falling back to a token pasted into the launcher config
+pub fn access_token(config: &crate::LauncherConfig) -> Option<String> {
+    if let Some(credentials) = load_credentials() {
+        let expired = credentials
+            .expires_at
+            .map(|at| at <= crate::bridge::now_ms() + 60_000)
+            .unwrap_or(false);
+        if !expired {
+            return Some(credentials.access_token);
+        }
+        if let Some(refreshed) = refresh(config, &credentials) {
+            return Some(refreshed.access_token);
+        }
+    }
+
+    config.banter_upload_token.clone().filter(|t| !t.is_empty())
+}
+
+/// Start a device-code login. Completion is reported via the `account-login` event.
+#[tauri::command]
+#[specta::specta]
+pub async fn start_login(
+    app: AppHandle,
+    state: tauri::State<'_, SharedState>,
+) -> Result<DeviceLogin, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Login", move || {
+        let config = state.config();
+        let endpoint = auth_endpoint(&config);
+
+        let response = post_form(
+            &config.network,
+            &format!("{}/device/code", endpoint),
+            &[("client_id", OAUTH_CLIENT_ID), ("scope", "upload")],
+        )?;
+
+        let field = |key: &str| {
+            response
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+        };
+        let missing = |what: &str| {
+            LauncherError::new(
+                ErrorKind::Auth,
+                format!("Device authorization returned no {}", what),
+            )
+        };
+        let device_code = field("device_code").ok_or_else(|| missing("code"))?;
+        let login = DeviceLogin {
+            user_code: field("user_code").ok_or_else(|| missing("user code"))?,
+            verification_uri: field("verification_uri")
+                .ok_or_else(|| missing("verification URL"))?,
+            verification_uri_complete: field("verification_uri_complete"),
+            expires_in: response
+                .get("expires_in")
+                .and_then(|e| e.as_u64())
+                .unwrap_or(600),
+        };
+        let interval = response
+            .get("interval")
+            .and_then(|i| i.as_u64())
+            .unwrap_or(5);
+        let expires_in = login.expires_in;
+        let network = config.network.clone();
+
+        thread::spawn(move || {
+            let event =
+                match poll_for_token(&network, &endpoint, &device_code, interval, expires_in) {
+                    Ok(credentials) => match store_credentials(&credentials) {
+                        Ok(()) => LoginEvent {
+                            status: "success".to_string(),
+                            message: credentials
+                                .username
+                                .map(|u| format!("Logged in as {}", u))
+                                .unwrap_or_else(|| "Logged in".to_string()),
+                        },
+                        Err(e) => LoginEvent {
+                            status: "failed".to_string(),
+                            message: e.message,
+                        },
+                    },
+                    Err((status, message)) => LoginEvent { status, message },
+                };
+            let _ = app.emit("account-login", event);
+        });
+
+        Ok(login)
+    })
+    .await
+}
+
+/// Report whether Banter credentials are available
+#[tauri::command]
+#[specta::specta]
+pub async fn get_account_status(
+    state: tauri::State<'_, SharedState>,
+) -> Result<AccountStatus, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Account status", move || {
+        if let Some(credentials) = load_credentials() {
+            return Ok(AccountStatus {
+                logged_in: true,
+                username: credentials.username,
+                expires_at: credentials.expires_at,
+                source: Some("keychain".to_string()),
+            });
+        }
+
+        let config = state.config();
+        let has_config_token = config
+            .banter_upload_token
+            .as_deref()
+            .is_some_and(|t| !t.is_empty());
+
+        Ok(AccountStatus {
+            logged_in: has_config_token,
+            username: None,
+            expires_at: None,
+            source: has_config_token.then(|| "config".to_string()),
+        })
+    })
+    .await
+}
+
+/// Remove stored Banter credentials from the keychain
+#[tauri::command]
+#[specta::specta]
+pub async fn logout() -> Result<(), LauncherError> {
+    crate::commands::blocking("Logout", move || {
+        match keychain_entry()?.delete_password() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(keychain_error(
+                "Failed to remove credentials from keychain",
+                e,
+            )),
+        }
+    })
+    .await
+}
@@ -0,0 +1,289 @@
+//! Prefab, material and script inventory of a Unity project
+//!
+//! `list_project_assets` tells the UI and generated context files what
+//! building blocks a project already has: every prefab, material and script
+//! under Assets with its GUID and size. The index is a `ProjectCache` in the
+//! store's `cache/assets` document. While no folder it walked has changed, a
+//! call only re-stats the files it knows about; a folder that
+//! changed is walked again, and GUIDs are read from `.meta` files only for
+//! files that are new or whose `.meta` was rewritten.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::error::LauncherError;
+use crate::project_cache::{self, mtime_ms, ProjectCache};
+use crate::state::SharedState;
+use crate::{sanitize, unity};
+
+/// Asset kinds and the file extension of each
+pub const KINDS: &[(&str, &str)] = &[("prefab", "prefab"), ("material", "mat"), ("script", "cs")];
+
+/// One prefab, material or script
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ProjectAsset {
+    pub kind: String,
+    /// Relative to the project, with forward slashes
+    pub path: String,
+    /// From the `.meta` file; `None` until Unity has imported the asset
+    pub guid: Option<String>,
+    pub size: u64,
+    /// Unix ms
+    pub modified_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct AssetInventory {
+    pub project_path: String,
+    /// Sorted by kind, then path
+    pub assets: Vec<ProjectAsset>,
+    /// Unix ms
+    pub scanned_at: i64,
+    /// Served from the index without walking the project
+    pub cached: bool,
+}
+
+/// A file as it was when last indexed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedFile {
+    asset: ProjectAsset,
+    /// mtime of the `.meta` file the GUID was read from
+    meta_modified_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ProjectIndex {
+    /// Keyed by relative path
+    files: BTreeMap<String, IndexedFile>,
+    /// mtime (Unix ms) of every walked directory
+    mtimes: BTreeMap<String, i64>,
+    scanned_at: i64,
+}
+
+/// Document of the `cache` store collection (see `storage`)
+const CACHE_KEY: &str = "assets";
+
+fn kind_of(path: &Path) -> Option<&'static str> {
+    let extension = path.extension().and_then(|e| e.to_str())?;
+    KINDS
+        .iter()
+        .find(|(_, ext)| *ext == extension)
+        .map(|(kind, _)| *kind)
+}
+
+fn meta_path(path: &Path) -> PathBuf {
+    let mut meta = path.as_os_str().to_owned();
+    meta.push(".meta");
+    PathBuf::from(meta)
+}
+
+/// `kinds` checked against `KINDS`; empty or `None` means all of them
+fn parse_kinds(kinds: Option<Vec<String>>) -> Result<BTreeSet<String>, LauncherError> {
+    let kinds = kinds.unwrap_or_default();
+    for kind in &kinds {
+        if !KINDS.iter().any(|(name, _)| name == kind) {
+            return Err(
+                LauncherError::invalid(format!("Unknown asset kind: {}", kind)).with_hint(format!(
+                    "Use {}",
+                    KINDS
+                        .iter()
+                        .map(|(name, _)| *name)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )),
+            );
+        }
+    }
+    Ok(kinds.into_iter().collect())
+}
+
+impl ProjectIndex {
+    /// Index `path` as it is now, reusing what `previous` knew when its
+    /// `.meta` file hasn't changed
+    fn index_file(
+        project: &Path,
+        path: &Path,
+        kind: &str,
+        previous: Option<&IndexedFile>,
+    ) -> Option<(String, IndexedFile)> {
+        let metadata = path.metadata().ok()?;
+        let relative = path
+            .strip_prefix(project)
+            .ok()?
+            .to_string_lossy()
+            .replace('\\', "/");
+        let meta = meta_path(path);
+        let meta_modified_at = mtime_ms(&meta);
+        let guid = match previous {
+            Some(previous) if previous.meta_modified_at == meta_modified_at => {
+                previous.asset.guid.clone()
+            }
+            _ => fs::read_to_string(&meta)
+                .ok()
+                .and_then(|m| unity::meta_guid(&m)),
+        };
+        let modified_at = metadata
+            .modified()
+            .ok()
+            .and_then(|m| m.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+        Some((
+            relative.clone(),
+            IndexedFile {
+                asset: ProjectAsset {
+                    kind: kind.to_string(),
+                    path: relative,
+                    guid,
+                    size: metadata.len(),
+                    modified_at,
+                },
+                meta_modified_at,
+            },
+        ))
+    }
+
+    /// Walk the project's Assets folder again
+    fn rebuild(&mut self, project: &Path) {
+        let mut files = BTreeMap::new();
+        let mut mtimes = BTreeMap::new();
+        let mut pending = vec![project.join("Assets")];
+
+        while let Some(dir) = pending.pop() {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            if let Some(mtime) = mtime_ms(&dir) {
+                mtimes.insert(dir.to_string_lossy().to_string(), mtime);
+            }
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    pending.push(path);
+                } else if let Some(kind) = kind_of(&path) {
+                    let relative = path
+                        .strip_prefix(project)
+                        .map(|p| p.to_string_lossy().replace('\\', "/"))
+                        .unwrap_or_default();
+                    if let Some((key, file)) =
+                        Self::index_file(project, &path, kind, self.files.get(&relative))
+                    {
+                        files.insert(key, file);
+                    }
+                }
+            }
+        }
+
+        self.files = files;
+        self.mtimes = mtimes;
+        self.scanned_at = crate::bridge::now_ms();
+    }
+
+    /// Re-stat the known files without walking, since edits change sizes but
+    /// not folder mtimes; returns whether any entry changed
+    fn restat(&mut self, project: &Path) -> bool {
+        let known: Vec<IndexedFile> = self.files.values().cloned().collect();
+        let mut changed = false;
+        for previous in known {
+            let path = project.join(&previous.asset.path);
+            match Self::index_file(project, &path, &previous.asset.kind, Some(&previous)) {
+                Some((key, file)) => {
+                    changed |= file.asset.size != previous.asset.size
+                        || file.asset.modified_at != previous.asset.modified_at
+                        || file.asset.guid != previous.asset.guid;
+                    self.files.insert(key, file);
+                }
+                None => {
+                    self.files.remove(&previous.asset.path);
+                    changed = true;
+                }
+            }
+        }
+        changed
+    }
+}
+
+/// Asset indexes shared between commands, kept in `AppState`
+pub struct AssetCache(ProjectCache<ProjectIndex>);
+
+impl Default for AssetCache {
+    fn default() -> Self {
+        AssetCache(ProjectCache::new(CACHE_KEY))
+    }
+}
+
+impl AssetCache {
+    /// The project's assets of `kinds` (all when empty), walking only what
+    /// changed since the last call unless `refresh` asks for a full walk
+    pub fn inventory(
+        &self,
+        project: &Path,
+        kinds: &BTreeSet<String>,
+        refresh: bool,
+    ) -> AssetInventory {
+        self.0.with(project, |index| {
+            let cached = !refresh && project_cache::is_current(&index.mtimes);
+            let changed = if cached {
+                index.restat(project)
+            } else {
+                if refresh {
+                    index.files.clear();
+                }
+                index.rebuild(project);
+                true
+            };
+
+            let mut assets: Vec<ProjectAsset> = index
+                .files
+                .values()
+                .filter(|f| kinds.is_empty() || kinds.contains(&f.asset.kind))
+                .map(|f| f.asset.clone())
+                .collect();
+            assets.sort_by(|a, b| a.kind.cmp(&b.kind).then_with(|| a.path.cmp(&b.path)));
+            let inventory = AssetInventory {
+                project_path: project.to_string_lossy().to_string(),
+                assets,
+                scanned_at: index.scanned_at,
+                cached,
+            };
+            (inventory, changed)
+        })
+    }
+
+    /// Forget indexes at or below `path`, or everything with `None`
+    pub fn invalidate(&self, path: Option<&Path>) {
+        self.0.invalidate(path)
+    }
+}
+
+/// List a project's prefabs, materials and scripts with their GUIDs and
+/// sizes. `kinds` narrows the list to some of `prefab`, `material` and
+/// `script`.
+#[tauri::command]
+#[specta::specta]
+pub async fn list_project_assets(
+    state: tauri::State<'_, SharedState>,
+    project: String,
+    kinds: Option<Vec<String>>,
+    refresh: Option<bool>,
+) -> Result<AssetInventory, LauncherError> {
+    let project = sanitize::path("Unity project path", &project)?;
+    let kinds = parse_kinds(kinds)?;
+    let state = state.inner().clone();
+    crate::commands::blocking("Asset inventory", move || {
+        if !project.join("Assets").is_dir() {
+            return Err(
+                LauncherError::not_found("Not a Unity project (no Assets folder)")
+                    .with_path(&project),
+            );
+        }
+        Ok(state
+            .assets
+            .inventory(&project, &kinds, refresh.unwrap_or(false)))
+    })
+    .await
+}
@@ -0,0 +1,78 @@
+//! Backup commands; the backup store itself is `launcher_core::backup`
+
+use std::cmp::Reverse;
+use std::fs;
+use std::path::PathBuf;
+
+pub use launcher_core::backup::*;
+
+use crate::error::LauncherError;
+use crate::state::SharedState;
+
+/// List backups, optionally of a single kind, newest first
+#[tauri::command]
+#[specta::specta]
+pub async fn list_backups(kind: Option<String>) -> Result<Vec<BackupEntry>, LauncherError> {
+    crate::commands::blocking("List backups", move || {
+        let kinds = match kind {
+            Some(kind) => vec![BackupKind::parse(&kind)?],
+            None => BackupKind::ALL.to_vec(),
+        };
+
+        let mut all: Vec<BackupEntry> = kinds.into_iter().flat_map(entries).collect();
+        all.sort_by_key(|e| Reverse(e.created_at));
+        Ok(all)
+    })
+    .await
+}
+
+/// Put a backup back in place. The current file is backed up first so the
+/// restore itself can be undone.
+#[tauri::command]
+#[specta::specta]
+pub async fn restore_backup(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, SharedState>,
+    id: String,
+) -> Result<BackupEntry, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Restore backup", move || {
+        crate::sanitize::identifier("Backup id", &id)?;
+
+        let entry = BackupKind::ALL
+            .into_iter()
+            .flat_map(entries)
+            .find(|e| e.id == id)
+            .ok_or_else(|| {
+                LauncherError::not_found(format!("Backup not found: {}", id))
+                    .with_hint("It may have been pruned by the retention policy")
+            })?;
+
+        if entry.kind == BackupKind::LauncherConfig {
+            // Pending edits go into the pre-restore backup rather than over the restore
+            state.flush()?;
+        }
+
+        let original = PathBuf::from(&entry.original_path);
+        crate::write_guard::check_write(&original)?;
+        create(entry.kind, &original, &state.config().backup_retention)?;
+
+        if let Some(parent) = original.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| LauncherError::io("Failed to create directory", parent, e))?;
+        }
+        fs::copy(
+            kind_dir(entry.kind).join(format!("{}.bak", entry.id)),
+            &original,
+        )
+        .map_err(|e| LauncherError::io("Failed to restore", &original, e))?;
+
+        if entry.kind == BackupKind::LauncherConfig {
+            let config = state.reload()?;
+            crate::fs_scope::sync(&app, &config);
+        }
+
+        Ok(entry)
+    })
+    .await
+}
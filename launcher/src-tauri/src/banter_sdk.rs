@@ -0,0 +1,301 @@
+//! Banter SDK version detection and compatibility checks
+
+use serde::Serialize;
+use specta::Type;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::state::SharedState;
+
+/// Unity package id the Banter SDK is published under
+pub const BANTER_PACKAGE_ID: &str = "com.sidequest.banter";
+
+/// Detected Banter SDK package in a Unity project
+#[derive(Debug, Clone, Serialize, Default, Type)]
+pub struct BanterSdkInfo {
+    pub package_id: Option<String>,
+    pub version: Option<String>,
+    /// Where the version came from: "lock", "manifest" or "embedded"
+    pub source: Option<String>,
+    /// Raw dependency reference from manifest.json (version, git URL or file: path)
+    pub reference: Option<String>,
+}
+
+/// A known problem with a combination of SDK / server / bridge versions
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct CompatIssue {
+    pub severity: String,
+    pub message: String,
+}
+
+/// Versions in use for a channel plus any compatibility issues found
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct CompatibilityReport {
+    pub sdk: BanterSdkInfo,
+    pub server_version: Option<String>,
+    pub bridge_version: Option<String>,
+    pub issues: Vec<CompatIssue>,
+}
+
+type Version = (u64, u64, u64);
+
+/// Inclusive-min / exclusive-max version range; `None` bounds are open
+#[derive(Clone, Copy)]
+struct Range {
+    min: Option<Version>,
+    max: Option<Version>,
+}
+
+impl Range {
+    const ANY: Range = Range {
+        min: None,
+        max: None,
+    };
+
+    fn contains(&self, v: Version) -> bool {
+        !matches!(self.min, Some(min) if v < min) && !matches!(self.max, Some(max) if v >= max)
+    }
+}
+
+/// One row of the compatibility table. A rule fires when every detected version
+/// falls inside its range; an unknown version only matches an open range.
+struct CompatRule {
+    sdk: Range,
+    server: Range,
+    bridge: Range,
+    severity: &'static str,
+    message: &'static str,
+}
+
+/// Known-broken combinations. Add a row here whenever a release breaks against
+/// an older counterpart.
+const COMPAT_TABLE: &[CompatRule] = &[CompatRule {
+    sdk: Range::ANY,
+    server: Range {
+        min: Some((1, 1, 0)),
+        max: None,
+    },
+    bridge: Range {
+        min: None,
+        max: Some((1, 3, 0)),
+    },
+    severity: "error",
+    message: "MCP server 1.1+ requires bridge 1.3+ - reinstall the Unity extension",
+}];
+
+/// Parse "1.2.3", "v1.2", "1.2.3-preview.4" etc. into a comparable triple
+pub fn parse_version(raw: &str) -> Option<Version> {
+    let raw = raw.trim();
+    let raw = raw.rsplit('#').next().unwrap_or(raw);
+    let raw = raw.trim_start_matches(['v', 'V']);
+
+    let numeric: String = raw
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+
+    let mut parts = numeric.split('.').filter(|p| !p.is_empty());
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().and_then(|p| p.parse().ok()).unwrap_or(0);
+    let patch = parts.next().and_then(|p| p.parse().ok()).unwrap_or(0);
+
+    Some((major, minor, patch))
+}
+
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn is_banter_package(id: &str) -> bool {
+    id == BANTER_PACKAGE_ID || id.to_lowercase().contains("banter")
+}
+
+/// Detect the Banter SDK version from Packages/packages-lock.json, manifest.json
+/// or an embedded package folder
+pub fn detect_sdk(unity_project_path: &Path) -> BanterSdkInfo {
+    let packages_dir = unity_project_path.join("Packages");
+    let mut info = BanterSdkInfo::default();
+
+    if let Some(manifest) = read_json(&packages_dir.join("manifest.json")) {
+        if let Some(deps) = manifest.get("dependencies").and_then(|d| d.as_object()) {
+            if let Some((id, reference)) = deps.iter().find(|(id, _)| is_banter_package(id)) {
+                info.package_id = Some(id.clone());
+                info.reference = reference.as_str().map(|s| s.to_string());
+            }
+        }
+    }
+
+    // The lock file has the resolved version even for git/file references
+    if let Some(lock) = read_json(&packages_dir.join("packages-lock.json")) {
+        if let Some(deps) = lock.get("dependencies").and_then(|d| d.as_object()) {
+            let entry = match &info.package_id {
+                Some(id) => deps.get(id).map(|entry| (id, entry)),
+                None => deps.iter().find(|(id, _)| is_banter_package(id)),
+            };
+            if let Some((id, entry)) = entry {
+                let version = entry.get("version").and_then(|v| v.as_str());
+                if let Some(version) = version.filter(|v| parse_version(v).is_some()) {
+                    info.package_id = Some(id.clone());
+                    info.version = Some(version.to_string());
+                    info.source = Some("lock".to_string());
+                    return info;
+                }
+            }
+        }
+    }
+
+    if let Some(reference) = &info.reference {
+        if let Some(path) = reference.strip_prefix("file:") {
+            let package_json = packages_dir.join(path).join("package.json");
+            if let Some(version) = read_package_version(&package_json) {
+                info.version = Some(version);
+                info.source = Some("embedded".to_string());
+                return info;
+            }
+        } else if parse_version(reference).is_some() {
+            info.version = Some(reference.clone());
+            info.source = Some("manifest".to_string());
+            return info;
+        }
+    }
+
+    // Embedded packages live directly under Packages/ and are not listed in the manifest
+    if let Ok(entries) = fs::read_dir(&packages_dir) {
+        for entry in entries.flatten() {
+            let package_json = entry.path().join("package.json");
+            let Some(json) = read_json(&package_json) else {
+                continue;
+            };
+            let Some(name) = json.get("name").and_then(|n| n.as_str()) else {
+                continue;
+            };
+            if is_banter_package(name) {
+                info.package_id = Some(name.to_string());
+                info.version = json
+                    .get("version")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string());
+                info.source = Some("embedded".to_string());
+                break;
+            }
+        }
+    }
+
+    info
+}
+
+fn read_package_version(package_json: &Path) -> Option<String> {
+    read_json(package_json)?
+        .get("version")?
+        .as_str()
+        .map(|s| s.to_string())
+}
+
+/// Read the MCP server version from the package.json next to its dist folder
+pub fn read_server_version(mcp_server_path: &str) -> Option<String> {
+    let server = PathBuf::from(mcp_server_path);
+    server
+        .ancestors()
+        .skip(1)
+        .take(3)
+        .map(|dir| dir.join("package.json"))
+        .find_map(|p| read_package_version(&p))
+}
+
+/// Read the installed bridge version from its `BridgeVersion` constant.
+/// Bridges installed before the constant existed report "0.0.0".
+pub fn read_bridge_version(unity_project_path: &Path) -> Option<String> {
+    read_bridge_version_file(
+        &unity_project_path
+            .join("Assets")
+            .join("Editor")
+            .join("BanterMCPBridge.cs"),
+    )
+}
+
+/// `read_bridge_version` for a `BanterMCPBridge.cs` anywhere, e.g. the copy
+/// shipped with the MCP server
+pub fn read_bridge_version_file(bridge: &Path) -> Option<String> {
+    let source = fs::read_to_string(bridge).ok()?;
+
+    let version = source
+        .lines()
+        .find(|l| l.contains("BridgeVersion") && l.contains('='))
+        .and_then(|l| l.split('"').nth(1))
+        .unwrap_or("0.0.0");
+
+    Some(version.to_string())
+}
+
+/// Check detected versions against the compatibility table
+pub fn check_compatibility(
+    sdk_version: Option<&str>,
+    server_version: Option<&str>,
+    bridge_version: Option<&str>,
+) -> Vec<CompatIssue> {
+    let sdk = sdk_version.and_then(parse_version);
+    let server = server_version.and_then(parse_version);
+    let bridge = bridge_version.and_then(parse_version);
+
+    let matches = |range: &Range, version: Option<Version>| match version {
+        Some(v) => range.contains(v),
+        None => range.min.is_none() && range.max.is_none(),
+    };
+
+    COMPAT_TABLE
+        .iter()
+        .filter(|rule| {
+            matches(&rule.sdk, sdk)
+                && matches(&rule.server, server)
+                && matches(&rule.bridge, bridge)
+        })
+        .map(|rule| CompatIssue {
+            severity: rule.severity.to_string(),
+            message: rule.message.to_string(),
+        })
+        .collect()
+}
+
+/// Build the compatibility report for a project
+pub fn compatibility_report(
+    unity_project_path: &Path,
+    mcp_server_path: &str,
+) -> CompatibilityReport {
+    let sdk = detect_sdk(unity_project_path);
+    let server_version = read_server_version(mcp_server_path);
+    let bridge_version = read_bridge_version(unity_project_path);
+
+    let issues = check_compatibility(
+        sdk.version.as_deref(),
+        server_version.as_deref(),
+        bridge_version.as_deref(),
+    );
+
+    CompatibilityReport {
+        sdk,
+        server_version,
+        bridge_version,
+        issues,
+    }
+}
+
+/// Get Banter SDK / server / bridge versions for a channel
+#[tauri::command]
+#[specta::specta]
+pub async fn get_banter_sdk_info(
+    state: tauri::State<'_, SharedState>,
+    channel_id: String,
+) -> Result<CompatibilityReport, crate::error::LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("SDK info", move || {
+        let config = state.config();
+        let channel = crate::find_channel(&config, &channel_id)?;
+
+        Ok(compatibility_report(
+            Path::new(&channel.unity_project_path),
+            &config.mcp_server_path,
+        ))
+    })
+    .await
+}
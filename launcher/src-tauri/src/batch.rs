@@ -0,0 +1,347 @@
+//! Transactional batches of setup operations
+//!
+//! `batch` runs a list of operations in order. Before each one its file
+//! effects are discovered with a dry run and the files it will touch are
+//! snapshotted; if any operation fails, every file is put back, the config is
+//! reloaded and a preview server the batch started is stopped, so a setup
+//! script never leaves the machine half-configured.
+//!
+//! ```json
+//! [
+//!   { "op": "add_channel", "name": "Lobby", "scene_path": "C:/Lobby/Assets/Lobby.unity", "activate": true },
+//!   { "op": "install_extension" },
+//!   { "op": "register_clients" },
+//!   { "op": "start_server", "port": 8080 }
+//! ]
+//! ```
+//!
+//! Operations without a `channel_id` apply to the channel added last in the
+//! batch, or else the active channel.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+use crate::error::LauncherError;
+use crate::plan::{FileAction, FilePlan};
+use crate::preview_server::{self, PreviewServerStatus};
+use crate::state::{AppState, SharedState};
+use crate::{fs_scope, hooks, ProjectChannel};
+
+/// One batch at a time, so snapshots never interleave
+static BATCH: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Deserialize, Type)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum BatchOperation {
+    /// Add a scene channel, optionally making it active
+    AddChannel {
+        name: String,
+        scene_path: String,
+        #[serde(default)]
+        activate: bool,
+    },
+    /// Copy the Unity bridge into the channel's project
+    InstallExtension {
+        #[serde(default)]
+        channel_id: Option<String>,
+        /// Defaults to the launcher's MCP root
+        #[serde(default)]
+        mcp_root: Option<String>,
+    },
+    /// Point the MCP clients (Claude Code) at the channel
+    RegisterClients {
+        #[serde(default)]
+        channel_id: Option<String>,
+    },
+    /// Start the preview server for the channel
+    StartServer {
+        #[serde(default)]
+        channel_id: Option<String>,
+        #[serde(default)]
+        port: Option<u16>,
+    },
+}
+
+impl BatchOperation {
+    fn name(&self) -> &'static str {
+        match self {
+            BatchOperation::AddChannel { .. } => "add_channel",
+            BatchOperation::InstallExtension { .. } => "install_extension",
+            BatchOperation::RegisterClients { .. } => "register_clients",
+            BatchOperation::StartServer { .. } => "start_server",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct OperationResult {
+    pub op: String,
+    pub channel_id: Option<String>,
+    pub plan: FilePlan,
+    /// Set by `start_server`
+    pub preview_server: Option<PreviewServerStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct BatchReport {
+    pub operations: Vec<OperationResult>,
+}
+
+/// Contents of the files a batch touches, as they were before it started
+#[derive(Default)]
+struct Snapshot {
+    /// `None`: the file didn't exist
+    files: Vec<(PathBuf, Option<Vec<u8>>)>,
+    /// Directories the batch created
+    dirs: Vec<PathBuf>,
+}
+
+impl Snapshot {
+    /// Remember the current state of every file `plan` would change
+    fn capture(&mut self, plan: &FilePlan) -> Result<(), LauncherError> {
+        for operation in &plan.operations {
+            let path = PathBuf::from(&operation.path);
+            match operation.action {
+                FileAction::Backup => {}
+                FileAction::CreateDir => {
+                    if !path.exists() && !self.dirs.contains(&path) {
+                        self.dirs.push(path);
+                    }
+                }
+                FileAction::Write | FileAction::Copy | FileAction::Delete => {
+                    if self.files.iter().any(|(p, _)| p == &path) {
+                        continue;
+                    }
+                    let content = if path.exists() {
+                        Some(
+                            fs::read(&path)
+                                .map_err(|e| LauncherError::io("Failed to snapshot", &path, e))?,
+                        )
+                    } else {
+                        None
+                    };
+                    self.files.push((path, content));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Put every file back, newest change first. Returns the paths that
+    /// couldn't be restored.
+    fn restore(&self) -> Vec<String> {
+        let mut failed = Vec::new();
+        for (path, content) in self.files.iter().rev() {
+            let result = match content {
+                Some(content) => fs::write(path, content),
+                None if path.exists() => fs::remove_file(path),
+                None => Ok(()),
+            };
+            if let Err(e) = result {
+                failed.push(format!("{} ({})", path.display(), e));
+            }
+        }
+        // Only removes directories that are empty again
+        for dir in self.dirs.iter().rev() {
+            fs::remove_dir(dir).ok();
+        }
+        failed
+    }
+}
+
+/// State carried from one operation to the next
+struct Context<'a> {
+    state: &'a AppState,
+    /// Channel added most recently in this batch
+    added: Option<String>,
+}
+
+impl Context<'_> {
+    fn channel(&self, channel_id: &Option<String>) -> Result<ProjectChannel, LauncherError> {
+        let id = channel_id
+            .clone()
+            .or_else(|| self.added.clone())
+            .or_else(|| self.state.config().active_channel_id)
+            .ok_or_else(|| {
+                LauncherError::invalid("No channel_id given and no channel is active")
+            })?;
+        self.state.channel(&id)
+    }
+}
+
+fn execute(
+    ctx: &mut Context,
+    operation: &BatchOperation,
+    dry_run: bool,
+) -> Result<OperationResult, LauncherError> {
+    let mut result = OperationResult {
+        op: operation.name().to_string(),
+        channel_id: None,
+        plan: FilePlan::new(dry_run),
+        preview_server: None,
+    };
+
+    match operation {
+        BatchOperation::AddChannel {
+            name,
+            scene_path,
+            activate,
+        } => {
+            let channel = crate::add_channel(name.clone(), scene_path.clone())?;
+            ctx.state.update(&mut result.plan, |config| {
+                if *activate {
+                    config.active_channel_id = Some(channel.id.clone());
+                }
+                config.channels.push(channel.clone());
+                Ok(())
+            })?;
+            if !dry_run {
+                ctx.added = Some(channel.id.clone());
+            }
+            result.channel_id = Some(channel.id);
+        }
+        BatchOperation::InstallExtension {
+            channel_id,
+            mcp_root,
+        } => {
+            let channel = ctx.channel(channel_id)?;
+            let mcp_root = match mcp_root {
+                Some(root) => root.clone(),
+                None => crate::get_mcp_root()?,
+            };
+            result.plan = crate::install_unity_extension(
+                channel.unity_project_path.clone(),
+                mcp_root,
+                Some(dry_run),
+                Some(false),
+            )?;
+            result.channel_id = Some(channel.id);
+        }
+        BatchOperation::RegisterClients { channel_id } => {
+            let channel = ctx.channel(channel_id)?;
+            let id = channel.id.clone();
+            let server = ctx.state.config().mcp_server_path;
+            result.plan =
+                crate::update_claude_mcp_config(ctx.state, channel, server, Some(dry_run))?;
+            result.channel_id = Some(id);
+        }
+        BatchOperation::StartServer { channel_id, port } => {
+            let channel = ctx.channel(channel_id)?;
+            if !dry_run {
+                result.preview_server = Some(preview_server::start(ctx.state, &channel.id, *port)?);
+            }
+            result.channel_id = Some(channel.id);
+        }
+    }
+
+    Ok(result)
+}
+
+/// Undo a failed batch and describe what happened
+fn roll_back(
+    state: &AppState,
+    snapshot: &Snapshot,
+    preview_before: &PreviewServerStatus,
+    failed_at: usize,
+    operation: &BatchOperation,
+    error: LauncherError,
+) -> LauncherError {
+    let mut unrestored = snapshot.restore();
+    if let Err(e) = state.reload() {
+        unrestored.push(format!("launcher config ({})", e));
+    }
+
+    let preview_now = preview_server::current_status(state);
+    if preview_now.channel_id != preview_before.channel_id
+        || preview_now.port != preview_before.port
+    {
+        preview_server::stop(state);
+        if let Some(channel_id) = &preview_before.channel_id {
+            if let Err(e) = preview_server::start(state, channel_id, preview_before.port) {
+                unrestored.push(format!("preview server ({})", e));
+            }
+        }
+    }
+
+    let rollback = if unrestored.is_empty() {
+        "changes made by the batch were rolled back".to_string()
+    } else {
+        format!("rollback incomplete: {}", unrestored.join(", "))
+    };
+    let hint = match &error.hint {
+        Some(hint) => format!("{}; {}", hint, rollback),
+        None => rollback,
+    };
+
+    LauncherError {
+        message: format!(
+            "Operation {} ({}) failed: {}",
+            failed_at + 1,
+            operation.name(),
+            error.message
+        ),
+        hint: Some(hint),
+        // The wrapped text no longer matches the catalogue entries
+        ids: Box::default(),
+        ..error
+    }
+}
+
+/// Run operations in order; on the first failure everything is rolled back
+pub fn run(state: &AppState, operations: &[BatchOperation]) -> Result<BatchReport, LauncherError> {
+    let _batch = BATCH.lock().unwrap_or_else(|e| e.into_inner());
+
+    // Snapshots are taken from disk, so it has to match memory
+    state.flush()?;
+    let before = state.config();
+    let preview_before = preview_server::current_status(state);
+
+    let mut snapshot = Snapshot::default();
+    let mut ctx = Context { state, added: None };
+    let mut report = BatchReport {
+        operations: Vec::new(),
+    };
+
+    for (i, operation) in operations.iter().enumerate() {
+        let outcome = execute(&mut ctx, operation, true)
+            .and_then(|preview| snapshot.capture(&preview.plan))
+            .and_then(|_| execute(&mut ctx, operation, false));
+        match outcome {
+            Ok(result) => report.operations.push(result),
+            Err(e) => {
+                return Err(roll_back(
+                    state,
+                    &snapshot,
+                    &preview_before,
+                    i,
+                    operation,
+                    e,
+                ))
+            }
+        }
+    }
+
+    hooks::active_channel_changed(&before, &state.config());
+    Ok(report)
+}
+
+/// Run a sequence of setup operations as one transaction
+#[tauri::command]
+#[specta::specta]
+pub async fn batch(
+    app: AppHandle,
+    state: tauri::State<'_, SharedState>,
+    ops: Vec<BatchOperation>,
+) -> Result<BatchReport, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Batch", move || {
+        let result = run(&state, &ops);
+        fs_scope::sync(&app, &state.config());
+        result
+    })
+    .await
+}
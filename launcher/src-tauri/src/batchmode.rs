@@ -0,0 +1,358 @@
+//! Unity without a running editor
+//!
+//! Editor operations that don't need anyone at the editor (bundle builds,
+//! checking that the extension compiles, listing the project's scenes) go
+//! through the bridge when an editor is connected. Otherwise, unless
+//! `batchmode_fallback` is turned off, the launcher starts Unity itself with
+//! `-batchmode -quit -executeMethod`, streams its log to the launcher logs and
+//! turns a failed exit into an error carrying the log's last lines. The
+//! methods are the bridge's `*Batch` entry points, so the extension has to be
+//! installed; Unity refuses to open a project another editor has open.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::VecDeque;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+use crate::bridge_queue::{self, Priority};
+use crate::error::{ErrorKind, LauncherError};
+use crate::state::SharedState;
+use crate::{
+    banter_sdk, bridge, logs, metrics, stack, unity, write_guard, LauncherConfig, ProjectChannel,
+};
+
+pub const VERIFY_EXTENSION_METHOD: &str = "BantworksMCP.BantworksMCPBridge.VerifyExtensionBatch";
+pub const LIST_SCENES_METHOD: &str = "BantworksMCP.BantworksMCPBridge.ListScenesBatch";
+
+/// Operations with a batchmode log of their own, for `ReadTarget::BatchLog`.
+/// Bundle builds keep theirs per platform.
+pub const OPERATIONS: &[&str] = &["verify-extension", "list-scenes"];
+
+/// Log lines kept to explain a failed run
+const TAIL_LINES: usize = 20;
+
+/// How long a connected editor may take to answer a request
+const BRIDGE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// One `-executeMethod` run
+pub struct Invocation<'a> {
+    pub method: &'a str,
+    /// `-buildTarget`, if the method needs one
+    pub build_target: Option<&'a str>,
+    /// `-name value` pairs the method reads from the command line
+    pub args: Vec<(&'a str, String)>,
+    /// Where Unity's log goes
+    pub log_path: PathBuf,
+}
+
+/// A scene as Unity sees it
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct UnityScene {
+    /// Relative to the project, e.g. `Assets/Scenes/Lobby.unity`
+    pub path: String,
+    #[serde(alias = "inBuildSettings")]
+    pub in_build_settings: bool,
+    /// Ticked in Build Settings
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct SceneList {
+    pub channel_id: String,
+    /// "bridge" or "batchmode"
+    pub mode: String,
+    pub scenes: Vec<UnityScene>,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ExtensionVerification {
+    pub channel_id: String,
+    /// "bridge" (a connected editor runs it) or "batchmode" (Unity compiled
+    /// and ran it headlessly)
+    pub mode: String,
+    pub bridge_version: Option<String>,
+    pub unity_version: Option<String>,
+}
+
+/// Refuse to start Unity when the fallback is turned off
+pub fn ensure_allowed(config: &LauncherConfig) -> Result<(), LauncherError> {
+    if config.batchmode_fallback {
+        return Ok(());
+    }
+    Err(LauncherError::new(
+        ErrorKind::Bridge,
+        "No Unity editor is connected to this project",
+    )
+    .with_hint("Open the project in Unity, or turn batchmode fallback on to run Unity headlessly"))
+}
+
+/// Best effort: a run shouldn't fail because its log can't be written
+fn open_log(path: &Path) -> Option<fs::File> {
+    write_guard::check_write(path).ok()?;
+    fs::create_dir_all(path.parent()?).ok()?;
+    fs::File::create(path).ok()
+}
+
+/// Run Unity headlessly on the channel's project. `on_line` sees every log
+/// line; an error from it (a cancelled job) stops Unity and is returned.
+pub fn run(
+    config: &LauncherConfig,
+    channel: &ProjectChannel,
+    invocation: &Invocation,
+    on_line: &mut dyn FnMut(&str) -> Result<(), LauncherError>,
+) -> Result<(), LauncherError> {
+    ensure_allowed(config)?;
+    stack::check_editor("Unity batchmode")?;
+    let project = Path::new(&channel.unity_project_path);
+    let editor = unity::find_editor(config, project)?;
+
+    let mut command = Command::new(&editor);
+    command
+        .arg("-batchmode")
+        .arg("-quit")
+        .arg("-projectPath")
+        .arg(project);
+    if let Some(target) = invocation.build_target {
+        command.arg("-buildTarget").arg(target);
+    }
+    command.arg("-executeMethod").arg(invocation.method);
+    for (name, value) in &invocation.args {
+        command.arg(name).arg(value);
+    }
+    let mut child = command
+        .arg("-logFile")
+        .arg("-")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| LauncherError::io("Failed to start Unity", &editor, e))?;
+
+    // The full log goes to a file for `read_file_tail`; keep its tail here to explain failures
+    let mut log_file = open_log(&invocation.log_path);
+    let mut tail: VecDeque<String> = VecDeque::new();
+    if let Some(stdout) = child.stdout.take() {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if let Err(e) = on_line(&line) {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(e);
+            }
+            if let Some(file) = log_file.as_mut() {
+                if writeln!(file, "{}", line).is_err() {
+                    log_file = None;
+                }
+            }
+            if tail.len() == TAIL_LINES {
+                tail.pop_front();
+            }
+            tail.push_back(line);
+        }
+    }
+
+    let status = child
+        .wait()
+        .map_err(|e| LauncherError::io("Failed to wait for Unity", &editor, e))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(LauncherError::new(
+            ErrorKind::Unity,
+            format!(
+                "Unity exited with {}:\n{}",
+                status,
+                Vec::from(tail).join("\n")
+            ),
+        )
+        .with_path(&invocation.log_path)
+        .with_hint("Close any editor open on this project - batchmode can't share it"))
+    }
+}
+
+/// A bridge state file written at or after `since`
+fn fresh_state(project: &Path, name: &str, since: i64) -> Option<serde_json::Value> {
+    let state = bridge::read_state(project, name)?;
+    let timestamp = state.get("timestamp").and_then(|t| t.as_i64()).unwrap_or(0);
+    (timestamp >= since).then_some(state)
+}
+
+/// Wait for a connected editor to write `name`
+pub fn await_state(
+    project: &Path,
+    name: &str,
+    since: i64,
+    on_poll: &mut dyn FnMut(&str) -> Result<(), LauncherError>,
+) -> Result<serde_json::Value, LauncherError> {
+    let deadline = Instant::now() + BRIDGE_TIMEOUT;
+    let round_trip = || Duration::from_millis((bridge::now_ms() - since).max(0) as u64);
+    while Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(250));
+        on_poll("")?;
+        if let Some(state) = fresh_state(project, name, since) {
+            metrics::record_bridge(name, round_trip(), true);
+            return Ok(state);
+        }
+    }
+    metrics::record_bridge(name, round_trip(), false);
+    Err(LauncherError::new(
+        ErrorKind::Bridge,
+        format!("Timed out waiting for Unity to write {}", name),
+    )
+    .with_hint("Make sure the editor isn't blocked by a modal dialog, then retry"))
+}
+
+fn missing_state(name: &str, log_path: &Path) -> LauncherError {
+    LauncherError::new(
+        ErrorKind::Unity,
+        format!("Unity finished without writing {}", name),
+    )
+    .with_path(log_path)
+    .with_hint("Update the Unity extension; older bridges lack the batchmode entry points")
+}
+
+fn require_extension(channel: &ProjectChannel) -> Result<(), LauncherError> {
+    if crate::check_unity_extension(channel.unity_project_path.clone())? {
+        return Ok(());
+    }
+    Err(LauncherError::not_found(format!(
+        "The Unity extension isn't installed in {}",
+        channel.unity_project_path
+    ))
+    .with_hint("Install the Unity extension first"))
+}
+
+/// Check that the extension compiles and runs in the channel's project
+pub fn verify_extension(
+    config: &LauncherConfig,
+    channel: &ProjectChannel,
+    on_line: &mut dyn FnMut(&str) -> Result<(), LauncherError>,
+) -> Result<ExtensionVerification, LauncherError> {
+    require_extension(channel)?;
+    let project = Path::new(&channel.unity_project_path);
+    if bridge::is_editor_connected(project) {
+        // A heartbeat means the bridge compiled and is running
+        return Ok(ExtensionVerification {
+            channel_id: channel.id.clone(),
+            mode: "bridge".to_string(),
+            bridge_version: banter_sdk::read_bridge_version(project),
+            unity_version: unity::project_editor_version(project),
+        });
+    }
+
+    let started = bridge::now_ms();
+    let log_path = logs::batch_log_path(&channel.id, "verify-extension");
+    let invocation = Invocation {
+        method: VERIFY_EXTENSION_METHOD,
+        build_target: None,
+        args: Vec::new(),
+        log_path: log_path.clone(),
+    };
+    run(config, channel, &invocation, on_line)?;
+    let check = fresh_state(project, "extension-check.json", started)
+        .ok_or_else(|| missing_state("extension-check.json", &log_path))?;
+    let field = |key: &str| check.get(key).and_then(|v| v.as_str()).map(str::to_string);
+    Ok(ExtensionVerification {
+        channel_id: channel.id.clone(),
+        mode: "batchmode".to_string(),
+        bridge_version: field("bridgeVersion"),
+        unity_version: field("unityVersion"),
+    })
+}
+
+/// Scenes under Assets and their Build Settings entries, from Unity itself
+pub fn list_scenes(
+    config: &LauncherConfig,
+    channel: &ProjectChannel,
+    on_line: &mut dyn FnMut(&str) -> Result<(), LauncherError>,
+) -> Result<SceneList, LauncherError> {
+    require_extension(channel)?;
+    let project = Path::new(&channel.unity_project_path);
+    let started = bridge::now_ms();
+    let (mode, state) = if bridge::is_editor_connected(project) {
+        bridge_queue::send(
+            project,
+            Priority::Interactive,
+            &serde_json::json!({ "type": "list_scenes" }),
+        )?;
+        (
+            "bridge",
+            await_state(project, "scenes.json", started, on_line)?,
+        )
+    } else {
+        let log_path = logs::batch_log_path(&channel.id, "list-scenes");
+        let invocation = Invocation {
+            method: LIST_SCENES_METHOD,
+            build_target: None,
+            args: Vec::new(),
+            log_path: log_path.clone(),
+        };
+        run(config, channel, &invocation, on_line)?;
+        let state = fresh_state(project, "scenes.json", started)
+            .ok_or_else(|| missing_state("scenes.json", &log_path))?;
+        ("batchmode", state)
+    };
+
+    let mut scenes: Vec<UnityScene> = state
+        .get("scenes")
+        .cloned()
+        .and_then(|s| serde_json::from_value(s).ok())
+        .unwrap_or_default();
+    scenes.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(SceneList {
+        channel_id: channel.id.clone(),
+        mode: mode.to_string(),
+        scenes,
+    })
+}
+
+/// Check the channel's Unity extension, headlessly if no editor is open
+#[tauri::command]
+#[specta::specta]
+pub async fn verify_unity_extension(
+    app: AppHandle,
+    state: tauri::State<'_, SharedState>,
+    channel_id: String,
+) -> Result<ExtensionVerification, LauncherError> {
+    crate::sanitize::channel_id(&channel_id)?;
+    let state = state.inner().clone();
+    crate::commands::blocking("Extension check", move || {
+        let config = state.config();
+        let channel = crate::find_channel(&config, &channel_id)?;
+        let label = format!("Check Unity extension in {}", channel.name);
+        state
+            .jobs
+            .run(&app, "verify_extension", label, None, move |job| {
+                verify_extension(&config, &channel, &mut |_| job.check_cancelled())
+            })
+    })
+    .await
+}
+
+/// The channel's scenes as Unity lists them, headlessly if no editor is open
+#[tauri::command]
+#[specta::specta]
+pub async fn list_unity_scenes(
+    app: AppHandle,
+    state: tauri::State<'_, SharedState>,
+    channel_id: String,
+) -> Result<SceneList, LauncherError> {
+    crate::sanitize::channel_id(&channel_id)?;
+    let state = state.inner().clone();
+    crate::commands::blocking("Scene list", move || {
+        let config = state.config();
+        let channel = crate::find_channel(&config, &channel_id)?;
+        let label = format!("List scenes of {}", channel.name);
+        state
+            .jobs
+            .run(&app, "list_scenes", label, None, move |job| {
+                list_scenes(&config, &channel, &mut |_| job.check_cancelled())
+            })
+    })
+    .await
+}
@@ -0,0 +1,122 @@
+//! TypeScript bindings for the command surface
+//!
+//! The command list in `main.rs` both builds the invoke handler and is exported
+//! to `launcher/src/bindings.ts`, with types for every command's arguments,
+//! result and error and for the event payloads listed in `EVENTS`. The window
+//! has no build step, so the same commands also go to `bindings.js`, with the
+//! types as JSDoc; `app.js` calls the launcher only through its `commands`
+//! wrappers (`commands.getDashboard(refresh)` for `get_dashboard`), which
+//! throw the `LauncherError` on failure. Debug builds rewrite both files at
+//! startup; `--export-bindings` does it without opening the window, and the
+//! `bindings` workflow fails when the committed files differ from its output.
+
+use specta_jsdoc::JSDoc;
+use specta_typescript::{BigIntExportBehavior, Typescript};
+use std::path::{Path, PathBuf};
+use tauri_specta::Builder;
+
+use crate::account::LoginEvent;
+use crate::bundles::BuildProgress;
+use crate::dashboard::PendingUpdate;
+use crate::editor_monitor::EditorStatus;
+use crate::error::LauncherError;
+use crate::health::ChannelCheckResult;
+use crate::jobs::JobInfo;
+use crate::sessions::SessionClaim;
+use crate::stack::Capabilities;
+use crate::startup::StartupReport;
+use crate::triggers::TriggerOutcome;
+use crate::upload::UploadProgress;
+use crate::LauncherConfig;
+
+/// Events emitted to the window (name, payload type). Emitting a new event
+/// means adding it here and to `with_events`.
+const EVENTS: &[(&str, &str)] = &[
+    ("account-login", "LoginEvent"),
+    ("bundle-build-progress", "BuildProgress"),
+    ("bundle-upload-progress", "UploadProgress"),
+    ("capabilities", "Capabilities"),
+    ("channel-check", "ChannelCheckResult"),
+    ("config-changed", "LauncherConfig"),
+    ("editor-status", "EditorStatus"),
+    ("file-trigger", "TriggerOutcome"),
+    ("job-progress", "JobInfo"),
+    ("session-taken-over", "SessionClaim"),
+    ("startup-complete", "StartupReport"),
+    ("updates-available", "PendingUpdate[]"),
+];
+
+/// Register the event payload types, which no command mentions
+pub fn with_events(builder: Builder<tauri::Wry>) -> Builder<tauri::Wry> {
+    builder
+        .typ::<LoginEvent>()
+        .typ::<BuildProgress>()
+        .typ::<UploadProgress>()
+        .typ::<Capabilities>()
+        .typ::<ChannelCheckResult>()
+        .typ::<LauncherConfig>()
+        .typ::<EditorStatus>()
+        .typ::<TriggerOutcome>()
+        .typ::<JobInfo>()
+        .typ::<SessionClaim>()
+        .typ::<StartupReport>()
+        .typ::<PendingUpdate>()
+}
+
+/// `launcher/src/bindings.ts` in the source tree this binary was built from
+pub fn default_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../src/bindings.ts")
+}
+
+const GENERATED: &str =
+    "// Generated from the launcher's commands by `--export-bindings`. Do not edit.\n\n";
+
+fn header() -> String {
+    let mut header = format!(
+        "{}/** Payload of each event the launcher emits, by event name */\n\
+         export type Events = {{\n",
+        GENERATED
+    );
+    for (name, payload) in EVENTS {
+        header += &format!("  \"{}\": {};\n", name, payload);
+    }
+    header += "};\n";
+    header
+}
+
+/// `header` for `bindings.js`, with `Events` as a typedef
+fn jsdoc_header() -> String {
+    let mut header = format!(
+        "{}/**\n * Payload of each event the launcher emits, by event name\n * @typedef {{{{\n",
+        GENERATED
+    );
+    for (name, payload) in EVENTS {
+        header += &format!(" *   \"{}\": {},\n", name, payload);
+    }
+    header += " * }} Events\n */\n";
+    header
+}
+
+/// Write the bindings to `path` and the JavaScript the window loads next to
+/// it. This is a developer tool writing into the source tree, so it bypasses
+/// `write_guard`.
+pub fn export(builder: &Builder<tauri::Wry>, path: &Path) -> Result<(), LauncherError> {
+    let failed = |path: &Path, e: &dyn std::fmt::Display| {
+        LauncherError::internal(format!("Failed to export bindings: {}", e)).with_path(path)
+    };
+    // Sizes and timestamps stay far below 2^53, so plain numbers are safe
+    let typescript = Typescript::default()
+        .bigint(BigIntExportBehavior::Number)
+        .header(header());
+    builder
+        .export(typescript, path)
+        .map_err(|e| failed(path, &e))?;
+
+    let script = path.with_extension("js");
+    let jsdoc = JSDoc::default()
+        .bigint(BigIntExportBehavior::Number)
+        .header(jsdoc_header());
+    builder
+        .export(jsdoc, &script)
+        .map_err(|e| failed(&script, &e))
+}
@@ -0,0 +1,191 @@
+//! One bridge command at a time per project
+//!
+//! Scene lists, screenshots and bundle builds all drop command files into
+//! the project's `Assets/_MCP/commands`. Written at once, they would be run in
+//! file name order rather than the order they were sent, and during a domain
+//! reload the write itself can fail while Unity reimports the folder. `send`
+//! queues commands per project instead: one is written at a time, at least
+//! `MIN_INTERVAL` after the last, and the next waits until the bridge has
+//! taken it. Interactive commands, which someone is waiting on, go ahead of
+//! background ones such as long builds; within a priority the order is first
+//! come, first served. While the editor is compiling or its heartbeat has
+//! stopped for a reload, the queue holds still for up to `RELOAD_WAIT`
+//! instead of letting requests time out, and a write that fails is retried.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::bridge;
+use crate::error::{ErrorKind, LauncherError};
+
+/// Who is waiting on a command; higher goes first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    /// Long jobs nobody watches step by step, like bundle builds
+    Background,
+    /// Something the user clicked and is waiting on
+    Interactive,
+}
+
+/// Least time between two commands to the same project
+const MIN_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long the queue waits out a compile or domain reload
+const RELOAD_WAIT: Duration = Duration::from_secs(60);
+
+/// How long a connected, idle editor gets to take a command before the next
+/// one is sent anyway; long commands like builds hold on to their file while
+/// they run
+const PICKUP_WAIT: Duration = Duration::from_secs(5);
+
+const WRITE_ATTEMPTS: u32 = 3;
+const RETRY_BACKOFF: Duration = Duration::from_millis(500);
+const POLL: Duration = Duration::from_millis(100);
+
+#[derive(Default)]
+struct Lane {
+    /// A command is being sent
+    busy: bool,
+    /// Waiting senders as (priority, ticket)
+    waiting: Vec<(Priority, u64)>,
+    last_sent: Option<Instant>,
+}
+
+impl Lane {
+    /// The waiter that goes next: highest priority, then lowest ticket
+    fn next(&self) -> Option<u64> {
+        self.waiting
+            .iter()
+            .max_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)))
+            .map(|(_, ticket)| *ticket)
+    }
+}
+
+struct Queue {
+    lanes: Mutex<(u64, BTreeMap<PathBuf, Lane>)>,
+    changed: Condvar,
+}
+
+static QUEUE: Queue = Queue {
+    lanes: Mutex::new((0, BTreeMap::new())),
+    changed: Condvar::new(),
+};
+
+impl Queue {
+    fn lock(&self) -> MutexGuard<'_, (u64, BTreeMap<PathBuf, Lane>)> {
+        self.lanes.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// A project's turn to send; the next sender goes when it is dropped
+struct Turn {
+    project: PathBuf,
+    last_sent: Option<Instant>,
+}
+
+impl Turn {
+    fn wait(project: &Path, priority: Priority) -> Turn {
+        let mut guard = QUEUE.lock();
+        guard.0 += 1;
+        let ticket = guard.0;
+        guard
+            .1
+            .entry(project.to_path_buf())
+            .or_default()
+            .waiting
+            .push((priority, ticket));
+        loop {
+            let lane = guard.1.entry(project.to_path_buf()).or_default();
+            if !lane.busy && lane.next() == Some(ticket) {
+                lane.waiting.retain(|(_, t)| *t != ticket);
+                lane.busy = true;
+                return Turn {
+                    project: project.to_path_buf(),
+                    last_sent: lane.last_sent,
+                };
+            }
+            guard = QUEUE.changed.wait(guard).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+}
+
+impl Drop for Turn {
+    fn drop(&mut self) {
+        let mut guard = QUEUE.lock();
+        if let Some(lane) = guard.1.get_mut(&self.project) {
+            lane.busy = false;
+            lane.last_sent = Some(Instant::now());
+        }
+        QUEUE.changed.notify_all();
+    }
+}
+
+/// Whether the editor is up and not compiling, so it takes commands now
+fn editor_ready(project: &Path) -> bool {
+    let compiling = bridge::read_state(project, "editor-state.json")
+        .and_then(|s| s.get("isCompiling").and_then(|c| c.as_bool()))
+        .unwrap_or(false);
+    bridge::is_editor_connected(project) && !compiling
+}
+
+/// Give a compile or reload up to `RELOAD_WAIT` to finish
+fn wait_until_ready(project: &Path) {
+    let deadline = Instant::now() + RELOAD_WAIT;
+    while !editor_ready(project) && Instant::now() < deadline {
+        thread::sleep(POLL);
+    }
+}
+
+fn write(project: &Path, command: &serde_json::Value) -> Result<PathBuf, LauncherError> {
+    let mut backoff = RETRY_BACKOFF;
+    let mut attempt = 1;
+    loop {
+        match bridge::send_command(project, command) {
+            Ok(path) => return Ok(path),
+            // Unity may hold the folder while it reimports; refusals are final
+            Err(e) if e.kind == ErrorKind::Io && attempt < WRITE_ATTEMPTS => {
+                thread::sleep(backoff);
+                backoff *= 2;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Wait for the bridge to take `path`. Time the editor spends compiling or
+/// reloading doesn't count against `PICKUP_WAIT`.
+fn wait_for_pickup(project: &Path, path: &Path) {
+    let started = Instant::now();
+    let mut waited = Duration::ZERO;
+    while path.exists() && waited < PICKUP_WAIT && started.elapsed() < RELOAD_WAIT {
+        thread::sleep(POLL);
+        if editor_ready(project) {
+            waited += POLL;
+        }
+    }
+}
+
+/// Send `command` to the project's bridge in its turn. Returns once the
+/// bridge has taken it, or the editor has had `PICKUP_WAIT` to; the caller
+/// then waits for the state the command writes as before.
+pub fn send(
+    project: &Path,
+    priority: Priority,
+    command: &serde_json::Value,
+) -> Result<(), LauncherError> {
+    let turn = Turn::wait(project, priority);
+    if let Some(wait) = turn
+        .last_sent
+        .and_then(|last| MIN_INTERVAL.checked_sub(last.elapsed()))
+    {
+        thread::sleep(wait);
+    }
+    wait_until_ready(project);
+    let path = write(project, command)?;
+    wait_for_pickup(project, &path);
+    Ok(())
+}
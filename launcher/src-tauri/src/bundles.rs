@@ -0,0 +1,399 @@
+//! Banter asset bundle builds, via the live editor bridge or Unity batchmode
+//! (see `batchmode`)
+
+use serde::Serialize;
+use specta::Type;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+use crate::bridge_queue::{self, Priority};
+use crate::error::{ErrorKind, LauncherError};
+use crate::jobs::{JobContext, JobRequest};
+use crate::state::SharedState;
+use crate::{batchmode, bridge, logs, preflight, LauncherConfig, ProjectChannel};
+
+/// Method invoked with `-executeMethod` when the channel doesn't override it
+const DEFAULT_BUILD_METHOD: &str = "BantworksMCP.BantworksMCPBridge.BuildBundlesBatch";
+
+/// Platforms built when a channel doesn't declare any
+pub const DEFAULT_PLATFORMS: &[&str] = &["windows", "android"];
+
+/// Give up waiting on the editor after this long
+const BRIDGE_BUILD_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct BundleArtifact {
+    pub path: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct BundleBuildResult {
+    pub channel_id: String,
+    pub platform: String,
+    /// "bridge" or "batchmode"
+    pub mode: String,
+    pub output_dir: String,
+    pub artifacts: Vec<BundleArtifact>,
+    pub total_size: u64,
+    pub duration_ms: u64,
+}
+
+/// Outcome of one platform in a multi-platform build
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct PlatformBuildOutcome {
+    pub platform: String,
+    pub success: bool,
+    pub result: Option<BundleBuildResult>,
+    pub error: Option<LauncherError>,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct BuildMatrixResult {
+    pub channel_id: String,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub outcomes: Vec<PlatformBuildOutcome>,
+    pub duration_ms: u64,
+}
+
+/// Payload of the `bundle-build-progress` event
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct BuildProgress {
+    channel_id: String,
+    platform: String,
+    stage: String,
+    message: String,
+}
+
+/// Unity `-buildTarget` name for a Banter platform
+fn unity_build_target(platform: &str) -> Result<&'static str, LauncherError> {
+    match platform {
+        "windows" => Ok("Win64"),
+        "android" => Ok("Android"),
+        _ => Err(LauncherError::invalid(format!(
+            "Unsupported platform: {} (expected windows or android)",
+            platform
+        ))),
+    }
+}
+
+/// Unity `BuildTarget` enum name, as reported in editor-state.json
+fn unity_build_target_name(platform: &str) -> &'static str {
+    match platform {
+        "android" => "Android",
+        _ => "StandaloneWindows64",
+    }
+}
+
+/// Platforms declared on a channel, or the defaults
+pub fn channel_platforms(channel: &ProjectChannel) -> Vec<String> {
+    if channel.platforms.is_empty() {
+        DEFAULT_PLATFORMS.iter().map(|p| p.to_string()).collect()
+    } else {
+        channel.platforms.clone()
+    }
+}
+
+/// Where bundles for a platform are written inside the project
+pub fn bundle_output_dir(unity_project_path: &Path, platform: &str) -> PathBuf {
+    unity_project_path
+        .join("Builds")
+        .join("Banter")
+        .join(platform)
+}
+
+/// List all files in the output directory with their sizes
+pub fn collect_artifacts(dir: &Path) -> Vec<BundleArtifact> {
+    let mut artifacts = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+
+    while let Some(current) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            match entry.metadata() {
+                Ok(meta) if meta.is_dir() => pending.push(path),
+                Ok(meta) => artifacts.push(BundleArtifact {
+                    path: path.to_string_lossy().to_string(),
+                    size: meta.len(),
+                }),
+                Err(_) => {}
+            }
+        }
+    }
+
+    artifacts.sort_by(|a, b| a.path.cmp(&b.path));
+    artifacts
+}
+
+fn build_via_bridge(
+    job: &JobContext,
+    progress: &dyn Fn(&str, &str),
+    unity_project_path: &Path,
+    platform: &str,
+    output_dir: &Path,
+) -> Result<(), LauncherError> {
+    let started = bridge::now_ms();
+
+    bridge_queue::send(
+        unity_project_path,
+        Priority::Background,
+        &serde_json::json!({
+            "type": "build_bundle",
+            "platform": platform,
+            "outputPath": output_dir.to_string_lossy(),
+        }),
+    )?;
+    progress("building", "Build requested from the running Unity editor");
+
+    let deadline = Instant::now() + BRIDGE_BUILD_TIMEOUT;
+    while Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(500));
+        // The editor finishes the build on its own; cancelling only stops the wait
+        job.check_cancelled()?;
+
+        let Some(result) = bridge::read_state(unity_project_path, "build-result.json") else {
+            continue;
+        };
+        let timestamp = result
+            .get("timestamp")
+            .and_then(|t| t.as_i64())
+            .unwrap_or(0);
+        if timestamp < started {
+            continue;
+        }
+
+        if result.get("success").and_then(|s| s.as_bool()) == Some(true) {
+            return Ok(());
+        }
+        let error = result
+            .get("error")
+            .and_then(|e| e.as_str())
+            .unwrap_or("unknown error");
+        return Err(LauncherError::new(
+            ErrorKind::Unity,
+            format!("Bundle build failed in Unity: {}", error),
+        )
+        .with_hint("Check the Unity console for the full build log"));
+    }
+
+    Err(LauncherError::new(
+        ErrorKind::Bridge,
+        "Timed out waiting for Unity to finish the bundle build",
+    )
+    .with_hint("Make sure the editor isn't blocked by a modal dialog, then retry"))
+}
+
+fn build_via_batchmode(
+    job: &JobContext,
+    progress: &dyn Fn(&str, &str),
+    config: &LauncherConfig,
+    channel: &ProjectChannel,
+    platform: &str,
+    output_dir: &Path,
+) -> Result<(), LauncherError> {
+    let method = channel
+        .bundle_build_method
+        .as_deref()
+        .unwrap_or(DEFAULT_BUILD_METHOD);
+    let invocation = batchmode::Invocation {
+        method,
+        build_target: Some(unity_build_target(platform)?),
+        args: vec![
+            ("-bundlePlatform", platform.to_string()),
+            ("-bundleOutput", output_dir.to_string_lossy().to_string()),
+        ],
+        log_path: logs::build_log_path(&channel.id, platform),
+    };
+
+    progress("building", "Starting Unity in batchmode");
+    batchmode::run(config, channel, &invocation, &mut |line| {
+        if job.is_cancelled() {
+            return Err(LauncherError::new(
+                ErrorKind::Cancelled,
+                format!("{} build cancelled", platform),
+            ));
+        }
+        progress("log", line);
+        Ok(())
+    })
+}
+
+/// Build bundles for one platform, streaming `bundle-build-progress` events
+pub fn build_bundle(
+    app: &AppHandle,
+    job: &JobContext,
+    config: &LauncherConfig,
+    channel: &ProjectChannel,
+    platform: &str,
+) -> Result<BundleBuildResult, LauncherError> {
+    unity_build_target(platform)?;
+    job.check_cancelled()?;
+
+    let progress = |stage: &str, message: &str| {
+        if stage != "log" {
+            job.progress(None, format!("{}: {}", platform, message));
+        }
+        let _ = app.emit(
+            "bundle-build-progress",
+            BuildProgress {
+                channel_id: channel.id.clone(),
+                platform: platform.to_string(),
+                stage: stage.to_string(),
+                message: message.to_string(),
+            },
+        );
+    };
+
+    let started = Instant::now();
+    let project = Path::new(&channel.unity_project_path);
+    let output_dir = bundle_output_dir(project, platform);
+    preflight::ensure("bundle build", &output_dir, preflight::BUILD_FREE_BYTES)?;
+
+    let (mode, built) = if bridge::is_editor_connected(project) {
+        let built = build_via_bridge(job, &progress, project, platform, &output_dir);
+        ("bridge", built)
+    } else {
+        let built = build_via_batchmode(job, &progress, config, channel, platform, &output_dir);
+        ("batchmode", built)
+    };
+    if let Err(e) = built {
+        progress("failed", &e.message);
+        return Err(e);
+    }
+
+    let artifacts = collect_artifacts(&output_dir);
+    let total_size = artifacts.iter().map(|a| a.size).sum();
+    progress(
+        "completed",
+        &format!("{} artifacts, {} bytes", artifacts.len(), total_size),
+    );
+
+    Ok(BundleBuildResult {
+        channel_id: channel.id.clone(),
+        platform: platform.to_string(),
+        mode: mode.to_string(),
+        output_dir: output_dir.to_string_lossy().to_string(),
+        artifacts,
+        total_size,
+        duration_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+/// Build the channel's Banter bundles for a platform ("windows" or "android")
+#[tauri::command]
+#[specta::specta]
+pub async fn build_banter_bundle(
+    app: AppHandle,
+    state: tauri::State<'_, SharedState>,
+    channel_id: String,
+    platform: String,
+) -> Result<BundleBuildResult, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Bundle build", move || {
+        let config = state.config();
+        let channel = crate::find_channel(&config, &channel_id)?;
+        let label = format!("Build {} bundle for {}", platform, channel.name);
+        let job_app = app.clone();
+        let request = JobRequest::BuildBundle {
+            channel_id: channel.id.clone(),
+            platform: platform.clone(),
+        };
+        state
+            .jobs
+            .run(&app, "build_bundle", label, Some(request), move |job| {
+                build_bundle(&job_app, job, &config, &channel, &platform)
+            })
+    })
+    .await
+}
+
+/// Build every declared platform in sequence. The editor's current build target
+/// goes first so Unity reimports as few times as possible.
+pub fn build_all(
+    app: &AppHandle,
+    job: &JobContext,
+    config: &LauncherConfig,
+    channel: &ProjectChannel,
+) -> BuildMatrixResult {
+    let started = Instant::now();
+    let project = Path::new(&channel.unity_project_path);
+
+    let active_target = bridge::read_state(project, "editor-state.json")
+        .and_then(|s| {
+            s.get("activeBuildTarget")
+                .and_then(|t| t.as_str())
+                .map(|t| t.to_string())
+        })
+        .unwrap_or_default();
+
+    let mut platforms = channel_platforms(channel);
+    platforms.sort_by_key(|p| unity_build_target_name(p) != active_target);
+
+    let total = platforms.len().max(1) as f64;
+    let outcomes: Vec<PlatformBuildOutcome> = platforms
+        .iter()
+        .enumerate()
+        .map(|(i, platform)| {
+            job.progress(Some(i as f64 / total), format!("Building {}", platform));
+            match build_bundle(app, job, config, channel, platform) {
+                Ok(result) => PlatformBuildOutcome {
+                    platform: platform.clone(),
+                    success: true,
+                    result: Some(result),
+                    error: None,
+                },
+                Err(e) => PlatformBuildOutcome {
+                    platform: platform.clone(),
+                    success: false,
+                    result: None,
+                    error: Some(e),
+                },
+            }
+        })
+        .collect();
+
+    let succeeded = outcomes.iter().filter(|o| o.success).count();
+
+    BuildMatrixResult {
+        channel_id: channel.id.clone(),
+        succeeded,
+        failed: outcomes.len() - succeeded,
+        outcomes,
+        duration_ms: started.elapsed().as_millis() as u64,
+    }
+}
+
+/// Build the channel's bundles for all of its declared platforms
+#[tauri::command]
+#[specta::specta]
+pub async fn build_all_platforms(
+    app: AppHandle,
+    state: tauri::State<'_, SharedState>,
+    channel_id: String,
+) -> Result<BuildMatrixResult, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Bundle build", move || {
+        let config = state.config();
+        let channel = crate::find_channel(&config, &channel_id)?;
+        let label = format!("Build all bundles for {}", channel.name);
+        let job_app = app.clone();
+        let request = JobRequest::BuildAllPlatforms {
+            channel_id: channel.id.clone(),
+        };
+        state.jobs.run(
+            &app,
+            "build_all_platforms",
+            label,
+            Some(request),
+            move |job| Ok(build_all(&job_app, job, &config, &channel)),
+        )
+    })
+    .await
+}
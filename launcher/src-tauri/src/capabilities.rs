@@ -0,0 +1,112 @@
+//! What a channel's MCP server can do
+//!
+//! The tools and resources a server offers depend on its version, so the
+//! launcher asks the server itself (`tools/list` and `resources/list`) and
+//! keeps the answer on the channel. The cache is stale once the channel points
+//! at a different server script or the package there has another version;
+//! watch mode refreshes it after each server start, the window when asked.
+
+use serde::Serialize;
+use specta::Type;
+use std::thread;
+
+use crate::error::LauncherError;
+use crate::mcp_client::McpClient;
+use crate::plan::FilePlan;
+use crate::state::{AppState, SharedState};
+use crate::{banter_sdk, bridge, LauncherConfig, ProjectChannel};
+use launcher_core::config::ServerCapabilities;
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct CapabilitiesReport {
+    pub channel_id: String,
+    /// `None` until the server has been asked once
+    pub capabilities: Option<ServerCapabilities>,
+    /// Cached for another server script or version than the current one
+    pub stale: bool,
+}
+
+fn is_stale(config: &LauncherConfig, capabilities: &ServerCapabilities) -> bool {
+    capabilities.mcp_server_path != config.mcp_server_path
+        || capabilities.package_version != banter_sdk::read_server_version(&config.mcp_server_path)
+}
+
+fn report(config: &LauncherConfig, channel: &ProjectChannel) -> CapabilitiesReport {
+    CapabilitiesReport {
+        channel_id: channel.id.clone(),
+        stale: channel
+            .capabilities
+            .as_ref()
+            .is_some_and(|c| is_stale(config, c)),
+        capabilities: channel.capabilities.clone(),
+    }
+}
+
+fn server_info(server: &serde_json::Value, key: &str) -> Option<String> {
+    server
+        .pointer(key)
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+}
+
+/// Ask the channel's server and store the answer on the channel
+pub fn refresh(state: &AppState, channel_id: &str) -> Result<ServerCapabilities, LauncherError> {
+    let config = state.config();
+    let channel = crate::find_channel(&config, channel_id)?;
+    let mut client = McpClient::connect(&config, &channel)?;
+    let capabilities = ServerCapabilities {
+        server_name: server_info(&client.server, "/serverInfo/name"),
+        server_version: server_info(&client.server, "/serverInfo/version"),
+        protocol_version: server_info(&client.server, "/protocolVersion"),
+        tools: client.list_tools()?,
+        resources: client.list_resources()?,
+        mcp_server_path: config.mcp_server_path.clone(),
+        package_version: banter_sdk::read_server_version(&config.mcp_server_path),
+        queried_at: bridge::now_ms(),
+    };
+    drop(client);
+
+    let mut plan = FilePlan::new(false);
+    state.update(&mut plan, |config| {
+        if let Some(channel) = config.channels.iter_mut().find(|c| c.id == channel_id) {
+            channel.capabilities = Some(capabilities.clone());
+        }
+        Ok(())
+    })?;
+    Ok(capabilities)
+}
+
+/// `refresh` off the calling thread; failures are only logged
+pub fn refresh_in_background(state: SharedState, channel_id: String) {
+    thread::spawn(move || {
+        if let Err(e) = refresh(&state, &channel_id) {
+            eprintln!(
+                "Failed to query server capabilities for {}: {}",
+                channel_id, e
+            );
+        }
+    });
+}
+
+/// The tools and resources the channel's server offers. Asks the server
+/// when nothing is cached or `refresh` is set; otherwise answers from the
+/// cache, flagged if it is stale.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_server_capabilities(
+    state: tauri::State<'_, SharedState>,
+    channel_id: String,
+    refresh: Option<bool>,
+) -> Result<CapabilitiesReport, LauncherError> {
+    crate::sanitize::channel_id(&channel_id)?;
+    let state = state.inner().clone();
+    crate::commands::blocking("Server capabilities", move || {
+        let channel = state.channel(&channel_id)?;
+        if refresh.unwrap_or(false) || channel.capabilities.is_none() {
+            self::refresh(&state, &channel_id)?;
+        }
+        let config = state.config();
+        Ok(report(&config, &crate::find_channel(&config, &channel_id)?))
+    })
+    .await
+}
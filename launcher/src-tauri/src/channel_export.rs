@@ -0,0 +1,487 @@
+//! Portable single-file channel exports (`.bantermcp`)
+//!
+//! `export_channel` turns a channel into a JSON document that can be shared
+//! with the team: machine-specific paths become `${PROJECT}` placeholders,
+//! and the file records the bridge version the channel was set up with, the
+//! MCP clients it was registered with and free-form notes. Secrets and the
+//! upload token are never exported.
+//!
+//! ```json
+//! {
+//!   "format": "bantermcp",
+//!   "version": 1,
+//!   "channel": {
+//!     "name": "Lobby",
+//!     "unity_project_path": "${PROJECT}",
+//!     "scene_path": "${PROJECT}/Assets/Scenes/Lobby.unity",
+//!     "platforms": ["windows", "android"]
+//!   },
+//!   "placeholders": [{ "name": "PROJECT", "description": "Unity project folder", "hint": "LobbyProject" }],
+//!   "requires": { "min_extension_version": "1.3.0" },
+//!   "clients": ["claude-code"],
+//!   "notes": "Build with the 2022.3 editor"
+//! }
+//! ```
+//!
+//! `import_channel` answers with `needs_input` and the placeholders to ask
+//! for until every value has been supplied, then adds the channel.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::error::LauncherError;
+use crate::plan::FilePlan;
+use crate::state::{AppState, SharedState};
+use crate::{banter_sdk, fs_scope, hooks, sanitize, ProjectChannel};
+use launcher_core::config::ChannelQuotas;
+
+pub const FILE_EXTENSION: &str = "bantermcp";
+
+const FORMAT: &str = "bantermcp";
+const FORMAT_VERSION: u32 = 1;
+
+pub const PROJECT_PLACEHOLDER: &str = "PROJECT";
+
+/// Client id recorded for Claude Code
+const CLAUDE_CODE: &str = "claude-code";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ExportFile {
+    format: String,
+    version: u32,
+    channel: ExportedChannel,
+    #[serde(default)]
+    placeholders: Vec<Placeholder>,
+    #[serde(default)]
+    requires: Requirements,
+    /// MCP clients the channel should be registered with
+    #[serde(default)]
+    clients: Vec<String>,
+    #[serde(default)]
+    notes: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ExportedChannel {
+    name: String,
+    unity_project_path: String,
+    #[serde(default)]
+    scene_path: Option<String>,
+    #[serde(default = "default_enabled")]
+    enabled: bool,
+    #[serde(default)]
+    bundle_build_method: Option<String>,
+    #[serde(default)]
+    platforms: Vec<String>,
+    #[serde(default)]
+    space_url: Option<String>,
+    #[serde(default)]
+    local_test_url: Option<String>,
+    #[serde(default)]
+    pass_upload_token: bool,
+    #[serde(default)]
+    server_cwd: Option<String>,
+    #[serde(default)]
+    server_data_dir: Option<String>,
+    #[serde(default)]
+    workspace_clients: Vec<String>,
+    #[serde(default)]
+    quotas: ChannelQuotas,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Placeholder {
+    pub name: String,
+    pub description: String,
+    /// What the value was on the exporting machine, e.g. the folder name
+    #[serde(default)]
+    pub hint: Option<String>,
+    /// Likely value on this machine (import only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Requirements {
+    #[serde(default)]
+    min_extension_version: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ChannelExport {
+    /// Suggested file name, e.g. "Lobby.bantermcp"
+    pub file_name: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportStatus {
+    /// Call again with values for `placeholders`
+    NeedsInput,
+    Imported,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ImportResult {
+    pub status: ImportStatus,
+    /// Placeholders still without a value
+    pub placeholders: Vec<Placeholder>,
+    pub channel: Option<ProjectChannel>,
+    pub clients: Vec<String>,
+    pub notes: Option<String>,
+    pub warnings: Vec<String>,
+    pub plan: Option<FilePlan>,
+}
+
+pub fn placeholder(name: &str) -> String {
+    format!("${{{}}}", name)
+}
+
+/// Swap a path under `project` for the project placeholder
+fn to_portable(path: &str, project: &Path) -> String {
+    match Path::new(path).strip_prefix(project) {
+        Ok(rest) if rest.as_os_str().is_empty() => placeholder(PROJECT_PLACEHOLDER),
+        Ok(rest) => format!(
+            "{}/{}",
+            placeholder(PROJECT_PLACEHOLDER),
+            rest.to_string_lossy().replace('\\', "/")
+        ),
+        Err(_) => path.to_string(),
+    }
+}
+
+/// File name safe on every platform
+fn file_name(channel_name: &str) -> String {
+    let stem: String = channel_name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' || c == ' ' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let stem = stem.trim();
+    let stem = if stem.is_empty() { "channel" } else { stem };
+    format!("{}.{}", stem, FILE_EXTENSION)
+}
+
+pub fn export(
+    state: &AppState,
+    channel_id: &str,
+    notes: Option<String>,
+) -> Result<ChannelExport, LauncherError> {
+    let channel = state.channel(channel_id)?;
+    let project = Path::new(&channel.unity_project_path);
+
+    let registered = crate::get_claude_mcp_config()?
+        .pointer("/mcpServers/banter/env/UNITY_PROJECT_PATH")
+        .and_then(|p| p.as_str())
+        .is_some_and(|p| p == channel.unity_project_path);
+
+    let file = ExportFile {
+        format: FORMAT.to_string(),
+        version: FORMAT_VERSION,
+        channel: ExportedChannel {
+            name: channel.name.clone(),
+            unity_project_path: placeholder(PROJECT_PLACEHOLDER),
+            scene_path: channel
+                .scene_path
+                .as_deref()
+                .map(|s| to_portable(s, project)),
+            enabled: channel.enabled,
+            bundle_build_method: channel.bundle_build_method.clone(),
+            platforms: channel.platforms.clone(),
+            space_url: channel.space_url.clone(),
+            local_test_url: channel.local_test_url.clone(),
+            pass_upload_token: channel.pass_upload_token,
+            server_cwd: channel
+                .server_cwd
+                .as_deref()
+                .map(|s| to_portable(s, project)),
+            server_data_dir: channel
+                .server_data_dir
+                .as_deref()
+                .map(|s| to_portable(s, project)),
+            workspace_clients: channel.workspace_clients.clone(),
+            quotas: channel.quotas.clone(),
+        },
+        placeholders: vec![Placeholder {
+            name: PROJECT_PLACEHOLDER.to_string(),
+            description: "Unity project folder".to_string(),
+            hint: project.file_name().map(|n| n.to_string_lossy().to_string()),
+            suggestion: None,
+        }],
+        requires: Requirements {
+            min_extension_version: banter_sdk::read_bridge_version(project)
+                .filter(|v| v != "0.0.0"),
+        },
+        clients: if registered {
+            vec![CLAUDE_CODE.to_string()]
+        } else {
+            Vec::new()
+        },
+        notes: notes.filter(|n| !n.trim().is_empty()),
+    };
+
+    let content = serde_json::to_string_pretty(&file)
+        .map_err(|e| LauncherError::internal(format!("Failed to serialize export: {}", e)))?;
+    Ok(ChannelExport {
+        file_name: file_name(&channel.name),
+        content,
+    })
+}
+
+fn parse(content: &str, path: &Path) -> Result<ExportFile, LauncherError> {
+    let file: ExportFile = serde_json::from_str(content).map_err(|e| {
+        LauncherError::parse(format!("Failed to parse channel export: {}", e)).with_path(path)
+    })?;
+
+    if file.format != FORMAT {
+        return Err(LauncherError::parse("Not a .bantermcp channel export").with_path(path));
+    }
+    if file.version > FORMAT_VERSION {
+        return Err(LauncherError::parse(format!(
+            "Channel export version {} is newer than this launcher supports ({})",
+            file.version, FORMAT_VERSION
+        ))
+        .with_path(path)
+        .with_hint("Update the launcher"));
+    }
+    Ok(file)
+}
+
+/// A Unity project the export file sits in or next to
+fn suggest_project(file: &Path) -> Option<String> {
+    file.ancestors()
+        .skip(1)
+        .take(3)
+        .find(|dir| dir.join("Assets").is_dir())
+        .map(|dir| dir.to_string_lossy().to_string())
+}
+
+pub fn substitute(value: &str, values: &HashMap<String, String>) -> Result<String, LauncherError> {
+    let mut resolved = value.to_string();
+    for (name, replacement) in values {
+        resolved = resolved.replace(
+            &placeholder(name),
+            replacement.trim_end_matches(['/', '\\']),
+        );
+    }
+    if resolved.contains("${") {
+        return Err(LauncherError::invalid(format!(
+            "Unknown placeholder in {}",
+            value
+        )));
+    }
+    Ok(resolved)
+}
+
+pub fn import(
+    state: &AppState,
+    path: &Path,
+    values: HashMap<String, String>,
+    activate: bool,
+    dry_run: bool,
+) -> Result<ImportResult, LauncherError> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| LauncherError::io("Failed to read channel export", path, e))?;
+    import_content(state, &content, path, values, activate, dry_run)
+}
+
+/// `import` of an export's `content`, read from or packed into `path`
+pub fn import_content(
+    state: &AppState,
+    content: &str,
+    path: &Path,
+    values: HashMap<String, String>,
+    activate: bool,
+    dry_run: bool,
+) -> Result<ImportResult, LauncherError> {
+    let file = parse(content, path)?;
+    let mut result = ImportResult {
+        status: ImportStatus::NeedsInput,
+        placeholders: Vec::new(),
+        channel: None,
+        clients: file.clients.clone(),
+        notes: file.notes.clone(),
+        warnings: Vec::new(),
+        plan: None,
+    };
+
+    for placeholder in &file.placeholders {
+        let value = values.get(&placeholder.name).map(|v| v.trim());
+        if !matches!(value, Some(v) if !v.is_empty()) {
+            let mut placeholder = placeholder.clone();
+            if placeholder.name == PROJECT_PLACEHOLDER {
+                placeholder.suggestion = suggest_project(path);
+            }
+            result.placeholders.push(placeholder);
+        }
+    }
+    if !result.placeholders.is_empty() {
+        return Ok(result);
+    }
+
+    let exported = &file.channel;
+    let channel = ProjectChannel {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: sanitize::channel_name(&exported.name)?,
+        unity_project_path: substitute(&exported.unity_project_path, &values)?,
+        scene_path: exported
+            .scene_path
+            .as_deref()
+            .map(|s| substitute(s, &values))
+            .transpose()?,
+        enabled: exported.enabled,
+        bundle_build_method: exported.bundle_build_method.clone(),
+        platforms: exported.platforms.clone(),
+        space_url: exported.space_url.clone(),
+        local_test_url: exported.local_test_url.clone(),
+        pass_upload_token: exported.pass_upload_token,
+        // Shared folders are specific to the exporting machine
+        extra_allowed_roots: Vec::new(),
+        server_cwd: exported
+            .server_cwd
+            .as_deref()
+            .map(|s| substitute(s, &values))
+            .transpose()?,
+        server_data_dir: exported
+            .server_data_dir
+            .as_deref()
+            .map(|s| substitute(s, &values))
+            .transpose()?,
+        workspace_clients: exported.workspace_clients.clone(),
+        capabilities: None,
+        quotas: exported.quotas.clone(),
+    };
+    sanitize::channel(&channel)?;
+
+    let project = Path::new(&channel.unity_project_path);
+    if !project.join("Assets").is_dir() {
+        return Err(LauncherError::not_found(format!(
+            "Not a Unity project: {}",
+            channel.unity_project_path
+        ))
+        .with_path(project)
+        .with_hint("Pick the folder that contains Assets"));
+    }
+    if let Some(scene) = channel
+        .scene_path
+        .as_deref()
+        .filter(|s| !Path::new(s).is_file())
+    {
+        result
+            .warnings
+            .push(format!("Scene not found in this project: {}", scene));
+    }
+
+    if let Some(required) = &file.requires.min_extension_version {
+        let installed = banter_sdk::read_bridge_version(project);
+        let outdated = match (
+            installed.as_deref().and_then(banter_sdk::parse_version),
+            banter_sdk::parse_version(required),
+        ) {
+            (Some(installed), Some(required)) => installed < required,
+            (None, _) => true,
+            (_, None) => false,
+        };
+        if outdated {
+            result.warnings.push(match installed {
+                Some(v) => format!(
+                    "Unity extension {} is older than the {} this channel was set up with",
+                    v, required
+                ),
+                None => format!(
+                    "Unity extension is not installed (channel was set up with {})",
+                    required
+                ),
+            });
+        }
+    }
+    for client in file.clients.iter().filter(|c| c.as_str() != CLAUDE_CODE) {
+        result.warnings.push(format!(
+            "Client {} is not supported by this launcher",
+            client
+        ));
+    }
+
+    let mut plan = FilePlan::new(dry_run);
+    let config = state.update(&mut plan, |config| {
+        config.channels.push(channel.clone());
+        if activate {
+            config.active_channel_id = Some(channel.id.clone());
+        }
+        Ok(config.clone())
+    })?;
+    if activate && file.clients.iter().any(|c| c == CLAUDE_CODE) {
+        let claude = crate::update_claude_mcp_config(
+            state,
+            channel.clone(),
+            config.mcp_server_path.clone(),
+            Some(dry_run),
+        )?;
+        plan.operations.extend(claude.operations);
+    }
+
+    result.status = ImportStatus::Imported;
+    result.channel = Some(channel);
+    result.plan = Some(plan);
+    Ok(result)
+}
+
+/// Export a channel as a `.bantermcp` document with machine paths replaced
+/// by placeholders
+#[tauri::command]
+#[specta::specta]
+pub async fn export_channel(
+    state: tauri::State<'_, SharedState>,
+    channel_id: String,
+    notes: Option<String>,
+) -> Result<ChannelExport, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Export channel", move || export(&state, &channel_id, notes)).await
+}
+
+/// Import a `.bantermcp` file. Returns `needs_input` with the placeholders to
+/// prompt for until `values` covers them all.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_channel(
+    app: AppHandle,
+    state: tauri::State<'_, SharedState>,
+    file: String,
+    values: Option<HashMap<String, String>>,
+    activate: Option<bool>,
+    dry_run: Option<bool>,
+) -> Result<ImportResult, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Import channel", move || {
+        let path = sanitize::path("Channel export", &file)?;
+        let previous = state.config();
+        let result = import(
+            &state,
+            &path,
+            values.unwrap_or_default(),
+            activate.unwrap_or(false),
+            dry_run.unwrap_or(false),
+        )?;
+        if result.plan.as_ref().is_some_and(|p| !p.dry_run) {
+            let config = state.config();
+            fs_scope::sync(&app, &config);
+            hooks::active_channel_changed(&previous, &config);
+        }
+        Ok(result)
+    })
+    .await
+}
@@ -0,0 +1,421 @@
+//! Declarative channel manifests
+//!
+//! `apply_channels_file` reads a JSON or YAML manifest (e.g. checked into a
+//! team repo) and reconciles the launcher config with it. Channels are
+//! matched by `id` and created or updated to match; with `prune: true`,
+//! channels the manifest doesn't list are deleted. Relative paths are
+//! resolved against the manifest's folder.
+//!
+//! ```yaml
+//! version: 1
+//! prune: true
+//! active_channel: lobby
+//! channels:
+//!   - id: lobby
+//!     name: Lobby
+//!     scene_path: ../LobbyProject/Assets/Scenes/Lobby.unity
+//!     platforms: [windows, android]
+//! ```
+//!
+//! `server_cwd` and `server_data_dir` stay relative to the channel's project,
+//! as in the launcher config. Everything about a channel comes from the
+//! manifest except `space_url`, which uploads set. `check` validates a manifest without applying it.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use tauri::AppHandle;
+
+use crate::config_check::{self, ConfigIssue};
+use crate::error::LauncherError;
+use crate::plan::FilePlan;
+use crate::state::{AppState, SharedState};
+use crate::{fs_scope, hooks, sanitize, secrets, LauncherConfig, ProjectChannel};
+use launcher_core::config::ChannelQuotas;
+
+const SUPPORTED_VERSION: u32 = 1;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ChannelsFile {
+    #[serde(default)]
+    version: Option<u32>,
+    /// Delete channels the manifest doesn't list
+    #[serde(default)]
+    prune: bool,
+    #[serde(default)]
+    active_channel: Option<String>,
+    channels: Vec<ChannelEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ChannelEntry {
+    id: String,
+    name: String,
+    /// Derived from `scene_path` when left out
+    #[serde(default)]
+    unity_project_path: Option<String>,
+    #[serde(default)]
+    scene_path: Option<String>,
+    #[serde(default = "default_enabled")]
+    enabled: bool,
+    #[serde(default)]
+    bundle_build_method: Option<String>,
+    #[serde(default)]
+    platforms: Vec<String>,
+    #[serde(default)]
+    local_test_url: Option<String>,
+    #[serde(default)]
+    pass_upload_token: bool,
+    #[serde(default)]
+    extra_allowed_roots: Vec<String>,
+    #[serde(default)]
+    server_cwd: Option<String>,
+    #[serde(default)]
+    server_data_dir: Option<String>,
+    #[serde(default)]
+    workspace_clients: Vec<String>,
+    #[serde(default)]
+    quotas: ChannelQuotas,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ChannelChange {
+    pub id: String,
+    pub name: String,
+    /// Fields that differ from the current config (updates only)
+    pub fields: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ChannelsFileReport {
+    pub path: String,
+    pub created: Vec<ChannelChange>,
+    pub updated: Vec<ChannelChange>,
+    pub deleted: Vec<ChannelChange>,
+    /// Ids of listed channels that already matched
+    pub unchanged: Vec<String>,
+    pub active_channel_id: Option<String>,
+    pub plan: FilePlan,
+}
+
+fn parse(path: &Path) -> Result<ChannelsFile, LauncherError> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| LauncherError::io("Failed to read channels file", path, e))?;
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+
+    let parsed = match extension.as_str() {
+        "json" => serde_json::from_str(&content).map_err(|e| e.to_string()),
+        "yaml" | "yml" => serde_yaml::from_str(&content).map_err(|e| e.to_string()),
+        // YAML is a superset of JSON
+        _ => serde_yaml::from_str(&content).map_err(|e| e.to_string()),
+    };
+    let file: ChannelsFile = parsed.map_err(|e| {
+        LauncherError::parse(format!("Failed to parse channels file: {}", e)).with_path(path)
+    })?;
+
+    match file.version {
+        Some(version) if version != SUPPORTED_VERSION => Err(LauncherError::parse(format!(
+            "Unsupported channels file version {} (expected {})",
+            version, SUPPORTED_VERSION
+        ))
+        .with_path(path)),
+        _ => Ok(file),
+    }
+}
+
+/// Join a manifest path onto its folder and fold away `.` and `..`
+fn resolve_path(base: &Path, value: &str) -> PathBuf {
+    let mut resolved = PathBuf::new();
+    for component in base.join(value).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            other => resolved.push(other),
+        }
+    }
+    resolved
+}
+
+/// The channel a manifest entry describes, starting from its current state
+fn desired_channel(
+    entry: &ChannelEntry,
+    existing: Option<&ProjectChannel>,
+    base: &Path,
+) -> Result<ProjectChannel, LauncherError> {
+    let scene_path = entry.scene_path.as_deref().map(|s| resolve_path(base, s));
+    let project = match (&entry.unity_project_path, &scene_path) {
+        (Some(project), _) => resolve_path(base, project),
+        (None, Some(scene)) => crate::project_root_for_scene(scene).ok_or_else(|| {
+            LauncherError::invalid(format!(
+                "Channel {}: no Assets folder above the scene",
+                entry.id
+            ))
+            .with_path(scene)
+        })?,
+        (None, None) => {
+            return Err(LauncherError::invalid(format!(
+                "Channel {} needs a unity_project_path or scene_path",
+                entry.id
+            )))
+        }
+    };
+
+    let channel = ProjectChannel {
+        id: entry.id.clone(),
+        name: entry.name.trim().to_string(),
+        unity_project_path: project.to_string_lossy().to_string(),
+        scene_path: scene_path.map(|s| s.to_string_lossy().to_string()),
+        enabled: entry.enabled,
+        bundle_build_method: entry.bundle_build_method.clone(),
+        platforms: entry.platforms.clone(),
+        space_url: existing.and_then(|c| c.space_url.clone()),
+        local_test_url: entry.local_test_url.clone(),
+        pass_upload_token: entry.pass_upload_token,
+        extra_allowed_roots: entry.extra_allowed_roots.clone(),
+        server_cwd: entry.server_cwd.clone(),
+        server_data_dir: entry.server_data_dir.clone(),
+        workspace_clients: entry.workspace_clients.clone(),
+        capabilities: existing.and_then(|c| c.capabilities.clone()),
+        quotas: entry.quotas.clone(),
+    };
+    sanitize::channel(&channel)?;
+    Ok(channel)
+}
+
+/// Names of the fields that differ between two versions of a channel
+fn changed_fields(old: &ProjectChannel, new: &ProjectChannel) -> Vec<String> {
+    let (Ok(serde_json::Value::Object(old)), Ok(serde_json::Value::Object(new))) =
+        (serde_json::to_value(old), serde_json::to_value(new))
+    else {
+        return Vec::new();
+    };
+    new.iter()
+        .filter(|(key, value)| old.get(*key) != Some(*value))
+        .map(|(key, _)| key.clone())
+        .collect()
+}
+
+fn change(channel: &ProjectChannel, fields: Vec<String>) -> ChannelChange {
+    ChannelChange {
+        id: channel.id.clone(),
+        name: channel.name.clone(),
+        fields,
+    }
+}
+
+/// Reconcile `config` with the manifest in place and describe what changed
+fn reconcile(
+    config: &mut LauncherConfig,
+    file: &ChannelsFile,
+    base: &Path,
+    report: &mut ChannelsFileReport,
+) -> Result<(), LauncherError> {
+    let mut listed = HashSet::new();
+    let mut channels = Vec::new();
+
+    for entry in &file.channels {
+        if !listed.insert(entry.id.as_str()) {
+            return Err(LauncherError::invalid(format!(
+                "Channel {} is listed twice",
+                entry.id
+            )));
+        }
+        let existing = config.channels.iter().find(|c| c.id == entry.id);
+        let channel = desired_channel(entry, existing, base)?;
+
+        match existing {
+            None => report.created.push(change(&channel, Vec::new())),
+            Some(old) => {
+                let fields = changed_fields(old, &channel);
+                if fields.is_empty() {
+                    report.unchanged.push(channel.id.clone());
+                } else {
+                    report.updated.push(change(&channel, fields));
+                }
+            }
+        }
+        channels.push(channel);
+    }
+
+    for channel in &config.channels {
+        if listed.contains(channel.id.as_str()) {
+            continue;
+        }
+        if file.prune {
+            report.deleted.push(change(channel, Vec::new()));
+        } else {
+            channels.push(channel.clone());
+        }
+    }
+    config.channels = channels;
+
+    if let Some(active) = &file.active_channel {
+        if !config.channels.iter().any(|c| &c.id == active) {
+            return Err(LauncherError::invalid(format!(
+                "active_channel {} is not a channel",
+                active
+            )));
+        }
+        config.active_channel_id = Some(active.clone());
+    } else if let Some(active) = &config.active_channel_id {
+        if !config.channels.iter().any(|c| &c.id == active) {
+            config.active_channel_id = config.channels.first().map(|c| c.id.clone());
+        }
+    }
+    report.active_channel_id = config.active_channel_id.clone();
+
+    sanitize::config(config)?;
+    Ok(())
+}
+
+fn empty_report(path: &Path, dry_run: bool) -> ChannelsFileReport {
+    ChannelsFileReport {
+        path: path.to_string_lossy().to_string(),
+        created: Vec::new(),
+        updated: Vec::new(),
+        deleted: Vec::new(),
+        unchanged: Vec::new(),
+        active_channel_id: None,
+        plan: FilePlan::new(dry_run),
+    }
+}
+
+/// Read a manifest and make the config match it. Nothing is written when the
+/// config already matches.
+pub fn apply(
+    state: &AppState,
+    path: &Path,
+    dry_run: bool,
+) -> Result<ChannelsFileReport, LauncherError> {
+    let file = parse(path)?;
+    let base = path.parent().unwrap_or(Path::new("."));
+
+    let current = state.config();
+    let mut preview = current.clone();
+    let mut report = empty_report(path, dry_run);
+    reconcile(&mut preview, &file, base, &mut report)?;
+    let changed = !report.created.is_empty()
+        || !report.updated.is_empty()
+        || !report.deleted.is_empty()
+        || preview.active_channel_id != current.active_channel_id;
+    if !changed {
+        return Ok(report);
+    }
+
+    // Reconcile again under the write lock in case the config moved meanwhile
+    let mut plan = FilePlan::new(dry_run);
+    let mut report = empty_report(path, dry_run);
+    state.update(&mut plan, |config| {
+        reconcile(config, &file, base, &mut report)
+    })?;
+
+    let claude_project = crate::get_claude_mcp_config()?
+        .pointer("/mcpServers/banter/env/UNITY_PROJECT_PATH")
+        .and_then(|p| p.as_str())
+        .map(|p| p.to_string());
+    let mut claude_removed = false;
+    for deleted in &report.deleted {
+        secrets::remove_channel_secrets(&mut plan, &deleted.id)?;
+        let deleted_project = current
+            .channels
+            .iter()
+            .find(|c| c.id == deleted.id)
+            .map(|c| c.unity_project_path.as_str());
+        if !claude_removed
+            && claude_project.is_some()
+            && claude_project.as_deref() == deleted_project
+        {
+            crate::plan_claude_mcp_removal(&mut plan, &current.backup_retention)?;
+            claude_removed = true;
+        }
+    }
+
+    report.plan = plan;
+    Ok(report)
+}
+
+/// Check a manifest on its own, without reading or writing the launcher
+/// config, as `--validate-config` does in CI
+pub fn check(path: &Path, issues: &mut Vec<ConfigIssue>) {
+    let file = match parse(path) {
+        Ok(file) => file,
+        Err(e) => return issues.push(ConfigIssue::error(None, e.message)),
+    };
+    let base = path.parent().unwrap_or(Path::new("."));
+
+    let mut listed = HashSet::new();
+    for (i, entry) in file.channels.iter().enumerate() {
+        let field = format!("channels[{}]", i);
+        if !listed.insert(entry.id.as_str()) {
+            issues.push(ConfigIssue::error(
+                Some(&field),
+                format!("Channel {} is listed twice", entry.id),
+            ));
+        }
+
+        for (name, value) in [
+            ("unity_project_path", &entry.unity_project_path),
+            ("scene_path", &entry.scene_path),
+        ] {
+            if value.as_deref().is_some_and(|v| Path::new(v).is_absolute()) {
+                issues.push(ConfigIssue::warning(
+                    Some(&format!("{}.{}", field, name)),
+                    "Absolute path; other machines will need the same layout",
+                ));
+            }
+        }
+
+        match desired_channel(entry, None, base) {
+            Ok(channel) => config_check::check_paths_exist(&field, &channel, issues),
+            Err(e) => issues.push(ConfigIssue::error(Some(&field), e.message)),
+        }
+    }
+
+    if let Some(active) = &file.active_channel {
+        if !listed.contains(active.as_str()) {
+            issues.push(ConfigIssue::error(
+                Some("active_channel"),
+                format!("active_channel {} is not a channel", active),
+            ));
+        }
+    }
+}
+
+/// Create, update and (with `prune`) delete channels to match a manifest.
+/// With `dry_run` the report shows what would change without writing.
+#[tauri::command]
+#[specta::specta]
+pub async fn apply_channels_file(
+    app: AppHandle,
+    state: tauri::State<'_, SharedState>,
+    path: String,
+    dry_run: Option<bool>,
+) -> Result<ChannelsFileReport, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Apply channels file", move || {
+        let path = sanitize::path("Channels file", &path)?;
+        let previous = state.config();
+        let report = apply(&state, &path, dry_run.unwrap_or(false))?;
+        if !report.plan.dry_run {
+            let config = state.config();
+            fs_scope::sync(&app, &config);
+            hooks::active_channel_changed(&previous, &config);
+        }
+        Ok(report)
+    })
+    .await
+}
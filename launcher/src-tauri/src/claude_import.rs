@@ -0,0 +1,241 @@
+//! Channels recovered from a hand-written Claude Code config
+//!
+//! `import_from_claude_config` looks through `~/.claude.json` for MCP server
+//! entries that run the Banter server, both the global `mcpServers` and the
+//! per-project ones under `projects`, and turns each distinct
+//! `UNITY_PROJECT_PATH` / `UNITY_SCENE_PATH` pair into a channel. Entries that
+//! match an existing channel are skipped, so running it twice is harmless.
+
+use serde::Serialize;
+use specta::Type;
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::error::LauncherError;
+use crate::plan::FilePlan;
+use crate::state::{AppState, SharedState};
+use crate::{fs_scope, hooks, sanitize, LauncherConfig, ProjectChannel};
+
+/// Older servers also read the project from this variable
+const LEGACY_PROJECT_VAR: &str = "BANTER_PROJECT_PATH";
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct SkippedEntry {
+    /// Where the entry was found, e.g. "mcpServers.banter"
+    pub entry: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ClaudeImportReport {
+    pub created: Vec<ProjectChannel>,
+    pub skipped: Vec<SkippedEntry>,
+    /// MCP server path taken from the entries because the configured one is missing
+    pub mcp_server_path: Option<String>,
+    pub plan: FilePlan,
+}
+
+/// An MCP server entry that runs our server
+struct Found {
+    entry: String,
+    project: String,
+    scene: Option<String>,
+    server: Option<String>,
+}
+
+fn env_str<'a>(env: Option<&'a serde_json::Value>, name: &str) -> Option<&'a str> {
+    env?.get(name)?.as_str().filter(|s| !s.trim().is_empty())
+}
+
+/// Collect the entries that run the Banter server: ones that set the Unity
+/// project, run the configured server or are the `banter` entry the launcher
+/// writes
+fn find_entries(
+    servers: &serde_json::Value,
+    prefix: &str,
+    config: &LauncherConfig,
+    found: &mut Vec<Found>,
+    skipped: &mut Vec<SkippedEntry>,
+) {
+    let Some(servers) = servers.as_object() else {
+        return;
+    };
+
+    for (name, server) in servers {
+        let entry = format!("{}.{}", prefix, name);
+        let env = server.get("env");
+        let script = server
+            .get("args")
+            .and_then(|a| a.as_array())
+            .and_then(|a| {
+                a.iter()
+                    .filter_map(|v| v.as_str())
+                    .find(|a| a.ends_with(".js"))
+            })
+            .map(|s| s.to_string());
+        let project = env_str(env, "UNITY_PROJECT_PATH").or(env_str(env, LEGACY_PROJECT_VAR));
+
+        let ours = project.is_some()
+            || name == "banter"
+            || script.as_deref() == Some(config.mcp_server_path.as_str());
+        if !ours {
+            continue;
+        }
+        let Some(project) = project else {
+            skipped.push(SkippedEntry {
+                entry,
+                reason: "No UNITY_PROJECT_PATH set".to_string(),
+            });
+            continue;
+        };
+
+        found.push(Found {
+            entry,
+            project: project.to_string(),
+            scene: env_str(env, "UNITY_SCENE_PATH").map(|s| s.to_string()),
+            server: script,
+        });
+    }
+}
+
+/// Name for a recovered channel: the scene, else the project folder
+fn channel_name(found: &Found) -> String {
+    let scene = found
+        .scene
+        .as_deref()
+        .and_then(|s| Path::new(s).file_stem());
+    scene
+        .or_else(|| Path::new(&found.project).file_name())
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Imported channel".to_string())
+}
+
+fn same_path(a: &str, b: &str) -> bool {
+    let normalize = |p: &str| p.replace('\\', "/").trim_end_matches('/').to_lowercase();
+    normalize(a) == normalize(b)
+}
+
+pub fn import(state: &AppState, dry_run: bool) -> Result<ClaudeImportReport, LauncherError> {
+    let claude = crate::get_claude_mcp_config()?;
+    let config = state.config();
+
+    let mut found = Vec::new();
+    let mut skipped = Vec::new();
+    find_entries(
+        &claude["mcpServers"],
+        "mcpServers",
+        &config,
+        &mut found,
+        &mut skipped,
+    );
+    if let Some(projects) = claude.get("projects").and_then(|p| p.as_object()) {
+        for (project, settings) in projects {
+            find_entries(
+                &settings["mcpServers"],
+                &format!("projects.{}.mcpServers", project),
+                &config,
+                &mut found,
+                &mut skipped,
+            );
+        }
+    }
+
+    let mut created: Vec<ProjectChannel> = Vec::new();
+    for found in &found {
+        let duplicate = config.channels.iter().chain(created.iter()).any(|c| {
+            same_path(&c.unity_project_path, &found.project)
+                && match (&c.scene_path, &found.scene) {
+                    (Some(a), Some(b)) => same_path(a, b),
+                    (None, None) => true,
+                    _ => false,
+                }
+        });
+        if duplicate {
+            skipped.push(SkippedEntry {
+                entry: found.entry.clone(),
+                reason: "A channel for this project and scene already exists".to_string(),
+            });
+            continue;
+        }
+
+        let channel = ProjectChannel {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: channel_name(found),
+            unity_project_path: found.project.clone(),
+            scene_path: found.scene.clone(),
+            enabled: true,
+            ..Default::default()
+        };
+        if let Err(e) = sanitize::channel(&channel) {
+            skipped.push(SkippedEntry {
+                entry: found.entry.clone(),
+                reason: e.to_string(),
+            });
+            continue;
+        }
+        if !Path::new(&channel.unity_project_path)
+            .join("Assets")
+            .is_dir()
+        {
+            skipped.push(SkippedEntry {
+                entry: found.entry.clone(),
+                reason: format!("Unity project not found: {}", channel.unity_project_path),
+            });
+            continue;
+        }
+        created.push(channel);
+    }
+
+    let mcp_server_path = if Path::new(&config.mcp_server_path).exists() {
+        None
+    } else {
+        found
+            .iter()
+            .filter_map(|f| f.server.as_deref())
+            .find(|s| Path::new(s).exists())
+            .map(|s| s.to_string())
+    };
+
+    let mut plan = FilePlan::new(dry_run);
+    if !created.is_empty() || mcp_server_path.is_some() {
+        state.update(&mut plan, |config| {
+            config.channels.extend(created.iter().cloned());
+            if config.active_channel_id.is_none() {
+                config.active_channel_id = created.first().map(|c| c.id.clone());
+            }
+            if let Some(server) = &mcp_server_path {
+                config.mcp_server_path = server.clone();
+            }
+            Ok(())
+        })?;
+    }
+
+    Ok(ClaudeImportReport {
+        created,
+        skipped,
+        mcp_server_path,
+        plan,
+    })
+}
+
+/// Create channels from existing Claude Code MCP entries for the Banter server
+#[tauri::command]
+#[specta::specta]
+pub async fn import_from_claude_config(
+    app: AppHandle,
+    state: tauri::State<'_, SharedState>,
+    dry_run: Option<bool>,
+) -> Result<ClaudeImportReport, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Import from Claude config", move || {
+        let previous = state.config();
+        let report = import(&state, dry_run.unwrap_or(false))?;
+        if !report.plan.dry_run {
+            let config = state.config();
+            fs_scope::sync(&app, &config);
+            hooks::active_channel_changed(&previous, &config);
+        }
+        Ok(report)
+    })
+    .await
+}
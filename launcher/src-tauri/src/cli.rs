@@ -0,0 +1,929 @@
+//! Headless command line mode
+//!
+//! `--list-channels`, `--activate <channel>` and `--doctor` run the same code
+//! as the window's commands and exit without starting Tauri, so activation
+//! can be scripted or run over SSH on a build machine. `--validate-config`
+//! gates committed config files in CI (see `config_check`), and
+//! `--export-bindings` regenerates the frontend's bindings (see
+//! `bindings`). `--uninstall-cleanup` is what the installer runs before
+//! removing the launcher (see `uninstall`).
+//!
+//! With `--json` every command prints one JSON object on stdout,
+//! `{ "version": 1, "command", "ok", "result" | "error" }`, whose `result`
+//! fields are only ever added to. Exit codes are listed in `USAGE`.
+//!
+//! `--watch` is the exception: it supervises the MCP server until killed and
+//! logs as it goes instead of printing a result (see `watch`). So is
+//! `--simulated-server`, the fake server `--watch` runs in simulation mode
+//! (see `simulation`), and `--mcp-proxy`, which Claude Code starts in place of
+//! the MCP server while sessions are recorded or servers pooled (see
+//! `mcp_proxy` and `server_pool`). `--mcp-status` is the launcher's own MCP
+//! server (see `status_server`).
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Command as Process;
+
+use crate::batchmode::{self, ExtensionVerification, SceneList};
+use crate::config_check::{self, ConfigCheck};
+use crate::error::{ErrorKind, LauncherError};
+use crate::faults::{self, Fault};
+use crate::guids::GuidCache;
+use crate::health::{self, ChannelHealth, HealthCheck, HealthStatus};
+use crate::hooks;
+use crate::mcp_proxy;
+use crate::observer;
+use crate::path_index::PathIndex;
+use crate::preflight;
+use crate::stack::{self, Capability};
+use crate::state::AppState;
+use crate::uninstall::{self, UninstallOptions, UninstallReport};
+use crate::{
+    banter_sdk, bindings, i18n, instances, simulation, status_server, watch, LauncherConfig,
+    ProjectChannel,
+};
+
+const USAGE: &str = "\
+Usage: banter-launcher [OPTION] [--json]
+
+Without an option the launcher window opens; with --observer it opens in
+read-only observer mode.
+
+Options:
+  --list-channels        List the configured channels
+  --activate <channel> [--take-over] [--ignore-health]
+                         Make a channel (id, name, or a file in its
+                         project) active and, with auto-configure on,
+                         point Claude Code at it;
+                         --take-over deactivates it in another launcher
+                         using the same Unity project, --ignore-health
+                         activates it even if the health gate refuses
+  --doctor               Check the config, MCP server and every channel
+  --list-scenes <channel>
+                         List the channel's scenes as Unity sees them
+  --verify-extension <channel>
+                         Check that the Unity extension compiles and runs
+                         in the channel's project; both go through the open
+                         editor, or start Unity in batchmode without one
+  --validate-config <file>
+                         Check a channels file or launcher config against
+                         the schema and path rules, without touching this
+                         machine's launcher config
+  --watch [--port <n>]   Keep the active channel's MCP server running in
+                         HTTP mode (default port 42067 plus a per-user
+                         offset), restarting it on
+                         failure; logs to watch.log in the launcher logs
+  --simulated-server <channel> [--port <n>]
+                         Run the fake MCP server of simulation mode for a
+                         channel id (default port as for --watch)
+  --mcp-proxy [--pooled] <channel> <server>
+                         Run the MCP server script behind the session
+                         recorder's stdio proxy; Claude Code starts this
+                         itself while record_sessions or server_pool is on.
+                         --pooled connects to the channel's shared server,
+                         starting it if none is running
+  --mcp-status           Serve the launcher's channels, health and logs as
+                         MCP resources on stdio; Claude Code starts this
+                         itself while serve_launcher_state is on
+  --export-bindings [<file>]
+                         Write TypeScript types for the window's commands
+                         and events, and the matching .js next to them
+                         (default: src/bindings.ts in the source tree the
+                         launcher was built from)
+  --uninstall-cleanup [--delete-config] [--delete-logs] [--dry-run]
+                         Remove the Claude Code entry and the Unity
+                         extension from every channel's project before
+                         uninstalling; optionally delete the launcher's
+                         config and logs too
+  --json                 Print the result as JSON on stdout
+  -h, --help             Show this help
+
+Exit codes:
+  0  success
+  1  unexpected failure
+  2  bad command line or argument
+  3  channel or file not found
+  4  unreadable config file
+  5  permission denied
+  6  --doctor or --validate-config found errors
+  7  conflict, e.g. another launcher is using the channel's Unity project
+";
+
+/// Version of the `--json` envelope; bumped only for breaking changes
+const JSON_VERSION: u32 = 1;
+
+const EXIT_OK: i32 = 0;
+const EXIT_FAILED: i32 = 1;
+const EXIT_USAGE: i32 = 2;
+const EXIT_NOT_FOUND: i32 = 3;
+const EXIT_CONFIG: i32 = 4;
+const EXIT_PERMISSION: i32 = 5;
+const EXIT_CHECKS_FAILED: i32 = 6;
+const EXIT_CONFLICT: i32 = 7;
+
+fn exit_code(e: &LauncherError) -> i32 {
+    match e.kind {
+        ErrorKind::NotFound => EXIT_NOT_FOUND,
+        ErrorKind::InvalidInput => EXIT_USAGE,
+        ErrorKind::Parse => EXIT_CONFIG,
+        ErrorKind::PermissionDenied => EXIT_PERMISSION,
+        ErrorKind::Conflict => EXIT_CONFLICT,
+        _ => EXIT_FAILED,
+    }
+}
+
+enum Command {
+    ListChannels,
+    Activate(String, bool, bool),
+    Doctor,
+    ListScenes(String),
+    VerifyExtension(String),
+    ValidateConfig(String),
+    Watch(u16),
+    SimulatedServer(String, u16),
+    McpProxy(String, String, bool),
+    McpStatus,
+    ExportBindings(PathBuf),
+    UninstallCleanup(UninstallOptions),
+    Help,
+}
+
+impl Command {
+    /// Name reported in the `--json` envelope
+    fn name(&self) -> &'static str {
+        match self {
+            Command::ListChannels => "list-channels",
+            Command::Activate(..) => "activate",
+            Command::Doctor => "doctor",
+            Command::ListScenes(_) => "list-scenes",
+            Command::VerifyExtension(_) => "verify-extension",
+            Command::ValidateConfig(_) => "validate-config",
+            Command::Watch(_) => "watch",
+            Command::SimulatedServer(..) => "simulated-server",
+            Command::McpProxy(..) => "mcp-proxy",
+            Command::McpStatus => "mcp-status",
+            Command::ExportBindings(_) => "export-bindings",
+            Command::UninstallCleanup(_) => "uninstall-cleanup",
+            Command::Help => "help",
+        }
+    }
+}
+
+/// Result of a command: JSON for `--json`, text otherwise
+trait Output: Serialize {
+    fn text(&self) -> String;
+
+    fn exit_code(&self) -> i32 {
+        EXIT_OK
+    }
+}
+
+#[derive(Serialize)]
+struct Envelope {
+    version: u32,
+    command: &'static str,
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<LauncherError>,
+}
+
+fn print_json(envelope: &Envelope) {
+    match serde_json::to_string_pretty(envelope) {
+        Ok(json) => println!("{}", json),
+        Err(e) => eprintln!("error: failed to serialize output: {}", e),
+    }
+}
+
+fn finish<T: Output>(command: &Command, json: bool, result: Result<T, LauncherError>) -> i32 {
+    let (code, envelope) = match result {
+        Ok(output) => {
+            let code = output.exit_code();
+            if !json {
+                print!("{}", output.text());
+                return code;
+            }
+            let envelope = Envelope {
+                version: JSON_VERSION,
+                command: command.name(),
+                ok: code == EXIT_OK,
+                result: serde_json::to_value(&output).ok(),
+                error: None,
+            };
+            (code, envelope)
+        }
+        Err(e) => {
+            let code = exit_code(&e);
+            if !json {
+                print_error(&e);
+                return code;
+            }
+            let envelope = Envelope {
+                version: JSON_VERSION,
+                command: command.name(),
+                ok: false,
+                result: None,
+                error: Some(e),
+            };
+            (code, envelope)
+        }
+    };
+    print_json(&envelope);
+    code
+}
+
+/// `Ok(None)` means no CLI command: start the window
+fn parse(args: &[String]) -> Result<Option<Command>, String> {
+    let Some(first) = args.first() else {
+        return Ok(None);
+    };
+    let (command, used) = match first.as_str() {
+        "--list-channels" => (Command::ListChannels, 1),
+        "--activate" => {
+            let Some(channel) = args.get(1).filter(|c| !c.starts_with("--")) else {
+                return Err("--activate needs a channel id or name".to_string());
+            };
+            let (mut take_over, mut ignore_health) = (false, false);
+            for flag in &args[2..] {
+                match flag.as_str() {
+                    "--take-over" => take_over = true,
+                    "--ignore-health" => ignore_health = true,
+                    other => return Err(format!("Unexpected argument: {}", other)),
+                }
+            }
+            let command = Command::Activate(channel.clone(), take_over, ignore_health);
+            (command, args.len())
+        }
+        "--doctor" => (Command::Doctor, 1),
+        "--list-scenes" => match args.get(1) {
+            Some(channel) => (Command::ListScenes(channel.clone()), 2),
+            None => return Err("--list-scenes needs a channel id or name".to_string()),
+        },
+        "--verify-extension" => match args.get(1) {
+            Some(channel) => (Command::VerifyExtension(channel.clone()), 2),
+            None => return Err("--verify-extension needs a channel id or name".to_string()),
+        },
+        "--validate-config" => match args.get(1) {
+            Some(file) => (Command::ValidateConfig(file.clone()), 2),
+            None => return Err("--validate-config needs a file".to_string()),
+        },
+        "--watch" => match args.get(1).map(String::as_str) {
+            Some("--port") => (Command::Watch(parse_port(args.get(2))?), 3),
+            _ => (Command::Watch(watch::default_port()), 1),
+        },
+        "--simulated-server" => {
+            let Some(channel) = args.get(1) else {
+                return Err("--simulated-server needs a channel id".to_string());
+            };
+            match args.get(2).map(String::as_str) {
+                Some("--port") => (
+                    Command::SimulatedServer(channel.clone(), parse_port(args.get(3))?),
+                    4,
+                ),
+                _ => (
+                    Command::SimulatedServer(channel.clone(), watch::default_port()),
+                    2,
+                ),
+            }
+        }
+        mcp_proxy::SWITCH => {
+            let pooled = args.get(1).is_some_and(|a| a == mcp_proxy::POOLED);
+            let skip = usize::from(pooled);
+            match (args.get(1 + skip), args.get(2 + skip)) {
+                (Some(channel), Some(server)) => (
+                    Command::McpProxy(channel.clone(), server.clone(), pooled),
+                    3 + skip,
+                ),
+                _ => {
+                    return Err(
+                        "--mcp-proxy needs a channel id and the MCP server script".to_string()
+                    )
+                }
+            }
+        }
+        status_server::SWITCH => (Command::McpStatus, 1),
+        "--export-bindings" => match args.get(1) {
+            Some(file) => (Command::ExportBindings(PathBuf::from(file)), 2),
+            None => (Command::ExportBindings(bindings::default_path()), 1),
+        },
+        "--uninstall-cleanup" => {
+            let mut options = UninstallOptions::default();
+            for flag in &args[1..] {
+                match flag.as_str() {
+                    "--delete-config" => options.delete_config = true,
+                    "--delete-logs" => options.delete_logs = true,
+                    "--dry-run" => options.dry_run = true,
+                    other => return Err(format!("Unexpected argument: {}", other)),
+                }
+            }
+            (Command::UninstallCleanup(options), args.len())
+        }
+        "-h" | "--help" => (Command::Help, 1),
+        "--observer" if args.len() == 1 => return Ok(None),
+        // Anything else (e.g. macOS `-psn_` args) belongs to the window
+        other if !other.starts_with("--") => return Ok(None),
+        other => return Err(format!("Unknown option: {}", other)),
+    };
+    if let Some(extra) = args.get(used) {
+        return Err(format!("Unexpected argument: {}", extra));
+    }
+    Ok(Some(command))
+}
+
+fn parse_port(arg: Option<&String>) -> Result<u16, String> {
+    arg.and_then(|p| p.parse::<u16>().ok())
+        .filter(|p| *p != 0)
+        .ok_or_else(|| "--port needs a port number".to_string())
+}
+
+/// Release builds on Windows have no console of their own; borrow the
+/// caller's so output reaches the terminal
+#[cfg(windows)]
+fn attach_console() {
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(windows))]
+fn attach_console() {}
+
+fn print_error(e: &LauncherError) {
+    eprintln!("error: {}", e);
+    if let Some(path) = &e.path {
+        eprintln!("  path: {}", path);
+    }
+    if let Some(hint) = &e.hint {
+        eprintln!("  hint: {}", hint);
+    }
+}
+
+/// Run the CLI command in `args` (without the program name). Returns the exit
+/// code, or `None` when the window should start instead.
+pub fn run(args: &[String]) -> Option<i32> {
+    let json = args.iter().any(|a| a == "--json");
+    let rest: Vec<String> = args.iter().filter(|a| *a != "--json").cloned().collect();
+
+    let command = match parse(&rest) {
+        Ok(None) if !json => return None,
+        Ok(Some(command)) => command,
+        Ok(None) => {
+            attach_console();
+            eprintln!("error: --json needs a command\n\n{}", USAGE);
+            return Some(EXIT_USAGE);
+        }
+        Err(message) => {
+            attach_console();
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            return Some(EXIT_USAGE);
+        }
+    };
+    attach_console();
+    if let Ok(config) = crate::read_config() {
+        i18n::set_locale(&config.locale);
+        crate::storage::follow(config.storage_backend);
+    }
+
+    let code = match &command {
+        Command::Help => {
+            print!("{}", USAGE);
+            EXIT_OK
+        }
+        Command::ListChannels => finish(&command, json, list_channels()),
+        Command::Activate(channel, take_over, ignore_health) => finish(
+            &command,
+            json,
+            activate(channel, *take_over, *ignore_health),
+        ),
+        Command::Doctor => finish(&command, json, doctor()),
+        Command::ListScenes(channel) => finish(&command, json, list_scenes(channel)),
+        Command::VerifyExtension(channel) => finish(&command, json, verify_extension(channel)),
+        Command::ValidateConfig(file) => {
+            finish(&command, json, config_check::check_file(Path::new(file)))
+        }
+        Command::ExportBindings(path) => finish(&command, json, export_bindings(path)),
+        Command::UninstallCleanup(options) => finish(&command, json, uninstall_cleanup(options)),
+        Command::Watch(port) => match crate::read_config()
+            .and_then(|config| {
+                observer::check(&config, "--watch")?;
+                stack::require(&config, Capability::Node, "--watch")
+            })
+            .and_then(|_| watch::run(*port))
+        {
+            Ok(()) => EXIT_OK,
+            Err(e) => {
+                print_error(&e);
+                exit_code(&e)
+            }
+        },
+        Command::SimulatedServer(channel, port) => match simulation::run_server(channel, *port) {
+            Ok(()) => EXIT_OK,
+            Err(e) => {
+                print_error(&e);
+                exit_code(&e)
+            }
+        },
+        Command::McpProxy(channel, server, pooled) => {
+            match mcp_proxy::run(channel, server, *pooled) {
+                Ok(code) => code,
+                Err(e) => {
+                    print_error(&e);
+                    exit_code(&e)
+                }
+            }
+        }
+        Command::McpStatus => match status_server::run() {
+            Ok(()) => EXIT_OK,
+            Err(e) => {
+                print_error(&e);
+                exit_code(&e)
+            }
+        },
+    };
+
+    // Activation hooks run in the background; let them finish
+    hooks::wait();
+    Some(code)
+}
+
+#[derive(Serialize)]
+struct ChannelEntry {
+    id: String,
+    name: String,
+    unity_project_path: String,
+    scene_path: Option<String>,
+    active: bool,
+}
+
+#[derive(Serialize)]
+struct ChannelList {
+    active_channel_id: Option<String>,
+    channels: Vec<ChannelEntry>,
+}
+
+impl Output for ChannelList {
+    fn text(&self) -> String {
+        if self.channels.is_empty() {
+            return "No channels configured\n".to_string();
+        }
+        let mut text = String::new();
+        for channel in &self.channels {
+            let marker = if channel.active { "*" } else { " " };
+            let location = channel
+                .scene_path
+                .as_deref()
+                .unwrap_or(&channel.unity_project_path);
+            text += &format!(
+                "{} {}  {}\n    {}\n",
+                marker, channel.id, channel.name, location
+            );
+        }
+        text
+    }
+}
+
+fn list_channels() -> Result<ChannelList, LauncherError> {
+    let config = crate::read_config()?;
+    let channels = config
+        .channels
+        .iter()
+        .map(|c| ChannelEntry {
+            id: c.id.clone(),
+            name: c.name.clone(),
+            unity_project_path: c.unity_project_path.clone(),
+            scene_path: c.scene_path.clone(),
+            active: config.active_channel_id.as_deref() == Some(c.id.as_str()),
+        })
+        .collect();
+    Ok(ChannelList {
+        active_channel_id: config.active_channel_id.clone(),
+        channels,
+    })
+}
+
+/// Find a channel by id, by (case-insensitive) name, or else by a file or
+/// folder in its project
+fn resolve_channel(config: &LauncherConfig, query: &str) -> Result<ProjectChannel, LauncherError> {
+    if let Some(channel) = config.channels.iter().find(|c| c.id == query) {
+        return Ok(channel.clone());
+    }
+    if Path::new(query).exists()
+        && !config
+            .channels
+            .iter()
+            .any(|c| c.name.eq_ignore_ascii_case(query))
+    {
+        let found = PathIndex::new(&config.channels)
+            .find(Path::new(query))
+            .ok_or_else(|| {
+                LauncherError::channel_not_found(query)
+                    .with_hint("No channel's project contains this path")
+            })?;
+        return crate::find_channel(config, &found.channel_id);
+    }
+
+    let matches: Vec<&ProjectChannel> = config
+        .channels
+        .iter()
+        .filter(|c| c.name.eq_ignore_ascii_case(query))
+        .collect();
+    match matches.as_slice() {
+        [channel] => Ok((*channel).clone()),
+        [] => Err(LauncherError::channel_not_found(query)
+            .with_hint("Run --list-channels to see channel ids")),
+        _ => {
+            Err(
+                LauncherError::invalid(format!("{} channels are named '{}'", matches.len(), query))
+                    .with_hint("Pass the channel id instead"),
+            )
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Activation {
+    channel_id: String,
+    channel_name: String,
+    /// Claude Code config written, `None` when auto-configure is off
+    client_config_path: Option<String>,
+    /// Failed health checks the gate let through
+    health_warnings: Vec<HealthCheck>,
+}
+
+impl Output for Activation {
+    fn text(&self) -> String {
+        let mut text = format!("Activated {} ({})\n", self.channel_name, self.channel_id);
+        if let Some(path) = &self.client_config_path {
+            text += &format!("Updated Claude Code config: {}\n", path);
+        }
+        for check in &self.health_warnings {
+            text += &format!("Warning: {}\n", check.message);
+        }
+        text
+    }
+}
+
+fn activate(
+    query: &str,
+    take_over: bool,
+    ignore_health: bool,
+) -> Result<Activation, LauncherError> {
+    let state = AppState::load()?;
+    observer::check(&state.config(), "--activate")?;
+    let channel = resolve_channel(&state.config(), query)?;
+    let activated = crate::activate_channel(&state, &channel.id, take_over, ignore_health)?;
+
+    Ok(Activation {
+        channel_id: channel.id,
+        channel_name: channel.name,
+        client_config_path: activated
+            .client_config
+            .map(|p| p.to_string_lossy().to_string()),
+        health_warnings: activated.health_warnings,
+    })
+}
+
+impl Output for SceneList {
+    fn text(&self) -> String {
+        let mut text = String::new();
+        for scene in &self.scenes {
+            let build = match (scene.in_build_settings, scene.enabled) {
+                (true, true) => "  [build]",
+                (true, false) => "  [build, disabled]",
+                _ => "",
+            };
+            text += &format!("{}{}\n", scene.path, build);
+        }
+        text + &format!("{} scenes (via {})\n", self.scenes.len(), self.mode)
+    }
+}
+
+impl Output for ExtensionVerification {
+    fn text(&self) -> String {
+        format!(
+            "Unity extension {} runs in Unity {} (via {})\n",
+            self.bridge_version
+                .as_deref()
+                .unwrap_or("(unknown version)"),
+            self.unity_version.as_deref().unwrap_or("(unknown version)"),
+            self.mode
+        )
+    }
+}
+
+impl Output for UninstallReport {
+    fn text(&self) -> String {
+        let prefix = if self.plan.dry_run {
+            "Would remove"
+        } else {
+            "Removed"
+        };
+        let mut text = String::new();
+        for stopped in &self.stopped {
+            text += &format!("Stopped the {}\n", stopped);
+        }
+        for path in &self.client_configs {
+            text += &format!("{} the banter entry from {}\n", prefix, path);
+        }
+        for channel_id in &self.extensions_removed {
+            text += &format!("{} the Unity extension from {}\n", prefix, channel_id);
+        }
+        for failure in &self.failures {
+            text += &format!("error: {}: {}\n", failure.channel_id, failure.error);
+        }
+        text + &format!("{} files and folders touched\n", self.plan.operations.len())
+    }
+
+    fn exit_code(&self) -> i32 {
+        if self.failures.is_empty() {
+            EXIT_OK
+        } else {
+            EXIT_FAILED
+        }
+    }
+}
+
+fn uninstall_cleanup(options: &UninstallOptions) -> Result<UninstallReport, LauncherError> {
+    // Not refused in observer mode: the uninstaller runs this, and skipping
+    // it would leave the broken client entry behind
+    uninstall::cleanup(&AppState::load()?, options)
+}
+
+fn list_scenes(query: &str) -> Result<SceneList, LauncherError> {
+    let config = AppState::load()?.config();
+    observer::check(&config, "--list-scenes")?;
+    let channel = resolve_channel(&config, query)?;
+    batchmode::list_scenes(&config, &channel, &mut |_| Ok(()))
+}
+
+fn verify_extension(query: &str) -> Result<ExtensionVerification, LauncherError> {
+    let config = AppState::load()?.config();
+    observer::check(&config, "--verify-extension")?;
+    let channel = resolve_channel(&config, query)?;
+    batchmode::verify_extension(&config, &channel, &mut |_| Ok(()))
+}
+
+fn status_label(status: HealthStatus) -> &'static str {
+    match status {
+        HealthStatus::Ok => "ok",
+        HealthStatus::Warning => "warn",
+        HealthStatus::Error => "error",
+    }
+}
+
+/// Checks that don't belong to a channel
+/// The MCP server file and Node.js, which simulation mode doesn't need
+fn server_checks(config: &LauncherConfig, checks: &mut Vec<HealthCheck>) {
+    let server = Path::new(&config.mcp_server_path);
+    if config.mcp_server_path.is_empty() {
+        checks.push(HealthCheck::new(
+            "mcp-server",
+            HealthStatus::Error,
+            "MCP server path is not set",
+        ));
+    } else if server.exists() {
+        let version = banter_sdk::read_server_version(&config.mcp_server_path)
+            .map(|v| format!(" ({})", v))
+            .unwrap_or_default();
+        checks.push(HealthCheck::new(
+            "mcp-server",
+            HealthStatus::Ok,
+            format!("MCP server found{}", version),
+        ));
+    } else {
+        checks.push(HealthCheck::new(
+            "mcp-server",
+            HealthStatus::Error,
+            format!("MCP server not found: {}", config.mcp_server_path),
+        ));
+    }
+
+    match faults::check(Fault::NodeMissing)
+        .and_then(|_| Process::new("node").arg("--version").output())
+    {
+        Ok(output) if output.status.success() => checks.push(HealthCheck::new(
+            "node",
+            HealthStatus::Ok,
+            format!("Node.js {}", String::from_utf8_lossy(&output.stdout).trim()),
+        )),
+        _ => checks.push(HealthCheck::new(
+            "node",
+            HealthStatus::Error,
+            "Node.js not found on PATH",
+        )),
+    }
+}
+
+fn launcher_checks(config: &LauncherConfig) -> Vec<HealthCheck> {
+    let mut checks = Vec::new();
+
+    let injected = faults::active();
+    if !injected.is_empty() {
+        let names: Vec<&str> = injected.iter().map(|f| f.as_str()).collect();
+        checks.push(HealthCheck::new(
+            "faults",
+            HealthStatus::Warning,
+            format!(
+                "Injecting faults ({}): {}",
+                faults::ENV_VAR,
+                names.join(", ")
+            ),
+        ));
+    }
+
+    // Config, backups, snapshots and logs all live here
+    let launcher_dir = crate::launcher_dir();
+    checks.push(health::volume_check(
+        "disk",
+        &preflight::check("launcher data", &launcher_dir, 0),
+    ));
+
+    if let Some(others) = instances::describe(&instances::report(config, &[])) {
+        checks.push(HealthCheck::new(
+            "other-users",
+            HealthStatus::Warning,
+            others,
+        ));
+    }
+
+    if simulation::enabled(config) {
+        checks.push(HealthCheck::new(
+            "simulation",
+            HealthStatus::Ok,
+            "Simulation mode: the MCP server and Unity bridge are simulated",
+        ));
+    } else {
+        server_checks(config, &mut checks);
+    }
+
+    match crate::get_claude_mcp_config() {
+        Ok(claude) if claude.pointer("/mcpServers/banter").is_some() => {
+            checks.push(HealthCheck::new(
+                "claude-config",
+                HealthStatus::Ok,
+                "Claude Code is configured",
+            ))
+        }
+        Ok(_) => checks.push(HealthCheck::new(
+            "claude-config",
+            HealthStatus::Warning,
+            "Claude Code has no banter MCP server entry",
+        )),
+        Err(e) => checks.push(HealthCheck::new(
+            "claude-config",
+            HealthStatus::Error,
+            e.message,
+        )),
+    }
+
+    match &config.active_channel_id {
+        Some(id) if config.channels.iter().any(|c| &c.id == id) => checks.push(HealthCheck::new(
+            "active-channel",
+            HealthStatus::Ok,
+            format!("Active channel {}", id),
+        )),
+        Some(id) => checks.push(HealthCheck::new(
+            "active-channel",
+            HealthStatus::Error,
+            format!("Active channel {} no longer exists", id),
+        )),
+        None => checks.push(HealthCheck::new(
+            "active-channel",
+            HealthStatus::Warning,
+            "No active channel",
+        )),
+    }
+
+    checks
+}
+
+fn format_checks(checks: &[HealthCheck]) -> String {
+    checks
+        .iter()
+        .map(|check| {
+            format!(
+                "  [{}] {}: {}\n",
+                status_label(check.status),
+                check.id,
+                check.message
+            )
+        })
+        .collect()
+}
+
+#[derive(Serialize)]
+struct DoctorReport {
+    /// Worst status of all checks
+    status: HealthStatus,
+    launcher: Vec<HealthCheck>,
+    channels: Vec<DoctorChannel>,
+}
+
+#[derive(Serialize)]
+struct DoctorChannel {
+    name: String,
+    #[serde(flatten)]
+    health: ChannelHealth,
+}
+
+impl Output for DoctorReport {
+    fn text(&self) -> String {
+        let mut text = format!("Launcher\n{}", format_checks(&self.launcher));
+        for channel in &self.channels {
+            text += &format!(
+                "\n{} ({})\n{}",
+                channel.name,
+                channel.health.channel_id,
+                format_checks(&channel.health.checks)
+            );
+        }
+        text + &format!("\nOverall: {}\n", status_label(self.status))
+    }
+
+    fn exit_code(&self) -> i32 {
+        if self.status == HealthStatus::Error {
+            EXIT_CHECKS_FAILED
+        } else {
+            EXIT_OK
+        }
+    }
+}
+
+fn doctor() -> Result<DoctorReport, LauncherError> {
+    let config = crate::read_config()?;
+    let launcher = launcher_checks(&config);
+    let guids = GuidCache::default();
+    let channels: Vec<DoctorChannel> = config
+        .channels
+        .iter()
+        .map(|channel| DoctorChannel {
+            name: channel.name.clone(),
+            health: health::check_channel(channel, &config, &guids),
+        })
+        .collect();
+
+    let status = launcher
+        .iter()
+        .map(|c| c.status)
+        .chain(channels.iter().map(|c| c.health.status))
+        .max()
+        .unwrap_or(HealthStatus::Ok);
+
+    Ok(DoctorReport {
+        status,
+        launcher,
+        channels,
+    })
+}
+
+impl Output for ConfigCheck {
+    fn text(&self) -> String {
+        let mut text = String::new();
+        for issue in &self.issues {
+            let label = if issue.severity == "error" {
+                "error"
+            } else {
+                "warn"
+            };
+            match &issue.field {
+                Some(field) => text += &format!("  [{}] {}: {}\n", label, field, issue.message),
+                None => text += &format!("  [{}] {}\n", label, issue.message),
+            }
+        }
+        text + &format!(
+            "{}: {} error(s), {} warning(s)\n",
+            self.path, self.errors, self.warnings
+        )
+    }
+
+    fn exit_code(&self) -> i32 {
+        if self.errors > 0 {
+            EXIT_CHECKS_FAILED
+        } else {
+            EXIT_OK
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Exported {
+    path: String,
+}
+
+impl Output for Exported {
+    fn text(&self) -> String {
+        format!("Wrote {}\n", self.path)
+    }
+}
+
+fn export_bindings(path: &Path) -> Result<Exported, LauncherError> {
+    bindings::export(&crate::command_builder(), path)?;
+    Ok(Exported {
+        path: path.to_string_lossy().to_string(),
+    })
+}
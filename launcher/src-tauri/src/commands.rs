@@ -0,0 +1,326 @@
+//! Async IPC entry points for the config, channel and client-config commands
+//!
+//! The logic lives in plain synchronous functions in `launcher_core` and, where
+//! it needs the app's state, `main.rs`; these wrappers run it on the blocking
+//! pool so file scans, copies and config I/O never hold up the IPC thread.
+
+use std::time::Instant;
+use tauri::{AppHandle, State};
+
+use crate::error::LauncherError;
+use crate::jobs::JobRequest;
+use crate::plan::FilePlan;
+use crate::state::SharedState;
+use crate::{LauncherConfig, ProjectChannel};
+use launcher_core::config::ChannelQuotas;
+use launcher_core::path_index::ChannelMatch;
+
+/// Run blocking work (filesystem, processes, network) on the blocking pool
+pub async fn blocking<T, F>(name: &str, work: F) -> Result<T, LauncherError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, LauncherError> + Send + 'static,
+{
+    let label = name.to_string();
+    tauri::async_runtime::spawn_blocking(move || {
+        let started = Instant::now();
+        let result = work();
+        crate::metrics::record_command(&label, started.elapsed(), result.is_ok());
+        crate::telemetry::record(&label, result.as_ref().err().map(|e| e.kind));
+        crate::support::trace(
+            "command",
+            &match &result {
+                Ok(_) => format!("{} ok in {:?}", label, started.elapsed()),
+                Err(e) => format!("{} failed in {:?}: {}", label, started.elapsed(), e),
+            },
+        );
+        result
+    })
+    .await
+    .map_err(|e| LauncherError::task(name, e))?
+}
+
+/// Save pending changes, then re-read the config from disk and return it.
+/// In observer mode the file is only read: nothing is saved or reapplied.
+#[tauri::command]
+#[specta::specta]
+pub async fn load_config(state: State<'_, SharedState>) -> Result<LauncherConfig, LauncherError> {
+    let state = state.inner().clone();
+    blocking("Load config", move || {
+        if crate::observer::is_active(&state.config()) {
+            return crate::read_config();
+        }
+        state.flush()?;
+        state.reload()
+    })
+    .await
+}
+
+/// Write pending config changes immediately
+#[tauri::command]
+#[specta::specta]
+pub async fn flush_config(state: State<'_, SharedState>) -> Result<bool, LauncherError> {
+    let state = state.inner().clone();
+    blocking("Flush config", move || state.flush()).await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn save_config(
+    app: AppHandle,
+    state: State<'_, SharedState>,
+    config: LauncherConfig,
+) -> Result<(), LauncherError> {
+    let state = state.inner().clone();
+    blocking("Save config", move || {
+        crate::save_config(app, &state, config)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn add_channel(
+    name: String,
+    scene_path: String,
+) -> Result<ProjectChannel, LauncherError> {
+    blocking("Add channel", move || crate::add_channel(name, scene_path)).await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn remove_channel(
+    app: AppHandle,
+    state: State<'_, SharedState>,
+    channel_id: String,
+    dry_run: Option<bool>,
+) -> Result<FilePlan, LauncherError> {
+    let state = state.inner().clone();
+    blocking("Remove channel", move || {
+        crate::remove_channel(app, &state, channel_id, dry_run)
+    })
+    .await
+}
+
+/// Set the folders besides its project that a channel's MCP server may use.
+/// Takes effect the next time the Claude Code entry is written.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_channel_allowed_roots(
+    state: State<'_, SharedState>,
+    channel_id: String,
+    roots: Vec<String>,
+) -> Result<ProjectChannel, LauncherError> {
+    crate::sanitize::channel_id(&channel_id)?;
+    let state = state.inner().clone();
+    blocking("Allowed roots", move || {
+        let roots = roots
+            .iter()
+            .map(|root| crate::sanitize::path("Extra allowed root", root.trim()))
+            .map(|root| root.map(|p| p.to_string_lossy().to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        state.update_channel(&channel_id, move |c| c.extra_allowed_roots = roots)
+    })
+    .await
+}
+
+/// Set the limits on what a channel's MCP server does for an agent. Takes
+/// effect the next time the Claude Code entry is written.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_channel_quotas(
+    state: State<'_, SharedState>,
+    channel_id: String,
+    quotas: ChannelQuotas,
+) -> Result<ProjectChannel, LauncherError> {
+    crate::sanitize::channel_id(&channel_id)?;
+    crate::sanitize::quotas(&quotas)?;
+    let state = state.inner().clone();
+    blocking("Channel quotas", move || {
+        state.update_channel(&channel_id, move |c| c.quotas = quotas)
+    })
+    .await
+}
+
+/// Set the folder a channel's MCP server starts in and the one it keeps its
+/// data in; absolute, or relative to the project. Empty or `None` goes back
+/// to the default. Takes effect the next time the Claude Code entry is
+/// written.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_channel_server_dirs(
+    state: State<'_, SharedState>,
+    channel_id: String,
+    cwd: Option<String>,
+    data_dir: Option<String>,
+) -> Result<ProjectChannel, LauncherError> {
+    crate::sanitize::channel_id(&channel_id)?;
+    let state = state.inner().clone();
+    blocking("Server folders", move || {
+        let folder = |field: &str, value: Option<String>| {
+            value
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .map(|v| crate::sanitize::path(field, &v).map(|_| v))
+                .transpose()
+        };
+        let cwd = folder("Server working directory", cwd)?;
+        let data_dir = folder("Server data directory", data_dir)?;
+        let mut candidate = state.channel(&channel_id)?;
+        candidate.server_cwd = cwd.clone();
+        crate::channels::server_cwd(&candidate)?;
+        state.update_channel(&channel_id, move |c| {
+            c.server_cwd = cwd;
+            c.server_data_dir = data_dir;
+        })
+    })
+    .await
+}
+
+/// Share a channel's project with other clients besides Claude Code
+/// (workspace mode). Their entries are written the next time Claude Code is
+/// configured; a client taken off the list loses its entry right away.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_channel_workspace_clients(
+    state: State<'_, SharedState>,
+    channel_id: String,
+    clients: Vec<String>,
+) -> Result<ProjectChannel, LauncherError> {
+    crate::sanitize::channel_id(&channel_id)?;
+    let mut clients: Vec<String> = clients.iter().map(|c| c.trim().to_string()).collect();
+    clients.sort();
+    clients.dedup();
+    for client in &clients {
+        crate::sanitize::workspace_client(client)?;
+    }
+    let state = state.inner().clone();
+    blocking("Workspace clients", move || {
+        let previous = state.channel(&channel_id)?;
+        let mut plan = FilePlan::new(false);
+        for client in previous
+            .workspace_clients
+            .iter()
+            .filter(|c| !clients.contains(c))
+        {
+            if let Some(path) = crate::channels::workspace_client_config(&previous, client) {
+                launcher_core::client_config::plan_claude_mcp_removal_from(
+                    &mut plan,
+                    &path,
+                    &state.config().backup_retention,
+                )?;
+            }
+        }
+        state.update_channel(&channel_id, move |c| c.workspace_clients = clients)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn validate_unity_scene(path: String) -> Result<bool, LauncherError> {
+    blocking("Scene validation", move || {
+        crate::validate_unity_scene(path)
+    })
+    .await
+}
+
+/// The existing channel whose project contains `path`, so a dropped file or
+/// picked scene selects it instead of adding a duplicate
+#[tauri::command]
+#[specta::specta]
+pub async fn find_channel_for_path(path: String) -> Result<Option<ChannelMatch>, LauncherError> {
+    blocking("Channel lookup", move || {
+        let path = crate::sanitize::path("Path", &path)?;
+        Ok(crate::path_index::find_channel_for_path(&path))
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_claude_mcp_config() -> Result<serde_json::Value, LauncherError> {
+    blocking("Read Claude config", crate::get_claude_mcp_config).await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn update_claude_mcp_config(
+    state: State<'_, SharedState>,
+    channel: ProjectChannel,
+    mcp_server_path: String,
+    dry_run: Option<bool>,
+) -> Result<FilePlan, LauncherError> {
+    let state = state.inner().clone();
+    blocking("Update Claude config", move || {
+        crate::update_claude_mcp_config(&state, channel, mcp_server_path, dry_run)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn remove_claude_mcp_config(
+    state: State<'_, SharedState>,
+    dry_run: Option<bool>,
+) -> Result<FilePlan, LauncherError> {
+    let state = state.inner().clone();
+    blocking("Remove Claude config", move || {
+        crate::remove_claude_mcp_config(&state, dry_run)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn check_unity_extension(unity_project_path: String) -> Result<bool, LauncherError> {
+    blocking("Extension check", move || {
+        crate::check_unity_extension(unity_project_path)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn install_unity_extension(
+    app: AppHandle,
+    state: tauri::State<'_, SharedState>,
+    unity_project_path: String,
+    mcp_root: String,
+    dry_run: Option<bool>,
+    elevate: Option<bool>,
+) -> Result<FilePlan, LauncherError> {
+    if dry_run.unwrap_or(false) {
+        return blocking("Extension install", move || {
+            crate::install_unity_extension(unity_project_path, mcp_root, dry_run, elevate)
+        })
+        .await;
+    }
+    let state = state.inner().clone();
+    blocking("Extension install", move || {
+        state.jobs.run(
+            &app,
+            "install_extension",
+            "Install Unity extension",
+            Some(JobRequest::InstallExtension {
+                unity_project_path: unity_project_path.clone(),
+                mcp_root: mcp_root.clone(),
+                elevate,
+            }),
+            move |_| crate::install_unity_extension(unity_project_path, mcp_root, None, elevate),
+        )
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_unity_custom_scripts(
+    unity_project_path: String,
+    enabled: bool,
+) -> Result<(), LauncherError> {
+    blocking("Custom scripts setting", move || {
+        crate::set_unity_custom_scripts(unity_project_path, enabled)
+    })
+    .await
+}
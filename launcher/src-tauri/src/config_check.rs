@@ -0,0 +1,163 @@
+//! Offline validation of config files for CI
+//!
+//! `--validate-config <file>` checks either a channels manifest (see
+//! `channels_file`) or a `launcher-config.json` against its schema and the
+//! same path and name rules the launcher applies before saving. Nothing on
+//! the machine is read or written besides the file itself, and every problem
+//! is collected rather than stopping at the first, so a CI job can report
+//! them all at once.
+
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use crate::error::LauncherError;
+use crate::{channels_file, features, sanitize, LauncherConfig, ProjectChannel};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigIssue {
+    /// "error" or "warning"
+    pub severity: String,
+    /// Where in the file, e.g. "channels[2].scene_path"
+    pub field: Option<String>,
+    pub message: String,
+}
+
+impl ConfigIssue {
+    pub fn error(field: Option<&str>, message: impl Into<String>) -> Self {
+        Self::new("error", field, message)
+    }
+
+    pub fn warning(field: Option<&str>, message: impl Into<String>) -> Self {
+        Self::new("warning", field, message)
+    }
+
+    fn new(severity: &str, field: Option<&str>, message: impl Into<String>) -> Self {
+        ConfigIssue {
+            severity: severity.to_string(),
+            field: field.map(|f| f.to_string()),
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigCheck {
+    pub path: String,
+    /// "channels_file" or "launcher_config"
+    pub kind: String,
+    pub errors: usize,
+    pub warnings: usize,
+    pub issues: Vec<ConfigIssue>,
+}
+
+/// Missing folders only warn: CI may not check out every project a config
+/// points at
+pub fn check_paths_exist(field: &str, channel: &ProjectChannel, issues: &mut Vec<ConfigIssue>) {
+    if !Path::new(&channel.unity_project_path).is_dir() {
+        issues.push(ConfigIssue::warning(
+            Some(&format!("{}.unity_project_path", field)),
+            format!("Folder not found: {}", channel.unity_project_path),
+        ));
+    }
+    if let Some(scene) = &channel.scene_path {
+        if !Path::new(scene).is_file() {
+            issues.push(ConfigIssue::warning(
+                Some(&format!("{}.scene_path", field)),
+                format!("Scene not found: {}", scene),
+            ));
+        }
+    }
+}
+
+fn check_launcher_config(content: &str, issues: &mut Vec<ConfigIssue>) {
+    let config: LauncherConfig = match serde_json::from_str(content) {
+        Ok(config) => config,
+        Err(e) => {
+            return issues.push(ConfigIssue::error(
+                None,
+                format!("Failed to parse config: {}", e),
+            ))
+        }
+    };
+
+    let mut ids = HashSet::new();
+    let mut channel_errors = false;
+    for (i, channel) in config.channels.iter().enumerate() {
+        let field = format!("channels[{}]", i);
+        if let Err(e) = sanitize::channel(channel) {
+            issues.push(ConfigIssue::error(Some(&field), e.to_string()));
+            channel_errors = true;
+            continue;
+        }
+        if !ids.insert(channel.id.as_str()) {
+            issues.push(ConfigIssue::error(
+                Some(&field),
+                format!("Duplicate channel id: {}", channel.id),
+            ));
+            channel_errors = true;
+        }
+        check_paths_exist(&field, channel, issues);
+    }
+
+    // Channels are already reported one by one; this covers the other fields
+    if !channel_errors {
+        if let Err(e) = sanitize::config(&config) {
+            issues.push(ConfigIssue::error(None, e.to_string()));
+        }
+    }
+
+    if let Some(active) = &config.active_channel_id {
+        if !ids.contains(active.as_str()) {
+            issues.push(ConfigIssue::error(
+                Some("active_channel_id"),
+                format!("Active channel {} is not a channel", active),
+            ));
+        }
+    }
+    if !Path::new(&config.mcp_server_path).exists() {
+        issues.push(ConfigIssue::warning(
+            Some("mcp_server_path"),
+            format!("MCP server not found: {}", config.mcp_server_path),
+        ));
+    }
+    for name in config.features.keys() {
+        if features::Feature::parse(name).is_none() {
+            issues.push(ConfigIssue::warning(
+                Some(&format!("features.{}", name)),
+                format!("Unknown feature (ignored): {}", name),
+            ));
+        }
+    }
+}
+
+/// Validate a channels manifest or launcher config. Only an unreadable file
+/// is an `Err`; everything wrong with its contents is reported as issues.
+pub fn check_file(path: &Path) -> Result<ConfigCheck, LauncherError> {
+    let content =
+        fs::read_to_string(path).map_err(|e| LauncherError::io("Failed to read", path, e))?;
+
+    // Only the launcher's own config has an MCP server path
+    let is_launcher_config = serde_json::from_str::<serde_json::Value>(&content)
+        .ok()
+        .is_some_and(|v| v.get("mcp_server_path").is_some());
+
+    let mut issues = Vec::new();
+    let kind = if is_launcher_config {
+        check_launcher_config(&content, &mut issues);
+        "launcher_config"
+    } else {
+        channels_file::check(path, &mut issues);
+        "channels_file"
+    };
+
+    let errors = issues.iter().filter(|i| i.severity == "error").count();
+    Ok(ConfigCheck {
+        path: path.to_string_lossy().to_string(),
+        kind: kind.to_string(),
+        errors,
+        warnings: issues.len() - errors,
+        issues,
+    })
+}
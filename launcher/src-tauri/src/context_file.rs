@@ -0,0 +1,316 @@
+//! Project context for coding agents
+//!
+//! `generate_context_file` writes what an agent should know before touching a
+//! channel's project into `CLAUDE.md` (Claude Code) or `.cursorrules`
+//! (Cursor) at the project root: its scenes, the prefabs, materials and
+//! scripts it already has, the limits Banter puts on content and the tools
+//! the channel's MCP server offers, as the server itself reports them. The launcher's part sits between two marker lines;
+//! everything else in the file is the user's and is kept as it is, so the
+//! file can be regenerated whenever the project or the server changes.
+
+use serde::Serialize;
+use specta::Type;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::assets::ProjectAsset;
+use crate::error::LauncherError;
+use crate::mcp_client::McpClient;
+use crate::plan::FilePlan;
+use crate::state::{AppState, SharedState};
+use crate::{banter_sdk, validation, LauncherConfig, ProjectChannel};
+use launcher_core::config::McpTool;
+
+const BEGIN_MARKER: &str = "<!-- BEGIN BANTWORKS MCP CONTEXT (generated by the launcher) -->";
+const END_MARKER: &str = "<!-- END BANTWORKS MCP CONTEXT -->";
+
+/// Scenes listed before the rest are summarized as a count
+const MAX_SCENES: usize = 50;
+
+/// Assets of each kind listed before the rest are summarized as a count
+const MAX_ASSETS_PER_KIND: usize = 30;
+
+/// Headings of the building-block lists, per asset kind
+const ASSET_HEADINGS: &[(&str, &str)] = &[
+    ("prefab", "Prefabs"),
+    ("material", "Materials"),
+    ("script", "Scripts"),
+];
+
+/// File names per format
+const FORMATS: &[(&str, &str)] = &[("claude", "CLAUDE.md"), ("cursor", ".cursorrules")];
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ContextFile {
+    pub path: String,
+    pub format: String,
+    /// Tools the server listed; `None` if it couldn't be asked
+    pub tool_count: Option<u32>,
+    /// Why the server couldn't be asked
+    pub tools_error: Option<String>,
+    pub plan: FilePlan,
+}
+
+fn file_name(format: &str) -> Result<&'static str, LauncherError> {
+    FORMATS
+        .iter()
+        .find(|(name, _)| *name == format)
+        .map(|(_, file)| *file)
+        .ok_or_else(|| {
+            LauncherError::invalid(format!("Unknown context file format: {}", format))
+                .with_hint("Use claude or cursor")
+        })
+}
+
+fn relative(path: &str, project: &Path) -> String {
+    Path::new(path)
+        .strip_prefix(project)
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_else(|_| path.to_string())
+}
+
+fn megabytes(bytes: u64) -> u64 {
+    bytes / (1024 * 1024)
+}
+
+/// The launcher's section, markers included
+fn render(
+    config: &LauncherConfig,
+    channel: &ProjectChannel,
+    scenes: &[String],
+    assets: &[ProjectAsset],
+    unity_version: Option<&str>,
+    tools: &Result<Vec<McpTool>, LauncherError>,
+) -> String {
+    let project = Path::new(&channel.unity_project_path);
+    let sdk = banter_sdk::detect_sdk(project);
+    let mut out = vec![
+        BEGIN_MARKER.to_string(),
+        format!("# {} (Banter space, Unity)", channel.name),
+        String::new(),
+        "Generated by the BANTWORKS MCP launcher; regenerate it there rather than editing \
+         between the markers."
+            .to_string(),
+        String::new(),
+        "## Project".to_string(),
+        String::new(),
+        format!("- Unity project: `{}`", channel.unity_project_path),
+    ];
+    if let Some(version) = unity_version {
+        out.push(format!("- Unity version: {}", version));
+    }
+    if let Some(scene) = &channel.scene_path {
+        out.push(format!("- Working scene: `{}`", relative(scene, project)));
+    }
+    out.push(format!(
+        "- Banter SDK: {}",
+        sdk.version.as_deref().unwrap_or("not installed")
+    ));
+    let platforms = if channel.platforms.is_empty() {
+        "windows, android".to_string()
+    } else {
+        channel.platforms.join(", ")
+    };
+    out.push(format!("- Bundle platforms: {}", platforms));
+    if !channel.extra_allowed_roots.is_empty() {
+        out.push(format!(
+            "- Shared folders the MCP tools may use: {}",
+            channel
+                .extra_allowed_roots
+                .iter()
+                .map(|r| format!("`{}`", r))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+
+    out.extend([String::new(), "## Scenes".to_string(), String::new()]);
+    if scenes.is_empty() {
+        out.push("No scenes found under Assets.".to_string());
+    }
+    for scene in scenes.iter().take(MAX_SCENES) {
+        out.push(format!("- `{}`", relative(scene, project)));
+    }
+    if scenes.len() > MAX_SCENES {
+        out.push(format!("- ...and {} more", scenes.len() - MAX_SCENES));
+    }
+
+    out.extend([
+        String::new(),
+        "## Building blocks".to_string(),
+        String::new(),
+        "Reuse these before creating new ones.".to_string(),
+    ]);
+    if assets.is_empty() {
+        out.extend([
+            String::new(),
+            "No prefabs, materials or scripts found under Assets.".to_string(),
+        ]);
+    }
+    for (kind, heading) in ASSET_HEADINGS {
+        let of_kind: Vec<&ProjectAsset> = assets.iter().filter(|a| a.kind == *kind).collect();
+        if of_kind.is_empty() {
+            continue;
+        }
+        out.extend([
+            String::new(),
+            format!("{} ({}):", heading, of_kind.len()),
+            String::new(),
+        ]);
+        for asset in of_kind.iter().take(MAX_ASSETS_PER_KIND) {
+            out.push(format!("- `{}`", asset.path));
+        }
+        if of_kind.len() > MAX_ASSETS_PER_KIND {
+            out.push(format!(
+                "- ...and {} more",
+                of_kind.len() - MAX_ASSETS_PER_KIND
+            ));
+        }
+    }
+
+    out.extend([
+        String::new(),
+        "## Banter constraints".to_string(),
+        String::new(),
+    ]);
+    for (platform, bytes) in validation::MAX_BUNDLE_BYTES {
+        out.push(format!(
+            "- The {} bundle must stay under {} MB.",
+            platform,
+            megabytes(*bytes)
+        ));
+    }
+    out.push(format!(
+        "- Keep textures at {}px or less; Quest GPUs struggle with larger imports.",
+        validation::MAX_TEXTURE_SIZE
+    ));
+    let supplied: Vec<&str> = validation::UNSUPPORTED_CLASSES
+        .iter()
+        .map(|(_, name)| *name)
+        .collect();
+    out.push(format!(
+        "- Don't add a {}: Banter supplies the player rig.",
+        supplied.join(" or ")
+    ));
+    out.push("- Give walkable geometry colliders so players can teleport onto it.".to_string());
+    if config.enable_custom_scripts {
+        out.push("- Custom C# scripts are allowed in this project.".to_string());
+    } else {
+        out.push(
+            "- Don't add custom C# scripts; Banter spaces only run Banter SDK components."
+                .to_string(),
+        );
+    }
+
+    out.extend([String::new(), "## MCP tools".to_string(), String::new()]);
+    match tools {
+        Ok(tools) if tools.is_empty() => out.push("The server lists no tools.".to_string()),
+        Ok(tools) => {
+            for tool in tools {
+                match tool.description.as_deref().map(first_line) {
+                    Some(description) if !description.is_empty() => {
+                        out.push(format!("- `{}`: {}", tool.name, description))
+                    }
+                    _ => out.push(format!("- `{}`", tool.name)),
+                }
+            }
+        }
+        Err(e) => out.push(format!("The server couldn't be asked for its tools: {}", e)),
+    }
+    out.push(END_MARKER.to_string());
+    out.join("\n") + "\n"
+}
+
+fn first_line(text: &str) -> &str {
+    text.lines().next().unwrap_or("").trim()
+}
+
+/// `existing` with the launcher's section replaced, or appended if it has none
+fn merge(existing: &str, section: &str) -> String {
+    if let (Some(start), Some(end)) = (existing.find(BEGIN_MARKER), existing.find(END_MARKER)) {
+        if start < end {
+            let after = &existing[end + END_MARKER.len()..];
+            let after = after.strip_prefix('\n').unwrap_or(after);
+            return format!("{}{}{}", &existing[..start], section, after);
+        }
+    }
+    if existing.trim().is_empty() {
+        return section.to_string();
+    }
+    let separator = if existing.ends_with('\n') {
+        "\n"
+    } else {
+        "\n\n"
+    };
+    format!("{}{}{}", existing, separator, section)
+}
+
+pub fn context_file_path(channel: &ProjectChannel, format: &str) -> Result<PathBuf, LauncherError> {
+    Ok(Path::new(&channel.unity_project_path).join(file_name(format)?))
+}
+
+pub fn generate(
+    state: &AppState,
+    channel_id: &str,
+    format: &str,
+    dry_run: bool,
+) -> Result<ContextFile, LauncherError> {
+    let config = state.config();
+    let channel = crate::find_channel(&config, channel_id)?;
+    let path = context_file_path(&channel, format)?;
+    let project = Path::new(&channel.unity_project_path);
+    if !project.join("Assets").is_dir() {
+        return Err(LauncherError::not_found(format!(
+            "Unity project not found: {}",
+            channel.unity_project_path
+        ))
+        .with_path(project));
+    }
+
+    let scan = state.scans.project(project, false);
+    let inventory = state.assets.inventory(project, &Default::default(), false);
+    let tools = McpClient::connect(&config, &channel).and_then(|mut client| client.list_tools());
+    let section = render(
+        &config,
+        &channel,
+        &scan.scenes,
+        &inventory.assets,
+        scan.unity_version.as_deref(),
+        &tools,
+    );
+
+    let existing = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(LauncherError::io("Failed to read", &path, e)),
+    };
+    let mut plan = FilePlan::new(dry_run);
+    plan.write(&path, merge(&existing, &section))?;
+
+    Ok(ContextFile {
+        path: path.to_string_lossy().to_string(),
+        format: format.to_string(),
+        tool_count: tools.as_ref().ok().map(|t| t.len() as u32),
+        tools_error: tools.err().map(|e| e.to_string()),
+        plan,
+    })
+}
+
+/// Write or refresh the agent context file in a channel's project. `format`
+/// is `claude` (the default) or `cursor`.
+#[tauri::command]
+#[specta::specta]
+pub async fn generate_context_file(
+    state: tauri::State<'_, SharedState>,
+    channel_id: String,
+    format: Option<String>,
+    dry_run: Option<bool>,
+) -> Result<ContextFile, LauncherError> {
+    crate::sanitize::channel_id(&channel_id)?;
+    let state = state.inner().clone();
+    crate::commands::blocking("Context file", move || {
+        let format = format.unwrap_or_else(|| "claude".to_string());
+        generate(&state, &channel_id, &format, dry_run.unwrap_or(false))
+    })
+    .await
+}
@@ -0,0 +1,436 @@
+//! Optional localhost control API for external tools
+//!
+//! Stream Deck plugins, editor extensions and scripts can list channels,
+//! activate one, control the preview server and read health at
+//! `http://127.0.0.1:<port>/api/v1/`. Off by default; every request needs
+//! `Authorization: Bearer <token>`, and the token lives in the OS keychain.
+//!
+//! Endpoints (JSON in and out):
+//! - `GET  /channels`, `POST /channels/<id>/activate`
+//!   (`{take_over?, ignore_health?}`), `GET /channels/<id>/health`,
+//!   `POST /channels/<id>/focus` (raises the channel's Unity editor)
+//! - `POST /window/focus`, which raises the launcher window
+//! - `GET  /health`
+//! - `GET  /preview-server`, `POST /preview-server/start` (`{channel_id, port?}`),
+//!   `POST /preview-server/stop`
+//! - `GET  /metrics`, in the Prometheus text format instead (see `metrics`)
+//!
+//! In observer mode every `POST` but the two focus endpoints is refused with
+//! 403.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::io;
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::account::{keychain_error, KEYCHAIN_SERVICE};
+use crate::error::{ErrorKind, LauncherError};
+use crate::plan::FilePlan;
+use crate::state::{AppState, SharedState};
+use crate::{focus, health, http, metrics, observer, preview_server};
+
+const KEYCHAIN_ACCOUNT: &str = "control-api";
+
+const API_PREFIX: &str = "/api/v1/";
+
+/// Handle to the running API server, held in `AppState`
+pub struct RunningApi {
+    port: u16,
+    stop: Arc<AtomicBool>,
+    /// Joined on stop so a restart can bind the same port again
+    thread: thread::JoinHandle<()>,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ControlApiStatus {
+    pub enabled: bool,
+    pub running: bool,
+    pub port: Option<u16>,
+    pub url: Option<String>,
+    /// Bearer token to paste into the external tool
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ActivateBody {
+    /// Activate even if another launcher is using the project
+    #[serde(default)]
+    take_over: bool,
+    /// Activate even if the health gate refuses
+    #[serde(default)]
+    ignore_health: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct StartPreviewBody {
+    channel_id: String,
+    port: Option<u16>,
+}
+
+fn keychain_entry() -> Result<keyring::Entry, LauncherError> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT)
+        .map_err(|e| keychain_error("Failed to open keychain", e))
+}
+
+fn new_token() -> Result<String, LauncherError> {
+    let token = format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    );
+    keychain_entry()?
+        .set_password(&token)
+        .map_err(|e| keychain_error("Failed to store control API token in keychain", e))?;
+    Ok(token)
+}
+
+/// The API token, created on first use
+fn token() -> Result<String, LauncherError> {
+    match keychain_entry()?.get_password() {
+        Ok(token) => Ok(token),
+        Err(keyring::Error::NoEntry) => new_token(),
+        Err(e) => Err(keychain_error("Failed to read control API token", e)),
+    }
+}
+
+/// Compare without returning early, so response timing doesn't leak the token
+fn tokens_match(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn http_status(e: &LauncherError) -> u16 {
+    match e.kind {
+        ErrorKind::NotFound => 404,
+        ErrorKind::InvalidInput | ErrorKind::Parse => 400,
+        ErrorKind::PermissionDenied | ErrorKind::Auth => 403,
+        ErrorKind::Conflict => 409,
+        _ => 500,
+    }
+}
+
+fn to_json(value: impl Serialize) -> Result<serde_json::Value, LauncherError> {
+    serde_json::to_value(value)
+        .map_err(|e| LauncherError::internal(format!("Failed to serialize response: {}", e)))
+}
+
+fn parse_body<T: for<'de> Deserialize<'de>>(request: &http::Request) -> Result<T, LauncherError> {
+    serde_json::from_slice(&request.body)
+        .map_err(|e| LauncherError::invalid(format!("Invalid request body: {}", e)))
+}
+
+/// Method an endpoint accepts; `None` for unknown paths
+fn endpoint_method(route: &[&str]) -> Option<&'static str> {
+    match route {
+        ["channels"]
+        | ["channels", _, "health"]
+        | ["health"]
+        | ["metrics"]
+        | ["preview-server"] => Some("GET"),
+        ["channels", _, "activate" | "focus"]
+        | ["window", "focus"]
+        | ["preview-server", "start" | "stop"] => Some("POST"),
+        _ => None,
+    }
+}
+
+/// `POST` endpoints that change nothing, which observer mode allows
+fn is_read_only(route: &[&str]) -> bool {
+    matches!(route, ["channels", _, "focus"] | ["window", "focus"])
+}
+
+fn route(
+    app: &AppHandle,
+    state: &AppState,
+    request: &http::Request,
+    route: &[&str],
+) -> Result<serde_json::Value, LauncherError> {
+    match route {
+        ["channels"] => {
+            let config = state.config();
+            Ok(serde_json::json!({
+                "active_channel_id": config.active_channel_id,
+                "channels": config.channels,
+            }))
+        }
+        ["channels", id, "activate"] => {
+            let body: ActivateBody = if request.body.is_empty() {
+                ActivateBody::default()
+            } else {
+                parse_body(request)?
+            };
+            let activated = crate::activate_channel(state, id, body.take_over, body.ignore_health)?;
+            // The window holds its own copy of the config; have it reload
+            let _ = app.emit("config-changed", state.config());
+            Ok(serde_json::json!({
+                "channel_id": id,
+                "client_config_path": activated.client_config,
+                "health_warnings": activated.health_warnings,
+            }))
+        }
+        ["channels", id, "health"] => {
+            let config = state.config();
+            let channel = crate::find_channel(&config, id)?;
+            to_json(health::check_channel(&channel, &config, &state.guids))
+        }
+        ["channels", id, "focus"] => to_json(focus::focus_editor(&state.config(), id)?),
+        ["window", "focus"] => {
+            focus::focus_launcher(app)?;
+            Ok(serde_json::json!({}))
+        }
+        ["health"] => to_json(health::check_all(app, &state.config(), &state.guids, false)),
+        ["preview-server"] => to_json(preview_server::current_status(state)),
+        ["preview-server", "start"] => {
+            let body: StartPreviewBody = parse_body(request)?;
+            to_json(preview_server::start(state, &body.channel_id, body.port)?)
+        }
+        ["preview-server", "stop"] => {
+            preview_server::stop(state);
+            to_json(preview_server::current_status(state))
+        }
+        _ => Err(LauncherError::not_found(format!(
+            "No such endpoint: {}",
+            request.path
+        ))),
+    }
+}
+
+fn respond(stream: &mut TcpStream, status: u16, body: serde_json::Value) -> io::Result<()> {
+    let body = serde_json::to_vec(&body).unwrap_or_default();
+    http::write_response(stream, status, "application/json", &body)
+}
+
+fn unauthorized(stream: &mut TcpStream, status: u16, message: &str) -> io::Result<()> {
+    respond(
+        stream,
+        status,
+        serde_json::json!({ "error": LauncherError::new(ErrorKind::Auth, message) }),
+    )
+}
+
+fn handle(
+    mut stream: TcpStream,
+    app: &AppHandle,
+    state: &AppState,
+    token: &str,
+    port: u16,
+) -> io::Result<()> {
+    let request = http::read_request(&stream)?;
+    if request.method == "OPTIONS" {
+        return http::write_preflight(&mut stream);
+    }
+
+    // A page on another origin resolving to 127.0.0.1 would send its own Host
+    let host = request.header("host").unwrap_or_default();
+    if host != format!("127.0.0.1:{}", port) && host != format!("localhost:{}", port) {
+        return unauthorized(&mut stream, 403, "Unexpected Host header");
+    }
+
+    let given = request
+        .header("authorization")
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .unwrap_or_default();
+    if !tokens_match(given.trim(), token) {
+        return unauthorized(&mut stream, 401, "Missing or wrong API token");
+    }
+
+    let segments: Vec<&str> = request
+        .path
+        .strip_prefix(API_PREFIX)
+        .unwrap_or_default()
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    // Prometheus reads its own text format, not JSON
+    if segments == ["metrics"] && request.method == "GET" {
+        let body = metrics::render(state);
+        return http::write_response(&mut stream, 200, metrics::CONTENT_TYPE, body.as_bytes());
+    }
+
+    let result = match endpoint_method(&segments) {
+        None => Err((
+            404,
+            LauncherError::not_found(format!("No such endpoint: {}", request.path)),
+        )),
+        Some(method) if method != request.method => Err((
+            405,
+            LauncherError::invalid(format!("Use {} for {}", method, request.path)),
+        )),
+        Some("POST") if !is_read_only(&segments) => observer::check(&state.config(), &request.path)
+            .map_err(|e| (http_status(&e), e))
+            .and_then(|_| route(app, state, &request, &segments).map_err(|e| (http_status(&e), e))),
+        Some(_) => route(app, state, &request, &segments).map_err(|e| (http_status(&e), e)),
+    };
+    match result {
+        Ok(body) => respond(&mut stream, 200, body),
+        Err((status, e)) => respond(&mut stream, status, serde_json::json!({ "error": e })),
+    }
+}
+
+fn serve(
+    listener: TcpListener,
+    app: AppHandle,
+    state: SharedState,
+    token: String,
+    stop: Arc<AtomicBool>,
+) {
+    // Non-blocking accept so the loop notices the stop flag
+    if listener.set_nonblocking(true).is_err() {
+        return;
+    }
+    let port = listener.local_addr().map(|a| a.port()).unwrap_or_default();
+    let token = Arc::new(token);
+
+    while !stop.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                let app = app.clone();
+                let state = state.clone();
+                let token = token.clone();
+                thread::spawn(move || {
+                    let _ = stream.set_nonblocking(false);
+                    let _ = stream.set_read_timeout(Some(Duration::from_secs(10)));
+                    let _ = handle(stream, &app, &state, &token, port);
+                });
+            }
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                thread::sleep(Duration::from_millis(50));
+            }
+            Err(_) => thread::sleep(Duration::from_millis(200)),
+        }
+    }
+}
+
+/// Stop the API server if it is running
+pub fn stop(state: &AppState) {
+    let running = state
+        .control_api
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take();
+    if let Some(api) = running {
+        api.stop.store(true, Ordering::Relaxed);
+        let _ = api.thread.join();
+        metrics::server_stopped(metrics::CONTROL_API);
+    }
+}
+
+/// Start (or restart) the API server on the configured port
+pub fn start(app: &AppHandle, state: &SharedState) -> Result<ControlApiStatus, LauncherError> {
+    let port = state.config().control_api_port;
+    let token = token()?;
+    stop(state);
+
+    let listener = TcpListener::bind(("127.0.0.1", port)).map_err(|e| {
+        LauncherError::new(
+            ErrorKind::Conflict,
+            format!("Failed to listen on port {}: {}", port, e),
+        )
+        .with_hint("Another program may be using this port - pick a different control API port")
+    })?;
+    let stop_flag = Arc::new(AtomicBool::new(false));
+
+    let thread = {
+        let app = app.clone();
+        let state = state.clone();
+        let stop_flag = stop_flag.clone();
+        thread::spawn(move || serve(listener, app, state, token, stop_flag))
+    };
+
+    *state.control_api.lock().unwrap_or_else(|e| e.into_inner()) = Some(RunningApi {
+        port,
+        stop: stop_flag,
+        thread,
+    });
+    metrics::server_started(metrics::CONTROL_API);
+    Ok(current_status(state))
+}
+
+pub fn current_status(state: &AppState) -> ControlApiStatus {
+    let port = state
+        .control_api
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .map(|api| api.port);
+    ControlApiStatus {
+        enabled: state.config().control_api_enabled,
+        running: port.is_some(),
+        port,
+        url: port.map(|p| format!("http://127.0.0.1:{}{}", p, API_PREFIX)),
+        token: port.and_then(|_| token().ok()),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_control_api_status(
+    state: tauri::State<'_, SharedState>,
+) -> Result<ControlApiStatus, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Control API", move || Ok(current_status(&state))).await
+}
+
+/// Turn the control API on or off (optionally on a new port) and remember the choice
+#[tauri::command]
+#[specta::specta]
+pub async fn set_control_api_enabled(
+    app: AppHandle,
+    state: tauri::State<'_, SharedState>,
+    enabled: bool,
+    port: Option<u16>,
+) -> Result<ControlApiStatus, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Control API", move || {
+        let mut plan = FilePlan::new(false);
+        state.update(&mut plan, |config| {
+            config.control_api_enabled = enabled;
+            if let Some(port) = port {
+                config.control_api_port = port;
+            }
+            Ok(())
+        })?;
+
+        if enabled {
+            start(&app, &state)
+        } else {
+            stop(&state);
+            Ok(current_status(&state))
+        }
+    })
+    .await
+}
+
+/// Replace the API token; tools using the old one stop working
+#[tauri::command]
+#[specta::specta]
+pub async fn regenerate_control_api_token(
+    app: AppHandle,
+    state: tauri::State<'_, SharedState>,
+) -> Result<ControlApiStatus, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Control API", move || {
+        new_token()?;
+        let running = state
+            .control_api
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_some();
+        if running {
+            start(&app, &state)
+        } else {
+            Ok(current_status(&state))
+        }
+    })
+    .await
+}
@@ -0,0 +1,288 @@
+//! Everything the main screen shows, in one call
+//!
+//! `get_dashboard` gathers what the window used to ask for channel by
+//! channel: health and extension status, icons, whether Claude Code points at
+//! each project, bridge connections, extension updates and the jobs that
+//! failed lately. The per-channel checks start processes and read project
+//! files, so their results are cached for `CACHE_TTL`, or until the config
+//! changes; bridge heartbeats and jobs are cheap and always current.
+
+use serde::Serialize;
+use specta::Type;
+use std::cmp::Reverse;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+use crate::error::LauncherError;
+use crate::guids::GuidCache;
+use crate::health::{self, ChannelHealth};
+use crate::icons::{self, ChannelIcon};
+use crate::jobs::{JobInfo, JobStatus};
+use crate::state::{AppState, SharedState};
+use crate::{
+    banter_sdk, bridge, client_config, editor_monitor, logs, LauncherConfig, ProjectChannel,
+};
+use launcher_core::editor::EditorCondition;
+
+/// How long channel checks are reused
+const CACHE_TTL: Duration = Duration::from_secs(15);
+
+/// Failed jobs listed, newest first
+const MAX_RECENT_ERRORS: usize = 10;
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ChannelSummary {
+    pub id: String,
+    pub name: String,
+    pub active: bool,
+    pub unity_project_path: String,
+    pub scene_path: Option<String>,
+    pub extension_installed: bool,
+    /// Tools in the cached server capabilities
+    pub tool_count: Option<u32>,
+    pub icon: Option<ChannelIcon>,
+    pub health: ChannelHealth,
+}
+
+/// The channel's MCP server as far as the launcher can see it; Claude Code
+/// starts and stops the server itself
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ServerState {
+    pub channel_id: String,
+    /// Claude Code's `banter` entry points at this channel's project
+    pub configured: bool,
+    pub server_version: Option<String>,
+    /// Last write to the channel's server log, Unix ms
+    pub last_output_ms: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct BridgeState {
+    pub channel_id: String,
+    pub connected: bool,
+    /// Last editor heartbeat, Unix ms
+    pub heartbeat_ms: Option<i64>,
+    pub bridge_version: Option<String>,
+    pub editor: EditorCondition,
+    /// Why the bridge is down when the editor crashed or froze
+    pub editor_cause: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct PendingUpdate {
+    pub channel_id: String,
+    /// Only `unity-extension` so far
+    pub component: String,
+    pub installed: String,
+    pub available: String,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct RecentError {
+    pub job_id: String,
+    pub kind: String,
+    pub label: String,
+    pub message: String,
+    /// Unix ms
+    pub at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct Dashboard {
+    pub active_channel_id: Option<String>,
+    pub channels: Vec<ChannelSummary>,
+    pub servers: Vec<ServerState>,
+    pub bridges: Vec<BridgeState>,
+    pub pending_updates: Vec<PendingUpdate>,
+    pub recent_errors: Vec<RecentError>,
+    /// When the channel checks ran, Unix ms
+    pub checked_at: i64,
+    /// The channel checks came from the cache
+    pub cached: bool,
+}
+
+/// The expensive part of a dashboard
+#[derive(Clone)]
+struct ChannelChecks {
+    channels: Vec<ChannelSummary>,
+    servers: Vec<ServerState>,
+    pending_updates: Vec<PendingUpdate>,
+    checked_at: i64,
+}
+
+struct CacheEntry {
+    built: Instant,
+    /// Config the checks ran against, serialized
+    config: String,
+    checks: ChannelChecks,
+}
+
+/// Channel checks of the last dashboard, kept in `AppState`
+#[derive(Default)]
+pub struct DashboardCache {
+    entry: Mutex<Option<CacheEntry>>,
+}
+
+impl DashboardCache {
+    fn get(
+        &self,
+        config: &LauncherConfig,
+        guids: &GuidCache,
+        refresh: bool,
+    ) -> (ChannelChecks, bool) {
+        let key = serde_json::to_string(config).unwrap_or_default();
+        let mut entry = self.entry.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(cached) = entry.as_ref() {
+            if !refresh && cached.config == key && cached.built.elapsed() < CACHE_TTL {
+                return (cached.checks.clone(), true);
+            }
+        }
+        let checks = check_channels(config, guids);
+        *entry = Some(CacheEntry {
+            built: Instant::now(),
+            config: key,
+            checks: checks.clone(),
+        });
+        (checks, false)
+    }
+}
+
+fn modified_ms(path: &Path) -> Option<i64> {
+    let modified = fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_millis() as i64)
+}
+
+/// Bridge version shipped with the MCP server, i.e. what an install would put in
+pub fn bundled_bridge_version() -> Option<String> {
+    let source = Path::new(&crate::get_mcp_root().ok()?)
+        .join("unity-extension")
+        .join("Editor")
+        .join("BanterMCPBridge.cs");
+    banter_sdk::read_bridge_version_file(&source)
+}
+
+fn summary(config: &LauncherConfig, channel: &ProjectChannel, guids: &GuidCache) -> ChannelSummary {
+    ChannelSummary {
+        id: channel.id.clone(),
+        name: channel.name.clone(),
+        active: config.active_channel_id.as_deref() == Some(channel.id.as_str()),
+        unity_project_path: channel.unity_project_path.clone(),
+        scene_path: channel.scene_path.clone(),
+        extension_installed: crate::check_unity_extension(channel.unity_project_path.clone())
+            .unwrap_or(false),
+        tool_count: channel.capabilities.as_ref().map(|c| c.tools.len() as u32),
+        icon: icons::cached(&channel.id),
+        health: health::check_channel(channel, config, guids),
+    }
+}
+
+fn check_channels(config: &LauncherConfig, guids: &GuidCache) -> ChannelChecks {
+    let claude_project = crate::get_claude_mcp_config()
+        .ok()
+        .and_then(|c| client_config::configured_project(&c));
+    let server_version = banter_sdk::read_server_version(&config.mcp_server_path);
+    let bundled = bundled_bridge_version();
+
+    let mut checks = ChannelChecks {
+        channels: Vec::new(),
+        servers: Vec::new(),
+        pending_updates: Vec::new(),
+        checked_at: bridge::now_ms(),
+    };
+    for channel in &config.channels {
+        let project = Path::new(&channel.unity_project_path);
+        checks.channels.push(summary(config, channel, guids));
+        checks.servers.push(ServerState {
+            channel_id: channel.id.clone(),
+            configured: claude_project.as_deref() == Some(channel.unity_project_path.as_str()),
+            server_version: server_version.clone(),
+            last_output_ms: modified_ms(&logs::server_log_path(&channel.id)),
+        });
+
+        // Projects without the extension have nothing to update
+        if let (Some(installed), Some(available)) =
+            (banter_sdk::read_bridge_version(project), &bundled)
+        {
+            if banter_sdk::parse_version(&installed) < banter_sdk::parse_version(available) {
+                checks.pending_updates.push(PendingUpdate {
+                    channel_id: channel.id.clone(),
+                    component: "unity-extension".to_string(),
+                    installed,
+                    available: available.clone(),
+                });
+            }
+        }
+    }
+    checks
+}
+
+fn bridge_states(config: &LauncherConfig) -> Vec<BridgeState> {
+    config
+        .channels
+        .iter()
+        .map(|channel| {
+            let project = Path::new(&channel.unity_project_path);
+            let editor = editor_monitor::status(channel);
+            BridgeState {
+                channel_id: channel.id.clone(),
+                connected: editor.condition == EditorCondition::Connected,
+                heartbeat_ms: bridge::editor_heartbeat_ms(project),
+                bridge_version: banter_sdk::read_bridge_version(project),
+                editor: editor.condition,
+                editor_cause: editor.cause,
+            }
+        })
+        .collect()
+}
+
+fn recent_errors(jobs: Vec<JobInfo>) -> Vec<RecentError> {
+    let mut errors: Vec<RecentError> = jobs
+        .into_iter()
+        .filter(|job| job.status == JobStatus::Failed)
+        .map(|job| RecentError {
+            at: job.finished_at.unwrap_or(job.created_at),
+            message: job
+                .error
+                .map(|e| e.message)
+                .unwrap_or_else(|| "Failed".to_string()),
+            job_id: job.id,
+            kind: job.kind,
+            label: job.label,
+        })
+        .collect();
+    errors.sort_by_key(|e| Reverse(e.at));
+    errors.truncate(MAX_RECENT_ERRORS);
+    errors
+}
+
+pub fn build(state: &AppState, refresh: bool) -> Dashboard {
+    let config = state.config();
+    let (checks, cached) = state.dashboard.get(&config, &state.guids, refresh);
+    Dashboard {
+        active_channel_id: config.active_channel_id.clone(),
+        channels: checks.channels,
+        servers: checks.servers,
+        bridges: bridge_states(&config),
+        pending_updates: checks.pending_updates,
+        recent_errors: recent_errors(state.jobs.list()),
+        checked_at: checks.checked_at,
+        cached,
+    }
+}
+
+/// Channel summaries, server and bridge states, pending updates and recent
+/// errors for the main screen. `refresh` reruns the cached channel checks.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_dashboard(
+    state: tauri::State<'_, SharedState>,
+    refresh: Option<bool>,
+) -> Result<Dashboard, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Dashboard", move || {
+        Ok(build(&state, refresh.unwrap_or(false)))
+    })
+    .await
+}
@@ -0,0 +1,199 @@
+//! Unity editor crashes and hangs
+//!
+//! A closed bridge can be a closed editor, a crashed one or a frozen one, and
+//! only the first is nothing to worry about. `status` tells them apart (the
+//! core `editor` module) from the project's instance file, the editor
+//! process and Unity's Editor.log, so the dashboard and the health checks
+//! can say why the bridge is down instead of just that it is. The window watches the active channel every `TICK`, emits
+//! `editor-status` when its editor crashes or stops responding, and with
+//! `relaunch_editor_on_crash` on opens the project in Unity again. A frozen
+//! editor is never killed; only the user can tell it from a long import.
+
+use serde::Serialize;
+use specta::Type;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::error::{ErrorKind, LauncherError};
+use crate::state::SharedState;
+use crate::{bridge, logs, stack, unity, LauncherConfig, ProjectChannel};
+use launcher_core::editor::{self, EditorCondition, EditorProbe};
+
+/// How often the window checks the active channel's editor
+const TICK: Duration = Duration::from_secs(5);
+
+/// Relaunches of one project this close together stop, so an editor that
+/// crashes while opening isn't started over and over
+const RELAUNCH_COOLDOWN_MS: i64 = 5 * 60 * 1000;
+
+/// Bytes of Editor.log read to tell whose log it is and how it ended
+const LOG_HEAD_BYTES: u64 = 16 * 1024;
+const LOG_TAIL_BYTES: u64 = 64 * 1024;
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct EditorStatus {
+    pub channel_id: String,
+    pub condition: EditorCondition,
+    /// Why the editor is down, for `crashed` and `not_responding`
+    pub cause: Option<String>,
+    /// Process of the editor that has (or had) the project open
+    pub pid: Option<u32>,
+}
+
+pub fn process_alive(pid: u32) -> bool {
+    let pid = pid.to_string();
+    let output = if cfg!(windows) {
+        Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {}", pid), "/NH"])
+            .output()
+    } else {
+        Command::new("kill").args(["-0", &pid]).output()
+    };
+    match output {
+        Ok(output) if cfg!(windows) => String::from_utf8_lossy(&output.stdout)
+            .split_whitespace()
+            .any(|word| word == pid),
+        Ok(output) => output.status.success(),
+        // Without a way to ask, don't cry crash
+        Err(_) => true,
+    }
+}
+
+/// The crash line of Editor.log, if the log is from an editor on `project`
+fn logged_crash(project: &Path) -> Option<String> {
+    let path = logs::editor_log_path()?;
+    let head = logs::read_chunk(&path, 0, LOG_HEAD_BYTES).ok()?;
+    if !editor::log_is_for(&head.text, project) {
+        return None;
+    }
+    let tail = logs::read_tail(&path, usize::MAX, LOG_TAIL_BYTES).ok()?;
+    editor::crash_line(&tail.text)
+}
+
+pub fn status(channel: &ProjectChannel) -> EditorStatus {
+    let project = Path::new(&channel.unity_project_path);
+    let connected = bridge::is_editor_connected(project);
+    let pid = editor::instance_pid(project);
+    let probe = EditorProbe {
+        connected,
+        heartbeat_age_ms: bridge::editor_heartbeat_ms(project).map(|ts| bridge::now_ms() - ts),
+        pid,
+        alive: !connected && pid.is_some_and(process_alive),
+        crash_line: if connected {
+            None
+        } else {
+            logged_crash(project)
+        },
+    };
+    let (condition, cause) = editor::classify(&probe);
+    EditorStatus {
+        channel_id: channel.id.clone(),
+        condition,
+        cause,
+        pid,
+    }
+}
+
+/// Open the channel's project in the Unity editor, unless one already has it
+pub fn relaunch(config: &LauncherConfig, channel: &ProjectChannel) -> Result<u32, LauncherError> {
+    let project = Path::new(&channel.unity_project_path);
+    if let Some(pid) = editor::instance_pid(project).filter(|pid| process_alive(*pid)) {
+        return Err(LauncherError::new(
+            ErrorKind::Unity,
+            format!("Unity (process {}) already has the project open", pid),
+        )
+        .with_path(project)
+        .with_hint("Close it first, or end the process if it is frozen"));
+    }
+    stack::check_editor("Relaunching the editor")?;
+    let editor = unity::find_editor(config, project)?;
+    let child = Command::new(&editor)
+        .arg("-projectPath")
+        .arg(project)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| LauncherError::io("Failed to start Unity", &editor, e))?;
+    Ok(child.id())
+}
+
+/// Start watching the active channel's editor from the window
+pub fn start(app: &AppHandle, state: &SharedState) {
+    let app = app.clone();
+    let state = state.clone();
+    thread::spawn(move || {
+        let mut last: Option<(String, EditorCondition)> = None;
+        let mut relaunched: Option<(String, i64)> = None;
+        loop {
+            thread::sleep(TICK);
+            let config = state.config();
+            let Some(channel) = config
+                .active_channel_id
+                .as_deref()
+                .and_then(|id| crate::find_channel(&config, id).ok())
+            else {
+                last = None;
+                continue;
+            };
+            let status = status(&channel);
+            let previous = last.replace((channel.id.clone(), status.condition));
+            let changed = previous != Some((channel.id.clone(), status.condition));
+            // Only a change seen while watching counts; an editor that was
+            // already down when the launcher started isn't news
+            if !changed || previous.is_none() || !status.condition.is_failure() {
+                continue;
+            }
+            let _ = app.emit("editor-status", status.clone());
+
+            let recent = relaunched.as_ref().is_some_and(|(id, at)| {
+                *id == channel.id && bridge::now_ms() - at < RELAUNCH_COOLDOWN_MS
+            });
+            if status.condition == EditorCondition::Crashed
+                && config.relaunch_editor_on_crash
+                && !recent
+            {
+                match relaunch(&config, &channel) {
+                    Ok(_) => relaunched = Some((channel.id.clone(), bridge::now_ms())),
+                    Err(e) => eprintln!("Failed to relaunch Unity for {}: {}", channel.id, e),
+                }
+            }
+        }
+    });
+}
+
+/// Whether the channel's Unity editor is connected, starting, frozen,
+/// crashed or closed
+#[tauri::command]
+#[specta::specta]
+pub async fn get_editor_status(
+    state: tauri::State<'_, SharedState>,
+    channel_id: String,
+) -> Result<EditorStatus, LauncherError> {
+    crate::sanitize::channel_id(&channel_id)?;
+    let state = state.inner().clone();
+    crate::commands::blocking("Editor status", move || {
+        Ok(status(&state.channel(&channel_id)?))
+    })
+    .await
+}
+
+/// Open the channel's project in Unity, e.g. after a crash. Returns the
+/// editor's process id.
+#[tauri::command]
+#[specta::specta]
+pub async fn relaunch_editor(
+    state: tauri::State<'_, SharedState>,
+    channel_id: String,
+) -> Result<u32, LauncherError> {
+    crate::sanitize::channel_id(&channel_id)?;
+    let state = state.inner().clone();
+    crate::commands::blocking("Relaunch editor", move || {
+        let config = state.config();
+        relaunch(&config, &crate::find_channel(&config, &channel_id)?)
+    })
+    .await
+}
@@ -0,0 +1,16 @@
+//! Elevation check command; see `launcher_core::elevation`
+
+pub use launcher_core::elevation::*;
+
+use crate::error::LauncherError;
+
+/// Report whether a target path needs elevation to write
+#[tauri::command]
+#[specta::specta]
+pub async fn check_elevation(path: String) -> Result<ElevationStatus, LauncherError> {
+    crate::commands::blocking("Elevation check", move || {
+        let path = crate::sanitize::path("Path", &path)?;
+        Ok(status(&path))
+    })
+    .await
+}
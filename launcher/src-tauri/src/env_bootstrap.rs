@@ -0,0 +1,158 @@
+//! The variables a portable client config refers to (see the core
+//! `portable` module)
+//!
+//! Claude Code falls back to the path an entry was written with when a
+//! variable is unset, so it only needs them on a machine with a different
+//! install. Cursor has no fallback and needs them everywhere.
+//! `apply_env_bootstrap` writes `env.sh` to the launcher folder with an
+//! `export` per variable, to source from a shell profile, and on Windows
+//! also stores them in the user environment with `setx`. Either way, clients
+//! started before don't see them until they are restarted.
+
+use serde::Serialize;
+use specta::Type;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::error::LauncherError;
+use crate::plan::FilePlan;
+use crate::state::SharedState;
+use launcher_core::portable::{self, Roots};
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct EnvVar {
+    pub name: String,
+    /// The folder it stands for on this machine
+    pub value: String,
+    /// What the launcher's own environment has, if anything
+    pub current: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct EnvBootstrap {
+    pub vars: Vec<EnvVar>,
+    /// The `env.sh` script, written or not
+    pub script: String,
+    /// Stored in the user environment by this call (Windows)
+    pub persisted: bool,
+    pub hint: String,
+}
+
+/// Each variable and the folder it stands for here
+pub fn vars(mcp_server_path: &str) -> Result<Vec<(&'static str, PathBuf)>, LauncherError> {
+    let launcher = std::env::current_exe()
+        .map_err(|e| LauncherError::internal(format!("Cannot locate the launcher: {}", e)))?;
+    let launcher_home = launcher.parent().unwrap_or(&launcher).to_path_buf();
+    Ok(vec![
+        (
+            portable::MCP_HOME_VAR,
+            portable::mcp_home(Path::new(mcp_server_path)),
+        ),
+        (portable::LAUNCHER_DIR_VAR, crate::launcher_dir()),
+        (portable::LAUNCHER_HOME_VAR, launcher_home),
+    ])
+}
+
+/// What a portable entry's paths are rewritten against, `workspace` being
+/// the project when the config lives in it
+pub fn roots(mcp_server_path: &str, workspace: Option<&Path>) -> Result<Roots, LauncherError> {
+    Ok(Roots {
+        vars: vars(mcp_server_path)?,
+        workspace: workspace.map(Path::to_path_buf),
+    })
+}
+
+pub fn script_path() -> PathBuf {
+    crate::launcher_dir().join("env.sh")
+}
+
+fn script(vars: &[(&'static str, PathBuf)]) -> String {
+    let mut content = String::from("# Written by the BANTWORKS MCP launcher\n");
+    for (name, value) in vars {
+        let value = value.to_string_lossy().replace('\'', "'\\''");
+        content.push_str(&format!("export {}='{}'\n", name, value));
+    }
+    content
+}
+
+fn hint(script: &Path) -> String {
+    if cfg!(windows) {
+        "Restart Claude Code and Cursor so they see the new variables".to_string()
+    } else {
+        format!(
+            "Add `. '{}'` to your shell profile, then restart Claude Code and Cursor",
+            script.to_string_lossy()
+        )
+    }
+}
+
+fn persist(name: &str, value: &Path) -> Result<(), LauncherError> {
+    let output = Command::new("setx")
+        .arg(name)
+        .arg(value)
+        .output()
+        .map_err(|e| LauncherError::io("Failed to run setx", Path::new("setx"), e))?;
+    if !output.status.success() {
+        return Err(LauncherError::internal(format!(
+            "setx {} failed: {}",
+            name,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+pub fn status(mcp_server_path: &str, persisted: bool) -> Result<EnvBootstrap, LauncherError> {
+    let script = script_path();
+    Ok(EnvBootstrap {
+        vars: vars(mcp_server_path)?
+            .into_iter()
+            .map(|(name, value)| EnvVar {
+                name: name.to_string(),
+                value: value.to_string_lossy().to_string(),
+                current: std::env::var(name).ok(),
+            })
+            .collect(),
+        hint: hint(&script),
+        script: script.to_string_lossy().to_string(),
+        persisted,
+    })
+}
+
+/// Write `env.sh` and, on Windows, set the variables for the user
+pub fn apply(mcp_server_path: &str) -> Result<EnvBootstrap, LauncherError> {
+    let vars = vars(mcp_server_path)?;
+    FilePlan::new(false).write(&script_path(), script(&vars))?;
+    if cfg!(windows) {
+        for (name, value) in &vars {
+            persist(name, value)?;
+        }
+    }
+    status(mcp_server_path, cfg!(windows))
+}
+
+/// The variables portable client configs refer to and what they are set to
+#[tauri::command]
+#[specta::specta]
+pub async fn get_env_bootstrap(
+    state: tauri::State<'_, SharedState>,
+) -> Result<EnvBootstrap, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Env bootstrap", move || {
+        status(&state.config().mcp_server_path, false)
+    })
+    .await
+}
+
+/// Set the variables up on this machine
+#[tauri::command]
+#[specta::specta]
+pub async fn apply_env_bootstrap(
+    state: tauri::State<'_, SharedState>,
+) -> Result<EnvBootstrap, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Apply env bootstrap", move || {
+        apply(&state.config().mcp_server_path)
+    })
+    .await
+}
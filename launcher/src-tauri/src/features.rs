@@ -0,0 +1,31 @@
+//! Feature flag commands; the flags are `launcher_core::features`
+
+pub use launcher_core::features::*;
+
+use crate::error::LauncherError;
+use crate::plan::FilePlan;
+use crate::state::SharedState;
+
+/// Every experimental feature and whether it is on
+#[tauri::command]
+#[specta::specta]
+pub fn get_feature_flags(state: tauri::State<'_, SharedState>) -> Vec<FeatureFlag> {
+    flags(&state.config())
+}
+
+/// Turn an experimental feature on or off and remember the choice
+#[tauri::command]
+#[specta::specta]
+pub async fn set_feature_flag(
+    state: tauri::State<'_, SharedState>,
+    name: String,
+    enabled: bool,
+) -> Result<Vec<FeatureFlag>, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Feature flag", move || {
+        let mut plan = FilePlan::new(false);
+        state.update(&mut plan, |config| set(config, &name, enabled))?;
+        Ok(flags(&state.config()))
+    })
+    .await
+}
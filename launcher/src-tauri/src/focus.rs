@@ -0,0 +1,222 @@
+//! Bring the Unity editor or the launcher window to the front
+//!
+//! "Show me the editor for this channel": `focus_unity_window` finds the
+//! editor that has the channel's project open through the
+//! `Library/EditorInstance.json` Unity writes while it runs, and raises that
+//! process's main window with whatever the platform offers (`user32` on
+//! Windows, System Events on macOS, `xdotool` or `wmctrl` on Linux).
+//! `focus_launcher_window` does the same for the launcher itself. Both are
+//! also on the control API, so a Stream Deck button or a link handler can ask
+//! for them. Neither changes anything, so observer mode allows them.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+use crate::error::LauncherError;
+use crate::state::SharedState;
+
+/// Label of the launcher's only window
+const MAIN_WINDOW: &str = "main";
+
+/// What Unity writes to `Library/EditorInstance.json`
+#[derive(Debug, Deserialize)]
+struct EditorInstance {
+    process_id: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct FocusedEditor {
+    pub channel_id: String,
+    /// Process id of the editor whose window was raised
+    pub pid: u32,
+}
+
+fn editor_instance_path(project: &Path) -> PathBuf {
+    project.join("Library").join("EditorInstance.json")
+}
+
+/// Process id of the editor that has `project` open. Unity removes the file
+/// when it quits, but not when it crashes.
+pub fn editor_pid(project: &Path) -> Option<u32> {
+    let content = fs::read_to_string(editor_instance_path(project)).ok()?;
+    let instance: EditorInstance = serde_json::from_str(&content).ok()?;
+    Some(instance.process_id)
+}
+
+fn no_window(pid: u32) -> LauncherError {
+    LauncherError::not_found(format!("Process {} has no window to focus", pid))
+        .with_hint("Unity may still be starting, or it quit without cleaning up")
+}
+
+#[cfg(windows)]
+fn activate_process(pid: u32) -> Result<(), LauncherError> {
+    use std::ffi::c_void;
+
+    type Hwnd = *mut c_void;
+
+    #[link(name = "user32")]
+    extern "system" {
+        fn EnumWindows(callback: extern "system" fn(Hwnd, isize) -> i32, param: isize) -> i32;
+        fn GetWindowThreadProcessId(window: Hwnd, process_id: *mut u32) -> u32;
+        fn IsWindowVisible(window: Hwnd) -> i32;
+        fn GetWindow(window: Hwnd, command: u32) -> Hwnd;
+        fn IsIconic(window: Hwnd) -> i32;
+        fn ShowWindow(window: Hwnd, command: i32) -> i32;
+        fn SetForegroundWindow(window: Hwnd) -> i32;
+    }
+    const GW_OWNER: u32 = 4;
+    const SW_RESTORE: i32 = 9;
+
+    struct Search {
+        pid: u32,
+        found: Hwnd,
+    }
+
+    // The main window is the visible one no other window owns
+    extern "system" fn visit(window: Hwnd, param: isize) -> i32 {
+        let search = unsafe { &mut *(param as *mut Search) };
+        let mut owner = 0;
+        unsafe { GetWindowThreadProcessId(window, &mut owner) };
+        let is_main = owner == search.pid
+            && unsafe { IsWindowVisible(window) } != 0
+            && unsafe { GetWindow(window, GW_OWNER) }.is_null();
+        if is_main {
+            search.found = window;
+            return 0;
+        }
+        1
+    }
+
+    let mut search = Search {
+        pid,
+        found: std::ptr::null_mut(),
+    };
+    unsafe { EnumWindows(visit, &mut search as *mut Search as isize) };
+    if search.found.is_null() {
+        return Err(no_window(pid));
+    }
+    unsafe {
+        if IsIconic(search.found) != 0 {
+            ShowWindow(search.found, SW_RESTORE);
+        }
+        if SetForegroundWindow(search.found) == 0 {
+            return Err(LauncherError::internal(format!(
+                "Windows refused to bring process {} to the front",
+                pid
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn activate_process(pid: u32) -> Result<(), LauncherError> {
+    use std::process::Command;
+
+    let script = format!(
+        "tell application \"System Events\" to set frontmost of (first process whose unix id is {}) to true",
+        pid
+    );
+    let output = Command::new("osascript")
+        .args(["-e", &script])
+        .output()
+        .map_err(|e| LauncherError::internal(format!("Failed to run osascript: {}", e)))?;
+    if !output.status.success() {
+        return Err(no_window(pid).with_hint(
+            "Allow the launcher to control System Events in System Settings > Privacy & Security > Automation",
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn activate_process(pid: u32) -> Result<(), LauncherError> {
+    use std::process::Command;
+
+    let id = pid.to_string();
+    let xdotool = Command::new("xdotool")
+        .args(["search", "--onlyvisible", "--pid", &id, "windowactivate"])
+        .output();
+    match xdotool {
+        Ok(output) if output.status.success() => return Ok(()),
+        Ok(_) => return Err(no_window(pid)),
+        Err(_) => {}
+    }
+
+    // Without xdotool, find the window in wmctrl's list by its process id
+    let list = Command::new("wmctrl").arg("-lp").output().map_err(|_| {
+        LauncherError::not_found("No window tool found to focus the editor with")
+            .with_hint("Install xdotool or wmctrl")
+    })?;
+    let window = String::from_utf8_lossy(&list.stdout)
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>())
+        .find(|fields| fields.get(2) == Some(&id.as_str()))
+        .and_then(|fields| fields.first().map(|w| w.to_string()))
+        .ok_or_else(|| no_window(pid))?;
+    let status = Command::new("wmctrl")
+        .args(["-i", "-a", &window])
+        .status()
+        .map_err(|e| LauncherError::internal(format!("Failed to run wmctrl: {}", e)))?;
+    if !status.success() {
+        return Err(no_window(pid));
+    }
+    Ok(())
+}
+
+/// Raise the editor that has the channel's project open
+pub fn focus_editor(
+    config: &crate::LauncherConfig,
+    channel_id: &str,
+) -> Result<FocusedEditor, LauncherError> {
+    let channel = crate::find_channel(config, channel_id)?;
+    let project = Path::new(&channel.unity_project_path);
+    let pid = editor_pid(project).ok_or_else(|| {
+        LauncherError::not_found(format!("Unity is not running for {}", channel.name))
+            .with_path(editor_instance_path(project))
+            .with_hint("Open the channel's project in Unity first")
+    })?;
+    activate_process(pid)?;
+    Ok(FocusedEditor {
+        channel_id: channel.id,
+        pid,
+    })
+}
+
+/// Show, restore and focus the launcher window
+pub fn focus_launcher(app: &AppHandle) -> Result<(), LauncherError> {
+    let window = app
+        .get_webview_window(MAIN_WINDOW)
+        .ok_or_else(|| LauncherError::internal("The launcher window is not open"))?;
+    let focus = || -> tauri::Result<()> {
+        window.show()?;
+        window.unminimize()?;
+        window.set_focus()
+    };
+    focus()
+        .map_err(|e| LauncherError::internal(format!("Failed to focus the launcher window: {}", e)))
+}
+
+/// Bring the Unity editor of a channel's project to the front
+#[tauri::command]
+#[specta::specta]
+pub async fn focus_unity_window(
+    state: tauri::State<'_, SharedState>,
+    channel_id: String,
+) -> Result<FocusedEditor, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Focus editor", move || {
+        focus_editor(&state.config(), &channel_id)
+    })
+    .await
+}
+
+/// Bring the launcher window to the front
+#[tauri::command]
+#[specta::specta]
+pub async fn focus_launcher_window(app: AppHandle) -> Result<(), LauncherError> {
+    focus_launcher(&app)
+}
@@ -0,0 +1,70 @@
+//! Webview filesystem scope, kept in step with the registered channels
+//!
+//! Only the launcher's own data dir, the MCP server checkout and the Unity
+//! projects of registered channels are reachable through `tauri_plugin_fs`,
+//! and nothing in observer mode.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::AppHandle;
+use tauri_plugin_fs::FsExt;
+
+/// Directories currently allowed by `sync`
+static ALLOWED: Mutex<Option<HashSet<PathBuf>>> = Mutex::new(None);
+
+/// Directories that should be reachable for this config
+fn wanted_dirs(config: &crate::LauncherConfig) -> HashSet<PathBuf> {
+    let mut dirs = HashSet::new();
+    if crate::observer::is_active(config) {
+        return dirs;
+    }
+
+    dirs.insert(crate::launcher_dir());
+
+    // dist/index.js -> server checkout root
+    let server = PathBuf::from(&config.mcp_server_path);
+    if let Some(root) = server.parent().and_then(|dist| dist.parent()) {
+        dirs.insert(root.to_path_buf());
+    }
+
+    for channel in &config.channels {
+        dirs.insert(PathBuf::from(&channel.unity_project_path));
+    }
+
+    dirs
+}
+
+/// Allow newly registered directories and forbid ones no longer in the config.
+/// Tauri scopes can't revoke an allow, so removed projects are forbidden for the
+/// rest of the session.
+pub fn sync(app: &AppHandle, config: &crate::LauncherConfig) {
+    let scope = app.fs_scope();
+    let wanted = wanted_dirs(config);
+
+    let mut allowed = ALLOWED.lock().unwrap_or_else(|e| e.into_inner());
+    let previous = allowed.take().unwrap_or_default();
+
+    for dir in wanted.difference(&previous) {
+        if let Err(e) = scope.allow_directory(dir, true) {
+            eprintln!("Failed to allow {} in fs scope: {}", dir.display(), e);
+        }
+    }
+    for dir in previous.difference(&wanted) {
+        if let Err(e) = scope.forbid_directory(dir, true) {
+            eprintln!("Failed to forbid {} in fs scope: {}", dir.display(), e);
+        }
+    }
+
+    // Channel secrets live under the launcher dir but are never for the webview
+    let secrets_dir = crate::launcher_dir().join("secrets");
+    if let Err(e) = scope.forbid_directory(&secrets_dir, true) {
+        eprintln!(
+            "Failed to forbid {} in fs scope: {}",
+            secrets_dir.display(),
+            e
+        );
+    }
+
+    *allowed = Some(wanted);
+}
@@ -0,0 +1,185 @@
+//! Git awareness for channel projects
+//!
+//! A channel's project is often a git repo, or a folder inside one. Channel
+//! health reports its branch and whether it has uncommitted changes, and the
+//! optional `git_guards` act on activation: `refuse_dirty_activation` stops a
+//! channel with uncommitted changes from being activated, and
+//! `checkpoint_on_activate` commits them instead, so the agent session starts
+//! from a commit it can be diffed against. Both only look at the project's
+//! folder, not the rest of the repo. Projects outside git, or machines
+//! without git, skip all of this.
+
+use serde::Serialize;
+use specta::Type;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use crate::error::{ErrorKind, LauncherError};
+use crate::state::SharedState;
+use crate::{LauncherConfig, ProjectChannel};
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct GitStatus {
+    /// `None` on a detached HEAD
+    pub branch: Option<String>,
+    /// Short commit hash; `None` before the first commit
+    pub head: Option<String>,
+    pub dirty: bool,
+    /// Changed or untracked paths under the project
+    pub changed_files: u32,
+}
+
+impl GitStatus {
+    /// e.g. "On branch main, 3 uncommitted changes"
+    pub fn summary(&self) -> String {
+        let position = match (&self.branch, &self.head) {
+            (Some(branch), _) => format!("On branch {}", branch),
+            (None, Some(head)) => format!("Detached at {}", head),
+            (None, None) => "No commits yet".to_string(),
+        };
+        match self.changed_files {
+            0 => format!("{}, clean", position),
+            1 => format!("{}, 1 uncommitted change", position),
+            n => format!("{}, {} uncommitted changes", position, n),
+        }
+    }
+}
+
+/// Run git in `project`
+pub fn run(project: &Path, args: &[&str]) -> Result<Output, LauncherError> {
+    Command::new("git")
+        .arg("-C")
+        .arg(project)
+        .args(args)
+        .output()
+        .map_err(|e| LauncherError::io("Failed to run git", project, e).with_hint("Install git"))
+}
+
+fn stdout(output: &Output) -> Option<String> {
+    let text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !text.is_empty()).then_some(text)
+}
+
+fn failed(action: &str, project: &Path, output: &Output) -> LauncherError {
+    LauncherError::new(
+        ErrorKind::Io,
+        format!(
+            "git {} failed: {}",
+            action,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+    )
+    .with_path(project)
+}
+
+/// Whether `project` is inside a git working tree
+pub fn is_repo(project: &Path) -> bool {
+    project.is_dir()
+        && run(project, &["rev-parse", "--is-inside-work-tree"]).is_ok_and(|o| o.status.success())
+}
+
+/// Branch and working tree state, `None` when the project isn't in a repo
+/// or git can't be run
+pub fn status(project: &Path) -> Option<GitStatus> {
+    if !is_repo(project) {
+        return None;
+    }
+    let changes = run(project, &["status", "--porcelain", "--", "."]).ok()?;
+    if !changes.status.success() {
+        return None;
+    }
+    let changed_files = String::from_utf8_lossy(&changes.stdout)
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .count() as u32;
+    Some(GitStatus {
+        branch: run(project, &["branch", "--show-current"])
+            .ok()
+            .and_then(|o| stdout(&o)),
+        head: run(project, &["rev-parse", "--short", "HEAD"])
+            .ok()
+            .and_then(|o| stdout(&o)),
+        dirty: changed_files > 0,
+        changed_files,
+    })
+}
+
+/// Refuse to activate a channel with uncommitted changes when
+/// `refuse_dirty_activation` is on. A checkpoint on activation would commit
+/// them anyway, so `checkpoint_on_activate` lets it through.
+pub fn check_activation(
+    config: &LauncherConfig,
+    channel: &ProjectChannel,
+) -> Result<(), LauncherError> {
+    let guards = &config.git_guards;
+    if !guards.refuse_dirty_activation || guards.checkpoint_on_activate {
+        return Ok(());
+    }
+    let project = Path::new(&channel.unity_project_path);
+    match status(project) {
+        Some(status) if status.dirty => Err(LauncherError::new(
+            ErrorKind::Conflict,
+            format!(
+                "{} has uncommitted changes ({})",
+                channel.name,
+                status.summary()
+            ),
+        )
+        .with_path(project)
+        .with_hint("Commit or stash them first, or turn refuse_dirty_activation off")),
+        _ => Ok(()),
+    }
+}
+
+/// Commit everything under the project, `None` when there was nothing to
+/// commit. Returns the new commit's short hash.
+pub fn checkpoint(channel: &ProjectChannel) -> Result<Option<String>, LauncherError> {
+    let project = Path::new(&channel.unity_project_path);
+    if !status(project).is_some_and(|s| s.dirty) {
+        return Ok(None);
+    }
+    let added = run(project, &["add", "-A", "--", "."])?;
+    if !added.status.success() {
+        return Err(failed("add", project, &added));
+    }
+    let message = format!("Checkpoint before Banter MCP session ({})", channel.name);
+    // The pathspec keeps anything staged elsewhere in the repo out of the commit
+    let committed = run(project, &["commit", "-m", &message, "--", "."])?;
+    if !committed.status.success() {
+        return Err(failed("commit", project, &committed)
+            .with_hint("Set user.name and user.email in git, or turn checkpoint_on_activate off"));
+    }
+    Ok(run(project, &["rev-parse", "--short", "HEAD"])
+        .ok()
+        .and_then(|o| stdout(&o)))
+}
+
+/// Commit a checkpoint for the newly active channel; called on every
+/// activation. Failures are logged rather than undoing the activation.
+pub fn channel_activated(config: &LauncherConfig, channel: &ProjectChannel) {
+    if !config.git_guards.checkpoint_on_activate {
+        return;
+    }
+    match checkpoint(channel) {
+        Ok(Some(commit)) => eprintln!("Committed checkpoint {} for {}", commit, channel.id),
+        Ok(None) => {}
+        Err(e) => eprintln!("Failed to commit a checkpoint for {}: {}", channel.id, e),
+    }
+}
+
+/// Git state of a channel's project, `None` outside a repo. The window asks
+/// before activating a channel so a refusal doesn't land mid-save.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_git_status(
+    state: tauri::State<'_, SharedState>,
+    channel_id: String,
+) -> Result<Option<GitStatus>, LauncherError> {
+    crate::sanitize::channel_id(&channel_id)?;
+    let state = state.inner().clone();
+    crate::commands::blocking("Git status", move || {
+        let channel = state.channel(&channel_id)?;
+        Ok(status(Path::new(&channel.unity_project_path)))
+    })
+    .await
+}
@@ -0,0 +1,358 @@
+//! GUID lookups for Unity projects
+//!
+//! Scenes, prefabs and materials reference assets by the GUID in the asset's
+//! `.meta` file, which tells a person nothing. `resolve_guid` turns one back
+//! into the asset's path and name, and `find_references` lists the files
+//! that point at it, so reports and previews can show names instead.
+//!
+//! The GUID of every `.meta` file under Assets, Packages and
+//! Library/PackageCache is indexed in a `ProjectCache` in the store's
+//! `cache/guids`, like the asset inventory: while no walked folder has
+//! changed its mtime, nothing is read again. A folder that changed is walked again, but
+//! a `.meta` file is read only when it is new or was rewritten. References
+//! aren't indexed; `find_references` reads the project's text assets on
+//! every call.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::LauncherError;
+use crate::project_cache::{self, mtime_ms, ProjectCache};
+use crate::state::SharedState;
+use crate::{sanitize, unity};
+
+/// Folders below the project whose `.meta` files are indexed
+const INDEXED_DIRS: &[&str] = &["Assets", "Packages", "Library/PackageCache"];
+
+/// GUIDs of Unity's own resources, which have no `.meta` file in a project
+const BUILT_IN: &[(&str, &str)] = &[
+    (
+        "0000000000000000e000000000000000",
+        "Unity built-in extra resources",
+    ),
+    (
+        "0000000000000000f000000000000000",
+        "Unity default resources",
+    ),
+];
+
+/// Extensions of the text assets that can reference other assets
+const REFERENCING_EXTENSIONS: &[&str] = &[
+    "unity",
+    "prefab",
+    "mat",
+    "asset",
+    "controller",
+    "overrideController",
+    "anim",
+    "playable",
+    "mask",
+    "physicMaterial",
+    "physicsMaterial2D",
+    "lighting",
+    "spriteatlas",
+    "terrainlayer",
+    "mixer",
+    "renderTexture",
+    "signal",
+    "meta",
+];
+
+/// The asset a GUID belongs to
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct GuidAsset {
+    pub guid: String,
+    /// Relative to the project, with forward slashes; `None` for built-in
+    /// resources
+    pub path: Option<String>,
+    /// File name without its extension, or the built-in resource's name
+    pub name: String,
+    pub built_in: bool,
+}
+
+/// A file that references a GUID
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct GuidReference {
+    /// Relative to the project, with forward slashes
+    pub path: String,
+    /// How many times the GUID appears in the file
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct GuidReferences {
+    pub guid: String,
+    /// `None` when no asset in the project has the GUID, so every reference
+    /// to it is broken
+    pub asset: Option<GuidAsset>,
+    /// Sorted by path
+    pub references: Vec<GuidReference>,
+}
+
+/// One `.meta` file as it was when last read
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MetaEntry {
+    guid: String,
+    modified_at: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ProjectIndex {
+    /// Keyed by the asset's relative path (the `.meta` file's without `.meta`)
+    metas: BTreeMap<String, MetaEntry>,
+    /// mtime (Unix ms) of every walked directory
+    mtimes: BTreeMap<String, i64>,
+    /// GUID -> relative asset path, rebuilt from `metas`
+    #[serde(skip)]
+    by_guid: HashMap<String, String>,
+}
+
+/// Document of the `cache` store collection (see `storage`)
+const CACHE_KEY: &str = "guids";
+
+fn relative(path: &Path, project: &Path) -> String {
+    path.strip_prefix(project)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// Check that `guid` is the 32 hex digits Unity writes
+fn check_guid(guid: &str) -> Result<String, LauncherError> {
+    let guid = guid.trim().to_ascii_lowercase();
+    if guid.len() != 32 || !guid.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(
+            LauncherError::invalid(format!("Not a Unity GUID: {}", guid))
+                .with_hint("A GUID is the 32 hex digits after guid: in a .meta file"),
+        );
+    }
+    Ok(guid)
+}
+
+fn built_in(guid: &str) -> Option<GuidAsset> {
+    BUILT_IN
+        .iter()
+        .find(|(id, _)| *id == guid)
+        .map(|(id, name)| GuidAsset {
+            guid: id.to_string(),
+            path: None,
+            name: name.to_string(),
+            built_in: true,
+        })
+}
+
+fn asset(guid: &str, path: &str) -> GuidAsset {
+    GuidAsset {
+        guid: guid.to_string(),
+        path: Some(path.to_string()),
+        name: Path::new(path)
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string()),
+        built_in: false,
+    }
+}
+
+impl ProjectIndex {
+    /// Walk the indexed folders again, reading only new or rewritten `.meta`
+    /// files
+    fn rebuild(&mut self, project: &Path) {
+        let mut metas = BTreeMap::new();
+        let mut mtimes = BTreeMap::new();
+        let mut pending: Vec<PathBuf> = INDEXED_DIRS.iter().map(|d| project.join(d)).collect();
+
+        while let Some(dir) = pending.pop() {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            if let Some(mtime) = mtime_ms(&dir) {
+                mtimes.insert(dir.to_string_lossy().to_string(), mtime);
+            }
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    pending.push(path);
+                    continue;
+                }
+                if path.extension().and_then(|e| e.to_str()) != Some("meta") {
+                    continue;
+                }
+                let Some(modified_at) = mtime_ms(&path) else {
+                    continue;
+                };
+                let key = relative(&path.with_extension(""), project);
+                let known = self
+                    .metas
+                    .get(&key)
+                    .filter(|m| m.modified_at == modified_at)
+                    .map(|m| m.guid.clone());
+                let guid = known.or_else(|| {
+                    fs::read_to_string(&path)
+                        .ok()
+                        .and_then(|m| unity::meta_guid(&m))
+                });
+                if let Some(guid) = guid {
+                    metas.insert(key, MetaEntry { guid, modified_at });
+                }
+            }
+        }
+
+        self.metas = metas;
+        self.mtimes = mtimes;
+        self.by_guid.clear();
+    }
+
+    fn by_guid(&mut self) -> &HashMap<String, String> {
+        if self.by_guid.is_empty() {
+            self.by_guid = self
+                .metas
+                .iter()
+                .map(|(path, meta)| (meta.guid.clone(), path.clone()))
+                .collect();
+        }
+        &self.by_guid
+    }
+}
+
+/// GUID indexes, kept in `AppState`. Checks that run without it (the CLI's
+/// `doctor`, the status server) use one of their own, read from the same
+/// document.
+pub struct GuidCache(ProjectCache<ProjectIndex>);
+
+impl Default for GuidCache {
+    fn default() -> Self {
+        GuidCache(ProjectCache::new(CACHE_KEY))
+    }
+}
+
+impl GuidCache {
+    /// The assets `guids` belong to, with the project walked again only if
+    /// it changed; GUIDs found nowhere are left out
+    pub fn resolve(&self, project: &Path, guids: &[&str]) -> HashMap<String, GuidAsset> {
+        self.0.with(project, |index| {
+            let walked = !project_cache::is_current(&index.mtimes);
+            if walked {
+                index.rebuild(project);
+            }
+
+            let by_guid = index.by_guid();
+            let resolved = guids
+                .iter()
+                .filter_map(|guid| {
+                    let asset =
+                        built_in(guid).or_else(|| by_guid.get(*guid).map(|p| asset(guid, p)))?;
+                    Some((guid.to_string(), asset))
+                })
+                .collect();
+            (resolved, walked)
+        })
+    }
+
+    /// Forget indexes at or below `path`, or everything with `None`
+    pub fn invalidate(&self, path: Option<&Path>) {
+        self.0.invalidate(path)
+    }
+}
+
+/// Files under Assets that mention `guid`, other than the asset's own `.meta`
+fn references(project: &Path, guid: &str, own_path: Option<&str>) -> Vec<GuidReference> {
+    let needle = format!("guid: {}", guid);
+    let own_meta = own_path.map(|p| format!("{}.meta", p));
+    let mut found = Vec::new();
+    let mut pending = vec![project.join("Assets")];
+
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            let referencing = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| REFERENCING_EXTENSIONS.contains(&e));
+            if !referencing {
+                continue;
+            }
+            // Binary-serialized assets aren't valid UTF-8 and are skipped
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let count = content.matches(&needle).count() as u32;
+            let path = relative(&path, project);
+            if count > 0 && own_meta.as_deref() != Some(path.as_str()) {
+                found.push(GuidReference { path, count });
+            }
+        }
+    }
+
+    found.sort_by(|a, b| a.path.cmp(&b.path));
+    found
+}
+
+fn project_path(project: &str) -> Result<PathBuf, LauncherError> {
+    let project = sanitize::path("Unity project path", project)?;
+    if !project.join("Assets").is_dir() {
+        return Err(
+            LauncherError::not_found("Not a Unity project (no Assets folder)").with_path(&project),
+        );
+    }
+    Ok(project)
+}
+
+/// The asset a GUID belongs to: its path and name, or the built-in resource
+#[tauri::command]
+#[specta::specta]
+pub async fn resolve_guid(
+    state: tauri::State<'_, SharedState>,
+    project: String,
+    guid: String,
+) -> Result<GuidAsset, LauncherError> {
+    let guid = check_guid(&guid)?;
+    let state = state.inner().clone();
+    crate::commands::blocking("Resolve GUID", move || {
+        let project = project_path(&project)?;
+        state
+            .guids
+            .resolve(&project, &[&guid])
+            .remove(&guid)
+            .ok_or_else(|| {
+                LauncherError::not_found(format!("No asset in the project has GUID {}", guid))
+                    .with_path(&project)
+                    .with_hint("The asset may have been deleted, or Unity hasn't imported it yet")
+            })
+    })
+    .await
+}
+
+/// The project files that reference a GUID, and the asset it belongs to if
+/// it still exists
+#[tauri::command]
+#[specta::specta]
+pub async fn find_references(
+    state: tauri::State<'_, SharedState>,
+    project: String,
+    guid: String,
+) -> Result<GuidReferences, LauncherError> {
+    let guid = check_guid(&guid)?;
+    let state = state.inner().clone();
+    crate::commands::blocking("Find references", move || {
+        let project = project_path(&project)?;
+        let asset = state.guids.resolve(&project, &[&guid]).remove(&guid);
+        let own_path = asset.as_ref().and_then(|a| a.path.as_deref());
+        let references = references(&project, &guid, own_path);
+        Ok(GuidReferences {
+            guid,
+            asset,
+            references,
+        })
+    })
+    .await
+}
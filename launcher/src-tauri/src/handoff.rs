@@ -0,0 +1,405 @@
+//! Moving a working session to another machine (`.bantermcp-session`)
+//!
+//! `export_session_state` packs the active channel the way `export_channel`
+//! does (machine paths as `${PROJECT}`), its scene snapshots with the file
+//! contents, and the jobs it still had to finish: queued or running ones and
+//! interrupted or failed ones not resumed yet. Snapshots of binary scenes
+//! are left out, and so are git stash entries and job checkpoints, which
+//! only mean something on the exporting machine.
+//!
+//! `import_session_state` asks for the placeholders like `import_channel`,
+//! then adds the channel, stores the snapshots under it, records the jobs as
+//! interrupted and activates the channel, which registers its MCP server
+//! with Claude Code again. With `resume_jobs` the jobs are queued right away;
+//! otherwise `resume_job` runs them when the project is ready.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::channel_export::{self, ImportStatus, Placeholder, PROJECT_PLACEHOLDER};
+use crate::error::LauncherError;
+use crate::job_history::{self, JobRecord};
+use crate::jobs::{JobInfo, JobRequest, JobStatus};
+use crate::plan::FilePlan;
+use crate::snapshots::{self, Snapshot};
+use crate::state::{AppState, SharedState};
+use crate::{bridge, fs_scope, sanitize, ProjectChannel};
+use launcher_core::user;
+
+pub const FILE_EXTENSION: &str = "bantermcp-session";
+
+const FORMAT: &str = "bantermcp-session";
+const FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SessionFile {
+    format: String,
+    version: u32,
+    /// Unix ms
+    exported_at: i64,
+    /// `user@host` of the exporting launcher
+    exported_by: String,
+    /// The channel as `export_channel` writes it
+    channel: serde_json::Value,
+    #[serde(default)]
+    snapshots: Vec<PackedSnapshot>,
+    #[serde(default)]
+    jobs: Vec<PackedJob>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PackedSnapshot {
+    snapshot: Snapshot,
+    /// File contents by project-relative path
+    contents: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PackedJob {
+    info: JobInfo,
+    /// With the channel id left out and project paths as placeholders
+    request: JobRequest,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct SessionStateExport {
+    /// Suggested file name, e.g. "Lobby.bantermcp-session"
+    pub file_name: String,
+    pub path: String,
+    pub snapshots: usize,
+    pub jobs: usize,
+    /// What couldn't be packed
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct SessionStateImport {
+    pub status: ImportStatus,
+    /// Placeholders still without a value
+    pub placeholders: Vec<Placeholder>,
+    pub channel: Option<ProjectChannel>,
+    /// `user@host` the session was exported from
+    pub exported_by: String,
+    pub snapshots: usize,
+    /// The carried over jobs, queued again with `resume_jobs`
+    pub jobs: Vec<JobInfo>,
+    /// Claude Code config written on activation, `None` when auto-configure
+    /// is off or the channel couldn't be activated
+    pub client_config: Option<String>,
+    pub warnings: Vec<String>,
+}
+
+/// The channel a job request works on, by id or by project
+fn concerns(request: &JobRequest, channel: &ProjectChannel) -> bool {
+    match request {
+        JobRequest::BuildBundle { channel_id, .. }
+        | JobRequest::BuildAllPlatforms { channel_id }
+        | JobRequest::UploadBundle { channel_id, .. } => *channel_id == channel.id,
+        JobRequest::InstallExtension {
+            unity_project_path, ..
+        }
+        | JobRequest::ScanProject { unity_project_path } => {
+            *unity_project_path == channel.unity_project_path
+        }
+        JobRequest::FindProjects { .. } | JobRequest::CheckAllChannels { .. } => false,
+    }
+}
+
+/// Point a job request at `channel`, with `project` for the project path
+/// and this machine's MCP root for installs
+fn retarget(
+    request: &JobRequest,
+    channel_id: &str,
+    project: &str,
+) -> Result<JobRequest, LauncherError> {
+    Ok(match request.clone() {
+        JobRequest::BuildBundle { platform, .. } => JobRequest::BuildBundle {
+            channel_id: channel_id.to_string(),
+            platform,
+        },
+        JobRequest::BuildAllPlatforms { .. } => JobRequest::BuildAllPlatforms {
+            channel_id: channel_id.to_string(),
+        },
+        JobRequest::UploadBundle { platform, .. } => JobRequest::UploadBundle {
+            channel_id: channel_id.to_string(),
+            platform,
+        },
+        JobRequest::InstallExtension { elevate, .. } => JobRequest::InstallExtension {
+            unity_project_path: project.to_string(),
+            mcp_root: crate::get_mcp_root()?,
+            elevate,
+        },
+        JobRequest::ScanProject { .. } => JobRequest::ScanProject {
+            unity_project_path: project.to_string(),
+        },
+        other => other,
+    })
+}
+
+/// A snapshot with its file contents; `None` when a file is missing or not
+/// text, as binary-serialized scenes are
+fn pack_snapshot(snapshot: Snapshot) -> Option<PackedSnapshot> {
+    let files = snapshots::snapshot_dir(&snapshot.channel_id, &snapshot.id).join("files");
+    let mut contents = BTreeMap::new();
+    for file in &snapshot.files {
+        let bytes = fs::read(files.join(&file.path)).ok()?;
+        contents.insert(file.path.clone(), String::from_utf8(bytes).ok()?);
+    }
+    Some(PackedSnapshot {
+        snapshot: Snapshot {
+            git_stash: None,
+            ..snapshot
+        },
+        contents,
+    })
+}
+
+pub fn export(state: &AppState, path: &Path) -> Result<SessionStateExport, LauncherError> {
+    let config = state.config();
+    let channel_id = config.active_channel_id.clone().ok_or_else(|| {
+        LauncherError::invalid("No active channel to export")
+            .with_hint("Activate the channel you are working on first")
+    })?;
+    let channel = state.channel(&channel_id)?;
+    let exported = channel_export::export(state, &channel_id, None)?;
+    let mut warnings = Vec::new();
+
+    let mut packed = Vec::new();
+    for snapshot in snapshots::list(&channel_id) {
+        let id = snapshot.id.clone();
+        let stash = snapshot.git_stash.is_some();
+        match pack_snapshot(snapshot) {
+            Some(snapshot) => {
+                if stash {
+                    warnings.push(format!(
+                        "Snapshot {} is packed without its git stash entry",
+                        id
+                    ));
+                }
+                packed.push(snapshot);
+            }
+            None => warnings.push(format!(
+                "Snapshot {} left out: its scene isn't saved as text",
+                id
+            )),
+        }
+    }
+
+    let portable = channel_export::placeholder(PROJECT_PLACEHOLDER);
+    let jobs: Vec<PackedJob> = job_history::pending()
+        .into_iter()
+        .filter_map(|record| {
+            let request = record.request.filter(|r| concerns(r, &channel))?;
+            let request = retarget(&request, "", &portable).ok()?;
+            if !record.info.status.is_finished() {
+                warnings.push(format!(
+                    "{} is still running here; it is packed as interrupted",
+                    record.info.label
+                ));
+            }
+            Some(PackedJob {
+                info: record.info,
+                request,
+            })
+        })
+        .collect();
+
+    let file = SessionFile {
+        format: FORMAT.to_string(),
+        version: FORMAT_VERSION,
+        exported_at: bridge::now_ms(),
+        exported_by: user::owner(),
+        channel: serde_json::from_str(&exported.content).map_err(|e| {
+            LauncherError::internal(format!("Failed to read channel export: {}", e))
+        })?,
+        snapshots: packed,
+        jobs,
+    };
+    let content = serde_json::to_string_pretty(&file)
+        .map_err(|e| LauncherError::internal(format!("Failed to serialize session: {}", e)))?;
+    let mut plan = FilePlan::new(false);
+    plan.write(path, content)?;
+
+    Ok(SessionStateExport {
+        file_name: format!(
+            "{}.{}",
+            exported
+                .file_name
+                .trim_end_matches(&format!(".{}", channel_export::FILE_EXTENSION)),
+            FILE_EXTENSION
+        ),
+        path: path.to_string_lossy().to_string(),
+        snapshots: file.snapshots.len(),
+        jobs: file.jobs.len(),
+        warnings,
+    })
+}
+
+fn parse(path: &Path) -> Result<SessionFile, LauncherError> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| LauncherError::io("Failed to read session export", path, e))?;
+    let file: SessionFile = serde_json::from_str(&content).map_err(|e| {
+        LauncherError::parse(format!("Failed to parse session export: {}", e)).with_path(path)
+    })?;
+    if file.format != FORMAT {
+        return Err(LauncherError::parse("Not a .bantermcp-session export").with_path(path));
+    }
+    if file.version > FORMAT_VERSION {
+        return Err(LauncherError::parse(format!(
+            "Session export version {} is newer than this launcher supports ({})",
+            file.version, FORMAT_VERSION
+        ))
+        .with_path(path)
+        .with_hint("Update the launcher"));
+    }
+    Ok(file)
+}
+
+pub fn import(
+    app: &AppHandle,
+    state: &SharedState,
+    path: &Path,
+    values: HashMap<String, String>,
+    resume_jobs: bool,
+) -> Result<SessionStateImport, LauncherError> {
+    let file = parse(path)?;
+    let channel_content = serde_json::to_string(&file.channel)
+        .map_err(|e| LauncherError::internal(format!("Failed to read packed channel: {}", e)))?;
+    let imported = channel_export::import_content(
+        state,
+        &channel_content,
+        path,
+        values.clone(),
+        false,
+        false,
+    )?;
+    let mut result = SessionStateImport {
+        status: imported.status,
+        placeholders: imported.placeholders,
+        channel: imported.channel.clone(),
+        exported_by: file.exported_by.clone(),
+        snapshots: 0,
+        jobs: Vec::new(),
+        client_config: None,
+        warnings: imported.warnings,
+    };
+    let Some(channel) = imported.channel else {
+        return Ok(result);
+    };
+
+    let config = state.config();
+    for packed in &file.snapshots {
+        let snapshot = Snapshot {
+            channel_id: channel.id.clone(),
+            ..packed.snapshot.clone()
+        };
+        match snapshots::add(&config, &snapshot, &packed.contents) {
+            Ok(()) => result.snapshots += 1,
+            Err(e) => result
+                .warnings
+                .push(format!("Snapshot {} not imported: {}", snapshot.id, e)),
+        }
+    }
+
+    let project =
+        channel_export::substitute(&channel_export::placeholder(PROJECT_PLACEHOLDER), &values)?;
+    let mut carried = Vec::new();
+    for packed in &file.jobs {
+        let record = JobRecord {
+            info: JobInfo {
+                id: uuid::Uuid::new_v4().to_string(),
+                status: JobStatus::Interrupted,
+                resumed_from: None,
+                ..packed.info.clone()
+            },
+            request: Some(retarget(&packed.request, &channel.id, &project)?),
+            checkpoint: None,
+            pid: 0,
+            resumed: false,
+        };
+        job_history::save(&record);
+        job_history::log(
+            &record.info.id,
+            record.info.created_at,
+            &format!("Carried over from {}", file.exported_by),
+        );
+        carried.push(record.info);
+    }
+
+    match crate::activate_channel(state, &channel.id, false, false) {
+        Ok(activated) => {
+            result.client_config = activated
+                .client_config
+                .map(|p| p.to_string_lossy().to_string());
+            result.warnings.extend(
+                activated
+                    .health_warnings
+                    .into_iter()
+                    .map(|check| check.message),
+            );
+        }
+        Err(e) => result
+            .warnings
+            .push(format!("Channel imported but not activated: {}", e)),
+    }
+    fs_scope::sync(app, &state.config());
+
+    for info in carried {
+        let info = match resume_jobs {
+            true => job_history::resume(app, state, &info.id).unwrap_or_else(|e| {
+                result
+                    .warnings
+                    .push(format!("{} not resumed: {}", info.label, e));
+                info
+            }),
+            false => info,
+        };
+        result.jobs.push(info);
+    }
+    Ok(result)
+}
+
+/// Pack the active channel, its snapshots and its unfinished jobs into
+/// `file` for `import_session_state` on another machine
+#[tauri::command]
+#[specta::specta]
+pub async fn export_session_state(
+    state: tauri::State<'_, SharedState>,
+    file: String,
+) -> Result<SessionStateExport, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Export session", move || {
+        let path = sanitize::path("Session export", &file)?;
+        export(&state, &path)
+    })
+    .await
+}
+
+/// Pick up a session from `export_session_state`. Returns `needs_input` with
+/// the placeholders to prompt for until `values` covers them all.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_session_state(
+    app: AppHandle,
+    state: tauri::State<'_, SharedState>,
+    file: String,
+    values: Option<HashMap<String, String>>,
+    resume_jobs: Option<bool>,
+) -> Result<SessionStateImport, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Import session", move || {
+        let path = sanitize::path("Session export", &file)?;
+        import(
+            &app,
+            &state,
+            &path,
+            values.unwrap_or_default(),
+            resume_jobs.unwrap_or(false),
+        )
+    })
+    .await
+}
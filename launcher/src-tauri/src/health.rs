@@ -0,0 +1,494 @@
+//! Per-channel health checks
+//!
+//! `health_gate` in the config puts them in front of activation from the CLI
+//! and the control API: depending on its severity a failed check is let
+//! through, reported, or refuses the activation unless health is explicitly
+//! ignored. The window asks `get_activation_check` first and lets the user
+//! decide.
+
+use serde::Serialize;
+use specta::Type;
+use std::path::Path;
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::Instant;
+use tauri::{AppHandle, Emitter};
+
+use crate::error::{ErrorKind, LauncherError};
+use crate::guids::GuidCache;
+use crate::preflight::{self, VolumeCheck};
+use crate::state::SharedState;
+use crate::validation::{self, ValidationReport};
+use crate::{banter_sdk, bridge, dashboard, editor_monitor, git, sessions};
+use launcher_core::config::GateAction;
+use launcher_core::editor::EditorCondition;
+
+/// Channels checked at once by `check_all_channels`; each check walks the
+/// project on disk, so more threads mostly contend for the same drive
+const MAX_PARALLEL_CHECKS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Ok,
+    Warning,
+    Error,
+}
+
+impl HealthStatus {
+    pub fn from_severity(severity: &str) -> Self {
+        match severity {
+            "error" => HealthStatus::Error,
+            "warning" => HealthStatus::Warning,
+            _ => HealthStatus::Ok,
+        }
+    }
+}
+
+/// Result of a single check
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct HealthCheck {
+    pub id: String,
+    pub status: HealthStatus,
+    pub message: String,
+}
+
+impl HealthCheck {
+    pub fn new(id: &str, status: HealthStatus, message: impl Into<String>) -> Self {
+        HealthCheck {
+            id: id.to_string(),
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// A preflight check as a health check: unwritable is an error, low space a
+/// warning
+pub fn volume_check(id: &str, check: &VolumeCheck) -> HealthCheck {
+    let status = if !check.writable {
+        HealthStatus::Error
+    } else if !check.has_space() {
+        HealthStatus::Warning
+    } else {
+        HealthStatus::Ok
+    };
+    HealthCheck::new(id, status, check.summary())
+}
+
+/// Health of a channel; `status` is the worst status of all checks
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ChannelHealth {
+    pub channel_id: String,
+    pub status: HealthStatus,
+    pub checks: Vec<HealthCheck>,
+}
+
+/// Run all health checks for a channel
+pub fn check_channel(
+    channel: &crate::ProjectChannel,
+    config: &crate::LauncherConfig,
+    guids: &GuidCache,
+) -> ChannelHealth {
+    let project = Path::new(&channel.unity_project_path);
+    let mut checks = Vec::new();
+
+    if project.join("Assets").is_dir() {
+        checks.push(HealthCheck::new(
+            "project",
+            HealthStatus::Ok,
+            "Unity project found",
+        ));
+    } else {
+        checks.push(HealthCheck::new(
+            "project",
+            HealthStatus::Error,
+            format!("Unity project not found: {}", channel.unity_project_path),
+        ));
+    }
+
+    if project.is_dir() {
+        // Sized for a bundle build, the most a channel writes at once
+        let disk = preflight::check("bundle build", project, preflight::BUILD_FREE_BYTES);
+        checks.push(volume_check("disk", &disk));
+    }
+
+    if let Some(scene) = &channel.scene_path {
+        if !Path::new(scene).exists() {
+            checks.push(HealthCheck::new(
+                "scene",
+                HealthStatus::Error,
+                format!("Scene file missing: {}", scene),
+            ));
+        } else {
+            // Missing scripts and prefabs otherwise only surface when the
+            // bundle build fails
+            match validation::check_references(Path::new(scene), project, guids) {
+                Ok(issues) if issues.is_empty() => checks.push(HealthCheck::new(
+                    "references",
+                    HealthStatus::Ok,
+                    "No missing scripts or prefabs in the scene",
+                )),
+                Ok(issues) => {
+                    for issue in issues {
+                        checks.push(HealthCheck::new(
+                            "references",
+                            HealthStatus::from_severity(&issue.severity),
+                            issue.message,
+                        ));
+                    }
+                }
+                Err(e) => checks.push(HealthCheck::new(
+                    "references",
+                    HealthStatus::Warning,
+                    format!("Couldn't check the scene's references: {}", e),
+                )),
+            }
+        }
+    }
+
+    let compat = banter_sdk::compatibility_report(project, &config.mcp_server_path);
+
+    let bundled = dashboard::bundled_bridge_version();
+    match &compat.bridge_version {
+        Some(version)
+            if bundled.as_deref().is_some_and(|available| {
+                banter_sdk::parse_version(version) < banter_sdk::parse_version(available)
+            }) =>
+        {
+            checks.push(HealthCheck::new(
+                "extension",
+                HealthStatus::Warning,
+                format!(
+                    "Unity extension outdated (bridge {}, {} available)",
+                    version,
+                    bundled.unwrap_or_default()
+                ),
+            ))
+        }
+        Some(version) => checks.push(HealthCheck::new(
+            "extension",
+            HealthStatus::Ok,
+            format!("Unity extension installed (bridge {})", version),
+        )),
+        None => checks.push(HealthCheck::new(
+            "extension",
+            HealthStatus::Warning,
+            "Unity extension not installed",
+        )),
+    }
+
+    match &compat.sdk.version {
+        Some(version) => checks.push(HealthCheck::new(
+            "banter-sdk",
+            HealthStatus::Ok,
+            format!("Banter SDK {}", version),
+        )),
+        None => checks.push(HealthCheck::new(
+            "banter-sdk",
+            HealthStatus::Warning,
+            "Banter SDK not detected in Packages",
+        )),
+    }
+
+    // A closed editor is normal, but bridge commands will queue; a crashed
+    // or frozen one is worth knowing about
+    let editor = editor_monitor::status(channel);
+    let connected = editor.condition == EditorCondition::Connected;
+    checks.push(match (editor.condition, editor.cause) {
+        (EditorCondition::Connected, _) => {
+            HealthCheck::new("editor", HealthStatus::Ok, "Unity editor connected")
+        }
+        (EditorCondition::Starting, _) => HealthCheck::new(
+            "editor",
+            HealthStatus::Ok,
+            "Unity editor is open; waiting for the bridge",
+        ),
+        (_, Some(cause)) => HealthCheck::new("editor", HealthStatus::Warning, cause),
+        _ => HealthCheck::new("editor", HealthStatus::Ok, "Unity editor not running"),
+    });
+
+    // Only a running editor knows; bridges before 1.6 don't say
+    let compile_errors = bridge::read_state(project, "editor-state.json")
+        .and_then(|s| s.get("hasCompileErrors").and_then(|c| c.as_bool()));
+    match compile_errors {
+        Some(true) if connected => checks.push(HealthCheck::new(
+            "compile",
+            HealthStatus::Error,
+            "Unity reports script compile errors",
+        )),
+        Some(false) if connected => checks.push(HealthCheck::new(
+            "compile",
+            HealthStatus::Ok,
+            "Scripts compile",
+        )),
+        _ => {}
+    }
+
+    // Uncommitted work is normal unless activation would be refused or
+    // warned about for it; a checkpoint on activation commits it anyway
+    let guards = &config.git_guards;
+    let dirty_matters = guards.refuse_dirty_activation
+        || (config.health_gate.on_warning != GateAction::Allow && !guards.checkpoint_on_activate);
+    match git::status(project) {
+        Some(status) => checks.push(HealthCheck::new(
+            "git",
+            if status.dirty && dirty_matters {
+                HealthStatus::Warning
+            } else {
+                HealthStatus::Ok
+            },
+            status.summary(),
+        )),
+        None => checks.push(HealthCheck::new(
+            "git",
+            HealthStatus::Ok,
+            "Not a git repository",
+        )),
+    }
+
+    if let Some(conflict) = sessions::conflict(channel) {
+        checks.push(HealthCheck::new(
+            "session",
+            HealthStatus::Warning,
+            conflict.message,
+        ));
+    }
+
+    for issue in &compat.issues {
+        checks.push(HealthCheck::new(
+            "compatibility",
+            HealthStatus::from_severity(&issue.severity),
+            issue.message.clone(),
+        ));
+    }
+
+    let status = checks
+        .iter()
+        .map(|c| c.status)
+        .max()
+        .unwrap_or(HealthStatus::Ok);
+
+    ChannelHealth {
+        channel_id: channel.id.clone(),
+        status,
+        checks,
+    }
+}
+
+/// The health gate's verdict on activating a channel
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ActivationCheck {
+    pub channel_id: String,
+    /// Failed checks that refuse the activation
+    pub refusing: Vec<HealthCheck>,
+    /// Failed checks that are only reported
+    pub warnings: Vec<HealthCheck>,
+}
+
+/// Sort the channel's failed checks by what `health_gate` does with them
+pub fn gate(
+    channel: &crate::ProjectChannel,
+    config: &crate::LauncherConfig,
+    guids: &GuidCache,
+) -> ActivationCheck {
+    let gate = &config.health_gate;
+    let mut result = ActivationCheck {
+        channel_id: channel.id.clone(),
+        refusing: Vec::new(),
+        warnings: Vec::new(),
+    };
+    if gate.is_off() {
+        return result;
+    }
+    for check in check_channel(channel, config, guids).checks {
+        // Another launcher on the project is for `take_over` to settle
+        if check.id == "session" || gate.ignore_checks.contains(&check.id) {
+            continue;
+        }
+        let action = match check.status {
+            HealthStatus::Ok => continue,
+            HealthStatus::Warning => gate.on_warning,
+            HealthStatus::Error => gate.on_error,
+        };
+        match action {
+            GateAction::Allow => {}
+            GateAction::Warn => result.warnings.push(check),
+            GateAction::Refuse => result.refusing.push(check),
+        }
+    }
+    result
+}
+
+/// Apply the health gate to an activation. Returns the failed checks to
+/// report; with `ignore_health` the refusing ones are only reported too.
+pub fn check_activation(
+    config: &crate::LauncherConfig,
+    channel: &crate::ProjectChannel,
+    guids: &GuidCache,
+    ignore_health: bool,
+) -> Result<Vec<HealthCheck>, LauncherError> {
+    let mut check = gate(channel, config, guids);
+    if ignore_health {
+        check.warnings.append(&mut check.refusing);
+    }
+    if check.refusing.is_empty() {
+        return Ok(check.warnings);
+    }
+    let failed: Vec<&str> = check.refusing.iter().map(|c| c.message.as_str()).collect();
+    Err(LauncherError::new(
+        ErrorKind::Conflict,
+        format!(
+            "{} failed health checks: {}",
+            channel.name,
+            failed.join("; ")
+        ),
+    )
+    .with_path(&channel.unity_project_path)
+    .with_hint(
+        "Fix them first, or activate with --ignore-health (ignore_health in the control API)",
+    ))
+}
+
+/// What the health gate would do if the channel were activated now
+#[tauri::command]
+#[specta::specta]
+pub async fn get_activation_check(
+    state: tauri::State<'_, SharedState>,
+    channel_id: String,
+) -> Result<ActivationCheck, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Activation check", move || {
+        let config = state.config();
+        let channel = crate::find_channel(&config, &channel_id)?;
+        Ok(gate(&channel, &config, &state.guids))
+    })
+    .await
+}
+
+/// Get health for a single channel
+#[tauri::command]
+#[specta::specta]
+pub async fn get_channel_health(
+    state: tauri::State<'_, SharedState>,
+    channel_id: String,
+) -> Result<ChannelHealth, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Health check", move || {
+        let config = state.config();
+        let channel = crate::find_channel(&config, &channel_id)?;
+
+        Ok(check_channel(&channel, &config, &state.guids))
+    })
+    .await
+}
+
+/// Health (and optionally validation) of one channel, as streamed by `check_all_channels`
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ChannelCheckResult {
+    pub channel_id: String,
+    pub health: ChannelHealth,
+    pub validation: Option<ValidationReport>,
+    pub validation_error: Option<LauncherError>,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct AllChannelsReport {
+    pub results: Vec<ChannelCheckResult>,
+    pub duration_ms: u64,
+}
+
+fn check_one(
+    channel: &crate::ProjectChannel,
+    config: &crate::LauncherConfig,
+    guids: &GuidCache,
+    validate: bool,
+) -> ChannelCheckResult {
+    let started = Instant::now();
+    let health = check_channel(channel, config, guids);
+    let (validation, validation_error) = if validate {
+        match validation::validate_channel(channel, config, guids) {
+            Ok(report) => (Some(report), None),
+            Err(e) => (None, Some(e)),
+        }
+    } else {
+        (None, None)
+    };
+
+    ChannelCheckResult {
+        channel_id: channel.id.clone(),
+        health,
+        validation,
+        validation_error,
+        duration_ms: started.elapsed().as_millis() as u64,
+    }
+}
+
+/// Check every channel on a bounded pool of threads. Each result is emitted
+/// as a `channel-check` event as soon as it is ready; the returned report is
+/// in channel order.
+pub fn check_all(
+    app: &AppHandle,
+    config: &crate::LauncherConfig,
+    guids: &GuidCache,
+    validate: bool,
+) -> AllChannelsReport {
+    let started = Instant::now();
+    let queue = Mutex::new(config.channels.iter());
+    let (tx, rx) = mpsc::channel();
+
+    let mut results: Vec<ChannelCheckResult> = thread::scope(|scope| {
+        for _ in 0..MAX_PARALLEL_CHECKS.min(config.channels.len()) {
+            let tx = tx.clone();
+            let queue = &queue;
+            scope.spawn(move || loop {
+                let next = queue.lock().unwrap_or_else(|e| e.into_inner()).next();
+                let Some(channel) = next else {
+                    break;
+                };
+                if tx
+                    .send(check_one(channel, config, guids, validate))
+                    .is_err()
+                {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+
+        rx.iter()
+            .inspect(|result| {
+                let _ = app.emit("channel-check", result);
+            })
+            .collect()
+    });
+
+    let order = |id: &str| config.channels.iter().position(|c| c.id == id);
+    results.sort_by_key(|r| order(&r.channel_id));
+
+    AllChannelsReport {
+        results,
+        duration_ms: started.elapsed().as_millis() as u64,
+    }
+}
+
+/// Check health (and with `validate`, content rules) of all channels concurrently
+#[tauri::command]
+#[specta::specta]
+pub async fn check_all_channels(
+    app: AppHandle,
+    state: tauri::State<'_, SharedState>,
+    validate: Option<bool>,
+) -> Result<AllChannelsReport, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Channel checks", move || {
+        Ok(check_all(
+            &app,
+            &state.config(),
+            &state.guids,
+            validate.unwrap_or(false),
+        ))
+    })
+    .await
+}
@@ -0,0 +1,253 @@
+//! Global hook scripts
+//!
+//! `LauncherConfig.hooks` names scripts to run when a channel is activated or
+//! deactivated and when a supervised MCP server crashes (`--watch`), so ops
+//! can plug the launcher into their own tooling. Scripts run in the
+//! background with the channel passed through the environment:
+//!
+//! | Variable | |
+//! |---|---|
+//! | `BANTER_HOOK_EVENT` | `on-activate`, `on-deactivate` or `on-server-crash` |
+//! | `BANTER_CHANNEL_ID`, `BANTER_CHANNEL_NAME` | The channel |
+//! | `BANTER_UNITY_PROJECT_PATH`, `BANTER_SCENE_PATH` | Its project and scene |
+//! | `BANTER_MCP_SERVER_PATH` | Configured MCP server |
+//! | `BANTER_PREVIOUS_CHANNEL_ID` | `on-activate`: channel deactivated |
+//! | `BANTER_EXIT_STATUS` | `on-server-crash`: how the server exited |
+//!
+//! Their output is appended to `hooks.log` in the launcher logs folder.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::{git, logs, recorder, snapshots};
+use crate::{LauncherConfig, ProjectChannel};
+use launcher_core::config::HookScripts;
+
+/// A hook still running after this long is killed
+const HOOK_TIMEOUT: Duration = Duration::from_secs(60);
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Hooks that haven't finished; the CLI waits for them before exiting
+static RUNNING: Mutex<Vec<JoinHandle<()>>> = Mutex::new(Vec::new());
+
+#[derive(Debug, Clone, Copy)]
+pub enum HookEvent {
+    Activate,
+    Deactivate,
+    ServerCrash,
+}
+
+impl HookEvent {
+    fn name(self) -> &'static str {
+        match self {
+            HookEvent::Activate => "on-activate",
+            HookEvent::Deactivate => "on-deactivate",
+            HookEvent::ServerCrash => "on-server-crash",
+        }
+    }
+
+    fn script(self, hooks: &HookScripts) -> Option<&str> {
+        match self {
+            HookEvent::Activate => hooks.on_activate.as_deref(),
+            HookEvent::Deactivate => hooks.on_deactivate.as_deref(),
+            HookEvent::ServerCrash => hooks.on_server_crash.as_deref(),
+        }
+        .filter(|s| !s.trim().is_empty())
+    }
+}
+
+/// A hook ready to run
+struct Invocation {
+    event: HookEvent,
+    script: String,
+    env: Vec<(&'static str, String)>,
+}
+
+fn invocation(
+    config: &LauncherConfig,
+    event: HookEvent,
+    channel: &ProjectChannel,
+    extra: Vec<(&'static str, String)>,
+) -> Option<Invocation> {
+    let script = event.script(&config.hooks)?.to_string();
+
+    let mut env = vec![
+        ("BANTER_HOOK_EVENT", event.name().to_string()),
+        ("BANTER_CHANNEL_ID", channel.id.clone()),
+        ("BANTER_CHANNEL_NAME", channel.name.clone()),
+        (
+            "BANTER_UNITY_PROJECT_PATH",
+            channel.unity_project_path.clone(),
+        ),
+        ("BANTER_MCP_SERVER_PATH", config.mcp_server_path.clone()),
+    ];
+    if let Some(scene) = &channel.scene_path {
+        env.push(("BANTER_SCENE_PATH", scene.clone()));
+    }
+    env.extend(extra);
+
+    Some(Invocation { event, script, env })
+}
+
+/// Interpreter for scripts that can't be executed directly
+fn command_for(script: &str) -> Command {
+    let extension = Path::new(script)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+
+    match extension.as_str() {
+        "ps1" => {
+            let mut command = Command::new("powershell");
+            command.args(["-NoProfile", "-ExecutionPolicy", "Bypass", "-File", script]);
+            command
+        }
+        "bat" | "cmd" => {
+            let mut command = Command::new("cmd");
+            command.args(["/C", script]);
+            command
+        }
+        "sh" => {
+            let mut command = Command::new("sh");
+            command.arg(script);
+            command
+        }
+        _ => Command::new(script),
+    }
+}
+
+/// Run one hook to completion (or timeout), logging its output
+fn execute(invocation: &Invocation) {
+    let log_path = logs::hooks_log_path();
+    if let Some(dir) = log_path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    let log = OpenOptions::new().create(true).append(true).open(&log_path);
+    let mut log = match log {
+        Ok(log) => log,
+        Err(e) => {
+            eprintln!("warning: cannot write {}: {}", log_path.display(), e);
+            return;
+        }
+    };
+    let _ = writeln!(
+        log,
+        "--- {} {} ({})",
+        invocation.event.name(),
+        invocation.script,
+        invocation
+            .env
+            .iter()
+            .find(|(name, _)| *name == "BANTER_CHANNEL_ID")
+            .map(|(_, id)| id.as_str())
+            .unwrap_or("")
+    );
+
+    let mut command = command_for(&invocation.script);
+    command
+        .envs(invocation.env.iter().map(|(k, v)| (*k, v)))
+        .stdin(Stdio::null());
+    if let (Ok(out), Ok(err)) = (log.try_clone(), log.try_clone()) {
+        command.stdout(out).stderr(err);
+    }
+
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            let _ = writeln!(log, "failed to start: {}", e);
+            return;
+        }
+    };
+
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status.to_string(),
+            Ok(None) if started.elapsed() >= HOOK_TIMEOUT => {
+                let _ = child.kill();
+                let _ = child.wait();
+                break format!("killed after {}s", HOOK_TIMEOUT.as_secs());
+            }
+            Ok(None) => thread::sleep(POLL_INTERVAL),
+            Err(e) => break format!("failed to wait: {}", e),
+        }
+    };
+    let _ = writeln!(log, "--- {}", status);
+}
+
+/// Run hooks one after another on a background thread
+fn spawn(invocations: Vec<Invocation>) {
+    if invocations.is_empty() {
+        return;
+    }
+    let handle = thread::spawn(move || {
+        for invocation in &invocations {
+            execute(invocation);
+        }
+    });
+
+    let mut running = RUNNING.lock().unwrap_or_else(|e| e.into_inner());
+    running.retain(|h| !h.is_finished());
+    running.push(handle);
+}
+
+/// Run a single hook for a channel, if one is configured
+pub fn run(
+    config: &LauncherConfig,
+    event: HookEvent,
+    channel: &ProjectChannel,
+    extra: Vec<(&'static str, String)>,
+) {
+    spawn(
+        invocation(config, event, channel, extra)
+            .into_iter()
+            .collect(),
+    );
+}
+
+/// Run `on-deactivate` then `on-activate` if a config change switched the
+/// active channel
+pub fn active_channel_changed(old: &LauncherConfig, new: &LauncherConfig) {
+    if old.active_channel_id == new.active_channel_id {
+        return;
+    }
+    fn find(config: &LauncherConfig) -> Option<&ProjectChannel> {
+        let id = config.active_channel_id.as_deref()?;
+        config.channels.iter().find(|c| c.id == id)
+    }
+
+    let mut invocations = Vec::new();
+    if let Some(previous) = find(old) {
+        recorder::channel_deactivated(new, previous);
+        invocations.extend(invocation(new, HookEvent::Deactivate, previous, Vec::new()));
+    }
+    if let Some(current) = find(new) {
+        // Before the activate hook, so checkpoint and snapshot predate anything it starts
+        git::channel_activated(new, current);
+        snapshots::channel_activated(new, current);
+        recorder::channel_activated(new, current);
+        let previous = old.active_channel_id.clone().unwrap_or_default();
+        invocations.extend(invocation(
+            new,
+            HookEvent::Activate,
+            current,
+            vec![("BANTER_PREVIOUS_CHANNEL_ID", previous)],
+        ));
+    }
+    spawn(invocations);
+}
+
+/// Block until every running hook has finished
+pub fn wait() {
+    let handles = std::mem::take(&mut *RUNNING.lock().unwrap_or_else(|e| e.into_inner()));
+    for handle in handles {
+        let _ = handle.join();
+    }
+}
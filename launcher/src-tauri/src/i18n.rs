@@ -0,0 +1,10 @@
+//! Message catalogue command; the catalogue is `launcher_core::i18n`
+
+pub use launcher_core::i18n::*;
+
+/// Every message template, for the frontend to render ids itself
+#[tauri::command]
+#[specta::specta]
+pub fn get_message_catalogue(locale: Option<String>) -> MessageCatalogue {
+    catalogue(locale.as_deref())
+}
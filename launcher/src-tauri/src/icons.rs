@@ -0,0 +1,283 @@
+//! Channel icons from the project's own assets
+//!
+//! `generate_channel_icon` picks a thumbnail for a channel card: the default
+//! icon set in the project's Player Settings, or, when there is none (or
+//! `source` asks for it), a screenshot of the open scene taken through the
+//! bridge. The image is copied into `icons/` in the launcher folder together
+//! with a small record of where it came from, and the window loads it from the
+//! `channel-icon` URI scheme (`channel-icon://localhost/<channel id>`), so
+//! it never needs file access to the project.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::borrow::Cow;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::bridge_queue::{self, Priority};
+use crate::error::{ErrorKind, LauncherError};
+use crate::plan::FilePlan;
+use crate::state::SharedState;
+use crate::{batchmode, bridge, unity, ProjectChannel};
+
+/// URI scheme the window loads icons from
+pub const SCHEME: &str = "channel-icon";
+
+pub const SOURCE_PLAYER_SETTINGS: &str = "player-settings";
+pub const SOURCE_SCENE: &str = "scene";
+
+/// Edge length of scene screenshots, in pixels
+const SCREENSHOT_SIZE: u32 = 256;
+
+/// Image types a webview can show, by extension
+const IMAGE_TYPES: &[(&str, &str)] = &[
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ChannelIcon {
+    pub channel_id: String,
+    /// `player-settings` or `scene`
+    pub source: String,
+    /// Project asset the icon was copied from, for `player-settings`
+    pub asset_path: Option<String>,
+    /// File name inside `icons/`
+    pub file: String,
+    /// Unix ms; also the cache buster for the icon's URL
+    pub updated_at: i64,
+}
+
+fn icon_dir() -> PathBuf {
+    crate::launcher_dir().join("icons")
+}
+
+fn record_path(channel_id: &str) -> PathBuf {
+    icon_dir().join(format!("{}.json", channel_id))
+}
+
+fn mime_type(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_lowercase();
+    IMAGE_TYPES
+        .iter()
+        .find(|(ext, _)| *ext == extension)
+        .map(|(_, mime)| *mime)
+}
+
+/// The cached icon of a channel, if one was generated
+pub fn cached(channel_id: &str) -> Option<ChannelIcon> {
+    let icon: ChannelIcon =
+        serde_json::from_str(&fs::read_to_string(record_path(channel_id)).ok()?).ok()?;
+    icon_dir().join(&icon.file).is_file().then_some(icon)
+}
+
+/// Guid of the default icon in `ProjectSettings.asset`. Icons are listed per
+/// build target; the default group has an empty `m_BuildTarget`, and any
+/// other target's icon does when there is no default.
+fn player_settings_icon_guid(settings: &str) -> Option<String> {
+    let mut in_icons = false;
+    let mut target = String::new();
+    let mut fallback = None;
+    for line in settings.lines() {
+        let trimmed = line.trim_start_matches([' ', '-']).trim();
+        if line.starts_with("  m_") && !line.starts_with("   ") {
+            in_icons = trimmed.starts_with("m_BuildTargetIcons:");
+            continue;
+        }
+        if !in_icons {
+            continue;
+        }
+        if let Some(value) = trimmed.strip_prefix("m_BuildTarget:") {
+            target = value.trim().to_string();
+        } else if let Some(value) = trimmed.strip_prefix("m_Icon:") {
+            let guid = crate::unity_yaml::parse_ref(value)
+                .and_then(|r| r.guid)
+                .filter(|g| !g.is_empty() && g.chars().any(|c| c != '0'));
+            match guid {
+                Some(guid) if target.is_empty() => return Some(guid),
+                Some(guid) => fallback = fallback.or(Some(guid)),
+                None => {}
+            }
+        }
+    }
+    fallback
+}
+
+/// Project asset with the given guid, found through its .meta file
+fn asset_by_guid(project: &Path, guid: &str) -> Option<PathBuf> {
+    unity::find_files(&project.join("Assets"), "meta")
+        .into_iter()
+        .find(|meta| {
+            fs::read_to_string(meta)
+                .ok()
+                .and_then(|m| unity::meta_guid(&m))
+                .is_some_and(|g| g == guid)
+        })
+        .map(|meta| meta.with_extension(""))
+        .filter(|asset| asset.is_file())
+}
+
+/// The Player Settings icon, if it is an image the window can show
+fn player_settings_icon(project: &Path) -> Result<PathBuf, LauncherError> {
+    let settings_path = project
+        .join("ProjectSettings")
+        .join("ProjectSettings.asset");
+    let settings = fs::read_to_string(&settings_path)
+        .map_err(|e| LauncherError::io("Failed to read", &settings_path, e))?;
+    let guid = player_settings_icon_guid(&settings).ok_or_else(|| {
+        LauncherError::not_found("No icon is set in Player Settings").with_path(&settings_path)
+    })?;
+    let asset = asset_by_guid(project, &guid).ok_or_else(|| {
+        LauncherError::not_found(format!("Player Settings icon not found (guid {})", guid))
+            .with_path(project)
+    })?;
+    if mime_type(&asset).is_none() {
+        return Err(LauncherError::invalid(format!(
+            "The Player Settings icon isn't a PNG or JPEG: {}",
+            asset.display()
+        ))
+        .with_path(&asset));
+    }
+    Ok(asset)
+}
+
+/// Have the connected editor render the open scene into a PNG
+fn scene_screenshot(project: &Path) -> Result<PathBuf, LauncherError> {
+    if !bridge::is_editor_connected(project) {
+        return Err(LauncherError::new(
+            ErrorKind::Bridge,
+            "No Unity editor is connected to this project",
+        )
+        .with_hint("Open the project in Unity to capture the scene"));
+    }
+    let started = bridge::now_ms();
+    bridge_queue::send(
+        project,
+        Priority::Interactive,
+        &serde_json::json!({ "type": "capture_thumbnail", "size": SCREENSHOT_SIZE }),
+    )?;
+    batchmode::await_state(project, "thumbnail.json", started, &mut |_| Ok(()))?;
+    let screenshot = bridge::state_dir(project).join("thumbnail.png");
+    if !screenshot.is_file() {
+        return Err(LauncherError::new(
+            ErrorKind::Unity,
+            "Unity reported a screenshot but didn't write it",
+        )
+        .with_path(&screenshot)
+        .with_hint("Update the Unity extension; older bridges can't capture thumbnails"));
+    }
+    Ok(screenshot)
+}
+
+/// Find or render the channel's icon and cache it. `source` forces one of
+/// the two; by default Player Settings are tried first.
+pub fn generate(
+    channel: &ProjectChannel,
+    source: Option<&str>,
+) -> Result<ChannelIcon, LauncherError> {
+    let project = Path::new(&channel.unity_project_path);
+    let (source, image, asset_path) = match source {
+        Some(SOURCE_SCENE) => (SOURCE_SCENE, scene_screenshot(project)?, None),
+        Some(SOURCE_PLAYER_SETTINGS) => {
+            let asset = player_settings_icon(project)?;
+            (SOURCE_PLAYER_SETTINGS, asset.clone(), Some(asset))
+        }
+        Some(other) => {
+            return Err(
+                LauncherError::invalid(format!("Unknown icon source: {}", other))
+                    .with_hint("Use player-settings or scene"),
+            )
+        }
+        None => match player_settings_icon(project) {
+            Ok(asset) => (SOURCE_PLAYER_SETTINGS, asset.clone(), Some(asset)),
+            Err(_) => {
+                let screenshot = scene_screenshot(project).map_err(|e| {
+                    e.with_hint(
+                        "Set a default icon in Player Settings, or open the project in Unity to capture the scene",
+                    )
+                })?;
+                (SOURCE_SCENE, screenshot, None)
+            }
+        },
+    };
+
+    let extension = image
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("png")
+        .to_lowercase();
+    let file = format!("{}.{}", channel.id, extension);
+    let dir = icon_dir();
+    let mut plan = FilePlan::new(false);
+    plan.create_dir(&dir)?;
+    // A previous icon of another type would otherwise linger
+    if let Some(previous) = cached(&channel.id).filter(|p| p.file != file) {
+        plan.delete(&dir.join(previous.file))?;
+    }
+    plan.copy(&image, &dir.join(&file))?;
+
+    let icon = ChannelIcon {
+        channel_id: channel.id.clone(),
+        source: source.to_string(),
+        asset_path: asset_path.map(|p| {
+            p.strip_prefix(project)
+                .unwrap_or(&p)
+                .to_string_lossy()
+                .replace('\\', "/")
+        }),
+        file,
+        updated_at: bridge::now_ms(),
+    };
+    let record = serde_json::to_string_pretty(&icon)
+        .map_err(|e| LauncherError::internal(format!("Failed to serialize icon: {}", e)))?;
+    plan.write(&record_path(&channel.id), record)?;
+    Ok(icon)
+}
+
+/// Answer a `channel-icon://localhost/<channel id>` request
+pub fn serve(request: &tauri::http::Request<Vec<u8>>) -> tauri::http::Response<Cow<'static, [u8]>> {
+    use tauri::http::{header, Response, StatusCode};
+
+    let respond = |status: StatusCode, mime: &str, body: Vec<u8>| {
+        Response::builder()
+            .status(status)
+            .header(header::CONTENT_TYPE, mime)
+            .header(header::CACHE_CONTROL, "no-cache")
+            .body(Cow::Owned(body))
+            .unwrap_or_else(|_| Response::new(Cow::Borrowed(&[][..])))
+    };
+    let channel_id = request.uri().path().trim_start_matches('/');
+    if crate::sanitize::channel_id(channel_id).is_err() {
+        return respond(
+            StatusCode::BAD_REQUEST,
+            "text/plain",
+            b"bad channel id".to_vec(),
+        );
+    }
+    let Some(icon) = cached(channel_id) else {
+        return respond(StatusCode::NOT_FOUND, "text/plain", b"no icon".to_vec());
+    };
+    let path = icon_dir().join(&icon.file);
+    match (fs::read(&path), mime_type(&path)) {
+        (Ok(bytes), Some(mime)) => respond(StatusCode::OK, mime, bytes),
+        _ => respond(StatusCode::NOT_FOUND, "text/plain", b"no icon".to_vec()),
+    }
+}
+
+/// Extract or render a thumbnail for the channel's card. `source` is
+/// `player-settings` or `scene`; by default Player Settings are tried first.
+#[tauri::command]
+#[specta::specta]
+pub async fn generate_channel_icon(
+    state: tauri::State<'_, SharedState>,
+    channel_id: String,
+    source: Option<String>,
+) -> Result<ChannelIcon, LauncherError> {
+    crate::sanitize::channel_id(&channel_id)?;
+    let state = state.inner().clone();
+    crate::commands::blocking("Channel icon", move || {
+        generate(&state.channel(&channel_id)?, source.as_deref())
+    })
+    .await
+}
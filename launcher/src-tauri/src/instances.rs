@@ -0,0 +1,107 @@
+//! Other users' launchers on the same machine
+//!
+//! The window and watch mode announce themselves through `user::announce` and
+//! renew the announcement while they run, so a launcher started by another
+//! artist on a shared workstation can say who else is running and whether
+//! they hold a port this launcher wants. See the core `user` module for how
+//! ports are kept apart in the first place.
+
+use serde::Serialize;
+use specta::Type;
+use std::thread;
+
+use crate::error::LauncherError;
+use crate::sessions::REFRESH_INTERVAL;
+use crate::state::SharedState;
+use crate::user::{self, Instance};
+use crate::{bridge, LauncherConfig};
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct InstanceReport {
+    /// Live launchers of other users, by user name
+    pub others: Vec<Instance>,
+    /// Ports this launcher is configured for that another user's holds
+    pub port_clashes: Vec<u16>,
+}
+
+/// Ports the launcher listens on with this config, plus `extra`
+fn ports(config: &LauncherConfig, extra: &[u16]) -> Vec<u16> {
+    let mut ports = vec![config.preview_server_port];
+    if config.control_api_enabled {
+        ports.push(config.control_api_port);
+    }
+    ports.extend_from_slice(extra);
+    ports
+}
+
+pub fn report(config: &LauncherConfig, extra_ports: &[u16]) -> InstanceReport {
+    let others = user::other_instances(&user::name());
+    let ours = ports(config, extra_ports);
+    let mut port_clashes: Vec<u16> = others
+        .iter()
+        .flat_map(|other| other.ports.iter().copied())
+        .filter(|port| ours.contains(port))
+        .collect();
+    port_clashes.sort_unstable();
+    port_clashes.dedup();
+    InstanceReport {
+        others,
+        port_clashes,
+    }
+}
+
+/// Human-readable summary of `report`, `None` when nobody else is running
+pub fn describe(report: &InstanceReport) -> Option<String> {
+    if report.others.is_empty() {
+        return None;
+    }
+    let names: Vec<&str> = report.others.iter().map(|i| i.user.as_str()).collect();
+    let mut text = format!(
+        "Another user's launcher is running on this machine ({})",
+        names.join(", ")
+    );
+    if !report.port_clashes.is_empty() {
+        let ports: Vec<String> = report.port_clashes.iter().map(|p| p.to_string()).collect();
+        text += &format!("; it holds port {} as well", ports.join(", "));
+    }
+    Some(text)
+}
+
+/// Announce this launcher and keep the announcement fresh until exit.
+/// `extra_ports` are listened on besides the configured ones.
+pub fn start(state: &SharedState, extra_ports: Vec<u16>) {
+    let state = state.clone();
+    let started_ms = bridge::now_ms();
+    thread::spawn(move || loop {
+        let instance = Instance {
+            user: user::name(),
+            host: user::host(),
+            pid: std::process::id(),
+            launcher_dir: crate::launcher_dir().to_string_lossy().to_string(),
+            ports: ports(&state.config(), &extra_ports),
+            started_ms,
+            updated_ms: bridge::now_ms(),
+        };
+        if let Err(e) = user::announce(&instance) {
+            eprintln!("Failed to announce launcher instance: {}", e);
+        }
+        thread::sleep(REFRESH_INTERVAL);
+    });
+}
+
+/// Remove this launcher's announcement; called on exit
+pub fn stop() {
+    if let Err(e) = user::withdraw(&user::name()) {
+        eprintln!("Failed to remove launcher instance: {}", e);
+    }
+}
+
+/// Other users' launchers running on this machine
+#[tauri::command]
+#[specta::specta]
+pub async fn get_other_instances(
+    state: tauri::State<'_, SharedState>,
+) -> Result<InstanceReport, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Instances", move || Ok(report(&state.config(), &[]))).await
+}
@@ -0,0 +1,246 @@
+//! Jobs kept across launcher restarts
+//!
+//! The job queue forgets everything when the launcher quits. Each job is
+//! also recorded in the `jobs` collection of the store (see `storage`), with
+//! its progress messages and outcome in `jobs/<id>.log` in the launcher
+//! folder, so `get_job_history` can still show last week's failed upload and
+//! what it said. The newest `MAX_RECORDS` are kept.
+//!
+//! A job that was queued or running when the launcher quit or crashed is
+//! marked `interrupted` on the next start. Jobs started from a request
+//! (everything `enqueue_job` takes, and the install and upload commands)
+//! remember it, and `resume_job` runs an interrupted one again, once: the
+//! record is marked resumed before the new run is queued.
+//! Uploads save which files they have sent as they go and skip those on
+//! resume, unless a file has changed since; installs, builds and scans start
+//! over.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::cmp::Reverse;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+use crate::error::{ErrorKind, LauncherError};
+use crate::jobs::{self, JobInfo, JobRequest, JobStatus};
+use crate::state::SharedState;
+use crate::storage;
+
+/// Store collection of the records
+const COLLECTION: &str = "jobs";
+
+/// Records kept; older finished ones are removed with their logs
+const MAX_RECORDS: usize = 200;
+
+/// Held while a record is checked and marked resumed, so two resumes of one
+/// job can't both queue it
+static HISTORY: Mutex<()> = Mutex::new(());
+
+/// What is stored for a job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobRecord {
+    pub info: JobInfo,
+    /// The request that started the job; `None` for work that can't be
+    /// queued again
+    pub request: Option<JobRequest>,
+    /// How far the job got, as saved by the job itself
+    pub checkpoint: Option<serde_json::Value>,
+    /// Launcher process that ran the job
+    pub pid: u32,
+    /// A run resuming this one has been queued
+    #[serde(default)]
+    pub resumed: bool,
+}
+
+/// A job in `get_job_history`
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct JobHistoryEntry {
+    #[serde(flatten)]
+    pub info: JobInfo,
+    /// `resume_job` can run it again
+    pub resumable: bool,
+    /// Its saved progress messages and outcome
+    pub log_path: String,
+}
+
+fn history_dir() -> PathBuf {
+    crate::launcher_dir().join("jobs")
+}
+
+pub fn log_path(id: &str) -> PathBuf {
+    history_dir().join(format!("{}.log", id))
+}
+
+/// Write a job's record. History is best effort: a job doesn't fail because
+/// its record couldn't be written.
+pub fn save(record: &JobRecord) {
+    if let Err(e) = write(record) {
+        eprintln!("warning: failed to record job {}: {}", record.info.id, e);
+    }
+}
+
+fn write(record: &JobRecord) -> Result<(), LauncherError> {
+    let json = serde_json::to_string_pretty(record)
+        .map_err(|e| LauncherError::internal(format!("Failed to serialize job record: {}", e)))?;
+    storage::store().put(COLLECTION, &record.info.id, &json)
+}
+
+/// Append a line to the job's log, stamped with the seconds since it was
+/// created
+pub fn log(id: &str, created_at: i64, message: &str) {
+    let path = log_path(id);
+    let _ = fs::create_dir_all(history_dir());
+    let Ok(mut log) = OpenOptions::new().create(true).append(true).open(&path) else {
+        return;
+    };
+    let elapsed = (crate::bridge::now_ms() - created_at).max(0) as f64 / 1000.0;
+    let _ = writeln!(log, "{:>8.1}s {}", elapsed, message);
+}
+
+/// Every record, newest first
+fn records() -> Vec<JobRecord> {
+    let Ok(documents) = storage::store().list(COLLECTION) else {
+        return Vec::new();
+    };
+    let mut records: Vec<JobRecord> = documents
+        .iter()
+        .filter_map(|(_, json)| serde_json::from_str(json).ok())
+        .collect();
+    records.sort_by_key(|r| Reverse(r.info.created_at));
+    records
+}
+
+pub fn load(id: &str) -> Result<JobRecord, LauncherError> {
+    crate::sanitize::identifier("Job id", id)?;
+    storage::store()
+        .get(COLLECTION, id)?
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .ok_or_else(|| LauncherError::not_found(format!("Job not in the history: {}", id)))
+}
+
+/// Mark jobs an earlier launcher left unfinished as interrupted, and drop
+/// the oldest records beyond `MAX_RECORDS`. Runs once at startup.
+pub fn recover() {
+    let own = std::process::id();
+    for (index, mut record) in records().into_iter().enumerate() {
+        if !record.info.status.is_finished() && record.pid != own {
+            record.info.status = JobStatus::Interrupted;
+            log(
+                &record.info.id,
+                record.info.created_at,
+                "Interrupted: the launcher quit before the job finished",
+            );
+            save(&record);
+        }
+        if index >= MAX_RECORDS && record.info.status.is_finished() {
+            let _ = storage::store().delete(COLLECTION, &record.info.id);
+            let _ = fs::remove_file(log_path(&record.info.id));
+        }
+    }
+}
+
+/// Recorded jobs, newest first
+pub fn history(limit: Option<usize>) -> Vec<JobHistoryEntry> {
+    let records = records();
+    let resumed: Vec<&str> = records
+        .iter()
+        .filter_map(|r| r.info.resumed_from.as_deref())
+        .collect();
+    records
+        .iter()
+        .take(limit.unwrap_or(MAX_RECORDS))
+        .map(|record| JobHistoryEntry {
+            resumable: is_resumable(record)
+                && !record.resumed
+                && !resumed.contains(&record.info.id.as_str()),
+            log_path: log_path(&record.info.id).to_string_lossy().to_string(),
+            info: record.info.clone(),
+        })
+        .collect()
+}
+
+/// Jobs still to finish: queued or running ones, and interrupted ones nobody
+/// has resumed yet. Newest first.
+pub fn pending() -> Vec<JobRecord> {
+    let records = records();
+    let resumed: Vec<String> = records
+        .iter()
+        .filter_map(|r| r.info.resumed_from.clone())
+        .collect();
+    records
+        .into_iter()
+        .filter(|r| r.request.is_some() && (!r.info.status.is_finished() || is_resumable(r)))
+        .filter(|r| !r.resumed && !resumed.contains(&r.info.id))
+        .collect()
+}
+
+fn is_resumable(record: &JobRecord) -> bool {
+    record.request.is_some() && record.info.status == JobStatus::Interrupted
+}
+
+/// Mark an interrupted job resumed, refusing one that already is
+fn claim(id: &str) -> Result<(JobRecord, JobRequest), LauncherError> {
+    let _history = HISTORY.lock().unwrap_or_else(|e| e.into_inner());
+    let mut record = load(id)?;
+    let Some(request) = record.request.clone() else {
+        return Err(
+            LauncherError::invalid(format!("{} can't be resumed", record.info.label))
+                .with_hint("Only installs, builds, uploads and scans can"),
+        );
+    };
+    if !is_resumable(&record) {
+        return Err(LauncherError::new(
+            ErrorKind::Conflict,
+            format!("{} wasn't interrupted", record.info.label),
+        )
+        .with_hint("Only a job the launcher quit during can be resumed"));
+    }
+    let resumed = record.resumed
+        || records()
+            .iter()
+            .any(|r| r.info.resumed_from.as_deref() == Some(id));
+    if resumed {
+        return Err(LauncherError::new(
+            ErrorKind::Conflict,
+            format!("{} has already been resumed", record.info.label),
+        )
+        .with_hint("The run that resumed it is in the job history"));
+    }
+    record.resumed = true;
+    write(&record)?;
+    Ok((record, request))
+}
+
+/// Queue an interrupted job again, continuing from its checkpoint
+pub fn resume(app: &AppHandle, state: &SharedState, id: &str) -> Result<JobInfo, LauncherError> {
+    let (mut record, request) = claim(id)?;
+    let queued = jobs::start(app, state, request, Some(&record));
+    if queued.is_err() {
+        // Nothing was queued, so it can be resumed again
+        record.resumed = false;
+        save(&record);
+    }
+    queued
+}
+
+/// Jobs of this and earlier launcher runs, newest first, with their logs
+#[tauri::command]
+#[specta::specta]
+pub async fn get_job_history(limit: Option<usize>) -> Result<Vec<JobHistoryEntry>, LauncherError> {
+    crate::commands::blocking("Job history", move || Ok(history(limit))).await
+}
+
+/// Run an interrupted job from the history again
+#[tauri::command]
+#[specta::specta]
+pub async fn resume_job(
+    app: AppHandle,
+    state: tauri::State<'_, SharedState>,
+    id: String,
+) -> Result<JobInfo, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Resume job", move || resume(&app, &state, &id)).await
+}
@@ -0,0 +1,642 @@
+//! Background jobs with progress and cancellation
+//!
+//! Long-running work (builds, uploads, installs, scans) runs as a job on a
+//! small pool of worker threads. Every job reports through the same
+//! `job-progress` event and stays listed for `get_job_status` after it ends.
+//! Cancellation is cooperative: jobs check their flag between steps, and
+//! batchmode builds kill Unity when it is set. Every job is also recorded in
+//! `job_history`, which outlasts the launcher.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Condvar, Mutex, Once};
+use std::thread;
+use tauri::{AppHandle, Emitter};
+
+use crate::error::{ErrorKind, LauncherError};
+use crate::job_history::{self, JobRecord};
+use crate::state::SharedState;
+use crate::{bundles, health, sanitize, upload};
+
+/// Jobs that may run at once; more wait in the queue
+const MAX_CONCURRENT_JOBS: usize = 2;
+
+/// Finished jobs kept for `list_jobs` / `get_job_status`
+const MAX_FINISHED_JOBS: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+    /// The launcher quit while the job was queued or running; only in the
+    /// history
+    Interrupted,
+}
+
+impl JobStatus {
+    pub fn is_finished(self) -> bool {
+        !matches!(self, JobStatus::Queued | JobStatus::Running)
+    }
+}
+
+/// Payload of `job-progress`, and what `get_job_status` returns
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct JobInfo {
+    pub id: String,
+    pub kind: String,
+    pub label: String,
+    pub status: JobStatus,
+    /// 0.0 - 1.0 when the job can tell
+    pub progress: Option<f64>,
+    pub message: Option<String>,
+    /// Unix ms
+    pub created_at: i64,
+    pub started_at: Option<i64>,
+    pub finished_at: Option<i64>,
+    pub result: Option<serde_json::Value>,
+    pub error: Option<LauncherError>,
+    /// The interrupted or failed job this one resumes
+    #[serde(default)]
+    pub resumed_from: Option<String>,
+}
+
+/// Work that can be queued through `enqueue_job`
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum JobRequest {
+    InstallExtension {
+        unity_project_path: String,
+        mcp_root: String,
+        elevate: Option<bool>,
+    },
+    ScanProject {
+        unity_project_path: String,
+    },
+    FindProjects {
+        root: String,
+    },
+    BuildBundle {
+        channel_id: String,
+        platform: String,
+    },
+    BuildAllPlatforms {
+        channel_id: String,
+    },
+    UploadBundle {
+        channel_id: String,
+        platform: String,
+    },
+    CheckAllChannels {
+        validate: Option<bool>,
+    },
+}
+
+type Work = Box<dyn FnOnce(&JobContext) -> Result<serde_json::Value, LauncherError> + Send>;
+
+struct QueuedJob {
+    id: String,
+    app: AppHandle,
+    work: Work,
+}
+
+/// What the history keeps of an unfinished job besides its info
+#[derive(Default)]
+struct Resume {
+    /// The request that started it, which makes it resumable
+    request: Option<JobRequest>,
+    checkpoint: Option<serde_json::Value>,
+}
+
+#[derive(Default)]
+struct Jobs {
+    /// Newest last
+    infos: Vec<JobInfo>,
+    cancel_flags: Vec<(String, Arc<AtomicBool>)>,
+    pending: VecDeque<QueuedJob>,
+    resume: BTreeMap<String, Resume>,
+}
+
+struct Shared {
+    jobs: Mutex<Jobs>,
+    available: Condvar,
+}
+
+impl Shared {
+    fn lock(&self) -> std::sync::MutexGuard<'_, Jobs> {
+        self.jobs.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Apply a change to a job's info and emit the result
+    fn update(&self, app: &AppHandle, id: &str, change: impl FnOnce(&mut JobInfo)) {
+        let info = {
+            let mut jobs = self.lock();
+            let Some(info) = jobs.infos.iter_mut().find(|j| j.id == id) else {
+                return;
+            };
+            change(info);
+            info.clone()
+        };
+        let _ = app.emit("job-progress", info);
+    }
+
+    /// Write the job's history record: on every status change and checkpoint
+    fn save(&self, id: &str) {
+        let mut jobs = self.lock();
+        let Some(info) = jobs.infos.iter().find(|j| j.id == id).cloned() else {
+            return;
+        };
+        let finished = info.status.is_finished();
+        let resume = jobs.resume.get(id);
+        let record = JobRecord {
+            request: resume.and_then(|r| r.request.clone()),
+            checkpoint: resume.and_then(|r| r.checkpoint.clone()),
+            pid: std::process::id(),
+            resumed: false,
+            info,
+        };
+        if finished {
+            jobs.resume.remove(id);
+        }
+        drop(jobs);
+        job_history::save(&record);
+        if finished {
+            let outcome = match &record.info.error {
+                Some(e) => format!("{:?}: {}", record.info.status, e.message),
+                None => format!("{:?}", record.info.status),
+            };
+            job_history::log(id, record.info.created_at, &outcome);
+        }
+    }
+}
+
+/// Handle a running job uses to report progress and check for cancellation
+pub struct JobContext {
+    id: String,
+    app: AppHandle,
+    cancel: Arc<AtomicBool>,
+    shared: Arc<Shared>,
+}
+
+impl JobContext {
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+
+    /// `Err(Cancelled)` once cancellation was requested
+    pub fn check_cancelled(&self) -> Result<(), LauncherError> {
+        if self.is_cancelled() {
+            return Err(LauncherError::new(
+                ErrorKind::Cancelled,
+                "Job was cancelled",
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn progress(&self, fraction: Option<f64>, message: impl Into<String>) {
+        let message = message.into();
+        let mut new_message = None;
+        self.shared.update(&self.app, &self.id, |info| {
+            if fraction.is_some() {
+                info.progress = fraction.map(|f| f.clamp(0.0, 1.0));
+            }
+            if info.message.as_deref() != Some(message.as_str()) {
+                new_message = Some((info.created_at, message.clone()));
+            }
+            info.message = Some(message);
+        });
+        if let Some((created_at, message)) = new_message {
+            job_history::log(&self.id, created_at, &message);
+        }
+    }
+
+    /// Progress saved by the run this job resumes, or by this run so far
+    pub fn checkpoint(&self) -> Option<serde_json::Value> {
+        let jobs = self.shared.lock();
+        jobs.resume.get(&self.id)?.checkpoint.clone()
+    }
+
+    /// Save how far the job got, so a run resuming it after a crash or restart
+    /// can skip what is done
+    pub fn save_checkpoint(&self, checkpoint: impl Serialize) {
+        let Ok(value) = serde_json::to_value(checkpoint) else {
+            return;
+        };
+        self.shared
+            .lock()
+            .resume
+            .entry(self.id.clone())
+            .or_default()
+            .checkpoint = Some(value);
+        self.shared.save(&self.id);
+    }
+}
+
+fn worker(shared: Arc<Shared>) {
+    loop {
+        let (job, cancel) = {
+            let mut jobs = shared.lock();
+            let job = loop {
+                match jobs.pending.pop_front() {
+                    Some(job) => break job,
+                    None => {
+                        jobs = shared
+                            .available
+                            .wait(jobs)
+                            .unwrap_or_else(|e| e.into_inner())
+                    }
+                }
+            };
+            let cancel = jobs
+                .cancel_flags
+                .iter()
+                .find(|(id, _)| *id == job.id)
+                .map(|(_, flag)| flag.clone())
+                .unwrap_or_default();
+            (job, cancel)
+        };
+
+        let QueuedJob { id, app, work } = job;
+        shared.update(&app, &id, |info| {
+            info.status = JobStatus::Running;
+            info.started_at = Some(crate::bridge::now_ms());
+        });
+        shared.save(&id);
+
+        let context = JobContext {
+            id: id.clone(),
+            app: app.clone(),
+            cancel: cancel.clone(),
+            shared: shared.clone(),
+        };
+        let outcome = work(&context);
+
+        shared.update(&app, &id, |info| {
+            info.finished_at = Some(crate::bridge::now_ms());
+            match outcome {
+                Ok(result) => {
+                    info.status = JobStatus::Succeeded;
+                    info.progress = Some(1.0);
+                    info.result = Some(result);
+                }
+                Err(e) if e.kind == ErrorKind::Cancelled || cancel.load(Ordering::Relaxed) => {
+                    info.status = JobStatus::Cancelled;
+                    info.error = Some(e);
+                }
+                Err(e) => {
+                    info.status = JobStatus::Failed;
+                    info.error = Some(e);
+                }
+            }
+        });
+        shared.save(&id);
+
+        let mut jobs = shared.lock();
+        jobs.cancel_flags.retain(|(job_id, _)| *job_id != id);
+        prune(&mut jobs);
+    }
+}
+
+/// Drop the oldest finished jobs beyond the history limit
+fn prune(jobs: &mut Jobs) {
+    let finished = jobs.infos.iter().filter(|j| j.status.is_finished()).count();
+    let mut excess = finished.saturating_sub(MAX_FINISHED_JOBS);
+    jobs.infos.retain(|j| {
+        if excess > 0 && j.status.is_finished() {
+            excess -= 1;
+            return false;
+        }
+        true
+    });
+}
+
+/// The job queue, held in `AppState`. Workers start with the first job.
+pub struct JobQueue {
+    shared: Arc<Shared>,
+    workers: Once,
+}
+
+impl Default for JobQueue {
+    fn default() -> Self {
+        JobQueue {
+            shared: Arc::new(Shared {
+                jobs: Mutex::new(Jobs::default()),
+                available: Condvar::new(),
+            }),
+            workers: Once::new(),
+        }
+    }
+}
+
+impl JobQueue {
+    /// Queue work and return its (queued) info straight away. Work started
+    /// from a `request` can be resumed from the history if it is interrupted;
+    /// `resume` is the record of the run it resumes.
+    pub fn enqueue(
+        &self,
+        app: &AppHandle,
+        kind: &str,
+        label: impl Into<String>,
+        request: Option<JobRequest>,
+        resume: Option<&JobRecord>,
+        work: impl FnOnce(&JobContext) -> Result<serde_json::Value, LauncherError> + Send + 'static,
+    ) -> JobInfo {
+        let info = JobInfo {
+            id: uuid::Uuid::new_v4().to_string(),
+            kind: kind.to_string(),
+            label: label.into(),
+            status: JobStatus::Queued,
+            progress: None,
+            message: None,
+            created_at: crate::bridge::now_ms(),
+            started_at: None,
+            finished_at: None,
+            result: None,
+            error: None,
+            resumed_from: resume.map(|r| r.info.id.clone()),
+        };
+
+        self.workers.call_once(|| {
+            for _ in 0..MAX_CONCURRENT_JOBS {
+                let shared = self.shared.clone();
+                thread::spawn(move || worker(shared));
+            }
+        });
+
+        let mut jobs = self.shared.lock();
+        jobs.infos.push(info.clone());
+        jobs.cancel_flags.push((info.id.clone(), Arc::default()));
+        jobs.pending.push_back(QueuedJob {
+            id: info.id.clone(),
+            app: app.clone(),
+            work: Box::new(work),
+        });
+        jobs.resume.insert(
+            info.id.clone(),
+            Resume {
+                request,
+                checkpoint: resume.and_then(|r| r.checkpoint.clone()),
+            },
+        );
+        drop(jobs);
+
+        self.shared.save(&info.id);
+        self.shared.available.notify_one();
+        let _ = app.emit("job-progress", info.clone());
+        info
+    }
+
+    /// Run work as a job and wait for its result, so direct commands are
+    /// tracked and cancellable like queued ones. Call from blocking context.
+    pub fn run<T: Serialize + Send + 'static>(
+        &self,
+        app: &AppHandle,
+        kind: &str,
+        label: impl Into<String>,
+        request: Option<JobRequest>,
+        work: impl FnOnce(&JobContext) -> Result<T, LauncherError> + Send + 'static,
+    ) -> Result<T, LauncherError> {
+        let (tx, rx) = mpsc::channel();
+        let info = self.enqueue(app, kind, label, request, None, move |job| {
+            let outcome = work(job);
+            let recorded = match &outcome {
+                Ok(value) => Ok(serde_json::to_value(value).unwrap_or_default()),
+                Err(e) => Err(e.clone()),
+            };
+            let _ = tx.send(outcome);
+            recorded
+        });
+
+        match rx.recv() {
+            Ok(outcome) => outcome,
+            // The job never ran: it was cancelled while still queued
+            Err(_) => Err(LauncherError::new(
+                ErrorKind::Cancelled,
+                format!("{} was cancelled", info.label),
+            )),
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Result<JobInfo, LauncherError> {
+        self.shared
+            .lock()
+            .infos
+            .iter()
+            .find(|j| j.id == id)
+            .cloned()
+            .ok_or_else(|| LauncherError::not_found(format!("Job not found: {}", id)))
+    }
+
+    pub fn list(&self) -> Vec<JobInfo> {
+        self.shared.lock().infos.clone()
+    }
+
+    /// Cancel a job: a queued job is dropped, a running one is asked to stop
+    pub fn cancel(&self, app: &AppHandle, id: &str) -> Result<JobInfo, LauncherError> {
+        let info = self.get(id)?;
+        if info.status.is_finished() {
+            return Ok(info);
+        }
+
+        let mut jobs = self.shared.lock();
+        if let Some((_, flag)) = jobs.cancel_flags.iter().find(|(job_id, _)| job_id == id) {
+            flag.store(true, Ordering::Relaxed);
+        }
+        let was_queued = jobs.pending.iter().any(|j| j.id == id);
+        jobs.pending.retain(|j| j.id != id);
+        if was_queued {
+            jobs.cancel_flags.retain(|(job_id, _)| job_id != id);
+        }
+        drop(jobs);
+
+        if was_queued {
+            self.shared.update(app, id, |info| {
+                info.status = JobStatus::Cancelled;
+                info.finished_at = Some(crate::bridge::now_ms());
+            });
+            self.shared.save(id);
+        }
+        self.get(id)
+    }
+}
+
+fn to_value<T: Serialize>(value: T) -> Result<serde_json::Value, LauncherError> {
+    serde_json::to_value(value)
+        .map_err(|e| LauncherError::internal(format!("Failed to serialize job result: {}", e)))
+}
+
+/// Queue the work of a request; `resume` is the history record of the run it
+/// resumes
+pub fn start(
+    app: &AppHandle,
+    state: &SharedState,
+    request: JobRequest,
+    resume: Option<&JobRecord>,
+) -> Result<JobInfo, LauncherError> {
+    let jobs = &state.jobs;
+    let saved = Some(request.clone());
+
+    let info = match request {
+        JobRequest::InstallExtension {
+            unity_project_path,
+            mcp_root,
+            elevate,
+        } => jobs.enqueue(
+            app,
+            "install_extension",
+            "Install Unity extension",
+            saved,
+            resume,
+            move |_| {
+                to_value(crate::install_unity_extension(
+                    unity_project_path,
+                    mcp_root,
+                    None,
+                    elevate,
+                )?)
+            },
+        ),
+        JobRequest::ScanProject { unity_project_path } => {
+            let project = sanitize::path("Unity project path", &unity_project_path)?;
+            let scans = state.clone();
+            jobs.enqueue(
+                app,
+                "scan_project",
+                "Scan Unity project",
+                saved,
+                resume,
+                move |_| to_value(scans.scans.project(&project, true)),
+            )
+        }
+        JobRequest::FindProjects { root } => {
+            let root = sanitize::path("Search folder", &root)?;
+            let scans = state.clone();
+            jobs.enqueue(
+                app,
+                "find_projects",
+                "Find Unity projects",
+                saved,
+                resume,
+                move |_| to_value(scans.scans.find_projects(&root, true)),
+            )
+        }
+        JobRequest::BuildBundle {
+            channel_id,
+            platform,
+        } => {
+            sanitize::platform(&platform)?;
+            let config = state.config();
+            let channel = crate::find_channel(&config, &channel_id)?;
+            let label = format!("Build {} bundle for {}", platform, channel.name);
+            let app_handle = app.clone();
+            jobs.enqueue(app, "build_bundle", label, saved, resume, move |job| {
+                to_value(bundles::build_bundle(
+                    &app_handle,
+                    job,
+                    &config,
+                    &channel,
+                    &platform,
+                )?)
+            })
+        }
+        JobRequest::BuildAllPlatforms { channel_id } => {
+            let config = state.config();
+            let channel = crate::find_channel(&config, &channel_id)?;
+            let label = format!("Build all bundles for {}", channel.name);
+            let app_handle = app.clone();
+            jobs.enqueue(
+                app,
+                "build_all_platforms",
+                label,
+                saved,
+                resume,
+                move |job| to_value(bundles::build_all(&app_handle, job, &config, &channel)),
+            )
+        }
+        JobRequest::UploadBundle {
+            channel_id,
+            platform,
+        } => {
+            sanitize::platform(&platform)?;
+            let config = state.config();
+            let channel = crate::find_channel(&config, &channel_id)?;
+            let label = format!("Upload {} bundle for {}", platform, channel.name);
+            let app_handle = app.clone();
+            let upload_state = state.clone();
+            jobs.enqueue(app, "upload_bundle", label, saved, resume, move |job| {
+                to_value(upload::upload_bundle(
+                    &app_handle,
+                    job,
+                    &upload_state,
+                    &config,
+                    &channel,
+                    &platform,
+                )?)
+            })
+        }
+        JobRequest::CheckAllChannels { validate } => {
+            let config = state.config();
+            let app_handle = app.clone();
+            let checks = state.clone();
+            jobs.enqueue(
+                app,
+                "check_all_channels",
+                "Check all channels",
+                saved,
+                resume,
+                move |_| {
+                    to_value(health::check_all(
+                        &app_handle,
+                        &config,
+                        &checks.guids,
+                        validate.unwrap_or(false),
+                    ))
+                },
+            )
+        }
+    };
+    Ok(info)
+}
+
+/// Queue a job (install, scan, build, upload, checks); returns immediately
+#[tauri::command]
+#[specta::specta]
+pub async fn enqueue_job(
+    app: AppHandle,
+    state: tauri::State<'_, SharedState>,
+    request: JobRequest,
+) -> Result<JobInfo, LauncherError> {
+    start(&app, state.inner(), request, None)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_job_status(
+    state: tauri::State<'_, SharedState>,
+    id: String,
+) -> Result<JobInfo, LauncherError> {
+    state.jobs.get(&id)
+}
+
+/// Queued, running and recently finished jobs, oldest first
+#[tauri::command]
+#[specta::specta]
+pub fn list_jobs(state: tauri::State<'_, SharedState>) -> Vec<JobInfo> {
+    state.jobs.list()
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_job(
+    app: AppHandle,
+    state: tauri::State<'_, SharedState>,
+    id: String,
+) -> Result<JobInfo, LauncherError> {
+    state.jobs.cancel(&app, &id)
+}
@@ -0,0 +1,124 @@
+//! Operations a crash cut short; the journaling is `launcher_core::journal`
+//!
+//! Activations, client config syncs and extension upgrades run under a
+//! journal entry. One left by a launcher process that is gone was
+//! interrupted: the window asks about those when it opens.
+//! `rollback_operation` puts the files back the way they were before it
+//! started, `resume_operation` runs it again from the start, and
+//! `discard_operation` leaves the files as they are. Entries of a launcher
+//! still running are its operations in flight and are left alone.
+
+pub use launcher_core::journal::*;
+
+use tauri::AppHandle;
+
+use crate::error::{ErrorKind, LauncherError};
+use crate::plan::FilePlan;
+use crate::state::SharedState;
+use crate::{editor_monitor, fs_scope};
+
+fn is_interrupted(entry: &JournalEntry) -> bool {
+    entry.pid != std::process::id() && !editor_monitor::process_alive(entry.pid)
+}
+
+/// Entries whose launcher is gone, oldest first
+pub fn interrupted() -> Vec<JournalEntry> {
+    incomplete().into_iter().filter(is_interrupted).collect()
+}
+
+fn interrupted_entry(id: &str) -> Result<JournalEntry, LauncherError> {
+    let entry = load(id)?;
+    if !is_interrupted(&entry) {
+        return Err(LauncherError::new(
+            ErrorKind::Conflict,
+            format!("Operation {} is still running", id),
+        )
+        .with_hint("Wait for it to finish"));
+    }
+    Ok(entry)
+}
+
+/// Undo an interrupted operation, then pick up the config it restored
+pub fn roll_back(
+    app: &AppHandle,
+    state: &SharedState,
+    id: &str,
+) -> Result<FilePlan, LauncherError> {
+    interrupted_entry(id)?;
+    let plan = rollback(id)?;
+    let config = state.reload()?;
+    fs_scope::sync(app, &config);
+    Ok(plan)
+}
+
+/// Run an interrupted operation again; its entry goes once it succeeds
+pub fn resume(app: &AppHandle, state: &SharedState, id: &str) -> Result<(), LauncherError> {
+    let entry = interrupted_entry(id)?;
+    match entry.operation {
+        Operation::Activation { channel_id } => {
+            crate::activate_channel(state, &channel_id, false, false)?;
+        }
+        Operation::ClientConfigSync {
+            channel_id,
+            mcp_server_path,
+        } => {
+            crate::update_claude_mcp_config(
+                state,
+                state.channel(&channel_id)?,
+                mcp_server_path,
+                None,
+            )?;
+        }
+        Operation::ExtensionUpgrade {
+            unity_project_path,
+            mcp_root,
+        } => {
+            crate::install_unity_extension(unity_project_path, mcp_root, None, None)?;
+        }
+    }
+    discard(id)?;
+    fs_scope::sync(app, &state.config());
+    Ok(())
+}
+
+/// Operations a crash or power loss interrupted
+#[tauri::command]
+#[specta::specta]
+pub async fn get_incomplete_operations() -> Result<Vec<JournalEntry>, LauncherError> {
+    crate::commands::blocking("Incomplete operations", || Ok(interrupted())).await
+}
+
+/// Put back every file an interrupted operation changed
+#[tauri::command]
+#[specta::specta]
+pub async fn rollback_operation(
+    app: AppHandle,
+    state: tauri::State<'_, SharedState>,
+    id: String,
+) -> Result<FilePlan, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Roll back operation", move || roll_back(&app, &state, &id)).await
+}
+
+/// Run an interrupted operation again
+#[tauri::command]
+#[specta::specta]
+pub async fn resume_operation(
+    app: AppHandle,
+    state: tauri::State<'_, SharedState>,
+    id: String,
+) -> Result<(), LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Resume operation", move || resume(&app, &state, &id)).await
+}
+
+/// Forget an interrupted operation and keep its files as they are
+#[tauri::command]
+#[specta::specta]
+pub async fn discard_operation(id: String) -> Result<(), LauncherError> {
+    crate::commands::blocking("Discard operation", move || {
+        interrupted_entry(&id)?;
+        discard(&id)
+    })
+    .await
+}
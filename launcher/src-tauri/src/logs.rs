@@ -0,0 +1,279 @@
+//! Chunked reads of logs and large text files
+//!
+//! Viewers page through files with `read_file_chunk` (byte offset + limit) or
+//! start from the end with `read_file_tail`, so a multi-hundred-megabyte
+//! Editor.log or scene never has to be loaded whole.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use crate::batchmode;
+use crate::error::LauncherError;
+use crate::sanitize;
+use crate::state::SharedState;
+
+/// Env var naming the log file the server mirrors its stderr into
+pub const LOG_FILE_VAR: &str = "BANTER_MCP_LOG_FILE";
+
+const DEFAULT_CHUNK_BYTES: u64 = 64 * 1024;
+const MAX_CHUNK_BYTES: u64 = 4 * 1024 * 1024;
+const DEFAULT_TAIL_LINES: usize = 200;
+
+/// Blocks read backwards when looking for the start of the tail
+const TAIL_BLOCK_BYTES: u64 = 64 * 1024;
+
+/// File a read refers to
+#[derive(Debug, Clone, Deserialize, Type)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ReadTarget {
+    /// Unity's Editor.log for the current user
+    EditorLog,
+    /// Stderr of the channel's MCP server
+    ServerLog { channel_id: String },
+    /// Output of the last batchmode bundle build
+    BuildLog {
+        channel_id: String,
+        platform: String,
+    },
+    /// Output of the last batchmode run of another operation
+    /// (`batchmode::OPERATIONS`)
+    BatchLog {
+        channel_id: String,
+        operation: String,
+    },
+    /// A `.unity` file in the channel's project; defaults to the channel scene
+    Scene {
+        channel_id: String,
+        path: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct FileChunk {
+    pub path: String,
+    /// Byte offset of `text` in the file
+    pub offset: u64,
+    /// Offset to pass to read the following chunk
+    pub next_offset: u64,
+    /// File size at the time of the read
+    pub size: u64,
+    pub eof: bool,
+    pub text: String,
+}
+
+fn logs_dir() -> PathBuf {
+    crate::launcher_dir().join("logs")
+}
+
+pub fn server_log_path(channel_id: &str) -> PathBuf {
+    logs_dir().join(format!("server-{}.log", channel_id))
+}
+
+/// Supervisor log of `--watch` mode
+pub fn watch_log_path() -> PathBuf {
+    logs_dir().join("watch.log")
+}
+
+/// Output of global hook scripts
+pub fn hooks_log_path() -> PathBuf {
+    logs_dir().join("hooks.log")
+}
+
+pub fn build_log_path(channel_id: &str, platform: &str) -> PathBuf {
+    logs_dir().join(format!("build-{}-{}.log", channel_id, platform))
+}
+
+pub fn batch_log_path(channel_id: &str, operation: &str) -> PathBuf {
+    logs_dir().join(format!("batch-{}-{}.log", channel_id, operation))
+}
+
+/// Where Unity writes Editor.log on this platform
+pub fn editor_log_path() -> Option<PathBuf> {
+    if cfg!(target_os = "windows") {
+        dirs::data_local_dir().map(|d| d.join("Unity").join("Editor").join("Editor.log"))
+    } else if cfg!(target_os = "macos") {
+        dirs::home_dir().map(|h| h.join("Library/Logs/Unity/Editor.log"))
+    } else {
+        dirs::config_dir().map(|d| d.join("unity3d").join("Editor.log"))
+    }
+}
+
+fn resolve(state: &SharedState, target: &ReadTarget) -> Result<PathBuf, LauncherError> {
+    match target {
+        ReadTarget::EditorLog => editor_log_path()
+            .ok_or_else(|| LauncherError::not_found("Could not locate Unity's Editor.log")),
+        ReadTarget::ServerLog { channel_id } => {
+            state.channel(channel_id)?;
+            Ok(server_log_path(channel_id))
+        }
+        ReadTarget::BuildLog {
+            channel_id,
+            platform,
+        } => {
+            sanitize::platform(platform)?;
+            state.channel(channel_id)?;
+            Ok(build_log_path(channel_id, platform))
+        }
+        ReadTarget::BatchLog {
+            channel_id,
+            operation,
+        } => {
+            if !batchmode::OPERATIONS.contains(&operation.as_str()) {
+                return Err(LauncherError::invalid(format!(
+                    "Unknown batchmode operation: {}",
+                    operation
+                )));
+            }
+            state.channel(channel_id)?;
+            Ok(batch_log_path(channel_id, operation))
+        }
+        ReadTarget::Scene { channel_id, path } => {
+            let channel = state.channel(channel_id)?;
+            let scene = path
+                .as_ref()
+                .or(channel.scene_path.as_ref())
+                .ok_or_else(|| LauncherError::not_found("Channel has no scene"))?;
+            let project = Path::new(&channel.unity_project_path);
+            Ok(sanitize::path_within("Scene path", scene, project)?)
+        }
+    }
+}
+
+fn open(path: &Path) -> Result<(File, u64), LauncherError> {
+    let file = File::open(path).map_err(|e| LauncherError::io("Failed to open", path, e))?;
+    let size = file
+        .metadata()
+        .map_err(|e| LauncherError::io("Failed to read", path, e))?
+        .len();
+    Ok((file, size))
+}
+
+/// Read up to `limit` bytes from `offset`. Unless the chunk reaches the end of
+/// the file it is cut after its last newline, so lines are never split.
+pub fn read_chunk(path: &Path, offset: u64, limit: u64) -> Result<FileChunk, LauncherError> {
+    let (mut file, size) = open(path)?;
+    let offset = offset.min(size);
+    let limit = limit.clamp(1, MAX_CHUNK_BYTES);
+
+    file.seek(SeekFrom::Start(offset))
+        .map_err(|e| LauncherError::io("Failed to read", path, e))?;
+    let mut buf = Vec::new();
+    file.take(limit)
+        .read_to_end(&mut buf)
+        .map_err(|e| LauncherError::io("Failed to read", path, e))?;
+
+    let mut eof = offset + buf.len() as u64 >= size;
+    if !eof {
+        if let Some(newline) = buf.iter().rposition(|b| *b == b'\n') {
+            buf.truncate(newline + 1);
+        }
+    }
+    let next_offset = offset + buf.len() as u64;
+    eof |= next_offset >= size;
+
+    Ok(FileChunk {
+        path: path.to_string_lossy().to_string(),
+        offset,
+        next_offset,
+        size,
+        eof,
+        text: String::from_utf8_lossy(&buf).to_string(),
+    })
+}
+
+/// The last `lines` lines of a file, reading at most `max_bytes`
+pub fn read_tail(path: &Path, lines: usize, max_bytes: u64) -> Result<FileChunk, LauncherError> {
+    let (mut file, size) = open(path)?;
+    let max_bytes = max_bytes.clamp(1, MAX_CHUNK_BYTES);
+    let lines = lines.max(1);
+
+    // Read blocks backwards until enough newlines (or bytes) are in hand
+    let mut start = size;
+    let mut buf = Vec::new();
+    while start > 0 && (buf.len() as u64) < max_bytes {
+        let newlines = buf.iter().filter(|b| **b == b'\n').count();
+        if newlines > lines {
+            break;
+        }
+        let step = TAIL_BLOCK_BYTES
+            .min(start)
+            .min(max_bytes - buf.len() as u64);
+        start -= step;
+
+        let mut block = vec![0; step as usize];
+        file.seek(SeekFrom::Start(start))
+            .and_then(|_| file.read_exact(&mut block))
+            .map_err(|e| LauncherError::io("Failed to read", path, e))?;
+        block.extend_from_slice(&buf);
+        buf = block;
+    }
+
+    // Count back from the last line, ignoring a trailing newline
+    let body_end = buf.len() - usize::from(buf.ends_with(b"\n"));
+    let cut = match buf[..body_end]
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, b)| **b == b'\n')
+        .nth(lines - 1)
+    {
+        Some((newline, _)) => newline + 1,
+        // Not enough lines: everything, unless the read stopped mid-line
+        None if start > 0 => buf.iter().position(|b| *b == b'\n').map_or(0, |n| n + 1),
+        None => 0,
+    };
+
+    Ok(FileChunk {
+        path: path.to_string_lossy().to_string(),
+        offset: start + cut as u64,
+        next_offset: size,
+        size,
+        eof: true,
+        text: String::from_utf8_lossy(&buf[cut..]).to_string(),
+    })
+}
+
+/// Read part of a log or scene file from a byte offset
+#[tauri::command]
+#[specta::specta]
+pub async fn read_file_chunk(
+    state: tauri::State<'_, SharedState>,
+    target: ReadTarget,
+    offset: Option<u64>,
+    limit: Option<u64>,
+) -> Result<FileChunk, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Read file", move || {
+        let path = resolve(&state, &target)?;
+        read_chunk(
+            &path,
+            offset.unwrap_or(0),
+            limit.unwrap_or(DEFAULT_CHUNK_BYTES),
+        )
+    })
+    .await
+}
+
+/// Read the end of a log or scene file
+#[tauri::command]
+#[specta::specta]
+pub async fn read_file_tail(
+    state: tauri::State<'_, SharedState>,
+    target: ReadTarget,
+    lines: Option<usize>,
+    max_bytes: Option<u64>,
+) -> Result<FileChunk, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Read file", move || {
+        let path = resolve(&state, &target)?;
+        read_tail(
+            &path,
+            lines.unwrap_or(DEFAULT_TAIL_LINES),
+            max_bytes.unwrap_or(MAX_CHUNK_BYTES),
+        )
+    })
+    .await
+}
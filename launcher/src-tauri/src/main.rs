@@ -1,307 +1,659 @@
 // Prevents additional console window on Windows in release
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use serde::{Deserialize, Serialize};
-use std::fs;
-use std::path::PathBuf;
-
-/// A scene channel configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct ProjectChannel {
-    id: String,
-    name: String,
-    unity_project_path: String,
-    scene_path: Option<String>,
-    enabled: bool,
-}
-
-/// Full launcher configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-struct LauncherConfig {
-    channels: Vec<ProjectChannel>,
-    active_channel_id: Option<String>,
-    mcp_server_path: String,
-    auto_start: bool,
-    #[serde(default)]
-    enable_custom_scripts: bool,
+mod account;
+mod assets;
+mod backup;
+mod banter_sdk;
+mod batch;
+mod batchmode;
+mod bindings;
+mod bridge_queue;
+mod bundles;
+mod capabilities;
+mod channel_export;
+mod channels_file;
+mod claude_import;
+mod cli;
+mod commands;
+mod config_check;
+mod context_file;
+mod control_api;
+mod dashboard;
+mod editor_monitor;
+mod elevation;
+mod env_bootstrap;
+mod features;
+mod focus;
+mod fs_scope;
+mod git;
+mod guids;
+mod handoff;
+mod health;
+mod hooks;
+mod http;
+mod i18n;
+mod icons;
+mod instances;
+mod job_history;
+mod jobs;
+mod journal;
+mod logs;
+mod mcp_client;
+mod mcp_proxy;
+mod metrics;
+mod mock_project;
+mod net;
+mod observer;
+mod preview_server;
+mod project_cache;
+mod recorder;
+mod scan;
+mod scene_diff;
+mod scheduler;
+mod secrets;
+mod server_pool;
+mod sessions;
+mod setup_report;
+mod simulation;
+mod snapshots;
+mod snippets;
+mod stack;
+mod startup;
+mod state;
+mod status_server;
+mod support;
+mod telemetry;
+mod triggers;
+mod uninstall;
+mod unity;
+mod unity_yaml;
+mod upload;
+mod validation;
+mod visit;
+mod watch;
+mod write_guard;
+
+use error::LauncherError;
+use launcher_core::channels::{self, add_channel, project_root_for_scene, validate_unity_scene};
+use launcher_core::client_config::{
+    self, get_claude_config_path, get_claude_mcp_config, plan_claude_mcp_removal,
+};
+use launcher_core::config::{
+    default_config, find_channel, launcher_dir, plan_config_write, read_config, LauncherConfig,
+    ProjectChannel,
+};
+use launcher_core::extension::{
+    self, check_unity_extension, install_unity_extension, set_unity_custom_scripts,
+};
+use launcher_core::{
+    bridge, error, faults, path_index, plan, portable, preflight, quotas, sanitize, storage, user,
+};
+use plan::FilePlan;
+use state::{AppState, SharedState};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Replace the configuration and refresh the webview fs scope. The write to
+/// disk is debounced; see `AppState::flush`.
+fn save_config(
+    app: tauri::AppHandle,
+    state: &AppState,
+    config: LauncherConfig,
+) -> Result<(), LauncherError> {
+    sanitize::config(&config)?;
+    let previous = state.config();
+    if config.active_channel_id != previous.active_channel_id {
+        if let Some(id) = config.active_channel_id.as_deref() {
+            git::check_activation(&config, &find_channel(&config, id)?)?;
+        }
+    }
+    let saved = config.clone();
+    state.update_deferred(move |current| {
+        *current = config;
+        Ok(())
+    })?;
+    fs_scope::sync(&app, &saved);
+    hooks::active_channel_changed(&previous, &saved);
+    Ok(())
 }
 
-/// Get the config file path
-fn get_config_path() -> PathBuf {
-    let config_dir = dirs::config_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join("banter-mcp");
+/// Remove a channel. If Claude Code is pointed at its project, the `banter`
+/// entry is removed as well.
+fn remove_channel(
+    app: tauri::AppHandle,
+    state: &AppState,
+    channel_id: String,
+    dry_run: Option<bool>,
+) -> Result<FilePlan, LauncherError> {
+    sanitize::channel_id(&channel_id)?;
+    let previous = state.config();
+    let retention = previous.backup_retention.clone();
+
+    let mut plan = FilePlan::new(dry_run.unwrap_or(false));
+    let channel = state.update(&mut plan, |config| {
+        let channel = find_channel(config, &channel_id)?;
+        config.channels.retain(|c| c.id != channel_id);
+        if config.active_channel_id.as_deref() == Some(channel_id.as_str()) {
+            config.active_channel_id = config.channels.first().map(|c| c.id.clone());
+        }
+        Ok(channel)
+    })?;
+    secrets::remove_channel_secrets(&mut plan, &channel_id)?;
 
-    fs::create_dir_all(&config_dir).ok();
-    config_dir.join("launcher-config.json")
-}
+    let claude_project = client_config::configured_project(&get_claude_mcp_config()?);
+    if claude_project.as_deref() == Some(channel.unity_project_path.as_str()) {
+        plan_claude_mcp_removal(&mut plan, &retention)?;
+    }
 
-/// Load configuration from disk
-#[tauri::command]
-fn load_config() -> Result<LauncherConfig, String> {
-    let config_path = get_config_path();
-
-    if config_path.exists() {
-        let content = fs::read_to_string(&config_path)
-            .map_err(|e| format!("Failed to read config: {}", e))?;
-        serde_json::from_str(&content)
-            .map_err(|e| format!("Failed to parse config: {}", e))
-    } else {
-        Ok(LauncherConfig {
-            channels: vec![],
-            active_channel_id: None,
-            mcp_server_path: "C:/tools/banter-mcp/dist/index.js".to_string(),
-            auto_start: false,
-            enable_custom_scripts: false,
-        })
+    if !plan.dry_run {
+        let config = state.config();
+        fs_scope::sync(&app, &config);
+        hooks::active_channel_changed(&previous, &config);
     }
+    Ok(plan)
 }
 
-/// Save configuration to disk
-#[tauri::command]
-fn save_config(config: LauncherConfig) -> Result<(), String> {
-    let config_path = get_config_path();
-    let content = serde_json::to_string_pretty(&config)
-        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+/// Environment the MCP server runs with for a channel. The channel's secrets
+/// env file and data folder are written as part of `plan`.
+fn mcp_server_env(
+    plan: &mut FilePlan,
+    config: &LauncherConfig,
+    channel: &ProjectChannel,
+) -> Result<serde_json::Value, LauncherError> {
+    let mut env = serde_json::json!({
+        "UNITY_PROJECT_PATH": channel.unity_project_path
+    });
 
-    fs::write(&config_path, content)
-        .map_err(|e| format!("Failed to write config: {}", e))
-}
+    if let Some(scene) = &channel.scene_path {
+        env["UNITY_SCENE_PATH"] = serde_json::json!(scene);
+    }
 
-/// Add a new scene channel
-#[tauri::command]
-fn add_channel(name: String, scene_path: String) -> Result<ProjectChannel, String> {
-    let scene_file = PathBuf::from(&scene_path);
+    env[logs::LOG_FILE_VAR] = serde_json::json!(logs::server_log_path(&channel.id));
+    env[channels::ALLOWED_ROOTS_VAR] =
+        serde_json::json!(channels::allowed_roots_value(channel)?.to_string_lossy());
 
-    if !scene_file.exists() {
-        return Err(format!("Scene file does not exist: {}", scene_path));
-    }
+    let data_dir = channels::server_data_dir(channel);
+    plan.create_dir(&data_dir)?;
+    env[channels::DATA_DIR_VAR] = serde_json::json!(data_dir);
 
-    // Validate it's a .unity file
-    if scene_file.extension().map(|e| e.to_str().unwrap_or("")) != Some("unity") {
-        return Err("Not a valid Unity scene file (must be .unity)".to_string());
+    for (name, value) in quotas::env(channel)? {
+        env[name] = serde_json::json!(value);
     }
 
-    // Extract project path from scene path (go up to find Assets folder)
-    let mut project_path: Option<PathBuf> = None;
-    let mut current = scene_file.parent();
+    if support::active().is_some() {
+        env[support::LOG_LEVEL_VAR] = serde_json::json!("trace");
+    }
 
-    while let Some(dir) = current {
-        if dir.file_name().map(|n| n.to_str().unwrap_or("")) == Some("Assets") {
-            project_path = dir.parent().map(|p| p.to_path_buf());
-            break;
-        }
-        current = dir.parent();
+    // Secrets go to a user-only env file rather than into the client config
+    if let Some(env_file) = secrets::write_env_file(plan, config, channel)? {
+        env[secrets::ENV_FILE_VAR] = serde_json::json!(env_file);
     }
 
-    let unity_project_path = project_path
-        .ok_or("Could not find Unity project root (no Assets folder in path)")?
-        .to_string_lossy()
-        .to_string();
-
-    let channel = ProjectChannel {
-        id: uuid::Uuid::new_v4().to_string(),
-        name,
-        unity_project_path,
-        scene_path: Some(scene_path),
-        enabled: true,
-    };
+    Ok(env)
+}
 
-    Ok(channel)
+/// `mcp_server_env` as plain name/value pairs, for spawning the server or
+/// writing shell snippets
+fn mcp_server_env_vars(
+    plan: &mut FilePlan,
+    config: &LauncherConfig,
+    channel: &ProjectChannel,
+) -> Result<BTreeMap<String, String>, LauncherError> {
+    Ok(env_vars(&mcp_server_env(plan, config, channel)?))
 }
 
-/// Validate a Unity scene file path
-#[tauri::command]
-fn validate_unity_scene(path: String) -> Result<bool, String> {
-    let scene_path = PathBuf::from(&path);
+/// The environment the client entries give a channel's pooled server
+fn pooled_server_env_vars(
+    plan: &mut FilePlan,
+    config: &LauncherConfig,
+    channel: &ProjectChannel,
+) -> Result<BTreeMap<String, String>, LauncherError> {
+    let env = mcp_server_env(plan, config, channel)?;
+    let env = workspace_env(plan, channel, env, server_pool::SHARED_INSTANCE)?;
+    Ok(env_vars(&env))
+}
 
-    if !scene_path.exists() {
-        return Ok(false);
-    }
+fn env_vars(env: &serde_json::Value) -> BTreeMap<String, String> {
+    env.as_object()
+        .into_iter()
+        .flatten()
+        .map(|(name, value)| {
+            let value = value
+                .as_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| value.to_string());
+            (name.clone(), value)
+        })
+        .collect()
+}
 
-    // Check if it's a .unity file
-    if scene_path.extension().map(|e| e.to_str().unwrap_or("")) != Some("unity") {
-        return Ok(false);
+/// `env` for one client's server of a workspace-mode channel: its own data
+/// folder, plus the write lock all of the channel's servers share. Left as it
+/// is when the channel isn't shared.
+fn workspace_env(
+    plan: &mut FilePlan,
+    channel: &ProjectChannel,
+    mut env: serde_json::Value,
+    instance: &str,
+) -> Result<serde_json::Value, LauncherError> {
+    if !channels::is_workspace(channel) {
+        return Ok(env);
     }
-
-    // Check if it's inside an Assets folder (valid Unity project structure)
-    let path_str = path.replace("\\", "/");
-    Ok(path_str.contains("/Assets/"))
+    let data_dir = channels::instance_data_dir(channel, instance);
+    plan.create_dir(&data_dir)?;
+    env[channels::DATA_DIR_VAR] = serde_json::json!(data_dir);
+    env[channels::INSTANCE_VAR] = serde_json::json!(instance);
+    env[channels::WRITE_LOCK_VAR] = serde_json::json!(channels::write_lock_path(channel));
+    Ok(env)
 }
 
-/// Get Claude Code config path
-fn get_claude_config_path() -> PathBuf {
-    dirs::home_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join(".claude.json")
+/// An entry's `command`, `args`, `cwd` and `env` with install paths as
+/// environment references for `client`, when `roots` is set (the
+/// `portable_client_config` setting) and the client expands them
+fn portable_entry(
+    roots: Option<&portable::Roots>,
+    client: &str,
+    command: &str,
+    args: &[String],
+    cwd: &str,
+    mut env: serde_json::Value,
+) -> (String, Vec<String>, String, serde_json::Value) {
+    let Some((roots, syntax)) = roots.zip(portable::Syntax::for_client(client)) else {
+        return (command.to_string(), args.to_vec(), cwd.to_string(), env);
+    };
+    portable::rewrite_json(&mut env, syntax, roots);
+    (
+        portable::rewrite(command, syntax, roots),
+        args.iter()
+            .map(|arg| portable::rewrite(arg, syntax, roots))
+            .collect(),
+        portable::rewrite(cwd, syntax, roots),
+        env,
+    )
 }
 
-/// Read current Claude Code MCP configuration
-#[tauri::command]
-fn get_claude_mcp_config() -> Result<serde_json::Value, String> {
-    let config_path = get_claude_config_path();
-
-    if config_path.exists() {
-        let content = fs::read_to_string(&config_path)
-            .map_err(|e| format!("Failed to read Claude config: {}", e))?;
-        serde_json::from_str(&content)
-            .map_err(|e| format!("Failed to parse Claude config: {}", e))
-    } else {
-        Ok(serde_json::json!({}))
+/// Update Claude Code MCP configuration for a channel, and in workspace mode
+/// the configs of the other clients sharing its project. The files are
+/// written under a journal entry, so a crash halfway can be rolled back.
+fn update_claude_mcp_config(
+    state: &AppState,
+    channel: ProjectChannel,
+    mcp_server_path: String,
+    dry_run: Option<bool>,
+) -> Result<FilePlan, LauncherError> {
+    if dry_run.unwrap_or(false) {
+        return plan_client_configs(state, channel, mcp_server_path, dry_run);
     }
+    let operation = journal::Operation::ClientConfigSync {
+        channel_id: channel.id.clone(),
+        mcp_server_path: mcp_server_path.clone(),
+    };
+    journal::run(operation, || {
+        plan_client_configs(state, channel, mcp_server_path, dry_run)
+    })
 }
 
-/// Update Claude Code MCP configuration for a channel
-#[tauri::command]
-fn update_claude_mcp_config(channel: ProjectChannel, mcp_server_path: String) -> Result<(), String> {
-    let config_path = get_claude_config_path();
-
-    let mut config: serde_json::Value = if config_path.exists() {
-        let content = fs::read_to_string(&config_path)
-            .map_err(|e| format!("Failed to read Claude config: {}", e))?;
-        serde_json::from_str(&content).unwrap_or(serde_json::json!({}))
+fn plan_client_configs(
+    state: &AppState,
+    channel: ProjectChannel,
+    mcp_server_path: String,
+    dry_run: Option<bool>,
+) -> Result<FilePlan, LauncherError> {
+    sanitize::channel(&channel)?;
+    sanitize::path("MCP server path", &mcp_server_path)?;
+
+    let launcher_config = state.config();
+    let mut plan = FilePlan::new(dry_run.unwrap_or(false));
+    let cwd = channels::server_cwd(&channel)?;
+    let env = mcp_server_env(&mut plan, &launcher_config, &channel)?;
+    let pooled = launcher_config.server_pool.enabled;
+    let (command, args) =
+        if launcher_config.record_sessions || pooled || support::active().is_some() {
+            // The recorder and support mode see tool calls through the
+            // launcher's stdio proxy, which also relays to a pooled server
+            let launcher = std::env::current_exe().map_err(|e| {
+                LauncherError::internal(format!("Cannot locate the launcher: {}", e))
+            })?;
+            (
+                launcher.to_string_lossy().to_string(),
+                mcp_proxy::args(&channel.id, &mcp_server_path, pooled),
+            )
+        } else {
+            ("node".to_string(), vec![mcp_server_path.clone()])
+        };
+
+    // A pooled server serves every client, so they share one instance
+    let instance = |client: &str| {
+        if pooled {
+            server_pool::SHARED_INSTANCE.to_string()
+        } else {
+            client.to_string()
+        }
+    };
+    let roots = |workspace: Option<&Path>| {
+        launcher_config
+            .portable_client_config
+            .then(|| env_bootstrap::roots(&mcp_server_path, workspace))
+            .transpose()
+    };
+    let claude_roots = roots(None)?;
+    let claude_env = workspace_env(
+        &mut plan,
+        &channel,
+        env.clone(),
+        &instance(channels::CLAUDE_INSTANCE),
+    )?;
+    let (claude_command, claude_args, claude_cwd, claude_env) = portable_entry(
+        claude_roots.as_ref(),
+        channels::CLAUDE_INSTANCE,
+        &command,
+        &args,
+        &cwd.to_string_lossy(),
+        claude_env,
+    );
+    client_config::plan_claude_mcp_update_with(
+        &mut plan,
+        &get_claude_config_path(),
+        &claude_command,
+        &claude_args,
+        &claude_cwd,
+        claude_env,
+        &launcher_config.backup_retention,
+    )?;
+    let launcher_entry = if launcher_config.serve_launcher_state {
+        let launcher = std::env::current_exe()
+            .map_err(|e| LauncherError::internal(format!("Cannot locate the launcher: {}", e)))?;
+        let launcher = launcher.to_string_lossy().to_string();
+        Some(match &claude_roots {
+            Some(roots) => portable::rewrite(&launcher, portable::Syntax::Claude, roots),
+            None => launcher,
+        })
     } else {
-        serde_json::json!({})
+        None
     };
-
-    if config.get("mcpServers").is_none() {
-        config["mcpServers"] = serde_json::json!({});
-    }
-
-    let mut env = serde_json::json!({
-        "UNITY_PROJECT_PATH": channel.unity_project_path
-    });
-
-    if let Some(scene) = &channel.scene_path {
-        env["UNITY_SCENE_PATH"] = serde_json::json!(scene);
+    let status_args = [status_server::SWITCH.to_string()];
+    client_config::plan_launcher_entry(
+        &mut plan,
+        &get_claude_config_path(),
+        launcher_entry.as_deref().map(|l| (l, &status_args[..])),
+        &launcher_config.backup_retention,
+    )?;
+    // The other clients read the same `mcpServers` layout from the project
+    for client in &channel.workspace_clients {
+        let Some(path) = channels::workspace_client_config(&channel, client) else {
+            continue;
+        };
+        if let Some(dir) = path.parent() {
+            plan.create_dir(dir)?;
+        }
+        let client_env = workspace_env(&mut plan, &channel, env.clone(), &instance(client))?;
+        let client_roots = roots(Some(Path::new(&channel.unity_project_path)))?;
+        let (client_command, client_args, client_cwd, client_env) = portable_entry(
+            client_roots.as_ref(),
+            client,
+            &command,
+            &args,
+            &cwd.to_string_lossy(),
+            client_env,
+        );
+        client_config::plan_claude_mcp_update_with(
+            &mut plan,
+            &path,
+            &client_command,
+            &client_args,
+            &client_cwd,
+            client_env,
+            &launcher_config.backup_retention,
+        )?;
     }
 
-    config["mcpServers"]["banter"] = serde_json::json!({
-        "command": "node",
-        "args": [mcp_server_path],
-        "env": env
-    });
-
-    let content = serde_json::to_string_pretty(&config)
-        .map_err(|e| format!("Failed to serialize Claude config: {}", e))?;
-
-    fs::write(&config_path, content)
-        .map_err(|e| format!("Failed to write Claude config: {}", e))
+    Ok(plan)
 }
 
 /// Remove Banter MCP from Claude config
-#[tauri::command]
-fn remove_claude_mcp_config() -> Result<(), String> {
-    let config_path = get_claude_config_path();
-
-    if !config_path.exists() {
-        return Ok(());
-    }
-
-    let content = fs::read_to_string(&config_path)
-        .map_err(|e| format!("Failed to read Claude config: {}", e))?;
-
-    let mut config: serde_json::Value = serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse Claude config: {}", e))?;
-
-    if let Some(servers) = config.get_mut("mcpServers") {
-        if let Some(obj) = servers.as_object_mut() {
-            obj.remove("banter");
-        }
-    }
-
-    let content = serde_json::to_string_pretty(&config)
-        .map_err(|e| format!("Failed to serialize Claude config: {}", e))?;
-
-    fs::write(&config_path, content)
-        .map_err(|e| format!("Failed to write Claude config: {}", e))
+fn remove_claude_mcp_config(
+    state: &AppState,
+    dry_run: Option<bool>,
+) -> Result<FilePlan, LauncherError> {
+    let mut plan = FilePlan::new(dry_run.unwrap_or(false));
+    let retention = state.config().backup_retention;
+    plan_claude_mcp_removal(&mut plan, &retention)?;
+    client_config::plan_launcher_entry(&mut plan, &get_claude_config_path(), None, &retention)?;
+    Ok(plan)
 }
 
-/// Check if Unity extension is installed in a project
-#[tauri::command]
-fn check_unity_extension(unity_project_path: String) -> Result<bool, String> {
-    let extension_path = PathBuf::from(&unity_project_path)
-        .join("Assets")
-        .join("Editor")
-        .join("BanterMCPBridge.cs");
-
-    Ok(extension_path.exists())
+/// What `activate_channel` did
+struct Activated {
+    /// Claude Code config written, `None` when auto-configure is off
+    client_config: Option<PathBuf>,
+    /// Failed health checks the gate let through
+    health_warnings: Vec<health::HealthCheck>,
 }
 
-/// Install Unity extension to a project
-#[tauri::command]
-fn install_unity_extension(unity_project_path: String, mcp_root: String) -> Result<(), String> {
-    let source = PathBuf::from(&mcp_root)
-        .join("unity-extension")
-        .join("Editor")
-        .join("BanterMCPBridge.cs");
-
-    let dest_dir = PathBuf::from(&unity_project_path)
-        .join("Assets")
-        .join("Editor");
-
-    let dest = dest_dir.join("BanterMCPBridge.cs");
-
-    fs::create_dir_all(&dest_dir)
-        .map_err(|e| format!("Failed to create Editor directory: {}", e))?;
-
-    fs::copy(&source, &dest)
-        .map_err(|e| format!("Failed to copy extension: {}", e))?;
-
-    Ok(())
+/// Make a channel active and, with auto-configure on, point Claude Code at it.
+/// Another launcher using the channel's project is a conflict unless
+/// `take_over` is set, and failed health checks the gate refuses are unless
+/// `ignore_health` is.
+fn activate_channel(
+    state: &AppState,
+    channel_id: &str,
+    take_over: bool,
+    ignore_health: bool,
+) -> Result<Activated, LauncherError> {
+    let channel = state.channel(channel_id)?;
+    sessions::check_activation(&channel, take_over)?;
+    let previous = state.config();
+    git::check_activation(&previous, &channel)?;
+    let health_warnings =
+        health::check_activation(&previous, &channel, &state.guids, ignore_health)?;
+    let operation = journal::Operation::Activation {
+        channel_id: channel.id.clone(),
+    };
+    journal::run(operation, || {
+        let mut plan = FilePlan::new(false);
+        let config = state.update(&mut plan, |config| {
+            config.active_channel_id = Some(channel.id.clone());
+            Ok(config.clone())
+        })?;
+        hooks::active_channel_changed(&previous, &config);
+
+        if !config.auto_start {
+            return Ok(Activated {
+                client_config: None,
+                health_warnings,
+            });
+        }
+        update_claude_mcp_config(state, channel, config.mcp_server_path, None)?;
+        Ok(Activated {
+            client_config: Some(get_claude_config_path()),
+            health_warnings,
+        })
+    })
 }
 
 /// Get the MCP root directory
 #[tauri::command]
-fn get_mcp_root() -> Result<String, String> {
+#[specta::specta]
+fn get_mcp_root() -> Result<String, LauncherError> {
     Ok("C:/tools/banter-mcp".to_string())
 }
 
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(code) = cli::run(&args) {
+        std::process::exit(code);
+    }
+    observer::set_forced(args.iter().any(|a| a == observer::SWITCH));
+
+    startup::begin();
+    let state: SharedState = startup::phase("config", || {
+        Arc::new(AppState::load().unwrap_or_else(|e| {
+            eprintln!("{}; starting with the default config", e);
+            AppState::new(default_config())
+        }))
+    });
+    let config = state.config();
+    let saver_state = state.clone();
+    std::thread::spawn(move || saver_state.run_saver());
+    let run_state = state.clone();
+    let guard_state = state.clone();
+
+    let handlers = command_builder();
+    #[cfg(debug_assertions)]
+    if let Err(e) = bindings::export(&handlers, &bindings::default_path()) {
+        eprintln!("{}", e);
+    }
+
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
-        .invoke_handler(tauri::generate_handler![
-            load_config,
-            save_config,
-            add_channel,
-            validate_unity_scene,
-            get_claude_mcp_config,
-            update_claude_mcp_config,
-            remove_claude_mcp_config,
-            check_unity_extension,
-            install_unity_extension,
-            get_mcp_root,
-            set_unity_custom_scripts,
-        ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .manage(state)
+        .register_uri_scheme_protocol(icons::SCHEME, |_ctx, request| icons::serve(&request))
+        .setup(move |app| {
+            startup::phase("fs_scope", || fs_scope::sync(app.handle(), &config));
+            Ok(())
+        })
+        .invoke_handler(observer::guard(
+            guard_state,
+            stack::guard(handlers.invoke_handler()),
+        ))
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(move |app, event| match event {
+            tauri::RunEvent::Ready => startup::run_deferred(app, run_state.clone()),
+            tauri::RunEvent::Exit => {
+                if let Err(e) = run_state.flush() {
+                    eprintln!("Failed to save config on exit: {}", e);
+                }
+                instances::stop();
+            }
+            _ => {}
+        });
 }
 
-/// Set the custom scripts preference in Unity project's MCP state
-/// This writes to the _MCP/state folder which the Unity extension reads
-#[tauri::command]
-fn set_unity_custom_scripts(unity_project_path: String, enabled: bool) -> Result<(), String> {
-    let state_dir = PathBuf::from(&unity_project_path)
-        .join("Assets")
-        .join("_MCP")
-        .join("state");
-
-    fs::create_dir_all(&state_dir)
-        .map_err(|e| format!("Failed to create MCP state directory: {}", e))?;
-
-    let settings_path = state_dir.join("launcher-settings.json");
-
-    let settings = serde_json::json!({
-        "enableCustomScripts": enabled
-    });
-
-    let content = serde_json::to_string_pretty(&settings)
-        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
-
-    fs::write(&settings_path, content)
-        .map_err(|e| format!("Failed to write settings: {}", e))?;
-
-    Ok(())
+/// Every command the window can invoke; also the source of `bindings.ts`
+fn command_builder() -> tauri_specta::Builder<tauri::Wry> {
+    let commands = tauri_specta::collect_commands![
+        commands::load_config,
+        commands::save_config,
+        commands::flush_config,
+        commands::add_channel,
+        commands::remove_channel,
+        commands::set_channel_allowed_roots,
+        commands::set_channel_server_dirs,
+        commands::set_channel_quotas,
+        commands::set_channel_workspace_clients,
+        commands::validate_unity_scene,
+        commands::find_channel_for_path,
+        commands::get_claude_mcp_config,
+        commands::update_claude_mcp_config,
+        commands::remove_claude_mcp_config,
+        commands::check_unity_extension,
+        commands::install_unity_extension,
+        get_mcp_root,
+        commands::set_unity_custom_scripts,
+        banter_sdk::get_banter_sdk_info,
+        health::get_channel_health,
+        health::check_all_channels,
+        health::get_activation_check,
+        bundles::build_banter_bundle,
+        bundles::build_all_platforms,
+        upload::upload_banter_bundle,
+        visit::visit_space,
+        validation::validate_banter_content,
+        account::start_login,
+        account::get_account_status,
+        account::logout,
+        preview_server::start_preview_server,
+        preview_server::stop_preview_server,
+        preview_server::get_preview_server_status,
+        control_api::get_control_api_status,
+        control_api::set_control_api_enabled,
+        simulation::get_simulation_status,
+        simulation::set_simulation_enabled,
+        features::get_feature_flags,
+        focus::focus_unity_window,
+        focus::focus_launcher_window,
+        features::set_feature_flag,
+        control_api::regenerate_control_api_token,
+        backup::list_backups,
+        backup::restore_backup,
+        channels_file::apply_channels_file,
+        batch::batch,
+        channel_export::export_channel,
+        channel_export::import_channel,
+        handoff::export_session_state,
+        handoff::import_session_state,
+        claude_import::import_from_claude_config,
+        snippets::generate_mcp_snippet,
+        env_bootstrap::get_env_bootstrap,
+        env_bootstrap::apply_env_bootstrap,
+        support::get_support_mode,
+        support::start_support_mode,
+        support::finish_support_mode,
+        context_file::generate_context_file,
+        capabilities::get_server_capabilities,
+        stack::get_capabilities,
+        instances::get_other_instances,
+        batchmode::verify_unity_extension,
+        batchmode::list_unity_scenes,
+        snapshots::snapshot_scene,
+        snapshots::list_snapshots,
+        snapshots::restore_snapshot,
+        git::get_git_status,
+        icons::generate_channel_icon,
+        dashboard::get_dashboard,
+        editor_monitor::get_editor_status,
+        editor_monitor::relaunch_editor,
+        uninstall::uninstall_cleanup,
+        setup_report::generate_setup_report,
+        i18n::get_message_catalogue,
+        logs::read_file_chunk,
+        logs::read_file_tail,
+        scan::scan_unity_project,
+        scan::find_unity_projects,
+        scan::invalidate_scan_cache,
+        assets::list_project_assets,
+        guids::resolve_guid,
+        guids::find_references,
+        mock_project::create_mock_project,
+        jobs::enqueue_job,
+        jobs::get_job_status,
+        jobs::list_jobs,
+        jobs::cancel_job,
+        job_history::get_job_history,
+        job_history::resume_job,
+        journal::get_incomplete_operations,
+        journal::rollback_operation,
+        journal::resume_operation,
+        journal::discard_operation,
+        secrets::set_channel_secret,
+        secrets::list_channel_secrets,
+        sessions::check_project_session,
+        write_guard::get_write_violations,
+        elevation::check_elevation,
+        startup::get_startup_report,
+        telemetry::get_telemetry,
+        telemetry::export_telemetry,
+        telemetry::clear_telemetry,
+        telemetry::send_telemetry,
+        observer::get_observer_mode,
+        observer::set_observer_mode,
+        recorder::list_sessions,
+        recorder::export_session,
+        recorder::set_session_recording,
+        server_pool::get_server_pool,
+        server_pool::set_server_pooling,
+        scheduler::get_schedules,
+        scheduler::set_schedule,
+        scene_diff::diff_scenes,
+    ];
+    bindings::with_events(
+        tauri_specta::Builder::<tauri::Wry>::new()
+            .commands(commands)
+            .error_handling(tauri_specta::ErrorHandlingMode::Throw),
+    )
 }
@@ -0,0 +1,225 @@
+//! Minimal MCP client for asking a channel's server what it offers
+//!
+//! Starts the server over stdio the way Claude Code would (same command,
+//! environment and working directory), performs the `initialize` handshake
+//! and then sends requests one at a time. Messages are newline-delimited
+//! JSON-RPC; anything on stdout that isn't the awaited response (log lines,
+//! notifications) is skipped. The server is killed when the client is dropped.
+
+use launcher_core::config::{McpResource, McpTool};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::error::{ErrorKind, LauncherError};
+use crate::faults::{self, Fault};
+use crate::plan::FilePlan;
+use crate::{simulation, LauncherConfig, ProjectChannel};
+
+/// Protocol revision sent in `initialize`; servers answer with theirs
+const PROTOCOL_VERSION: &str = "2025-06-18";
+
+/// How long a server may take to answer one request, startup included
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Pages followed for list requests, in case a server loops its cursor
+const MAX_PAGES: usize = 20;
+
+pub struct McpClient {
+    child: Child,
+    stdin: ChildStdin,
+    lines: Receiver<String>,
+    next_id: u64,
+    /// `serverInfo` and `capabilities` from the `initialize` result
+    pub server: serde_json::Value,
+}
+
+impl McpClient {
+    /// Start the channel's server and complete the handshake
+    pub fn connect(
+        config: &LauncherConfig,
+        channel: &ProjectChannel,
+    ) -> Result<Self, LauncherError> {
+        if simulation::enabled(config) {
+            return Err(LauncherError::new(
+                ErrorKind::Conflict,
+                "Simulation mode has no MCP server to ask",
+            )
+            .with_hint("Turn simulation mode off to query the real server"));
+        }
+
+        let cwd = crate::channels::server_cwd(channel)?;
+        let mut plan = FilePlan::new(false);
+        let env = crate::mcp_server_env_vars(&mut plan, config, channel)?;
+        let server = Path::new(&config.mcp_server_path);
+        let mut child = faults::check(Fault::NodeMissing)
+            .and_then(|_| {
+                Command::new("node")
+                    .arg(server)
+                    .current_dir(&cwd)
+                    .envs(env)
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::null())
+                    .spawn()
+            })
+            .map_err(|e| {
+                LauncherError::io("Failed to start MCP server", server, e)
+                    .with_hint("Check that Node.js is installed and on PATH")
+            })?;
+
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            let _ = child.kill();
+            return Err(LauncherError::internal("MCP server started without pipes"));
+        };
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut client = McpClient {
+            child,
+            stdin,
+            lines,
+            next_id: 1,
+            server: serde_json::Value::Null,
+        };
+        client.server = client.request(
+            "initialize",
+            serde_json::json!({
+                "protocolVersion": PROTOCOL_VERSION,
+                "capabilities": {},
+                "clientInfo": { "name": "banter-launcher", "version": env!("CARGO_PKG_VERSION") },
+            }),
+        )?;
+        client.send(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/initialized",
+        }))?;
+        Ok(client)
+    }
+
+    fn send(&mut self, message: &serde_json::Value) -> Result<(), LauncherError> {
+        writeln!(self.stdin, "{}", message)
+            .and_then(|_| self.stdin.flush())
+            .map_err(|e| {
+                LauncherError::new(ErrorKind::Io, format!("MCP server closed its input: {}", e))
+            })
+    }
+
+    /// Send a request and wait for its result
+    pub fn request(
+        &mut self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, LauncherError> {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.send(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        }))?;
+
+        let deadline = Instant::now() + REQUEST_TIMEOUT;
+        loop {
+            let wait = deadline.saturating_duration_since(Instant::now());
+            let line = match self.lines.recv_timeout(wait) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(LauncherError::new(
+                        ErrorKind::Network,
+                        format!("MCP server didn't answer {} in time", method),
+                    ))
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(LauncherError::new(
+                        ErrorKind::Network,
+                        format!("MCP server exited before answering {}", method),
+                    ))
+                }
+            };
+            let Ok(message) = serde_json::from_str::<serde_json::Value>(&line) else {
+                continue;
+            };
+            if message.get("id").and_then(|i| i.as_u64()) != Some(id) {
+                continue;
+            }
+            if let Some(error) = message.get("error") {
+                let text = error
+                    .get("message")
+                    .and_then(|m| m.as_str())
+                    .unwrap_or("unknown error");
+                return Err(LauncherError::new(
+                    ErrorKind::Network,
+                    format!("MCP server refused {}: {}", method, text),
+                ));
+            }
+            return Ok(message.get("result").cloned().unwrap_or_default());
+        }
+    }
+
+    /// Every item of a paginated list request (`tools/list`, ...), from the
+    /// `key` array of each page
+    pub fn list_all(
+        &mut self,
+        method: &str,
+        key: &str,
+    ) -> Result<Vec<serde_json::Value>, LauncherError> {
+        let mut items = Vec::new();
+        let mut cursor: Option<String> = None;
+        for _ in 0..MAX_PAGES {
+            let params = match &cursor {
+                Some(cursor) => serde_json::json!({ "cursor": cursor }),
+                None => serde_json::json!({}),
+            };
+            let page = self.request(method, params)?;
+            if let Some(page_items) = page.get(key).and_then(|i| i.as_array()) {
+                items.extend(page_items.iter().cloned());
+            }
+            cursor = page
+                .get("nextCursor")
+                .and_then(|c| c.as_str())
+                .map(str::to_string);
+            if cursor.is_none() {
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    pub fn list_tools(&mut self) -> Result<Vec<McpTool>, LauncherError> {
+        Ok(self
+            .list_all("tools/list", "tools")?
+            .into_iter()
+            .filter_map(|tool| serde_json::from_value(tool).ok())
+            .collect())
+    }
+
+    /// Resources, or none if the server doesn't declare the capability
+    pub fn list_resources(&mut self) -> Result<Vec<McpResource>, LauncherError> {
+        if self.server.pointer("/capabilities/resources").is_none() {
+            return Ok(Vec::new());
+        }
+        Ok(self
+            .list_all("resources/list", "resources")?
+            .into_iter()
+            .filter_map(|resource| serde_json::from_value(resource).ok())
+            .collect())
+    }
+}
+
+impl Drop for McpClient {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
@@ -0,0 +1,393 @@
+//! Stdio proxy between Claude Code and the MCP server
+//!
+//! While sessions are recorded, the `banter` client entry runs
+//! `banter-launcher --mcp-proxy <channel> <server>` instead of `node
+//! <server>`. The proxy starts the server with the environment and working
+//! directory the entry gave it, and passes every line through unchanged in
+//! both directions. On the way it notes `tools/call` requests and their
+//! responses in the channel's open session, and, while the server runs,
+//! the bridge commands it queues and the editor connecting or disconnecting
+//! (see `recorder`). The server's stderr goes straight to Claude Code.
+//!
+//! With `--pooled` the proxy starts no server of its own: it connects to the
+//! channel's shared server (see `server_pool`) and relays over that socket
+//! the same way.
+//!
+//! Tool calls that break the channel's quotas (see the core `quotas`) never
+//! reach the server: the proxy answers them with a tool error itself.
+
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::error::LauncherError;
+use crate::faults::{self, Fault};
+use crate::{bridge, recorder, server_pool, support, ProjectChannel};
+use launcher_core::quotas::{self, CallRate};
+
+/// Command line switch that runs the proxy
+pub const SWITCH: &str = "--mcp-proxy";
+
+/// Follows `SWITCH` to relay to the channel's pooled server
+pub const POOLED: &str = "--pooled";
+
+/// How often the bridge's command folder and heartbeat are checked
+const BRIDGE_POLL: Duration = Duration::from_millis(500);
+
+/// A `tools/call` waiting for its response
+struct PendingCall {
+    session_id: String,
+    tool: String,
+    sent: Instant,
+}
+
+type Pending = Arc<Mutex<HashMap<String, PendingCall>>>;
+
+/// Arguments of a client entry that runs the server through the proxy
+pub fn args(channel_id: &str, mcp_server_path: &str, pooled: bool) -> Vec<String> {
+    let mut args = vec![SWITCH.to_string()];
+    if pooled {
+        args.push(POOLED.to_string());
+    }
+    args.extend([channel_id.to_string(), mcp_server_path.to_string()]);
+    args
+}
+
+/// Writing half of the pooled server's socket; dropping it closes the
+/// connection the way dropping a child's stdin closes the pipe
+struct SocketWriter(TcpStream);
+
+impl Write for SocketWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl Drop for SocketWriter {
+    fn drop(&mut self) {
+        let _ = self.0.shutdown(Shutdown::Write);
+    }
+}
+
+/// JSON-RPC messages on a line; a batch holds several
+fn messages(line: &str) -> Vec<Value> {
+    match serde_json::from_str(line) {
+        Ok(Value::Array(batch)) => batch,
+        Ok(message) => vec![message],
+        Err(_) => Vec::new(),
+    }
+}
+
+fn record(session_id: &str, kind: &str, data: Value) {
+    if let Err(e) = recorder::append(session_id, kind, data) {
+        eprintln!("banter-launcher: {}", e);
+    }
+}
+
+/// Note the tool calls Claude Code sends
+fn client_line(channel_id: &str, line: &str, pending: &Pending) {
+    for message in messages(line) {
+        if message.get("method").and_then(Value::as_str) != Some("tools/call") {
+            continue;
+        }
+        let Some(session) = recorder::open_session(channel_id) else {
+            return;
+        };
+        let id = message.get("id").cloned().unwrap_or(Value::Null);
+        let tool = message
+            .pointer("/params/name")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        record(
+            &session.id,
+            recorder::TOOL_CALL,
+            serde_json::json!({
+                "id": id,
+                "tool": tool,
+                "arguments": message.pointer("/params/arguments"),
+            }),
+        );
+        pending.lock().unwrap_or_else(|e| e.into_inner()).insert(
+            id.to_string(),
+            PendingCall {
+                session_id: session.id,
+                tool,
+                sent: Instant::now(),
+            },
+        );
+    }
+}
+
+/// Note the server's answers to recorded tool calls
+fn server_line(line: &str, pending: &Pending) {
+    for message in messages(line) {
+        let Some(id) = message.get("id") else {
+            continue;
+        };
+        let Some(call) = pending
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&id.to_string())
+        else {
+            continue;
+        };
+        let is_error = message.get("error").is_some()
+            || message
+                .pointer("/result/isError")
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+        record(
+            &call.session_id,
+            recorder::TOOL_RESULT,
+            serde_json::json!({
+                "id": id,
+                "tool": call.tool,
+                "duration_ms": call.sent.elapsed().as_millis() as u64,
+                "is_error": is_error,
+                "result": message.get("result"),
+                "error": message.get("error"),
+            }),
+        );
+    }
+}
+
+/// The tool error the proxy answers `message` with when it breaks the
+/// channel's quotas
+fn refusal(channel: &ProjectChannel, rate: &mut CallRate, message: &Value) -> Option<Value> {
+    if message.get("method").and_then(Value::as_str) != Some("tools/call") {
+        return None;
+    }
+    let tool = message
+        .pointer("/params/name")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let args = message
+        .pointer("/params/arguments")
+        .cloned()
+        .unwrap_or(Value::Null);
+    let reason = quotas::check(channel, rate, tool, &args, Instant::now())?;
+    Some(serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": message.get("id"),
+        "result": { "content": [{ "type": "text", "text": reason }], "isError": true },
+    }))
+}
+
+/// Answer the calls on `line` that break the channel's quotas, and return
+/// what is left to send on
+fn enforce_quotas(channel: &ProjectChannel, rate: &mut CallRate, line: String) -> Option<String> {
+    let reply = |response: Value| {
+        let mut stdout = io::stdout().lock();
+        let _ = writeln!(stdout, "{}", response).and_then(|_| stdout.flush());
+    };
+    match serde_json::from_str(&line) {
+        Ok(Value::Array(batch)) => {
+            let count = batch.len();
+            let mut kept = Vec::new();
+            for message in batch {
+                match refusal(channel, rate, &message) {
+                    Some(response) => reply(response),
+                    None => kept.push(message),
+                }
+            }
+            match kept.len() {
+                0 => None,
+                n if n == count => Some(line),
+                _ => Some(Value::Array(kept).to_string()),
+            }
+        }
+        Ok(message) => match refusal(channel, rate, &message) {
+            Some(response) => {
+                reply(response);
+                None
+            }
+            None => Some(line),
+        },
+        Err(_) => Some(line),
+    }
+}
+
+/// Copy Claude Code's requests to the server until either side closes
+fn forward_client(
+    channel_id: String,
+    channel: Option<ProjectChannel>,
+    mut server: Box<dyn Write + Send>,
+    pending: Pending,
+) {
+    let mut rate = CallRate::default();
+    for line in io::stdin().lock().lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        let line = match &channel {
+            Some(channel) => match enforce_quotas(channel, &mut rate, line) {
+                Some(line) => line,
+                None => continue,
+            },
+            None => line,
+        };
+        client_line(&channel_id, &line, &pending);
+        support::inspect(&channel_id, "client", &line);
+        if writeln!(server, "{}", line)
+            .and_then(|_| server.flush())
+            .is_err()
+        {
+            break;
+        }
+    }
+    // Dropping stdin tells the server its client is gone
+}
+
+/// Record bridge commands and editor connects and disconnects while the
+/// channel has an open session
+fn watch_bridge(channel_id: String, project: PathBuf, stop: Arc<AtomicBool>) {
+    let commands = bridge::commands_dir(&project);
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut connected: Option<bool> = None;
+    while !stop.load(Ordering::Relaxed) {
+        thread::sleep(BRIDGE_POLL);
+        let Some(session) = recorder::open_session(&channel_id) else {
+            continue;
+        };
+
+        let now_connected = bridge::is_editor_connected(&project);
+        if connected.is_some_and(|c| c != now_connected) {
+            let kind = if now_connected {
+                recorder::BRIDGE_CONNECTED
+            } else {
+                recorder::BRIDGE_DISCONNECTED
+            };
+            record(&session.id, kind, serde_json::json!({}));
+        }
+        connected = Some(now_connected);
+
+        // The bridge deletes command files once it has run them; one it
+        // picks up within a poll is missed
+        let Ok(entries) = std::fs::read_dir(&commands) else {
+            continue;
+        };
+        let mut present = HashSet::new();
+        for path in entries.flatten().map(|e| e.path()) {
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let name = path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            if !seen.contains(&name) {
+                if let Some(command) = std::fs::read_to_string(&path)
+                    .ok()
+                    .and_then(|c| serde_json::from_str::<Value>(&c).ok())
+                {
+                    record(
+                        &session.id,
+                        recorder::BRIDGE_COMMAND,
+                        serde_json::json!({
+                            "type": command.get("type"),
+                            "command": command,
+                        }),
+                    );
+                }
+            }
+            present.insert(name);
+        }
+        seen = present;
+    }
+}
+
+/// Start the server as the proxy's child, talking over its stdio
+fn spawn_server(server: &Path) -> Result<Child, LauncherError> {
+    faults::check(Fault::NodeMissing)
+        .and_then(|_| {
+            Command::new("node")
+                .arg(server)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::inherit())
+                .spawn()
+        })
+        .map_err(|e| {
+            LauncherError::io("Failed to start MCP server", server, e)
+                .with_hint("Check that Node.js is installed and on PATH")
+        })
+}
+
+/// Run the MCP server behind the proxy until it exits, or with `pooled`
+/// relay to the channel's shared server until it closes the connection;
+/// returns the exit code
+pub fn run(channel_id: &str, mcp_server_path: &str, pooled: bool) -> Result<i32, LauncherError> {
+    let server = Path::new(mcp_server_path);
+    let config = crate::read_config().unwrap_or_else(|_| crate::default_config());
+    let channel = crate::find_channel(&config, channel_id).ok();
+    let mut child = None;
+    let (server_in, server_out): (Box<dyn Write + Send>, Box<dyn Read + Send>) = if pooled {
+        let idle_timeout_secs = server_pool::idle_timeout_secs(&config);
+        let stream = server_pool::connect(channel_id, server, idle_timeout_secs)?;
+        let reader = stream.try_clone().map_err(|e| {
+            LauncherError::internal(format!("Failed to use the pooled server's socket: {}", e))
+        })?;
+        (Box::new(SocketWriter(stream)), Box::new(reader))
+    } else {
+        let mut spawned = spawn_server(server)?;
+        let (Some(server_in), Some(server_out)) = (spawned.stdin.take(), spawned.stdout.take())
+        else {
+            return Err(LauncherError::internal("The MCP server has no stdio pipes"));
+        };
+        child = Some(spawned);
+        (Box::new(server_in), Box::new(server_out))
+    };
+
+    let pending: Pending = Arc::default();
+    let stop = Arc::new(AtomicBool::new(false));
+    {
+        let channel_id = channel_id.to_string();
+        let pending = pending.clone();
+        thread::spawn(move || forward_client(channel_id, channel, server_in, pending));
+    }
+    // The client entry sets the project for the server; the proxy reads the same
+    if let Ok(project) = std::env::var("UNITY_PROJECT_PATH") {
+        let channel_id = channel_id.to_string();
+        let stop = stop.clone();
+        thread::spawn(move || watch_bridge(channel_id, PathBuf::from(project), stop));
+    }
+
+    // Locked per line: refused tool calls are answered from the other thread
+    let stdout = io::stdout();
+    for line in BufReader::new(server_out).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        let mut stdout = stdout.lock();
+        if writeln!(stdout, "{}", line)
+            .and_then(|_| stdout.flush())
+            .is_err()
+        {
+            break;
+        }
+        server_line(&line, &pending);
+        support::inspect(channel_id, "server", &line);
+    }
+
+    stop.store(true, Ordering::Relaxed);
+    let Some(mut child) = child else {
+        return Ok(0);
+    };
+    let status = child.wait().map_err(|e| {
+        LauncherError::internal(format!("Failed to wait for the MCP server: {}", e))
+    })?;
+    Ok(status.code().unwrap_or(1))
+}
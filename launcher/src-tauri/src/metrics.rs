@@ -0,0 +1,321 @@
+//! Prometheus metrics for the control API
+//!
+//! `GET /api/v1/metrics` answers in the Prometheus text format, so a studio
+//! running the launcher on a shared machine can scrape it like any other
+//! service. Command and bridge timings are counted in this process from the
+//! moment it starts; server uptimes cover the preview server, the control API
+//! and, while `--watch` runs on the same machine, the MCP server it
+//! supervises, which it reports through `WATCH_FILE`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::error::LauncherError;
+use crate::plan::FilePlan;
+use crate::state::AppState;
+use crate::{bridge, preview_server};
+
+/// Content type of the text exposition format
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// Written by watch mode on every poll
+pub const WATCH_FILE: &str = "watch-metrics.json";
+
+/// A watch report older than this is from a supervisor that has stopped
+const WATCH_STALE_MS: i64 = 30_000;
+
+pub const PREVIEW_SERVER: &str = "preview-server";
+pub const CONTROL_API: &str = "control-api";
+pub const MCP_SERVER: &str = "mcp-server";
+
+#[derive(Default)]
+struct Timing {
+    count: u64,
+    errors: u64,
+    seconds: f64,
+}
+
+struct Uptime {
+    since: Option<Instant>,
+    starts: u64,
+}
+
+struct Registry {
+    commands: BTreeMap<String, Timing>,
+    bridge: BTreeMap<String, Timing>,
+    servers: BTreeMap<&'static str, Uptime>,
+}
+
+static REGISTRY: Mutex<Registry> = Mutex::new(Registry {
+    commands: BTreeMap::new(),
+    bridge: BTreeMap::new(),
+    servers: BTreeMap::new(),
+});
+
+/// What watch mode knows about the MCP server it supervises
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WatchMetrics {
+    pub channel_id: Option<String>,
+    /// When the running server started, Unix ms; `None` while it is down
+    pub started_at_ms: Option<i64>,
+    pub starts: u64,
+    pub updated_at_ms: i64,
+}
+
+fn with_registry<T>(f: impl FnOnce(&mut Registry) -> T) -> T {
+    f(&mut REGISTRY.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
+fn add(timings: &mut BTreeMap<String, Timing>, name: &str, elapsed: Duration, ok: bool) {
+    let timing = timings.entry(name.to_string()).or_default();
+    timing.count += 1;
+    timing.seconds += elapsed.as_secs_f64();
+    if !ok {
+        timing.errors += 1;
+    }
+}
+
+/// Count one run of an IPC command
+pub fn record_command(name: &str, elapsed: Duration, ok: bool) {
+    with_registry(|r| add(&mut r.commands, name, elapsed, ok));
+}
+
+/// Count one bridge round trip, from the command being sent to Unity writing
+/// `request`
+pub fn record_bridge(request: &str, elapsed: Duration, ok: bool) {
+    with_registry(|r| add(&mut r.bridge, request, elapsed, ok));
+}
+
+pub fn server_started(server: &'static str) {
+    with_registry(|r| {
+        let uptime = r.servers.entry(server).or_insert(Uptime {
+            since: None,
+            starts: 0,
+        });
+        uptime.since = Some(Instant::now());
+        uptime.starts += 1;
+    });
+}
+
+pub fn server_stopped(server: &'static str) {
+    with_registry(|r| {
+        if let Some(uptime) = r.servers.get_mut(server) {
+            uptime.since = None;
+        }
+    });
+}
+
+pub fn watch_file() -> PathBuf {
+    crate::launcher_dir().join(WATCH_FILE)
+}
+
+pub fn write_watch_metrics(metrics: &WatchMetrics) -> Result<(), LauncherError> {
+    let json = serde_json::to_string(metrics)
+        .map_err(|e| LauncherError::internal(format!("Failed to serialize metrics: {}", e)))?;
+    FilePlan::new(false).write(&watch_file(), json)
+}
+
+fn read_watch_metrics() -> Option<WatchMetrics> {
+    let metrics: WatchMetrics =
+        serde_json::from_str(&std::fs::read_to_string(watch_file()).ok()?).ok()?;
+    (bridge::now_ms() - metrics.updated_at_ms < WATCH_STALE_MS).then_some(metrics)
+}
+
+/// Quote a label value
+fn label(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    )
+}
+
+/// Writes metric families, each with its `# HELP` and `# TYPE` lines
+#[derive(Default)]
+struct Exposition {
+    text: String,
+}
+
+impl Exposition {
+    fn family(&mut self, name: &str, kind: &str, help: &str) {
+        let _ = writeln!(self.text, "# HELP {} {}", name, help);
+        let _ = writeln!(self.text, "# TYPE {} {}", name, kind);
+    }
+
+    fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: f64) {
+        let labels: Vec<String> = labels
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, label(value)))
+            .collect();
+        if labels.is_empty() {
+            let _ = writeln!(self.text, "{} {}", name, value);
+        } else {
+            let _ = writeln!(self.text, "{}{{{}}} {}", name, labels.join(","), value);
+        }
+    }
+
+    fn timings(&mut self, name: &str, key: &str, help: &str, timings: &BTreeMap<String, Timing>) {
+        self.family(
+            &format!("{}_duration_seconds", name),
+            "summary",
+            &format!("Time taken by {}", help),
+        );
+        for (label, timing) in timings {
+            let labels = [(key, label.as_str())];
+            self.sample(
+                &format!("{}_duration_seconds_count", name),
+                &labels,
+                timing.count as f64,
+            );
+            self.sample(
+                &format!("{}_duration_seconds_sum", name),
+                &labels,
+                timing.seconds,
+            );
+        }
+        self.family(
+            &format!("{}_errors_total", name),
+            "counter",
+            &format!("Failed {}", help),
+        );
+        for (label, timing) in timings {
+            self.sample(
+                &format!("{}_errors_total", name),
+                &[(key, label.as_str())],
+                timing.errors as f64,
+            );
+        }
+    }
+}
+
+/// One server's row: labels, whether it runs, uptime in seconds, restarts
+type ServerRow = (Vec<(&'static str, String)>, bool, f64, u64);
+
+fn server_rows(state: &AppState) -> Vec<ServerRow> {
+    let preview = preview_server::current_status(state);
+    let mut rows = with_registry(|r| {
+        r.servers
+            .iter()
+            .map(|(server, uptime)| {
+                let mut labels = vec![("server", server.to_string())];
+                if *server == PREVIEW_SERVER {
+                    if let Some(channel_id) = &preview.channel_id {
+                        labels.push(("channel", channel_id.clone()));
+                    }
+                }
+                let seconds = uptime
+                    .since
+                    .map(|since| since.elapsed().as_secs_f64())
+                    .unwrap_or(0.0);
+                (
+                    labels,
+                    uptime.since.is_some(),
+                    seconds,
+                    uptime.starts.saturating_sub(1),
+                )
+            })
+            .collect::<Vec<ServerRow>>()
+    });
+    if let Some(watch) = read_watch_metrics() {
+        let mut labels = vec![("server", MCP_SERVER.to_string())];
+        if let Some(channel_id) = watch.channel_id {
+            labels.push(("channel", channel_id));
+        }
+        let seconds = watch
+            .started_at_ms
+            .map(|started| (watch.updated_at_ms - started).max(0) as f64 / 1000.0)
+            .unwrap_or(0.0);
+        rows.push((
+            labels,
+            watch.started_at_ms.is_some(),
+            seconds,
+            watch.starts.saturating_sub(1),
+        ));
+    }
+    rows
+}
+
+/// Everything the launcher measures, in the Prometheus text format
+pub fn render(state: &AppState) -> String {
+    let mut out = Exposition::default();
+
+    let servers = server_rows(state);
+    let families: [(&str, &str, &str); 3] = [
+        ("banter_server_up", "gauge", "Whether the server is running"),
+        (
+            "banter_server_uptime_seconds",
+            "gauge",
+            "Time since the server last started",
+        ),
+        (
+            "banter_server_restarts_total",
+            "counter",
+            "Times the server was started again",
+        ),
+    ];
+    for (index, (name, kind, help)) in families.iter().enumerate() {
+        out.family(name, kind, help);
+        for (labels, running, seconds, restarts) in &servers {
+            let labels: Vec<(&str, &str)> = labels.iter().map(|(k, v)| (*k, v.as_str())).collect();
+            let value = match index {
+                0 => f64::from(u8::from(*running)),
+                1 => *seconds,
+                _ => *restarts as f64,
+            };
+            out.sample(name, &labels, value);
+        }
+    }
+
+    let config = state.config();
+    out.family(
+        "banter_bridge_connected",
+        "gauge",
+        "Whether a Unity editor is connected to the channel's project",
+    );
+    for channel in &config.channels {
+        let connected = bridge::is_editor_connected(Path::new(&channel.unity_project_path));
+        out.sample(
+            "banter_bridge_connected",
+            &[("channel", &channel.id)],
+            f64::from(u8::from(connected)),
+        );
+    }
+    out.family(
+        "banter_bridge_heartbeat_age_seconds",
+        "gauge",
+        "Time since the editor's last bridge heartbeat",
+    );
+    let now = bridge::now_ms();
+    for channel in &config.channels {
+        if let Some(heartbeat) = bridge::editor_heartbeat_ms(Path::new(&channel.unity_project_path))
+        {
+            out.sample(
+                "banter_bridge_heartbeat_age_seconds",
+                &[("channel", &channel.id)],
+                (now - heartbeat).max(0) as f64 / 1000.0,
+            );
+        }
+    }
+
+    with_registry(|r| {
+        out.timings(
+            "banter_bridge_request",
+            "request",
+            "bridge requests, from sending the command to Unity answering",
+            &r.bridge,
+        );
+        out.timings(
+            "banter_command",
+            "command",
+            "launcher commands",
+            &r.commands,
+        );
+    });
+    out.text
+}
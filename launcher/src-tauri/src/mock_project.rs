@@ -0,0 +1,272 @@
+//! Minimal Unity projects for tests and demos
+//!
+//! `create_mock_project` writes the few files the launcher actually reads —
+//! `ProjectSettings/ProjectVersion.txt`, `Packages/manifest.json`, scenes
+//! with their `.meta` files and `EditorBuildSettings.asset` — so channels,
+//! scans, health checks and content validation can be exercised without a
+//! real project. Unity itself will open the result and fill in the rest.
+//! Each scene holds a light and a floor with a collider, so it passes
+//! `validation` as-is.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::Path;
+
+use crate::error::LauncherError;
+use crate::plan::FilePlan;
+use crate::sanitize::InputError;
+use crate::{banter_sdk, sanitize, write_guard};
+
+const DEFAULT_UNITY_VERSION: &str = "2022.3.22f1";
+
+#[derive(Debug, Clone, Deserialize, Type)]
+#[serde(default)]
+pub struct MockProjectOptions {
+    /// Editor version written to ProjectVersion.txt
+    pub unity_version: String,
+    /// Scene names, created as Assets/Scenes/<name>.unity
+    pub scenes: Vec<String>,
+    /// Banter SDK version listed in Packages/manifest.json; `None` leaves
+    /// the SDK out
+    pub banter_sdk_version: Option<String>,
+}
+
+impl Default for MockProjectOptions {
+    fn default() -> Self {
+        MockProjectOptions {
+            unity_version: DEFAULT_UNITY_VERSION.to_string(),
+            scenes: vec!["Main".to_string(), "Lobby".to_string()],
+            banter_sdk_version: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct MockProject {
+    pub unity_project_path: String,
+    /// Full paths of the created scenes, in `options.scenes` order
+    pub scene_paths: Vec<String>,
+    pub plan: FilePlan,
+}
+
+fn new_guid() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+fn meta(guid: &str) -> String {
+    format!(
+        "fileFormatVersion: 2\nguid: {}\nDefaultImporter:\n  externalObjects: {{}}\n  userData: \n  assetBundleName: \n  assetBundleVariant: \n",
+        guid
+    )
+}
+
+fn folder_meta(guid: &str) -> String {
+    format!(
+        "fileFormatVersion: 2\nguid: {}\nfolderAsset: yes\nDefaultImporter:\n  externalObjects: {{}}\n  userData: \n  assetBundleName: \n  assetBundleVariant: \n",
+        guid
+    )
+}
+
+const SCENE: &str = r#"%YAML 1.1
+%TAG !u! tag:unity3d.com,2011:
+--- !u!29 &1
+OcclusionCullingSettings:
+  m_ObjectHideFlags: 0
+  serializedVersion: 2
+--- !u!104 &2
+RenderSettings:
+  m_ObjectHideFlags: 0
+  serializedVersion: 9
+--- !u!157 &3
+LightmapSettings:
+  m_ObjectHideFlags: 0
+  serializedVersion: 12
+--- !u!196 &4
+NavMeshSettings:
+  serializedVersion: 2
+  m_ObjectHideFlags: 0
+--- !u!1 &100
+GameObject:
+  m_ObjectHideFlags: 0
+  serializedVersion: 6
+  m_Component:
+  - component: {fileID: 101}
+  - component: {fileID: 102}
+  m_Layer: 0
+  m_Name: Directional Light
+  m_TagString: Untagged
+  m_IsActive: 1
+--- !u!4 &101
+Transform:
+  m_ObjectHideFlags: 0
+  m_GameObject: {fileID: 100}
+  m_LocalRotation: {x: 0.40821788, y: -0.23456968, z: 0.10938163, w: 0.8754261}
+  m_LocalPosition: {x: 0, y: 3, z: 0}
+  m_LocalScale: {x: 1, y: 1, z: 1}
+  m_Children: []
+  m_Father: {fileID: 0}
+--- !u!108 &102
+Light:
+  m_ObjectHideFlags: 0
+  m_GameObject: {fileID: 100}
+  m_Enabled: 1
+  serializedVersion: 10
+  m_Type: 1
+  m_Intensity: 1
+--- !u!1 &200
+GameObject:
+  m_ObjectHideFlags: 0
+  serializedVersion: 6
+  m_Component:
+  - component: {fileID: 201}
+  - component: {fileID: 202}
+  - component: {fileID: 203}
+  - component: {fileID: 204}
+  m_Layer: 0
+  m_Name: Floor
+  m_TagString: Untagged
+  m_IsActive: 1
+--- !u!4 &201
+Transform:
+  m_ObjectHideFlags: 0
+  m_GameObject: {fileID: 200}
+  m_LocalRotation: {x: 0, y: 0, z: 0, w: 1}
+  m_LocalPosition: {x: 0, y: 0, z: 0}
+  m_LocalScale: {x: 10, y: 1, z: 10}
+  m_Children: []
+  m_Father: {fileID: 0}
+--- !u!33 &202
+MeshFilter:
+  m_ObjectHideFlags: 0
+  m_GameObject: {fileID: 200}
+  m_Mesh: {fileID: 10209, guid: 0000000000000000e000000000000000, type: 0}
+--- !u!23 &203
+MeshRenderer:
+  m_ObjectHideFlags: 0
+  m_GameObject: {fileID: 200}
+  m_Enabled: 1
+--- !u!64 &204
+MeshCollider:
+  m_ObjectHideFlags: 0
+  m_GameObject: {fileID: 200}
+  m_Enabled: 1
+  m_Mesh: {fileID: 10209, guid: 0000000000000000e000000000000000, type: 0}
+"#;
+
+fn build_settings(scenes: &[(String, String)]) -> String {
+    let mut asset = String::from(
+        "%YAML 1.1\n%TAG !u! tag:unity3d.com,2011:\n--- !u!1045 &1\nEditorBuildSettings:\n  m_ObjectHideFlags: 0\n  serializedVersion: 2\n  m_Scenes:\n",
+    );
+    for (path, guid) in scenes {
+        asset += &format!("  - enabled: 1\n    path: {}\n    guid: {}\n", path, guid);
+    }
+    asset
+}
+
+fn manifest(banter_sdk_version: Option<&str>) -> serde_json::Value {
+    let mut dependencies = serde_json::json!({
+        "com.unity.modules.physics": "1.0.0",
+        "com.unity.modules.xr": "1.0.0",
+    });
+    if let Some(version) = banter_sdk_version {
+        dependencies[banter_sdk::BANTER_PACKAGE_ID] = serde_json::json!(version);
+    }
+    serde_json::json!({ "dependencies": dependencies })
+}
+
+/// Write a mock project into `project`, which must be new or empty
+pub fn create(
+    project: &Path,
+    options: &MockProjectOptions,
+    dry_run: bool,
+) -> Result<MockProject, LauncherError> {
+    let unity_version = sanitize::text(
+        "Unity version",
+        options.unity_version.trim(),
+        sanitize::MAX_ID_LEN,
+    )?;
+    if unity_version.is_empty() {
+        return Err(InputError::Empty {
+            field: "Unity version".to_string(),
+        }
+        .into());
+    }
+    for scene in &options.scenes {
+        sanitize::identifier("Scene name", scene)?;
+    }
+    if let Some(version) = &options.banter_sdk_version {
+        if banter_sdk::parse_version(version).is_none() {
+            return Err(LauncherError::invalid(format!(
+                "Not a version number: {}",
+                version
+            )));
+        }
+    }
+    write_guard::allow_new_dir(project)?;
+
+    let mut plan = FilePlan::new(dry_run);
+    let assets = project.join("Assets");
+    let scenes_dir = assets.join("Scenes");
+    let settings = project.join("ProjectSettings");
+    let packages = project.join("Packages");
+    for dir in [&scenes_dir, &settings, &packages] {
+        plan.create_dir(dir)?;
+    }
+
+    plan.write(
+        &settings.join("ProjectVersion.txt"),
+        format!(
+            "m_EditorVersion: {0}\nm_EditorVersionWithRevision: {0} (000000000000)\n",
+            unity_version
+        ),
+    )?;
+    let manifest =
+        serde_json::to_string_pretty(&manifest(options.banter_sdk_version.as_deref()))
+            .map_err(|e| LauncherError::internal(format!("Failed to serialize manifest: {}", e)))?;
+    plan.write(&packages.join("manifest.json"), manifest)?;
+
+    plan.write(&assets.join("Scenes.meta"), folder_meta(&new_guid()))?;
+    let mut scene_paths = Vec::new();
+    let mut build_scenes = Vec::new();
+    for scene in &options.scenes {
+        let path = scenes_dir.join(format!("{}.unity", scene));
+        let guid = new_guid();
+        plan.write(&path, SCENE)?;
+        plan.write(
+            &scenes_dir.join(format!("{}.unity.meta", scene)),
+            meta(&guid),
+        )?;
+        scene_paths.push(path.to_string_lossy().to_string());
+        build_scenes.push((format!("Assets/Scenes/{}.unity", scene), guid));
+    }
+    plan.write(
+        &settings.join("EditorBuildSettings.asset"),
+        build_settings(&build_scenes),
+    )?;
+
+    Ok(MockProject {
+        unity_project_path: project.to_string_lossy().to_string(),
+        scene_paths,
+        plan,
+    })
+}
+
+/// Create a minimal Unity project for tests and demos. Not used by the
+/// window itself.
+#[tauri::command]
+#[specta::specta]
+pub async fn create_mock_project(
+    path: String,
+    options: Option<MockProjectOptions>,
+    dry_run: Option<bool>,
+) -> Result<MockProject, LauncherError> {
+    let project = sanitize::path("Project folder", &path)?;
+    crate::commands::blocking("Create mock project", move || {
+        create(
+            &project,
+            &options.unwrap_or_default(),
+            dry_run.unwrap_or(false),
+        )
+    })
+    .await
+}
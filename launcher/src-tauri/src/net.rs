@@ -0,0 +1,258 @@
+//! Proxies and the bandwidth cap for the launcher's own HTTP traffic
+//!
+//! Everything the launcher fetches or sends itself (Banter login and bundle
+//! uploads today) goes through `agent`, which picks the proxy from
+//! `LauncherConfig.network`: `system` uses `HTTPS_PROXY`/`HTTP_PROXY`/
+//! `ALL_PROXY` when set and otherwise the proxy configured in Windows or
+//! macOS, `none` connects directly, and anything else is the proxy URL to
+//! use. Hosts in `NO_PROXY`, the OS exception list or `no_proxy` skip it.
+//!
+//! Request and response bodies wrapped in `throttle` share one
+//! `max_kib_per_sec` budget, so three uploads at once together stay under
+//! the cap rather than each using all of it.
+
+use std::io::{self, Read};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use launcher_core::config::{NetworkSettings, PROXY_NONE, PROXY_SYSTEM};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Largest read between two pauses under the cap, so transfers stay smooth
+const THROTTLE_CHUNK: usize = 16 * 1024;
+
+/// The proxy set in the OS, read once per launcher run
+#[derive(Debug, Default)]
+struct SystemProxy {
+    http: Option<String>,
+    https: Option<String>,
+    exceptions: Vec<String>,
+}
+
+static SYSTEM_PROXY: OnceLock<SystemProxy> = OnceLock::new();
+
+/// When the next throttled byte may go, shared by all transfers
+static NEXT_SLOT: Mutex<Option<Instant>> = Mutex::new(None);
+
+fn env(names: &[&str]) -> Option<String> {
+    names
+        .iter()
+        .flat_map(|name| [name.to_string(), name.to_lowercase()])
+        .find_map(|name| std::env::var(name).ok())
+        .filter(|value| !value.trim().is_empty())
+}
+
+fn scheme_and_host(url: &str) -> (&str, &str) {
+    let (scheme, rest) = url.split_once("://").unwrap_or(("http", url));
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    let host = match host.strip_prefix('[') {
+        Some(v6) => v6.split(']').next().unwrap_or_default(),
+        None => host.split(':').next().unwrap_or_default(),
+    };
+    (scheme, host)
+}
+
+/// Whether a `NO_PROXY`-style `entry` covers `host`: `*` covers everything,
+/// `example.com` and `.example.com` cover the domain and its subdomains, and
+/// Windows' `<local>` covers names without a dot
+fn bypasses(entry: &str, host: &str) -> bool {
+    let entry = entry.trim().to_ascii_lowercase();
+    let host = host.to_ascii_lowercase();
+    let entry = entry.split(':').next().unwrap_or_default();
+    match entry {
+        "" => false,
+        "*" => true,
+        "<local>" => !host.contains('.'),
+        _ => {
+            let domain = entry.trim_start_matches("*.").trim_start_matches('.');
+            host == domain || host.ends_with(&format!(".{}", domain))
+        }
+    }
+}
+
+#[cfg(windows)]
+fn read_system_proxy() -> SystemProxy {
+    let Ok(output) = std::process::Command::new("reg")
+        .args([
+            "query",
+            r"HKCU\Software\Microsoft\Windows\CurrentVersion\Internet Settings",
+        ])
+        .output()
+    else {
+        return SystemProxy::default();
+    };
+    let text = String::from_utf8_lossy(&output.stdout);
+    let value = |name: &str| {
+        text.lines().find_map(|line| {
+            let mut parts = line.split_whitespace();
+            (parts.next() == Some(name))
+                .then(|| parts.nth(1).map(str::to_string))
+                .flatten()
+        })
+    };
+    if value("ProxyEnable").as_deref() != Some("0x1") {
+        return SystemProxy::default();
+    }
+    let mut proxy = SystemProxy::default();
+    // Either one `host:port` for everything or `http=host:port;https=...`
+    for part in value("ProxyServer").unwrap_or_default().split(';') {
+        match part.split_once('=') {
+            Some(("http", server)) => proxy.http = Some(format!("http://{}", server)),
+            Some(("https", server)) => proxy.https = Some(format!("http://{}", server)),
+            Some(_) => {}
+            None if !part.is_empty() => {
+                proxy.http = Some(format!("http://{}", part));
+                proxy.https = proxy.http.clone();
+            }
+            None => {}
+        }
+    }
+    proxy.exceptions = value("ProxyOverride")
+        .map(|list| list.split(';').map(str::to_string).collect())
+        .unwrap_or_default();
+    proxy
+}
+
+#[cfg(target_os = "macos")]
+fn read_system_proxy() -> SystemProxy {
+    let Ok(output) = std::process::Command::new("scutil").arg("--proxy").output() else {
+        return SystemProxy::default();
+    };
+    let text = String::from_utf8_lossy(&output.stdout);
+    let mut fields = Vec::new();
+    let mut exceptions = Vec::new();
+    let mut in_exceptions = false;
+    for line in text.lines().map(str::trim) {
+        if line.starts_with("ExceptionsList") {
+            in_exceptions = true;
+        } else if in_exceptions && line == "}" {
+            in_exceptions = false;
+        } else if let Some((key, value)) = line.split_once(" : ") {
+            match in_exceptions {
+                true => exceptions.push(value.to_string()),
+                false => fields.push((key.to_string(), value.to_string())),
+            }
+        }
+    }
+    let field = |key: &str| {
+        fields
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    };
+    let server = |prefix: &str| {
+        if field(&format!("{}Enable", prefix)) != Some("1") {
+            return None;
+        }
+        let host = field(&format!("{}Proxy", prefix))?;
+        let port = field(&format!("{}Port", prefix)).unwrap_or("80");
+        Some(format!("http://{}:{}", host, port))
+    };
+    SystemProxy {
+        http: server("HTTP"),
+        https: server("HTTPS"),
+        exceptions,
+    }
+}
+
+/// Linux desktops have no one place for it; the environment covers them
+#[cfg(not(any(windows, target_os = "macos")))]
+fn read_system_proxy() -> SystemProxy {
+    SystemProxy::default()
+}
+
+/// The proxy to reach `url` through, if any
+fn proxy_for(network: &NetworkSettings, url: &str) -> Option<String> {
+    let (scheme, host) = scheme_and_host(url);
+    if network.proxy == PROXY_NONE || host.is_empty() {
+        return None;
+    }
+    let system = SYSTEM_PROXY.get_or_init(read_system_proxy);
+    let env_exceptions = env(&["NO_PROXY"]).unwrap_or_default();
+    let bypassed = network
+        .no_proxy
+        .iter()
+        .map(String::as_str)
+        .chain(env_exceptions.split(','))
+        .chain(system.exceptions.iter().map(String::as_str))
+        .any(|entry| bypasses(entry, host));
+    if bypassed {
+        return None;
+    }
+    if network.proxy != PROXY_SYSTEM {
+        return Some(network.proxy.clone());
+    }
+    let from_env = match scheme {
+        "https" => env(&["HTTPS_PROXY", "ALL_PROXY"]),
+        _ => env(&["HTTP_PROXY", "ALL_PROXY"]),
+    };
+    from_env.or_else(|| match scheme {
+        "https" => system.https.clone(),
+        _ => system.http.clone(),
+    })
+}
+
+/// An HTTP agent for requests to `url`, through the proxy that applies to it.
+/// A proxy URL ureq can't use is reported and skipped rather than failing
+/// the request.
+pub fn agent(network: &NetworkSettings, url: &str) -> ureq::Agent {
+    let builder = ureq::AgentBuilder::new().timeout_connect(CONNECT_TIMEOUT);
+    let builder = match proxy_for(network, url).map(|p| ureq::Proxy::new(&p)) {
+        Some(Ok(proxy)) => builder.proxy(proxy),
+        Some(Err(e)) => {
+            eprintln!("warning: ignoring proxy for {}: {}", url, e);
+            builder
+        }
+        None => builder,
+    };
+    builder.build()
+}
+
+/// Reader held to the shared bandwidth cap
+pub struct Throttled<R> {
+    inner: R,
+    bytes_per_sec: Option<u64>,
+}
+
+/// Hold `reader` to the configured cap; passes reads straight through
+/// without one
+pub fn throttle<R: Read>(network: &NetworkSettings, reader: R) -> Throttled<R> {
+    Throttled {
+        inner: reader,
+        bytes_per_sec: network.max_kib_per_sec.map(|kib| kib.max(1) * 1024),
+    }
+}
+
+/// Book `bytes` into the shared budget and wait for their turn
+fn pace(bytes: usize, bytes_per_sec: u64) {
+    let share = Duration::from_secs_f64(bytes as f64 / bytes_per_sec as f64);
+    let until = {
+        let mut next = NEXT_SLOT.lock().unwrap_or_else(|e| e.into_inner());
+        // Time nobody used isn't saved up for a burst later
+        let start = next
+            .filter(|at| *at > Instant::now())
+            .unwrap_or_else(Instant::now);
+        *next = Some(start + share);
+        start + share
+    };
+    if let Some(wait) = until.checked_duration_since(Instant::now()) {
+        thread::sleep(wait);
+    }
+}
+
+impl<R: Read> Read for Throttled<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let Some(bytes_per_sec) = self.bytes_per_sec else {
+            return self.inner.read(buf);
+        };
+        let len = buf.len().min(THROTTLE_CHUNK);
+        let n = self.inner.read(&mut buf[..len])?;
+        if n > 0 {
+            pace(n, bytes_per_sec);
+        }
+        Ok(n)
+    }
+}
@@ -0,0 +1,182 @@
+//! Read-only observer mode
+//!
+//! For screen-sharing with a client or letting QA look at a setup: with
+//! `observer_mode` on in the config, or the window started with `--observer`,
+//! only the commands in `READ_ONLY_COMMANDS` run: status, health, logs and
+//! reports. Everything else is refused before it starts, the same way from
+//! the window, the control API and the headless CLI, and the webview loses
+//! its file system scope. `--observer` lasts for that run only and can't be
+//! turned off from the window; the config setting can.
+
+use serde::Serialize;
+use specta::Type;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::error::{ErrorKind, LauncherError};
+use crate::plan::FilePlan;
+use crate::state::SharedState;
+use crate::LauncherConfig;
+
+/// Command line switch that starts the window in observer mode
+pub const SWITCH: &str = "--observer";
+
+/// Commands that only read: status, health, logs and reports. Anything that
+/// writes a file, starts Unity or the MCP server or talks to Banter is left
+/// out, even when it also reports something.
+const READ_ONLY_COMMANDS: &[&str] = &[
+    // Only reads the file in observer mode
+    "load_config",
+    "validate_unity_scene",
+    "find_channel_for_path",
+    "get_claude_mcp_config",
+    "check_unity_extension",
+    "get_mcp_root",
+    "get_banter_sdk_info",
+    "get_channel_health",
+    "get_activation_check",
+    "check_all_channels",
+    "get_account_status",
+    "get_preview_server_status",
+    "get_control_api_status",
+    "get_simulation_status",
+    "get_feature_flags",
+    "list_backups",
+    "get_other_instances",
+    "list_snapshots",
+    "get_git_status",
+    "get_editor_status",
+    "get_dashboard",
+    "generate_setup_report",
+    "get_message_catalogue",
+    "read_file_chunk",
+    "read_file_tail",
+    "find_unity_projects",
+    "list_project_assets",
+    "resolve_guid",
+    "find_references",
+    "get_server_pool",
+    "get_schedules",
+    "focus_unity_window",
+    "focus_launcher_window",
+    "get_job_status",
+    "list_jobs",
+    "get_job_history",
+    "get_support_mode",
+    "get_capabilities",
+    "get_incomplete_operations",
+    "list_channel_secrets",
+    "check_project_session",
+    "get_write_violations",
+    "get_startup_report",
+    "list_sessions",
+    "diff_scenes",
+    "export_session",
+    "get_telemetry",
+    "get_env_bootstrap",
+    "get_observer_mode",
+    "set_observer_mode",
+];
+
+/// Set by `--observer` for the life of the process
+static FORCED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ObserverStatus {
+    pub active: bool,
+    /// Started with `--observer`, so the config setting has no effect
+    pub forced_by_switch: bool,
+}
+
+pub fn set_forced(forced: bool) {
+    FORCED.store(forced, Ordering::Relaxed);
+}
+
+pub fn is_active(config: &LauncherConfig) -> bool {
+    FORCED.load(Ordering::Relaxed) || config.observer_mode
+}
+
+pub fn status(config: &LauncherConfig) -> ObserverStatus {
+    ObserverStatus {
+        active: is_active(config),
+        forced_by_switch: FORCED.load(Ordering::Relaxed),
+    }
+}
+
+/// Whether `command` may run in observer mode
+pub fn allows(command: &str) -> bool {
+    READ_ONLY_COMMANDS.contains(&command)
+}
+
+/// Refuse `action` in observer mode
+pub fn check(config: &LauncherConfig, action: &str) -> Result<(), LauncherError> {
+    if !is_active(config) {
+        return Ok(());
+    }
+    let hint = if FORCED.load(Ordering::Relaxed) {
+        "The launcher was started with --observer; restart it without"
+    } else {
+        "Turn observer mode off in Settings, or set observer_mode to false"
+    };
+    Err(LauncherError::new(
+        ErrorKind::PermissionDenied,
+        format!("Observer mode is on; {} is disabled", action),
+    )
+    .with_hint(hint))
+}
+
+/// Wrap the window's command handler so that observer mode refuses every
+/// command outside `READ_ONLY_COMMANDS`
+pub fn guard<F>(
+    state: SharedState,
+    handler: F,
+) -> impl Fn(tauri::ipc::Invoke) -> bool + Send + Sync + 'static
+where
+    F: Fn(tauri::ipc::Invoke) -> bool + Send + Sync + 'static,
+{
+    move |invoke| {
+        let command = invoke.message.command().to_string();
+        if !allows(&command) {
+            if let Err(e) = check(&state.config(), &command) {
+                invoke.resolver.reject(e);
+                return true;
+            }
+        }
+        handler(invoke)
+    }
+}
+
+/// Whether observer mode is on, and whether the window may turn it off
+#[tauri::command]
+#[specta::specta]
+pub fn get_observer_mode(state: tauri::State<'_, SharedState>) -> ObserverStatus {
+    status(&state.config())
+}
+
+/// Turn the config's observer mode on or off. Off is refused while
+/// `--observer` holds it on.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_observer_mode(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, SharedState>,
+    enabled: bool,
+) -> Result<ObserverStatus, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Observer mode", move || {
+        if !enabled && FORCED.load(Ordering::Relaxed) {
+            return Err(LauncherError::new(
+                ErrorKind::Conflict,
+                "The launcher was started with --observer",
+            )
+            .with_hint("Restart it without --observer to make changes"));
+        }
+        let mut plan = FilePlan::new(false);
+        let config = state.update(&mut plan, |config| {
+            config.observer_mode = enabled;
+            Ok(config.clone())
+        })?;
+        crate::fs_scope::sync(&app, &config);
+        Ok(status(&config))
+    })
+    .await
+}
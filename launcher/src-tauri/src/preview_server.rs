@@ -0,0 +1,248 @@
+//! Localhost static file server for previewing a channel's space content
+//!
+//! Serves `Assets/WebRoot` at `/` and built bundles at `/bundles/<platform>/`,
+//! so Banter's "visit local URL" flow can load them without a separate web server.
+
+use serde::Serialize;
+use specta::Type;
+use std::fs::File;
+use std::io;
+use std::net::{TcpListener, TcpStream};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crate::error::{ErrorKind, LauncherError};
+use crate::state::{AppState, SharedState};
+use crate::{bundles, http, metrics};
+
+/// Handle to the running server, held in `AppState`
+pub struct RunningServer {
+    channel_id: String,
+    port: u16,
+    stop: Arc<AtomicBool>,
+    /// Joined on stop so a restart can bind the same port again
+    thread: thread::JoinHandle<()>,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct PreviewServerStatus {
+    pub running: bool,
+    pub channel_id: Option<String>,
+    pub port: Option<u16>,
+    pub url: Option<String>,
+}
+
+fn content_type(path: &Path) -> &'static str {
+    match path.extension().and_then(|e| e.to_str()).unwrap_or("") {
+        "html" | "htm" => "text/html; charset=utf-8",
+        "js" | "mjs" => "text/javascript; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "json" | "manifest" => "application/json",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "wasm" => "application/wasm",
+        "glb" => "model/gltf-binary",
+        "mp3" => "audio/mpeg",
+        "ogg" => "audio/ogg",
+        "mp4" => "video/mp4",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Map a request path onto the project, refusing anything that escapes its root
+fn resolve(project: &Path, request_path: &str) -> Option<PathBuf> {
+    let relative = request_path.trim_start_matches('/');
+    let (root, rest) = match relative.strip_prefix("bundles/") {
+        Some(rest) => {
+            let (platform, rest) = rest.split_once('/').unwrap_or((rest, ""));
+            if platform.is_empty() || !platform.chars().all(|c| c.is_ascii_alphanumeric()) {
+                return None;
+            }
+            (bundles::bundle_output_dir(project, platform), rest)
+        }
+        None => (project.join("Assets").join("WebRoot"), relative),
+    };
+
+    let rest = Path::new(rest);
+    if rest
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_)))
+    {
+        return None;
+    }
+
+    let path = root.join(rest);
+    if path.is_dir() {
+        Some(path.join("index.html"))
+    } else {
+        Some(path)
+    }
+}
+
+fn handle(mut stream: TcpStream, project: &Path) -> io::Result<()> {
+    let request = http::read_request(&stream)?;
+
+    if request.method != "GET" && request.method != "HEAD" {
+        return http::write_response(&mut stream, 405, "text/plain", b"Method not allowed");
+    }
+
+    let Some(path) = resolve(project, &request.path) else {
+        return http::write_response(&mut stream, 403, "text/plain", b"Forbidden");
+    };
+    let Ok(mut file) = File::open(&path) else {
+        return http::write_response(&mut stream, 404, "text/plain", b"Not found");
+    };
+
+    let length = file.metadata()?.len();
+    http::write_head(&mut stream, 200, content_type(&path), length)?;
+    if request.method == "GET" {
+        io::copy(&mut file, &mut stream)?;
+    }
+    Ok(())
+}
+
+fn serve(listener: TcpListener, project: PathBuf, stop: Arc<AtomicBool>) {
+    // Non-blocking accept so the loop notices the stop flag
+    if listener.set_nonblocking(true).is_err() {
+        return;
+    }
+
+    while !stop.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                let project = project.clone();
+                thread::spawn(move || {
+                    let _ = stream.set_nonblocking(false);
+                    let _ = stream.set_read_timeout(Some(Duration::from_secs(10)));
+                    let _ = handle(stream, &project);
+                });
+            }
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                thread::sleep(Duration::from_millis(50));
+            }
+            Err(_) => thread::sleep(Duration::from_millis(200)),
+        }
+    }
+}
+
+fn status(server: Option<&RunningServer>) -> PreviewServerStatus {
+    match server {
+        Some(s) => PreviewServerStatus {
+            running: true,
+            channel_id: Some(s.channel_id.clone()),
+            port: Some(s.port),
+            url: Some(format!("http://localhost:{}/", s.port)),
+        },
+        None => PreviewServerStatus {
+            running: false,
+            channel_id: None,
+            port: None,
+            url: None,
+        },
+    }
+}
+
+/// Stop the preview server if one is running
+pub fn stop(state: &AppState) {
+    let running = state
+        .preview_server
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take();
+    if let Some(server) = running {
+        server.stop.store(true, Ordering::Relaxed);
+        let _ = server.thread.join();
+        metrics::server_stopped(metrics::PREVIEW_SERVER);
+    }
+}
+
+/// Status of the running server, if any
+pub fn current_status(state: &AppState) -> PreviewServerStatus {
+    let guard = state
+        .preview_server
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    status(guard.as_ref())
+}
+
+/// Serve a channel's content, replacing any running server, and record the
+/// served URL as the channel's local test URL
+pub fn start(
+    state: &AppState,
+    channel_id: &str,
+    port: Option<u16>,
+) -> Result<PreviewServerStatus, LauncherError> {
+    let config = state.config();
+    let channel = crate::find_channel(&config, channel_id)?;
+    let port = port.unwrap_or(config.preview_server_port);
+
+    stop(state);
+
+    let listener = TcpListener::bind(("127.0.0.1", port)).map_err(|e| {
+        LauncherError::new(
+            ErrorKind::Conflict,
+            format!("Failed to listen on port {}: {}", port, e),
+        )
+        .with_hint("Another program may be using this port - pick a different preview port")
+    })?;
+    let root = PathBuf::from(&channel.unity_project_path);
+    let stop_flag = Arc::new(AtomicBool::new(false));
+
+    let thread = {
+        let stop_flag = stop_flag.clone();
+        thread::spawn(move || serve(listener, root, stop_flag))
+    };
+
+    let server = RunningServer {
+        channel_id: channel_id.to_string(),
+        port,
+        stop: stop_flag,
+        thread,
+    };
+    let result = status(Some(&server));
+    *state
+        .preview_server
+        .lock()
+        .unwrap_or_else(|e| e.into_inner()) = Some(server);
+    metrics::server_started(metrics::PREVIEW_SERVER);
+
+    let url = result.url.clone();
+    state.update_channel(channel_id, move |c| c.local_test_url = url)?;
+
+    Ok(result)
+}
+
+/// Serve a channel's content on localhost; replaces any running preview server.
+/// The served URL is recorded as the channel's local test URL.
+#[tauri::command]
+#[specta::specta]
+pub async fn start_preview_server(
+    state: tauri::State<'_, SharedState>,
+    channel_id: String,
+    port: Option<u16>,
+) -> Result<PreviewServerStatus, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Preview server", move || start(&state, &channel_id, port)).await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn stop_preview_server(
+    state: tauri::State<'_, SharedState>,
+) -> Result<PreviewServerStatus, LauncherError> {
+    stop(&state);
+    Ok(status(None))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_preview_server_status(
+    state: tauri::State<'_, SharedState>,
+) -> Result<PreviewServerStatus, LauncherError> {
+    Ok(current_status(&state))
+}
@@ -0,0 +1,107 @@
+//! Per-project indexes kept in the store's `cache` collection
+//!
+//! The asset inventory and the GUID index both walk a project's folders and
+//! keep what they found by project path, along with the mtime of every
+//! folder walked. A `ProjectCache` holds one kind of index: it is read from
+//! its `cache/<key>` document (see `storage`) on first use and written back
+//! whenever an index changes. An index is current while every folder it
+//! walked has the mtime it had then, since adding, removing or renaming a
+//! file bumps its folder's mtime.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::UNIX_EPOCH;
+
+use crate::storage;
+
+pub fn mtime_ms(path: &Path) -> Option<i64> {
+    let modified = path.metadata().ok()?.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_millis() as i64)
+}
+
+/// Whether every walked folder in `mtimes` (path -> Unix ms) is unchanged;
+/// an index that walked nothing never is
+pub fn is_current(mtimes: &BTreeMap<String, i64>) -> bool {
+    !mtimes.is_empty()
+        && mtimes
+            .iter()
+            .all(|(path, mtime)| mtime_ms(Path::new(path)) == Some(*mtime))
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Document<T> {
+    #[serde(default)]
+    projects: BTreeMap<String, T>,
+}
+
+pub struct ProjectCache<T> {
+    /// Document of the `cache` collection
+    key: &'static str,
+    entries: Mutex<Document<T>>,
+    /// Whether the document has been read into `entries`
+    loaded: AtomicBool,
+}
+
+impl<T: Default + Serialize + DeserializeOwned> ProjectCache<T> {
+    pub fn new(key: &'static str) -> Self {
+        ProjectCache {
+            key,
+            entries: Mutex::new(Document {
+                projects: BTreeMap::new(),
+            }),
+            loaded: AtomicBool::new(false),
+        }
+    }
+
+    /// Cached indexes, read from the store on first use
+    fn lock(&self) -> MutexGuard<'_, Document<T>> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if !self.loaded.swap(true, Ordering::AcqRel) {
+            *entries = storage::store()
+                .get("cache", self.key)
+                .ok()
+                .flatten()
+                .and_then(|content| serde_json::from_str(&content).ok())
+                .unwrap_or_default();
+        }
+        entries
+    }
+
+    fn persist(&self, entries: &Document<T>) {
+        let Ok(content) = serde_json::to_string(entries) else {
+            return;
+        };
+        if let Err(e) = storage::store().put("cache", self.key, &content) {
+            eprintln!("{}", e);
+        }
+    }
+
+    /// Run `f` on the project's index, an empty one if there is none yet.
+    /// `f` returns its result and whether it changed the index, which is
+    /// then written back.
+    pub fn with<R>(&self, project: &Path, f: impl FnOnce(&mut T) -> (R, bool)) -> R {
+        let key = project.to_string_lossy().to_string();
+        let mut entries = self.lock();
+        let (result, changed) = f(entries.projects.entry(key).or_default());
+        if changed {
+            self.persist(&entries);
+        }
+        result
+    }
+
+    /// Forget indexes at or below `path`, or everything with `None`
+    pub fn invalidate(&self, path: Option<&Path>) {
+        let mut entries = self.lock();
+        match path {
+            Some(path) => entries
+                .projects
+                .retain(|key, _| !Path::new(key).starts_with(path)),
+            None => entries.projects.clear(),
+        }
+        self.persist(&entries);
+    }
+}
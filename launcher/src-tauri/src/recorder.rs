@@ -0,0 +1,497 @@
+//! Session recorder
+//!
+//! With `record_sessions` on, every activation is recorded as a session.
+//! Claude Code's `banter` entry starts the MCP server through the launcher's
+//! stdio proxy (see `mcp_proxy`), which notes each `tools/call` and its
+//! result, the bridge commands the server queues and editor connects and
+//! disconnects. The session opens and closes with a scene snapshot (see
+//! `snapshots`), and closing it records what changed in each scene between
+//! the two (see `scene_diff`).
+//!
+//! A session's record is kept in the launcher's store (collection
+//! `sessions`, see `storage`) and its events in `recordings/<id>/events.jsonl`
+//! in the launcher folder, appended to a line at a time by the window and the
+//! proxy. Sessions are pruned like backups
+//! (`backup_retention`), per channel. `export_session` bundles one into a
+//! single JSON file together with its snapshots' scene files, so a session
+//! can be reviewed or replayed on another machine once the snapshots are
+//! pruned.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::cmp::Reverse;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Once;
+
+use crate::error::LauncherError;
+use crate::plan::FilePlan;
+use crate::snapshots::{self, Snapshot};
+use crate::state::{AppState, SharedState};
+use crate::{bridge, scene_diff, storage, LauncherConfig, ProjectChannel};
+
+const COLLECTION: &str = "sessions";
+/// Where a session's record was kept before it moved to the store
+const RECORD_FILE: &str = "session.json";
+const EVENTS_FILE: &str = "events.jsonl";
+
+/// `format` of an exported session
+const EXPORT_FORMAT: &str = "banter-session";
+const EXPORT_VERSION: u32 = 1;
+
+pub const SESSION_START: &str = "session-start";
+pub const SESSION_END: &str = "session-end";
+pub const SNAPSHOT: &str = "snapshot";
+pub const SCENE_DIFF: &str = "scene-diff";
+pub const TOOL_CALL: &str = "tool-call";
+pub const TOOL_RESULT: &str = "tool-result";
+pub const BRIDGE_COMMAND: &str = "bridge-command";
+pub const BRIDGE_CONNECTED: &str = "bridge-connected";
+pub const BRIDGE_DISCONNECTED: &str = "bridge-disconnected";
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct RecordedSession {
+    pub id: String,
+    pub channel_id: String,
+    pub channel_name: String,
+    /// Unix ms
+    pub started_at: i64,
+    /// `None` while the channel is still active
+    pub ended_at: Option<i64>,
+    /// Filled in when listing
+    #[serde(default, skip_deserializing)]
+    pub tool_calls: u32,
+    #[serde(default, skip_deserializing)]
+    pub events: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SessionEvent {
+    /// Unix ms
+    pub at: i64,
+    /// `session-start`, `session-end`, `snapshot`, `scene-diff`,
+    /// `tool-call`, `tool-result`, `bridge-command`, `bridge-connected` or
+    /// `bridge-disconnected`
+    pub kind: String,
+    /// Depends on `kind`, e.g. `{id, tool, arguments}` for `tool-call`
+    pub data: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct SessionExport {
+    /// Suggested file name, e.g. "Lobby-<session id>.banter-session.json"
+    pub file_name: String,
+    pub content: String,
+}
+
+#[derive(Serialize)]
+struct ExportedFile {
+    /// Relative to the project, with `/` separators
+    path: String,
+    /// `None` for files that aren't text
+    content: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ExportedSnapshot {
+    snapshot: Snapshot,
+    /// Empty once the snapshot has been pruned
+    files: Vec<ExportedFile>,
+}
+
+#[derive(Serialize)]
+struct ExportFile {
+    format: &'static str,
+    version: u32,
+    session: RecordedSession,
+    events: Vec<SessionEvent>,
+    snapshots: Vec<ExportedSnapshot>,
+}
+
+fn recordings_dir() -> PathBuf {
+    crate::launcher_dir().join("recordings")
+}
+
+fn session_dir(id: &str) -> PathBuf {
+    recordings_dir().join(id)
+}
+
+fn write_record(session: &RecordedSession) -> Result<(), LauncherError> {
+    let record = serde_json::to_string_pretty(session)
+        .map_err(|e| LauncherError::internal(format!("Failed to serialize session: {}", e)))?;
+    storage::store().put(COLLECTION, &session.id, &record)
+}
+
+/// Add an event to a session's log. The window and the proxy both append,
+/// one line at a time.
+pub fn append(session_id: &str, kind: &str, data: serde_json::Value) -> Result<(), LauncherError> {
+    let path = session_dir(session_id).join(EVENTS_FILE);
+    let event = SessionEvent {
+        at: bridge::now_ms(),
+        kind: kind.to_string(),
+        data,
+    };
+    let line = serde_json::to_string(&event)
+        .map_err(|e| LauncherError::internal(format!("Failed to serialize event: {}", e)))?;
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| writeln!(file, "{}", line))
+        .map_err(|e| LauncherError::io("Failed to write", &path, e))
+}
+
+/// Events of a session, oldest first
+pub fn events(session_id: &str) -> Result<Vec<SessionEvent>, LauncherError> {
+    if storage::store().get(COLLECTION, session_id)?.is_none() {
+        return Err(LauncherError::not_found(format!(
+            "No recorded session {}",
+            session_id
+        )));
+    }
+    let path = session_dir(session_id).join(EVENTS_FILE);
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(LauncherError::io("Failed to read", &path, e)),
+    };
+    // A line cut short by a crash is skipped rather than failing the session
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+fn read_record(id: &str) -> Option<RecordedSession> {
+    serde_json::from_str(&storage::store().get(COLLECTION, id).ok()??).ok()
+}
+
+/// Move records left in `recordings/<id>/session.json` by an older launcher
+/// into the store, once per process
+fn adopt_old_records() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        let Ok(read_dir) = fs::read_dir(recordings_dir()) else {
+            return;
+        };
+        for item in read_dir.flatten() {
+            let path = item.path().join(RECORD_FILE);
+            let id = item.file_name().to_string_lossy().to_string();
+            let Ok(record) = fs::read_to_string(&path) else {
+                continue;
+            };
+            if storage::store().put(COLLECTION, &id, &record).is_ok() {
+                fs::remove_file(&path).ok();
+            }
+        }
+    });
+}
+
+/// Session records without their event counts, newest first
+fn records(channel_id: Option<&str>) -> Vec<RecordedSession> {
+    adopt_old_records();
+    let mut sessions: Vec<RecordedSession> = storage::store()
+        .list(COLLECTION)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(_, value)| serde_json::from_str(&value).ok())
+        .filter(|s: &RecordedSession| channel_id.is_none_or(|id| s.channel_id == id))
+        .collect();
+    sessions.sort_by_key(|s| Reverse(s.started_at));
+    sessions
+}
+
+fn with_counts(mut session: RecordedSession) -> RecordedSession {
+    let events = events(&session.id).unwrap_or_default();
+    session.events = events.len() as u32;
+    session.tool_calls = events.iter().filter(|e| e.kind == TOOL_CALL).count() as u32;
+    session
+}
+
+/// Recorded sessions, newest first, of one channel or all of them
+pub fn list(channel_id: Option<&str>) -> Vec<RecordedSession> {
+    records(channel_id).into_iter().map(with_counts).collect()
+}
+
+/// The channel's session that is still being recorded. Cheap enough for the
+/// proxy to ask on every tool call.
+pub fn open_session(channel_id: &str) -> Option<RecordedSession> {
+    records(Some(channel_id))
+        .into_iter()
+        .find(|s| s.ended_at.is_none())
+}
+
+fn prune(config: &LauncherConfig, channel_id: &str) {
+    let retention = &config.backup_retention;
+    let cutoff = (retention.max_age_days > 0)
+        .then(|| bridge::now_ms() - retention.max_age_days as i64 * 24 * 60 * 60 * 1000);
+    let ended = records(Some(channel_id))
+        .into_iter()
+        .filter(|s| s.ended_at.is_some());
+    for (index, session) in ended.enumerate() {
+        if index >= retention.max_per_kind || cutoff.is_some_and(|c| session.started_at < c) {
+            storage::store().delete(COLLECTION, &session.id).ok();
+            fs::remove_dir_all(session_dir(&session.id)).ok();
+        }
+    }
+}
+
+/// Snapshot the channel's scenes into the session; an unchanged scene
+/// reuses the latest snapshot
+fn snapshot(
+    config: &LauncherConfig,
+    channel: &ProjectChannel,
+    session_id: &str,
+) -> Result<(), LauncherError> {
+    let snapshot = match snapshots::create(config, channel, "session", false, true)? {
+        Some(snapshot) => snapshot,
+        None => snapshots::list(&channel.id)
+            .into_iter()
+            .next()
+            .ok_or_else(|| LauncherError::internal("The latest snapshot disappeared"))?,
+    };
+    append(
+        session_id,
+        SNAPSHOT,
+        serde_json::json!({ "snapshot_id": snapshot.id, "files": snapshot.files.len() }),
+    )
+}
+
+/// Open a new session for the channel, closing any it still has open
+pub fn start(
+    config: &LauncherConfig,
+    channel: &ProjectChannel,
+) -> Result<RecordedSession, LauncherError> {
+    end(config, channel)?;
+    let started_at = bridge::now_ms();
+    let session = RecordedSession {
+        id: format!(
+            "{}-{}",
+            started_at,
+            &uuid::Uuid::new_v4().simple().to_string()[..8]
+        ),
+        channel_id: channel.id.clone(),
+        channel_name: channel.name.clone(),
+        started_at,
+        ended_at: None,
+        tool_calls: 0,
+        events: 0,
+    };
+    FilePlan::new(false).create_dir(&session_dir(&session.id))?;
+    write_record(&session)?;
+    append(
+        &session.id,
+        SESSION_START,
+        serde_json::json!({
+            "unity_project_path": channel.unity_project_path,
+            "scene_path": channel.scene_path,
+        }),
+    )?;
+    // A scene that can't be snapshotted still gets its tool calls recorded
+    if let Err(e) = snapshot(config, channel, &session.id) {
+        eprintln!("Failed to snapshot {} for its session: {}", channel.id, e);
+    }
+    Ok(session)
+}
+
+/// Snapshots a session took, in order
+fn snapshot_ids(events: &[SessionEvent]) -> Vec<&str> {
+    let mut ids: Vec<&str> = events
+        .iter()
+        .filter(|e| e.kind == SNAPSHOT)
+        .filter_map(|e| e.data.get("snapshot_id")?.as_str())
+        .collect();
+    ids.dedup();
+    ids
+}
+
+/// Record what changed in each scene between the session's first and last
+/// snapshot
+fn diff_snapshots(channel: &ProjectChannel, session_id: &str) -> Result<(), LauncherError> {
+    let events = events(session_id)?;
+    let ids = snapshot_ids(&events);
+    let (Some(first), Some(last)) = (ids.first(), ids.last()) else {
+        return Ok(());
+    };
+    if first == last {
+        return Ok(());
+    }
+    let Some(before) = snapshots::list(&channel.id)
+        .into_iter()
+        .find(|s| s.id == *first)
+    else {
+        return Ok(());
+    };
+    let (before_dir, after_dir) = (
+        snapshots::snapshot_dir(&channel.id, first),
+        snapshots::snapshot_dir(&channel.id, last),
+    );
+    for file in before.files.iter().filter(|f| f.path.ends_with(".unity")) {
+        let after = after_dir.join(&file.path);
+        if !after.is_file() {
+            continue;
+        }
+        let diff = scene_diff::diff_files(&before_dir.join(&file.path), &after)?;
+        append(
+            session_id,
+            SCENE_DIFF,
+            serde_json::json!({ "scene": file.path, "diff": diff }),
+        )?;
+    }
+    Ok(())
+}
+
+/// Close the channel's open session, if any, with a final snapshot and a
+/// diff of its scenes
+pub fn end(config: &LauncherConfig, channel: &ProjectChannel) -> Result<(), LauncherError> {
+    let Some(mut session) = open_session(&channel.id) else {
+        return Ok(());
+    };
+    match snapshot(config, channel, &session.id) {
+        Ok(()) => {
+            if let Err(e) = diff_snapshots(channel, &session.id) {
+                eprintln!("Failed to diff the scenes of {}: {}", channel.id, e);
+            }
+        }
+        Err(e) => eprintln!("Failed to snapshot {} for its session: {}", channel.id, e),
+    }
+    append(&session.id, SESSION_END, serde_json::json!({}))?;
+    session.ended_at = Some(bridge::now_ms());
+    write_record(&session)?;
+    prune(config, &channel.id);
+    Ok(())
+}
+
+/// Start recording the newly active channel; called on every activation.
+/// Failures are logged rather than blocking the activation.
+pub fn channel_activated(config: &LauncherConfig, channel: &ProjectChannel) {
+    if !config.record_sessions {
+        return;
+    }
+    if let Err(e) = start(config, channel) {
+        eprintln!("Failed to start recording {}: {}", channel.id, e);
+    }
+}
+
+/// Stop recording a channel that is no longer active
+pub fn channel_deactivated(config: &LauncherConfig, channel: &ProjectChannel) {
+    if let Err(e) = end(config, channel) {
+        eprintln!("Failed to end the session of {}: {}", channel.id, e);
+    }
+}
+
+fn exported_snapshot(channel_id: &str, snapshot_id: &str) -> Option<ExportedSnapshot> {
+    let snapshot = snapshots::list(channel_id)
+        .into_iter()
+        .find(|s| s.id == snapshot_id)?;
+    let dir = snapshots::snapshot_dir(channel_id, snapshot_id);
+    let files = snapshot
+        .files
+        .iter()
+        .map(|file| ExportedFile {
+            path: file.path.clone(),
+            content: fs::read_to_string(dir.join(&file.path)).ok(),
+        })
+        .collect();
+    Some(ExportedSnapshot { snapshot, files })
+}
+
+pub fn export(session_id: &str) -> Result<SessionExport, LauncherError> {
+    let session = read_record(session_id)
+        .map(with_counts)
+        .ok_or_else(|| LauncherError::not_found(format!("No recorded session {}", session_id)))?;
+    let events = events(session_id)?;
+    let snapshots = snapshot_ids(&events)
+        .into_iter()
+        .filter_map(|id| exported_snapshot(&session.channel_id, id))
+        .collect();
+
+    let file_name = format!(
+        "{}-{}.banter-session.json",
+        session.channel_name, session.id
+    )
+    .replace(|c: char| "<>:\"/\\|?*".contains(c), "_");
+    let file = ExportFile {
+        format: EXPORT_FORMAT,
+        version: EXPORT_VERSION,
+        session,
+        events,
+        snapshots,
+    };
+    let content = serde_json::to_string_pretty(&file)
+        .map_err(|e| LauncherError::internal(format!("Failed to serialize session: {}", e)))?;
+    Ok(SessionExport { file_name, content })
+}
+
+/// Turn session recording on or off. The active channel's session starts or
+/// ends at once, and with auto-configure on its Claude Code entry is
+/// rewritten to go through the proxy or not; Claude picks that up the next
+/// time it starts the server.
+pub fn set_enabled(
+    state: &AppState,
+    enabled: bool,
+) -> Result<Option<RecordedSession>, LauncherError> {
+    let mut plan = FilePlan::new(false);
+    let config = state.update(&mut plan, |config| {
+        config.record_sessions = enabled;
+        Ok(config.clone())
+    })?;
+    let Some(channel) = config
+        .active_channel_id
+        .as_deref()
+        .and_then(|id| config.channels.iter().find(|c| c.id == id))
+        .cloned()
+    else {
+        return Ok(None);
+    };
+
+    if config.auto_start {
+        crate::update_claude_mcp_config(
+            state,
+            channel.clone(),
+            config.mcp_server_path.clone(),
+            None,
+        )?;
+    }
+    if !enabled {
+        end(&config, &channel)?;
+        return Ok(None);
+    }
+    match open_session(&channel.id) {
+        Some(session) => Ok(Some(session)),
+        None => start(&config, &channel).map(Some),
+    }
+}
+
+/// Recorded sessions, newest first; only the channel's with `channel_id`
+#[tauri::command]
+#[specta::specta]
+pub async fn list_sessions(
+    channel_id: Option<String>,
+) -> Result<Vec<RecordedSession>, LauncherError> {
+    if let Some(id) = &channel_id {
+        crate::sanitize::channel_id(id)?;
+    }
+    crate::commands::blocking("List sessions", move || Ok(list(channel_id.as_deref()))).await
+}
+
+/// A recorded session as one JSON file: its events and the scene files of
+/// its snapshots
+#[tauri::command]
+#[specta::specta]
+pub async fn export_session(session_id: String) -> Result<SessionExport, LauncherError> {
+    crate::sanitize::identifier("Session id", &session_id)?;
+    crate::commands::blocking("Export session", move || export(&session_id)).await
+}
+
+/// Turn session recording on or off; returns the active channel's session
+#[tauri::command]
+#[specta::specta]
+pub async fn set_session_recording(
+    state: tauri::State<'_, SharedState>,
+    enabled: bool,
+) -> Result<Option<RecordedSession>, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Session recording", move || set_enabled(&state, enabled)).await
+}
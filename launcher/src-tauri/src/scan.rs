@@ -0,0 +1,509 @@
+//! Cached project and scene scans
+//!
+//! Walking a large Unity project is slow, so scan results are kept in the
+//! store's `cache/scans` document (see `storage`). An entry stays valid while every
+//! directory it walked has the same mtime (adding, removing or renaming a file
+//! bumps its directory's mtime).
+//!
+//! For channel projects a watcher on the Assets folder keeps the scene list
+//! current without rescanning: events are collected into batches (an asset
+//! import can produce thousands) and only the touched paths are re-examined.
+//!
+//! Neither happens at launch: the cache file is read on first use and the
+//! watcher is started by `start_watching` once the window is up.
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+use crate::error::LauncherError;
+use crate::state::SharedState;
+use crate::{sanitize, storage, unity};
+
+/// How deep `find_projects` looks below the search root
+const MAX_SEARCH_DEPTH: usize = 4;
+
+/// Directories never worth descending into when looking for projects
+const SKIPPED_DIRS: &[&str] = &["node_modules", "Library", "Temp", "Logs", "obj"];
+
+/// A watcher batch is applied once events have stopped for this long...
+const BATCH_QUIET: Duration = Duration::from_millis(300);
+
+/// ...or after this long, so a long import still updates the list as it goes
+const BATCH_MAX_WAIT: Duration = Duration::from_secs(2);
+
+/// Scenes and editor version of one Unity project
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ProjectScan {
+    pub project_path: String,
+    pub unity_version: Option<String>,
+    pub scenes: Vec<String>,
+    /// Unix ms
+    pub scanned_at: i64,
+    /// Served from the cache rather than walked for this call
+    #[serde(default)]
+    pub cached: bool,
+}
+
+/// Unity projects found below a directory
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ProjectSearch {
+    pub root: String,
+    pub projects: Vec<String>,
+    /// Unix ms
+    pub scanned_at: i64,
+    #[serde(default)]
+    pub cached: bool,
+}
+
+/// A scan result with the mtimes that keep it valid
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Cached<T> {
+    result: T,
+    /// mtime (Unix ms) of every walked directory, plus ProjectVersion.txt for projects
+    mtimes: BTreeMap<String, i64>,
+}
+
+impl<T> Cached<T> {
+    fn is_current(&self) -> bool {
+        !self.mtimes.is_empty()
+            && self
+                .mtimes
+                .iter()
+                .all(|(path, mtime)| mtime_ms(Path::new(path)) == Some(*mtime))
+    }
+}
+
+impl Cached<ProjectScan> {
+    /// Update the scene list for paths reported by the watcher. Each path is
+    /// looked at as it is now, so create, delete and both halves of a rename
+    /// are handled the same way.
+    fn apply_changes(&mut self, paths: &[&PathBuf]) {
+        let mut scenes: BTreeSet<String> = self.result.scenes.drain(..).collect();
+
+        for path in paths {
+            if path.is_dir() {
+                walk_scenes(path, &mut scenes, &mut self.mtimes);
+            } else if path.exists() {
+                if is_scene(path) {
+                    scenes.insert(path.to_string_lossy().to_string());
+                }
+            } else {
+                // Gone: a single file or a whole folder
+                scenes.retain(|s| !Path::new(s).starts_with(path));
+                self.mtimes.retain(|d, _| !Path::new(d).starts_with(path));
+            }
+            if let Some(parent) = path.parent() {
+                record(&mut self.mtimes, parent);
+            }
+        }
+
+        self.result.scenes = scenes.into_iter().collect();
+        self.result.scanned_at = crate::bridge::now_ms();
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    #[serde(default)]
+    projects: BTreeMap<String, Cached<ProjectScan>>,
+    #[serde(default)]
+    searches: BTreeMap<String, Cached<ProjectSearch>>,
+}
+
+impl CacheFile {
+    /// Apply a batch of watcher paths to the cached projects they fall under;
+    /// returns whether any entry changed
+    fn apply_changes(&mut self, paths: &HashSet<PathBuf>) -> bool {
+        let mut changed = false;
+        for (project, scan) in self.projects.iter_mut() {
+            let assets = Path::new(project).join("Assets");
+            let touched: Vec<&PathBuf> = paths.iter().filter(|p| p.starts_with(&assets)).collect();
+            if !touched.is_empty() {
+                scan.apply_changes(&touched);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Drop entries at or below `path`; returns whether anything was removed
+    fn invalidate(&mut self, path: &Path) -> bool {
+        let before = self.projects.len() + self.searches.len();
+        self.projects
+            .retain(|key, _| !Path::new(key).starts_with(path));
+        self.searches.retain(|_, search| {
+            !search
+                .mtimes
+                .keys()
+                .any(|dir| Path::new(dir).starts_with(path))
+        });
+        before != self.projects.len() + self.searches.len()
+    }
+}
+
+/// Document of the `cache` store collection (see `storage`)
+const CACHE_KEY: &str = "scans";
+
+fn mtime_ms(path: &Path) -> Option<i64> {
+    let modified = path.metadata().ok()?.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_millis() as i64)
+}
+
+fn record(mtimes: &mut BTreeMap<String, i64>, path: &Path) {
+    if let Some(mtime) = mtime_ms(path) {
+        mtimes.insert(path.to_string_lossy().to_string(), mtime);
+    }
+}
+
+fn is_project(dir: &Path) -> bool {
+    dir.join("Assets").is_dir()
+        && dir
+            .join("ProjectSettings")
+            .join("ProjectVersion.txt")
+            .is_file()
+}
+
+/// Walk a project's Assets folder for scenes
+fn is_scene(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()) == Some("unity")
+}
+
+/// Collect scenes below `dir`, recording the mtime of each directory walked
+fn walk_scenes(dir: &Path, scenes: &mut BTreeSet<String>, mtimes: &mut BTreeMap<String, i64>) {
+    let mut pending = vec![dir.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        record(mtimes, &dir);
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if is_scene(&path) {
+                scenes.insert(path.to_string_lossy().to_string());
+            }
+        }
+    }
+}
+
+fn scan_project(project: &Path) -> Cached<ProjectScan> {
+    let mut mtimes = BTreeMap::new();
+    let mut scenes = BTreeSet::new();
+    walk_scenes(&project.join("Assets"), &mut scenes, &mut mtimes);
+    record(
+        &mut mtimes,
+        &project.join("ProjectSettings").join("ProjectVersion.txt"),
+    );
+
+    Cached {
+        result: ProjectScan {
+            project_path: project.to_string_lossy().to_string(),
+            unity_version: unity::project_editor_version(project),
+            scenes: scenes.into_iter().collect(),
+            scanned_at: crate::bridge::now_ms(),
+            cached: false,
+        },
+        mtimes,
+    }
+}
+
+/// Look for Unity projects below `root`, without descending into them
+fn search_projects(root: &Path) -> Cached<ProjectSearch> {
+    let mut mtimes = BTreeMap::new();
+    let mut projects = Vec::new();
+    let mut pending = vec![(root.to_path_buf(), 0)];
+
+    while let Some((dir, depth)) = pending.pop() {
+        record(&mut mtimes, &dir);
+        if is_project(&dir) {
+            projects.push(dir.to_string_lossy().to_string());
+            continue;
+        }
+        if depth >= MAX_SEARCH_DEPTH {
+            continue;
+        }
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if path.is_dir() && !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_str()) {
+                pending.push((path, depth + 1));
+            }
+        }
+    }
+
+    projects.sort();
+    Cached {
+        result: ProjectSearch {
+            root: root.to_string_lossy().to_string(),
+            projects,
+            scanned_at: crate::bridge::now_ms(),
+            cached: false,
+        },
+        mtimes,
+    }
+}
+
+/// Scan results shared between commands and the watcher
+#[derive(Default)]
+pub struct ScanCache {
+    entries: Arc<Mutex<CacheFile>>,
+    /// Whether `cache/scans` has been read into `entries`
+    loaded: AtomicBool,
+    /// Set by `start_watching`; until then `watch` does nothing
+    watching: AtomicBool,
+    /// Watcher and the Assets folders it currently covers
+    watcher: Mutex<Option<(RecommendedWatcher, HashSet<PathBuf>)>>,
+}
+
+impl ScanCache {
+    /// Load the persisted cache; a missing or unreadable file starts empty
+    /// Cached entries, read from disk on first use
+    fn lock(&self) -> MutexGuard<'_, CacheFile> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if !self.loaded.swap(true, Ordering::AcqRel) {
+            *entries = storage::store()
+                .get("cache", CACHE_KEY)
+                .ok()
+                .flatten()
+                .and_then(|content| serde_json::from_str(&content).ok())
+                .unwrap_or_default();
+        }
+        entries
+    }
+
+    /// Read the cache file now rather than on the first scan
+    pub fn preload(&self) {
+        drop(self.lock());
+    }
+
+    fn persist(entries: &CacheFile) {
+        let Ok(content) = serde_json::to_string(entries) else {
+            return;
+        };
+        if let Err(e) = storage::store().put("cache", CACHE_KEY, &content) {
+            eprintln!("{}", e);
+        }
+    }
+
+    /// Scenes and Unity version of a project, rescanned only if it changed
+    pub fn project(&self, project: &Path, refresh: bool) -> ProjectScan {
+        let key = project.to_string_lossy().to_string();
+        if !refresh {
+            let entries = self.lock();
+            if let Some(scan) = entries.projects.get(&key).filter(|s| s.is_current()) {
+                return ProjectScan {
+                    cached: true,
+                    ..scan.result.clone()
+                };
+            }
+        }
+
+        let scan = scan_project(project);
+        let result = scan.result.clone();
+        let mut entries = self.lock();
+        entries.projects.insert(key, scan);
+        Self::persist(&entries);
+        result
+    }
+
+    /// Unity projects below `root`, searched again only if a walked directory changed
+    pub fn find_projects(&self, root: &Path, refresh: bool) -> ProjectSearch {
+        let key = root.to_string_lossy().to_string();
+        if !refresh {
+            let entries = self.lock();
+            if let Some(search) = entries.searches.get(&key).filter(|s| s.is_current()) {
+                return ProjectSearch {
+                    cached: true,
+                    ..search.result.clone()
+                };
+            }
+        }
+
+        let search = search_projects(root);
+        let result = search.result.clone();
+        let mut entries = self.lock();
+        entries.searches.insert(key, search);
+        Self::persist(&entries);
+        result
+    }
+
+    /// Forget cached scans at or below `path`, or everything with `None`
+    pub fn invalidate(&self, path: Option<&Path>) {
+        let mut entries = self.lock();
+        match path {
+            Some(path) => {
+                entries.invalidate(path);
+            }
+            None => *entries = CacheFile::default(),
+        }
+        Self::persist(&entries);
+    }
+
+    /// Load the cache and begin watching the config's channel projects
+    pub fn start_watching(&self, config: &crate::LauncherConfig) {
+        self.preload();
+        self.watching.store(true, Ordering::Release);
+        self.watch(config);
+    }
+
+    /// Watch the Assets folders of the config's channels and keep their cached
+    /// scene lists up to date. A no-op until `start_watching` has run.
+    pub fn watch(&self, config: &crate::LauncherConfig) {
+        if !self.watching.load(Ordering::Acquire) {
+            return;
+        }
+        let wanted: HashSet<PathBuf> = config
+            .channels
+            .iter()
+            .map(|c| Path::new(&c.unity_project_path).join("Assets"))
+            .filter(|assets| assets.is_dir())
+            .collect();
+
+        let mut guard = self.watcher.lock().unwrap_or_else(|e| e.into_inner());
+        if guard.is_none() {
+            let (tx, rx) = mpsc::channel();
+            let watcher =
+                notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                    let Ok(event) = event else {
+                        return;
+                    };
+                    // Content edits don't change what a scan finds; only files coming and going do
+                    let structural = matches!(
+                        event.kind,
+                        EventKind::Create(_)
+                            | EventKind::Remove(_)
+                            | EventKind::Modify(notify::event::ModifyKind::Name(_))
+                    );
+                    if structural {
+                        for path in event.paths {
+                            let _ = tx.send(path);
+                        }
+                    }
+                });
+            match watcher {
+                Ok(watcher) => *guard = Some((watcher, HashSet::new())),
+                Err(e) => {
+                    eprintln!("Failed to start scan watcher: {}", e);
+                    return;
+                }
+            }
+
+            let entries = self.entries.clone();
+            thread::spawn(move || apply_batches(rx, entries));
+        }
+
+        let Some((watcher, watched)) = guard.as_mut() else {
+            return;
+        };
+        for dir in watched.difference(&wanted) {
+            watcher.unwatch(dir).ok();
+        }
+        for dir in wanted.difference(watched) {
+            if let Err(e) = watcher.watch(dir, RecursiveMode::Recursive) {
+                eprintln!("Failed to watch {}: {}", dir.display(), e);
+            }
+        }
+        *watched = wanted;
+    }
+}
+
+/// Collect watcher paths into batches and apply them to the cache. Ends when
+/// the watcher (and with it the sender) is dropped.
+fn apply_batches(rx: mpsc::Receiver<PathBuf>, entries: Arc<Mutex<CacheFile>>) {
+    while let Ok(first) = rx.recv() {
+        let mut batch = HashSet::from([first]);
+        let started = Instant::now();
+        while started.elapsed() < BATCH_MAX_WAIT {
+            match rx.recv_timeout(BATCH_QUIET) {
+                Ok(path) => {
+                    batch.insert(path);
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => break,
+                Err(mpsc::RecvTimeoutError::Disconnected) => return,
+            }
+        }
+
+        let mut entries = entries.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.apply_changes(&batch) {
+            ScanCache::persist(&entries);
+        }
+    }
+}
+
+/// List a Unity project's scenes and editor version, from the cache when the
+/// project hasn't changed since the last scan
+#[tauri::command]
+#[specta::specta]
+pub async fn scan_unity_project(
+    state: tauri::State<'_, SharedState>,
+    unity_project_path: String,
+    refresh: Option<bool>,
+) -> Result<ProjectScan, LauncherError> {
+    let project = sanitize::path("Unity project path", &unity_project_path)?;
+    let state = state.inner().clone();
+    crate::commands::blocking("Project scan", move || {
+        if !is_project(&project) {
+            return Err(LauncherError::not_found(
+                "Not a Unity project (no Assets / ProjectSettings)",
+            )
+            .with_path(&project));
+        }
+        Ok(state.scans.project(&project, refresh.unwrap_or(false)))
+    })
+    .await
+}
+
+/// Find Unity projects below a folder
+#[tauri::command]
+#[specta::specta]
+pub async fn find_unity_projects(
+    state: tauri::State<'_, SharedState>,
+    root: String,
+    refresh: Option<bool>,
+) -> Result<ProjectSearch, LauncherError> {
+    let root = sanitize::path("Search folder", &root)?;
+    let state = state.inner().clone();
+    crate::commands::blocking("Project search", move || {
+        if !root.is_dir() {
+            return Err(LauncherError::not_found(format!(
+                "Folder does not exist: {}",
+                root.display()
+            ))
+            .with_path(&root));
+        }
+        Ok(state.scans.find_projects(&root, refresh.unwrap_or(false)))
+    })
+    .await
+}
+
+/// Drop cached scans, asset and GUID indexes for a path (a project or search
+/// folder), or all of them
+#[tauri::command]
+#[specta::specta]
+pub async fn invalidate_scan_cache(
+    state: tauri::State<'_, SharedState>,
+    path: Option<String>,
+) -> Result<(), LauncherError> {
+    let path = path.map(|p| sanitize::path("Path", &p)).transpose()?;
+    let state = state.inner().clone();
+    crate::commands::blocking("Invalidate scan cache", move || {
+        state.scans.invalidate(path.as_deref());
+        state.assets.invalidate(path.as_deref());
+        state.guids.invalidate(path.as_deref());
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,437 @@
+//! Structured diff of two versions of a `.unity` scene
+//!
+//! A raw YAML diff of a scene is mostly noise: file ids, reordered lists and
+//! fields nobody edited by hand. `diff_scenes` matches documents by file id,
+//! which Unity keeps stable, and reports what a person would: GameObjects
+//! added or removed, renamed, moved in the hierarchy or turned on or off,
+//! components added, removed or edited (by field name), and transform edits
+//! with their old and new values. Documents that belong to no GameObject
+//! (render settings, prefab instances) are listed on their own.
+//!
+//! Only a hash of each field is kept, so two large scenes can be compared
+//! without holding either in memory.
+
+use serde::Serialize;
+use specta::Type;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+use crate::error::LauncherError;
+use crate::unity_yaml::{self, UnityDocument};
+
+/// Transform fields reported as edits with values, and their names
+const TRANSFORM_FIELDS: &[(&str, &str)] = &[
+    ("m_LocalPosition", "position"),
+    ("m_LocalRotation", "rotation"),
+    ("m_LocalScale", "scale"),
+];
+
+/// GameObject fields that have their own place in `ObjectChange`
+const GAME_OBJECT_FIELDS: &[&str] = &["m_Name", "m_IsActive", "m_Component"];
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct SceneObject {
+    pub file_id: i64,
+    pub name: String,
+    /// Hierarchy path, e.g. "Environment/Floor"
+    pub path: String,
+    /// Component types, e.g. "Transform", "MonoBehaviour"
+    pub components: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct DocumentChange {
+    pub file_id: i64,
+    pub type_name: String,
+    /// `added`, `removed` or `changed`
+    pub status: String,
+    /// Top-level fields that differ, for `changed`
+    pub fields: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct TransformEdit {
+    /// `position`, `rotation` (a quaternion) or `scale`
+    pub property: String,
+    pub before: Option<Vec<f64>>,
+    pub after: Option<Vec<f64>>,
+}
+
+/// A GameObject in both versions that differs
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ObjectChange {
+    pub file_id: i64,
+    pub name: String,
+    pub path: String,
+    pub previous_name: Option<String>,
+    /// Set when the object moved in the hierarchy or an ancestor was renamed
+    pub previous_path: Option<String>,
+    /// The new state, when the object was turned on or off
+    pub active: Option<bool>,
+    /// Other GameObject fields that differ, e.g. `m_Layer`
+    pub fields: Vec<String>,
+    pub components_added: Vec<String>,
+    pub components_removed: Vec<String>,
+    pub components_changed: Vec<DocumentChange>,
+    pub transform: Vec<TransformEdit>,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct SceneDiff {
+    pub added: Vec<SceneObject>,
+    pub removed: Vec<SceneObject>,
+    pub changed: Vec<ObjectChange>,
+    /// GameObjects present in both without differences
+    pub unchanged: u32,
+    /// Documents outside any GameObject that were added, removed or changed
+    pub other: Vec<DocumentChange>,
+}
+
+/// What is kept of one document
+struct Document {
+    class_id: u32,
+    type_name: String,
+    is_transform: bool,
+    /// Owning GameObject, for components
+    game_object: Option<i64>,
+    /// Parent transform, for transforms
+    father: Option<i64>,
+    name: Option<String>,
+    active: Option<bool>,
+    transform: BTreeMap<&'static str, Vec<f64>>,
+    fields: BTreeMap<String, u64>,
+}
+
+/// The documents of one version, by file id
+struct Scene {
+    documents: HashMap<i64, Document>,
+    /// GameObject to its transform
+    transforms: HashMap<i64, i64>,
+    /// GameObject to its components, in file order
+    components: HashMap<i64, Vec<i64>>,
+}
+
+fn hash(value: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Top-level fields of a document with their values, nested lines included
+fn fields(body: &str) -> BTreeMap<String, String> {
+    let mut fields: BTreeMap<String, String> = BTreeMap::new();
+    let mut current: Option<String> = None;
+    for line in body.lines() {
+        let Some(rest) = line.strip_prefix("  ") else {
+            continue;
+        };
+        // Nested values are indented further; lists start at the key's indent
+        if rest.starts_with(' ') || rest.starts_with('-') {
+            if let Some(key) = &current {
+                let value = fields.entry(key.clone()).or_default();
+                value.push('\n');
+                value.push_str(rest);
+            }
+            continue;
+        }
+        if let Some((key, value)) = rest.split_once(':') {
+            fields.insert(key.to_string(), value.trim().to_string());
+            current = Some(key.to_string());
+        }
+    }
+    fields
+}
+
+/// `{x: 1, y: 2, z: 3}` (and `w` for quaternions)
+fn parse_vector(value: &str) -> Option<Vec<f64>> {
+    let inner = value.trim().strip_prefix('{')?.strip_suffix('}')?;
+    inner
+        .split(',')
+        .map(|pair| pair.split_once(':')?.1.trim().parse().ok())
+        .collect()
+}
+
+fn document(doc: UnityDocument) -> Document {
+    let fields = fields(&doc.body);
+    let is_transform = matches!(
+        doc.class_id,
+        unity_yaml::CLASS_TRANSFORM | unity_yaml::CLASS_RECT_TRANSFORM
+    );
+    let transform = if is_transform {
+        TRANSFORM_FIELDS
+            .iter()
+            .filter_map(|(field, property)| Some((*property, parse_vector(fields.get(*field)?)?)))
+            .collect()
+    } else {
+        BTreeMap::new()
+    };
+    Document {
+        class_id: doc.class_id,
+        is_transform,
+        game_object: doc.game_object_id(),
+        father: is_transform
+            .then(|| doc.field_ref("m_Father"))
+            .flatten()
+            .map(|r| r.file_id)
+            .filter(|id| *id != 0),
+        name: (doc.class_id == unity_yaml::CLASS_GAME_OBJECT)
+            .then(|| doc.name().map(str::to_string))
+            .flatten(),
+        active: (doc.class_id == unity_yaml::CLASS_GAME_OBJECT)
+            .then(|| doc.field("m_IsActive").map(|v| v == "1"))
+            .flatten(),
+        transform,
+        fields: fields.iter().map(|(k, v)| (k.clone(), hash(v))).collect(),
+        type_name: doc.type_name,
+    }
+}
+
+fn read_scene(path: &Path) -> Result<Scene, LauncherError> {
+    let mut scene = Scene {
+        documents: HashMap::new(),
+        transforms: HashMap::new(),
+        components: HashMap::new(),
+    };
+    for doc in unity_yaml::read_documents(path)? {
+        let doc = doc.map_err(|e| LauncherError::io("Failed to read scene", path, e))?;
+        let file_id = doc.file_id;
+        let doc = document(doc);
+        if let Some(owner) = doc.game_object {
+            scene.components.entry(owner).or_default().push(file_id);
+            if doc.is_transform {
+                scene.transforms.insert(owner, file_id);
+            }
+        }
+        scene.documents.insert(file_id, doc);
+    }
+    Ok(scene)
+}
+
+impl Scene {
+    fn game_objects(&self) -> impl Iterator<Item = (i64, &Document)> {
+        self.documents
+            .iter()
+            .filter(|(_, d)| d.class_id == unity_yaml::CLASS_GAME_OBJECT)
+            .map(|(id, d)| (*id, d))
+    }
+
+    fn name(&self, game_object: i64) -> String {
+        self.documents
+            .get(&game_object)
+            .and_then(|d| d.name.clone())
+            .unwrap_or_default()
+    }
+
+    /// Hierarchy path through the transforms' parents
+    fn path(&self, game_object: i64) -> String {
+        let mut names = vec![self.name(game_object)];
+        let mut transform = self.transforms.get(&game_object).copied();
+        // Bounded in case a broken scene has a cycle
+        for _ in 0..256 {
+            let Some(parent) = transform
+                .and_then(|t| self.documents.get(&t))
+                .and_then(|t| t.father)
+                .and_then(|f| self.documents.get(&f))
+            else {
+                break;
+            };
+            let Some(owner) = parent.game_object else {
+                break;
+            };
+            names.push(self.name(owner));
+            transform = self.transforms.get(&owner).copied();
+        }
+        names.reverse();
+        names.join("/")
+    }
+
+    fn component_types(&self, game_object: i64) -> Vec<String> {
+        self.components
+            .get(&game_object)
+            .into_iter()
+            .flatten()
+            .filter_map(|id| self.documents.get(id))
+            .map(|d| d.type_name.clone())
+            .collect()
+    }
+
+    fn object(&self, game_object: i64) -> SceneObject {
+        SceneObject {
+            file_id: game_object,
+            name: self.name(game_object),
+            path: self.path(game_object),
+            components: self.component_types(game_object),
+        }
+    }
+}
+
+fn changed_fields(before: &Document, after: &Document, skip: &[&str]) -> Vec<String> {
+    let mut keys: Vec<&String> = before.fields.keys().chain(after.fields.keys()).collect();
+    keys.sort();
+    keys.dedup();
+    keys.into_iter()
+        .filter(|key| !skip.contains(&key.as_str()))
+        .filter(|key| before.fields.get(*key) != after.fields.get(*key))
+        .cloned()
+        .collect()
+}
+
+fn transform_edits(before: &Document, after: &Document) -> Vec<TransformEdit> {
+    TRANSFORM_FIELDS
+        .iter()
+        .filter_map(|(_, property)| {
+            let (old, new) = (
+                before.transform.get(property),
+                after.transform.get(property),
+            );
+            (old != new).then(|| TransformEdit {
+                property: property.to_string(),
+                before: old.cloned(),
+                after: new.cloned(),
+            })
+        })
+        .collect()
+}
+
+fn compare_object(before: &Scene, after: &Scene, id: i64) -> Option<ObjectChange> {
+    let (old, new) = (before.documents.get(&id)?, after.documents.get(&id)?);
+    let empty = Vec::new();
+    let old_components = before.components.get(&id).unwrap_or(&empty);
+    let new_components = after.components.get(&id).unwrap_or(&empty);
+
+    let mut change = ObjectChange {
+        file_id: id,
+        name: after.name(id),
+        path: after.path(id),
+        previous_name: (old.name != new.name).then(|| before.name(id)),
+        previous_path: None,
+        active: (old.active != new.active).then_some(new.active).flatten(),
+        fields: changed_fields(old, new, GAME_OBJECT_FIELDS),
+        components_added: Vec::new(),
+        components_removed: Vec::new(),
+        components_changed: Vec::new(),
+        transform: Vec::new(),
+    };
+    let previous_path = before.path(id);
+    if previous_path != change.path {
+        change.previous_path = Some(previous_path);
+    }
+
+    for component in new_components {
+        let new_doc = &after.documents[component];
+        match before.documents.get(component) {
+            None => change.components_added.push(new_doc.type_name.clone()),
+            Some(old_doc) if new_doc.is_transform => {
+                change.transform = transform_edits(old_doc, new_doc);
+            }
+            Some(old_doc) => {
+                let fields = changed_fields(old_doc, new_doc, &[]);
+                if !fields.is_empty() {
+                    change.components_changed.push(DocumentChange {
+                        file_id: *component,
+                        type_name: new_doc.type_name.clone(),
+                        status: "changed".to_string(),
+                        fields,
+                    });
+                }
+            }
+        }
+    }
+    for component in old_components {
+        if !after.documents.contains_key(component) {
+            change
+                .components_removed
+                .push(before.documents[component].type_name.clone());
+        }
+    }
+
+    let unchanged = change.previous_name.is_none()
+        && change.previous_path.is_none()
+        && change.active.is_none()
+        && change.fields.is_empty()
+        && change.components_added.is_empty()
+        && change.components_removed.is_empty()
+        && change.components_changed.is_empty()
+        && change.transform.is_empty();
+    (!unchanged).then_some(change)
+}
+
+/// Documents that aren't GameObjects or their components
+fn other_changes(before: &Scene, after: &Scene) -> Vec<DocumentChange> {
+    let loose =
+        |doc: &Document| doc.class_id != unity_yaml::CLASS_GAME_OBJECT && doc.game_object.is_none();
+    let mut changes = Vec::new();
+    for (id, new) in after.documents.iter().filter(|(_, d)| loose(d)) {
+        let (status, fields) = match before.documents.get(id) {
+            None => ("added", Vec::new()),
+            Some(old) => {
+                let fields = changed_fields(old, new, &[]);
+                if fields.is_empty() {
+                    continue;
+                }
+                ("changed", fields)
+            }
+        };
+        changes.push(DocumentChange {
+            file_id: *id,
+            type_name: new.type_name.clone(),
+            status: status.to_string(),
+            fields,
+        });
+    }
+    for (id, old) in before.documents.iter().filter(|(_, d)| loose(d)) {
+        if !after.documents.contains_key(id) {
+            changes.push(DocumentChange {
+                file_id: *id,
+                type_name: old.type_name.clone(),
+                status: "removed".to_string(),
+                fields: Vec::new(),
+            });
+        }
+    }
+    changes.sort_by_key(|c| c.file_id);
+    changes
+}
+
+/// Compare two versions of a scene file
+pub fn diff_files(before: &Path, after: &Path) -> Result<SceneDiff, LauncherError> {
+    let (before, after) = (read_scene(before)?, read_scene(after)?);
+
+    let mut diff = SceneDiff {
+        added: Vec::new(),
+        removed: Vec::new(),
+        changed: Vec::new(),
+        unchanged: 0,
+        other: other_changes(&before, &after),
+    };
+    for (id, _) in after.game_objects() {
+        if !before.documents.contains_key(&id) {
+            diff.added.push(after.object(id));
+        } else if let Some(change) = compare_object(&before, &after, id) {
+            diff.changed.push(change);
+        } else {
+            diff.unchanged += 1;
+        }
+    }
+    for (id, _) in before.game_objects() {
+        if !after.documents.contains_key(&id) {
+            diff.removed.push(before.object(id));
+        }
+    }
+    diff.added.sort_by(|a, b| a.path.cmp(&b.path));
+    diff.removed.sort_by(|a, b| a.path.cmp(&b.path));
+    diff.changed.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(diff)
+}
+
+/// What changed between two versions of a `.unity` file: objects added and
+/// removed, components changed and transform edits
+#[tauri::command]
+#[specta::specta]
+pub async fn diff_scenes(before: String, after: String) -> Result<SceneDiff, LauncherError> {
+    let before = crate::sanitize::path("Scene", &before)?;
+    let after = crate::sanitize::path("Scene", &after)?;
+    crate::commands::blocking("Diff scenes", move || diff_files(&before, &after)).await
+}
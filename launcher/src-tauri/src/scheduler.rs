@@ -0,0 +1,219 @@
+//! Scheduled server restarts, update checks and idle shutdowns
+//!
+//! Each task in `LauncherConfig.schedules` runs on its own cron-like schedule
+//! in the zone it names (the core `schedule` module). The window checks them
+//! every `TICK` and runs the update check (emitting `updates-available` when
+//! an extension is behind) and the idle shutdown; `--watch` polls them too
+//! and also restarts the server it supervises. Next run times are worked out
+//! again whenever a schedule changes, and a run missed while the launcher was
+//! closed is not caught up.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::error::LauncherError;
+use crate::plan::FilePlan;
+use crate::state::{AppState, SharedState};
+use crate::{bridge, dashboard, server_pool, LauncherConfig};
+use launcher_core::config::SchedulePolicy;
+use launcher_core::schedule::{self, Schedule, TimeZone};
+
+/// How often the window checks for due tasks
+const TICK: Duration = Duration::from_secs(20);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum Task {
+    ServerRestart,
+    UpdateCheck,
+    IdleShutdown,
+}
+
+impl Task {
+    const ALL: [Task; 3] = [Task::ServerRestart, Task::UpdateCheck, Task::IdleShutdown];
+
+    fn label(self) -> &'static str {
+        match self {
+            Task::ServerRestart => "Server restart",
+            Task::UpdateCheck => "Update check",
+            Task::IdleShutdown => "Idle shutdown",
+        }
+    }
+
+    fn slot(self, config: &mut LauncherConfig) -> &mut Option<SchedulePolicy> {
+        let schedules = &mut config.schedules;
+        match self {
+            Task::ServerRestart => &mut schedules.server_restart,
+            Task::UpdateCheck => &mut schedules.update_check,
+            Task::IdleShutdown => &mut schedules.idle_shutdown,
+        }
+    }
+
+    fn configured(self, config: &LauncherConfig) -> Option<&SchedulePolicy> {
+        let schedules = &config.schedules;
+        match self {
+            Task::ServerRestart => schedules.server_restart.as_ref(),
+            Task::UpdateCheck => schedules.update_check.as_ref(),
+            Task::IdleShutdown => schedules.idle_shutdown.as_ref(),
+        }
+    }
+
+    /// The task's schedule, if it has one and it is on
+    pub fn policy(self, config: &LauncherConfig) -> Option<&SchedulePolicy> {
+        self.configured(config).filter(|p| p.enabled)
+    }
+}
+
+/// A task's schedule and when it runs next, for `get_schedules`
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ScheduleStatus {
+    pub task: Task,
+    pub policy: Option<SchedulePolicy>,
+    /// Unix ms; `None` when off or the schedule never matches
+    pub next_run_ms: Option<i64>,
+    /// The next run on the schedule's own wall clock, with its UTC offset
+    pub next_run_local: Option<String>,
+}
+
+fn parse(policy: &SchedulePolicy) -> Option<(Schedule, TimeZone)> {
+    let schedule = Schedule::parse(&policy.cron).ok()?;
+    let zone = TimeZone::parse(&policy.time_zone).ok()?;
+    Some((schedule, zone))
+}
+
+fn now_secs() -> i64 {
+    bridge::now_ms() / 1000
+}
+
+/// Tracks when each task is due next
+#[derive(Default)]
+pub struct Scheduler {
+    /// Next run in Unix seconds, by task, with the policy it was worked out for
+    next: BTreeMap<Task, (String, i64)>,
+}
+
+impl Scheduler {
+    /// Tasks whose time has come since the last call
+    pub fn due(&mut self, config: &LauncherConfig) -> Vec<Task> {
+        let now = now_secs();
+        let mut due = Vec::new();
+        for task in Task::ALL {
+            let Some((policy, (schedule, zone))) =
+                task.policy(config).and_then(|p| Some((p, parse(p)?)))
+            else {
+                self.next.remove(&task);
+                continue;
+            };
+            let key = format!("{} {}", policy.cron, policy.time_zone);
+            match self.next.get(&task) {
+                Some((k, at)) if *k == key => {
+                    if now < *at {
+                        continue;
+                    }
+                    due.push(task);
+                }
+                _ => {}
+            }
+            match schedule.next_after(now, zone) {
+                Some(next) => self.next.insert(task, (key, next)),
+                None => self.next.remove(&task),
+            };
+        }
+        due
+    }
+}
+
+/// Stop the pooled servers no client is connected to; returns how many
+pub fn idle_shutdown() -> usize {
+    server_pool::servers()
+        .into_iter()
+        .filter(|s| s.connections == 0)
+        .filter(|s| server_pool::stop(s).is_ok())
+        .count()
+}
+
+/// Start checking the schedules from the window
+pub fn start(app: &AppHandle, state: &SharedState) {
+    let app = app.clone();
+    let state = state.clone();
+    thread::spawn(move || {
+        let mut scheduler = Scheduler::default();
+        loop {
+            for task in scheduler.due(&state.config()) {
+                match task {
+                    Task::UpdateCheck => {
+                        let pending = dashboard::build(&state, true).pending_updates;
+                        if !pending.is_empty() {
+                            let _ = app.emit("updates-available", pending);
+                        }
+                    }
+                    Task::IdleShutdown => {
+                        idle_shutdown();
+                    }
+                    // Only watch mode has a server of its own to restart
+                    Task::ServerRestart => {}
+                }
+            }
+            thread::sleep(TICK);
+        }
+    });
+}
+
+pub fn statuses(config: &LauncherConfig) -> Vec<ScheduleStatus> {
+    let now = now_secs();
+    Task::ALL
+        .into_iter()
+        .map(|task| {
+            let next = task
+                .policy(config)
+                .and_then(parse)
+                .and_then(|(schedule, zone)| Some((schedule.next_after(now, zone)?, zone)));
+            ScheduleStatus {
+                task,
+                policy: task.configured(config).cloned(),
+                next_run_ms: next.map(|(at, _)| at * 1000),
+                next_run_local: next.map(|(at, zone)| schedule::format(at, zone)),
+            }
+        })
+        .collect()
+}
+
+/// Set or clear one task's schedule
+pub fn set(
+    state: &AppState,
+    task: Task,
+    policy: Option<SchedulePolicy>,
+) -> Result<Vec<ScheduleStatus>, LauncherError> {
+    if let Some(policy) = &policy {
+        schedule::validate(task.label(), &policy.cron, &policy.time_zone)?;
+    }
+    let mut plan = FilePlan::new(false);
+    let config = state.update(&mut plan, |config| {
+        *task.slot(config) = policy;
+        Ok(config.clone())
+    })?;
+    Ok(statuses(&config))
+}
+
+/// Every scheduled task with its next run
+#[tauri::command]
+#[specta::specta]
+pub fn get_schedules(state: tauri::State<'_, SharedState>) -> Vec<ScheduleStatus> {
+    statuses(&state.config())
+}
+
+/// Give a task a schedule, or turn it off with `None`
+#[tauri::command]
+#[specta::specta]
+pub async fn set_schedule(
+    state: tauri::State<'_, SharedState>,
+    task: Task,
+    policy: Option<SchedulePolicy>,
+) -> Result<Vec<ScheduleStatus>, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Schedule", move || set(&state, task, policy)).await
+}
@@ -0,0 +1,183 @@
+//! Per-channel secrets for the MCP server
+//!
+//! Secret values live in the OS keychain. When a client config is generated they
+//! are written to a user-only env file and the config just references it via
+//! `BANTER_MCP_ENV_FILE`, so tokens never land in `.claude.json`.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::account::{self, keychain_error};
+use crate::error::{ErrorKind, LauncherError};
+use crate::plan::FilePlan;
+use crate::sanitize;
+use crate::state::SharedState;
+
+/// Env var naming the secrets file; read by the server on startup
+pub const ENV_FILE_VAR: &str = "BANTER_MCP_ENV_FILE";
+
+/// Env var carrying the Banter upload token for channels that opt in
+const UPLOAD_TOKEN_VAR: &str = "BANTER_UPLOAD_TOKEN";
+
+fn keychain_entry(channel_id: &str) -> Result<keyring::Entry, LauncherError> {
+    keyring::Entry::new(
+        account::KEYCHAIN_SERVICE,
+        &format!("channel-{}", channel_id),
+    )
+    .map_err(|e| keychain_error("Failed to open keychain", e))
+}
+
+/// No entry means no secrets yet; any other keychain failure is an error, so
+/// a locked keychain is never mistaken for an empty one and overwritten
+fn load(channel_id: &str) -> Result<BTreeMap<String, String>, LauncherError> {
+    let secret = match keychain_entry(channel_id)?.get_password() {
+        Ok(secret) => secret,
+        Err(keyring::Error::NoEntry) => return Ok(BTreeMap::new()),
+        Err(e) => return Err(keychain_error("Failed to read channel secrets", e)),
+    };
+    serde_json::from_str(&secret).map_err(|e| {
+        LauncherError::internal(format!(
+            "Channel secrets in the keychain are corrupt: {}",
+            e
+        ))
+    })
+}
+
+fn store(channel_id: &str, secrets: &BTreeMap<String, String>) -> Result<(), LauncherError> {
+    let entry = keychain_entry(channel_id)?;
+    if secrets.is_empty() {
+        return match entry.delete_password() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(keychain_error("Failed to remove channel secrets", e)),
+        };
+    }
+
+    let secret = serde_json::to_string(secrets).map_err(|e| {
+        LauncherError::internal(format!("Failed to serialize channel secrets: {}", e))
+    })?;
+    entry
+        .set_password(&secret)
+        .map_err(|e| keychain_error("Failed to store channel secrets in keychain", e))
+}
+
+/// Env var names: upper-case letters, digits and `_`
+fn check_name(name: &str) -> Result<(), LauncherError> {
+    let valid = !name.is_empty()
+        && name.len() <= sanitize::MAX_ID_LEN
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(LauncherError::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "Invalid secret name '{}' (use upper-case letters, digits and '_')",
+                name
+            ),
+        ))
+    }
+}
+
+pub fn env_file_path(channel_id: &str) -> PathBuf {
+    crate::launcher_dir()
+        .join("secrets")
+        .join(format!("{}.env", channel_id))
+}
+
+/// Secrets the channel's server should receive
+fn channel_env(
+    config: &crate::LauncherConfig,
+    channel: &crate::ProjectChannel,
+) -> Result<BTreeMap<String, String>, LauncherError> {
+    let mut env = load(&channel.id)?;
+    if channel.pass_upload_token {
+        if let Some(token) = account::access_token(config) {
+            env.insert(UPLOAD_TOKEN_VAR.to_string(), token);
+        }
+    }
+    Ok(env)
+}
+
+/// Write the channel's env file, returning its path, or remove a stale one
+/// when the channel has no secrets
+pub fn write_env_file(
+    plan: &mut FilePlan,
+    config: &crate::LauncherConfig,
+    channel: &crate::ProjectChannel,
+) -> Result<Option<PathBuf>, LauncherError> {
+    let path = env_file_path(&channel.id);
+    let env = channel_env(config, channel)?;
+
+    if env.is_empty() {
+        plan.delete(&path)?;
+        return Ok(None);
+    }
+
+    if let Some(dir) = path.parent() {
+        plan.create_dir(dir)?;
+    }
+    let content: String = env
+        .iter()
+        .map(|(name, value)| format!("{}={}\n", name, value))
+        .collect();
+    plan.write_private(&path, content)?;
+
+    Ok(Some(path))
+}
+
+/// Drop a channel's keychain secrets and env file
+pub fn remove_channel_secrets(plan: &mut FilePlan, channel_id: &str) -> Result<(), LauncherError> {
+    plan.delete(&env_file_path(channel_id))?;
+    if !plan.dry_run {
+        store(channel_id, &BTreeMap::new())?;
+    }
+    Ok(())
+}
+
+/// Set (or with `value: None`, remove) a secret passed to the channel's server.
+/// Takes effect the next time the client config is written.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_channel_secret(
+    state: tauri::State<'_, SharedState>,
+    channel_id: String,
+    name: String,
+    value: Option<String>,
+) -> Result<(), LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Set secret", move || {
+        state.channel(&channel_id)?;
+        check_name(&name)?;
+
+        let mut secrets = load(&channel_id)?;
+        match value {
+            Some(value) => {
+                sanitize::text(&name, &value, sanitize::MAX_URL_LEN)?;
+                secrets.insert(name, value);
+            }
+            None => {
+                secrets.remove(&name);
+            }
+        }
+        store(&channel_id, &secrets)
+    })
+    .await
+}
+
+/// Names (never values) of the channel's stored secrets
+#[tauri::command]
+#[specta::specta]
+pub async fn list_channel_secrets(
+    state: tauri::State<'_, SharedState>,
+    channel_id: String,
+) -> Result<Vec<String>, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("List secrets", move || {
+        state.channel(&channel_id)?;
+        Ok(load(&channel_id)?.into_keys().collect())
+    })
+    .await
+}
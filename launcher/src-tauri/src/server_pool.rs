@@ -0,0 +1,373 @@
+//! One MCP server shared by the clients of a channel
+//!
+//! With `server_pool` on, every client entry of the active channel (Claude
+//! Code's and, in workspace mode, the other clients') runs
+//! `banter-launcher --mcp-proxy --pooled <channel> <server>`. The first proxy
+//! of a channel starts the server in socket mode, detached from any client,
+//! and the rest connect to the port it listens on instead of starting a
+//! server of their own. The server announces itself in
+//! `pool/<channel>.json` in the launcher folder, counts the proxies connected
+//! to it there and renews the file while it runs; once no proxy has been
+//! connected for `idle_timeout_secs` it exits and removes the file. With an
+//! `idle_shutdown` schedule it stays up instead until the scheduler stops it
+//! at a scheduled time with no client connected.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs::{self, OpenOptions};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::bridge;
+use crate::error::{ErrorKind, LauncherError};
+use crate::faults::{self, Fault};
+use crate::plan::FilePlan;
+use crate::scheduler::Task;
+use crate::state::{AppState, SharedState};
+use launcher_core::config::ServerPoolSettings;
+
+/// Tells the server where to announce itself
+pub const POOL_FILE_VAR: &str = "BANTER_MCP_POOL_FILE";
+
+/// Workspace instance name of a pooled server, which serves every client
+pub const SHARED_INSTANCE: &str = "shared";
+
+/// The server renews its pool file every 10s; one not renewed for this long
+/// belongs to a server that is gone
+const STALE_AFTER_MS: i64 = 30_000;
+
+/// How long a proxy waits for a server it started to start listening
+const START_TIMEOUT: Duration = Duration::from_secs(15);
+
+const POLL: Duration = Duration::from_millis(100);
+
+/// A pooled server, as announced in its pool file
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PooledServer {
+    /// From the pool file's name
+    #[serde(default)]
+    pub channel_id: String,
+    pub pid: u32,
+    pub port: u16,
+    /// Proxies connected right now
+    pub connections: u32,
+    /// Unix ms
+    pub started_at: i64,
+    /// Last renewal, Unix ms
+    pub updated_at: i64,
+    /// When the last proxy went away, Unix ms; `None` while one is connected
+    pub idle_since: Option<i64>,
+}
+
+impl PooledServer {
+    fn is_stale(&self) -> bool {
+        bridge::now_ms() - self.updated_at > STALE_AFTER_MS
+    }
+}
+
+fn pool_dir() -> PathBuf {
+    crate::launcher_dir().join("pool")
+}
+
+pub fn pool_file(channel_id: &str) -> PathBuf {
+    pool_dir().join(format!("{}.json", channel_id))
+}
+
+fn read_entry(path: &Path) -> Option<PooledServer> {
+    let mut entry: PooledServer = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+    entry.channel_id = path.file_stem()?.to_string_lossy().to_string();
+    Some(entry)
+}
+
+/// The channel's server, if one is running
+fn live_entry(channel_id: &str) -> Option<PooledServer> {
+    read_entry(&pool_file(channel_id)).filter(|e| !e.is_stale())
+}
+
+/// Pooled servers that are running, by channel id
+pub fn servers() -> Vec<PooledServer> {
+    let Ok(entries) = fs::read_dir(pool_dir()) else {
+        return Vec::new();
+    };
+    let mut servers: Vec<PooledServer> = entries
+        .filter_map(Result::ok)
+        .filter(|e| e.path().extension().is_some_and(|x| x == "json"))
+        .filter_map(|e| read_entry(&e.path()))
+        .filter(|e| !e.is_stale())
+        .collect();
+    servers.sort_by(|a, b| a.channel_id.cmp(&b.channel_id));
+    servers
+}
+
+/// Idle timeout a server is started with; 0, never, when the scheduler shuts
+/// idle servers down instead
+pub fn idle_timeout_secs(config: &crate::LauncherConfig) -> u64 {
+    if Task::IdleShutdown.policy(config).is_some() {
+        return 0;
+    }
+    config.server_pool.idle_timeout_secs
+}
+
+/// Ask a pooled server to exit; it removes its pool file on the way out
+pub fn stop(server: &PooledServer) -> Result<(), LauncherError> {
+    let pid = server.pid.to_string();
+    let status = if cfg!(windows) {
+        Command::new("taskkill").args(["/PID", &pid, "/F"]).status()
+    } else {
+        Command::new("kill").args(["-TERM", &pid]).status()
+    };
+    match status {
+        Ok(status) if status.success() => {
+            // A forced stop on Windows skips the server's own cleanup
+            if cfg!(windows) {
+                let _ = fs::remove_file(pool_file(&server.channel_id));
+            }
+            Ok(())
+        }
+        Ok(_) => Err(LauncherError::not_found(format!(
+            "Pooled server {} is no longer running",
+            server.pid
+        ))),
+        Err(e) => Err(LauncherError::internal(format!(
+            "Failed to stop pooled server {}: {}",
+            server.pid, e
+        ))),
+    }
+}
+
+/// Held by the one proxy starting a channel's server, so proxies started
+/// together don't each start one
+struct StartLock(PathBuf);
+
+impl StartLock {
+    fn claim(channel_id: &str) -> Option<StartLock> {
+        let path = pool_dir().join(format!("{}.lock", channel_id));
+        fs::create_dir_all(pool_dir()).ok()?;
+        // A lock outliving a start attempt was left by a proxy that died
+        let abandoned = path
+            .metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|m| m.elapsed().ok())
+            .is_some_and(|age| age > 2 * START_TIMEOUT);
+        if abandoned {
+            let _ = fs::remove_file(&path);
+        }
+        OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .ok()
+            .map(|_| StartLock(path))
+    }
+}
+
+impl Drop for StartLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+fn try_connect(channel_id: &str) -> Option<TcpStream> {
+    let entry = live_entry(channel_id)?;
+    TcpStream::connect(("127.0.0.1", entry.port)).ok()
+}
+
+/// Start the server in socket mode with the proxy's own environment and
+/// working directory, which the client entry set up; `setup` adds to them
+fn spawn(
+    channel_id: &str,
+    server: &Path,
+    idle_timeout_secs: u64,
+    setup: &dyn Fn(&mut Command),
+) -> Result<(), LauncherError> {
+    let mut command = Command::new("node");
+    command
+        .arg(server)
+        .args([
+            "--socket",
+            "0",
+            "--idle-timeout",
+            &idle_timeout_secs.to_string(),
+        ])
+        .env(POOL_FILE_VAR, pool_file(channel_id))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    setup(&mut command);
+    // The server outlives the client that started it
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const DETACHED_PROCESS: u32 = 0x0000_0008;
+        const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+        command.creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP);
+    }
+
+    let mut child = faults::check(Fault::NodeMissing)
+        .and_then(|_| command.spawn())
+        .map_err(|e| {
+            LauncherError::io("Failed to start MCP server", server, e)
+                .with_hint("Check that Node.js is installed and on PATH")
+        })?;
+    thread::spawn(move || child.wait());
+    Ok(())
+}
+
+/// Connect to the channel's pooled server, starting it if none is running
+pub fn connect(
+    channel_id: &str,
+    server: &Path,
+    idle_timeout_secs: u64,
+) -> Result<TcpStream, LauncherError> {
+    connect_with(channel_id, server, idle_timeout_secs, &|_| {})
+}
+
+fn connect_with(
+    channel_id: &str,
+    server: &Path,
+    idle_timeout_secs: u64,
+    setup: &dyn Fn(&mut Command),
+) -> Result<TcpStream, LauncherError> {
+    let deadline = Instant::now() + START_TIMEOUT;
+    let mut lock = None;
+    loop {
+        if let Some(stream) = try_connect(channel_id) {
+            return Ok(stream);
+        }
+        if lock.is_none() {
+            lock = StartLock::claim(channel_id);
+            if lock.is_some() {
+                // Another proxy may have finished starting it meanwhile
+                if let Some(stream) = try_connect(channel_id) {
+                    return Ok(stream);
+                }
+                spawn(channel_id, server, idle_timeout_secs, setup)?;
+            }
+        }
+        if Instant::now() > deadline {
+            return Err(LauncherError::internal(format!(
+                "The pooled MCP server for channel {} did not start listening",
+                channel_id
+            ))
+            .with_path(pool_file(channel_id))
+            .with_hint("Check the channel's server log"));
+        }
+        thread::sleep(POLL);
+    }
+}
+
+/// Stop the channel's pooled server, if one is running, and start it again
+/// the way the first proxy does, with the environment and working directory
+/// its client entries set up. Clients connect to the new one as they come;
+/// with none connected it exits after the idle timeout like any other.
+pub fn restart(
+    config: &crate::LauncherConfig,
+    channel: &crate::ProjectChannel,
+) -> Result<PooledServer, LauncherError> {
+    if !config.server_pool.enabled {
+        return Err(LauncherError::new(
+            ErrorKind::Conflict,
+            format!(
+                "Server pooling is off, so {} has no pooled MCP server",
+                channel.name
+            ),
+        )
+        .with_hint("Restarts need server pooling on; otherwise the client owns the server"));
+    }
+    if let Some(running) = live_entry(&channel.id) {
+        stop(&running)?;
+        // Until it is gone, starting would only connect to it again
+        let deadline = Instant::now() + START_TIMEOUT;
+        while live_entry(&channel.id).is_some_and(|e| {
+            e.pid == running.pid && TcpStream::connect(("127.0.0.1", e.port)).is_ok()
+        }) {
+            if Instant::now() > deadline {
+                return Err(LauncherError::internal(format!(
+                    "Pooled server {} did not exit",
+                    running.pid
+                )));
+            }
+            thread::sleep(POLL);
+        }
+    }
+
+    let cwd = crate::channels::server_cwd(channel)?;
+    let env = crate::pooled_server_env_vars(&mut FilePlan::new(false), config, channel)?;
+    let server = Path::new(&config.mcp_server_path);
+    // The connection only shows it listens; dropping it leaves the server idle
+    connect_with(&channel.id, server, idle_timeout_secs(config), &|command| {
+        command.current_dir(&cwd).envs(&env);
+    })?;
+    live_entry(&channel.id).ok_or_else(|| {
+        LauncherError::internal(format!(
+            "The pooled MCP server for channel {} did not announce itself",
+            channel.id
+        ))
+        .with_path(pool_file(&channel.id))
+    })
+}
+
+/// Turn server pooling on or off. With auto-configure on, the active
+/// channel's client entries are rewritten to go through the pool or not;
+/// clients pick that up the next time they start the server, and a pooled
+/// server still running exits once its last client has gone.
+pub fn set_pooling(
+    state: &AppState,
+    settings: ServerPoolSettings,
+) -> Result<ServerPoolSettings, LauncherError> {
+    let mut plan = FilePlan::new(false);
+    let config = state.update(&mut plan, |config| {
+        config.server_pool = settings;
+        Ok(config.clone())
+    })?;
+    let channel = config
+        .active_channel_id
+        .as_deref()
+        .and_then(|id| config.channels.iter().find(|c| c.id == id))
+        .cloned();
+    if let (Some(channel), true) = (channel, config.auto_start) {
+        crate::update_claude_mcp_config(state, channel, config.mcp_server_path.clone(), None)?;
+    }
+    Ok(config.server_pool)
+}
+
+/// List the pooled servers that are running
+#[tauri::command]
+#[specta::specta]
+pub async fn get_server_pool() -> Result<Vec<PooledServer>, LauncherError> {
+    crate::commands::blocking("Server pool", || Ok(servers())).await
+}
+
+/// Turn server pooling on or off and set the idle timeout
+#[tauri::command]
+#[specta::specta]
+pub async fn set_server_pooling(
+    state: tauri::State<'_, SharedState>,
+    enabled: bool,
+    idle_timeout_secs: Option<u64>,
+) -> Result<ServerPoolSettings, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Server pooling", move || {
+        let mut settings = state.config().server_pool;
+        settings.enabled = enabled;
+        if let Some(secs) = idle_timeout_secs {
+            if secs == 0 {
+                return Err(LauncherError::invalid(
+                    "The idle timeout must be at least a second",
+                ));
+            }
+            settings.idle_timeout_secs = secs;
+        }
+        set_pooling(&state, settings)
+    })
+    .await
+}
@@ -0,0 +1,213 @@
+//! One launcher per Unity project at a time
+//!
+//! Unity opens a project in one editor only, and two MCP servers on the same
+//! project would fight over its bridge. The launcher whose channel is active
+//! claims the project in `Library/BanterMcpSession.json`, which travels with
+//! the project on a synced drive and which Unity and version control ignore.
+//! Activating a channel whose project another launcher (another user, another
+//! machine or another launcher folder) has claimed in the last few minutes
+//! is a conflict: the window asks, `--activate` wants `--take-over`. The
+//! launcher that was taken over notices on its next refresh and deactivates
+//! its channel.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::error::{ErrorKind, LauncherError};
+use crate::plan::FilePlan;
+use crate::state::{AppState, SharedState};
+use crate::{bridge, hooks, user, LauncherConfig, ProjectChannel};
+
+/// How often the active channel's claim is renewed
+pub const REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// A claim not renewed for this long belongs to a launcher that is gone
+const STALE_AFTER_MS: i64 = 3 * 60 * 1000;
+
+/// Who holds a project, as written to its session file
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SessionClaim {
+    pub channel_id: String,
+    pub channel_name: String,
+    /// `user@host` of the launcher holding the project
+    pub owner: String,
+    /// Launcher data folder, which tells launchers of the same user apart
+    pub launcher_dir: String,
+    pub pid: u32,
+    /// Last renewal, in milliseconds since the Unix epoch
+    pub updated_ms: i64,
+}
+
+impl SessionClaim {
+    fn is_ours(&self) -> bool {
+        self.owner == user::owner() && self.launcher_dir == launcher_dir()
+    }
+
+    fn is_stale(&self) -> bool {
+        bridge::now_ms() - self.updated_ms > STALE_AFTER_MS
+    }
+}
+
+/// Another launcher is using the project a channel points at
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct SessionConflict {
+    pub channel_id: String,
+    pub unity_project_path: String,
+    pub other: SessionClaim,
+    pub message: String,
+}
+
+fn launcher_dir() -> String {
+    crate::launcher_dir().to_string_lossy().to_string()
+}
+
+pub fn session_path(unity_project_path: &Path) -> PathBuf {
+    unity_project_path
+        .join("Library")
+        .join("BanterMcpSession.json")
+}
+
+fn read_claim(unity_project_path: &Path) -> Option<SessionClaim> {
+    let content = fs::read_to_string(session_path(unity_project_path)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// The live claim of another launcher on the channel's project, if any
+pub fn conflict(channel: &ProjectChannel) -> Option<SessionConflict> {
+    let other = read_claim(Path::new(&channel.unity_project_path))?;
+    if other.is_ours() || other.is_stale() {
+        return None;
+    }
+    Some(SessionConflict {
+        channel_id: channel.id.clone(),
+        unity_project_path: channel.unity_project_path.clone(),
+        message: format!(
+            "{} is already using {} through channel \"{}\"; Unity opens a project in \
+             one editor only, and two MCP servers would fight over its bridge",
+            other.owner, channel.unity_project_path, other.channel_name
+        ),
+        other,
+    })
+}
+
+/// Refuse to activate a channel whose project another launcher holds
+pub fn check_activation(channel: &ProjectChannel, take_over: bool) -> Result<(), LauncherError> {
+    match conflict(channel) {
+        Some(conflict) if !take_over => {
+            Err(LauncherError::new(ErrorKind::Conflict, conflict.message)
+                .with_path(session_path(Path::new(&channel.unity_project_path)))
+                .with_hint("Deactivate the channel there first, or take the project over"))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn claim(channel: &ProjectChannel) -> Result<(), LauncherError> {
+    let project = Path::new(&channel.unity_project_path);
+    // A project that isn't there can't be shared either
+    if !project.is_dir() {
+        return Ok(());
+    }
+    let claim = SessionClaim {
+        channel_id: channel.id.clone(),
+        channel_name: channel.name.clone(),
+        owner: user::owner(),
+        launcher_dir: launcher_dir(),
+        pid: std::process::id(),
+        updated_ms: bridge::now_ms(),
+    };
+    let content = serde_json::to_string_pretty(&claim)
+        .map_err(|e| LauncherError::internal(format!("Failed to serialize session: {}", e)))?;
+    let path = session_path(project);
+    let mut plan = FilePlan::new(false);
+    plan.create_dir(path.parent().unwrap_or(project))?;
+    plan.write(&path, content)
+}
+
+/// Give the project up again, unless someone else has taken it meanwhile
+pub fn release(channel: &ProjectChannel) -> Result<(), LauncherError> {
+    let project = Path::new(&channel.unity_project_path);
+    match read_claim(project) {
+        Some(claim) if claim.is_ours() => FilePlan::new(false).delete(&session_path(project)),
+        _ => Ok(()),
+    }
+}
+
+fn active(config: &LauncherConfig) -> Option<&ProjectChannel> {
+    let id = config.active_channel_id.as_deref()?;
+    config.channels.iter().find(|c| c.id == id)
+}
+
+/// Move the claim along when a config change switched the active channel
+pub fn active_channel_changed(old: &LauncherConfig, new: &LauncherConfig) {
+    if old.active_channel_id == new.active_channel_id {
+        return;
+    }
+    let results = [active(old).map(release), active(new).map(claim)];
+    for e in results.into_iter().flatten().filter_map(Result::err) {
+        eprintln!("Failed to update project session: {}", e);
+    }
+}
+
+/// Renew the active channel's claim. If another launcher has taken the
+/// project over, deactivate the channel and return their claim.
+pub fn refresh(state: &AppState) -> Option<SessionClaim> {
+    let previous = state.config();
+    let channel = active(&previous)?.clone();
+    if let Some(other) = conflict(&channel) {
+        let mut plan = FilePlan::new(false);
+        let deactivated = state.update(&mut plan, |config| {
+            if config.active_channel_id.as_deref() == Some(channel.id.as_str()) {
+                config.active_channel_id = None;
+            }
+            Ok(config.clone())
+        });
+        match deactivated {
+            Ok(config) => hooks::active_channel_changed(&previous, &config),
+            Err(e) => eprintln!("Failed to deactivate {}: {}", channel.id, e),
+        }
+        return Some(other.other);
+    }
+
+    let due = read_claim(Path::new(&channel.unity_project_path))
+        .is_none_or(|c| bridge::now_ms() - c.updated_ms >= REFRESH_INTERVAL.as_millis() as i64);
+    if due {
+        if let Err(e) = claim(&channel) {
+            eprintln!("Failed to renew project session: {}", e);
+        }
+    }
+    None
+}
+
+/// Keep the window's claim renewed, telling it when it has been taken over
+pub fn start_refresher(app: &AppHandle, state: &SharedState) {
+    let app = app.clone();
+    let state = state.clone();
+    thread::spawn(move || loop {
+        if let Some(other) = refresh(&state) {
+            let _ = app.emit("config-changed", state.config());
+            let _ = app.emit("session-taken-over", other);
+        }
+        thread::sleep(REFRESH_INTERVAL);
+    });
+}
+
+/// Who else is using a channel's project, asked before activating it
+#[tauri::command]
+#[specta::specta]
+pub async fn check_project_session(
+    state: tauri::State<'_, SharedState>,
+    channel_id: String,
+) -> Result<Option<SessionConflict>, LauncherError> {
+    crate::sanitize::channel_id(&channel_id)?;
+    let state = state.inner().clone();
+    crate::commands::blocking("Session check", move || {
+        Ok(conflict(&state.channel(&channel_id)?))
+    })
+    .await
+}
@@ -0,0 +1,190 @@
+//! Shareable markdown description of a channel's setup
+//!
+//! `generate_setup_report` collects paths, versions, the Claude Code entry and
+//! the channel's health checks into a markdown document for issue reports,
+//! plus a condensed version short enough to paste into a Discord message.
+//! The home folder is shown as `~` and only the names of server environment
+//! variables are listed, so neither leaks anything private.
+
+use serde::Serialize;
+use specta::Type;
+use std::path::Path;
+use std::process::Command;
+
+use crate::error::LauncherError;
+use crate::faults::{self, Fault};
+use crate::health::{self, HealthStatus};
+use crate::state::{AppState, SharedState};
+use crate::{banter_sdk, unity};
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct SetupReport {
+    pub markdown: String,
+    /// A few lines for chat, e.g. copied to the clipboard
+    pub condensed: String,
+}
+
+/// Show the home folder as `~` wherever it appears in a path or message
+pub fn redact(text: &str) -> String {
+    let Some(home) = dirs::home_dir() else {
+        return text.to_string();
+    };
+    let home = home.to_string_lossy();
+    text.replace(home.as_ref(), "~")
+        .replace(&home.replace('\\', "/"), "~")
+}
+
+fn node_version() -> Option<String> {
+    let output = faults::check(Fault::NodeMissing)
+        .and_then(|_| Command::new("node").arg("--version").output())
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn or_unknown(value: &Option<String>) -> &str {
+    value.as_deref().unwrap_or("unknown")
+}
+
+fn status_icon(status: HealthStatus) -> &'static str {
+    match status {
+        HealthStatus::Ok => "✅",
+        HealthStatus::Warning => "⚠️",
+        HealthStatus::Error => "❌",
+    }
+}
+
+/// How Claude Code is configured relative to this channel
+fn claude_entry(channel: &crate::ProjectChannel) -> (String, Vec<String>) {
+    let claude = match crate::get_claude_mcp_config() {
+        Ok(claude) => claude,
+        Err(e) => return (format!("unreadable ({})", e.message), Vec::new()),
+    };
+    let Some(entry) = claude.pointer("/mcpServers/banter") else {
+        return ("no `banter` entry".to_string(), Vec::new());
+    };
+
+    let env_names: Vec<String> = entry
+        .get("env")
+        .and_then(|e| e.as_object())
+        .map(|env| env.keys().cloned().collect())
+        .unwrap_or_default();
+    let project = entry
+        .pointer("/env/UNITY_PROJECT_PATH")
+        .and_then(|p| p.as_str());
+    let summary = match project {
+        Some(p) if p == channel.unity_project_path => "registered for this channel".to_string(),
+        Some(p) => format!("registered for another project ({})", redact(p)),
+        None => "`banter` entry without a Unity project".to_string(),
+    };
+    (summary, env_names)
+}
+
+pub fn generate(state: &AppState, channel_id: &str) -> Result<SetupReport, LauncherError> {
+    let config = state.config();
+    let channel = crate::find_channel(&config, channel_id)?;
+    let project = Path::new(&channel.unity_project_path);
+
+    let launcher_version = env!("CARGO_PKG_VERSION");
+    let server_version = banter_sdk::read_server_version(&config.mcp_server_path);
+    let bridge_version = banter_sdk::read_bridge_version(project);
+    let sdk = banter_sdk::detect_sdk(project);
+    let unity_version = unity::project_editor_version(project);
+    let node = node_version();
+    let os = format!("{} {}", std::env::consts::OS, std::env::consts::ARCH);
+    let (claude, env_names) = claude_entry(&channel);
+    let health = health::check_channel(&channel, &config, &state.guids);
+
+    let mut md = format!("# Banter MCP setup: {}\n\n", channel.name);
+
+    md += "## Paths\n\n";
+    md += &format!(
+        "- Unity project: `{}`\n",
+        redact(&channel.unity_project_path)
+    );
+    if let Some(scene) = &channel.scene_path {
+        md += &format!("- Scene: `{}`\n", redact(scene));
+    }
+    md += &format!("- MCP server: `{}`\n", redact(&config.mcp_server_path));
+    md += &format!(
+        "- Claude Code config: `{}`\n\n",
+        redact(&crate::get_claude_config_path().to_string_lossy())
+    );
+
+    md += "## Versions\n\n| Component | Version |\n|---|---|\n";
+    for (component, version) in [
+        ("Launcher", Some(launcher_version.to_string())),
+        ("MCP server", server_version.clone()),
+        ("Unity bridge", bridge_version.clone()),
+        ("Banter SDK", sdk.version.clone()),
+        ("Unity", unity_version.clone()),
+        ("Node.js", node.clone()),
+        ("OS", Some(os.clone())),
+    ] {
+        md += &format!("| {} | {} |\n", component, or_unknown(&version));
+    }
+
+    md += "\n## Clients\n\n";
+    md += &format!("- Claude Code: {}\n", claude);
+    if !env_names.is_empty() {
+        md += &format!("  - env: {}\n", env_names.join(", "));
+    }
+    md += &format!(
+        "- Active channel: {}\n",
+        if config.active_channel_id.as_deref() == Some(channel.id.as_str()) {
+            "yes"
+        } else {
+            "no"
+        }
+    );
+
+    md += &format!("\n## Health {}\n\n", status_icon(health.status));
+    for check in &health.checks {
+        md += &format!(
+            "- {} `{}` {}\n",
+            status_icon(check.status),
+            check.id,
+            redact(&check.message)
+        );
+    }
+
+    let problems: Vec<String> = health
+        .checks
+        .iter()
+        .filter(|c| c.status != HealthStatus::Ok)
+        .map(|c| redact(&c.message))
+        .collect();
+    let mut condensed = format!(
+        "**Banter MCP** {} | launcher {} | server {} | bridge {} | SDK {} | Unity {} | node {} | {}\n",
+        status_icon(health.status),
+        launcher_version,
+        or_unknown(&server_version),
+        or_unknown(&bridge_version),
+        or_unknown(&sdk.version),
+        or_unknown(&unity_version),
+        or_unknown(&node),
+        os
+    );
+    condensed += &format!("Claude Code: {}\n", claude);
+    for problem in problems {
+        condensed += &format!("- {}\n", problem);
+    }
+
+    Ok(SetupReport {
+        markdown: md,
+        condensed,
+    })
+}
+
+/// Describe a channel's setup as markdown for sharing
+#[tauri::command]
+#[specta::specta]
+pub async fn generate_setup_report(
+    state: tauri::State<'_, SharedState>,
+    channel_id: String,
+) -> Result<SetupReport, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Setup report", move || generate(&state, &channel_id)).await
+}
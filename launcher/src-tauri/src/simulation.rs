@@ -0,0 +1,570 @@
+//! Simulated MCP server and Unity bridge for demos and testing
+//!
+//! With `simulation_enabled` set in the config, or `BANTER_SIMULATE=1` in the
+//! environment, the launcher stands in for the two programs it only ever
+//! talks to, so the window, activation and `--watch` can be exercised on a
+//! machine without Unity or Node.js:
+//!
+//! - The fake bridge does what the Unity extension does for the active
+//!   channel every couple of seconds: it writes the editor heartbeat, a scene
+//!   hierarchy read from the channel's scene and a console log to
+//!   `Assets/_MCP/state`, and answers queued commands, bundle builds
+//!   included. A project from `create_mock_project` is enough.
+//! - The fake MCP server is what `--watch` runs instead of `node`: it answers
+//!   JSON-RPC `initialize`, `ping` and `tools/list` on `/mcp` (and `GET
+//!   /health`) and writes made-up activity to the channel's server log.
+//!
+//! Everything simulated is marked `"simulated": true` or `(simulated)`.
+
+use serde::Serialize;
+use specta::Type;
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::error::{ErrorKind, LauncherError};
+use crate::plan::FilePlan;
+use crate::state::SharedState;
+use crate::{bridge, http, logs, unity_yaml, write_guard, LauncherConfig, ProjectChannel};
+
+/// Environment variable that turns simulation on regardless of the config
+pub const ENV_VAR: &str = "BANTER_SIMULATE";
+
+/// How often the fake bridge exports state, like the real one
+const TICK: Duration = Duration::from_secs(2);
+
+/// How often the fake server writes to its log
+const SERVER_HEARTBEAT: Duration = Duration::from_secs(10);
+
+const MAX_LOG_ENTRIES: usize = 50;
+
+/// Scene objects put in the fake hierarchy; demos don't need more
+const MAX_OBJECTS: usize = 500;
+
+/// Console messages the fake editor cycles through
+const EDITOR_LOGS: &[(&str, &str)] = &[
+    ("Log", "[BANTWORKS MCP] Bridge initialized (simulated)"),
+    ("Log", "Refreshing native plugins compatible for Editor"),
+    ("Log", "Asset import finished"),
+    ("Warning", "Lightmap baking skipped in simulation mode"),
+    ("Log", "[BANTWORKS MCP] State exported"),
+];
+
+/// Lines the fake server writes to its log
+const SERVER_LOGS: &[&str] = &[
+    "Connected to Unity bridge",
+    "Scene hierarchy refreshed",
+    "No pending bridge commands",
+];
+
+static BRIDGE_RUNNING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct SimulationStatus {
+    pub enabled: bool,
+    /// Turned on by `BANTER_SIMULATE=1`, so the config setting has no effect
+    pub forced_by_env: bool,
+    pub bridge_running: bool,
+}
+
+fn forced_by_env() -> bool {
+    std::env::var(ENV_VAR).is_ok_and(|v| v == "1")
+}
+
+pub fn enabled(config: &LauncherConfig) -> bool {
+    config.simulation_enabled || forced_by_env()
+}
+
+pub fn status(config: &LauncherConfig) -> SimulationStatus {
+    SimulationStatus {
+        enabled: enabled(config),
+        forced_by_env: forced_by_env(),
+        bridge_running: BRIDGE_RUNNING.load(Ordering::Acquire),
+    }
+}
+
+fn active_channel(config: &LauncherConfig) -> Option<ProjectChannel> {
+    let id = config.active_channel_id.as_deref()?;
+    config.channels.iter().find(|c| c.id == id).cloned()
+}
+
+/// Start the fake bridge if simulation is on and it isn't running yet. It
+/// follows channel switches and stops once simulation is turned off.
+pub fn start_bridge(state: &SharedState) {
+    if !enabled(&state.config()) || BRIDGE_RUNNING.swap(true, Ordering::AcqRel) {
+        return;
+    }
+
+    let state = state.clone();
+    thread::spawn(move || {
+        let mut bridge = FakeBridge::default();
+        loop {
+            if !enabled(&state.config()) {
+                BRIDGE_RUNNING.store(false, Ordering::Release);
+                // Turned back on between the check and the store: keep going
+                if !enabled(&state.config()) || BRIDGE_RUNNING.swap(true, Ordering::AcqRel) {
+                    return;
+                }
+            }
+            if let Some(channel) = active_channel(&state.config()) {
+                bridge.tick(&channel);
+            }
+            thread::sleep(TICK);
+        }
+    });
+}
+
+fn write_state(project: &Path, name: &str, value: &serde_json::Value) -> Result<(), LauncherError> {
+    let dir = bridge::state_dir(project);
+    let mut plan = FilePlan::new(false);
+    plan.create_dir(&dir)?;
+    let content = serde_json::to_string_pretty(value).map_err(|e| {
+        LauncherError::internal(format!("Failed to serialize simulated state: {}", e))
+    })?;
+    plan.write(&dir.join(name), content)
+}
+
+/// GameObjects of a scene with their component types, as the bridge exports them
+fn scene_objects(scene: &Path) -> Result<Vec<serde_json::Value>, LauncherError> {
+    let mut names: Vec<(i64, String)> = Vec::new();
+    let mut components: HashMap<i64, Vec<String>> = HashMap::new();
+
+    for doc in unity_yaml::read_documents(scene)? {
+        let doc = doc.map_err(|e| LauncherError::io("Failed to read scene", scene, e))?;
+        if doc.class_id == unity_yaml::CLASS_GAME_OBJECT {
+            if let Some(name) = doc.name() {
+                names.push((doc.file_id, name.to_string()));
+            }
+        } else if let Some(owner) = doc.game_object_id() {
+            components
+                .entry(owner)
+                .or_default()
+                .push(doc.type_name.clone());
+        }
+        if names.len() >= MAX_OBJECTS {
+            break;
+        }
+    }
+
+    Ok(names
+        .into_iter()
+        .map(|(id, name)| {
+            let types = components.remove(&id).unwrap_or_default();
+            game_object(&name, &types)
+        })
+        .collect())
+}
+
+fn game_object(name: &str, components: &[String]) -> serde_json::Value {
+    serde_json::json!({
+        "name": name,
+        "path": name,
+        "active": true,
+        "layer": 0,
+        "tag": "Untagged",
+        "depth": 0,
+        "position": [0.0, 0.0, 0.0],
+        "rotation": [0.0, 0.0, 0.0],
+        "scale": [1.0, 1.0, 1.0],
+        "components": components
+            .iter()
+            .map(|t| serde_json::json!({
+                "type": t,
+                "fullType": format!("UnityEngine.{}", t),
+                "properties": [],
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// Stands in for the Unity extension of the active channel
+#[derive(Default)]
+struct FakeBridge {
+    ticks: u64,
+    logs: Vec<serde_json::Value>,
+    /// Scene objects read from disk, with the scene path and mtime they came from
+    scene: Option<(PathBuf, Option<SystemTime>, Vec<serde_json::Value>)>,
+    /// Objects created by `create_gameobject` commands
+    created: Vec<serde_json::Value>,
+    /// Object paths removed by `delete_gameobject` commands
+    deleted: Vec<String>,
+    last_error: Option<String>,
+}
+
+impl FakeBridge {
+    fn log(&mut self, level: &str, message: impl Into<String>) {
+        if self.logs.len() >= MAX_LOG_ENTRIES {
+            self.logs.remove(0);
+        }
+        self.logs.push(serde_json::json!({
+            "level": level,
+            "message": message.into(),
+            "timestamp": bridge::now_ms(),
+        }));
+    }
+
+    fn tick(&mut self, channel: &ProjectChannel) {
+        let project = Path::new(&channel.unity_project_path);
+        if !project.join("Assets").is_dir() {
+            return;
+        }
+
+        if self.ticks.is_multiple_of(5) {
+            let (level, message) = EDITOR_LOGS[(self.ticks / 5) as usize % EDITOR_LOGS.len()];
+            self.log(level, message);
+        }
+        self.ticks += 1;
+
+        let result = self
+            .handle_commands(project)
+            .and_then(|_| self.export(project, channel));
+        match result {
+            Ok(()) => self.last_error = None,
+            Err(e) => {
+                if self.last_error.as_deref() != Some(e.message.as_str()) {
+                    eprintln!("Simulated bridge: {}", e);
+                    self.last_error = Some(e.message);
+                }
+            }
+        }
+    }
+
+    fn objects(&mut self, channel: &ProjectChannel) -> Vec<serde_json::Value> {
+        let scene = channel.scene_path.as_ref().map(PathBuf::from);
+        let mut objects = match scene {
+            Some(scene) => {
+                let mtime = fs::metadata(&scene).and_then(|m| m.modified()).ok();
+                let cached = matches!(&self.scene, Some((path, time, _)) if *path == scene && *time == mtime);
+                if !cached {
+                    let objects = scene_objects(&scene).unwrap_or_default();
+                    self.scene = Some((scene, mtime, objects));
+                }
+                self.scene
+                    .as_ref()
+                    .map(|(_, _, o)| o.clone())
+                    .unwrap_or_default()
+            }
+            None => Vec::new(),
+        };
+        if objects.is_empty() {
+            let light = ["Transform".to_string(), "Light".to_string()];
+            objects.push(game_object("Directional Light", &light));
+        }
+        objects.extend(self.created.iter().cloned());
+        objects.retain(|o| {
+            let path = o["path"].as_str().unwrap_or_default();
+            !self.deleted.iter().any(|d| d == path)
+        });
+        objects
+    }
+
+    fn export(&mut self, project: &Path, channel: &ProjectChannel) -> Result<(), LauncherError> {
+        let now = bridge::now_ms();
+        let scene_path = channel.scene_path.clone().unwrap_or_default();
+        let scene_name = Path::new(&scene_path)
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        write_state(
+            project,
+            "editor-state.json",
+            &serde_json::json!({
+                "isPlaying": false,
+                "isPaused": false,
+                "isCompiling": false,
+                "hasCompileErrors": false,
+                "activeScene": scene_name,
+                "activeBuildTarget": "StandaloneWindows64",
+                "selectedObjects": [],
+                "timestamp": now,
+                "simulated": true,
+            }),
+        )?;
+        write_state(
+            project,
+            "scene-hierarchy.json",
+            &serde_json::json!({
+                "sceneName": scene_name,
+                "scenePath": scene_path,
+                "objects": self.objects(channel),
+                "timestamp": now,
+                "simulated": true,
+            }),
+        )?;
+        write_state(
+            project,
+            "console-log.json",
+            &serde_json::json!({
+                "timestamp": now,
+                "count": self.logs.len(),
+                "logs": self.logs,
+                "simulated": true,
+            }),
+        )
+    }
+
+    /// Take the queued command files and answer them the way the bridge would
+    fn handle_commands(&mut self, project: &Path) -> Result<(), LauncherError> {
+        let dir = bridge::commands_dir(project);
+        let Ok(entries) = fs::read_dir(&dir) else {
+            return Ok(());
+        };
+        let mut files: Vec<PathBuf> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|e| e == "json"))
+            .collect();
+        files.sort();
+
+        for file in files {
+            let command = fs::read_to_string(&file)
+                .ok()
+                .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok());
+            FilePlan::new(false).delete(&file)?;
+            let Some(command) = command else {
+                self.log(
+                    "Error",
+                    format!("Unreadable command file {}", file.display()),
+                );
+                continue;
+            };
+            self.handle_command(project, &command)?;
+        }
+        Ok(())
+    }
+
+    fn handle_command(
+        &mut self,
+        project: &Path,
+        command: &serde_json::Value,
+    ) -> Result<(), LauncherError> {
+        let kind = command["type"].as_str().unwrap_or_default();
+        match kind {
+            "build_bundle" => {
+                let platform = command["platform"].as_str().unwrap_or("windows");
+                let output = command["outputPath"].as_str().map(PathBuf::from);
+                let mut plan = FilePlan::new(false);
+                if let Some(output) = &output {
+                    plan.create_dir(output)?;
+                    plan.write(
+                        &output.join(format!("simulated_{}.bundle", platform)),
+                        b"Simulated Banter bundle\n",
+                    )?;
+                }
+                self.log("Log", format!("Built {} bundle (simulated)", platform));
+                write_state(
+                    project,
+                    "build-result.json",
+                    &serde_json::json!({
+                        "success": true,
+                        "platform": platform,
+                        "outputPath": output,
+                        "timestamp": bridge::now_ms(),
+                        "simulated": true,
+                    }),
+                )?;
+            }
+            "refresh" => {
+                write_state(
+                    project,
+                    "import-status.json",
+                    &serde_json::json!({
+                        "completed": true,
+                        "hasErrors": false,
+                        "errorMessage": null,
+                        "timestamp": bridge::now_ms(),
+                        "simulated": true,
+                    }),
+                )?;
+            }
+            "create_gameobject" => {
+                let name = command["name"].as_str().unwrap_or("GameObject");
+                let path = match command["parentPath"].as_str().filter(|p| !p.is_empty()) {
+                    Some(parent) => format!("{}/{}", parent, name),
+                    None => name.to_string(),
+                };
+                let mut object = game_object(name, &["Transform".to_string()]);
+                object["path"] = serde_json::json!(path);
+                self.deleted.retain(|d| *d != path);
+                self.created.push(object);
+                self.log("Log", format!("Created {} (simulated)", path));
+            }
+            "delete_gameobject" => {
+                let path = command["objectPath"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string();
+                self.created
+                    .retain(|o| o["path"].as_str() != Some(path.as_str()));
+                self.log("Log", format!("Deleted {} (simulated)", path));
+                self.deleted.push(path);
+            }
+            other => self.log("Log", format!("Applied {} (simulated)", other)),
+        }
+        Ok(())
+    }
+}
+
+/// Appends to the channel's server log; best effort like the real server's
+struct ServerLog {
+    file: Option<fs::File>,
+}
+
+impl ServerLog {
+    fn open(channel_id: &str) -> Self {
+        let path = logs::server_log_path(channel_id);
+        let file = write_guard::check_write(&path).ok().and_then(|_| {
+            fs::create_dir_all(path.parent()?).ok()?;
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .ok()
+        });
+        ServerLog { file }
+    }
+
+    fn line(&mut self, message: impl AsRef<str>) {
+        if let Some(file) = &mut self.file {
+            let _ = writeln!(file, "[simulated] {}", message.as_ref());
+        }
+    }
+}
+
+fn rpc_result(id: &serde_json::Value, result: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+/// Answer one JSON-RPC message; `None` for notifications
+fn rpc_response(message: &serde_json::Value) -> Option<serde_json::Value> {
+    let id = message.get("id")?;
+    let method = message["method"].as_str().unwrap_or_default();
+    Some(match method {
+        "initialize" => rpc_result(
+            id,
+            serde_json::json!({
+                "protocolVersion": message
+                    .pointer("/params/protocolVersion")
+                    .cloned()
+                    .unwrap_or_else(|| serde_json::json!("2024-11-05")),
+                "capabilities": { "tools": {} },
+                "serverInfo": {
+                    "name": "banter-mcp (simulated)",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            }),
+        ),
+        "ping" => rpc_result(id, serde_json::json!({})),
+        "tools/list" => rpc_result(id, serde_json::json!({ "tools": [] })),
+        _ => serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": -32601, "message": format!("Method not found: {}", method) },
+        }),
+    })
+}
+
+fn handle_connection(mut stream: TcpStream, log: &mut ServerLog) -> std::io::Result<()> {
+    let request = http::read_request(&stream)?;
+    if request.method == "OPTIONS" {
+        return http::write_preflight(&mut stream);
+    }
+
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/health") => {
+            let body = serde_json::json!({ "status": "ok", "simulated": true }).to_string();
+            http::write_response(&mut stream, 200, "application/json", body.as_bytes())
+        }
+        ("POST", "/mcp") => {
+            let Ok(message) = serde_json::from_slice::<serde_json::Value>(&request.body) else {
+                return http::write_response(&mut stream, 400, "text/plain", b"Invalid JSON");
+            };
+            log.line(format!(
+                "<- {}",
+                message["method"].as_str().unwrap_or("response")
+            ));
+            match rpc_response(&message) {
+                Some(response) => http::write_response(
+                    &mut stream,
+                    200,
+                    "application/json",
+                    response.to_string().as_bytes(),
+                ),
+                None => http::write_response(&mut stream, 202, "text/plain", b""),
+            }
+        }
+        _ => http::write_response(&mut stream, 404, "text/plain", b"Not found"),
+    }
+}
+
+/// Run the fake MCP server for a channel until the process is killed
+pub fn run_server(channel_id: &str, port: u16) -> Result<(), LauncherError> {
+    // A process of its own, so the write allowlist isn't set up yet
+    write_guard::set_roots(&crate::read_config()?);
+    let listener = TcpListener::bind(("127.0.0.1", port)).map_err(|e| {
+        LauncherError::new(
+            ErrorKind::Conflict,
+            format!("Failed to listen on port {}: {}", port, e),
+        )
+        .with_hint("Another program may be using this port - pass a different --port")
+    })?;
+
+    let mut log = ServerLog::open(channel_id);
+    log.line(format!(
+        "Banter MCP server listening on http://localhost:{}/mcp",
+        port
+    ));
+
+    let channel = channel_id.to_string();
+    thread::spawn(move || {
+        let mut log = ServerLog::open(&channel);
+        for message in SERVER_LOGS.iter().cycle() {
+            thread::sleep(SERVER_HEARTBEAT);
+            log.line(message);
+        }
+    });
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                if let Err(e) = handle_connection(stream, &mut log) {
+                    log.line(format!("Request failed: {}", e));
+                }
+            }
+            Err(e) => log.line(format!("Accept failed: {}", e)),
+        }
+    }
+    Ok(())
+}
+
+/// Turn simulation mode on or off and remember the choice
+#[tauri::command]
+#[specta::specta]
+pub async fn set_simulation_enabled(
+    state: tauri::State<'_, SharedState>,
+    enabled: bool,
+) -> Result<SimulationStatus, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Simulation", move || {
+        let mut plan = FilePlan::new(false);
+        state.update(&mut plan, |config| {
+            config.simulation_enabled = enabled;
+            Ok(())
+        })?;
+        start_bridge(&state);
+        Ok(status(&state.config()))
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_simulation_status(state: tauri::State<'_, SharedState>) -> SimulationStatus {
+    status(&state.config())
+}
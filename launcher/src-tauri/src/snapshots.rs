@@ -0,0 +1,380 @@
+//! Scene snapshots taken before agent sessions
+//!
+//! Activating a channel copies its scene (or, without a working scene, every
+//! scene under Assets) together with the `.meta` files into
+//! `snapshots/<channel>/<id>/` in the launcher folder, unless
+//! `snapshot_on_activate` is off or nothing changed since the last snapshot.
+//! With `snapshot_git_stash` on, a project under git also gets a stash entry
+//! of its uncommitted work (`git stash create` + `git stash store`, which
+//! leaves the working tree alone). Restoring copies the files back; the state
+//! at that moment is snapshotted first so a restore can be undone. Snapshots
+//! are pruned like backups (`backup_retention`), per channel.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::error::{ErrorKind, LauncherError};
+use crate::plan::FilePlan;
+use crate::state::SharedState;
+use crate::{bridge, git, preflight, unity, LauncherConfig, ProjectChannel};
+
+const RECORD_FILE: &str = "snapshot.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SnapshotFile {
+    /// Relative to the project, with `/` separators
+    pub path: String,
+    pub size: u64,
+    /// Modification time of the original when it was copied, Unix ms
+    pub modified_ms: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Snapshot {
+    pub id: String,
+    pub channel_id: String,
+    /// "activation", "manual", "before-restore" or "session"
+    pub reason: String,
+    /// Unix ms
+    pub created_at: i64,
+    pub files: Vec<SnapshotFile>,
+    /// Commit of the `git stash` entry holding the project's uncommitted work
+    pub git_stash: Option<String>,
+}
+
+fn channel_dir(channel_id: &str) -> PathBuf {
+    crate::launcher_dir().join("snapshots").join(channel_id)
+}
+
+pub fn snapshot_dir(channel_id: &str, id: &str) -> PathBuf {
+    channel_dir(channel_id).join(id)
+}
+
+fn modified_ms(path: &Path) -> i64 {
+    path.metadata()
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+/// The channel's scene files and their `.meta` files
+fn scene_files(channel: &ProjectChannel) -> Vec<PathBuf> {
+    let project = Path::new(&channel.unity_project_path);
+    let scenes = match &channel.scene_path {
+        Some(scene) => vec![PathBuf::from(scene)],
+        None => unity::find_files(&project.join("Assets"), "unity"),
+    };
+    let mut files = Vec::new();
+    for scene in scenes.into_iter().filter(|s| s.is_file()) {
+        let meta = PathBuf::from(format!("{}.meta", scene.to_string_lossy()));
+        files.push(scene);
+        if meta.is_file() {
+            files.push(meta);
+        }
+    }
+    files.sort();
+    files
+}
+
+fn relative(path: &Path, project: &Path) -> Option<String> {
+    let relative = path.strip_prefix(project).ok()?;
+    Some(relative.to_string_lossy().replace('\\', "/"))
+}
+
+/// Snapshots of a channel, newest first
+pub fn list(channel_id: &str) -> Vec<Snapshot> {
+    let Ok(read_dir) = fs::read_dir(channel_dir(channel_id)) else {
+        return Vec::new();
+    };
+    let mut snapshots: Vec<Snapshot> = read_dir
+        .flatten()
+        .map(|e| e.path().join(RECORD_FILE))
+        .filter_map(|p| serde_json::from_str(&fs::read_to_string(p).ok()?).ok())
+        .collect();
+    snapshots.sort_by_key(|s| Reverse(s.created_at));
+    snapshots
+}
+
+/// Whether the files are still exactly what `snapshot` holds
+fn unchanged_since(snapshot: &Snapshot, files: &[SnapshotFile]) -> bool {
+    snapshot.files.len() == files.len()
+        && snapshot
+            .files
+            .iter()
+            .zip(files)
+            .all(|(a, b)| a.path == b.path && a.size == b.size && a.modified_ms == b.modified_ms)
+}
+
+/// Record the project's uncommitted work as a stash entry without touching
+/// the working tree. `None` when the project isn't in a repo or is clean.
+fn git_stash(project: &Path, message: &str) -> Result<Option<String>, LauncherError> {
+    let git = |args: &[&str]| {
+        git::run(project, args)
+            .map_err(|e| e.with_hint("Install git, or turn snapshot_git_stash off"))
+    };
+    if !git::is_repo(project) {
+        return Ok(None);
+    }
+    let created = git(&["stash", "create", message])?;
+    let commit = String::from_utf8_lossy(&created.stdout).trim().to_string();
+    if !created.status.success() || commit.is_empty() {
+        return Ok(None);
+    }
+    let stored = git(&["stash", "store", "-m", message, &commit])?;
+    if !stored.status.success() {
+        return Err(LauncherError::new(
+            ErrorKind::Io,
+            format!(
+                "git stash store failed: {}",
+                String::from_utf8_lossy(&stored.stderr).trim()
+            ),
+        )
+        .with_path(project));
+    }
+    Ok(Some(commit))
+}
+
+fn prune(config: &LauncherConfig, channel_id: &str) {
+    let retention = &config.backup_retention;
+    let cutoff = (retention.max_age_days > 0)
+        .then(|| bridge::now_ms() - retention.max_age_days as i64 * 24 * 60 * 60 * 1000);
+    for (index, snapshot) in list(channel_id).iter().enumerate() {
+        if index >= retention.max_per_kind || cutoff.is_some_and(|c| snapshot.created_at < c) {
+            fs::remove_dir_all(snapshot_dir(channel_id, &snapshot.id)).ok();
+        }
+    }
+}
+
+/// `create` without pruning
+fn take(
+    channel: &ProjectChannel,
+    reason: &str,
+    git: bool,
+    skip_unchanged: bool,
+) -> Result<Option<Snapshot>, LauncherError> {
+    let project = Path::new(&channel.unity_project_path);
+    let sources = scene_files(channel);
+    if sources.is_empty() {
+        return Err(LauncherError::not_found(format!(
+            "No scenes to snapshot in {}",
+            channel.unity_project_path
+        ))
+        .with_path(project));
+    }
+    let mut files = Vec::new();
+    for source in &sources {
+        let Some(path) = relative(source, project) else {
+            return Err(LauncherError::invalid(format!(
+                "Scene is outside its project: {}",
+                source.display()
+            ))
+            .with_path(source));
+        };
+        files.push(SnapshotFile {
+            path,
+            size: source.metadata().map(|m| m.len()).unwrap_or(0),
+            modified_ms: modified_ms(source),
+        });
+    }
+    if skip_unchanged
+        && list(&channel.id)
+            .first()
+            .is_some_and(|s| unchanged_since(s, &files))
+    {
+        return Ok(None);
+    }
+
+    let total: u64 = files.iter().map(|f| f.size).sum();
+    preflight::ensure("scene snapshot", &channel_dir(&channel.id), total)?;
+
+    let created_at = bridge::now_ms();
+    let id = format!(
+        "{}-{}",
+        created_at,
+        &uuid::Uuid::new_v4().simple().to_string()[..8]
+    );
+    let dir = snapshot_dir(&channel.id, &id);
+    let mut plan = FilePlan::new(false);
+    for (source, file) in sources.iter().zip(&files) {
+        let dest = dir.join("files").join(&file.path);
+        plan.create_dir(dest.parent().unwrap_or(&dir))?;
+        plan.copy(source, &dest)?;
+    }
+
+    let git_stash = if git {
+        git_stash(project, &format!("banter-launcher snapshot {}", id))?
+    } else {
+        None
+    };
+    let snapshot = Snapshot {
+        id,
+        channel_id: channel.id.clone(),
+        reason: reason.to_string(),
+        created_at,
+        files,
+        git_stash,
+    };
+    let record = serde_json::to_string_pretty(&snapshot)
+        .map_err(|e| LauncherError::internal(format!("Failed to serialize snapshot: {}", e)))?;
+    plan.write(&dir.join(RECORD_FILE), record)?;
+    Ok(Some(snapshot))
+}
+
+/// Copy the channel's scenes into a new snapshot. `git` adds a stash entry
+/// when the project is a repo. Returns `None` for `skip_unchanged` when the
+/// files match the latest snapshot.
+pub fn create(
+    config: &LauncherConfig,
+    channel: &ProjectChannel,
+    reason: &str,
+    git: bool,
+    skip_unchanged: bool,
+) -> Result<Option<Snapshot>, LauncherError> {
+    let snapshot = take(channel, reason, git, skip_unchanged)?;
+    prune(config, &channel.id);
+    Ok(snapshot)
+}
+
+/// Snapshot the newly active channel; called on every activation. Failures
+/// are logged rather than blocking the activation.
+pub fn channel_activated(config: &LauncherConfig, channel: &ProjectChannel) {
+    if !config.snapshot_on_activate {
+        return;
+    }
+    if let Err(e) = create(
+        config,
+        channel,
+        "activation",
+        config.snapshot_git_stash,
+        true,
+    ) {
+        eprintln!("Failed to snapshot {} before activation: {}", channel.id, e);
+    }
+}
+
+/// Store a snapshot taken elsewhere, e.g. on another machine, with the
+/// contents of its files by path
+pub fn add(
+    config: &LauncherConfig,
+    snapshot: &Snapshot,
+    contents: &BTreeMap<String, String>,
+) -> Result<(), LauncherError> {
+    crate::sanitize::identifier("Snapshot id", &snapshot.id)?;
+    let dir = snapshot_dir(&snapshot.channel_id, &snapshot.id);
+    let total: u64 = contents.values().map(|c| c.len() as u64).sum();
+    preflight::ensure("scene snapshot", &channel_dir(&snapshot.channel_id), total)?;
+    let mut plan = FilePlan::new(false);
+    for file in &snapshot.files {
+        let content = contents.get(&file.path).ok_or_else(|| {
+            LauncherError::invalid(format!("Snapshot {} is missing {}", snapshot.id, file.path))
+        })?;
+        let dest = crate::sanitize::path_within(
+            "Snapshot file",
+            &dir.join("files").join(&file.path).to_string_lossy(),
+            &dir,
+        )?;
+        plan.create_dir(dest.parent().unwrap_or(&dir))?;
+        plan.write(&dest, content)?;
+    }
+    let record = serde_json::to_string_pretty(snapshot)
+        .map_err(|e| LauncherError::internal(format!("Failed to serialize snapshot: {}", e)))?;
+    plan.write(&dir.join(RECORD_FILE), record)?;
+    prune(config, &snapshot.channel_id);
+    Ok(())
+}
+
+/// Put a snapshot's files back into the channel's project
+pub fn restore(
+    config: &LauncherConfig,
+    channel: &ProjectChannel,
+    snapshot_id: &str,
+) -> Result<Snapshot, LauncherError> {
+    let snapshot = list(&channel.id)
+        .into_iter()
+        .find(|s| s.id == snapshot_id)
+        .ok_or_else(|| {
+            LauncherError::not_found(format!("Snapshot not found: {}", snapshot_id))
+                .with_hint("It may have been pruned by the retention policy")
+        })?;
+
+    // Pruning waits until the restore is done; it could pick the snapshot being restored
+    take(channel, "before-restore", false, false)?;
+
+    let project = Path::new(&channel.unity_project_path);
+    let total: u64 = snapshot.files.iter().map(|f| f.size).sum();
+    preflight::ensure("snapshot restore", project, total)?;
+    let dir = snapshot_dir(&channel.id, &snapshot.id);
+    let mut plan = FilePlan::new(false);
+    for file in &snapshot.files {
+        let dest = project.join(&file.path);
+        crate::sanitize::path_within("Snapshot file", &dest.to_string_lossy(), project)?;
+        plan.create_dir(dest.parent().unwrap_or(project))?;
+        plan.copy(&dir.join("files").join(&file.path), &dest)?;
+    }
+    prune(config, &channel.id);
+    Ok(snapshot)
+}
+
+/// Copy the channel's scene files into a new snapshot. `include_git`
+/// overrides `snapshot_git_stash`.
+#[tauri::command]
+#[specta::specta]
+pub async fn snapshot_scene(
+    state: tauri::State<'_, SharedState>,
+    channel_id: String,
+    include_git: Option<bool>,
+) -> Result<Snapshot, LauncherError> {
+    crate::sanitize::channel_id(&channel_id)?;
+    let state = state.inner().clone();
+    crate::commands::blocking("Snapshot", move || {
+        let config = state.config();
+        let channel = crate::find_channel(&config, &channel_id)?;
+        let git = include_git.unwrap_or(config.snapshot_git_stash);
+        create(&config, &channel, "manual", git, false)?
+            .ok_or_else(|| LauncherError::internal("Snapshot was skipped"))
+    })
+    .await
+}
+
+/// The channel's snapshots, newest first
+#[tauri::command]
+#[specta::specta]
+pub async fn list_snapshots(
+    state: tauri::State<'_, SharedState>,
+    channel_id: String,
+) -> Result<Vec<Snapshot>, LauncherError> {
+    crate::sanitize::channel_id(&channel_id)?;
+    let state = state.inner().clone();
+    crate::commands::blocking("List snapshots", move || {
+        state.channel(&channel_id)?;
+        Ok(list(&channel_id))
+    })
+    .await
+}
+
+/// Put a snapshot's scene files back. Its git stash, if any, is left for
+/// `git stash apply`, since merging it is the user's call.
+#[tauri::command]
+#[specta::specta]
+pub async fn restore_snapshot(
+    state: tauri::State<'_, SharedState>,
+    channel_id: String,
+    snapshot_id: String,
+) -> Result<Snapshot, LauncherError> {
+    crate::sanitize::channel_id(&channel_id)?;
+    crate::sanitize::identifier("Snapshot id", &snapshot_id)?;
+    let state = state.inner().clone();
+    crate::commands::blocking("Restore snapshot", move || {
+        let config = state.config();
+        let channel = crate::find_channel(&config, &channel_id)?;
+        restore(&config, &channel, &snapshot_id)
+    })
+    .await
+}
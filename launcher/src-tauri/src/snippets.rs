@@ -0,0 +1,218 @@
+//! MCP config snippets for clients the launcher doesn't configure itself
+//!
+//! `generate_mcp_snippet` renders a channel into a built-in template
+//! (`claude`, `cursor`, `json`, `shell`) or any template text the user
+//! supplies. Templates use `{{variable}}` or `{{variable|filter}}`:
+//!
+//! | Variable | |
+//! |---|---|
+//! | `id`, `name` | The channel |
+//! | `project_path`, `scene_path` | Its Unity project and scene |
+//! | `server_cwd` | Folder the server starts in |
+//! | `server_path` | Configured MCP server script |
+//! | `command` | Executable that runs it (`node`) |
+//! | `env` | Server environment as `KEY=value` lines |
+//!
+//! Filters: `json` renders a JSON string (or, for `env`, an object) and
+//! `shell` a POSIX-quoted word (or, for `env`, `KEY='value'` assignments).
+
+use serde::Serialize;
+use specta::Type;
+use std::collections::BTreeMap;
+
+use crate::error::LauncherError;
+use crate::plan::FilePlan;
+use crate::state::{AppState, SharedState};
+
+const COMMAND: &str = "node";
+
+const CLAUDE_TEMPLATE: &str = r#"{
+  "mcpServers": {
+    "banter": {
+      "command": {{command|json}},
+      "args": [{{server_path|json}}],
+      "cwd": {{server_cwd|json}},
+      "env": {{env|json}}
+    }
+  }
+}
+"#;
+
+const JSON_TEMPLATE: &str = r#"{
+  "command": {{command|json}},
+  "args": [{{server_path|json}}],
+  "cwd": {{server_cwd|json}},
+  "env": {{env|json}}
+}
+"#;
+
+const SHELL_TEMPLATE: &str =
+    "cd {{server_cwd|shell}} && {{env|shell}} {{command}} {{server_path|shell}}\n";
+
+/// Built-in templates and where their output goes
+const BUILT_IN: &[(&str, &str, &str)] = &[
+    ("claude", CLAUDE_TEMPLATE, "Merge into ~/.claude.json"),
+    (
+        "cursor",
+        CLAUDE_TEMPLATE,
+        "Merge into ~/.cursor/mcp.json or .cursor/mcp.json in a project",
+    ),
+    (
+        "json",
+        JSON_TEMPLATE,
+        "Use as the server entry in any client with a JSON MCP config",
+    ),
+    (
+        "shell",
+        SHELL_TEMPLATE,
+        "Run in a POSIX shell to start the server over stdio",
+    ),
+];
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct McpSnippet {
+    /// Built-in template name, or "custom"
+    pub template: String,
+    pub text: String,
+    /// Where the snippet goes, for built-in templates
+    pub hint: Option<String>,
+}
+
+/// Values a template can refer to
+struct Variables {
+    strings: BTreeMap<&'static str, String>,
+    env: BTreeMap<String, String>,
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+fn json_string(value: &str) -> String {
+    serde_json::Value::String(value.to_string()).to_string()
+}
+
+impl Variables {
+    fn render(&self, name: &str, filter: Option<&str>) -> Result<String, LauncherError> {
+        if name == "env" {
+            return Ok(match filter {
+                None => self
+                    .env
+                    .iter()
+                    .map(|(k, v)| format!("{}={}", k, v))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                Some("json") => serde_json::to_string(&self.env).unwrap_or_default(),
+                Some("shell") => self
+                    .env
+                    .iter()
+                    .map(|(k, v)| format!("{}={}", k, shell_quote(v)))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                Some(other) => return Err(unknown_filter(other)),
+            });
+        }
+
+        let value = self.strings.get(name).ok_or_else(|| {
+            LauncherError::invalid(format!("Unknown template variable: {}", name)).with_hint(
+                "Use id, name, project_path, scene_path, server_cwd, server_path, command or env",
+            )
+        })?;
+        match filter {
+            None => Ok(value.clone()),
+            Some("json") => Ok(json_string(value)),
+            Some("shell") => Ok(shell_quote(value)),
+            Some(other) => Err(unknown_filter(other)),
+        }
+    }
+}
+
+fn unknown_filter(filter: &str) -> LauncherError {
+    LauncherError::invalid(format!("Unknown template filter: {}", filter))
+        .with_hint("Use json or shell")
+}
+
+/// Replace every `{{variable|filter}}` in a template
+fn render(template: &str, variables: &Variables) -> Result<String, LauncherError> {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find("}}")
+            .ok_or_else(|| LauncherError::invalid("Template has an unclosed {{"))?;
+
+        let tag = after[..end].trim();
+        let (name, filter) = match tag.split_once('|') {
+            Some((name, filter)) => (name.trim(), Some(filter.trim())),
+            None => (tag, None),
+        };
+        output.push_str(&variables.render(name, filter)?);
+        rest = &after[end + 2..];
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
+/// Render a channel into a built-in template name or custom template text.
+/// The channel's secrets env file is written so the snippet can refer to it.
+pub fn generate(
+    state: &AppState,
+    channel_id: &str,
+    template: &str,
+) -> Result<McpSnippet, LauncherError> {
+    let config = state.config();
+    let channel = crate::find_channel(&config, channel_id)?;
+
+    let (name, text, hint) = match BUILT_IN.iter().find(|(name, _, _)| *name == template) {
+        Some((name, text, hint)) => (name.to_string(), *text, Some(hint.to_string())),
+        None if template.contains("{{") => ("custom".to_string(), template, None),
+        None => {
+            let names: Vec<&str> = BUILT_IN.iter().map(|(name, _, _)| *name).collect();
+            return Err(
+                LauncherError::invalid(format!("Unknown template: {}", template))
+                    .with_hint(format!("Built-in templates: {}", names.join(", "))),
+            );
+        }
+    };
+
+    let cwd = crate::channels::server_cwd(&channel)?;
+    let mut plan = FilePlan::new(false);
+    let env = crate::mcp_server_env_vars(&mut plan, &config, &channel)?;
+
+    let variables = Variables {
+        strings: BTreeMap::from([
+            ("id", channel.id.clone()),
+            ("name", channel.name.clone()),
+            ("project_path", channel.unity_project_path.clone()),
+            ("scene_path", channel.scene_path.clone().unwrap_or_default()),
+            ("server_cwd", cwd.to_string_lossy().to_string()),
+            ("server_path", config.mcp_server_path.clone()),
+            ("command", COMMAND.to_string()),
+        ]),
+        env,
+    };
+
+    Ok(McpSnippet {
+        template: name,
+        text: render(text, &variables)?,
+        hint,
+    })
+}
+
+/// Render a channel's MCP server entry for copy/paste into another client
+#[tauri::command]
+#[specta::specta]
+pub async fn generate_mcp_snippet(
+    state: tauri::State<'_, SharedState>,
+    channel_id: String,
+    template: String,
+) -> Result<McpSnippet, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("MCP snippet", move || {
+        generate(&state, &channel_id, &template)
+    })
+    .await
+}
@@ -0,0 +1,239 @@
+//! Finding which parts of the toolchain this machine has; the command table
+//! is `launcher_core::stack`
+//!
+//! Detection runs once the window is up and again whenever
+//! `get_capabilities` is asked to refresh, e.g. after installing Node.js;
+//! the result is emitted as `capabilities`. `guard` refuses the commands
+//! that need a missing capability, and `check_editor` starting Unity without
+//! an editor, from the last detection only, so a command never waits on one. Until the first detection finishes nothing
+//! is refused. In simulation mode the fake server and bridge stand in for
+//! Node.js and Unity.
+
+pub use launcher_core::stack::*;
+
+use std::process::Command;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::error::LauncherError;
+use crate::faults::{self, Fault};
+use crate::state::SharedState;
+use crate::{account, bridge, client_config, net, simulation, unity, LauncherConfig};
+
+/// How long the network probe waits for an answer
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+static LAST: Mutex<Option<Capabilities>> = Mutex::new(None);
+
+fn flag(capability: Capability, found: Result<String, String>) -> CapabilityFlag {
+    let (available, detail) = match found {
+        Ok(detail) => (true, detail),
+        Err(detail) => (false, detail),
+    };
+    CapabilityFlag {
+        capability,
+        available,
+        detail,
+    }
+}
+
+fn node() -> Result<String, String> {
+    match faults::check(Fault::NodeMissing)
+        .and_then(|_| Command::new("node").arg("--version").output())
+    {
+        Ok(output) if output.status.success() => Ok(format!(
+            "Node.js {}",
+            String::from_utf8_lossy(&output.stdout).trim()
+        )),
+        _ => Err("Node.js not found on PATH".to_string()),
+    }
+}
+
+fn unity_editor(config: &LauncherConfig) -> Result<String, String> {
+    match unity::installed_editors(config).first() {
+        Some(editor) => Ok(editor.to_string_lossy().to_string()),
+        None if config.unity_editor_path.is_some() => Err(format!(
+            "The Unity editor path doesn't exist and Unity Hub has no editors: {}",
+            config.unity_editor_path.as_deref().unwrap_or_default()
+        )),
+        None => Err("No editors installed by Unity Hub and no Unity editor path set".to_string()),
+    }
+}
+
+fn on_path(program: &str) -> Option<std::path::PathBuf> {
+    let names: &[&str] = if cfg!(windows) {
+        &[".exe", ".cmd"]
+    } else {
+        &[""]
+    };
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .flat_map(|dir| {
+            names
+                .iter()
+                .map(move |ext| dir.join(format!("{}{}", program, ext)))
+        })
+        .find(|path| path.is_file())
+}
+
+fn claude_code() -> Result<String, String> {
+    if let Some(path) = on_path("claude") {
+        return Ok(path.to_string_lossy().to_string());
+    }
+    let config = client_config::get_claude_config_path();
+    if config.exists() {
+        return Ok(config.to_string_lossy().to_string());
+    }
+    Err(format!(
+        "No claude command on PATH and no {}",
+        config.to_string_lossy()
+    ))
+}
+
+/// Any HTTP answer from the auth endpoint, error statuses included, means
+/// the network works
+fn network(config: &LauncherConfig) -> Result<String, String> {
+    let url = account::auth_endpoint(config);
+    if let Err(e) = faults::check(Fault::Offline) {
+        return Err(format!("No answer from {}: {}", url, e));
+    }
+    match net::agent(&config.network, &url)
+        .request("HEAD", &url)
+        .timeout(PROBE_TIMEOUT)
+        .call()
+    {
+        Ok(_) | Err(ureq::Error::Status(..)) => Ok(url),
+        Err(e) => Err(format!("No answer from {}: {}", url, e)),
+    }
+}
+
+/// Look for every capability now, the slow ones side by side
+pub fn detect(config: &LauncherConfig) -> Capabilities {
+    let simulated = simulation::enabled(config);
+    let (node, unity, claude_code, network) = thread::scope(|scope| {
+        let node = scope.spawn(|| {
+            if simulated {
+                Ok("Simulated MCP server".to_string())
+            } else {
+                node()
+            }
+        });
+        let network = scope.spawn(|| network(config));
+        let unity = if simulated {
+            Ok("Simulated Unity bridge".to_string())
+        } else {
+            unity_editor(config)
+        };
+        let claude_code = claude_code();
+        let join = |handle: thread::ScopedJoinHandle<'_, Result<String, String>>| {
+            handle
+                .join()
+                .unwrap_or_else(|_| Err("Detection failed".to_string()))
+        };
+        (join(node), unity, claude_code, join(network))
+    });
+    Capabilities::new(
+        vec![
+            flag(Capability::Node, node),
+            flag(Capability::Unity, unity),
+            flag(Capability::ClaudeCode, claude_code),
+            flag(Capability::Network, network),
+        ],
+        bridge::now_ms(),
+    )
+}
+
+/// The last detection, if one has finished
+pub fn current() -> Option<Capabilities> {
+    LAST.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Detect again, keep the result and tell the window
+pub fn refresh(app: &AppHandle, config: &LauncherConfig) -> Capabilities {
+    let capabilities = detect(config);
+    for flag in capabilities.flags.iter().filter(|f| !f.available) {
+        eprintln!("{} unavailable: {}", flag.capability.label(), flag.detail);
+    }
+    *LAST.lock().unwrap_or_else(|e| e.into_inner()) = Some(capabilities.clone());
+    let _ = app.emit("capabilities", capabilities.clone());
+    capabilities
+}
+
+/// Run the first detection from the window
+pub fn start(app: &AppHandle, state: &SharedState) {
+    let app = app.clone();
+    let state = state.clone();
+    thread::spawn(move || refresh(&app, &state.config()));
+}
+
+/// Refuse `action` now if `capability` is missing; for the command line,
+/// which has no earlier detection to go by
+pub fn require(
+    config: &LauncherConfig,
+    capability: Capability,
+    action: &str,
+) -> Result<(), LauncherError> {
+    let simulated = simulation::enabled(config);
+    let found = match capability {
+        Capability::Node | Capability::Unity if simulated => Ok(String::new()),
+        Capability::Node => node(),
+        Capability::Unity => unity_editor(config),
+        Capability::ClaudeCode => claude_code(),
+        Capability::Network => network(config),
+    };
+    let flag = flag(capability, found);
+    if flag.available {
+        Ok(())
+    } else {
+        Err(missing(&flag, action))
+    }
+}
+
+/// Refuse to start a Unity editor for `action` when the last detection
+/// found none; called where the launcher starts one
+pub fn check_editor(action: &str) -> Result<(), LauncherError> {
+    let last = current();
+    match last.as_ref().and_then(|c| c.flag(Capability::Unity)) {
+        Some(flag) if !flag.available => Err(missing(flag, action)),
+        _ => Ok(()),
+    }
+}
+
+/// Wrap the window's command handler so that commands needing a missing
+/// capability are refused
+pub fn guard<F>(handler: F) -> impl Fn(tauri::ipc::Invoke) -> bool + Send + Sync + 'static
+where
+    F: Fn(tauri::ipc::Invoke) -> bool + Send + Sync + 'static,
+{
+    move |invoke| {
+        let command = invoke.message.command().to_string();
+        if requirement(&command).is_some() {
+            if let Some(Err(e)) = current().map(|c| c.check(&command)) {
+                invoke.resolver.reject(e);
+                return true;
+            }
+        }
+        handler(invoke)
+    }
+}
+
+/// Which of Node.js, a Unity editor, Claude Code and the network this
+/// machine has, and the commands refused for what is missing. `refresh`
+/// looks again instead of answering from the last detection.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_capabilities(
+    app: AppHandle,
+    state: tauri::State<'_, SharedState>,
+    refresh: Option<bool>,
+) -> Result<Capabilities, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Capabilities", move || {
+        Ok(match current().filter(|_| !refresh.unwrap_or(false)) {
+            Some(capabilities) => capabilities,
+            None => self::refresh(&app, &state.config()),
+        })
+    })
+    .await
+}
@@ -0,0 +1,134 @@
+//! Startup timings and deferred initialization
+//!
+//! Only what the first screen needs (config, file scopes) runs before the
+//! window opens. Scan cache loading, the project watcher, backup pruning and
+//! job history recovery run on a background thread once the event loop is
+//! ready, so launch time doesn't grow with the number of channels. `get_startup_report` shows how
+//! long each phase took.
+
+use serde::Serialize;
+use specta::Type;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::Instant;
+use tauri::{AppHandle, Emitter};
+
+use crate::state::SharedState;
+use crate::{
+    backup, control_api, editor_monitor, instances, job_history, scheduler, sessions, simulation,
+    stack, support, triggers,
+};
+
+static PROCESS_START: OnceLock<Instant> = OnceLock::new();
+static PHASES: Mutex<Vec<StartupPhase>> = Mutex::new(Vec::new());
+static WINDOW_READY_MS: OnceLock<u64> = OnceLock::new();
+static DEFERRED_STARTED: AtomicBool = AtomicBool::new(false);
+static DEFERRED_DONE: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct StartupPhase {
+    pub name: String,
+    /// Ms since process start
+    pub started_ms: u64,
+    pub duration_ms: u64,
+    /// Ran in the background after the window opened
+    pub deferred: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct StartupReport {
+    pub phases: Vec<StartupPhase>,
+    /// Ms from process start until the event loop was ready
+    pub window_ready_ms: Option<u64>,
+    pub deferred_complete: bool,
+    /// Ms since process start
+    pub uptime_ms: u64,
+}
+
+fn since_start(at: Instant) -> u64 {
+    let start = *PROCESS_START.get_or_init(Instant::now);
+    at.saturating_duration_since(start).as_millis() as u64
+}
+
+/// Record the process start; call first thing in `main`
+pub fn begin() {
+    PROCESS_START.get_or_init(Instant::now);
+}
+
+fn timed<T>(name: &str, deferred: bool, work: impl FnOnce() -> T) -> T {
+    let started = Instant::now();
+    let result = work();
+    let phase = StartupPhase {
+        name: name.to_string(),
+        started_ms: since_start(started),
+        duration_ms: started.elapsed().as_millis() as u64,
+        deferred,
+    };
+    PHASES.lock().unwrap_or_else(|e| e.into_inner()).push(phase);
+    result
+}
+
+/// Run and time a phase that blocks the window from opening
+pub fn phase<T>(name: &str, work: impl FnOnce() -> T) -> T {
+    timed(name, false, work)
+}
+
+pub fn report() -> StartupReport {
+    StartupReport {
+        phases: PHASES.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+        window_ready_ms: WINDOW_READY_MS.get().copied(),
+        deferred_complete: DEFERRED_DONE.load(Ordering::Acquire),
+        uptime_ms: since_start(Instant::now()),
+    }
+}
+
+/// Start the deferred phases; called once the event loop is ready.
+/// Emits `startup-complete` with the report when they finish.
+pub fn run_deferred(app: &AppHandle, state: SharedState) {
+    WINDOW_READY_MS.get_or_init(|| since_start(Instant::now()));
+    if DEFERRED_STARTED.swap(true, Ordering::AcqRel) {
+        return;
+    }
+
+    let app = app.clone();
+    thread::spawn(move || {
+        let config = state.config();
+        timed("scan_cache", true, || state.scans.preload());
+        timed("project_watcher", true, || {
+            state.scans.start_watching(&config)
+        });
+        timed("backup_prune", true, || {
+            backup::prune_all(&config.backup_retention)
+        });
+        timed("job_history", true, job_history::recover);
+        if config.control_api_enabled {
+            timed("control_api", true, || {
+                if let Err(e) = control_api::start(&app, &state) {
+                    eprintln!("Failed to start control API: {}", e);
+                }
+            });
+        }
+        if simulation::enabled(&config) {
+            timed("simulation", true, || simulation::start_bridge(&state));
+        }
+        timed("sessions", true, || sessions::start_refresher(&app, &state));
+        timed("scheduler", true, || scheduler::start(&app, &state));
+        timed("editor_monitor", true, || {
+            editor_monitor::start(&app, &state)
+        });
+        timed("triggers", true, || triggers::start(&app, &state));
+        timed("support", true, || support::start_bridge_capture(&state));
+        timed("capabilities", true, || stack::start(&app, &state));
+        timed("instances", true, || instances::start(&state, Vec::new()));
+        DEFERRED_DONE.store(true, Ordering::Release);
+        let _ = app.emit("startup-complete", report());
+    });
+}
+
+/// How long each startup phase took, and whether the deferred ones are done
+#[tauri::command]
+#[specta::specta]
+pub fn get_startup_report() -> StartupReport {
+    report()
+}
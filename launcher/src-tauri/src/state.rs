@@ -0,0 +1,254 @@
+//! Launcher state shared by all commands
+//!
+//! The config is read from disk once and then kept in memory. Changes go
+//! through `update`, which holds the write lock while the new config is
+//! persisted, so two commands can no longer both read the file, edit their
+//! copy and have the second write silently undo the first.
+//!
+//! Edits from the UI (`save_config`, channel field updates) are saved in the
+//! background once they stop coming, so a drag-reorder or bulk edit becomes one
+//! write. `flush` writes any pending change immediately.
+
+use std::sync::{Arc, Condvar, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use crate::assets::AssetCache;
+use crate::dashboard::DashboardCache;
+use crate::error::LauncherError;
+use crate::guids::GuidCache;
+use crate::jobs::JobQueue;
+use crate::plan::FilePlan;
+use crate::scan::ScanCache;
+use crate::{
+    control_api, i18n, path_index, preview_server, sessions, storage, telemetry, write_guard,
+    LauncherConfig, ProjectChannel,
+};
+
+/// Pending changes are saved once there has been no change for this long...
+const SAVE_DELAY: Duration = Duration::from_millis(500);
+
+/// ...or at the latest this long after the first unsaved change
+const SAVE_MAX_DELAY: Duration = Duration::from_secs(3);
+
+/// Unsaved in-memory changes
+#[derive(Clone, Copy)]
+struct PendingSave {
+    first: Instant,
+    last: Instant,
+}
+
+/// Handle registered with `tauri::Builder::manage`; commands clone it into
+/// blocking work
+pub type SharedState = Arc<AppState>;
+
+pub struct AppState {
+    config: RwLock<LauncherConfig>,
+    pending: Mutex<Option<PendingSave>>,
+    pending_changed: Condvar,
+    /// Running localhost preview server, if any
+    pub preview_server: Mutex<Option<preview_server::RunningServer>>,
+    /// Running localhost control API, if enabled
+    pub control_api: Mutex<Option<control_api::RunningApi>>,
+    pub scans: ScanCache,
+    /// Prefab, material and script indexes behind `list_project_assets`
+    pub assets: AssetCache,
+    /// GUID indexes behind `resolve_guid` and the reference checks
+    pub guids: GuidCache,
+    /// Channel checks behind `get_dashboard`
+    pub dashboard: DashboardCache,
+    pub jobs: JobQueue,
+}
+
+impl AppState {
+    pub fn new(config: LauncherConfig) -> Self {
+        write_guard::set_roots(&config);
+        path_index::rebuild(&config);
+        i18n::set_locale(&config.locale);
+        telemetry::set_enabled(config.telemetry.enabled);
+        storage::follow(config.storage_backend);
+        AppState {
+            config: RwLock::new(config),
+            pending: Mutex::new(None),
+            pending_changed: Condvar::new(),
+            preview_server: Mutex::new(None),
+            control_api: Mutex::new(None),
+            scans: ScanCache::default(),
+            assets: AssetCache::default(),
+            guids: GuidCache::default(),
+            dashboard: DashboardCache::default(),
+            jobs: JobQueue::default(),
+        }
+    }
+
+    /// Load the config from disk
+    pub fn load() -> Result<Self, LauncherError> {
+        crate::read_config().map(AppState::new)
+    }
+
+    /// Snapshot of the current config
+    pub fn config(&self) -> LauncherConfig {
+        self.config
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Look up a channel by id
+    pub fn channel(&self, channel_id: &str) -> Result<ProjectChannel, LauncherError> {
+        let config = self.config.read().unwrap_or_else(|e| e.into_inner());
+        crate::find_channel(&config, channel_id)
+    }
+
+    /// Re-read the config from disk, replacing the in-memory copy. Unsaved
+    /// changes are discarded; `flush` first to keep them.
+    pub fn reload(&self) -> Result<LauncherConfig, LauncherError> {
+        let mut config = self.config.write().unwrap_or_else(|e| e.into_inner());
+        let loaded = crate::read_config()?;
+        self.pending
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+        write_guard::set_roots(&loaded);
+        path_index::rebuild(&loaded);
+        i18n::set_locale(&loaded.locale);
+        telemetry::set_enabled(loaded.telemetry.enabled);
+        storage::follow(loaded.storage_backend);
+        self.scans.watch(&loaded);
+        *config = loaded.clone();
+        Ok(loaded)
+    }
+
+    /// Apply a change to the config and persist it as part of `plan`. In a
+    /// dry run nothing is written and the in-memory config is left as is.
+    pub fn update<T>(
+        &self,
+        plan: &mut FilePlan,
+        change: impl FnOnce(&mut LauncherConfig) -> Result<T, LauncherError>,
+    ) -> Result<T, LauncherError> {
+        let mut config = self.config.write().unwrap_or_else(|e| e.into_inner());
+        let mut updated = config.clone();
+        let result = change(&mut updated)?;
+
+        crate::plan_config_write(plan, &updated)?;
+        if !plan.dry_run {
+            // The write included any pending changes too
+            self.pending
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .take();
+            write_guard::set_roots(&updated);
+            path_index::rebuild(&updated);
+            i18n::set_locale(&updated.locale);
+            telemetry::set_enabled(updated.telemetry.enabled);
+            storage::follow(updated.storage_backend);
+            self.scans.watch(&updated);
+            sessions::active_channel_changed(&config, &updated);
+            *config = updated;
+        }
+        Ok(result)
+    }
+
+    /// Apply a change to the config in memory and schedule a debounced save
+    pub fn update_deferred<T>(
+        &self,
+        change: impl FnOnce(&mut LauncherConfig) -> Result<T, LauncherError>,
+    ) -> Result<T, LauncherError> {
+        let mut config = self.config.write().unwrap_or_else(|e| e.into_inner());
+        let mut updated = config.clone();
+        let result = change(&mut updated)?;
+
+        write_guard::set_roots(&updated);
+        path_index::rebuild(&updated);
+
+        i18n::set_locale(&updated.locale);
+        telemetry::set_enabled(updated.telemetry.enabled);
+        storage::follow(updated.storage_backend);
+        self.scans.watch(&updated);
+        sessions::active_channel_changed(&config, &updated);
+        *config = updated;
+        drop(config);
+
+        let now = Instant::now();
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        let first = pending.map(|p| p.first).unwrap_or(now);
+        *pending = Some(PendingSave { first, last: now });
+        self.pending_changed.notify_all();
+        Ok(result)
+    }
+
+    /// Write pending changes now. Returns whether there was anything to write.
+    pub fn flush(&self) -> Result<bool, LauncherError> {
+        // Holding the read lock keeps updates out until the write is done
+        let config = self.config.read().unwrap_or_else(|e| e.into_inner());
+        let Some(pending) = self
+            .pending
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+        else {
+            return Ok(false);
+        };
+
+        if let Err(e) = crate::plan_config_write(&mut FilePlan::new(false), &config) {
+            // Keep the change queued so the next flush retries
+            let mut current = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+            current.get_or_insert(PendingSave {
+                first: pending.first,
+                last: Instant::now(),
+            });
+            return Err(e);
+        }
+        Ok(true)
+    }
+
+    /// Background loop writing pending changes once they settle; runs for the
+    /// life of the app
+    pub fn run_saver(&self) {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        loop {
+            let Some(save) = *pending else {
+                pending = self
+                    .pending_changed
+                    .wait(pending)
+                    .unwrap_or_else(|e| e.into_inner());
+                continue;
+            };
+
+            let due = (save.last + SAVE_DELAY).min(save.first + SAVE_MAX_DELAY);
+            let now = Instant::now();
+            if now < due {
+                pending = self
+                    .pending_changed
+                    .wait_timeout(pending, due - now)
+                    .unwrap_or_else(|e| e.into_inner())
+                    .0;
+                continue;
+            }
+
+            drop(pending);
+            if let Err(e) = self.flush() {
+                eprintln!("Failed to save config: {}", e);
+                // Back off rather than retrying in a tight loop
+                std::thread::sleep(SAVE_MAX_DELAY);
+            }
+            pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    /// Apply a change to a stored channel; saved with the next debounced write
+    pub fn update_channel(
+        &self,
+        channel_id: &str,
+        change: impl FnOnce(&mut ProjectChannel),
+    ) -> Result<ProjectChannel, LauncherError> {
+        self.update_deferred(|config| {
+            let channel = config
+                .channels
+                .iter_mut()
+                .find(|c| c.id == channel_id)
+                .ok_or_else(|| LauncherError::channel_not_found(channel_id))?;
+            change(channel);
+            Ok(channel.clone())
+        })
+    }
+}
@@ -0,0 +1,253 @@
+//! The launcher's own state as an MCP server
+//!
+//! With `serve_launcher_state` on, Claude Code's config gets a
+//! `banter-launcher` entry next to `banter` that runs `banter-launcher
+//! --mcp-status`: a small stdio MCP server with resources only, so an agent
+//! can find out which channel is active and whether Unity is connected
+//! without someone relaying it. The config is read from disk for every
+//! request, so the answers follow activations made in the window. Nothing
+//! here writes.
+//!
+//! Resources:
+//! - `launcher://state`: the active channel, its editor connection and which
+//!   modes are on
+//! - `launcher://channels`: every channel with its editor connection
+//! - `launcher://channels/<id>/health`: the channel's health checks
+//! - `launcher://channels/<id>/server-log`: the end of its MCP server log
+
+use serde_json::Value;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use crate::error::{ErrorKind, LauncherError};
+use crate::guids::GuidCache;
+use crate::{bridge, health, logs, observer, server_pool, simulation};
+use crate::{LauncherConfig, ProjectChannel};
+
+/// Command line switch that runs the server
+pub const SWITCH: &str = "--mcp-status";
+
+const URI_PREFIX: &str = "launcher://";
+
+/// How much of a server log `server-log` returns
+const LOG_LINES: usize = 200;
+const LOG_MAX_BYTES: u64 = 64 * 1024;
+
+/// JSON-RPC error codes: unknown method, and the MCP code for a resource
+/// that doesn't exist
+const METHOD_NOT_FOUND: i64 = -32601;
+const RESOURCE_NOT_FOUND: i64 = -32002;
+const INTERNAL_ERROR: i64 = -32603;
+
+fn channel_summary(config: &LauncherConfig, channel: &ProjectChannel) -> Value {
+    let project = Path::new(&channel.unity_project_path);
+    serde_json::json!({
+        "id": channel.id,
+        "name": channel.name,
+        "unity_project_path": channel.unity_project_path,
+        "scene_path": channel.scene_path,
+        "active": config.active_channel_id.as_deref() == Some(channel.id.as_str()),
+        "editor_connected": bridge::is_editor_connected(project),
+        "editor_heartbeat_age_ms": bridge::editor_heartbeat_ms(project)
+            .map(|ts| bridge::now_ms() - ts),
+    })
+}
+
+fn state(config: &LauncherConfig) -> Value {
+    let active = config
+        .active_channel_id
+        .as_deref()
+        .and_then(|id| config.channels.iter().find(|c| c.id == id))
+        .map(|c| channel_summary(config, c));
+    serde_json::json!({
+        "launcher_version": env!("CARGO_PKG_VERSION"),
+        "active_channel": active,
+        "channel_count": config.channels.len(),
+        "auto_configure": config.auto_start,
+        "observer_mode": observer::is_active(config),
+        "simulation": simulation::enabled(config),
+        "record_sessions": config.record_sessions,
+        "server_pool": config.server_pool.enabled,
+        "pooled_servers": server_pool::servers(),
+    })
+}
+
+fn resource(uri: String, name: String, description: &str, mime_type: &str) -> Value {
+    serde_json::json!({
+        "uri": uri,
+        "name": name,
+        "description": description,
+        "mimeType": mime_type,
+    })
+}
+
+fn resources(config: &LauncherConfig) -> Vec<Value> {
+    let mut resources = vec![
+        resource(
+            format!("{}state", URI_PREFIX),
+            "Launcher state".to_string(),
+            "Active channel, whether Unity is connected to it, and which launcher modes are on",
+            "application/json",
+        ),
+        resource(
+            format!("{}channels", URI_PREFIX),
+            "Channels".to_string(),
+            "Every configured channel and whether its Unity editor is connected",
+            "application/json",
+        ),
+    ];
+    for channel in &config.channels {
+        resources.push(resource(
+            format!("{}channels/{}/health", URI_PREFIX, channel.id),
+            format!("{} health", channel.name),
+            "Health checks of the channel: project, scene, extension, bridge, server",
+            "application/json",
+        ));
+        resources.push(resource(
+            format!("{}channels/{}/server-log", URI_PREFIX, channel.id),
+            format!("{} server log", channel.name),
+            "The last lines of the channel's MCP server log",
+            "text/plain",
+        ));
+    }
+    resources
+}
+
+fn to_text(value: impl serde::Serialize) -> Result<String, LauncherError> {
+    serde_json::to_string_pretty(&value)
+        .map_err(|e| LauncherError::internal(format!("Failed to serialize resource: {}", e)))
+}
+
+/// Contents and MIME type of the resource at `uri`
+fn read(
+    config: &LauncherConfig,
+    guids: &GuidCache,
+    uri: &str,
+) -> Result<(String, &'static str), LauncherError> {
+    let not_found = || LauncherError::not_found(format!("No such resource: {}", uri));
+    let path = uri.strip_prefix(URI_PREFIX).ok_or_else(not_found)?;
+    let segments: Vec<&str> = path.split('/').collect();
+    match segments.as_slice() {
+        ["state"] => Ok((to_text(state(config))?, "application/json")),
+        ["channels"] => {
+            let channels: Vec<Value> = config
+                .channels
+                .iter()
+                .map(|c| channel_summary(config, c))
+                .collect();
+            Ok((to_text(channels)?, "application/json"))
+        }
+        ["channels", id, "health"] => {
+            let channel = crate::find_channel(config, id)?;
+            Ok((
+                to_text(health::check_channel(&channel, config, guids))?,
+                "application/json",
+            ))
+        }
+        ["channels", id, "server-log"] => {
+            let channel = crate::find_channel(config, id)?;
+            let path = logs::server_log_path(&channel.id);
+            if !path.exists() {
+                return Ok((
+                    "(The server has not written a log yet)".to_string(),
+                    "text/plain",
+                ));
+            }
+            let tail = logs::read_tail(&path, LOG_LINES, LOG_MAX_BYTES)?;
+            Ok((tail.text, "text/plain"))
+        }
+        _ => Err(not_found()),
+    }
+}
+
+fn rpc_result(id: &Value, result: Value) -> Value {
+    serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+fn rpc_error(id: &Value, code: i64, message: impl Into<String>) -> Value {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message.into() },
+    })
+}
+
+/// Answer one JSON-RPC message; `None` for notifications
+fn respond(message: &Value, guids: &GuidCache) -> Option<Value> {
+    let id = message.get("id")?;
+    let method = message["method"].as_str().unwrap_or_default();
+    Some(match method {
+        "initialize" => rpc_result(
+            id,
+            serde_json::json!({
+                "protocolVersion": message
+                    .pointer("/params/protocolVersion")
+                    .cloned()
+                    .unwrap_or_else(|| serde_json::json!("2024-11-05")),
+                "capabilities": { "resources": {} },
+                "serverInfo": {
+                    "name": "banter-launcher",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            }),
+        ),
+        "ping" => rpc_result(id, serde_json::json!({})),
+        "resources/list" => match crate::read_config() {
+            Ok(config) => rpc_result(id, serde_json::json!({ "resources": resources(&config) })),
+            Err(e) => rpc_error(id, INTERNAL_ERROR, e.to_string()),
+        },
+        "resources/read" => {
+            let uri = message
+                .pointer("/params/uri")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            match crate::read_config().and_then(|config| read(&config, guids, uri)) {
+                Ok((text, mime_type)) => rpc_result(
+                    id,
+                    serde_json::json!({
+                        "contents": [{ "uri": uri, "mimeType": mime_type, "text": text }],
+                    }),
+                ),
+                Err(e) if e.kind == ErrorKind::NotFound => {
+                    rpc_error(id, RESOURCE_NOT_FOUND, e.to_string())
+                }
+                Err(e) => rpc_error(id, INTERNAL_ERROR, e.to_string()),
+            }
+        }
+        // Clients ask for these regardless of the capabilities
+        "tools/list" => rpc_result(id, serde_json::json!({ "tools": [] })),
+        "prompts/list" => rpc_result(id, serde_json::json!({ "prompts": [] })),
+        "resources/templates/list" => {
+            rpc_result(id, serde_json::json!({ "resourceTemplates": [] }))
+        }
+        _ => rpc_error(
+            id,
+            METHOD_NOT_FOUND,
+            format!("Method not found: {}", method),
+        ),
+    })
+}
+
+/// Serve MCP on stdio until the client closes it
+pub fn run() -> Result<(), LauncherError> {
+    let mut stdout = io::stdout().lock();
+    let guids = GuidCache::default();
+    for line in io::stdin().lock().lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        let Ok(message) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        let Some(response) = respond(&message, &guids) else {
+            continue;
+        };
+        if writeln!(stdout, "{}", response)
+            .and_then(|_| stdout.flush())
+            .is_err()
+        {
+            break;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,382 @@
+//! Support mode: one switch that captures what a bug report needs
+//!
+//! `start_support_mode` opens a capture in `support/<id>/` in the launcher
+//! folder. Until `finish_support_mode`:
+//! - every command the launcher runs is traced with its duration and error
+//!   (`launcher.log`)
+//! - the client entry goes through the launcher's stdio proxy (see
+//!   `mcp_proxy`), whose inspector copies every JSON-RPC line both ways
+//!   (`mcp-traffic.jsonl`)
+//! - the window records the command files the MCP server queues for the
+//!   Unity editor, and the editor connecting and disconnecting
+//!   (`bridge.jsonl`)
+//! - the MCP server logs at trace level (`BANTER_MCP_LOG_LEVEL`)
+//!
+//! The new client entry takes effect once Claude Code reconnects to the
+//! server. `finish_support_mode` puts the entry back and packs the capture,
+//! the tails of the server log and Editor.log, the setup report and the
+//! health checks into a single `.bantermcp-support` file, with the home
+//! folder shown as `~`. Each captured file stops growing at
+//! `MAX_PART_BYTES`, so a capture left on stays bounded.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{BTreeMap, HashSet};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::error::LauncherError;
+use crate::plan::FilePlan;
+use crate::state::{AppState, SharedState};
+use crate::{bridge, health, logs, setup_report};
+
+/// Set to `trace` for the MCP server while support mode is on
+pub const LOG_LEVEL_VAR: &str = "BANTER_MCP_LOG_LEVEL";
+
+const BUNDLE_FORMAT: &str = "bantermcp-support";
+const BUNDLE_VERSION: u32 = 1;
+
+pub const LAUNCHER_LOG: &str = "launcher.log";
+pub const MCP_TRAFFIC: &str = "mcp-traffic.jsonl";
+pub const BRIDGE_TRAFFIC: &str = "bridge.jsonl";
+
+/// Largest a captured file or log tail gets
+const MAX_PART_BYTES: u64 = 4 * 1024 * 1024;
+
+/// How long processes trust what they last read of the marker file
+const RECHECK: Duration = Duration::from_secs(2);
+
+/// How often the window polls the bridge while capturing
+const BRIDGE_POLL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SupportSession {
+    pub id: String,
+    pub started_ms: i64,
+    /// The channel active when it started, whose bridge is captured
+    pub channel_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct SupportStatus {
+    pub session: Option<SupportSession>,
+    /// The client entry was rewritten; Claude Code needs to reconnect
+    pub client_config_updated: bool,
+}
+
+/// What `finish_support_mode` wrote
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct SupportBundle {
+    pub file_name: String,
+    /// A copy kept in the launcher folder
+    pub path: String,
+    pub content: String,
+    /// Names of the files packed
+    pub parts: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct BundleFile {
+    format: String,
+    version: u32,
+    session: SupportSession,
+    finished_ms: i64,
+    launcher_version: String,
+    os: String,
+    setup_report: Option<String>,
+    health: Option<serde_json::Value>,
+    parts: BTreeMap<String, String>,
+}
+
+/// The marker as this process last read it, and when
+static ACTIVE: Mutex<Option<(Instant, Option<SupportSession>)>> = Mutex::new(None);
+
+fn support_dir() -> PathBuf {
+    crate::launcher_dir().join("support")
+}
+
+fn marker_path() -> PathBuf {
+    support_dir().join("active.json")
+}
+
+pub fn capture_dir(id: &str) -> PathBuf {
+    support_dir().join(id)
+}
+
+/// The running capture, if support mode is on. Read from the marker file, so
+/// the proxy processes Claude Code starts see it too.
+pub fn active() -> Option<SupportSession> {
+    let mut cached = ACTIVE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((read, session)) = cached.as_ref() {
+        if read.elapsed() < RECHECK {
+            return session.clone();
+        }
+    }
+    let session = fs::read_to_string(marker_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok());
+    *cached = Some((Instant::now(), session.clone()));
+    session
+}
+
+fn set_active(session: Option<SupportSession>) {
+    *ACTIVE.lock().unwrap_or_else(|e| e.into_inner()) = Some((Instant::now(), session));
+}
+
+/// Add a line to one of the capture's files. Best effort: capturing never
+/// makes anything else fail.
+fn append(session: &SupportSession, file: &str, line: &str) {
+    let path = capture_dir(&session.id).join(file);
+    if path.metadata().is_ok_and(|m| m.len() >= MAX_PART_BYTES) {
+        return;
+    }
+    let _ = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut f| writeln!(f, "{}", line));
+}
+
+/// Note what a subsystem did, while support mode is on
+pub fn trace(subsystem: &str, message: &str) {
+    if let Some(session) = active() {
+        let line = format!("{} [{}] {}", bridge::now_ms(), subsystem, message);
+        append(&session, LAUNCHER_LOG, &line);
+    }
+}
+
+/// The proxy inspector: copy a JSON-RPC line passing through the proxy,
+/// `direction` being `client` or `server`
+pub fn inspect(channel_id: &str, direction: &str, line: &str) {
+    if let Some(session) = active() {
+        let entry = serde_json::json!({
+            "at_ms": bridge::now_ms(),
+            "channel_id": channel_id,
+            "from": direction,
+            "line": line,
+        });
+        append(&session, MCP_TRAFFIC, &entry.to_string());
+    }
+}
+
+/// Point the active channel's client entry at the proxy, or back, when the
+/// launcher manages it
+fn reconfigure(state: &AppState) -> Result<bool, LauncherError> {
+    let config = state.config();
+    let Some(id) = config
+        .active_channel_id
+        .as_deref()
+        .filter(|_| config.auto_start)
+    else {
+        return Ok(false);
+    };
+    let channel = crate::find_channel(&config, id)?;
+    crate::update_claude_mcp_config(state, channel, config.mcp_server_path, None)?;
+    Ok(true)
+}
+
+pub fn status() -> SupportStatus {
+    SupportStatus {
+        session: active(),
+        client_config_updated: false,
+    }
+}
+
+pub fn start(state: &AppState) -> Result<SupportStatus, LauncherError> {
+    if let Some(session) = active() {
+        return Ok(SupportStatus {
+            session: Some(session),
+            client_config_updated: false,
+        });
+    }
+    let session = SupportSession {
+        id: uuid::Uuid::new_v4().to_string(),
+        started_ms: bridge::now_ms(),
+        channel_id: state.config().active_channel_id,
+    };
+    let content = serde_json::to_string_pretty(&session)
+        .map_err(|e| LauncherError::internal(format!("Failed to serialize session: {}", e)))?;
+    let mut plan = FilePlan::new(false);
+    plan.create_dir(&capture_dir(&session.id))?;
+    plan.write(&marker_path(), content)?;
+    set_active(Some(session.clone()));
+    trace(
+        "support",
+        &format!(
+            "Support mode on: launcher {} on {} {}",
+            env!("CARGO_PKG_VERSION"),
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        ),
+    );
+    Ok(SupportStatus {
+        session: Some(session),
+        client_config_updated: reconfigure(state)?,
+    })
+}
+
+fn tail(path: &Path) -> Option<String> {
+    logs::read_tail(path, usize::MAX, MAX_PART_BYTES)
+        .ok()
+        .map(|chunk| chunk.text)
+}
+
+/// Turn support mode off and pack the capture into one file
+pub fn finish(state: &AppState) -> Result<SupportBundle, LauncherError> {
+    let session = active()
+        .ok_or_else(|| LauncherError::invalid("Support mode is off").with_hint("Start it first"))?;
+    trace("support", "Support mode off");
+    FilePlan::new(false).delete(&marker_path())?;
+    set_active(None);
+
+    let mut warnings = Vec::new();
+    if let Err(e) = reconfigure(state) {
+        warnings.push(format!("The client entry wasn't put back: {}", e));
+    }
+
+    let dir = capture_dir(&session.id);
+    let mut parts = BTreeMap::new();
+    for name in [LAUNCHER_LOG, MCP_TRAFFIC, BRIDGE_TRAFFIC] {
+        if let Some(text) = tail(&dir.join(name)) {
+            parts.insert(name.to_string(), setup_report::redact(&text));
+        }
+    }
+    if let Some(text) = logs::editor_log_path().and_then(|path| tail(&path)) {
+        parts.insert("Editor.log".to_string(), setup_report::redact(&text));
+    }
+
+    let config = state.config();
+    let channel = session
+        .channel_id
+        .as_deref()
+        .and_then(|id| crate::find_channel(&config, id).ok());
+    let mut setup = None;
+    let mut checks = None;
+    if let Some(channel) = &channel {
+        if let Some(text) = tail(&logs::server_log_path(&channel.id)) {
+            parts.insert("server.log".to_string(), setup_report::redact(&text));
+        }
+        match setup_report::generate(state, &channel.id) {
+            Ok(report) => setup = Some(report.markdown),
+            Err(e) => warnings.push(format!("No setup report: {}", e)),
+        }
+        checks = serde_json::to_value(health::check_channel(channel, &config, &state.guids)).ok();
+    }
+
+    let bundle = BundleFile {
+        format: BUNDLE_FORMAT.to_string(),
+        version: BUNDLE_VERSION,
+        session: session.clone(),
+        finished_ms: bridge::now_ms(),
+        launcher_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+        setup_report: setup,
+        health: checks,
+        parts,
+    };
+    let content = serde_json::to_string_pretty(&bundle)
+        .map_err(|e| LauncherError::internal(format!("Failed to serialize bundle: {}", e)))?;
+    let file_name = format!("support-{}.{}", &session.id[..8], BUNDLE_FORMAT);
+    let path = support_dir().join(&file_name);
+    let mut plan = FilePlan::new(false);
+    plan.write(&path, &content)?;
+    plan.delete_dir(&dir)?;
+    Ok(SupportBundle {
+        file_name,
+        path: path.to_string_lossy().to_string(),
+        content,
+        parts: bundle.parts.into_keys().collect(),
+        warnings,
+    })
+}
+
+/// Record the captured channel's bridge traffic from the window
+pub fn start_bridge_capture(state: &SharedState) {
+    let state = state.clone();
+    thread::spawn(move || {
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut connected: Option<bool> = None;
+        loop {
+            thread::sleep(BRIDGE_POLL);
+            let Some(session) = active() else {
+                seen.clear();
+                connected = None;
+                continue;
+            };
+            let config = state.config();
+            let Some(channel) = session
+                .channel_id
+                .as_deref()
+                .and_then(|id| crate::find_channel(&config, id).ok())
+            else {
+                continue;
+            };
+            let project = Path::new(&channel.unity_project_path);
+            let record = |event: serde_json::Value| {
+                let mut entry = serde_json::json!({ "at_ms": bridge::now_ms() });
+                entry["event"] = event;
+                append(&session, BRIDGE_TRAFFIC, &entry.to_string());
+            };
+
+            let now_connected = bridge::is_editor_connected(project);
+            if connected.is_some_and(|c| c != now_connected) {
+                record(serde_json::json!({ "editor_connected": now_connected }));
+            }
+            connected = Some(now_connected);
+
+            let Ok(entries) = fs::read_dir(bridge::commands_dir(project)) else {
+                continue;
+            };
+            let mut present = HashSet::new();
+            for path in entries.flatten().map(|e| e.path()) {
+                if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                    continue;
+                }
+                let name = path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string();
+                if !seen.contains(&name) {
+                    if let Ok(command) = fs::read_to_string(&path) {
+                        record(serde_json::json!({ "file": name, "command": command }));
+                    }
+                }
+                present.insert(name);
+            }
+            seen = present;
+        }
+    });
+}
+
+/// Whether support mode is on, and since when
+#[tauri::command]
+#[specta::specta]
+pub async fn get_support_mode() -> Result<SupportStatus, LauncherError> {
+    crate::commands::blocking("Support mode", || Ok(status())).await
+}
+
+/// Start capturing traces, MCP traffic and bridge traffic
+#[tauri::command]
+#[specta::specta]
+pub async fn start_support_mode(
+    state: tauri::State<'_, SharedState>,
+) -> Result<SupportStatus, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Start support mode", move || start(&state)).await
+}
+
+/// Stop capturing and return the diagnostics bundle
+#[tauri::command]
+#[specta::specta]
+pub async fn finish_support_mode(
+    state: tauri::State<'_, SharedState>,
+) -> Result<SupportBundle, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Finish support mode", move || finish(&state)).await
+}
@@ -0,0 +1,137 @@
+//! Telemetry commands; the counting is `launcher_core::telemetry`
+//!
+//! `get_telemetry` shows what is and isn't recorded and the exact report
+//! `send_telemetry` would post, so the window can explain the switch before
+//! anyone turns it on. `export_telemetry` writes that report to a file
+//! instead. Sending needs telemetry on and an endpoint set, and only ever
+//! happens when asked; a sent report's counts are cleared.
+
+pub use launcher_core::telemetry::*;
+
+use serde::Serialize;
+use specta::Type;
+use std::collections::BTreeMap;
+
+use crate::error::LauncherError;
+use crate::net;
+use crate::plan::FilePlan;
+use crate::state::SharedState;
+use crate::{bridge, sanitize, LauncherConfig};
+
+/// What a report holds; nothing else is sent
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct TelemetryReport {
+    pub launcher_version: String,
+    pub os: String,
+    /// Unix ms span of the counts
+    pub since_ms: i64,
+    pub until_ms: i64,
+    pub features: BTreeMap<String, u64>,
+    pub errors: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct TelemetryStatus {
+    pub enabled: bool,
+    pub endpoint: Option<String>,
+    pub recorded: Vec<String>,
+    pub not_recorded: Vec<String>,
+    /// Where the counts are kept
+    pub file: String,
+    /// The report as it would be sent now
+    pub report: TelemetryReport,
+}
+
+pub fn report(usage: Usage) -> TelemetryReport {
+    TelemetryReport {
+        launcher_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        since_ms: usage.since_ms,
+        until_ms: bridge::now_ms(),
+        features: usage.features,
+        errors: usage.errors,
+    }
+}
+
+pub fn status(config: &LauncherConfig) -> Result<TelemetryStatus, LauncherError> {
+    let lines = |lines: &[&str]| lines.iter().map(|l| l.to_string()).collect();
+    Ok(TelemetryStatus {
+        enabled: config.telemetry.enabled,
+        endpoint: config.telemetry.endpoint.clone(),
+        recorded: lines(RECORDED),
+        not_recorded: lines(NOT_RECORDED),
+        file: usage_path().to_string_lossy().to_string(),
+        report: report(flush()?),
+    })
+}
+
+/// Post the counts to the configured endpoint and clear them
+pub fn send(config: &LauncherConfig) -> Result<TelemetryReport, LauncherError> {
+    let settings = &config.telemetry;
+    if !settings.enabled {
+        return Err(LauncherError::invalid("Telemetry is off")
+            .with_hint("Turn telemetry on in settings first"));
+    }
+    let Some(endpoint) = settings.endpoint.as_deref() else {
+        return Err(LauncherError::invalid("No telemetry endpoint is set")
+            .with_hint("Set telemetry.endpoint, or export the report and share it yourself"));
+    };
+    let usage = flush()?;
+    if usage.is_empty() {
+        return Err(LauncherError::invalid("Nothing has been counted yet"));
+    }
+    let report = report(usage);
+    let body = serde_json::to_value(&report)
+        .map_err(|e| LauncherError::internal(format!("Failed to serialize report: {}", e)))?;
+    net::agent(&config.network, endpoint)
+        .post(endpoint)
+        .send_json(body)
+        .map_err(|e| {
+            LauncherError::network(format!("Sending telemetry to {} failed: {}", endpoint, e))
+                .with_hint("The counts are kept; try again later")
+        })?;
+    clear()?;
+    Ok(report)
+}
+
+/// Whether telemetry is on, what it records, and the report as it stands
+#[tauri::command]
+#[specta::specta]
+pub async fn get_telemetry(
+    state: tauri::State<'_, SharedState>,
+) -> Result<TelemetryStatus, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Telemetry", move || status(&state.config())).await
+}
+
+/// Write the report `send_telemetry` would post to `file`
+#[tauri::command]
+#[specta::specta]
+pub async fn export_telemetry(file: String) -> Result<TelemetryReport, LauncherError> {
+    crate::commands::blocking("Export telemetry", move || {
+        let path = sanitize::path("Telemetry export", &file)?;
+        let report = report(flush()?);
+        let content = serde_json::to_string_pretty(&report)
+            .map_err(|e| LauncherError::internal(format!("Failed to serialize report: {}", e)))?;
+        FilePlan::new(false).write(&path, content)?;
+        Ok(report)
+    })
+    .await
+}
+
+/// Forget every count
+#[tauri::command]
+#[specta::specta]
+pub async fn clear_telemetry() -> Result<(), LauncherError> {
+    crate::commands::blocking("Clear telemetry", clear).await
+}
+
+/// Post the counts to `telemetry.endpoint` now; returns what was sent
+#[tauri::command]
+#[specta::specta]
+pub async fn send_telemetry(
+    state: tauri::State<'_, SharedState>,
+) -> Result<TelemetryReport, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Send telemetry", move || send(&state.config())).await
+}
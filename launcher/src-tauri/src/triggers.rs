@@ -0,0 +1,119 @@
+//! Running the command files in channel projects; the file format is
+//! `launcher_core::triggers`
+//!
+//! While `file_triggers` is on the window looks in every channel's trigger
+//! folder each `TICK`, runs what it finds on that channel and deletes the
+//! file. Each outcome is emitted as `file-trigger`, so the window can show
+//! it. In observer mode the files are left where they are, unread, until it
+//! is turned off.
+
+use serde::Serialize;
+use specta::Type;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::error::LauncherError;
+use crate::plan::FilePlan;
+use crate::state::SharedState;
+use crate::{fs_scope, observer, server_pool, snapshots, ProjectChannel};
+use launcher_core::triggers::{self, TriggerCommand};
+
+/// How often the trigger folders are checked
+const TICK: Duration = Duration::from_secs(2);
+
+/// Payload of `file-trigger`
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct TriggerOutcome {
+    pub channel_id: String,
+    pub file: String,
+    /// `None` when the file couldn't be read as a command
+    pub command: Option<String>,
+    pub ok: bool,
+    pub message: String,
+}
+
+fn run(
+    app: &AppHandle,
+    state: &SharedState,
+    channel: &ProjectChannel,
+    command: &TriggerCommand,
+) -> Result<String, LauncherError> {
+    match command {
+        TriggerCommand::Activate { take_over } => {
+            crate::activate_channel(state, &channel.id, *take_over, false)?;
+            let config = state.config();
+            fs_scope::sync(app, &config);
+            let _ = app.emit("config-changed", config);
+            Ok(format!("Activated {}", channel.name))
+        }
+        TriggerCommand::RestartServer => {
+            let server = server_pool::restart(&state.config(), channel)?;
+            Ok(format!(
+                "Restarted the MCP server of {} on port {}",
+                channel.name, server.port
+            ))
+        }
+        TriggerCommand::Snapshot { reason } => {
+            let reason = reason.as_deref().unwrap_or("File trigger");
+            match snapshots::create(&state.config(), channel, reason, false, false)? {
+                Some(snapshot) => Ok(format!("Snapshot {} of {}", snapshot.id, channel.name)),
+                None => Ok(format!("{} has no changes to snapshot", channel.name)),
+            }
+        }
+    }
+}
+
+/// Delete one trigger file and run its command. The file goes first, so one
+/// that can't be deleted isn't run again every tick.
+pub fn process(
+    app: &AppHandle,
+    state: &SharedState,
+    channel: &ProjectChannel,
+    path: &Path,
+) -> TriggerOutcome {
+    let command = triggers::read(path);
+    // Checked before the file goes, so observer mode leaves the project as it is
+    let result = observer::check(&state.config(), "File triggers")
+        .and_then(|_| FilePlan::new(false).delete(path))
+        .and_then(|_| {
+            command
+                .as_ref()
+                .map_err(Clone::clone)
+                .and_then(|command| run(app, state, channel, command))
+        });
+    let (ok, message) = match result {
+        Ok(message) => (true, message),
+        Err(e) => (false, e.to_string()),
+    };
+    TriggerOutcome {
+        channel_id: channel.id.clone(),
+        file: path.to_string_lossy().to_string(),
+        command: command.ok().map(|c| c.name().to_string()),
+        ok,
+        message,
+    }
+}
+
+/// Start watching the trigger folders from the window
+pub fn start(app: &AppHandle, state: &SharedState) {
+    let app = app.clone();
+    let state = state.clone();
+    thread::spawn(move || loop {
+        thread::sleep(TICK);
+        let config = state.config();
+        if !config.file_triggers || observer::is_active(&config) {
+            continue;
+        }
+        for channel in &config.channels {
+            for path in triggers::pending(&triggers::trigger_dir(channel)) {
+                let outcome = process(&app, &state, channel, &path);
+                if !outcome.ok {
+                    eprintln!("File trigger {} failed: {}", outcome.file, outcome.message);
+                }
+                let _ = app.emit("file-trigger", outcome);
+            }
+        }
+    });
+}
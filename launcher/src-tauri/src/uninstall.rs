@@ -0,0 +1,219 @@
+//! Cleanup before the launcher is removed
+//!
+//! Uninstalling the launcher alone leaves its `banter` entry in Claude Code's
+//! config pointing at a server that may be gone, which makes Claude report a
+//! broken MCP server on every start, and the bridge script in every project.
+//! `uninstall_cleanup` undoes what the launcher set up: it stops the servers
+//! it runs, removes the `banter` client entry, takes the Unity extension out
+//! of every channel's project and gives up the launcher's project claims.
+//! With `delete_config` the launcher folder goes too (config, secrets,
+//! backups, icons, snapshots), and with `delete_logs` its logs. The
+//! installer runs the same cleanup through `--uninstall-cleanup`.
+//!
+//! A project that can't be cleaned doesn't stop the others; its error is
+//! listed in the report.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs;
+use std::path::Path;
+
+use crate::error::LauncherError;
+use crate::plan::FilePlan;
+use crate::state::{AppState, SharedState};
+use crate::{
+    client_config, control_api, extension, instances, preview_server, secrets, sessions,
+    LauncherConfig,
+};
+
+#[derive(Debug, Clone, Default, Deserialize, Type)]
+pub struct UninstallOptions {
+    /// Delete the launcher folder: config, secrets, backups, icons, snapshots
+    #[serde(default)]
+    pub delete_config: bool,
+    /// Delete the launcher's logs
+    #[serde(default)]
+    pub delete_logs: bool,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// A channel whose project couldn't be cleaned
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct UninstallFailure {
+    pub channel_id: String,
+    pub error: LauncherError,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct UninstallReport {
+    pub plan: FilePlan,
+    /// What was stopped, e.g. "preview server"
+    pub stopped: Vec<String>,
+    /// Client configs the `banter` entry was removed from
+    pub client_configs: Vec<String>,
+    /// Channels whose project no longer has the extension
+    pub extensions_removed: Vec<String>,
+    pub failures: Vec<UninstallFailure>,
+}
+
+/// Stop the servers this launcher runs; the MCP servers themselves belong to
+/// their clients
+fn stop_servers(state: &AppState) -> Vec<String> {
+    let mut stopped = Vec::new();
+    if preview_server::current_status(state).running {
+        preview_server::stop(state);
+        stopped.push("preview server".to_string());
+    }
+    if control_api::current_status(state).running {
+        control_api::stop(state);
+        stopped.push("control API".to_string());
+    }
+    instances::stop();
+    stopped
+}
+
+/// Everything in the launcher folder, except the logs unless they go too
+fn plan_data_removal(plan: &mut FilePlan, delete_logs: bool) -> Result<(), LauncherError> {
+    let dir = crate::launcher_dir();
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Ok(());
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !delete_logs && entry.file_name() == "logs" {
+            continue;
+        }
+        if path.is_dir() {
+            plan.delete_dir(&path)?;
+        } else {
+            plan.delete(&path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Take the extension out of every channel's project. Returns the channels
+/// cleaned and the ones that failed.
+fn clean_projects(
+    plan: &mut FilePlan,
+    config: &LauncherConfig,
+) -> (Vec<String>, Vec<UninstallFailure>) {
+    let mut removed = Vec::new();
+    let mut failures = Vec::new();
+    for channel in &config.channels {
+        let project = Path::new(&channel.unity_project_path);
+        if !project.is_dir() {
+            continue;
+        }
+        let had_extension =
+            crate::check_unity_extension(channel.unity_project_path.clone()).unwrap_or(false);
+        let result =
+            extension::plan_extension_removal(plan, &channel.unity_project_path).and_then(|_| {
+                if plan.dry_run {
+                    Ok(())
+                } else {
+                    sessions::release(channel)
+                }
+            });
+        match result {
+            Ok(()) if had_extension => removed.push(channel.id.clone()),
+            Ok(()) => {}
+            Err(error) => failures.push(UninstallFailure {
+                channel_id: channel.id.clone(),
+                error,
+            }),
+        }
+    }
+    (removed, failures)
+}
+
+pub fn cleanup(
+    state: &AppState,
+    options: &UninstallOptions,
+) -> Result<UninstallReport, LauncherError> {
+    let config = state.config();
+    let mut plan = FilePlan::new(options.dry_run);
+    let stopped = if options.dry_run {
+        Vec::new()
+    } else {
+        stop_servers(state)
+    };
+
+    let mut client_configs = Vec::new();
+    let claude_config = crate::get_claude_mcp_config().ok();
+    let has_entry = claude_config
+        .as_ref()
+        .is_some_and(|c| c.pointer("/mcpServers/banter").is_some());
+    let has_launcher_entry = claude_config
+        .as_ref()
+        .is_some_and(|c| c["mcpServers"].get(client_config::LAUNCHER_ENTRY).is_some());
+    if has_entry || has_launcher_entry {
+        client_config::plan_claude_mcp_removal(&mut plan, &config.backup_retention)?;
+        client_config::plan_launcher_entry(
+            &mut plan,
+            &crate::get_claude_config_path(),
+            None,
+            &config.backup_retention,
+        )?;
+        client_configs.push(
+            crate::get_claude_config_path()
+                .to_string_lossy()
+                .to_string(),
+        );
+    }
+    for channel in &config.channels {
+        for client in &channel.workspace_clients {
+            let Some(path) = crate::channels::workspace_client_config(channel, client) else {
+                continue;
+            };
+            if path.is_file() {
+                client_config::plan_claude_mcp_removal_from(
+                    &mut plan,
+                    &path,
+                    &config.backup_retention,
+                )?;
+                client_configs.push(path.to_string_lossy().to_string());
+            }
+        }
+    }
+
+    let (extensions_removed, failures) = clean_projects(&mut plan, &config);
+
+    if options.delete_config {
+        // A pending save would otherwise bring the config back
+        if !options.dry_run {
+            state.flush()?;
+        }
+        for channel in &config.channels {
+            secrets::remove_channel_secrets(&mut plan, &channel.id)?;
+        }
+        plan_data_removal(&mut plan, options.delete_logs)?;
+    } else if options.delete_logs {
+        plan.delete_dir(&crate::launcher_dir().join("logs"))?;
+    }
+
+    Ok(UninstallReport {
+        plan,
+        stopped,
+        client_configs,
+        extensions_removed,
+        failures,
+    })
+}
+
+/// Undo everything the launcher set up before it is uninstalled: stop its
+/// servers, remove the `banter` client entry and the Unity extension from
+/// every channel's project, and optionally delete its config and logs.
+#[tauri::command]
+#[specta::specta]
+pub async fn uninstall_cleanup(
+    state: tauri::State<'_, SharedState>,
+    options: Option<UninstallOptions>,
+) -> Result<UninstallReport, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Uninstall cleanup", move || {
+        cleanup(&state, &options.unwrap_or_default())
+    })
+    .await
+}
@@ -0,0 +1,141 @@
+//! Unity editor discovery
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{ErrorKind, LauncherError};
+
+/// Read the editor version from ProjectSettings/ProjectVersion.txt
+pub fn project_editor_version(unity_project_path: &Path) -> Option<String> {
+    let content = fs::read_to_string(
+        unity_project_path
+            .join("ProjectSettings")
+            .join("ProjectVersion.txt"),
+    )
+    .ok()?;
+
+    content
+        .lines()
+        .find_map(|l| l.strip_prefix("m_EditorVersion:"))
+        .map(|v| v.trim().to_string())
+}
+
+/// Default Unity Hub install folders, each holding a folder per version
+fn hub_roots() -> Vec<PathBuf> {
+    if cfg!(target_os = "windows") {
+        vec![
+            PathBuf::from("C:/Program Files/Unity/Hub/Editor"),
+            PathBuf::from("C:/Program Files/Unity"),
+        ]
+    } else if cfg!(target_os = "macos") {
+        vec![PathBuf::from("/Applications/Unity/Hub/Editor")]
+    } else {
+        dirs::home_dir()
+            .map(|home| home.join("Unity/Hub/Editor"))
+            .into_iter()
+            .collect()
+    }
+}
+
+/// The editor executable in a Hub version folder
+fn hub_executable(version_dir: &Path) -> PathBuf {
+    if cfg!(target_os = "windows") {
+        version_dir.join("Editor").join("Unity.exe")
+    } else if cfg!(target_os = "macos") {
+        version_dir.join("Unity.app/Contents/MacOS/Unity")
+    } else {
+        version_dir.join("Editor").join("Unity")
+    }
+}
+
+/// Default Unity Hub install locations for an editor version
+fn hub_editor_candidates(version: &str) -> Vec<PathBuf> {
+    hub_roots()
+        .iter()
+        .map(|root| hub_executable(&root.join(version)))
+        .collect()
+}
+
+/// Every editor this machine has: the configured one and those Unity Hub
+/// installed, whatever the version
+pub fn installed_editors(config: &crate::LauncherConfig) -> Vec<PathBuf> {
+    let configured = config
+        .unity_editor_path
+        .as_ref()
+        .map(PathBuf::from)
+        .filter(|path| path.exists());
+    let hub = hub_roots()
+        .into_iter()
+        .flat_map(|root| fs::read_dir(root).into_iter().flatten().flatten())
+        .map(|version| hub_executable(&version.path()))
+        .filter(|path| path.exists());
+    configured.into_iter().chain(hub).collect()
+}
+
+/// Find the Unity editor executable for a project, preferring the configured override
+pub fn find_editor(
+    config: &crate::LauncherConfig,
+    unity_project_path: &Path,
+) -> Result<PathBuf, LauncherError> {
+    if let Some(path) = &config.unity_editor_path {
+        let path = PathBuf::from(path);
+        if path.exists() {
+            return Ok(path);
+        }
+        return Err(LauncherError::not_found(format!(
+            "Configured Unity editor not found: {}",
+            path.display()
+        ))
+        .with_path(&path)
+        .with_hint("Fix the Unity editor path in settings, or clear it to use Unity Hub"));
+    }
+
+    let version = project_editor_version(unity_project_path).ok_or_else(|| {
+        LauncherError::new(
+            ErrorKind::Unity,
+            "Could not read Unity version from ProjectSettings/ProjectVersion.txt",
+        )
+        .with_path(
+            unity_project_path
+                .join("ProjectSettings")
+                .join("ProjectVersion.txt"),
+        )
+    })?;
+
+    hub_editor_candidates(&version)
+        .into_iter()
+        .find(|p| p.exists())
+        .ok_or_else(|| {
+            LauncherError::not_found(format!("Unity {} is not installed via Unity Hub", version))
+                .with_hint("Install that editor version in Unity Hub, or set the Unity editor path in settings")
+        })
+}
+
+/// Recursively list files under `dir` with a given extension
+pub fn find_files(dir: &Path, extension: &str) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+
+    while let Some(current) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.extension().and_then(|e| e.to_str()) == Some(extension) {
+                found.push(path);
+            }
+        }
+    }
+
+    found
+}
+
+/// Read the `guid:` line from a .meta file
+pub fn meta_guid(meta: &str) -> Option<String> {
+    meta.lines()
+        .find_map(|l| l.strip_prefix("guid:"))
+        .map(|g| g.trim().to_string())
+}
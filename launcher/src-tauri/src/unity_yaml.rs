@@ -0,0 +1,196 @@
+//! Minimal reader for Unity's YAML serialization (.unity / .prefab / .asset)
+//!
+//! Unity files are a stream of documents headed by `--- !u!<classId> &<fileId>`,
+//! each holding a single top-level mapping named after the object type. Values
+//! are only tokenized as far as the launcher needs: top-level fields and
+//! `{fileID: .., guid: ..}` references.
+//!
+//! Scenes can run to hundreds of megabytes, so files are read as a stream:
+//! `DocumentReader` yields one document at a time and only that document is
+//! held in memory.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use crate::error::LauncherError;
+
+pub const CLASS_GAME_OBJECT: u32 = 1;
+pub const CLASS_TRANSFORM: u32 = 4;
+pub const CLASS_CAMERA: u32 = 20;
+pub const CLASS_MESH_RENDERER: u32 = 23;
+pub const CLASS_MESH_COLLIDER: u32 = 64;
+pub const CLASS_BOX_COLLIDER: u32 = 65;
+pub const CLASS_AUDIO_LISTENER: u32 = 81;
+pub const CLASS_MONO_BEHAVIOUR: u32 = 114;
+pub const CLASS_SPHERE_COLLIDER: u32 = 135;
+pub const CLASS_CAPSULE_COLLIDER: u32 = 136;
+pub const CLASS_TERRAIN_COLLIDER: u32 = 154;
+pub const CLASS_RECT_TRANSFORM: u32 = 224;
+pub const CLASS_PREFAB_INSTANCE: u32 = 1001;
+
+pub const COLLIDER_CLASSES: &[u32] = &[
+    CLASS_MESH_COLLIDER,
+    CLASS_BOX_COLLIDER,
+    CLASS_SPHERE_COLLIDER,
+    CLASS_CAPSULE_COLLIDER,
+    CLASS_TERRAIN_COLLIDER,
+];
+
+/// One `--- !u!` document
+#[derive(Debug, Clone)]
+pub struct UnityDocument {
+    pub class_id: u32,
+    pub file_id: i64,
+    pub type_name: String,
+    pub body: String,
+}
+
+/// A `{fileID: .., guid: .., type: ..}` reference
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectRef {
+    pub file_id: i64,
+    pub guid: Option<String>,
+}
+
+/// Parse a `--- !u!114 &12345` header line
+pub fn parse_header(line: &str) -> Option<(u32, i64)> {
+    let rest = line.strip_prefix("--- !u!")?;
+    let mut parts = rest.split_whitespace();
+    let class_id = parts.next()?.parse().ok()?;
+    let file_id = parts.next()?.strip_prefix('&')?.parse().ok()?;
+    Some((class_id, file_id))
+}
+
+/// Parse an inline `{fileID: 123, guid: abc, type: 3}` value
+pub fn parse_ref(value: &str) -> Option<ObjectRef> {
+    let inner = value.trim().strip_prefix('{')?.strip_suffix('}')?;
+    let mut file_id = None;
+    let mut guid = None;
+
+    for pair in inner.split(',') {
+        let (key, val) = pair.split_once(':')?;
+        match key.trim() {
+            "fileID" => file_id = val.trim().parse().ok(),
+            "guid" => guid = Some(val.trim().to_string()),
+            _ => {}
+        }
+    }
+
+    Some(ObjectRef {
+        file_id: file_id?,
+        guid,
+    })
+}
+
+impl UnityDocument {
+    /// Value of a top-level field (`  m_Name: Floor` -> `Floor`)
+    pub fn field(&self, key: &str) -> Option<&str> {
+        self.body.lines().find_map(|line| {
+            let rest = line.strip_prefix("  ")?;
+            if rest.starts_with(' ') {
+                return None;
+            }
+            let value = rest.strip_prefix(key)?.strip_prefix(':')?;
+            Some(value.trim())
+        })
+    }
+
+    pub fn field_ref(&self, key: &str) -> Option<ObjectRef> {
+        parse_ref(self.field(key)?)
+    }
+
+    /// Name of a GameObject
+    pub fn name(&self) -> Option<&str> {
+        self.field("m_Name")
+    }
+
+    /// GameObject that owns this component
+    pub fn game_object_id(&self) -> Option<i64> {
+        self.field_ref("m_GameObject").map(|r| r.file_id)
+    }
+}
+
+/// Reads a Unity YAML stream document by document
+pub struct DocumentReader<R> {
+    reader: R,
+    buf: Vec<u8>,
+    /// Header of the next document, read while finishing the previous one
+    next_header: Option<(u32, i64)>,
+}
+
+impl<R: BufRead> DocumentReader<R> {
+    pub fn new(reader: R) -> Self {
+        DocumentReader {
+            reader,
+            buf: Vec::new(),
+            next_header: None,
+        }
+    }
+
+    /// Next line without its line ending, `None` at end of input.
+    /// Invalid UTF-8 is replaced rather than ending the read.
+    fn next_line(&mut self) -> io::Result<Option<String>> {
+        self.buf.clear();
+        if self.reader.read_until(b'\n', &mut self.buf)? == 0 {
+            return Ok(None);
+        }
+        let line = String::from_utf8_lossy(&self.buf);
+        Ok(Some(line.trim_end_matches(['\n', '\r']).to_string()))
+    }
+}
+
+impl<R: BufRead> Iterator for DocumentReader<R> {
+    type Item = io::Result<UnityDocument>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (class_id, file_id) = match self.next_header.take() {
+            Some(header) => header,
+            None => loop {
+                match self.next_line() {
+                    Ok(Some(line)) => {
+                        if let Some(header) = parse_header(&line) {
+                            break header;
+                        }
+                    }
+                    Ok(None) => return None,
+                    Err(e) => return Some(Err(e)),
+                }
+            },
+        };
+
+        let mut doc = UnityDocument {
+            class_id,
+            file_id,
+            type_name: String::new(),
+            body: String::new(),
+        };
+        loop {
+            let line = match self.next_line() {
+                Ok(Some(line)) => line,
+                Ok(None) => break,
+                Err(e) => return Some(Err(e)),
+            };
+            if let Some(header) = parse_header(&line) {
+                self.next_header = Some(header);
+                break;
+            }
+            if doc.type_name.is_empty() && !line.starts_with(' ') {
+                doc.type_name = line.trim_end_matches(':').trim().to_string();
+            } else {
+                doc.body.push_str(&line);
+                doc.body.push('\n');
+            }
+        }
+        Some(Ok(doc))
+    }
+}
+
+/// Stream the documents of a Unity YAML file
+pub fn read_documents(path: &Path) -> Result<DocumentReader<BufReader<File>>, LauncherError> {
+    let file = File::open(path).map_err(|e| LauncherError::io("Failed to open", path, e))?;
+    Ok(DocumentReader::new(BufReader::with_capacity(
+        256 * 1024,
+        file,
+    )))
+}
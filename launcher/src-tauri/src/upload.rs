@@ -0,0 +1,299 @@
+//! Upload built bundles to Banter hosting
+//!
+//! An upload saves the files it has sent as its job checkpoint, so resuming
+//! it from the job history after a restart sends only the rest.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+use std::time::UNIX_EPOCH;
+use tauri::{AppHandle, Emitter};
+
+use crate::error::{ErrorKind, LauncherError};
+use crate::features::{self, Feature};
+use crate::jobs::{JobContext, JobRequest};
+use crate::state::{AppState, SharedState};
+use crate::{account, bundles, net};
+use launcher_core::config::NetworkSettings;
+
+/// Used when `banter_upload_endpoint` isn't set in the launcher config
+const DEFAULT_UPLOAD_ENDPOINT: &str = "https://api.sidequestvr.com/banter/uploads";
+
+const MAX_ATTEMPTS: u32 = 3;
+
+/// Emit a progress event at most once per this many bytes
+const PROGRESS_INTERVAL: u64 = 256 * 1024;
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct UploadResult {
+    pub channel_id: String,
+    pub platform: String,
+    pub files_uploaded: usize,
+    /// Sent by the interrupted run this one resumed, and unchanged since
+    pub files_skipped: usize,
+    pub bytes_uploaded: u64,
+    pub space_url: Option<String>,
+}
+
+/// Job checkpoint of an upload
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct UploadCheckpoint {
+    files: Vec<UploadedFile>,
+    space_url: Option<String>,
+}
+
+/// A file the upload has sent, as it was then
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct UploadedFile {
+    file: String,
+    size: u64,
+    /// Unix ms
+    modified_ms: i64,
+}
+
+/// Payload of the `bundle-upload-progress` event
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct UploadProgress {
+    channel_id: String,
+    file: String,
+    bytes_sent: u64,
+    total_bytes: u64,
+    attempt: u32,
+}
+
+/// Reader that reports how many bytes have been consumed
+struct ProgressReader<R, F> {
+    inner: R,
+    sent: u64,
+    last_reported: u64,
+    on_progress: F,
+}
+
+impl<R: Read, F: FnMut(u64)> Read for ProgressReader<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.sent += n as u64;
+        if n == 0 || self.sent - self.last_reported >= PROGRESS_INTERVAL {
+            self.last_reported = self.sent;
+            (self.on_progress)(self.sent);
+        }
+        Ok(n)
+    }
+}
+
+fn upload_endpoint(config: &crate::LauncherConfig) -> String {
+    config
+        .banter_upload_endpoint
+        .clone()
+        .unwrap_or_else(|| DEFAULT_UPLOAD_ENDPOINT.to_string())
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// Upload one file, retrying transport errors and 5xx responses with backoff
+fn upload_file(
+    agent: &ureq::Agent,
+    network: &NetworkSettings,
+    url: &str,
+    token: &str,
+    path: &Path,
+    on_progress: &dyn Fn(u64, u32),
+) -> Result<serde_json::Value, LauncherError> {
+    let size = path
+        .metadata()
+        .map_err(|e| LauncherError::io("Failed to read", path, e))?
+        .len();
+
+    let mut attempt = 1;
+    loop {
+        let file = File::open(path).map_err(|e| LauncherError::io("Failed to open", path, e))?;
+        let reader = ProgressReader {
+            inner: net::throttle(network, file),
+            sent: 0,
+            last_reported: 0,
+            on_progress: |sent| on_progress(sent, attempt),
+        };
+
+        let response = agent
+            .put(url)
+            .set("Authorization", &format!("Bearer {}", token))
+            .set("Content-Type", "application/octet-stream")
+            .set("Content-Length", &size.to_string())
+            .send(reader);
+
+        let retryable = match response {
+            Ok(response) => {
+                // Hosting replies with JSON metadata; an empty body is fine too
+                let body = response.into_string().unwrap_or_default();
+                return Ok(serde_json::from_str(&body).unwrap_or(serde_json::Value::Null));
+            }
+            Err(ureq::Error::Status(code, response)) if code < 500 => {
+                let body = response.into_string().unwrap_or_default();
+                let kind = match code {
+                    401 | 403 => ErrorKind::Auth,
+                    _ => ErrorKind::Network,
+                };
+                let error =
+                    LauncherError::new(kind, format!("Upload rejected ({}): {}", code, body))
+                        .with_path(path);
+                return Err(match kind {
+                    ErrorKind::Auth => error.with_hint("Log in to Banter again"),
+                    _ => error,
+                });
+            }
+            Err(e) => e.to_string(),
+        };
+
+        if attempt >= MAX_ATTEMPTS {
+            return Err(LauncherError::network(format!(
+                "Upload failed after {} attempts: {}",
+                attempt, retryable
+            ))
+            .with_path(path)
+            .with_hint("Check your internet connection and retry"));
+        }
+        thread::sleep(Duration::from_secs(1 << (attempt - 1)));
+        attempt += 1;
+    }
+}
+
+/// Upload all artifacts of a built platform and record the resulting URL on the channel
+pub fn upload_bundle(
+    app: &AppHandle,
+    job: &JobContext,
+    state: &AppState,
+    config: &crate::LauncherConfig,
+    channel: &crate::ProjectChannel,
+    platform: &str,
+) -> Result<UploadResult, LauncherError> {
+    features::require(config, Feature::BanterUploads)?;
+    let token = account::access_token(config).ok_or_else(|| {
+        LauncherError::new(ErrorKind::Auth, "Not logged in to Banter")
+            .with_hint("Log in from the launcher first")
+    })?;
+
+    let output_dir = bundles::bundle_output_dir(Path::new(&channel.unity_project_path), platform);
+    let artifacts = bundles::collect_artifacts(&output_dir);
+    if artifacts.is_empty() {
+        return Err(LauncherError::not_found(format!(
+            "No built bundles in {}",
+            output_dir.display()
+        ))
+        .with_path(&output_dir)
+        .with_hint(format!("Build the {} bundle first", platform)));
+    }
+
+    let endpoint = upload_endpoint(config);
+    let agent = net::agent(&config.network, &endpoint);
+
+    let total_bytes = artifacts.iter().map(|a| a.size).sum::<u64>().max(1);
+    let mut bytes_done = 0;
+    let mut bytes_uploaded = 0;
+    let mut checkpoint: UploadCheckpoint = job
+        .checkpoint()
+        .and_then(|c| serde_json::from_value(c).ok())
+        .unwrap_or_default();
+    let mut files_skipped = 0;
+
+    for artifact in &artifacts {
+        job.check_cancelled()?;
+        let path = Path::new(&artifact.path);
+        let relative = path
+            .strip_prefix(&output_dir)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let uploaded = UploadedFile {
+            file: relative.clone(),
+            size: artifact.size,
+            modified_ms: path
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|m| m.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |d| d.as_millis() as i64),
+        };
+        if checkpoint.files.contains(&uploaded) {
+            files_skipped += 1;
+            bytes_done += artifact.size;
+            continue;
+        }
+        let url = format!("{}/{}/{}/{}", endpoint, channel.id, platform, relative);
+
+        let on_progress = |sent: u64, attempt: u32| {
+            job.progress(
+                Some((bytes_done + sent) as f64 / total_bytes as f64),
+                format!("Uploading {}", relative),
+            );
+            let _ = app.emit(
+                "bundle-upload-progress",
+                UploadProgress {
+                    channel_id: channel.id.clone(),
+                    file: relative.clone(),
+                    bytes_sent: sent,
+                    total_bytes: artifact.size,
+                    attempt,
+                },
+            );
+        };
+
+        let response = upload_file(&agent, &config.network, &url, &token, path, &on_progress)?;
+        if let Some(url) = response.get("url").and_then(|u| u.as_str()) {
+            checkpoint.space_url = Some(url.to_string());
+        }
+        bytes_done += artifact.size;
+        bytes_uploaded += artifact.size;
+        checkpoint.files.retain(|f| f.file != uploaded.file);
+        checkpoint.files.push(uploaded);
+        job.save_checkpoint(&checkpoint);
+    }
+
+    let space_url = checkpoint.space_url;
+    if let Some(url) = &space_url {
+        let url = url.clone();
+        state.update_channel(&channel.id, move |c| c.space_url = Some(url))?;
+    }
+
+    Ok(UploadResult {
+        channel_id: channel.id.clone(),
+        platform: platform.to_string(),
+        files_uploaded: artifacts.len() - files_skipped,
+        files_skipped,
+        bytes_uploaded,
+        space_url,
+    })
+}
+
+/// Upload the channel's built bundles for a platform to Banter hosting
+#[tauri::command]
+#[specta::specta]
+pub async fn upload_banter_bundle(
+    app: AppHandle,
+    state: tauri::State<'_, SharedState>,
+    channel_id: String,
+    platform: String,
+) -> Result<UploadResult, LauncherError> {
+    crate::sanitize::platform(&platform)?;
+    let state = state.inner().clone();
+    crate::commands::blocking("Upload", move || {
+        let config = state.config();
+        let channel = crate::find_channel(&config, &channel_id)?;
+        let label = format!("Upload {} bundle for {}", platform, channel.name);
+        let job_app = app.clone();
+        let job_state = state.clone();
+        let request = JobRequest::UploadBundle {
+            channel_id: channel.id.clone(),
+            platform: platform.clone(),
+        };
+        state
+            .jobs
+            .run(&app, "upload_bundle", label, Some(request), move |job| {
+                upload_bundle(&job_app, job, &job_state, &config, &channel, &platform)
+            })
+    })
+    .await
+}
@@ -0,0 +1,431 @@
+//! Banter content validation rules for a channel's scene and builds
+
+use serde::Serialize;
+use specta::Type;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::LauncherError;
+use crate::guids::{GuidAsset, GuidCache};
+use crate::i18n::{self, Params};
+use crate::state::SharedState;
+use crate::unity::{find_files, meta_guid};
+use crate::{bundles, unity_yaml};
+
+/// Largest bundle Banter will load per platform
+pub const MAX_BUNDLE_BYTES: &[(&str, u64)] = &[
+    ("windows", 100 * 1024 * 1024),
+    ("android", 50 * 1024 * 1024),
+];
+
+/// Quest GPUs struggle above this import size
+pub const MAX_TEXTURE_SIZE: u64 = 2048;
+
+/// Built-in components Banter supplies itself; a scene copy conflicts with the player rig
+pub const UNSUPPORTED_CLASSES: &[(u32, &str)] = &[
+    (unity_yaml::CLASS_CAMERA, "Camera"),
+    (unity_yaml::CLASS_AUDIO_LISTENER, "AudioListener"),
+];
+
+/// Object names that usually mark walkable / teleportable geometry
+const TELEPORT_NAME_HINTS: &[&str] = &["floor", "ground", "teleport", "walkable", "terrain"];
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ValidationIssue {
+    /// Rule id, e.g. "bundle-size" or "missing-collider"
+    pub rule: String,
+    /// "error" or "warning"
+    pub severity: String,
+    pub message: String,
+    /// Catalogue id of `message`, rendered from `params`
+    pub message_id: String,
+    pub params: Params,
+    /// Scene object the issue refers to
+    pub object: Option<String>,
+    /// File the issue refers to
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ValidationReport {
+    pub channel_id: String,
+    pub scene_path: Option<String>,
+    pub errors: usize,
+    pub warnings: usize,
+    pub issues: Vec<ValidationIssue>,
+}
+
+fn issue(
+    rule: &str,
+    severity: &str,
+    message_id: &str,
+    params: Params,
+    object: Option<&str>,
+    path: Option<&Path>,
+) -> ValidationIssue {
+    ValidationIssue {
+        rule: rule.to_string(),
+        severity: severity.to_string(),
+        message: i18n::text(message_id, &params),
+        message_id: message_id.to_string(),
+        params,
+        object: object.map(|o| o.to_string()),
+        path: path.map(|p| p.to_string_lossy().to_string()),
+    }
+}
+
+fn check_bundle_sizes(project: &Path, issues: &mut Vec<ValidationIssue>) {
+    for (platform, limit) in MAX_BUNDLE_BYTES {
+        let dir = bundles::bundle_output_dir(project, platform);
+        for artifact in bundles::collect_artifacts(&dir) {
+            if artifact.size > *limit {
+                issues.push(issue(
+                    "bundle-size",
+                    "error",
+                    "validation.bundle_size",
+                    i18n::params(&[
+                        ("platform", platform.to_string()),
+                        (
+                            "size_mb",
+                            format!("{:.1}", artifact.size as f64 / (1024.0 * 1024.0)),
+                        ),
+                        ("limit_mb", (limit / (1024 * 1024)).to_string()),
+                    ]),
+                    None,
+                    Some(Path::new(&artifact.path)),
+                ));
+            }
+        }
+    }
+}
+
+fn check_texture_sizes(project: &Path, issues: &mut Vec<ValidationIssue>) {
+    for meta_path in find_files(&project.join("Assets"), "meta") {
+        let Ok(meta) = fs::read_to_string(&meta_path) else {
+            continue;
+        };
+        if !meta.contains("TextureImporter:") {
+            continue;
+        }
+
+        let max_size = meta
+            .lines()
+            .filter_map(|l| l.trim().strip_prefix("maxTextureSize:"))
+            .filter_map(|v| v.trim().parse::<u64>().ok())
+            .max();
+
+        if let Some(size) = max_size.filter(|s| *s > MAX_TEXTURE_SIZE) {
+            let texture = meta_path.with_extension("");
+            issues.push(issue(
+                "texture-size",
+                "warning",
+                "validation.texture_size",
+                i18n::params(&[
+                    ("size", size.to_string()),
+                    ("max", MAX_TEXTURE_SIZE.to_string()),
+                ]),
+                None,
+                Some(&texture),
+            ));
+        }
+    }
+}
+
+/// GUIDs of C# scripts inside Assets (project scripts, not SDK packages)
+fn project_script_guids(project: &Path) -> HashMap<String, PathBuf> {
+    find_files(&project.join("Assets"), "meta")
+        .into_iter()
+        .filter(|p| p.to_string_lossy().ends_with(".cs.meta"))
+        .filter_map(|p| {
+            let guid = meta_guid(&fs::read_to_string(&p).ok()?)?;
+            Some((guid, p.with_extension("")))
+        })
+        .collect()
+}
+
+/// What the scene rules need, collected in one pass over the file so large
+/// scenes never have to be held in memory
+#[derive(Default)]
+struct SceneFacts {
+    /// GameObject fileID -> name
+    names: HashMap<i64, String>,
+    /// Unsupported component and the GameObject it sits on
+    unsupported: Vec<(&'static str, Option<i64>)>,
+    /// Project script and the GameObject it sits on
+    custom_scripts: Vec<(PathBuf, Option<i64>)>,
+    collider_owners: HashSet<i64>,
+    /// GameObjects with a MeshRenderer
+    renderers: Vec<i64>,
+    /// `m_Script` GUID of each MonoBehaviour (`None` when no script is
+    /// assigned) and the GameObject it sits on
+    scripts: Vec<(Option<String>, Option<i64>)>,
+    /// Source prefab GUID and fileID of each prefab instance
+    prefab_instances: Vec<(String, i64)>,
+    /// Prefab instance fileID -> the name it overrides onto its root
+    instance_names: HashMap<i64, String>,
+}
+
+impl SceneFacts {
+    fn object_name(&self, game_object: Option<i64>) -> String {
+        game_object
+            .and_then(|id| self.names.get(&id))
+            .map(|n| n.as_str())
+            .unwrap_or("<unnamed>")
+            .to_string()
+    }
+}
+
+fn collect_scene_facts(
+    scene_path: &Path,
+    scripts: &HashMap<String, PathBuf>,
+) -> Result<SceneFacts, LauncherError> {
+    let mut facts = SceneFacts::default();
+
+    for doc in unity_yaml::read_documents(scene_path)? {
+        let doc = doc.map_err(|e| LauncherError::io("Failed to read scene", scene_path, e))?;
+        match doc.class_id {
+            unity_yaml::CLASS_GAME_OBJECT => {
+                if let Some(name) = doc.name() {
+                    facts.names.insert(doc.file_id, name.to_string());
+                }
+            }
+            unity_yaml::CLASS_MONO_BEHAVIOUR => {
+                // Stripped components of prefab instances carry no m_Script
+                let Some(script_ref) = doc.field_ref("m_Script") else {
+                    continue;
+                };
+                let guid = script_ref.guid.filter(|_| script_ref.file_id != 0);
+                if let Some(script) = guid.as_ref().and_then(|g| scripts.get(g)) {
+                    facts
+                        .custom_scripts
+                        .push((script.clone(), doc.game_object_id()));
+                }
+                facts.scripts.push((guid, doc.game_object_id()));
+            }
+            unity_yaml::CLASS_PREFAB_INSTANCE => {
+                // m_ParentPrefab before Unity 2018.3
+                let source = doc
+                    .field_ref("m_SourcePrefab")
+                    .or_else(|| doc.field_ref("m_ParentPrefab"));
+                if let Some(guid) = source.and_then(|r| r.guid) {
+                    facts.prefab_instances.push((guid, doc.file_id));
+                }
+                if let Some(name) = overridden_name(&doc.body) {
+                    facts.instance_names.insert(doc.file_id, name);
+                }
+            }
+            unity_yaml::CLASS_MESH_RENDERER => facts.renderers.extend(doc.game_object_id()),
+            class if unity_yaml::COLLIDER_CLASSES.contains(&class) => {
+                facts.collider_owners.extend(doc.game_object_id())
+            }
+            class => {
+                if let Some((_, name)) = UNSUPPORTED_CLASSES.iter().find(|(c, _)| *c == class) {
+                    facts.unsupported.push((name, doc.game_object_id()));
+                }
+            }
+        }
+    }
+
+    Ok(facts)
+}
+
+/// The `m_Name` a prefab instance's modifications give its root
+fn overridden_name(body: &str) -> Option<String> {
+    let mut lines = body.lines().map(str::trim);
+    while let Some(line) = lines.next() {
+        if line == "propertyPath: m_Name" {
+            return lines
+                .next()
+                .and_then(|l| l.strip_prefix("value:"))
+                .map(|v| v.trim().to_string());
+        }
+    }
+    None
+}
+
+/// Missing scripts and prefab instances whose prefab is gone, looked up in
+/// the project's GUID index
+fn reference_issues(
+    facts: &SceneFacts,
+    project: &Path,
+    guids: &GuidCache,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    let wanted: Vec<&str> = facts
+        .scripts
+        .iter()
+        .filter_map(|(guid, _)| guid.as_deref())
+        .chain(facts.prefab_instances.iter().map(|(guid, _)| guid.as_str()))
+        .collect();
+    let resolved: HashMap<String, GuidAsset> = if wanted.is_empty() {
+        HashMap::new()
+    } else {
+        guids.resolve(project, &wanted)
+    };
+
+    for (guid, game_object) in &facts.scripts {
+        let object = facts.object_name(*game_object);
+        match guid {
+            None => issues.push(issue(
+                "missing-script",
+                "error",
+                "validation.missing_script_unassigned",
+                i18n::params(&[("object", object.clone())]),
+                Some(&object),
+                None,
+            )),
+            Some(guid) if !resolved.contains_key(guid) => issues.push(issue(
+                "missing-script",
+                "error",
+                "validation.missing_script",
+                i18n::params(&[("object", object.clone()), ("guid", guid.clone())]),
+                Some(&object),
+                None,
+            )),
+            Some(_) => {}
+        }
+    }
+
+    for (guid, file_id) in &facts.prefab_instances {
+        if resolved.contains_key(guid) {
+            continue;
+        }
+        let object = facts
+            .instance_names
+            .get(file_id)
+            .cloned()
+            .unwrap_or_else(|| "<unnamed>".to_string());
+        issues.push(issue(
+            "broken-prefab",
+            "error",
+            "validation.broken_prefab",
+            i18n::params(&[("object", object.clone()), ("guid", guid.clone())]),
+            Some(&object),
+            None,
+        ));
+    }
+}
+
+/// Missing scripts and broken prefab references in a scene
+pub fn check_references(
+    scene_path: &Path,
+    project: &Path,
+    guids: &GuidCache,
+) -> Result<Vec<ValidationIssue>, LauncherError> {
+    let facts = collect_scene_facts(scene_path, &HashMap::new())?;
+    let mut issues = Vec::new();
+    reference_issues(&facts, project, guids, &mut issues);
+    Ok(issues)
+}
+
+fn check_scene(
+    scene_path: &Path,
+    project: &Path,
+    allow_custom_scripts: bool,
+    guids: &GuidCache,
+    issues: &mut Vec<ValidationIssue>,
+) -> Result<(), LauncherError> {
+    let scripts = if allow_custom_scripts {
+        HashMap::new()
+    } else {
+        project_script_guids(project)
+    };
+    let facts = collect_scene_facts(scene_path, &scripts)?;
+
+    for (name, game_object) in &facts.unsupported {
+        let object = facts.object_name(*game_object);
+        issues.push(issue(
+            "unsupported-component",
+            "warning",
+            "validation.unsupported_component",
+            i18n::params(&[("component", name.to_string()), ("object", object.clone())]),
+            Some(&object),
+            None,
+        ));
+    }
+
+    for (script, game_object) in &facts.custom_scripts {
+        let object = facts.object_name(*game_object);
+        issues.push(issue(
+            "custom-script",
+            "error",
+            "validation.custom_script",
+            i18n::params(&[("object", object.clone())]),
+            Some(&object),
+            Some(script),
+        ));
+    }
+
+    reference_issues(&facts, project, guids, issues);
+
+    // Walkable-looking renderers need a collider or players can't teleport onto them
+    for go_id in &facts.renderers {
+        let Some(name) = facts.names.get(go_id) else {
+            continue;
+        };
+        let lower = name.to_lowercase();
+        if TELEPORT_NAME_HINTS.iter().any(|h| lower.contains(h))
+            && !facts.collider_owners.contains(go_id)
+        {
+            issues.push(issue(
+                "missing-collider",
+                "warning",
+                "validation.missing_collider",
+                i18n::params(&[("object", name.clone())]),
+                Some(name),
+                None,
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Run all content rules for a channel
+pub fn validate_channel(
+    channel: &crate::ProjectChannel,
+    config: &crate::LauncherConfig,
+    guids: &GuidCache,
+) -> Result<ValidationReport, LauncherError> {
+    let project = Path::new(&channel.unity_project_path);
+    let mut issues = Vec::new();
+
+    if let Some(scene) = &channel.scene_path {
+        check_scene(
+            Path::new(scene),
+            project,
+            config.enable_custom_scripts,
+            guids,
+            &mut issues,
+        )?;
+    }
+    check_texture_sizes(project, &mut issues);
+    check_bundle_sizes(project, &mut issues);
+
+    let errors = issues.iter().filter(|i| i.severity == "error").count();
+
+    Ok(ValidationReport {
+        channel_id: channel.id.clone(),
+        scene_path: channel.scene_path.clone(),
+        errors,
+        warnings: issues.len() - errors,
+        issues,
+    })
+}
+
+/// Check a channel's scene and builds against Banter constraints
+#[tauri::command]
+#[specta::specta]
+pub async fn validate_banter_content(
+    state: tauri::State<'_, SharedState>,
+    channel_id: String,
+) -> Result<ValidationReport, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Validation", move || {
+        let config = state.config();
+        let channel = crate::find_channel(&config, &channel_id)?;
+        validate_channel(&channel, &config, &state.guids)
+    })
+    .await
+}
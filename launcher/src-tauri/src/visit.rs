@@ -0,0 +1,130 @@
+//! Launch the Banter client to visit a channel's space
+
+use serde::Serialize;
+use specta::Type;
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::error::LauncherError;
+use crate::state::SharedState;
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct VisitResult {
+    pub url: String,
+    /// Client executable used, or `None` when the URL was handed to the OS
+    pub client_path: Option<String>,
+}
+
+/// Known install locations of the Banter desktop client
+fn client_candidates() -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+
+    if cfg!(target_os = "windows") {
+        for steam in [
+            "C:/Program Files (x86)/Steam",
+            "C:/Program Files/Steam",
+            "D:/SteamLibrary",
+            "E:/SteamLibrary",
+        ] {
+            candidates.push(
+                PathBuf::from(steam)
+                    .join("steamapps/common/Banter")
+                    .join("Banter.exe"),
+            );
+        }
+        if let Some(local) = dirs::data_local_dir() {
+            candidates.push(local.join("Programs/Banter/Banter.exe"));
+        }
+    } else if cfg!(target_os = "macos") {
+        candidates.push(PathBuf::from(
+            "/Applications/Banter.app/Contents/MacOS/Banter",
+        ));
+    } else if let Some(home) = dirs::home_dir() {
+        candidates.push(home.join(".steam/steam/steamapps/common/Banter/Banter.x86_64"));
+    }
+
+    candidates
+}
+
+/// Find the Banter client, preferring the configured override
+pub fn find_client(config: &crate::LauncherConfig) -> Option<PathBuf> {
+    if let Some(path) = &config.banter_client_path {
+        let path = PathBuf::from(path);
+        return path.exists().then_some(path);
+    }
+
+    client_candidates().into_iter().find(|p| p.exists())
+}
+
+/// Hand a URL to the OS default handler
+fn open_with_os(url: &str) -> Result<(), LauncherError> {
+    let result = if cfg!(target_os = "windows") {
+        Command::new("cmd").args(["/C", "start", "", url]).spawn()
+    } else if cfg!(target_os = "macos") {
+        Command::new("open").arg(url).spawn()
+    } else {
+        Command::new("xdg-open").arg(url).spawn()
+    };
+
+    result
+        .map(|_| ())
+        .map_err(|e| LauncherError::internal(format!("Failed to open {}: {}", url, e)))
+}
+
+/// Launch the Banter client pointed at the channel's space.
+/// With `local` set, the channel's local test URL is preferred over the hosted one.
+#[tauri::command]
+#[specta::specta]
+pub async fn visit_space(
+    state: tauri::State<'_, SharedState>,
+    channel_id: String,
+    local: Option<bool>,
+) -> Result<VisitResult, LauncherError> {
+    let state = state.inner().clone();
+    crate::commands::blocking("Visit", move || {
+        let config = state.config();
+        let channel = crate::find_channel(&config, &channel_id)?;
+
+        let (first, second) = if local.unwrap_or(false) {
+            (&channel.local_test_url, &channel.space_url)
+        } else {
+            (&channel.space_url, &channel.local_test_url)
+        };
+        let url = first.as_ref().or(second.as_ref()).cloned().ok_or_else(|| {
+            LauncherError::not_found("Channel has no space URL")
+                .with_hint("Upload a bundle or start the preview server to get one")
+        })?;
+
+        match find_client(&config) {
+            Some(client) => {
+                Command::new(&client)
+                    .arg(&url)
+                    .spawn()
+                    .map_err(|e| LauncherError::io("Failed to launch Banter", &client, e))?;
+
+                Ok(VisitResult {
+                    url,
+                    client_path: Some(client.to_string_lossy().to_string()),
+                })
+            }
+            None => {
+                // No client found - let the banter:// handler (or browser) take it
+                let link = if url.starts_with("banter://") {
+                    url.clone()
+                } else {
+                    let bare = url
+                        .trim_start_matches("https://")
+                        .trim_start_matches("http://");
+                    format!("banter://{}", bare)
+                };
+                open_with_os(&link)?;
+
+                Ok(VisitResult {
+                    url,
+                    client_path: None,
+                })
+            }
+        }
+    })
+    .await
+}
@@ -0,0 +1,362 @@
+//! Unattended watch mode
+//!
+//! `--watch` is for a dedicated machine hosting the MCP side of a Banter build
+//! pipeline: it keeps the active channel's MCP server running in HTTP mode,
+//! restarts it when it exits or the active channel changes, and notes bridge
+//! connects and disconnects; a crash runs the `on-server-crash` hook. It
+//! also runs the scheduled tasks (`scheduler`), including the server restart
+//! only watch mode has a server for.
+//! Supervisor events go to stderr and `watch.log`;
+//! the server's own output goes to its channel server log as usual.
+
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::bridge;
+use crate::capabilities;
+use crate::error::LauncherError;
+use crate::faults::{self, Fault};
+use crate::hooks::{self, HookEvent};
+use crate::logs;
+use crate::metrics::{self, WatchMetrics};
+use crate::plan::FilePlan;
+use crate::scheduler::{self, Scheduler, Task};
+use crate::state::AppState;
+use crate::{dashboard, instances, sessions, simulation, user, LauncherConfig, ProjectChannel};
+
+/// Base port of the MCP server; without `--port` each user gets their own
+/// offset from it (`user::port`)
+pub const DEFAULT_PORT: u16 = 42067;
+
+pub fn default_port() -> u16 {
+    user::port(DEFAULT_PORT)
+}
+
+/// How often the config, server and bridge are checked
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// A server that stays up this long resets the restart backoff
+const STABLE_AFTER: Duration = Duration::from_secs(120);
+
+/// Appends timestamped supervisor events to stderr and the watch log
+struct WatchLog {
+    file: Option<File>,
+}
+
+impl WatchLog {
+    fn open() -> Self {
+        let path = logs::watch_log_path();
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path);
+        if let Err(e) = &file {
+            eprintln!("warning: cannot write {}: {}", path.display(), e);
+        }
+        WatchLog { file: file.ok() }
+    }
+
+    fn line(&mut self, message: impl AsRef<str>) {
+        let line = format!(
+            "[{}] {}",
+            utc_timestamp(SystemTime::now()),
+            message.as_ref()
+        );
+        eprintln!("{}", line);
+        if let Some(file) = &mut self.file {
+            let _ = writeln!(file, "{}", line);
+        }
+    }
+}
+
+/// `YYYY-MM-DD HH:MM:SSZ` without pulling in a date crate
+fn utc_timestamp(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    let (days, rem) = (secs.div_euclid(86_400), secs.rem_euclid(86_400));
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+struct Server {
+    channel_id: String,
+    child: Child,
+    started: Instant,
+}
+
+impl Server {
+    fn stop(mut self, log: &mut WatchLog) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        log.line(format!("Stopped MCP server for {}", self.channel_id));
+    }
+}
+
+/// Start the MCP server for a channel in HTTP mode, with the same environment
+/// Claude Code would give it. In simulation mode this is the launcher's own
+/// fake server instead.
+fn spawn_server(
+    config: &LauncherConfig,
+    channel: &ProjectChannel,
+    port: u16,
+) -> Result<Child, LauncherError> {
+    if simulation::enabled(config) {
+        let exe = std::env::current_exe()
+            .map_err(|e| LauncherError::internal(format!("Cannot locate the launcher: {}", e)))?;
+        return Command::new(&exe)
+            .args([
+                "--simulated-server",
+                &channel.id,
+                "--port",
+                &port.to_string(),
+            ])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| LauncherError::io("Failed to start simulated MCP server", &exe, e));
+    }
+
+    let server = Path::new(&config.mcp_server_path);
+    if !server.exists() {
+        return Err(LauncherError::not_found(format!(
+            "MCP server not found: {}",
+            server.display()
+        ))
+        .with_path(server));
+    }
+
+    let cwd = crate::channels::server_cwd(channel)?;
+    let mut plan = FilePlan::new(false);
+    let env = crate::mcp_server_env_vars(&mut plan, config, channel)?;
+
+    faults::check(Fault::NodeMissing)
+        .and_then(|_| {
+            Command::new("node")
+                .arg(server)
+                .current_dir(&cwd)
+                .args(["--http", "--port", &port.to_string()])
+                .envs(env)
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+        })
+        .map_err(|e| {
+            LauncherError::io("Failed to start MCP server", server, e)
+                .with_hint("Check that Node.js is installed and on PATH")
+        })
+}
+
+fn active_channel(config: &LauncherConfig) -> Option<ProjectChannel> {
+    let id = config.active_channel_id.as_deref()?;
+    config.channels.iter().find(|c| c.id == id).cloned()
+}
+
+/// Supervise the active channel's MCP server until the process is killed
+pub fn run(port: u16) -> Result<(), LauncherError> {
+    let state = Arc::new(AppState::load()?);
+    simulation::start_bridge(&state);
+    let mut log = WatchLog::open();
+    log.line(format!(
+        "Watch mode started (port {}, log {})",
+        port,
+        logs::watch_log_path().display()
+    ));
+    if let Some(others) = instances::describe(&instances::report(&state.config(), &[port])) {
+        log.line(others);
+    }
+    instances::start(&state, vec![port]);
+
+    let mut server: Option<Server> = None;
+    let mut backoff = MIN_BACKOFF;
+    let mut next_start = Instant::now();
+    let mut bridge_connected: Option<bool> = None;
+    let mut last_error: Option<String> = None;
+    let mut stats = WatchMetrics::default();
+    let mut schedules = Scheduler::default();
+
+    loop {
+        // Pick up channel switches made from the window, CLI or control API
+        let config = match state.reload() {
+            Ok(config) => config,
+            Err(e) => {
+                if last_error.as_deref() != Some(e.message.as_str()) {
+                    log.line(format!("Config unreadable, keeping current server: {}", e));
+                    last_error = Some(e.message.clone());
+                }
+                state.config()
+            }
+        };
+        let config = match sessions::refresh(&state) {
+            Some(other) => {
+                log.line(format!(
+                    "{} took the project over (channel \"{}\"); channel deactivated",
+                    other.owner, other.channel_name
+                ));
+                state.config()
+            }
+            None => config,
+        };
+        let channel = active_channel(&config);
+
+        for task in schedules.due(&config) {
+            match task {
+                Task::ServerRestart => {
+                    if let Some(running) = server.take() {
+                        log.line("Scheduled restart");
+                        running.stop(&mut log);
+                        stats.started_at_ms = None;
+                        backoff = MIN_BACKOFF;
+                        next_start = Instant::now();
+                    }
+                }
+                Task::UpdateCheck => {
+                    for update in dashboard::build(&state, true).pending_updates {
+                        log.line(format!(
+                            "Update available for {}: {} {} -> {}",
+                            update.channel_id, update.component, update.installed, update.available
+                        ));
+                    }
+                }
+                Task::IdleShutdown => {
+                    let stopped = scheduler::idle_shutdown();
+                    if stopped > 0 {
+                        log.line(format!("Stopped {} idle pooled server(s)", stopped));
+                    }
+                }
+            }
+        }
+
+        if let Some(running) = &server {
+            if channel.as_ref().map(|c| c.id.as_str()) != Some(running.channel_id.as_str()) {
+                log.line("Active channel changed");
+                if let Some(running) = server.take() {
+                    running.stop(&mut log);
+                }
+                stats.started_at_ms = None;
+                bridge_connected = None;
+                backoff = MIN_BACKOFF;
+                next_start = Instant::now();
+            }
+        }
+
+        if let Some(running) = &mut server {
+            match running.child.try_wait() {
+                Ok(Some(status)) => {
+                    if running.started.elapsed() >= STABLE_AFTER {
+                        backoff = MIN_BACKOFF;
+                    }
+                    log.line(format!(
+                        "MCP server for {} exited ({}); restarting in {}s",
+                        running.channel_id,
+                        status,
+                        backoff.as_secs()
+                    ));
+                    if let Some(channel) = &channel {
+                        hooks::run(
+                            &config,
+                            HookEvent::ServerCrash,
+                            channel,
+                            vec![("BANTER_EXIT_STATUS", status.to_string())],
+                        );
+                    }
+                    next_start = Instant::now() + backoff;
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                    server = None;
+                    stats.started_at_ms = None;
+                }
+                Ok(None) => {}
+                Err(e) => log.line(format!("Failed to poll MCP server: {}", e)),
+            }
+        }
+
+        match &channel {
+            None => {
+                if last_error.as_deref() != Some("no-channel") {
+                    log.line("No active channel; waiting for one to be activated");
+                    last_error = Some("no-channel".to_string());
+                }
+            }
+            Some(channel) if server.is_none() && Instant::now() >= next_start => {
+                match spawn_server(&config, channel, port) {
+                    Ok(child) => {
+                        log.line(format!(
+                            "Started MCP server for {} ({}) on http://localhost:{}/mcp",
+                            channel.name, channel.id, port
+                        ));
+                        server = Some(Server {
+                            channel_id: channel.id.clone(),
+                            child,
+                            started: Instant::now(),
+                        });
+                        stats.channel_id = Some(channel.id.clone());
+                        stats.started_at_ms = Some(bridge::now_ms());
+                        stats.starts += 1;
+                        last_error = None;
+                        if !simulation::enabled(&config) {
+                            capabilities::refresh_in_background(state.clone(), channel.id.clone());
+                        }
+                    }
+                    Err(e) => {
+                        log.line(format!(
+                            "Failed to start MCP server: {}; retrying in {}s",
+                            e,
+                            backoff.as_secs()
+                        ));
+                        next_start = Instant::now() + backoff;
+                        backoff = (backoff * 2).min(MAX_BACKOFF);
+                    }
+                }
+            }
+            Some(_) => {}
+        }
+
+        if let Some(channel) = &channel {
+            let connected = bridge::is_editor_connected(Path::new(&channel.unity_project_path));
+            if bridge_connected != Some(connected) {
+                log.line(if connected {
+                    format!("Unity bridge connected ({})", channel.name)
+                } else {
+                    format!("Unity bridge not connected ({})", channel.name)
+                });
+                bridge_connected = Some(connected);
+            }
+        }
+
+        // Read by the window's control API for `/metrics`
+        stats.updated_at_ms = bridge::now_ms();
+        let _ = metrics::write_watch_metrics(&stats);
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
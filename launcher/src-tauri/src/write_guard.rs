@@ -0,0 +1,10 @@
+//! Write violation command; the allowlist is `launcher_core::write_guard`
+
+pub use launcher_core::write_guard::*;
+
+/// Writes refused since the launcher started, oldest first
+#[tauri::command]
+#[specta::specta]
+pub fn get_write_violations() -> Vec<WriteViolation> {
+    violations()
+}
@@ -1,19 +1,56 @@
+// Every call to the launcher goes through the generated wrappers
+import { commands } from './bindings.js';
+
 // State
 let config = {
   channels: [],
   active_channel_id: null,
   mcp_server_path: 'C:/tools/banter-mcp/dist/index.js',
   auto_start: false,
-  enable_custom_scripts: false
+  enable_custom_scripts: false,
+  batchmode_fallback: true,
+  relaunch_editor_on_crash: false,
+  git_guards: { refuse_dirty_activation: false, checkpoint_on_activate: false },
+  health_gate: { on_error: 'allow', on_warning: 'allow', ignore_checks: [] },
+  locale: 'en',
+  observer_mode: false,
+  record_sessions: false,
+  server_pool: { enabled: false, idle_timeout_secs: 300 },
+  serve_launcher_state: false,
+  portable_client_config: false,
+  file_triggers: false,
+  storage_backend: 'json',
+  schedules: { server_restart: null, update_check: null, idle_shutdown: null },
+  network: { proxy: 'system', no_proxy: [], max_kib_per_sec: null },
+  telemetry: { enabled: false, endpoint: null },
+  features: {}
 };
 
 let mcpRoot = 'C:/tools/banter-mcp';
 
+// Set when a project changed under the dashboard's cached channel checks
+let dashboardStale = false;
+
+// Backend message catalogue: id -> template with {name} placeholders
+let messages = {};
+
 // DOM Elements (set after DOM loads)
 let statusEl, channelsList, emptyState, addChannelBtn, addChannelModal;
 let modalBackdrop, channelNameInput, scenePathInput, pathValidation;
 let browseBtn, cancelBtn, confirmAddBtn, mcpServerPathInput;
-let autoConfigCheckbox, customScriptsCheckbox, applyConfigBtn, disconnectBtn, installExtensionBtn, openDocsBtn;
+let autoConfigCheckbox, customScriptsCheckbox, batchmodeCheckbox, relaunchEditorCheckbox, refuseDirtyCheckbox, gitCheckpointCheckbox, verifyExtensionBtn, snapshotBtn, applyConfigBtn, disconnectBtn, installExtensionBtn, openDocsBtn;
+let copyReportBtn, contextFileBtn, cleanupBtn, localeSelect, featureFlagsEl, observerCheckbox;
+let recordSessionsCheckbox, exportSessionBtn, serverPoolCheckbox, serveLauncherStateCheckbox, telemetryCheckbox;
+let portableConfigCheckbox, fileTriggersCheckbox, supportModeBtn, storageBackendSelect;
+
+// The running support capture, from get_support_mode
+let supportSession = null;
+
+// What this machine has, from get_capabilities; null until detected
+let capabilities = null;
+
+// Read-only observer mode, from get_observer_mode
+let observer = { active: false, forced_by_switch: false };
 
 // Initialize when DOM is ready
 document.addEventListener('DOMContentLoaded', async () => {
@@ -33,22 +70,91 @@ document.addEventListener('DOMContentLoaded', async () => {
   mcpServerPathInput = document.getElementById('mcpServerPath');
   autoConfigCheckbox = document.getElementById('autoConfig');
   customScriptsCheckbox = document.getElementById('customScripts');
+  batchmodeCheckbox = document.getElementById('batchmodeFallback');
+  relaunchEditorCheckbox = document.getElementById('relaunchEditorOnCrash');
+  refuseDirtyCheckbox = document.getElementById('refuseDirty');
+  gitCheckpointCheckbox = document.getElementById('gitCheckpoint');
+  verifyExtensionBtn = document.getElementById('verifyExtensionBtn');
+  snapshotBtn = document.getElementById('snapshotBtn');
   applyConfigBtn = document.getElementById('applyConfigBtn');
   disconnectBtn = document.getElementById('disconnectBtn');
   installExtensionBtn = document.getElementById('installExtensionBtn');
+  copyReportBtn = document.getElementById('copyReportBtn');
+  cleanupBtn = document.getElementById('cleanupBtn');
+  contextFileBtn = document.getElementById('contextFileBtn');
+  localeSelect = document.getElementById('locale');
   openDocsBtn = document.getElementById('openDocsBtn');
+  featureFlagsEl = document.getElementById('featureFlags');
+  observerCheckbox = document.getElementById('observerMode');
+  recordSessionsCheckbox = document.getElementById('recordSessions');
+  telemetryCheckbox = document.getElementById('telemetryEnabled');
+  serverPoolCheckbox = document.getElementById('serverPool');
+  serveLauncherStateCheckbox = document.getElementById('serveLauncherState');
+  portableConfigCheckbox = document.getElementById('portableClientConfig');
+  fileTriggersCheckbox = document.getElementById('fileTriggers');
+  storageBackendSelect = document.getElementById('storageBackend');
+  exportSessionBtn = document.getElementById('exportSessionBtn');
+  supportModeBtn = document.getElementById('supportModeBtn');
 
   // Set up event listeners
   setupEventListeners();
 
+  // Config changed outside the window (control API)
+  window.__TAURI__.event.listen('config-changed', function(event) {
+    config = event.payload;
+    updateUI();
+  });
+
+  // Another launcher activated our project and we stepped aside
+  window.__TAURI__.event.listen('session-taken-over', function(event) {
+    showToast(event.payload.owner + ' took over this project; channel deactivated', 'error');
+  });
+
+  // The active channel's editor crashed or froze
+  window.__TAURI__.event.listen('editor-status', function(event) {
+    showToast(event.payload.cause || 'Unity editor is down', 'error');
+    dashboardStale = true;
+    renderChannels();
+  });
+
+  // A file dropped on the window selects its channel, or offers to add one
+  window.__TAURI__.event.listen('tauri://drag-drop', function(event) {
+    var paths = event.payload.paths || [];
+    if (paths.length > 0) openDroppedPath(paths[0]);
+  });
+
+  // Detection finished, at startup or after Check again
+  window.__TAURI__.event.listen('capabilities', function(event) {
+    capabilities = event.payload;
+    applyCapabilities();
+  });
+
+  window.__TAURI__.event.listen('file-trigger', function(event) {
+    showToast(event.payload.message, event.payload.ok ? 'success' : 'error');
+  });
+
+  window.__TAURI__.event.listen('updates-available', function(event) {
+    var count = event.payload.length;
+    showToast(count + (count === 1 ? ' Unity extension is' : ' Unity extensions are') + ' out of date');
+    dashboardStale = true;
+    renderChannels();
+  });
+
   // Load config
   try {
-    mcpRoot = await window.__TAURI__.core.invoke('get_mcp_root');
-    config = await window.__TAURI__.core.invoke('load_config');
+    mcpRoot = await commands.getMcpRoot();
+    config = await commands.loadConfig();
+    await loadMessages();
+    await loadObserverMode();
     updateUI();
+    await loadFeatureFlags();
+    await checkOtherInstances();
+    await checkInterruptedOperations();
+    await loadSupportMode();
+    await loadCapabilities(false);
   } catch (err) {
     console.error('Failed to load config:', err);
-    showToast('Failed to load configuration: ' + err, 'error');
+    showToast('Failed to load configuration: ' + errorText(err), 'error');
   }
 });
 
@@ -111,7 +217,7 @@ function setupEventListeners() {
   mcpServerPathInput.addEventListener('change', async function() {
     config.mcp_server_path = mcpServerPathInput.value;
     try {
-      await window.__TAURI__.core.invoke('save_config', { config: config });
+      await commands.saveConfig(config);
     } catch (err) {
       console.error('Failed to save config:', err);
     }
@@ -120,7 +226,7 @@ function setupEventListeners() {
   autoConfigCheckbox.addEventListener('change', async function() {
     config.auto_start = autoConfigCheckbox.checked;
     try {
-      await window.__TAURI__.core.invoke('save_config', { config: config });
+      await commands.saveConfig(config);
     } catch (err) {
       console.error('Failed to save config:', err);
     }
@@ -129,14 +235,11 @@ function setupEventListeners() {
   customScriptsCheckbox.addEventListener('change', async function() {
     config.enable_custom_scripts = customScriptsCheckbox.checked;
     try {
-      await window.__TAURI__.core.invoke('save_config', { config: config });
+      await commands.saveConfig(config);
       // Also update Unity extension setting if active channel exists
       var channel = config.channels.find(function(c) { return c.id === config.active_channel_id; });
       if (channel) {
-        await window.__TAURI__.core.invoke('set_unity_custom_scripts', {
-          unityProjectPath: channel.unity_project_path,
-          enabled: customScriptsCheckbox.checked
-        });
+        await commands.setUnityCustomScripts(channel.unity_project_path, customScriptsCheckbox.checked);
       }
       showToast(customScriptsCheckbox.checked ? 'Custom scripts enabled' : 'Custom scripts disabled', 'success');
     } catch (err) {
@@ -144,10 +247,174 @@ function setupEventListeners() {
     }
   });
 
+  batchmodeCheckbox.addEventListener('change', async function() {
+    config.batchmode_fallback = batchmodeCheckbox.checked;
+    try {
+      await commands.saveConfig(config);
+    } catch (err) {
+      console.error('Failed to save config:', err);
+    }
+  });
+
+  relaunchEditorCheckbox.addEventListener('change', async function() {
+    config.relaunch_editor_on_crash = relaunchEditorCheckbox.checked;
+    try {
+      await commands.saveConfig(config);
+    } catch (err) {
+      console.error('Failed to save config:', err);
+    }
+  });
+
+  [refuseDirtyCheckbox, gitCheckpointCheckbox].forEach(function(checkbox) {
+    checkbox.addEventListener('change', async function() {
+      config.git_guards = {
+        refuse_dirty_activation: refuseDirtyCheckbox.checked,
+        checkpoint_on_activate: gitCheckpointCheckbox.checked
+      };
+      try {
+        await commands.saveConfig(config);
+      } catch (err) {
+        console.error('Failed to save config:', err);
+      }
+    });
+  });
+
+  observerCheckbox.addEventListener('change', async function() {
+    var enabled = observerCheckbox.checked;
+    if (!enabled && !confirm('Leave observer mode? Changes will be possible again.')) {
+      observerCheckbox.checked = true;
+      return;
+    }
+    try {
+      observer = await commands.setObserverMode(enabled);
+      config.observer_mode = enabled;
+    } catch (err) {
+      showToast('Failed to change observer mode: ' + errorText(err), 'error');
+    }
+    applyObserverMode();
+  });
+
+  recordSessionsCheckbox.addEventListener('change', async function() {
+    var enabled = recordSessionsCheckbox.checked;
+    try {
+      var session = await commands.setSessionRecording(enabled);
+      config.record_sessions = enabled;
+      showToast(enabled
+        ? (session ? 'Recording ' + session.channel_name + '; restart Claude Code to record its tool calls' : 'Sessions will be recorded from the next activation')
+        : 'Session recording off', 'success');
+    } catch (err) {
+      recordSessionsCheckbox.checked = !enabled;
+      showToast('Failed to change session recording: ' + errorText(err), 'error');
+    }
+  });
+
+  serverPoolCheckbox.addEventListener('change', async function() {
+    var enabled = serverPoolCheckbox.checked;
+    try {
+      config.server_pool = await commands.setServerPooling(enabled);
+      showToast(enabled
+        ? 'Clients will share one server per channel; restart them to pick it up'
+        : 'Each client will start its own server again', 'success');
+    } catch (err) {
+      serverPoolCheckbox.checked = !enabled;
+      showToast('Failed to change server pooling: ' + errorText(err), 'error');
+    }
+  });
+
+  serveLauncherStateCheckbox.addEventListener('change', async function() {
+    config.serve_launcher_state = serveLauncherStateCheckbox.checked;
+    try {
+      await commands.saveConfig(config);
+      showToast('Applied the next time Claude Code is configured', 'success');
+    } catch (err) {
+      console.error('Failed to save config:', err);
+    }
+  });
+
+  // Turning it on also sets the variables up on this machine
+  portableConfigCheckbox.addEventListener('change', async function() {
+    var enabled = portableConfigCheckbox.checked;
+    try {
+      var hint = 'Applied the next time Claude Code is configured';
+      if (enabled) {
+        var bootstrap = await commands.applyEnvBootstrap();
+        hint = bootstrap.hint;
+      }
+      config.portable_client_config = enabled;
+      await commands.saveConfig(config);
+      showToast(hint, 'success');
+    } catch (err) {
+      portableConfigCheckbox.checked = !enabled;
+      showToast('Failed to set up portable client config: ' + errorText(err), 'error');
+    }
+  });
+
+  fileTriggersCheckbox.addEventListener('change', async function() {
+    config.file_triggers = fileTriggersCheckbox.checked;
+    try {
+      await commands.saveConfig(config);
+    } catch (err) {
+      console.error('Failed to save config:', err);
+    }
+  });
+
+  storageBackendSelect.addEventListener('change', async function() {
+    config.storage_backend = storageBackendSelect.value;
+    try {
+      await commands.saveConfig(config);
+      showToast('Job history and caches moved to ' + storageBackendSelect.options[storageBackendSelect.selectedIndex].text, 'success');
+    } catch (err) {
+      console.error('Failed to save config:', err);
+    }
+  });
+
+  // Say exactly what is counted before anything is
+  telemetryCheckbox.addEventListener('change', async function() {
+    var enabled = telemetryCheckbox.checked;
+    try {
+      if (enabled) {
+        var status = await commands.getTelemetry();
+        var text = 'Counted:\n- ' + status.recorded.join('\n- ') +
+          '\n\nNever recorded:\n- ' + status.not_recorded.join('\n- ') +
+          '\n\nCounts stay in ' + status.file + ' until you send or clear them. Turn counting on?';
+        if (!confirm(text)) {
+          telemetryCheckbox.checked = false;
+          return;
+        }
+      }
+      config.telemetry = Object.assign({}, config.telemetry, { enabled: enabled });
+      await commands.saveConfig(config);
+      showToast(enabled ? 'Usage counting on' : 'Usage counting off', 'success');
+    } catch (err) {
+      telemetryCheckbox.checked = !enabled;
+      showToast('Failed to change usage counting: ' + errorText(err), 'error');
+    }
+  });
+
+  localeSelect.addEventListener('change', async function() {
+    config.locale = localeSelect.value;
+    try {
+      await commands.saveConfig(config);
+      await loadMessages();
+    } catch (err) {
+      console.error('Failed to save config:', err);
+    }
+  });
+
   // Quick actions
   applyConfigBtn.addEventListener('click', applyToClaudeCode);
   disconnectBtn.addEventListener('click', disconnectFromClaude);
   installExtensionBtn.addEventListener('click', installExtension);
+  verifyExtensionBtn.addEventListener('click', verifyExtension);
+  snapshotBtn.addEventListener('click', snapshotScene);
+  copyReportBtn.addEventListener('click', copySetupReport);
+  cleanupBtn.addEventListener('click', uninstallCleanup);
+  exportSessionBtn.addEventListener('click', exportLastSession);
+  supportModeBtn.addEventListener('click', toggleSupportMode);
+  document.getElementById('capabilitiesRefreshBtn').addEventListener('click', function() {
+    loadCapabilities(true);
+  });
+  contextFileBtn.addEventListener('click', writeContextFile);
   openDocsBtn.addEventListener('click', async function() {
     try {
       await window.__TAURI__.shell.open('https://github.com/anthropics/claude-code');
@@ -161,6 +428,19 @@ function updateUI() {
   mcpServerPathInput.value = config.mcp_server_path;
   autoConfigCheckbox.checked = config.auto_start !== false;
   customScriptsCheckbox.checked = config.enable_custom_scripts === true;
+  batchmodeCheckbox.checked = config.batchmode_fallback !== false;
+  relaunchEditorCheckbox.checked = config.relaunch_editor_on_crash === true;
+  var guards = config.git_guards || {};
+  refuseDirtyCheckbox.checked = guards.refuse_dirty_activation === true;
+  gitCheckpointCheckbox.checked = guards.checkpoint_on_activate === true;
+  recordSessionsCheckbox.checked = config.record_sessions === true;
+  telemetryCheckbox.checked = (config.telemetry || {}).enabled === true;
+  serverPoolCheckbox.checked = (config.server_pool || {}).enabled === true;
+  serveLauncherStateCheckbox.checked = config.serve_launcher_state === true;
+  portableConfigCheckbox.checked = config.portable_client_config === true;
+  fileTriggersCheckbox.checked = config.file_triggers === true;
+  storageBackendSelect.value = config.storage_backend || 'json';
+  localeSelect.value = config.locale || 'en';
   renderChannels();
   updateStatus();
 }
@@ -177,10 +457,51 @@ function renderChannels() {
 
   emptyState.style.display = 'none';
 
-  config.channels.forEach(function(channel) {
+  var cards = config.channels.map(function(channel) {
     var card = createChannelCard(channel);
     channelsList.appendChild(card);
+    return { channel: channel, card: card };
   });
+
+  // The dashboard touches every project folder; let the list paint first
+  var refresh = dashboardStale;
+  dashboardStale = false;
+  applyCapabilities();
+  whenIdle(function() { loadDashboard(cards, refresh); });
+}
+
+// One call for what every card shows; the backend caches the channel checks
+async function loadDashboard(cards, refresh) {
+  try {
+    var dashboard = await commands.getDashboard(refresh);
+    dashboard.channels.forEach(function(summary) {
+      var entry = cards.find(function(e) { return e.channel.id === summary.id; });
+      if (!entry) return;
+      var badge = entry.card.querySelector('.extension-badge');
+      var update = dashboard.pending_updates.find(function(u) { return u.channel_id === summary.id; });
+      if (update) {
+        badge.style.display = 'inline';
+        badge.className = 'badge warning extension-badge';
+        badge.textContent = 'Update';
+        badge.title = 'Extension ' + update.installed + ' installed, ' + update.available + ' available';
+      } else if (summary.extension_installed) {
+        badge.style.display = 'inline';
+        badge.className = 'badge success extension-badge';
+        badge.textContent = 'Extension';
+      }
+      if (summary.icon) showChannelIcon(entry.card, summary.icon);
+    });
+  } catch (err) {
+    console.error('Failed to load dashboard:', err);
+  }
+}
+
+function whenIdle(callback) {
+  if (window.requestIdleCallback) {
+    window.requestIdleCallback(callback, { timeout: 1000 });
+  } else {
+    setTimeout(callback, 0);
+  }
 }
 
 function createChannelCard(channel) {
@@ -193,14 +514,52 @@ function createChannelCard(channel) {
 
   card.innerHTML =
     '<div class="channel-radio"></div>' +
+    '<img class="channel-icon" alt="" style="display: none;">' +
     '<div class="channel-info">' +
       '<div class="channel-name">' + escapeHtml(channel.name) + '</div>' +
       '<div class="channel-path">' + escapeHtml(displayPath) + '</div>' +
     '</div>' +
     '<div class="channel-badges">' +
       '<span class="badge extension-badge" style="display: none;">Extension</span>' +
+      '<span class="badge capabilities-badge" data-command="get_server_capabilities" title="Ask the MCP server what it offers">Tools?</span>' +
+      '<span class="badge assets-badge" title="List the project\'s prefabs, materials and scripts">Assets?</span>' +
     '</div>' +
     '<div class="channel-actions">' +
+      '<button class="btn-icon-small icon" title="Make an icon from Player Settings or the open scene">' +
+        '<svg width="16" height="16" viewBox="0 0 16 16" fill="none">' +
+          '<rect x="2" y="3" width="12" height="10" rx="1.5" stroke="currentColor" stroke-width="1.5"/>' +
+          '<path d="M2.5 11l3.5-3.5 3 3 1.5-1.5 3 3" stroke="currentColor" stroke-width="1.5" stroke-linejoin="round"/>' +
+        '</svg>' +
+      '</button>' +
+      '<button class="btn-icon-small roots" title="Extra folders the MCP server may use">' +
+        '<svg width="16" height="16" viewBox="0 0 16 16" fill="none">' +
+          '<path d="M2 4.5h4l1.5 1.5H14v6.5H2z" stroke="currentColor" stroke-width="1.5" stroke-linejoin="round"/>' +
+        '</svg>' +
+      '</button>' +
+      '<button class="btn-icon-small server-dirs" title="Folders the MCP server starts in and keeps its data in">' +
+        '<svg width="16" height="16" viewBox="0 0 16 16" fill="none">' +
+          '<rect x="2" y="3" width="12" height="10" rx="1.5" stroke="currentColor" stroke-width="1.5"/>' +
+          '<path d="M5 7l2 1.5L5 10M8.5 10.5H11" stroke="currentColor" stroke-width="1.5" stroke-linecap="round" stroke-linejoin="round"/>' +
+        '</svg>' +
+      '</button>' +
+      '<button class="btn-icon-small quotas" title="Limits on what the MCP server does for an agent">' +
+        '<svg width="16" height="16" viewBox="0 0 16 16" fill="none">' +
+          '<path d="M8 2l5 2v4c0 3-2.2 5-5 6-2.8-1-5-3-5-6V4z" stroke="currentColor" stroke-width="1.5" stroke-linejoin="round"/>' +
+        '</svg>' +
+      '</button>' +
+      '<button class="btn-icon-small focus-editor" title="Bring this channel\'s Unity editor to the front">' +
+        '<svg width="16" height="16" viewBox="0 0 16 16" fill="none">' +
+          '<rect x="2" y="3" width="9" height="8" rx="1.5" stroke="currentColor" stroke-width="1.5"/>' +
+          '<path d="M9 13.5l4.5-4.5M10 9h3.5v3.5" stroke="currentColor" stroke-width="1.5" stroke-linecap="round" stroke-linejoin="round"/>' +
+        '</svg>' +
+      '</button>' +
+      '<button class="btn-icon-small workspace" title=""Share the project with other agent clients (workspace mode)">' +
+        '<svg width="16" height="16" viewBox="0 0 16 16" fill="none">' +
+          '<circle cx="5.5" cy="6" r="2" stroke="currentColor" stroke-width="1.5"/>' +
+          '<circle cx="10.5" cy="6" r="2" stroke="currentColor" stroke-width="1.5"/>' +
+          '<path d="M2 13c0-2 1.5-3 3.5-3s3.5 1 3.5 3M7 13c0-2 1.5-3 3.5-3S14 11 14 13" stroke="currentColor" stroke-width="1.5" stroke-linecap="round"/>' +
+        '</svg>' +
+      '</button>' +
       '<button class="btn-icon-small delete" title="Remove channel">' +
         '<svg width="16" height="16" viewBox="0 0 16 16" fill="none">' +
           '<path d="M4 4l8 8M12 4l-8 8" stroke="currentColor" stroke-width="2" stroke-linecap="round"/>' +
@@ -214,49 +573,124 @@ function createChannelCard(channel) {
     }
   });
 
+  var capabilitiesBadge = card.querySelector('.capabilities-badge');
+  showCapabilities(channel, capabilitiesBadge, false);
+  capabilitiesBadge.addEventListener('click', function(e) {
+    e.stopPropagation();
+    refreshCapabilities(channel, capabilitiesBadge);
+  });
+
+  var assetsBadge = card.querySelector('.assets-badge');
+  assetsBadge.addEventListener('click', function(e) {
+    e.stopPropagation();
+    showProjectAssets(channel, assetsBadge);
+  });
+
+  var iconBtn = card.querySelector('.icon');
+  iconBtn.addEventListener('click', function(e) {
+    e.stopPropagation();
+    generateChannelIcon(channel, card);
+  });
+
+  var rootsBtn = card.querySelector('.roots');
+  rootsBtn.addEventListener('click', function(e) {
+    e.stopPropagation();
+    editAllowedRoots(channel);
+  });
+
+  var serverDirsBtn = card.querySelector('.server-dirs');
+  serverDirsBtn.addEventListener('click', function(e) {
+    e.stopPropagation();
+    editServerDirs(channel);
+  });
+
+  var quotasBtn = card.querySelector('.quotas');
+  quotasBtn.addEventListener('click', function(e) {
+    e.stopPropagation();
+    editQuotas(channel);
+  });
+
+  var focusEditorBtn = card.querySelector('.focus-editor');
+  focusEditorBtn.addEventListener('click', function(e) {
+    e.stopPropagation();
+    focusUnityWindow(channel);
+  });
+
+  var workspaceBtn = card.querySelector('.workspace');
+  workspaceBtn.addEventListener('click', function(e) {
+    e.stopPropagation();
+    editWorkspaceClients(channel);
+  });
+
   var deleteBtn = card.querySelector('.delete');
   deleteBtn.addEventListener('click', function(e) {
     e.stopPropagation();
     removeChannel(channel.id);
   });
 
-  checkExtension(channel, card);
   return card;
 }
 
-async function checkExtension(channel, card) {
-  try {
-    var hasExtension = await window.__TAURI__.core.invoke('check_unity_extension', {
-      unityProjectPath: channel.unity_project_path
-    });
+async function selectChannel(channelId) {
+  if (observer.active) {
+    showToast('Observer mode is on; channels can\'t be switched', 'error');
+    return;
+  }
+  if (channelId !== config.active_channel_id) {
+    try {
+      var conflict = await commands.checkProjectSession(channelId);
+      if (conflict && !confirm(conflict.message + '.\n\nTake the project over? The channel will be deactivated there.')) {
+        return;
+      }
+    } catch (err) {
+      console.error('Failed to check project session:', err);
+    }
 
-    var badge = card.querySelector('.extension-badge');
-    if (hasExtension) {
-      badge.style.display = 'inline';
-      badge.className = 'badge success';
-      badge.textContent = 'Extension';
+    // save_config refuses too; asking first keeps the local config in step
+    var guards = config.git_guards || {};
+    if (guards.refuse_dirty_activation && !guards.checkpoint_on_activate) {
+      try {
+        var git = await commands.getGitStatus(channelId);
+        if (git && git.dirty) {
+          showToast('Commit or stash the project first (' + git.changed_files + ' uncommitted changes)', 'error');
+          return;
+        }
+      } catch (err) {
+        console.error('Failed to check git status:', err);
+      }
+    }
+
+    // The CLI and control API enforce the health gate; here the user decides
+    var healthWarnings = [];
+    try {
+      var gate = await commands.getActivationCheck(channelId);
+      var failed = function(checks) {
+        return checks.map(function(c) { return '- ' + c.message; }).join('\n');
+      };
+      if (gate.refusing.length && !confirm('The channel failed health checks:\n' + failed(gate.refusing) + '\n\nActivate anyway?')) {
+        return;
+      }
+      healthWarnings = gate.warnings;
+    } catch (err) {
+      console.error('Failed to check channel health:', err);
     }
-  } catch (err) {
-    console.error('Failed to check extension:', err);
   }
-}
 
-async function selectChannel(channelId) {
   config.active_channel_id = channelId;
 
   try {
-    await window.__TAURI__.core.invoke('save_config', { config: config });
+    await commands.saveConfig(config);
 
     if (autoConfigCheckbox.checked) {
       var channel = config.channels.find(function(c) { return c.id === channelId; });
       if (channel) {
-        await window.__TAURI__.core.invoke('update_claude_mcp_config', {
-          channel: channel,
-          mcpServerPath: config.mcp_server_path
-        });
+        await commands.updateClaudeMcpConfig(channel, config.mcp_server_path);
         showToast('Applied to Claude Code', 'success');
       }
     }
+    (healthWarnings || []).forEach(function(check) {
+      showToast(check.message);
+    });
 
     updateUI();
   } catch (err) {
@@ -265,15 +699,215 @@ async function selectChannel(channelId) {
   }
 }
 
-async function removeChannel(channelId) {
-  config.channels = config.channels.filter(function(c) { return c.id !== channelId; });
+// Another artist's launcher on this workstation, or one holding our ports
+async function checkOtherInstances() {
+  try {
+    var report = await commands.getOtherInstances();
+    if (report.others.length === 0) return;
+    var names = report.others.map(function(i) { return i.user; }).join(', ');
+    if (report.port_clashes.length) {
+      showToast(names + ' is running the launcher on port ' + report.port_clashes.join(', ') +
+        ' as well; change the port in the config', 'error');
+    } else {
+      showToast('Also running the launcher on this machine: ' + names, 'success');
+    }
+  } catch (err) {
+    console.error('Failed to check other instances:', err);
+  }
+}
+
+var OPERATION_NAMES = {
+  activation: 'Activating a channel',
+  client_config_sync: 'Updating the client configs',
+  extension_upgrade: 'Installing the Unity extension'
+};
 
-  if (config.active_channel_id === channelId) {
-    config.active_channel_id = config.channels.length > 0 ? config.channels[0].id : null;
+// Operations a crash cut short: run them again, roll them back, or ask next time
+async function checkInterruptedOperations() {
+  try {
+    var entries = await commands.getIncompleteOperations();
+    for (var i = 0; i < entries.length; i++) {
+      var entry = entries[i];
+      var name = OPERATION_NAMES[entry.operation.kind] || entry.operation.kind;
+      var when = new Date(entry.started_ms).toLocaleString();
+      if (confirm(name + ' was interrupted on ' + when + '.\n\nRun it again?')) {
+        await commands.resumeOperation(entry.id);
+        showToast(name + ' finished', 'success');
+      } else if (confirm('Undo the ' + entry.files.length + ' file change(s) it made instead?')) {
+        await commands.rollbackOperation(entry.id);
+        showToast(name + ' was rolled back', 'success');
+      } else {
+        continue;
+      }
+      config = await commands.loadConfig();
+      updateUI();
+    }
+  } catch (err) {
+    showToast('Failed to recover an interrupted operation: ' + errorText(err), 'error');
   }
+}
 
+// Tool count from the last time the server was asked; the tooltip lists them
+// Icons are served from the launcher folder over the channel-icon scheme
+function showChannelIcon(card, icon) {
+  var img = card.querySelector('.channel-icon');
+  img.src = window.__TAURI__.core.convertFileSrc(icon.channel_id, 'channel-icon') + '?v=' + icon.updated_at;
+  img.title = icon.source === 'scene' ? 'Captured from the open scene' : 'Player Settings icon';
+  img.style.display = '';
+}
+
+async function generateChannelIcon(channel, card) {
+  try {
+    var icon = await commands.generateChannelIcon(channel.id);
+    showChannelIcon(card, icon);
+  } catch (err) {
+    showToast('Failed to make an icon: ' + errorText(err), 'error');
+  }
+}
+
+function showCapabilities(channel, badge, stale) {
+  var capabilities = channel.capabilities;
+  if (!capabilities) return;
+  var server = [capabilities.server_name, capabilities.server_version].filter(Boolean).join(' ');
+  var names = capabilities.tools.map(function(t) { return t.name; });
+  badge.textContent = capabilities.tools.length + ' tools' + (stale ? '?' : '');
+  badge.title = (server || 'MCP server') +
+    (stale ? ' (the server has changed since; click to ask again)' : '') +
+    '\n' + names.join('\n') +
+    (capabilities.resources.length ? '\n\n' + capabilities.resources.length + ' resources' : '');
+}
+
+async function refreshCapabilities(channel, badge) {
+  badge.textContent = 'Asking...';
+  try {
+    var report = await commands.getServerCapabilities(channel.id, true);
+    channel.capabilities = report.capabilities;
+    showCapabilities(channel, badge, report.stale);
+  } catch (err) {
+    badge.textContent = 'Tools?';
+    showToast('Failed to ask the MCP server: ' + errorText(err), 'error');
+  }
+}
+
+// Prefabs, materials and scripts an agent can build with
+async function showProjectAssets(channel, badge) {
+  badge.textContent = 'Indexing...';
+  try {
+    var inventory = await commands.listProjectAssets(channel.unity_project_path, null, false);
+    var counts = { prefab: 0, material: 0, script: 0 };
+    inventory.assets.forEach(function(a) { counts[a.kind] = (counts[a.kind] || 0) + 1; });
+    badge.textContent = inventory.assets.length + ' assets';
+    badge.title = counts.prefab + ' prefabs, ' + counts.material + ' materials, ' + counts.script + ' scripts' +
+      '\n\n' + inventory.assets.filter(function(a) { return a.kind === 'prefab'; })
+        .slice(0, 40).map(function(a) { return a.path; }).join('\n');
+  } catch (err) {
+    badge.textContent = 'Assets?';
+    showToast('Failed to list project assets: ' + errorText(err), 'error');
+  }
+}
+
+// Shared folders (asset libraries) the channel's server may use besides its project
+async function editAllowedRoots(channel) {
+  var current = (channel.extra_allowed_roots || []).join('; ');
+  var answer = prompt('Folders the MCP server may use besides ' + channel.unity_project_path +
+    ' (separate with ;). Applied the next time Claude Code is configured.', current);
+  if (answer === null) return;
+
+  var roots = answer.split(';').map(function(r) { return r.trim(); }).filter(function(r) { return r.length > 0; });
+  try {
+    var updated = await commands.setChannelAllowedRoots(channel.id, roots);
+    var index = config.channels.findIndex(function(c) { return c.id === channel.id; });
+    if (index >= 0) config.channels[index] = updated;
+    showToast(roots.length ? 'Allowed folders updated' : 'Allowed folders cleared', 'success');
+  } catch (err) {
+    showToast('Failed to update folders: ' + errorText(err), 'error');
+  }
+}
+
+// Where the channel's server starts and keeps its caches; relative paths are inside the project
+async function editServerDirs(channel) {
+  var cwd = prompt('Folder the MCP server starts in, absolute or relative to ' + channel.unity_project_path +
+    ' (leave empty for the project itself).', channel.server_cwd || '');
+  if (cwd === null) return;
+  var dataDir = prompt('Folder for the MCP server\'s caches and generated files, absolute or relative to the project' +
+    ' (leave empty for Library/BanterMCP).', channel.server_data_dir || '');
+  if (dataDir === null) return;
+
+  try {
+    var updated = await commands.setChannelServerDirs(channel.id, cwd.trim() || null, dataDir.trim() || null);
+    var index = config.channels.findIndex(function(c) { return c.id === channel.id; });
+    if (index >= 0) config.channels[index] = updated;
+    showToast('Server folders updated; applied the next time Claude Code is configured', 'success');
+  } catch (err) {
+    showToast('Failed to update server folders: ' + errorText(err), 'error');
+  }
+}
+
+// Guardrails passed to the channel's server; the launcher's proxy enforces them too
+async function editQuotas(channel) {
+  var quotas = channel.quotas || {};
+  var number = function(answer) {
+    var value = parseInt(answer, 10);
+    return value > 0 ? value : null;
+  };
+  var bytes = prompt('Largest file the MCP server may write, in bytes (leave empty for no limit).',
+    quotas.max_write_bytes || '');
+  if (bytes === null) return;
+  var calls = prompt('Tool calls allowed per minute (leave empty for no limit).', quotas.max_calls_per_minute || '');
+  if (calls === null) return;
+  var denied = prompt('Folders tools may not write to, absolute or relative to ' + channel.unity_project_path +
+    ' (separate with ;).', (quotas.denied_paths || []).join('; '));
+  if (denied === null) return;
+
+  try {
+    var updated = await commands.setChannelQuotas(
+      channel.id,
+      {
+        max_write_bytes: number(bytes),
+        max_calls_per_minute: number(calls),
+        denied_paths: denied.split(';').map(function(p) { return p.trim(); }).filter(function(p) { return p.length > 0; })
+      }
+    );
+    var index = config.channels.findIndex(function(c) { return c.id === channel.id; });
+    if (index >= 0) config.channels[index] = updated;
+    showToast('Quotas updated; applied the next time Claude Code is configured', 'success');
+  } catch (err) {
+    showToast('Failed to update quotas: ' + errorText(err), 'error');
+  }
+}
+
+async function focusUnityWindow(channel) {
+  try {
+    await commands.focusUnityWindow(channel.id);
+  } catch (err) {
+    showToast('Failed to show the editor: ' + errorText(err), 'error');
+  }
+}
+
+// Other clients that get their own server for the project alongside Claude Code
+async function editWorkspaceClients(channel) {
+  var current = (channel.workspace_clients || []).join(', ');
+  var answer = prompt('Clients that share ' + channel.name + ' with Claude Code, each with its own MCP server' +
+    ' (cursor; leave empty for Claude Code only). Writes from all of them take turns.', current);
+  if (answer === null) return;
+
+  var clients = answer.split(',').map(function(c) { return c.trim().toLowerCase(); }).filter(function(c) { return c.length > 0; });
   try {
-    await window.__TAURI__.core.invoke('save_config', { config: config });
+    var updated = await commands.setChannelWorkspaceClients(channel.id, clients);
+    var index = config.channels.findIndex(function(c) { return c.id === channel.id; });
+    if (index >= 0) config.channels[index] = updated;
+    showToast(clients.length
+      ? 'Workspace mode on; applied the next time Claude Code is configured'
+      : 'Workspace mode off', 'success');
+  } catch (err) {
+    showToast('Failed to update workspace clients: ' + errorText(err), 'error');
+  }
+}
+
+async function removeChannel(channelId) {
+  try {
+    await commands.removeChannel(channelId);
+    config = await commands.loadConfig();
     updateUI();
     showToast('Channel removed', 'success');
   } catch (err) {
@@ -297,6 +931,27 @@ function updateStatus() {
   }
 }
 
+async function openDroppedPath(path) {
+  try {
+    var existing = await commands.findChannelForPath(path);
+    if (existing) {
+      if (existing.channel_id !== config.active_channel_id) {
+        await selectChannel(existing.channel_id);
+      }
+      showToast('Belongs to ' + existing.channel_name, 'info');
+    } else if (/\.unity$/i.test(path)) {
+      addChannelBtn.click();
+      scenePathInput.value = path;
+      validateScenePath(path);
+    } else {
+      showToast('No channel\'s project contains this file', 'error');
+    }
+  } catch (err) {
+    console.error('Failed to look up dropped file:', err);
+    showToast('Failed to look up file: ' + errorText(err), 'error');
+  }
+}
+
 function closeModal() {
   addChannelModal.classList.remove('open');
 }
@@ -310,7 +965,7 @@ async function validateScenePath(path) {
   }
 
   try {
-    var isValid = await window.__TAURI__.core.invoke('validate_unity_scene', { path: path });
+    var isValid = await commands.validateUnityScene(path);
 
     if (isValid) {
       pathValidation.textContent = 'Valid Unity scene file';
@@ -335,10 +990,15 @@ async function addChannel() {
   if (!name || !path) return;
 
   try {
-    var channel = await window.__TAURI__.core.invoke('add_channel', {
-      name: name,
-      scenePath: path
-    });
+    var existing = await commands.findChannelForPath(path);
+    if (existing && existing.scene_match) {
+      closeModal();
+      showToast('This scene is already the channel ' + existing.channel_name, 'info');
+      await selectChannel(existing.channel_id);
+      return;
+    }
+
+    var channel = await commands.addChannel(name, path);
 
     config.channels.push(channel);
 
@@ -346,14 +1006,14 @@ async function addChannel() {
       config.active_channel_id = channel.id;
     }
 
-    await window.__TAURI__.core.invoke('save_config', { config: config });
+    await commands.saveConfig(config);
 
     closeModal();
     updateUI();
     showToast('Channel added', 'success');
   } catch (err) {
     console.error('Failed to add channel:', err);
-    pathValidation.textContent = String(err);
+    pathValidation.textContent = errorText(err);
     pathValidation.className = 'validation-msg error';
   }
 }
@@ -367,10 +1027,7 @@ async function applyToClaudeCode() {
   }
 
   try {
-    await window.__TAURI__.core.invoke('update_claude_mcp_config', {
-      channel: channel,
-      mcpServerPath: config.mcp_server_path
-    });
+    await commands.updateClaudeMcpConfig(channel, config.mcp_server_path);
     showToast('Applied to Claude Code (~/.claude.json)', 'success');
   } catch (err) {
     console.error('Failed to apply config:', err);
@@ -380,11 +1037,33 @@ async function applyToClaudeCode() {
 
 async function disconnectFromClaude() {
   try {
-    await window.__TAURI__.core.invoke('remove_claude_mcp_config');
+    await commands.removeClaudeMcpConfig();
     showToast('Disconnected Banter MCP from Claude Code', 'success');
   } catch (err) {
     console.error('Failed to disconnect:', err);
-    showToast('Failed to disconnect: ' + String(err), 'error');
+    showToast('Failed to disconnect: ' + errorText(err), 'error');
+  }
+}
+
+// Everything the launcher set up, removed before uninstalling it
+async function uninstallCleanup() {
+  if (!confirm('Remove Banter MCP from Claude Code and the Unity extension from all ' +
+      config.channels.length + ' channel projects?')) return;
+  var deleteData = confirm('Also delete the launcher\'s config, secrets and logs? ' +
+    'Cancel keeps them for a reinstall.');
+
+  try {
+    var report = await commands.uninstallCleanup(
+      { delete_config: deleteData, delete_logs: deleteData, dry_run: false }
+    );
+    if (report.failures.length) {
+      showToast('Cleaned up, but ' + report.failures.length + ' project(s) failed: ' +
+        errorText(report.failures[0].error), 'error');
+    } else {
+      showToast('Cleaned up; the launcher can be uninstalled now', 'success');
+    }
+  } catch (err) {
+    showToast('Cleanup failed: ' + errorText(err), 'error');
   }
 }
 
@@ -397,15 +1076,157 @@ async function installExtension() {
   }
 
   try {
-    await window.__TAURI__.core.invoke('install_unity_extension', {
-      unityProjectPath: channel.unity_project_path,
-      mcpRoot: mcpRoot
-    });
+    await commands.installUnityExtension(channel.unity_project_path, mcpRoot);
     showToast('Unity extension installed', 'success');
+    dashboardStale = true;
     updateUI();
   } catch (err) {
     console.error('Failed to install extension:', err);
-    showToast('Failed: ' + String(err), 'error');
+    showToast('Failed: ' + errorText(err), 'error');
+  }
+}
+
+// Through the open editor, or a headless Unity run that can take a minute
+async function verifyExtension() {
+  var channel = config.channels.find(function(c) { return c.id === config.active_channel_id; });
+
+  if (!channel) {
+    showToast('No channel selected', 'error');
+    return;
+  }
+
+  showToast('Checking the Unity extension...', 'success');
+  try {
+    var result = await commands.verifyUnityExtension(channel.id);
+    showToast('Extension ' + (result.bridge_version || '') + ' runs in Unity ' +
+      (result.unity_version || '') + ' (' + result.mode + ')', 'success');
+  } catch (err) {
+    console.error('Failed to verify extension:', err);
+    showToast('Extension check failed: ' + errorText(err), 'error');
+  }
+}
+
+// Activation snapshots on its own; this one is for before a risky prompt
+async function snapshotScene() {
+  var channel = config.channels.find(function(c) { return c.id === config.active_channel_id; });
+
+  if (!channel) {
+    showToast('No channel selected', 'error');
+    return;
+  }
+
+  try {
+    var snapshot = await commands.snapshotScene(channel.id);
+    showToast('Snapshot saved (' + snapshot.files.length + ' files' +
+      (snapshot.git_stash ? ', git stash ' + snapshot.git_stash.slice(0, 7) : '') + ')', 'success');
+  } catch (err) {
+    console.error('Failed to snapshot scene:', err);
+    showToast('Snapshot failed: ' + errorText(err), 'error');
+  }
+}
+
+// Condensed report for chat; the full markdown goes to the console for issues
+async function writeContextFile() {
+  var channel = config.channels.find(function(c) { return c.id === config.active_channel_id; });
+
+  if (!channel) {
+    showToast('No channel selected', 'error');
+    return;
+  }
+
+  try {
+    var result = await commands.generateContextFile(channel.id);
+    if (result.tools_error) {
+      showToast('Wrote ' + result.path + ' without the tool list: ' + result.tools_error, 'error');
+    } else {
+      showToast('Wrote ' + result.path + ' (' + result.tool_count + ' tools)', 'success');
+    }
+  } catch (err) {
+    console.error('Failed to write context file:', err);
+    showToast('Failed: ' + errorText(err), 'error');
+  }
+}
+
+async function copySetupReport() {
+  if (!config.active_channel_id) {
+    showToast('No channel selected', 'error');
+    return;
+  }
+
+  try {
+    var report = await commands.generateSetupReport(config.active_channel_id);
+    await navigator.clipboard.writeText(report.condensed);
+    console.log(report.markdown);
+    showToast('Setup report copied to clipboard', 'success');
+  } catch (err) {
+    console.error('Failed to copy setup report:', err);
+    showToast('Failed: ' + errorText(err), 'error');
+  }
+}
+
+// Saves the newest recorded session of the active channel as a download
+async function exportLastSession() {
+  if (!config.active_channel_id) {
+    showToast('No channel selected', 'error');
+    return;
+  }
+
+  try {
+    var sessions = await commands.listSessions(config.active_channel_id);
+    if (sessions.length === 0) {
+      showToast('No recorded sessions for this channel', 'error');
+      return;
+    }
+    var exported = await commands.exportSession(sessions[0].id);
+    var link = document.createElement('a');
+    link.href = URL.createObjectURL(new Blob([exported.content], { type: 'application/json' }));
+    link.download = exported.file_name;
+    link.click();
+    URL.revokeObjectURL(link.href);
+    showToast('Exported session with ' + sessions[0].tool_calls + ' tool calls', 'success');
+  } catch (err) {
+    console.error('Failed to export session:', err);
+    showToast('Export failed: ' + errorText(err), 'error');
+  }
+}
+
+async function loadSupportMode() {
+  var status = await commands.getSupportMode();
+  supportSession = status.session;
+  updateSupportModeBtn();
+}
+
+function updateSupportModeBtn() {
+  supportModeBtn.lastChild.textContent = supportSession ? ' Finish Support Mode' : ' Support Mode';
+  supportModeBtn.classList.toggle('btn-danger', !!supportSession);
+}
+
+// Start a capture, or finish it and download the bundle
+async function toggleSupportMode() {
+  try {
+    if (!supportSession) {
+      var status = await commands.startSupportMode();
+      supportSession = status.session;
+      updateSupportModeBtn();
+      var steps = status.client_config_updated
+        ? 'Reconnect Claude Code to the MCP server, reproduce the problem, then click Finish Support Mode'
+        : 'Reproduce the problem, then click Finish Support Mode';
+      showToast('Support mode on. ' + steps, 'info');
+      return;
+    }
+    var bundle = await commands.finishSupportMode();
+    supportSession = null;
+    updateSupportModeBtn();
+    var link = document.createElement('a');
+    link.href = URL.createObjectURL(new Blob([bundle.content], { type: 'application/json' }));
+    link.download = bundle.file_name;
+    link.click();
+    URL.revokeObjectURL(link.href);
+    bundle.warnings.forEach(function(warning) { console.warn(warning); });
+    showToast('Saved ' + bundle.file_name + ' with ' + bundle.parts.length + ' captures', 'success');
+  } catch (err) {
+    console.error('Support mode failed:', err);
+    showToast('Support mode failed: ' + errorText(err), 'error');
   }
 }
 
@@ -430,6 +1251,119 @@ function showToast(message, type) {
   setTimeout(function() { toast.remove(); }, 3000);
 }
 
+async function loadObserverMode() {
+  try {
+    observer = await commands.getObserverMode();
+  } catch (err) {
+    console.error('Failed to load observer mode:', err);
+  }
+  applyObserverMode();
+}
+
+// The backend refuses changes either way; this just greys out what would be refused
+function applyObserverMode() {
+  document.body.classList.toggle('observer', observer.active);
+  document.getElementById('observerBanner').hidden = !observer.active;
+  observerCheckbox.checked = observer.active;
+  observerCheckbox.disabled = observer.forced_by_switch;
+}
+
+async function loadCapabilities(refresh) {
+  try {
+    capabilities = await commands.getCapabilities(refresh);
+  } catch (err) {
+    console.error('Failed to detect capabilities:', err);
+  }
+  applyCapabilities();
+}
+
+// The backend refuses these commands either way; this greys out their controls
+// and says what is missing
+function applyCapabilities() {
+  var disabled = capabilities ? capabilities.disabled_commands : [];
+  document.querySelectorAll('[data-command]').forEach(function(el) {
+    var off = disabled.indexOf(el.dataset.command) >= 0;
+    el.classList.toggle('unavailable', off);
+    if (el.tagName === 'BUTTON') el.disabled = off;
+  });
+  var missing = capabilities ? capabilities.flags.filter(function(f) { return !f.available; }) : [];
+  document.getElementById('capabilitiesBanner').hidden = missing.length === 0;
+  document.getElementById('capabilitiesMissing').textContent = missing.map(function(f) {
+    return f.detail;
+  }).join('; ');
+}
+
+async function loadFeatureFlags() {
+  try {
+    renderFeatureFlags(await commands.getFeatureFlags());
+  } catch (err) {
+    console.error('Failed to load feature flags:', err);
+  }
+}
+
+function renderFeatureFlags(flags) {
+  featureFlagsEl.innerHTML = '';
+  flags.forEach(function(flag) {
+    var row = document.createElement('div');
+    row.className = 'setting-row';
+    row.innerHTML =
+      '<div class="setting-info">' +
+        '<label>' + escapeHtml(flag.description) + '</label>' +
+        '<p class="hint">Experimental (' + escapeHtml(flag.name) + ')</p>' +
+      '</div>' +
+      '<label class="toggle">' +
+        '<input type="checkbox">' +
+        '<span class="toggle-slider"></span>' +
+      '</label>';
+
+    var checkbox = row.querySelector('input');
+    checkbox.checked = flag.enabled;
+    checkbox.addEventListener('change', async function() {
+      try {
+        var updated = await commands.setFeatureFlag(flag.name, checkbox.checked);
+        // Keep the copy save_config sends in step
+        config.features = config.features || {};
+        config.features[flag.name] = checkbox.checked;
+        renderFeatureFlags(updated);
+      } catch (err) {
+        checkbox.checked = !checkbox.checked;
+        showToast('Failed to change feature: ' + errorText(err), 'error');
+      }
+    });
+
+    featureFlagsEl.appendChild(row);
+  });
+}
+
+async function loadMessages() {
+  try {
+    var catalogue = await commands.getMessageCatalogue(config.locale);
+    messages = catalogue.messages;
+  } catch (err) {
+    console.error('Failed to load messages:', err);
+  }
+}
+
+// Render a catalogue message, or the fallback text the backend sent
+function messageText(id, params, fallback) {
+  var template = id && messages[id];
+  if (!template) return fallback;
+  return template.replace(/\{(\w+)\}/g, function(match, name) {
+    return params && name in params ? params[name] : match;
+  });
+}
+
+// Commands reject with { kind, message, hint, path }, plus message_id,
+// params and hint_id for catalogue messages
+function errorText(err) {
+  if (err && typeof err === 'object' && err.message) {
+    var message = messageText(err.message_id, err.params, err.message);
+    var hint = err.hint && messageText(err.hint_id, {}, err.hint);
+    return hint ? message + ' (' + hint + ')' : message;
+  }
+  return String(err);
+}
+
 function escapeHtml(text) {
   var div = document.createElement('div');
   div.textContent = text;
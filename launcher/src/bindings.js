@@ -0,0 +1,1791 @@
+// Generated from the launcher's commands by `--export-bindings`. Do not edit.
+
+/**
+ * Payload of each event the launcher emits, by event name
+ * @typedef {{
+ *   "account-login": LoginEvent,
+ *   "bundle-build-progress": BuildProgress,
+ *   "bundle-upload-progress": UploadProgress,
+ *   "capabilities": Capabilities,
+ *   "channel-check": ChannelCheckResult,
+ *   "config-changed": LauncherConfig,
+ *   "editor-status": EditorStatus,
+ *   "file-trigger": TriggerOutcome,
+ *   "job-progress": JobInfo,
+ *   "session-taken-over": SessionClaim,
+ *   "startup-complete": StartupReport,
+ *   "updates-available": PendingUpdate[],
+ * }} Events
+ */
+
+/** user-defined commands **/
+
+
+export const commands = {
+/**
+ * Save pending changes, then re-read the config from disk and return it.
+ * In observer mode the file is only read: nothing is saved or reapplied.
+ *
+ * @returns { Promise<LauncherConfig> }
+ */
+async loadConfig() {
+    return await TAURI_INVOKE("load_config");
+},
+/**
+ * @param { LauncherConfig } config
+ * @returns { Promise<null> }
+ */
+async saveConfig(config) {
+    return await TAURI_INVOKE("save_config", { config });
+},
+/**
+ * Write pending config changes immediately
+ *
+ * @returns { Promise<boolean> }
+ */
+async flushConfig() {
+    return await TAURI_INVOKE("flush_config");
+},
+/**
+ * @param { string } name
+ * @param { string } scenePath
+ * @returns { Promise<ProjectChannel> }
+ */
+async addChannel(name, scenePath) {
+    return await TAURI_INVOKE("add_channel", { name, scenePath });
+},
+/**
+ * @param { string } channelId
+ * @param { boolean | null } dryRun
+ * @returns { Promise<FilePlan> }
+ */
+async removeChannel(channelId, dryRun) {
+    return await TAURI_INVOKE("remove_channel", { channelId, dryRun });
+},
+/**
+ * Set the folders besides its project that a channel's MCP server may use.
+ * Takes effect the next time the Claude Code entry is written.
+ *
+ * @param { string } channelId
+ * @param { string[] } roots
+ * @returns { Promise<ProjectChannel> }
+ */
+async setChannelAllowedRoots(channelId, roots) {
+    return await TAURI_INVOKE("set_channel_allowed_roots", { channelId, roots });
+},
+/**
+ * Set the folder a channel's MCP server starts in and the one it keeps its
+ * data in; absolute, or relative to the project. Empty or `None` goes back
+ * to the default. Takes effect the next time the Claude Code entry is
+ * written.
+ *
+ * @param { string } channelId
+ * @param { string | null } cwd
+ * @param { string | null } dataDir
+ * @returns { Promise<ProjectChannel> }
+ */
+async setChannelServerDirs(channelId, cwd, dataDir) {
+    return await TAURI_INVOKE("set_channel_server_dirs", { channelId, cwd, dataDir });
+},
+/**
+ * Set the limits on what a channel's MCP server does for an agent. Takes
+ * effect the next time the Claude Code entry is written.
+ *
+ * @param { string } channelId
+ * @param { ChannelQuotas } quotas
+ * @returns { Promise<ProjectChannel> }
+ */
+async setChannelQuotas(channelId, quotas) {
+    return await TAURI_INVOKE("set_channel_quotas", { channelId, quotas });
+},
+/**
+ * Share a channel's project with other clients besides Claude Code
+ * (workspace mode). Their entries are written the next time Claude Code is
+ * configured; a client taken off the list loses its entry right away.
+ *
+ * @param { string } channelId
+ * @param { string[] } clients
+ * @returns { Promise<ProjectChannel> }
+ */
+async setChannelWorkspaceClients(channelId, clients) {
+    return await TAURI_INVOKE("set_channel_workspace_clients", { channelId, clients });
+},
+/**
+ * @param { string } path
+ * @returns { Promise<boolean> }
+ */
+async validateUnityScene(path) {
+    return await TAURI_INVOKE("validate_unity_scene", { path });
+},
+/**
+ * The existing channel whose project contains `path`, so a dropped file or
+ * picked scene selects it instead of adding a duplicate
+ *
+ * @param { string } path
+ * @returns { Promise<ChannelMatch | null> }
+ */
+async findChannelForPath(path) {
+    return await TAURI_INVOKE("find_channel_for_path", { path });
+},
+/**
+ * @returns { Promise<JsonValue> }
+ */
+async getClaudeMcpConfig() {
+    return await TAURI_INVOKE("get_claude_mcp_config");
+},
+/**
+ * @param { ProjectChannel } channel
+ * @param { string } mcpServerPath
+ * @param { boolean | null } dryRun
+ * @returns { Promise<FilePlan> }
+ */
+async updateClaudeMcpConfig(channel, mcpServerPath, dryRun) {
+    return await TAURI_INVOKE("update_claude_mcp_config", { channel, mcpServerPath, dryRun });
+},
+/**
+ * @param { boolean | null } dryRun
+ * @returns { Promise<FilePlan> }
+ */
+async removeClaudeMcpConfig(dryRun) {
+    return await TAURI_INVOKE("remove_claude_mcp_config", { dryRun });
+},
+/**
+ * @param { string } unityProjectPath
+ * @returns { Promise<boolean> }
+ */
+async checkUnityExtension(unityProjectPath) {
+    return await TAURI_INVOKE("check_unity_extension", { unityProjectPath });
+},
+/**
+ * @param { string } unityProjectPath
+ * @param { string } mcpRoot
+ * @param { boolean | null } dryRun
+ * @param { boolean | null } elevate
+ * @returns { Promise<FilePlan> }
+ */
+async installUnityExtension(unityProjectPath, mcpRoot, dryRun, elevate) {
+    return await TAURI_INVOKE("install_unity_extension", { unityProjectPath, mcpRoot, dryRun, elevate });
+},
+/**
+ * Get the MCP root directory
+ *
+ * @returns { Promise<string> }
+ */
+async getMcpRoot() {
+    return await TAURI_INVOKE("get_mcp_root");
+},
+/**
+ * @param { string } unityProjectPath
+ * @param { boolean } enabled
+ * @returns { Promise<null> }
+ */
+async setUnityCustomScripts(unityProjectPath, enabled) {
+    return await TAURI_INVOKE("set_unity_custom_scripts", { unityProjectPath, enabled });
+},
+/**
+ * Get Banter SDK / server / bridge versions for a channel
+ *
+ * @param { string } channelId
+ * @returns { Promise<CompatibilityReport> }
+ */
+async getBanterSdkInfo(channelId) {
+    return await TAURI_INVOKE("get_banter_sdk_info", { channelId });
+},
+/**
+ * Get health for a single channel
+ *
+ * @param { string } channelId
+ * @returns { Promise<ChannelHealth> }
+ */
+async getChannelHealth(channelId) {
+    return await TAURI_INVOKE("get_channel_health", { channelId });
+},
+/**
+ * Check health (and with `validate`, content rules) of all channels concurrently
+ *
+ * @param { boolean | null } validate
+ * @returns { Promise<AllChannelsReport> }
+ */
+async checkAllChannels(validate) {
+    return await TAURI_INVOKE("check_all_channels", { validate });
+},
+/**
+ * What the health gate would do if the channel were activated now
+ *
+ * @param { string } channelId
+ * @returns { Promise<ActivationCheck> }
+ */
+async getActivationCheck(channelId) {
+    return await TAURI_INVOKE("get_activation_check", { channelId });
+},
+/**
+ * Build the channel's Banter bundles for a platform ("windows" or "android")
+ *
+ * @param { string } channelId
+ * @param { string } platform
+ * @returns { Promise<BundleBuildResult> }
+ */
+async buildBanterBundle(channelId, platform) {
+    return await TAURI_INVOKE("build_banter_bundle", { channelId, platform });
+},
+/**
+ * Build the channel's bundles for all of its declared platforms
+ *
+ * @param { string } channelId
+ * @returns { Promise<BuildMatrixResult> }
+ */
+async buildAllPlatforms(channelId) {
+    return await TAURI_INVOKE("build_all_platforms", { channelId });
+},
+/**
+ * Upload the channel's built bundles for a platform to Banter hosting
+ *
+ * @param { string } channelId
+ * @param { string } platform
+ * @returns { Promise<UploadResult> }
+ */
+async uploadBanterBundle(channelId, platform) {
+    return await TAURI_INVOKE("upload_banter_bundle", { channelId, platform });
+},
+/**
+ * Launch the Banter client pointed at the channel's space.
+ * With `local` set, the channel's local test URL is preferred over the hosted one.
+ *
+ * @param { string } channelId
+ * @param { boolean | null } local
+ * @returns { Promise<VisitResult> }
+ */
+async visitSpace(channelId, local) {
+    return await TAURI_INVOKE("visit_space", { channelId, local });
+},
+/**
+ * Check a channel's scene and builds against Banter constraints
+ *
+ * @param { string } channelId
+ * @returns { Promise<ValidationReport> }
+ */
+async validateBanterContent(channelId) {
+    return await TAURI_INVOKE("validate_banter_content", { channelId });
+},
+/**
+ * Start a device-code login. Completion is reported via the `account-login` event.
+ *
+ * @returns { Promise<DeviceLogin> }
+ */
+async startLogin() {
+    return await TAURI_INVOKE("start_login");
+},
+/**
+ * Report whether Banter credentials are available
+ *
+ * @returns { Promise<AccountStatus> }
+ */
+async getAccountStatus() {
+    return await TAURI_INVOKE("get_account_status");
+},
+/**
+ * Remove stored Banter credentials from the keychain
+ *
+ * @returns { Promise<null> }
+ */
+async logout() {
+    return await TAURI_INVOKE("logout");
+},
+/**
+ * Serve a channel's content on localhost; replaces any running preview server.
+ * The served URL is recorded as the channel's local test URL.
+ *
+ * @param { string } channelId
+ * @param { number | null } port
+ * @returns { Promise<PreviewServerStatus> }
+ */
+async startPreviewServer(channelId, port) {
+    return await TAURI_INVOKE("start_preview_server", { channelId, port });
+},
+/**
+ * @returns { Promise<PreviewServerStatus> }
+ */
+async stopPreviewServer() {
+    return await TAURI_INVOKE("stop_preview_server");
+},
+/**
+ * @returns { Promise<PreviewServerStatus> }
+ */
+async getPreviewServerStatus() {
+    return await TAURI_INVOKE("get_preview_server_status");
+},
+/**
+ * @returns { Promise<ControlApiStatus> }
+ */
+async getControlApiStatus() {
+    return await TAURI_INVOKE("get_control_api_status");
+},
+/**
+ * Turn the control API on or off (optionally on a new port) and remember the choice
+ *
+ * @param { boolean } enabled
+ * @param { number | null } port
+ * @returns { Promise<ControlApiStatus> }
+ */
+async setControlApiEnabled(enabled, port) {
+    return await TAURI_INVOKE("set_control_api_enabled", { enabled, port });
+},
+/**
+ * @returns { Promise<SimulationStatus> }
+ */
+async getSimulationStatus() {
+    return await TAURI_INVOKE("get_simulation_status");
+},
+/**
+ * Turn simulation mode on or off and remember the choice
+ *
+ * @param { boolean } enabled
+ * @returns { Promise<SimulationStatus> }
+ */
+async setSimulationEnabled(enabled) {
+    return await TAURI_INVOKE("set_simulation_enabled", { enabled });
+},
+/**
+ * Every experimental feature and whether it is on
+ *
+ * @returns { Promise<FeatureFlag[]> }
+ */
+async getFeatureFlags() {
+    return await TAURI_INVOKE("get_feature_flags");
+},
+/**
+ * Bring the Unity editor of a channel's project to the front
+ *
+ * @param { string } channelId
+ * @returns { Promise<FocusedEditor> }
+ */
+async focusUnityWindow(channelId) {
+    return await TAURI_INVOKE("focus_unity_window", { channelId });
+},
+/**
+ * Bring the launcher window to the front
+ *
+ * @returns { Promise<null> }
+ */
+async focusLauncherWindow() {
+    return await TAURI_INVOKE("focus_launcher_window");
+},
+/**
+ * Turn an experimental feature on or off and remember the choice
+ *
+ * @param { string } name
+ * @param { boolean } enabled
+ * @returns { Promise<FeatureFlag[]> }
+ */
+async setFeatureFlag(name, enabled) {
+    return await TAURI_INVOKE("set_feature_flag", { name, enabled });
+},
+/**
+ * Replace the API token; tools using the old one stop working
+ *
+ * @returns { Promise<ControlApiStatus> }
+ */
+async regenerateControlApiToken() {
+    return await TAURI_INVOKE("regenerate_control_api_token");
+},
+/**
+ * List backups, optionally of a single kind, newest first
+ *
+ * @param { string | null } kind
+ * @returns { Promise<BackupEntry[]> }
+ */
+async listBackups(kind) {
+    return await TAURI_INVOKE("list_backups", { kind });
+},
+/**
+ * Put a backup back in place. The current file is backed up first so the
+ * restore itself can be undone.
+ *
+ * @param { string } id
+ * @returns { Promise<BackupEntry> }
+ */
+async restoreBackup(id) {
+    return await TAURI_INVOKE("restore_backup", { id });
+},
+/**
+ * Create, update and (with `prune`) delete channels to match a manifest.
+ * With `dry_run` the report shows what would change without writing.
+ *
+ * @param { string } path
+ * @param { boolean | null } dryRun
+ * @returns { Promise<ChannelsFileReport> }
+ */
+async applyChannelsFile(path, dryRun) {
+    return await TAURI_INVOKE("apply_channels_file", { path, dryRun });
+},
+/**
+ * Run a sequence of setup operations as one transaction
+ *
+ * @param { BatchOperation[] } ops
+ * @returns { Promise<BatchReport> }
+ */
+async batch(ops) {
+    return await TAURI_INVOKE("batch", { ops });
+},
+/**
+ * Export a channel as a `.bantermcp` document with machine paths replaced
+ * by placeholders
+ *
+ * @param { string } channelId
+ * @param { string | null } notes
+ * @returns { Promise<ChannelExport> }
+ */
+async exportChannel(channelId, notes) {
+    return await TAURI_INVOKE("export_channel", { channelId, notes });
+},
+/**
+ * Import a `.bantermcp` file. Returns `needs_input` with the placeholders to
+ * prompt for until `values` covers them all.
+ *
+ * @param { string } file
+ * @param { { [key in string]: string } | null } values
+ * @param { boolean | null } activate
+ * @param { boolean | null } dryRun
+ * @returns { Promise<ImportResult> }
+ */
+async importChannel(file, values, activate, dryRun) {
+    return await TAURI_INVOKE("import_channel", { file, values, activate, dryRun });
+},
+/**
+ * Pack the active channel, its snapshots and its unfinished jobs into
+ * `file` for `import_session_state` on another machine
+ *
+ * @param { string } file
+ * @returns { Promise<SessionStateExport> }
+ */
+async exportSessionState(file) {
+    return await TAURI_INVOKE("export_session_state", { file });
+},
+/**
+ * Pick up a session from `export_session_state`. Returns `needs_input` with
+ * the placeholders to prompt for until `values` covers them all.
+ *
+ * @param { string } file
+ * @param { { [key in string]: string } | null } values
+ * @param { boolean | null } resumeJobs
+ * @returns { Promise<SessionStateImport> }
+ */
+async importSessionState(file, values, resumeJobs) {
+    return await TAURI_INVOKE("import_session_state", { file, values, resumeJobs });
+},
+/**
+ * Create channels from existing Claude Code MCP entries for the Banter server
+ *
+ * @param { boolean | null } dryRun
+ * @returns { Promise<ClaudeImportReport> }
+ */
+async importFromClaudeConfig(dryRun) {
+    return await TAURI_INVOKE("import_from_claude_config", { dryRun });
+},
+/**
+ * Render a channel's MCP server entry for copy/paste into another client
+ *
+ * @param { string } channelId
+ * @param { string } template
+ * @returns { Promise<McpSnippet> }
+ */
+async generateMcpSnippet(channelId, template) {
+    return await TAURI_INVOKE("generate_mcp_snippet", { channelId, template });
+},
+/**
+ * The variables portable client configs refer to and what they are set to
+ *
+ * @returns { Promise<EnvBootstrap> }
+ */
+async getEnvBootstrap() {
+    return await TAURI_INVOKE("get_env_bootstrap");
+},
+/**
+ * Set the variables up on this machine
+ *
+ * @returns { Promise<EnvBootstrap> }
+ */
+async applyEnvBootstrap() {
+    return await TAURI_INVOKE("apply_env_bootstrap");
+},
+/**
+ * Whether support mode is on, and since when
+ *
+ * @returns { Promise<SupportStatus> }
+ */
+async getSupportMode() {
+    return await TAURI_INVOKE("get_support_mode");
+},
+/**
+ * Start capturing traces, MCP traffic and bridge traffic
+ *
+ * @returns { Promise<SupportStatus> }
+ */
+async startSupportMode() {
+    return await TAURI_INVOKE("start_support_mode");
+},
+/**
+ * Stop capturing and return the diagnostics bundle
+ *
+ * @returns { Promise<SupportBundle> }
+ */
+async finishSupportMode() {
+    return await TAURI_INVOKE("finish_support_mode");
+},
+/**
+ * Write or refresh the agent context file in a channel's project. `format`
+ * is `claude` (the default) or `cursor`.
+ *
+ * @param { string } channelId
+ * @param { string | null } format
+ * @param { boolean | null } dryRun
+ * @returns { Promise<ContextFile> }
+ */
+async generateContextFile(channelId, format, dryRun) {
+    return await TAURI_INVOKE("generate_context_file", { channelId, format, dryRun });
+},
+/**
+ * The tools and resources the channel's server offers. Asks the server
+ * when nothing is cached or `refresh` is set; otherwise answers from the
+ * cache, flagged if it is stale.
+ *
+ * @param { string } channelId
+ * @param { boolean | null } refresh
+ * @returns { Promise<CapabilitiesReport> }
+ */
+async getServerCapabilities(channelId, refresh) {
+    return await TAURI_INVOKE("get_server_capabilities", { channelId, refresh });
+},
+/**
+ * Which of Node.js, a Unity editor, Claude Code and the network this
+ * machine has, and the commands refused for what is missing. `refresh`
+ * looks again instead of answering from the last detection.
+ *
+ * @param { boolean | null } refresh
+ * @returns { Promise<Capabilities> }
+ */
+async getCapabilities(refresh) {
+    return await TAURI_INVOKE("get_capabilities", { refresh });
+},
+/**
+ * Other users' launchers running on this machine
+ *
+ * @returns { Promise<InstanceReport> }
+ */
+async getOtherInstances() {
+    return await TAURI_INVOKE("get_other_instances");
+},
+/**
+ * Check the channel's Unity extension, headlessly if no editor is open
+ *
+ * @param { string } channelId
+ * @returns { Promise<ExtensionVerification> }
+ */
+async verifyUnityExtension(channelId) {
+    return await TAURI_INVOKE("verify_unity_extension", { channelId });
+},
+/**
+ * The channel's scenes as Unity lists them, headlessly if no editor is open
+ *
+ * @param { string } channelId
+ * @returns { Promise<SceneList> }
+ */
+async listUnityScenes(channelId) {
+    return await TAURI_INVOKE("list_unity_scenes", { channelId });
+},
+/**
+ * Copy the channel's scene files into a new snapshot. `include_git`
+ * overrides `snapshot_git_stash`.
+ *
+ * @param { string } channelId
+ * @param { boolean | null } includeGit
+ * @returns { Promise<Snapshot> }
+ */
+async snapshotScene(channelId, includeGit) {
+    return await TAURI_INVOKE("snapshot_scene", { channelId, includeGit });
+},
+/**
+ * The channel's snapshots, newest first
+ *
+ * @param { string } channelId
+ * @returns { Promise<Snapshot[]> }
+ */
+async listSnapshots(channelId) {
+    return await TAURI_INVOKE("list_snapshots", { channelId });
+},
+/**
+ * Put a snapshot's scene files back. Its git stash, if any, is left for
+ * `git stash apply`, since merging it is the user's call.
+ *
+ * @param { string } channelId
+ * @param { string } snapshotId
+ * @returns { Promise<Snapshot> }
+ */
+async restoreSnapshot(channelId, snapshotId) {
+    return await TAURI_INVOKE("restore_snapshot", { channelId, snapshotId });
+},
+/**
+ * Git state of a channel's project, `None` outside a repo. The window asks
+ * before activating a channel so a refusal doesn't land mid-save.
+ *
+ * @param { string } channelId
+ * @returns { Promise<GitStatus | null> }
+ */
+async getGitStatus(channelId) {
+    return await TAURI_INVOKE("get_git_status", { channelId });
+},
+/**
+ * Extract or render a thumbnail for the channel's card. `source` is
+ * `player-settings` or `scene`; by default Player Settings are tried first.
+ *
+ * @param { string } channelId
+ * @param { string | null } source
+ * @returns { Promise<ChannelIcon> }
+ */
+async generateChannelIcon(channelId, source) {
+    return await TAURI_INVOKE("generate_channel_icon", { channelId, source });
+},
+/**
+ * Channel summaries, server and bridge states, pending updates and recent
+ * errors for the main screen. `refresh` reruns the cached channel checks.
+ *
+ * @param { boolean | null } refresh
+ * @returns { Promise<Dashboard> }
+ */
+async getDashboard(refresh) {
+    return await TAURI_INVOKE("get_dashboard", { refresh });
+},
+/**
+ * Whether the channel's Unity editor is connected, starting, frozen,
+ * crashed or closed
+ *
+ * @param { string } channelId
+ * @returns { Promise<EditorStatus> }
+ */
+async getEditorStatus(channelId) {
+    return await TAURI_INVOKE("get_editor_status", { channelId });
+},
+/**
+ * Open the channel's project in Unity, e.g. after a crash. Returns the
+ * editor's process id.
+ *
+ * @param { string } channelId
+ * @returns { Promise<number> }
+ */
+async relaunchEditor(channelId) {
+    return await TAURI_INVOKE("relaunch_editor", { channelId });
+},
+/**
+ * Undo everything the launcher set up before it is uninstalled: stop its
+ * servers, remove the `banter` client entry and the Unity extension from
+ * every channel's project, and optionally delete its config and logs.
+ *
+ * @param { UninstallOptions | null } options
+ * @returns { Promise<UninstallReport> }
+ */
+async uninstallCleanup(options) {
+    return await TAURI_INVOKE("uninstall_cleanup", { options });
+},
+/**
+ * Describe a channel's setup as markdown for sharing
+ *
+ * @param { string } channelId
+ * @returns { Promise<SetupReport> }
+ */
+async generateSetupReport(channelId) {
+    return await TAURI_INVOKE("generate_setup_report", { channelId });
+},
+/**
+ * Every message template, for the frontend to render ids itself
+ *
+ * @param { string | null } locale
+ * @returns { Promise<MessageCatalogue> }
+ */
+async getMessageCatalogue(locale) {
+    return await TAURI_INVOKE("get_message_catalogue", { locale });
+},
+/**
+ * Read part of a log or scene file from a byte offset
+ *
+ * @param { ReadTarget } target
+ * @param { number | null } offset
+ * @param { number | null } limit
+ * @returns { Promise<FileChunk> }
+ */
+async readFileChunk(target, offset, limit) {
+    return await TAURI_INVOKE("read_file_chunk", { target, offset, limit });
+},
+/**
+ * Read the end of a log or scene file
+ *
+ * @param { ReadTarget } target
+ * @param { number | null } lines
+ * @param { number | null } maxBytes
+ * @returns { Promise<FileChunk> }
+ */
+async readFileTail(target, lines, maxBytes) {
+    return await TAURI_INVOKE("read_file_tail", { target, lines, maxBytes });
+},
+/**
+ * List a Unity project's scenes and editor version, from the cache when the
+ * project hasn't changed since the last scan
+ *
+ * @param { string } unityProjectPath
+ * @param { boolean | null } refresh
+ * @returns { Promise<ProjectScan> }
+ */
+async scanUnityProject(unityProjectPath, refresh) {
+    return await TAURI_INVOKE("scan_unity_project", { unityProjectPath, refresh });
+},
+/**
+ * Find Unity projects below a folder
+ *
+ * @param { string } root
+ * @param { boolean | null } refresh
+ * @returns { Promise<ProjectSearch> }
+ */
+async findUnityProjects(root, refresh) {
+    return await TAURI_INVOKE("find_unity_projects", { root, refresh });
+},
+/**
+ * Drop cached scans, asset and GUID indexes for a path (a project or search
+ * folder), or all of them
+ *
+ * @param { string | null } path
+ * @returns { Promise<null> }
+ */
+async invalidateScanCache(path) {
+    return await TAURI_INVOKE("invalidate_scan_cache", { path });
+},
+/**
+ * List a project's prefabs, materials and scripts with their GUIDs and
+ * sizes. `kinds` narrows the list to some of `prefab`, `material` and
+ * `script`.
+ *
+ * @param { string } project
+ * @param { string[] | null } kinds
+ * @param { boolean | null } refresh
+ * @returns { Promise<AssetInventory> }
+ */
+async listProjectAssets(project, kinds, refresh) {
+    return await TAURI_INVOKE("list_project_assets", { project, kinds, refresh });
+},
+/**
+ * The asset a GUID belongs to: its path and name, or the built-in resource
+ *
+ * @param { string } project
+ * @param { string } guid
+ * @returns { Promise<GuidAsset> }
+ */
+async resolveGuid(project, guid) {
+    return await TAURI_INVOKE("resolve_guid", { project, guid });
+},
+/**
+ * The project files that reference a GUID, and the asset it belongs to if
+ * it still exists
+ *
+ * @param { string } project
+ * @param { string } guid
+ * @returns { Promise<GuidReferences> }
+ */
+async findReferences(project, guid) {
+    return await TAURI_INVOKE("find_references", { project, guid });
+},
+/**
+ * Create a minimal Unity project for tests and demos. Not used by the
+ * window itself.
+ *
+ * @param { string } path
+ * @param { MockProjectOptions | null } options
+ * @param { boolean | null } dryRun
+ * @returns { Promise<MockProject> }
+ */
+async createMockProject(path, options, dryRun) {
+    return await TAURI_INVOKE("create_mock_project", { path, options, dryRun });
+},
+/**
+ * Queue a job (install, scan, build, upload, checks); returns immediately
+ *
+ * @param { JobRequest } request
+ * @returns { Promise<JobInfo> }
+ */
+async enqueueJob(request) {
+    return await TAURI_INVOKE("enqueue_job", { request });
+},
+/**
+ * @param { string } id
+ * @returns { Promise<JobInfo> }
+ */
+async getJobStatus(id) {
+    return await TAURI_INVOKE("get_job_status", { id });
+},
+/**
+ * Queued, running and recently finished jobs, oldest first
+ *
+ * @returns { Promise<JobInfo[]> }
+ */
+async listJobs() {
+    return await TAURI_INVOKE("list_jobs");
+},
+/**
+ * @param { string } id
+ * @returns { Promise<JobInfo> }
+ */
+async cancelJob(id) {
+    return await TAURI_INVOKE("cancel_job", { id });
+},
+/**
+ * Jobs of this and earlier launcher runs, newest first, with their logs
+ *
+ * @param { number | null } limit
+ * @returns { Promise<JobHistoryEntry[]> }
+ */
+async getJobHistory(limit) {
+    return await TAURI_INVOKE("get_job_history", { limit });
+},
+/**
+ * Run an interrupted job from the history again
+ *
+ * @param { string } id
+ * @returns { Promise<JobInfo> }
+ */
+async resumeJob(id) {
+    return await TAURI_INVOKE("resume_job", { id });
+},
+/**
+ * Operations a crash or power loss interrupted
+ *
+ * @returns { Promise<JournalEntry[]> }
+ */
+async getIncompleteOperations() {
+    return await TAURI_INVOKE("get_incomplete_operations");
+},
+/**
+ * Put back every file an interrupted operation changed
+ *
+ * @param { string } id
+ * @returns { Promise<FilePlan> }
+ */
+async rollbackOperation(id) {
+    return await TAURI_INVOKE("rollback_operation", { id });
+},
+/**
+ * Run an interrupted operation again
+ *
+ * @param { string } id
+ * @returns { Promise<null> }
+ */
+async resumeOperation(id) {
+    return await TAURI_INVOKE("resume_operation", { id });
+},
+/**
+ * Forget an interrupted operation and keep its files as they are
+ *
+ * @param { string } id
+ * @returns { Promise<null> }
+ */
+async discardOperation(id) {
+    return await TAURI_INVOKE("discard_operation", { id });
+},
+/**
+ * Set (or with `value: None`, remove) a secret passed to the channel's server.
+ * Takes effect the next time the client config is written.
+ *
+ * @param { string } channelId
+ * @param { string } name
+ * @param { string | null } value
+ * @returns { Promise<null> }
+ */
+async setChannelSecret(channelId, name, value) {
+    return await TAURI_INVOKE("set_channel_secret", { channelId, name, value });
+},
+/**
+ * Names (never values) of the channel's stored secrets
+ *
+ * @param { string } channelId
+ * @returns { Promise<string[]> }
+ */
+async listChannelSecrets(channelId) {
+    return await TAURI_INVOKE("list_channel_secrets", { channelId });
+},
+/**
+ * Who else is using a channel's project, asked before activating it
+ *
+ * @param { string } channelId
+ * @returns { Promise<SessionConflict | null> }
+ */
+async checkProjectSession(channelId) {
+    return await TAURI_INVOKE("check_project_session", { channelId });
+},
+/**
+ * Writes refused since the launcher started, oldest first
+ *
+ * @returns { Promise<WriteViolation[]> }
+ */
+async getWriteViolations() {
+    return await TAURI_INVOKE("get_write_violations");
+},
+/**
+ * Report whether a target path needs elevation to write
+ *
+ * @param { string } path
+ * @returns { Promise<ElevationStatus> }
+ */
+async checkElevation(path) {
+    return await TAURI_INVOKE("check_elevation", { path });
+},
+/**
+ * How long each startup phase took, and whether the deferred ones are done
+ *
+ * @returns { Promise<StartupReport> }
+ */
+async getStartupReport() {
+    return await TAURI_INVOKE("get_startup_report");
+},
+/**
+ * Whether telemetry is on, what it records, and the report as it stands
+ *
+ * @returns { Promise<TelemetryStatus> }
+ */
+async getTelemetry() {
+    return await TAURI_INVOKE("get_telemetry");
+},
+/**
+ * Write the report `send_telemetry` would post to `file`
+ *
+ * @param { string } file
+ * @returns { Promise<TelemetryReport> }
+ */
+async exportTelemetry(file) {
+    return await TAURI_INVOKE("export_telemetry", { file });
+},
+/**
+ * Forget every count
+ *
+ * @returns { Promise<null> }
+ */
+async clearTelemetry() {
+    return await TAURI_INVOKE("clear_telemetry");
+},
+/**
+ * Post the counts to `telemetry.endpoint` now; returns what was sent
+ *
+ * @returns { Promise<TelemetryReport> }
+ */
+async sendTelemetry() {
+    return await TAURI_INVOKE("send_telemetry");
+},
+/**
+ * Whether observer mode is on, and whether the window may turn it off
+ *
+ * @returns { Promise<ObserverStatus> }
+ */
+async getObserverMode() {
+    return await TAURI_INVOKE("get_observer_mode");
+},
+/**
+ * Turn the config's observer mode on or off. Off is refused while
+ * `--observer` holds it on.
+ *
+ * @param { boolean } enabled
+ * @returns { Promise<ObserverStatus> }
+ */
+async setObserverMode(enabled) {
+    return await TAURI_INVOKE("set_observer_mode", { enabled });
+},
+/**
+ * Recorded sessions, newest first; only the channel's with `channel_id`
+ *
+ * @param { string | null } channelId
+ * @returns { Promise<RecordedSession[]> }
+ */
+async listSessions(channelId) {
+    return await TAURI_INVOKE("list_sessions", { channelId });
+},
+/**
+ * A recorded session as one JSON file: its events and the scene files of
+ * its snapshots
+ *
+ * @param { string } sessionId
+ * @returns { Promise<SessionExport> }
+ */
+async exportSession(sessionId) {
+    return await TAURI_INVOKE("export_session", { sessionId });
+},
+/**
+ * Turn session recording on or off; returns the active channel's session
+ *
+ * @param { boolean } enabled
+ * @returns { Promise<RecordedSession | null> }
+ */
+async setSessionRecording(enabled) {
+    return await TAURI_INVOKE("set_session_recording", { enabled });
+},
+/**
+ * List the pooled servers that are running
+ *
+ * @returns { Promise<PooledServer[]> }
+ */
+async getServerPool() {
+    return await TAURI_INVOKE("get_server_pool");
+},
+/**
+ * Turn server pooling on or off and set the idle timeout
+ *
+ * @param { boolean } enabled
+ * @param { number | null } idleTimeoutSecs
+ * @returns { Promise<ServerPoolSettings> }
+ */
+async setServerPooling(enabled, idleTimeoutSecs) {
+    return await TAURI_INVOKE("set_server_pooling", { enabled, idleTimeoutSecs });
+},
+/**
+ * Every scheduled task with its next run
+ *
+ * @returns { Promise<ScheduleStatus[]> }
+ */
+async getSchedules() {
+    return await TAURI_INVOKE("get_schedules");
+},
+/**
+ * Give a task a schedule, or turn it off with `None`
+ *
+ * @param { Task } task
+ * @param { SchedulePolicy | null } policy
+ * @returns { Promise<ScheduleStatus[]> }
+ */
+async setSchedule(task, policy) {
+    return await TAURI_INVOKE("set_schedule", { task, policy });
+},
+/**
+ * What changed between two versions of a `.unity` file: objects added and
+ * removed, components changed and transform edits
+ *
+ * @param { string } before
+ * @param { string } after
+ * @returns { Promise<SceneDiff> }
+ */
+async diffScenes(before, after) {
+    return await TAURI_INVOKE("diff_scenes", { before, after });
+},
+}
+
+/** user-defined events **/
+
+
+
+/** user-defined constants **/
+
+
+
+/** user-defined types **/
+
+/**
+ * @typedef { { logged_in: boolean; username: string | null; expires_at: number | null; source: string | null } } AccountStatus
+ */
+
+/**
+ * The health gate's verdict on activating a channel
+ *
+ * @typedef { { channel_id: string; refusing: HealthCheck[]; warnings: HealthCheck[] } } ActivationCheck
+ */
+
+/**
+ * @typedef { { results: ChannelCheckResult[]; duration_ms: number } } AllChannelsReport
+ */
+
+/**
+ * @typedef { { project_path: string; assets: ProjectAsset[]; scanned_at: number; cached: boolean } } AssetInventory
+ */
+
+/**
+ * @typedef { { id: string; kind: BackupKind; original_path: string; created_at: number; size: number } } BackupEntry
+ */
+
+/**
+ * @typedef { "launcher-config" | "client-config" | "manifest" } BackupKind
+ */
+
+/**
+ * How many backups to keep, per kind
+ *
+ * @typedef { { max_per_kind: number; max_age_days: number } } BackupRetention
+ */
+
+/**
+ * Detected Banter SDK package in a Unity project
+ *
+ * @typedef { { package_id: string | null; version: string | null; source: string | null; reference: string | null } } BanterSdkInfo
+ */
+
+/**
+ * @typedef { { op: "add_channel"; name: string; scene_path: string; activate: boolean } | { op: "install_extension"; channel_id: string | null; mcp_root: string | null } | { op: "register_clients"; channel_id: string | null } | { op: "start_server"; channel_id: string | null; port: number | null } } BatchOperation
+ */
+
+/**
+ * @typedef { { operations: OperationResult[] } } BatchReport
+ */
+
+/**
+ * @typedef { { channel_id: string; connected: boolean; heartbeat_ms: number | null; bridge_version: string | null; editor: EditorCondition; editor_cause: string | null } } BridgeState
+ */
+
+/**
+ * @typedef { { channel_id: string; succeeded: number; failed: number; outcomes: PlatformBuildOutcome[]; duration_ms: number } } BuildMatrixResult
+ */
+
+/**
+ * Payload of the `bundle-build-progress` event
+ *
+ * @typedef { { channel_id: string; platform: string; stage: string; message: string } } BuildProgress
+ */
+
+/**
+ * @typedef { { path: string; size: number } } BundleArtifact
+ */
+
+/**
+ * @typedef { { channel_id: string; platform: string; mode: string; output_dir: string; artifacts: BundleArtifact[]; total_size: number; duration_ms: number } } BundleBuildResult
+ */
+
+/**
+ * Payload of `get_capabilities`
+ *
+ * @typedef { { node: boolean; unity: boolean; claude_code: boolean; online: boolean; flags: CapabilityFlag[]; disabled_commands: string[]; checked_at: number } } Capabilities
+ */
+
+/**
+ * @typedef { { channel_id: string; capabilities: ServerCapabilities | null; stale: boolean } } CapabilitiesReport
+ */
+
+/**
+ * @typedef { "node" | "unity" | "claude_code" | "network" } Capability
+ */
+
+/**
+ * @typedef { { capability: Capability; available: boolean; detail: string } } CapabilityFlag
+ */
+
+/**
+ * @typedef { { id: string; name: string; fields: string[] } } ChannelChange
+ */
+
+/**
+ * Health (and optionally validation) of one channel, as streamed by `check_all_channels`
+ *
+ * @typedef { { channel_id: string; health: ChannelHealth; validation: ValidationReport | null; validation_error: LauncherError | null; duration_ms: number } } ChannelCheckResult
+ */
+
+/**
+ * @typedef { { file_name: string; content: string } } ChannelExport
+ */
+
+/**
+ * Health of a channel; `status` is the worst status of all checks
+ *
+ * @typedef { { channel_id: string; status: HealthStatus; checks: HealthCheck[] } } ChannelHealth
+ */
+
+/**
+ * @typedef { { channel_id: string; source: string; asset_path: string | null; file: string; updated_at: number } } ChannelIcon
+ */
+
+/**
+ * @typedef { { channel_id: string; channel_name: string; unity_project_path: string; scene_match: boolean } } ChannelMatch
+ */
+
+/**
+ * Limits a channel puts on its MCP server; passed to the server and, with
+ * the launcher's proxy in between, enforced there too (see `quotas`)
+ *
+ * @typedef { { max_write_bytes: number | null; max_calls_per_minute: number | null; denied_paths: string[] } } ChannelQuotas
+ */
+
+/**
+ * @typedef { { id: string; name: string; active: boolean; unity_project_path: string; scene_path: string | null; extension_installed: boolean; tool_count: number | null; icon: ChannelIcon | null; health: ChannelHealth } } ChannelSummary
+ */
+
+/**
+ * @typedef { { path: string; created: ChannelChange[]; updated: ChannelChange[]; deleted: ChannelChange[]; unchanged: string[]; active_channel_id: string | null; plan: FilePlan } } ChannelsFileReport
+ */
+
+/**
+ * @typedef { { created: ProjectChannel[]; skipped: SkippedEntry[]; mcp_server_path: string | null; plan: FilePlan } } ClaudeImportReport
+ */
+
+/**
+ * A known problem with a combination of SDK / server / bridge versions
+ *
+ * @typedef { { severity: string; message: string } } CompatIssue
+ */
+
+/**
+ * Versions in use for a channel plus any compatibility issues found
+ *
+ * @typedef { { sdk: BanterSdkInfo; server_version: string | null; bridge_version: string | null; issues: CompatIssue[] } } CompatibilityReport
+ */
+
+/**
+ * @typedef { { path: string; format: string; tool_count: number | null; tools_error: string | null; plan: FilePlan } } ContextFile
+ */
+
+/**
+ * @typedef { { enabled: boolean; running: boolean; port: number | null; url: string | null; token: string | null } } ControlApiStatus
+ */
+
+/**
+ * @typedef { { active_channel_id: string | null; channels: ChannelSummary[]; servers: ServerState[]; bridges: BridgeState[]; pending_updates: PendingUpdate[]; recent_errors: RecentError[]; checked_at: number; cached: boolean } } Dashboard
+ */
+
+/**
+ * What the user needs to complete a device-code login
+ *
+ * @typedef { { user_code: string; verification_uri: string; verification_uri_complete: string | null; expires_in: number } } DeviceLogin
+ */
+
+/**
+ * @typedef { { file_id: number; type_name: string; status: string; fields: string[] } } DocumentChange
+ */
+
+/**
+ * @typedef { "connected" | "starting" | "not_responding" | "crashed" | "closed" } EditorCondition
+ */
+
+/**
+ * @typedef { { channel_id: string; condition: EditorCondition; cause: string | null; pid: number | null } } EditorStatus
+ */
+
+/**
+ * @typedef { { path: string; writable: boolean; elevated: boolean; can_elevate: boolean } } ElevationStatus
+ */
+
+/**
+ * @typedef { { vars: EnvVar[]; script: string; persisted: boolean; hint: string } } EnvBootstrap
+ */
+
+/**
+ * @typedef { { name: string; value: string; current: string | null } } EnvVar
+ */
+
+/**
+ * @typedef { "not_found" | "invalid_input" | "parse" | "io" | "permission_denied" | "network" | "auth" | "unity" | "bridge" | "conflict" | "cancelled" | "internal" } ErrorKind
+ */
+
+/**
+ * @typedef { { channel_id: string; mode: string; bridge_version: string | null; unity_version: string | null } } ExtensionVerification
+ */
+
+/**
+ * A flag as the settings screen shows it
+ *
+ * @typedef { { name: string; description: string; enabled: boolean; default_enabled: boolean } } FeatureFlag
+ */
+
+/**
+ * @typedef { "create_dir" | "write" | "copy" | "delete" | "backup" } FileAction
+ */
+
+/**
+ * @typedef { { path: string; offset: number; next_offset: number; size: number; eof: boolean; text: string } } FileChunk
+ */
+
+/**
+ * @typedef { { action: FileAction; path: string; source: string | null; bytes: number | null } } FileOperation
+ */
+
+/**
+ * @typedef { { dry_run: boolean; operations: FileOperation[] } } FilePlan
+ */
+
+/**
+ * @typedef { { channel_id: string; pid: number } } FocusedEditor
+ */
+
+/**
+ * What a failed health check does to an activation
+ *
+ * @typedef { "allow" | "warn" | "refuse" } GateAction
+ */
+
+/**
+ * Checks against a channel project's git working tree; the app's `git`
+ * module applies them
+ *
+ * @typedef { { refuse_dirty_activation: boolean; checkpoint_on_activate: boolean } } GitGuards
+ */
+
+/**
+ * @typedef { { branch: string | null; head: string | null; dirty: boolean; changed_files: number } } GitStatus
+ */
+
+/**
+ * The asset a GUID belongs to
+ *
+ * @typedef { { guid: string; path: string | null; name: string; built_in: boolean } } GuidAsset
+ */
+
+/**
+ * A file that references a GUID
+ *
+ * @typedef { { path: string; count: number } } GuidReference
+ */
+
+/**
+ * @typedef { { guid: string; asset: GuidAsset | null; references: GuidReference[] } } GuidReferences
+ */
+
+/**
+ * Result of a single check
+ *
+ * @typedef { { id: string; status: HealthStatus; message: string } } HealthCheck
+ */
+
+/**
+ * Health checks a channel has to pass to be activated; the app's `health`
+ * module applies them
+ *
+ * @typedef { { on_error: GateAction; on_warning: GateAction; ignore_checks: string[] } } HealthGate
+ */
+
+/**
+ * @typedef { "ok" | "warning" | "error" } HealthStatus
+ */
+
+/**
+ * Scripts run on activation changes and server crashes; the app's `hooks`
+ * module runs them
+ *
+ * @typedef { { on_activate: string | null; on_deactivate: string | null; on_server_crash: string | null } } HookScripts
+ */
+
+/**
+ * @typedef { { status: ImportStatus; placeholders: Placeholder[]; channel: ProjectChannel | null; clients: string[]; notes: string | null; warnings: string[]; plan: FilePlan | null } } ImportResult
+ */
+
+/**
+ * @typedef { "needs_input" | "imported" } ImportStatus
+ */
+
+/**
+ * A running launcher, as announced in the instance folder
+ *
+ * @typedef { { user: string; host: string; pid: number; launcher_dir: string; ports: number[]; started_ms: number; updated_ms: number } } Instance
+ */
+
+/**
+ * @typedef { { others: Instance[]; port_clashes: number[] } } InstanceReport
+ */
+
+/**
+ * A job in `get_job_history`
+ *
+ * @typedef { ({ resumable: boolean; log_path: string }) & (JobInfo) } JobHistoryEntry
+ */
+
+/**
+ * Payload of `job-progress`, and what `get_job_status` returns
+ *
+ * @typedef { { id: string; kind: string; label: string; status: JobStatus; progress: number | null; message: string | null; created_at: number; started_at: number | null; finished_at: number | null; result: JsonValue | null; error: LauncherError | null; resumed_from: string | null } } JobInfo
+ */
+
+/**
+ * Work that can be queued through `enqueue_job`
+ *
+ * @typedef { { kind: "install_extension"; unity_project_path: string; mcp_root: string; elevate: boolean | null } | { kind: "scan_project"; unity_project_path: string } | { kind: "find_projects"; root: string } | { kind: "build_bundle"; channel_id: string; platform: string } | { kind: "build_all_platforms"; channel_id: string } | { kind: "upload_bundle"; channel_id: string; platform: string } | { kind: "check_all_channels"; validate: boolean | null } } JobRequest
+ */
+
+/**
+ * @typedef { "queued" | "running" | "succeeded" | "failed" | "cancelled" | "interrupted" } JobStatus
+ */
+
+/**
+ * @typedef { { id: string; operation: Operation; started_ms: number; pid: number; files: PriorFile[] } } JournalEntry
+ */
+
+/**
+ * @typedef { null | boolean | number | string | JsonValue[] | { [key in string]: JsonValue } } JsonValue
+ */
+
+/**
+ * Full launcher configuration
+ *
+ * @typedef { { channels: ProjectChannel[]; active_channel_id: string | null; mcp_server_path: string; auto_start: boolean; enable_custom_scripts: boolean; unity_editor_path: string | null; batchmode_fallback: boolean; relaunch_editor_on_crash: boolean; banter_upload_endpoint: string | null; banter_upload_token: string | null; banter_auth_endpoint: string | null; banter_client_path: string | null; preview_server_port: number; backup_retention: BackupRetention; snapshot_on_activate: boolean; snapshot_git_stash: boolean; git_guards: GitGuards; health_gate: HealthGate; control_api_enabled: boolean; control_api_port: number; hooks: HookScripts; locale: string; simulation_enabled: boolean; observer_mode: boolean; record_sessions: boolean; server_pool: ServerPoolSettings; serve_launcher_state: boolean; portable_client_config: boolean; file_triggers: boolean; storage_backend: StorageBackend; schedules: Schedules; network: NetworkSettings; telemetry: TelemetrySettings; features: { [key in string]: boolean } } } LauncherConfig
+ */
+
+/**
+ * @typedef { ({ kind: ErrorKind; message: string; hint: string | null; path: string | null }) & (MessageIds) } LauncherError
+ */
+
+/**
+ * Payload of the `account-login` event emitted when polling finishes
+ *
+ * @typedef { { status: string; message: string } } LoginEvent
+ */
+
+/**
+ * A resource an MCP server offers
+ *
+ * @typedef { { uri: string; name: string | null; description: string | null; mimeType: string | null } } McpResource
+ */
+
+/**
+ * @typedef { { template: string; text: string; hint: string | null } } McpSnippet
+ */
+
+/**
+ * A tool an MCP server offers
+ *
+ * @typedef { { name: string; description: string | null } } McpTool
+ */
+
+/**
+ * @typedef { { locale: string; locales: string[]; messages: { [key in string]: string } } } MessageCatalogue
+ */
+
+/**
+ * @typedef { { message_id?: string | null; params?: { [key in string]: string }; hint_id?: string | null } } MessageIds
+ */
+
+/**
+ * @typedef { { unity_project_path: string; scene_paths: string[]; plan: FilePlan } } MockProject
+ */
+
+/**
+ * @typedef { { unity_version: string; scenes: string[]; banter_sdk_version: string | null } } MockProjectOptions
+ */
+
+/**
+ * How the launcher reaches the network; the app's `net` module applies it
+ *
+ * @typedef { { proxy: string; no_proxy: string[]; max_kib_per_sec: number | null } } NetworkSettings
+ */
+
+/**
+ * A GameObject in both versions that differs
+ *
+ * @typedef { { file_id: number; name: string; path: string; previous_name: string | null; previous_path: string | null; active: boolean | null; fields: string[]; components_added: string[]; components_removed: string[]; components_changed: DocumentChange[]; transform: TransformEdit[] } } ObjectChange
+ */
+
+/**
+ * @typedef { { active: boolean; forced_by_switch: boolean } } ObserverStatus
+ */
+
+/**
+ * What an interrupted operation was doing, enough to run it again
+ *
+ * @typedef { { kind: "activation"; channel_id: string } | { kind: "client_config_sync"; channel_id: string; mcp_server_path: string } | { kind: "extension_upgrade"; unity_project_path: string; mcp_root: string } } Operation
+ */
+
+/**
+ * @typedef { { op: string; channel_id: string | null; plan: FilePlan; preview_server: PreviewServerStatus | null } } OperationResult
+ */
+
+/**
+ * @typedef { { channel_id: string; component: string; installed: string; available: string } } PendingUpdate
+ */
+
+/**
+ * @typedef { { name: string; description: string; hint: string | null; suggestion?: string | null } } Placeholder
+ */
+
+/**
+ * Outcome of one platform in a multi-platform build
+ *
+ * @typedef { { platform: string; success: boolean; result: BundleBuildResult | null; error: LauncherError | null } } PlatformBuildOutcome
+ */
+
+/**
+ * A pooled server, as announced in its pool file
+ *
+ * @typedef { { channel_id: string; pid: number; port: number; connections: number; started_at: number; updated_at: number; idle_since: number | null } } PooledServer
+ */
+
+/**
+ * @typedef { { running: boolean; channel_id: string | null; port: number | null; url: string | null } } PreviewServerStatus
+ */
+
+/**
+ * A path as it was before the operation first changed it
+ *
+ * @typedef { { path: string; saved: string | null; created_dir: boolean } } PriorFile
+ */
+
+/**
+ * One prefab, material or script
+ *
+ * @typedef { { kind: string; path: string; guid: string | null; size: number; modified_at: number } } ProjectAsset
+ */
+
+/**
+ * A scene channel configuration
+ *
+ * @typedef { { id: string; name: string; unity_project_path: string; scene_path: string | null; enabled: boolean; bundle_build_method: string | null; platforms: string[]; space_url: string | null; local_test_url: string | null; pass_upload_token: boolean; extra_allowed_roots: string[]; server_cwd: string | null; server_data_dir: string | null; workspace_clients: string[]; capabilities: ServerCapabilities | null; quotas: ChannelQuotas } } ProjectChannel
+ */
+
+/**
+ * Scenes and editor version of one Unity project
+ *
+ * @typedef { { project_path: string; unity_version: string | null; scenes: string[]; scanned_at: number; cached: boolean } } ProjectScan
+ */
+
+/**
+ * Unity projects found below a directory
+ *
+ * @typedef { { root: string; projects: string[]; scanned_at: number; cached: boolean } } ProjectSearch
+ */
+
+/**
+ * File a read refers to
+ *
+ * @typedef { { kind: "editor_log" } | { kind: "server_log"; channel_id: string } | { kind: "build_log"; channel_id: string; platform: string } | { kind: "batch_log"; channel_id: string; operation: string } | { kind: "scene"; channel_id: string; path: string | null } } ReadTarget
+ */
+
+/**
+ * @typedef { { job_id: string; kind: string; label: string; message: string; at: number } } RecentError
+ */
+
+/**
+ * @typedef { { id: string; channel_id: string; channel_name: string; started_at: number; ended_at: number | null; tool_calls: number; events: number } } RecordedSession
+ */
+
+/**
+ * @typedef { { added: SceneObject[]; removed: SceneObject[]; changed: ObjectChange[]; unchanged: number; other: DocumentChange[] } } SceneDiff
+ */
+
+/**
+ * @typedef { { channel_id: string; mode: string; scenes: UnityScene[] } } SceneList
+ */
+
+/**
+ * @typedef { { file_id: number; name: string; path: string; components: string[] } } SceneObject
+ */
+
+/**
+ * When a scheduled task runs; see `schedule` for the syntax
+ *
+ * @typedef { { enabled: boolean; cron: string; time_zone: string } } SchedulePolicy
+ */
+
+/**
+ * A task's schedule and when it runs next, for `get_schedules`
+ *
+ * @typedef { { task: Task; policy: SchedulePolicy | null; next_run_ms: number | null; next_run_local: string | null } } ScheduleStatus
+ */
+
+/**
+ * Scheduled tasks, each off until given a schedule; the app's `scheduler`
+ * module runs them
+ *
+ * @typedef { { server_restart: SchedulePolicy | null; update_check: SchedulePolicy | null; idle_shutdown: SchedulePolicy | null } } Schedules
+ */
+
+/**
+ * Result of `tools/list` and `resources/list` against a channel's server
+ *
+ * @typedef { { server_name: string | null; server_version: string | null; protocol_version: string | null; tools: McpTool[]; resources: McpResource[]; mcp_server_path: string; package_version: string | null; queried_at: number } } ServerCapabilities
+ */
+
+/**
+ * Sharing one MCP server between the clients of a channel; the app's
+ * `server_pool` module runs the pool
+ *
+ * @typedef { { enabled: boolean; idle_timeout_secs: number } } ServerPoolSettings
+ */
+
+/**
+ * The channel's MCP server as far as the launcher can see it; Claude Code
+ * starts and stops the server itself
+ *
+ * @typedef { { channel_id: string; configured: boolean; server_version: string | null; last_output_ms: number | null } } ServerState
+ */
+
+/**
+ * Who holds a project, as written to its session file
+ *
+ * @typedef { { channel_id: string; channel_name: string; owner: string; launcher_dir: string; pid: number; updated_ms: number } } SessionClaim
+ */
+
+/**
+ * Another launcher is using the project a channel points at
+ *
+ * @typedef { { channel_id: string; unity_project_path: string; other: SessionClaim; message: string } } SessionConflict
+ */
+
+/**
+ * @typedef { { file_name: string; content: string } } SessionExport
+ */
+
+/**
+ * @typedef { { file_name: string; path: string; snapshots: number; jobs: number; warnings: string[] } } SessionStateExport
+ */
+
+/**
+ * @typedef { { status: ImportStatus; placeholders: Placeholder[]; channel: ProjectChannel | null; exported_by: string; snapshots: number; jobs: JobInfo[]; client_config: string | null; warnings: string[] } } SessionStateImport
+ */
+
+/**
+ * @typedef { { markdown: string; condensed: string } } SetupReport
+ */
+
+/**
+ * @typedef { { enabled: boolean; forced_by_env: boolean; bridge_running: boolean } } SimulationStatus
+ */
+
+/**
+ * @typedef { { entry: string; reason: string } } SkippedEntry
+ */
+
+/**
+ * @typedef { { id: string; channel_id: string; reason: string; created_at: number; files: SnapshotFile[]; git_stash: string | null } } Snapshot
+ */
+
+/**
+ * @typedef { { path: string; size: number; modified_ms: number } } SnapshotFile
+ */
+
+/**
+ * @typedef { { name: string; started_ms: number; duration_ms: number; deferred: boolean } } StartupPhase
+ */
+
+/**
+ * @typedef { { phases: StartupPhase[]; window_ready_ms: number | null; deferred_complete: boolean; uptime_ms: number } } StartupReport
+ */
+
+/**
+ * @typedef { "json" | "sqlite" } StorageBackend
+ */
+
+/**
+ * What `finish_support_mode` wrote
+ *
+ * @typedef { { file_name: string; path: string; content: string; parts: string[]; warnings: string[] } } SupportBundle
+ */
+
+/**
+ * @typedef { { id: string; started_ms: number; channel_id: string | null } } SupportSession
+ */
+
+/**
+ * @typedef { { session: SupportSession | null; client_config_updated: boolean } } SupportStatus
+ */
+
+/**
+ * @typedef { "server_restart" | "update_check" | "idle_shutdown" } Task
+ */
+
+/**
+ * What a report holds; nothing else is sent
+ *
+ * @typedef { { launcher_version: string; os: string; since_ms: number; until_ms: number; features: { [key in string]: number }; errors: { [key in string]: number } } } TelemetryReport
+ */
+
+/**
+ * Anonymous usage counts (the `telemetry` module); off unless turned on
+ *
+ * @typedef { { enabled: boolean; endpoint: string | null } } TelemetrySettings
+ */
+
+/**
+ * @typedef { { enabled: boolean; endpoint: string | null; recorded: string[]; not_recorded: string[]; file: string; report: TelemetryReport } } TelemetryStatus
+ */
+
+/**
+ * @typedef { { property: string; before: number[] | null; after: number[] | null } } TransformEdit
+ */
+
+/**
+ * Payload of `file-trigger`
+ *
+ * @typedef { { channel_id: string; file: string; command: string | null; ok: boolean; message: string } } TriggerOutcome
+ */
+
+/**
+ * A channel whose project couldn't be cleaned
+ *
+ * @typedef { { channel_id: string; error: LauncherError } } UninstallFailure
+ */
+
+/**
+ * @typedef { { delete_config: boolean; delete_logs: boolean; dry_run: boolean } } UninstallOptions
+ */
+
+/**
+ * @typedef { { plan: FilePlan; stopped: string[]; client_configs: string[]; extensions_removed: string[]; failures: UninstallFailure[] } } UninstallReport
+ */
+
+/**
+ * A scene as Unity sees it
+ *
+ * @typedef { { path: string; in_build_settings: boolean; enabled: boolean } } UnityScene
+ */
+
+/**
+ * Payload of the `bundle-upload-progress` event
+ *
+ * @typedef { { channel_id: string; file: string; bytes_sent: number; total_bytes: number; attempt: number } } UploadProgress
+ */
+
+/**
+ * @typedef { { channel_id: string; platform: string; files_uploaded: number; files_skipped: number; bytes_uploaded: number; space_url: string | null } } UploadResult
+ */
+
+/**
+ * @typedef { { rule: string; severity: string; message: string; message_id: string; params: { [key in string]: string }; object: string | null; path: string | null } } ValidationIssue
+ */
+
+/**
+ * @typedef { { channel_id: string; scene_path: string | null; errors: number; warnings: number; issues: ValidationIssue[] } } ValidationReport
+ */
+
+/**
+ * @typedef { { url: string; client_path: string | null } } VisitResult
+ */
+
+/**
+ * @typedef { { path: string; at: number } } WriteViolation
+ */
+
+/** tauri-specta globals **/
+
+import {
+	invoke as TAURI_INVOKE,
+	Channel as TAURI_CHANNEL,
+} from "@tauri-apps/api/core";
+import * as TAURI_API_EVENT from "@tauri-apps/api/event";
+
+/**
+ * @template T, E
+ * @typedef { { status: "ok"; data: T } | { status: "error"; error: E } } Result
+ */
+
+/**
+ * @param { Record<string, string> } mappings
+ */
+function __makeEvents__(mappings) {
+	return new Proxy(
+		{},
+		{
+			get: (_, event) => {
+				const name = mappings[event];
+
+				return new Proxy(() => {}, {
+					apply: (_, __, [window]) => ({
+						listen: (arg) => window.listen(name, arg),
+						once: (arg) => window.once(name, arg),
+						emit: (arg) => window.emit(name, arg),
+					}),
+					get: (_, command) => {
+						switch (command) {
+							case "listen":
+								return (arg) => TAURI_API_EVENT.listen(name, arg);
+							case "once":
+								return (arg) => TAURI_API_EVENT.once(name, arg);
+							case "emit":
+								return (arg) => TAURI_API_EVENT.emit(name, arg);
+						}
+					},
+				});
+			},
+		},
+	);
+}
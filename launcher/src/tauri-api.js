@@ -0,0 +1,4 @@
+// The parts of @tauri-apps/api that bindings.js imports, taken from the
+// globals Tauri injects (app.withGlobalTauri), since the window isn't bundled
+export const { invoke, Channel } = window.__TAURI__.core;
+export const { listen, once, emit } = window.__TAURI__.event;
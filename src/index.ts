@@ -8,6 +8,7 @@
  * Usage:
  *   stdio mode (default): node dist/index.js
  *   HTTP mode: node dist/index.js --http [--port 42067]
+ *   pooled mode: node dist/index.js --socket <port> [--idle-timeout 300]
  */
 
 import { Server } from "@modelcontextprotocol/sdk/server/index.js";
@@ -24,72 +25,87 @@ import {
 import { registerTools, handleToolCall } from "./tools/index.js";
 import { registerResources, handleResourceRead } from "./resources/index.js";
 import { registerPrompts, handlePromptGet } from "./prompts/index.js";
-import { getConfig } from "./lib/config.js";
+import { getConfig, startLogFile, traced } from "./lib/config.js";
 
+startLogFile();
 const config = getConfig();
 
-// Create MCP server
-const server = new Server(
-  {
-    name: "banter-mcp",
-    version: "1.0.0",
-  },
-  {
-    capabilities: {
-      tools: {},
-      resources: {},
-      prompts: {},
+// Create an MCP server; pooled mode makes one per connected client
+function createServer(): Server {
+  const server = new Server(
+    {
+      name: "banter-mcp",
+      version: "1.0.0",
     },
-  }
-);
+    {
+      capabilities: {
+        tools: {},
+        resources: {},
+        prompts: {},
+      },
+    }
+  );
 
-// List available tools
-server.setRequestHandler(ListToolsRequestSchema, async () => {
-  return { tools: registerTools() };
-});
+  // List available tools
+  server.setRequestHandler(ListToolsRequestSchema, async () => {
+    return { tools: registerTools() };
+  });
 
-// Handle tool calls
-server.setRequestHandler(CallToolRequestSchema, async (request) => {
-  return handleToolCall(request.params.name, request.params.arguments ?? {}, config);
-});
+  // Handle tool calls
+  server.setRequestHandler(CallToolRequestSchema, async (request) => {
+    const { name, arguments: args } = request.params;
+    return traced(`tool ${name}`, () => handleToolCall(name, args ?? {}, config));
+  });
 
-// List available resources
-server.setRequestHandler(ListResourcesRequestSchema, async () => {
-  return { resources: registerResources(config) };
-});
+  // List available resources
+  server.setRequestHandler(ListResourcesRequestSchema, async () => {
+    return { resources: registerResources(config) };
+  });
 
-// Read resource content
-server.setRequestHandler(ReadResourceRequestSchema, async (request) => {
-  return handleResourceRead(request.params.uri, config);
-});
+  // Read resource content
+  server.setRequestHandler(ReadResourceRequestSchema, async (request) => {
+    const { uri } = request.params;
+    return traced(`resource ${uri}`, async () => handleResourceRead(uri, config));
+  });
 
-// List available prompts
-server.setRequestHandler(ListPromptsRequestSchema, async () => {
-  return { prompts: registerPrompts() };
-});
+  // List available prompts
+  server.setRequestHandler(ListPromptsRequestSchema, async () => {
+    return { prompts: registerPrompts() };
+  });
 
-// Get prompt content
-server.setRequestHandler(GetPromptRequestSchema, async (request) => {
-  return handlePromptGet(request.params.name, request.params.arguments ?? {});
-});
+  // Get prompt content
+  server.setRequestHandler(GetPromptRequestSchema, async (request) => {
+    return handlePromptGet(request.params.name, request.params.arguments ?? {});
+  });
+
+  return server;
+}
 
 // Start server
 async function main() {
   const args = process.argv.slice(2);
   const useHttp = args.includes("--http");
+  const socketArg = args.find((a, i) => args[i - 1] === "--socket");
+
+  if (socketArg !== undefined) {
+    // Socket transport - one server shared by the clients of a channel
+    const idleArg = args.find((a, i) => args[i - 1] === "--idle-timeout");
+    const idleTimeout = idleArg ? parseInt(idleArg) || 0 : 300;
 
-  if (useHttp) {
+    const { servePool } = await import("./lib/server-pool.js");
+    await servePool(createServer, parseInt(socketArg) || 0, idleTimeout);
+  } else if (useHttp) {
     // HTTP transport - for shared/remote access
     const portArg = args.find((a, i) => args[i - 1] === "--port");
     const port = portArg ? parseInt(portArg) : 42067;
 
     const { createHttpServer } = await import("./lib/http-server.js");
-    await createHttpServer(server, port);
+    await createHttpServer(createServer(), port);
     console.error(`Banter MCP running on http://localhost:${port}/mcp`);
   } else {
     // Stdio transport - for Claude Code integration
     const transport = new StdioServerTransport();
-    await server.connect(transport);
+    await createServer().connect(transport);
     console.error("Banter MCP running on stdio");
   }
 }
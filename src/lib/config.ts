@@ -16,20 +16,141 @@ export interface BanterMCPConfig {
   assetsPath: string;
   /** Path to WebRoot folder (for built scenes) */
   webRootPath: string;
+  /** Folder for server-side caches and generated files */
+  dataPath: string;
   /** Whether the Unity extension is detected */
   hasUnityExtension: boolean;
+  /** Client this server serves when the launcher shares the project (workspace mode) */
+  instanceName?: string;
+  /** Lock file shared with the project's other servers in workspace mode */
+  writeLockPath?: string;
+  /** Limits the launcher sets for the channel; see quotas.ts */
+  quotas: Quotas;
+}
+
+export interface Quotas {
+  maxWriteBytes?: number;
+  maxCallsPerMinute?: number;
+  /** Absolute folders tools may not write to */
+  deniedPaths: string[];
+}
+
+/**
+ * Quotas from BANTER_MCP_MAX_WRITE_BYTES, BANTER_MCP_MAX_CALLS_PER_MINUTE and
+ * BANTER_MCP_DENIED_PATHS; unset or unreadable ones don't limit anything
+ */
+function readQuotas(): Quotas {
+  const positive = (value: string | undefined) => {
+    const parsed = Number.parseInt(value ?? "", 10);
+    return parsed > 0 ? parsed : undefined;
+  };
+  return {
+    maxWriteBytes: positive(process.env.BANTER_MCP_MAX_WRITE_BYTES),
+    maxCallsPerMinute: positive(process.env.BANTER_MCP_MAX_CALLS_PER_MINUTE),
+    deniedPaths: (process.env.BANTER_MCP_DENIED_PATHS || "")
+      .split(path.delimiter)
+      .filter((p) => p.length > 0),
+  };
+}
+
+/**
+ * Load secrets from the env file the launcher points at via BANTER_MCP_ENV_FILE,
+ * so tokens never have to be inlined into client configs. Variables already set
+ * in the environment win.
+ */
+function loadEnvFile(): void {
+  const envFile = process.env.BANTER_MCP_ENV_FILE;
+  if (!envFile || !fs.existsSync(envFile)) return;
+
+  for (const line of fs.readFileSync(envFile, "utf-8").split(/\r?\n/)) {
+    const trimmed = line.trim();
+    if (!trimmed || trimmed.startsWith("#")) continue;
+
+    const eq = trimmed.indexOf("=");
+    if (eq <= 0) continue;
+
+    const key = trimmed.slice(0, eq);
+    if (process.env[key] === undefined) {
+      process.env[key] = trimmed.slice(eq + 1);
+    }
+  }
+}
+
+/**
+ * Mirror stderr into the file named by BANTER_MCP_LOG_FILE, so the launcher can
+ * show server logs without owning the server process. Best effort: a log file
+ * that can't be opened is ignored.
+ */
+export function startLogFile(): void {
+  const logFile = process.env.BANTER_MCP_LOG_FILE;
+  if (!logFile) return;
+
+  try {
+    fs.mkdirSync(path.dirname(logFile), { recursive: true });
+    const stream = fs.createWriteStream(logFile, { flags: "a" });
+    stream.on("error", () => {});
+
+    const write = process.stderr.write.bind(process.stderr);
+    process.stderr.write = ((chunk: string | Uint8Array, ...rest: unknown[]) => {
+      stream.write(chunk);
+      return (write as (...args: unknown[]) => boolean)(chunk, ...rest);
+    }) as typeof process.stderr.write;
+  } catch {
+    // Logging to the terminal still works
+  }
+}
+
+/**
+ * Log a line to stderr when BANTER_MCP_LOG_LEVEL is `trace`; the launcher
+ * sets it while support mode is on.
+ */
+export function trace(message: string): void {
+  if (process.env.BANTER_MCP_LOG_LEVEL !== "trace") return;
+  console.error(`[trace] ${new Date().toISOString()} ${message}`);
+}
+
+/**
+ * Times `work` and traces how it went
+ */
+export async function traced<T>(label: string, work: () => Promise<T>): Promise<T> {
+  const started = Date.now();
+  try {
+    const result = await work();
+    trace(`${label} ok in ${Date.now() - started}ms`);
+    return result;
+  } catch (error) {
+    trace(`${label} failed in ${Date.now() - started}ms: ${error}`);
+    throw error;
+  }
+}
+
+/**
+ * Data folder from `--data-dir` or BANTER_MCP_DATA_DIR; the launcher sets the
+ * latter per channel. Defaults to Library/BanterMCP in the project, which
+ * Unity doesn't import.
+ */
+function resolveDataPath(unityProjectPath: string): string {
+  const args = process.argv.slice(2);
+  const argIndex = args.indexOf("--data-dir");
+  const fromArg = argIndex >= 0 ? args[argIndex + 1] : undefined;
+  const dataDir = fromArg || process.env.BANTER_MCP_DATA_DIR;
+  if (dataDir) return path.resolve(dataDir);
+  return path.join(unityProjectPath, "Library", "BanterMCP");
 }
 
 /**
  * Get configuration from environment or defaults
  */
 export function getConfig(): BanterMCPConfig {
+  loadEnvFile();
+
   const unityProjectPath = process.env.UNITY_PROJECT_PATH || process.env.BANTER_PROJECT_PATH || "";
 
   const assetsPath = path.join(unityProjectPath, "Assets");
   const mcpStatePath = path.join(assetsPath, "_MCP", "state");
   const mcpCommandsPath = path.join(assetsPath, "_MCP", "commands");
   const webRootPath = path.join(assetsPath, "WebRoot");
+  const dataPath = resolveDataPath(unityProjectPath);
 
   // Check if Unity extension is installed by looking for state directory
   const hasUnityExtension = fs.existsSync(mcpStatePath);
@@ -40,7 +161,11 @@ export function getConfig(): BanterMCPConfig {
     mcpCommandsPath,
     assetsPath,
     webRootPath,
+    dataPath,
     hasUnityExtension,
+    instanceName: process.env.BANTER_MCP_INSTANCE || undefined,
+    writeLockPath: process.env.BANTER_MCP_WRITE_LOCK || undefined,
+    quotas: readQuotas(),
   };
 }
 
@@ -52,7 +177,7 @@ export function ensureMCPDirectories(config: BanterMCPConfig): void {
     throw new Error("UNITY_PROJECT_PATH environment variable not set");
   }
 
-  const dirs = [config.mcpStatePath, config.mcpCommandsPath];
+  const dirs = [config.mcpStatePath, config.mcpCommandsPath, config.dataPath];
 
   for (const dir of dirs) {
     if (!fs.existsSync(dir)) {
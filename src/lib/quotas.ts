@@ -0,0 +1,62 @@
+/**
+ * Channel quotas set by the launcher
+ *
+ * A channel can limit what its server does for an agent: the largest file a
+ * tool may write (BANTER_MCP_MAX_WRITE_BYTES), the tool calls allowed in any
+ * minute (BANTER_MCP_MAX_CALLS_PER_MINUTE), and folders tools may not write
+ * to (BANTER_MCP_DENIED_PATHS, separated like PATH). A refused call fails
+ * like any other tool error. Without the variables nothing is limited.
+ */
+
+import * as path from "path";
+import type { BanterMCPConfig } from "./config.js";
+
+const WINDOW_MS = 60_000;
+
+/** When the calls of the last minute came in */
+const recentCalls: number[] = [];
+
+/** Why another tool call now would go over the rate, if it would; counts it otherwise */
+export function checkCallRate(config: BanterMCPConfig): string | undefined {
+  const limit = config.quotas.maxCallsPerMinute;
+  if (!limit) return undefined;
+
+  const now = Date.now();
+  while (recentCalls.length && now - recentCalls[0] >= WINDOW_MS) {
+    recentCalls.shift();
+  }
+  if (recentCalls.length >= limit) {
+    return `This channel allows ${limit} tool calls a minute; try again shortly`;
+  }
+  recentCalls.push(now);
+  return undefined;
+}
+
+function pathKey(p: string): string {
+  const resolved = path.resolve(p).replace(/\\/g, "/").replace(/\/+$/, "");
+  // Unity projects mostly live on Windows and macOS, where case doesn't tell folders apart
+  return process.platform === "linux" ? resolved : resolved.toLowerCase();
+}
+
+/** Why writing `bytes` to `filePath` breaks the channel's quotas, if it does */
+export function checkWrite(
+  config: BanterMCPConfig,
+  filePath: string,
+  bytes: number
+): string | undefined {
+  const shown = path.relative(config.unityProjectPath, filePath);
+  const target = pathKey(filePath);
+  const denied = config.quotas.deniedPaths.find((dir) => {
+    const key = pathKey(dir);
+    return target === key || target.startsWith(`${key}/`);
+  });
+  if (denied) {
+    return `Writing to ${shown} is not allowed: the folder is denied for this channel`;
+  }
+
+  const max = config.quotas.maxWriteBytes;
+  if (max && bytes > max) {
+    return `${shown} would be ${bytes} bytes; this channel allows ${max}`;
+  }
+  return undefined;
+}
@@ -0,0 +1,128 @@
+/**
+ * Socket transport for a pooled server
+ *
+ * With server pooling on, the launcher runs one server per channel and every
+ * client reaches it through `banter-launcher --mcp-proxy --pooled`, which
+ * connects to the port this server listens on. Each connection gets its own
+ * MCP session over the same line-delimited JSON-RPC as stdio. The server
+ * counts its connections in the pool file named by BANTER_MCP_POOL_FILE,
+ * renewing it while it runs, and exits once it has had no client for the
+ * idle timeout.
+ */
+
+import * as fs from "fs";
+import * as net from "net";
+import * as path from "path";
+import { Server } from "@modelcontextprotocol/sdk/server/index.js";
+import { StdioServerTransport } from "@modelcontextprotocol/sdk/server/stdio.js";
+
+/** How often the pool file is renewed; the launcher treats a file not renewed for 30s as stale */
+const RENEW_MS = 10_000;
+
+interface PoolFile {
+  pid: number;
+  port: number;
+  connections: number;
+  started_at: number;
+  updated_at: number;
+  /** When the last client went away; null while one is connected */
+  idle_since: number | null;
+}
+
+function writePoolFile(poolPath: string | undefined, entry: PoolFile): void {
+  if (!poolPath) return;
+  try {
+    fs.mkdirSync(path.dirname(poolPath), { recursive: true });
+    const temp = `${poolPath}.${process.pid}.tmp`;
+    fs.writeFileSync(temp, JSON.stringify(entry));
+    fs.renameSync(temp, poolPath);
+  } catch (error) {
+    console.error("[Banter MCP] Failed to write pool file:", error);
+  }
+}
+
+/**
+ * Listen on 127.0.0.1:`port` (0 picks a free one) and serve every connection
+ * with a fresh server from `createServer`, until no client has been connected
+ * for `idleTimeoutSecs`. With 0 it runs until the launcher stops it.
+ */
+export async function servePool(
+  createServer: () => Server,
+  port: number,
+  idleTimeoutSecs: number
+): Promise<void> {
+  const poolPath = process.env.BANTER_MCP_POOL_FILE || undefined;
+  const entry: PoolFile = {
+    pid: process.pid,
+    port,
+    connections: 0,
+    started_at: Date.now(),
+    updated_at: Date.now(),
+    idle_since: Date.now(),
+  };
+  let idleTimer: NodeJS.Timeout | undefined;
+
+  const publish = () => {
+    entry.updated_at = Date.now();
+    writePoolFile(poolPath, entry);
+  };
+
+  const shutdown = () => {
+    if (poolPath && fs.existsSync(poolPath)) {
+      try {
+        const current = JSON.parse(fs.readFileSync(poolPath, "utf-8")) as PoolFile;
+        if (current.pid === process.pid) fs.rmSync(poolPath, { force: true });
+      } catch {
+        // Unreadable pool files are the launcher's to replace
+      }
+    }
+    process.exit(0);
+  };
+
+  const startIdleTimer = () => {
+    clearTimeout(idleTimer);
+    if (idleTimeoutSecs <= 0) return;
+    idleTimer = setTimeout(() => {
+      console.error(`[Banter MCP] No client for ${idleTimeoutSecs}s, shutting down`);
+      shutdown();
+    }, idleTimeoutSecs * 1000);
+  };
+
+  const listener = net.createServer((socket) => {
+    entry.connections += 1;
+    entry.idle_since = null;
+    clearTimeout(idleTimer);
+    publish();
+
+    const server = createServer();
+    server.connect(new StdioServerTransport(socket, socket)).catch((error) => {
+      console.error("[Banter MCP] Failed to serve pooled client:", error);
+      socket.destroy();
+    });
+
+    socket.once("close", () => {
+      server.close().catch(() => undefined);
+      entry.connections -= 1;
+      if (entry.connections === 0) {
+        entry.idle_since = Date.now();
+        startIdleTimer();
+      }
+      publish();
+    });
+    socket.on("error", () => socket.destroy());
+  });
+
+  await new Promise<void>((resolve, reject) => {
+    listener.once("error", reject);
+    listener.listen(port, "127.0.0.1", () => resolve());
+  });
+  const address = listener.address();
+  entry.port = typeof address === "object" && address ? address.port : port;
+
+  publish();
+  setInterval(publish, RENEW_MS).unref();
+  startIdleTimer();
+  process.on("SIGTERM", shutdown);
+  process.on("SIGINT", shutdown);
+  console.error(`Banter MCP pooled on 127.0.0.1:${entry.port}`);
+}
@@ -0,0 +1,178 @@
+/**
+ * Write lock shared by the servers of a workspace-mode channel
+ *
+ * When the launcher shares one Unity project between several clients (say
+ * Cursor and Claude Code), each client runs its own server and the launcher
+ * points all of them at the same lock file via BANTER_MCP_WRITE_LOCK. A tool
+ * that changes the project takes the lock first, so two agents never edit
+ * the scene at the same moment. Without the variable nothing is locked.
+ */
+
+import { randomUUID } from "crypto";
+import * as fs from "fs";
+import * as path from "path";
+import type { BanterMCPConfig } from "./config.js";
+
+/** How long a tool waits for another server to finish writing */
+const LOCK_WAIT_MS = 30_000;
+
+/** A lock this old is from a server that died without releasing it */
+const LOCK_STALE_MS = 120_000;
+
+const POLL_MS = 100;
+
+interface LockOwner {
+  instance: string;
+  pid: number;
+  tool: string;
+  acquiredAt: number;
+}
+
+/** A lock file as read, enough to tell whether it was replaced since */
+interface LockFile {
+  owner?: LockOwner;
+  raw: string;
+  ino: number;
+  mtimeMs: number;
+}
+
+function readLock(lockPath: string): LockFile | undefined {
+  try {
+    const stat = fs.statSync(lockPath);
+    const raw = fs.readFileSync(lockPath, "utf-8");
+    let owner: LockOwner | undefined;
+    try {
+      owner = JSON.parse(raw) as LockOwner;
+    } catch {
+      owner = undefined;
+    }
+    return { owner, raw, ino: stat.ino, mtimeMs: stat.mtimeMs };
+  } catch {
+    return undefined;
+  }
+}
+
+function sameLock(a: LockFile | undefined, b: LockFile): boolean {
+  return !!a && a.ino === b.ino && a.mtimeMs === b.mtimeMs && a.raw === b.raw;
+}
+
+function readOwner(lockPath: string): LockOwner | undefined {
+  try {
+    return JSON.parse(fs.readFileSync(lockPath, "utf-8")) as LockOwner;
+  } catch {
+    return undefined;
+  }
+}
+
+function isAlive(pid: number): boolean {
+  try {
+    process.kill(pid, 0);
+    return true;
+  } catch (error) {
+    // EPERM: the process exists but belongs to someone else
+    return (error as NodeJS.ErrnoException).code === "EPERM";
+  }
+}
+
+/** Create the lock file; false when it already exists */
+function tryCreate(lockPath: string, owner: LockOwner): boolean {
+  try {
+    const fd = fs.openSync(lockPath, "wx");
+    fs.writeSync(fd, JSON.stringify(owner));
+    fs.closeSync(fd);
+    return true;
+  } catch (error) {
+    if ((error as NodeJS.ErrnoException).code !== "EEXIST") throw error;
+    return false;
+  }
+}
+
+function isAbandoned(lock: LockFile): boolean {
+  // Unreadable: its owner may still be writing it, so only age counts
+  if (!lock.owner) return Date.now() - lock.mtimeMs > LOCK_STALE_MS;
+  return (
+    Date.now() - lock.owner.acquiredAt > LOCK_STALE_MS ||
+    !isAlive(lock.owner.pid)
+  );
+}
+
+/**
+ * Remove the abandoned lock `stale`, and only that one. Another server may
+ * break it too, or take the lock, between reading it and removing it, so it
+ * is first renamed to a name of our own: whatever the rename moved is then
+ * ours alone to check. A fresh lock moved by mistake is put back unless yet
+ * another one took its place.
+ */
+function breakLock(lockPath: string, stale: LockFile): void {
+  const tombstone = `${lockPath}.${randomUUID()}.stale`;
+  try {
+    fs.renameSync(lockPath, tombstone);
+  } catch {
+    return; // already broken or released
+  }
+  if (!sameLock(readLock(tombstone), stale)) {
+    try {
+      fs.linkSync(tombstone, lockPath);
+    } catch {
+      // A newer lock is in place
+    }
+  }
+  fs.rmSync(tombstone, { force: true });
+}
+
+/** Take the lock if it is free or abandoned; false while another server holds it */
+function tryAcquire(lockPath: string, owner: LockOwner): boolean {
+  if (tryCreate(lockPath, owner)) return true;
+
+  const current = readLock(lockPath);
+  if (!current || !isAbandoned(current)) return false;
+  breakLock(lockPath, current);
+  return tryCreate(lockPath, owner);
+}
+
+function release(lockPath: string): void {
+  if (readOwner(lockPath)?.pid === process.pid) {
+    fs.rmSync(lockPath, { force: true });
+  }
+}
+
+/**
+ * Run `write` holding the channel's write lock. Resolves to a failure result
+ * when another server keeps the lock longer than LOCK_WAIT_MS.
+ */
+export async function withWriteLock<T>(
+  config: BanterMCPConfig,
+  tool: string,
+  write: () => Promise<T>
+): Promise<T | { success: false; error: string }> {
+  const lockPath = config.writeLockPath;
+  if (!lockPath) return write();
+
+  fs.mkdirSync(path.dirname(lockPath), { recursive: true });
+  const owner: LockOwner = {
+    instance: config.instanceName || "server",
+    pid: process.pid,
+    tool,
+    acquiredAt: Date.now(),
+  };
+
+  const deadline = Date.now() + LOCK_WAIT_MS;
+  while (!tryAcquire(lockPath, { ...owner, acquiredAt: Date.now() })) {
+    if (Date.now() > deadline) {
+      const holder = readOwner(lockPath);
+      return {
+        success: false,
+        error: holder
+          ? `The project is busy: the ${holder.instance} server is running ${holder.tool}. Try again shortly.`
+          : "The project is busy: another server is writing to it. Try again shortly.",
+      };
+    }
+    await new Promise((resolve) => setTimeout(resolve, POLL_MS));
+  }
+
+  try {
+    return await write();
+  } finally {
+    release(lockPath);
+  }
+}
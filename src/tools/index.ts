@@ -9,6 +9,29 @@ import { generateVSGraph, GenerateVSGraphResult } from "./generate-vs-graph.js";
 import { queryProjectState, ProjectStateResult } from "./query-project.js";
 import { checkImportStatus, ImportStatusResult } from "./check-import-status.js";
 import { writeWebRootJS, WriteWebRootResult } from "./write-webroot-js.js";
+import { withWriteLock } from "../lib/write-lock.js";
+import { checkCallRate } from "../lib/quotas.js";
+
+/** Tools that change the project; in workspace mode they take the write lock */
+const WRITE_TOOLS = new Set([
+  "write_vs_graph",
+  "write_webroot_js",
+  "refresh_unity_assets",
+  "create_gameobject",
+  "delete_gameobject",
+  "modify_gameobject",
+  "add_component",
+  "remove_component",
+  "set_component_property",
+  "set_object_reference",
+  "batch_create",
+  "instantiate_prefab",
+  "batch_instantiate_prefabs",
+  "scan_prefabs",
+]);
+
+/** How long a locked write waits for Unity to pick up its bridge command */
+const COMMAND_SETTLE_MS = 10_000;
 
 interface Tool {
   name: string;
@@ -622,6 +645,28 @@ export async function handleToolCall(
   args: Record<string, unknown>,
   config: BanterMCPConfig
 ): Promise<{ content: Array<{ type: "text"; text: string }> }> {
+  const refused = checkCallRate(config);
+  const result = refused
+    ? { success: false, error: refused }
+    : WRITE_TOOLS.has(name)
+      ? await withWriteLock(config, name, () => callTool(name, args, config))
+      : await callTool(name, args, config);
+
+  return {
+    content: [
+      {
+        type: "text",
+        text: typeof result === "string" ? result : JSON.stringify(result, null, 2),
+      },
+    ],
+  };
+}
+
+async function callTool(
+  name: string,
+  args: Record<string, unknown>,
+  config: BanterMCPConfig
+): Promise<unknown> {
   let result: unknown;
 
   switch (name) {
@@ -802,14 +847,7 @@ case "set_object_reference":      result = await setObjectReference(        args
       throw new Error(`Unknown tool: ${name}`);
   }
 
-  return {
-    content: [
-      {
-        type: "text",
-        text: typeof result === "string" ? result : JSON.stringify(result, null, 2),
-      },
-    ],
-  };
+  return result;
 }
 
 // Helper functions for simple tools
@@ -921,6 +959,15 @@ async function sendUnityCommand(
       timestamp: Date.now(),
     }, null, 2));
 
+    // Holding the write lock: let Unity run the command (it deletes the file)
+    // before another server's write can start
+    if (config.writeLockPath && config.hasUnityExtension) {
+      const deadline = Date.now() + COMMAND_SETTLE_MS;
+      while (fs.existsSync(commandFile) && Date.now() < deadline) {
+        await new Promise((resolve) => setTimeout(resolve, 100));
+      }
+    }
+
     return { success: true, commandId };
   } catch (error) {
     return {
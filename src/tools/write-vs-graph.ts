@@ -7,6 +7,7 @@
 import * as fs from "fs";
 import * as path from "path";
 import type { BanterMCPConfig } from "../lib/config.js";
+import { checkWrite } from "../lib/quotas.js";
 
 export interface WriteVSGraphResult {
   success: boolean;
@@ -59,17 +60,21 @@ export async function writeVSGraph(
       };
     }
 
-    // Create the target directory
+    // Generate the .asset file content
     const targetDir = path.join(config.assetsPath, folder);
+    const assetContent = generateAssetFile(graphName, graphJson);
+    const assetPath = path.join(targetDir, `${graphName}.asset`);
+    const refused = checkWrite(config, assetPath, Buffer.byteLength(assetContent, "utf-8"));
+    if (refused) {
+      return { success: false, error: refused };
+    }
+
+    // Create the target directory
     if (!fs.existsSync(targetDir)) {
       fs.mkdirSync(targetDir, { recursive: true });
     }
 
-    // Generate the .asset file content
-    const assetContent = generateAssetFile(graphName, graphJson);
-
     // Write the file
-    const assetPath = path.join(targetDir, `${graphName}.asset`);
     fs.writeFileSync(assetPath, assetContent, "utf-8");
 
     // Write a .meta file hint for Unity (helps with import)
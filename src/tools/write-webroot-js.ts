@@ -7,6 +7,7 @@
 import * as fs from "fs";
 import * as path from "path";
 import type { BanterMCPConfig } from "../lib/config.js";
+import { checkWrite } from "../lib/quotas.js";
 
 export interface WriteWebRootResult {
   success: boolean;
@@ -62,6 +63,10 @@ ${code}`;
 
     // Write the file
     const filePath = path.join(config.webRootPath, filename);
+    const refused = checkWrite(config, filePath, Buffer.byteLength(finalCode, "utf-8"));
+    if (refused) {
+      return { success: false, error: refused };
+    }
     fs.writeFileSync(filePath, finalCode, "utf-8");
 
     const relativePath = path.relative(config.unityProjectPath, filePath);
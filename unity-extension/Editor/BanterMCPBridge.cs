@@ -22,6 +22,9 @@ namespace BantworksMCP
     [InitializeOnLoad]
     public static class BantworksMCPBridge
     {
+        // Reported to the launcher for compatibility checks - bump on protocol changes
+        public const string BridgeVersion = "1.6.0";
+
         private static readonly string MCPFolder = "Assets/_MCP";
         private static readonly string StateFolder = "Assets/_MCP/state";
         private static readonly string CommandsFolder = "Assets/_MCP/commands";
@@ -306,6 +309,20 @@ namespace BantworksMCP
                     GetObjectBounds(boundsCmd);
                     break;
 
+                case "build_bundle":
+                    var buildCmd = JsonUtility.FromJson<BuildBundleCommand>(json);
+                    BuildBundles(buildCmd.platform, buildCmd.outputPath);
+                    break;
+
+                case "list_scenes":
+                    ExportSceneList();
+                    break;
+
+                case "capture_thumbnail":
+                    var thumbnailCmd = JsonUtility.FromJson<CaptureThumbnailCommand>(json);
+                    CaptureThumbnail(thumbnailCmd.size);
+                    break;
+
                 default:
                     Debug.LogWarning($"[BANTWORKS MCP] Unknown command type: {baseCommand.type}");
                     break;
@@ -1208,7 +1225,9 @@ namespace BantworksMCP
                     isPlaying = EditorApplication.isPlaying,
                     isPaused = EditorApplication.isPaused,
                     isCompiling = EditorApplication.isCompiling,
+                    hasCompileErrors = EditorUtility.scriptCompilationFailed,
                     activeScene = UnityEngine.SceneManagement.SceneManager.GetActiveScene().name,
+                    activeBuildTarget = EditorUserBuildSettings.activeBuildTarget.ToString(),
                     selectedObjects = Selection.gameObjects?.Select(o => o.name).ToArray() ?? new string[0],
                     timestamp = DateTimeOffset.UtcNow.ToUnixTimeMilliseconds()
                 };
@@ -1582,6 +1601,223 @@ namespace BantworksMCP
             public float[] boundsCenter;  // [x, y, z] offset from pivot
         }
 
+        /// <summary>
+        /// Every scene under Assets and whether Build Settings include it
+        /// </summary>
+        private static void ExportSceneList()
+        {
+            try
+            {
+                var buildScenes = EditorBuildSettings.scenes;
+                var list = new SceneList
+                {
+                    scenes = new List<SceneEntry>(),
+                    timestamp = DateTimeOffset.UtcNow.ToUnixTimeMilliseconds()
+                };
+
+                foreach (string guid in AssetDatabase.FindAssets("t:Scene", new[] { "Assets" }))
+                {
+                    string path = AssetDatabase.GUIDToAssetPath(guid);
+                    var buildScene = buildScenes.FirstOrDefault(s => s.path == path);
+                    list.scenes.Add(new SceneEntry
+                    {
+                        path = path,
+                        inBuildSettings = buildScene != null,
+                        enabled = buildScene != null && buildScene.enabled
+                    });
+                }
+
+                File.WriteAllText(Path.Combine(StateFolder, "scenes.json"), JsonUtility.ToJson(list, true));
+            }
+            catch (Exception e)
+            {
+                Debug.LogError($"[BANTWORKS MCP] Error exporting scene list: {e.Message}");
+            }
+        }
+
+        /// <summary>
+        /// Render the open scene into a square PNG for the launcher's channel card,
+        /// from the scene view camera when there is one, else the main camera
+        /// </summary>
+        private static void CaptureThumbnail(int size)
+        {
+            Camera camera = null;
+            RenderTexture previousTarget = null;
+            RenderTexture previousActive = RenderTexture.active;
+            RenderTexture target = null;
+            Texture2D image = null;
+            try
+            {
+                if (size <= 0) size = 256;
+                camera = SceneView.lastActiveSceneView != null
+                    ? SceneView.lastActiveSceneView.camera
+                    : Camera.main;
+                if (camera == null)
+                {
+                    Debug.LogWarning("[BANTWORKS MCP] No camera to capture a thumbnail with");
+                    return;
+                }
+
+                target = RenderTexture.GetTemporary(size, size, 24);
+                previousTarget = camera.targetTexture;
+                camera.targetTexture = target;
+                camera.Render();
+                RenderTexture.active = target;
+                image = new Texture2D(size, size, TextureFormat.RGB24, false);
+                image.ReadPixels(new Rect(0, 0, size, size), 0, 0);
+                image.Apply();
+
+                // The image first: the launcher waits for the JSON and then reads it
+                File.WriteAllBytes(Path.Combine(StateFolder, "thumbnail.png"), image.EncodeToPNG());
+                var info = new ThumbnailInfo
+                {
+                    scenePath = EditorSceneManager.GetActiveScene().path,
+                    size = size,
+                    timestamp = DateTimeOffset.UtcNow.ToUnixTimeMilliseconds()
+                };
+                File.WriteAllText(Path.Combine(StateFolder, "thumbnail.json"), JsonUtility.ToJson(info, true));
+            }
+            catch (Exception e)
+            {
+                Debug.LogError($"[BANTWORKS MCP] Error capturing thumbnail: {e.Message}");
+            }
+            finally
+            {
+                if (camera != null) camera.targetTexture = previousTarget;
+                RenderTexture.active = previousActive;
+                if (target != null) RenderTexture.ReleaseTemporary(target);
+                if (image != null) UnityEngine.Object.DestroyImmediate(image);
+            }
+        }
+
+        #endregion
+
+        #region Bundle Builds
+
+        private static BuildTarget ParseBuildTarget(string platform)
+        {
+            switch ((platform ?? "").ToLowerInvariant())
+            {
+                case "android":
+                case "quest":
+                    return BuildTarget.Android;
+                default:
+                    return BuildTarget.StandaloneWindows64;
+            }
+        }
+
+        private static bool BuildBundles(string platform, string outputPath)
+        {
+            var result = new BuildBundleResult
+            {
+                platform = platform,
+                outputPath = outputPath
+            };
+
+            try
+            {
+                var target = ParseBuildTarget(platform);
+                if (EditorUserBuildSettings.activeBuildTarget != target)
+                {
+                    // Switch first so the reimport happens once, not per bundle
+                    EditorUserBuildSettings.SwitchActiveBuildTarget(BuildPipeline.GetBuildTargetGroup(target), target);
+                }
+
+                Directory.CreateDirectory(outputPath);
+                var manifest = BuildPipeline.BuildAssetBundles(outputPath, BuildAssetBundleOptions.None, target);
+                result.success = manifest != null;
+                if (manifest == null)
+                    result.error = "BuildAssetBundles returned no manifest (no asset bundles assigned?)";
+            }
+            catch (Exception e)
+            {
+                result.success = false;
+                result.error = e.Message;
+                Debug.LogError($"[BANTWORKS MCP] Bundle build failed: {e.Message}");
+            }
+
+            result.timestamp = DateTimeOffset.UtcNow.ToUnixTimeMilliseconds();
+
+            try
+            {
+                File.WriteAllText(Path.Combine(StateFolder, "build-result.json"), JsonUtility.ToJson(result, true));
+            }
+            catch (Exception e)
+            {
+                Debug.LogError($"[BANTWORKS MCP] Error exporting build result: {e.Message}");
+            }
+
+            return result.success;
+        }
+
+        private static string GetCommandLineArg(string name)
+        {
+            string[] args = Environment.GetCommandLineArgs();
+            for (int i = 0; i < args.Length - 1; i++)
+            {
+                if (args[i] == name)
+                    return args[i + 1];
+            }
+            return null;
+        }
+
+        /// <summary>
+        /// Batchmode entry point used by the launcher:
+        /// -executeMethod BantworksMCP.BantworksMCPBridge.BuildBundlesBatch -bundlePlatform windows -bundleOutput path
+        /// </summary>
+        public static void BuildBundlesBatch()
+        {
+            EnsureDirectories();
+
+            string platform = GetCommandLineArg("-bundlePlatform") ?? "windows";
+            string outputPath = GetCommandLineArg("-bundleOutput") ?? Path.Combine("Builds", "Banter", platform);
+
+            bool success = BuildBundles(platform, outputPath);
+            EditorApplication.Exit(success ? 0 : 1);
+        }
+
+        #endregion
+
+        #region Batchmode
+
+        /// <summary>
+        /// Batchmode entry point used by the launcher to check that the bridge compiles:
+        /// -executeMethod BantworksMCP.BantworksMCPBridge.VerifyExtensionBatch
+        /// </summary>
+        public static void VerifyExtensionBatch()
+        {
+            EnsureDirectories();
+
+            var check = new ExtensionCheck
+            {
+                bridgeVersion = BridgeVersion,
+                unityVersion = Application.unityVersion,
+                timestamp = DateTimeOffset.UtcNow.ToUnixTimeMilliseconds()
+            };
+            try
+            {
+                File.WriteAllText(Path.Combine(StateFolder, "extension-check.json"), JsonUtility.ToJson(check, true));
+            }
+            catch (Exception e)
+            {
+                Debug.LogError($"[BANTWORKS MCP] Error exporting extension check: {e.Message}");
+                EditorApplication.Exit(1);
+                return;
+            }
+            EditorApplication.Exit(0);
+        }
+
+        /// <summary>
+        /// Batchmode entry point used by the launcher:
+        /// -executeMethod BantworksMCP.BantworksMCPBridge.ListScenesBatch
+        /// </summary>
+        public static void ListScenesBatch()
+        {
+            EnsureDirectories();
+            ExportSceneList();
+            EditorApplication.Exit(File.Exists(Path.Combine(StateFolder, "scenes.json")) ? 0 : 1);
+        }
+
         #endregion
 
         #region Data Classes
@@ -1722,7 +1958,9 @@ namespace BantworksMCP
             public bool isPlaying;
             public bool isPaused;
             public bool isCompiling;
+            public bool hasCompileErrors;
             public string activeScene;
+            public string activeBuildTarget;
             public string[] selectedObjects;
             public long timestamp;
         }
@@ -1752,6 +1990,62 @@ namespace BantworksMCP
             public long timestamp;
         }
 
+        [Serializable]
+        private class BuildBundleCommand
+        {
+            public string type;
+            public string platform;
+            public string outputPath;
+        }
+
+        [Serializable]
+        private class CaptureThumbnailCommand
+        {
+            public string type;
+            public int size;
+        }
+
+        [Serializable]
+        private class ThumbnailInfo
+        {
+            public string scenePath;
+            public int size;
+            public long timestamp;
+        }
+
+        [Serializable]
+        private class BuildBundleResult
+        {
+            public bool success;
+            public string platform;
+            public string outputPath;
+            public string error;
+            public long timestamp;
+        }
+
+        [Serializable]
+        private class SceneList
+        {
+            public List<SceneEntry> scenes;
+            public long timestamp;
+        }
+
+        [Serializable]
+        private class SceneEntry
+        {
+            public string path;
+            public bool inBuildSettings;
+            public bool enabled;
+        }
+
+        [Serializable]
+        private class ExtensionCheck
+        {
+            public string bridgeVersion;
+            public string unityVersion;
+            public long timestamp;
+        }
+
         #endregion
     }
 